@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use sysinfo::System;
 use tauri::Emitter;
 
@@ -8,6 +9,11 @@ const DEFAULT_OLLAMA_URL: &str = "http://127.0.0.1:11434";
 const DEFAULT_OPENAI_URL: &str = "https://api.openai.com/v1";
 const DEFAULT_ANTHROPIC_URL: &str = "https://api.anthropic.com/v1";
 
+/// How long `call_llm_streaming` waits for the first SSE chunk before
+/// emitting a `"loading"` event, so a slow-to-warm-up (e.g. cold Ollama)
+/// model reads as "loading" rather than "hung".
+const FIRST_CHUNK_LOADING_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(4);
+
 // ============================================================================
 // LLM Configuration Types
 // ============================================================================
@@ -22,6 +28,7 @@ pub enum LLMProvider {
     #[serde(rename = "claude-code")]
     ClaudeCode, // Handling the hyphenated name if needed, though 'claude-code' string from JS might map here
     Groq,
+    Replicate,
 }
 
 impl Default for LLMProvider {
@@ -41,6 +48,16 @@ pub struct LLMConfig {
     pub embedding_endpoint: Option<String>,
     pub temperature: f32,
     pub max_tokens: u32,
+    /// Ollama's KV-cache window size (its `options.num_ctx`). Ollama has no
+    /// API to report a model's max context, and defaults to a small window
+    /// that silently truncates long prompts, so this lets a user hosting a
+    /// large-context local model raise it. Defaults to 4096 when unset.
+    #[serde(default)]
+    pub num_ctx: Option<u32>,
+    /// Extra Ollama `options` passed through verbatim alongside `num_ctx`
+    /// (e.g. `num_gpu`, `repeat_penalty`) - only sent when talking to Ollama.
+    #[serde(default)]
+    pub options: Option<serde_json::Map<String, Value>>,
 }
 
 impl Default for LLMConfig {
@@ -55,10 +72,132 @@ impl Default for LLMConfig {
             embedding_endpoint: Some(DEFAULT_OLLAMA_URL.to_string()),
             temperature: 0.0,
             max_tokens: 8192,
+            num_ctx: None,
+            options: None,
         }
     }
 }
 
+const DEFAULT_OLLAMA_NUM_CTX: u32 = 4096;
+
+/// Ollama's `options` object (`{"num_ctx": ..., ...}`), combining the
+/// explicit `num_ctx` field with any passthrough `options` - only relevant
+/// when `is_ollama_config(config)` is true.
+fn ollama_options(config: &LLMConfig) -> Value {
+    let mut map = config.options.clone().unwrap_or_default();
+    map.insert("num_ctx".to_string(), json!(config.num_ctx.unwrap_or(DEFAULT_OLLAMA_NUM_CTX)));
+    Value::Object(map)
+}
+
+// ============================================================================
+// Built-in presets for OpenAI-compatible hosting platforms
+// ============================================================================
+
+/// A known OpenAI-compatible hosting platform. All of these speak the same
+/// wire protocol as OpenAI, so picking a preset just fills in `base_url` and
+/// a sane default model and routes through the existing
+/// `call_openai_compatible` path - no provider-specific code needed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMPreset {
+    pub name: String,
+    pub display_name: String,
+    pub provider: LLMProvider,
+    pub base_url: String,
+    pub default_model: String,
+}
+
+fn llm_presets() -> Vec<LLMPreset> {
+    vec![
+        LLMPreset {
+            name: "groq".to_string(),
+            display_name: "Groq".to_string(),
+            provider: LLMProvider::Groq,
+            base_url: "https://api.groq.com/openai/v1".to_string(),
+            default_model: "llama-3.1-70b-versatile".to_string(),
+        },
+        LLMPreset {
+            name: "together".to_string(),
+            display_name: "Together AI".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.together.xyz/v1".to_string(),
+            default_model: "meta-llama/Llama-3.3-70B-Instruct-Turbo".to_string(),
+        },
+        LLMPreset {
+            name: "fireworks".to_string(),
+            display_name: "Fireworks AI".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.fireworks.ai/inference/v1".to_string(),
+            default_model: "accounts/fireworks/models/llama-v3p1-70b-instruct".to_string(),
+        },
+        LLMPreset {
+            name: "mistral".to_string(),
+            display_name: "Mistral".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.mistral.ai/v1".to_string(),
+            default_model: "mistral-large-latest".to_string(),
+        },
+        LLMPreset {
+            name: "openrouter".to_string(),
+            display_name: "OpenRouter".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://openrouter.ai/api/v1".to_string(),
+            default_model: "openai/gpt-4o-mini".to_string(),
+        },
+        LLMPreset {
+            name: "perplexity".to_string(),
+            display_name: "Perplexity".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.perplexity.ai".to_string(),
+            default_model: "llama-3.1-sonar-large-128k-online".to_string(),
+        },
+        LLMPreset {
+            name: "deepinfra".to_string(),
+            display_name: "DeepInfra".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.deepinfra.com/v1/openai".to_string(),
+            default_model: "meta-llama/Meta-Llama-3.1-70B-Instruct".to_string(),
+        },
+        LLMPreset {
+            name: "anyscale".to_string(),
+            display_name: "Anyscale".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.endpoints.anyscale.com/v1".to_string(),
+            default_model: "meta-llama/Meta-Llama-3.1-70B-Instruct".to_string(),
+        },
+        LLMPreset {
+            name: "moonshot".to_string(),
+            display_name: "Moonshot AI".to_string(),
+            provider: LLMProvider::Custom,
+            base_url: "https://api.moonshot.cn/v1".to_string(),
+            default_model: "moonshot-v1-8k".to_string(),
+        },
+    ]
+}
+
+/// List the built-in OpenAI-compatible presets, so the UI can offer them as
+/// one-click provider choices instead of making the user hand-enter a base
+/// URL and guess a default model.
+#[tauri::command]
+pub fn list_llm_presets() -> Vec<LLMPreset> {
+    llm_presets()
+}
+
+/// Build an `LLMConfig` from a preset by name, carrying over everything
+/// else (API key, temperature, etc.) from the caller-supplied base config.
+#[tauri::command]
+pub fn apply_preset(name: String, base_config: Option<LLMConfig>) -> Result<LLMConfig, String> {
+    let preset = llm_presets()
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown preset '{}'", name))?;
+
+    let mut config = base_config.unwrap_or_default();
+    config.provider = preset.provider;
+    config.base_url = preset.base_url;
+    config.model = preset.default_model;
+    Ok(config)
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct SystemSpecs {
     pub total_memory: u64,
@@ -129,10 +268,21 @@ struct OllamaModel {
 // OpenAI-compatible types (works for OpenAI, Ollama, and many others)
 // ============================================================================
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 struct ChatMessage {
     role: String,
-    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<ToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    fn text(role: &str, content: String) -> Self {
+        Self { role: role.to_string(), content: Some(content), tool_calls: None, tool_call_id: None }
+    }
 }
 
 // Response format for structured output
@@ -142,6 +292,38 @@ struct ResponseFormat {
     format_type: String,
 }
 
+/// A tool the model may call, in OpenAI's `{type:"function", function:{...}}`
+/// shape. Passed straight through to the wire format for OpenAI-compatible
+/// providers; translated into Anthropic's flatter `{name, input_schema}`
+/// tools by `call_anthropic_with_tools`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolFunctionDef {
+    pub name: String,
+    pub description: Option<String>,
+    pub parameters: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: ToolFunctionDef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallFunctionCall {
+    pub name: String,
+    pub arguments: String, // JSON-encoded, per the OpenAI wire format
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: ToolCallFunctionCall,
+}
+
 #[derive(Serialize)]
 struct ChatRequest {
     model: String,
@@ -158,12 +340,24 @@ struct ChatRequest {
     // For OpenAI JSON mode
     #[serde(skip_serializing_if = "Option::is_none")]
     response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ToolDefinition>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<Value>,
+    // Ollama's `options` object (`num_ctx`, etc.) - unused by other providers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<Value>,
 }
 
 // Streaming response types
 #[derive(Deserialize, Debug)]
 struct StreamDelta {
     content: Option<String>,
+    // Arrives as partial `{index, id, function: {name, arguments}}` fragments
+    // across chunks; `call_llm_streaming` doesn't reassemble them today, so
+    // this is kept as raw `Value` rather than the full `ToolCall` shape.
+    #[allow(dead_code)]
+    tool_calls: Option<Vec<Value>>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -182,13 +376,23 @@ struct StreamChunk {
 #[derive(Clone, Serialize)]
 pub struct LLMStreamEvent {
     pub stream_id: String,
-    pub event_type: String, // "start", "chunk", "done", "error"
+    pub event_type: String, // "start", "loading", "loaded", "chunk", "done", "error"
     pub content: String,
+    /// Input token count for the terminal "done" event - only populated by
+    /// the Ollama-native streaming path, which is the only one Ollama
+    /// reports real token accounting on (`prompt_eval_count`/`eval_count`
+    /// from its `/api/chat` NDJSON stream).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_eval_count: Option<u32>,
+    /// Output token count for the terminal "done" event. See `prompt_eval_count`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eval_count: Option<u32>,
 }
 
 #[derive(Deserialize)]
 struct ChatChoiceMessage {
     content: Option<String>,
+    tool_calls: Option<Vec<ToolCall>>,
 }
 
 #[derive(Deserialize)]
@@ -205,10 +409,22 @@ struct ChatResponse {
 // Anthropic-specific types
 // ============================================================================
 
-#[derive(Serialize)]
+// `content` is `Value` rather than `String` so this can carry either a
+// plain string turn or, for tool use/results, Anthropic's content-block
+// array (`[{type:"tool_use",...}]` / `[{type:"tool_result",...}]`).
+#[derive(Serialize, Clone)]
 struct AnthropicMessage {
     role: String,
-    content: String,
+    content: Value,
+}
+
+/// A tool in Anthropic's flatter shape - no `type`/`function` wrapper,
+/// `input_schema` instead of OpenAI's `parameters`.
+#[derive(Serialize, Clone)]
+struct AnthropicTool {
+    name: String,
+    description: Option<String>,
+    input_schema: Value,
 }
 
 #[derive(Serialize)]
@@ -220,11 +436,21 @@ struct AnthropicRequest {
     temperature: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<AnthropicTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
     text: Option<String>,
+    // Only present on `type:"tool_use"` blocks
+    id: Option<String>,
+    name: Option<String>,
+    input: Option<Value>,
 }
 
 #[derive(Deserialize)]
@@ -257,6 +483,7 @@ pub async fn check_llm_status(config: LLMConfig) -> Result<LLMStatus, String> {
         LLMProvider::Ollama => check_ollama_status_internal(&config).await,
         LLMProvider::OpenAI | LLMProvider::Custom | LLMProvider::Groq => check_openai_status_internal(&config).await,
         LLMProvider::Anthropic => check_anthropic_status_internal(&config).await,
+        LLMProvider::Replicate => check_replicate_status_internal(&config).await,
         LLMProvider::ClaudeCode => Ok(LLMStatus {
             connected: true,
             provider: "Claude Code".to_string(),
@@ -269,16 +496,18 @@ pub async fn check_llm_status(config: LLMConfig) -> Result<LLMStatus, String> {
 
 /// Legacy Ollama status check (for backwards compatibility)
 #[tauri::command]
-pub async fn check_ollama_status() -> Result<OllamaStatus, String> {
+pub async fn check_ollama_status(api_key: Option<String>) -> Result<OllamaStatus, String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(3))
         .build()
         .map_err(|e| e.to_string())?;
 
-    let tags_result = client
-        .get(format!("{}/api/tags", DEFAULT_OLLAMA_URL))
-        .send()
-        .await;
+    let mut request = client.get(format!("{}/api/tags", DEFAULT_OLLAMA_URL));
+    if let Some(ref api_key) = api_key {
+        request = request.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let tags_result = request.send().await;
 
     match tags_result {
         Ok(resp) if resp.status().is_success() => {
@@ -337,7 +566,7 @@ struct CreateModelRequest {
 
 /// Create a new Ollama model from a Modelfile
 #[tauri::command]
-pub async fn create_ollama_model(model_name: String, modelfile: String) -> Result<String, String> {
+pub async fn create_ollama_model(model_name: String, modelfile: String, api_key: Option<String>) -> Result<String, String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(900)) // Model creation can take time (pulling base image)
         .build()
@@ -349,8 +578,12 @@ pub async fn create_ollama_model(model_name: String, modelfile: String) -> Resul
         modelfile,
     };
 
-    let resp = client.post(&url)
-        .json(&body)
+    let mut request = client.post(&url).json(&body);
+    if let Some(ref api_key) = api_key {
+        request = request.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let resp = request
         .send()
         .await
         .map_err(|e| format!("Request failed: {}", e))?;
@@ -379,6 +612,7 @@ pub async fn call_llm(
         LLMProvider::Anthropic => {
             call_anthropic(&config, prompt, systemPrompt, conversationHistory).await
         }
+        LLMProvider::Replicate => call_replicate(&config, prompt, systemPrompt).await,
         LLMProvider::ClaudeCode => {
             Err("Claude Code not supported for direct LLM calls".to_string())
         }
@@ -404,36 +638,102 @@ pub async fn call_llm_streaming(
         stream_id: stream_id.clone(),
         event_type: "start".to_string(),
         content: "".to_string(),
+        prompt_eval_count: None,
+        eval_count: None,
     });
 
     let sys = systemPrompt.unwrap_or_else(|| "You are a helpful assistant.".to_string());
 
-    let mut messages = vec![ChatMessage {
-        role: "system".to_string(),
-        content: sys.clone(),
-    }];
+    if matches!(config.provider, LLMProvider::Replicate) {
+        return match call_replicate_streaming(&config, prompt, Some(sys), &window, &stream_id).await {
+            Ok(full_response) => {
+                let _ = window.emit("llm-stream", LLMStreamEvent {
+                    stream_id: stream_id.clone(),
+                    event_type: "done".to_string(),
+                    content: "".to_string(),
+                    prompt_eval_count: None,
+                    eval_count: None,
+                });
+                Ok(full_response)
+            }
+            Err(e) => {
+                let _ = window.emit("llm-stream", LLMStreamEvent {
+                    stream_id: stream_id.clone(),
+                    event_type: "error".to_string(),
+                    content: e.clone(),
+                    prompt_eval_count: None,
+                    eval_count: None,
+                });
+                Err(e)
+            }
+        };
+    }
+
+    if is_ollama_config(&config) {
+        return match call_ollama_streaming(&config, prompt, Some(sys), conversationHistory, &window, &stream_id).await {
+            Ok((full_response, prompt_eval_count, eval_count)) => {
+                let _ = window.emit("llm-stream", LLMStreamEvent {
+                    stream_id: stream_id.clone(),
+                    event_type: "done".to_string(),
+                    content: "".to_string(),
+                    prompt_eval_count,
+                    eval_count,
+                });
+                Ok(full_response)
+            }
+            Err(e) => {
+                let _ = window.emit("llm-stream", LLMStreamEvent {
+                    stream_id: stream_id.clone(),
+                    event_type: "error".to_string(),
+                    content: e.clone(),
+                    prompt_eval_count: None,
+                    eval_count: None,
+                });
+                Err(e)
+            }
+        };
+    }
+
+    if matches!(config.provider, LLMProvider::Anthropic) {
+        return match call_anthropic_streaming(&config, prompt, Some(sys), conversationHistory, &window, &stream_id).await {
+            Ok(full_response) => {
+                let _ = window.emit("llm-stream", LLMStreamEvent {
+                    stream_id: stream_id.clone(),
+                    event_type: "done".to_string(),
+                    content: "".to_string(),
+                    prompt_eval_count: None,
+                    eval_count: None,
+                });
+                Ok(full_response)
+            }
+            Err(e) => {
+                let _ = window.emit("llm-stream", LLMStreamEvent {
+                    stream_id: stream_id.clone(),
+                    event_type: "error".to_string(),
+                    content: e.clone(),
+                    prompt_eval_count: None,
+                    eval_count: None,
+                });
+                Err(e)
+            }
+        };
+    }
+
+    let mut messages = vec![ChatMessage::text("system", sys.clone())];
 
     for msg in conversationHistory {
         if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
             if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
-                messages.push(ChatMessage {
-                    role: role.to_string(),
-                    content: content.to_string(),
-                });
+                messages.push(ChatMessage::text(role, content.to_string()));
             }
         }
     }
 
-    messages.push(ChatMessage {
-        role: "user".to_string(),
-        content: prompt.clone(),
-    });
+    messages.push(ChatMessage::text("user", prompt.clone()));
 
     // Determine JSON mode
     let use_json_mode = sys.to_lowercase().contains("json") || sys.contains("JSON");
-    let is_ollama = config.base_url.contains("ollama") ||
-                    config.base_url.contains("11434") ||
-                    matches!(config.provider, LLMProvider::Ollama);
+    let is_ollama = is_ollama_config(&config);
 
     let body = ChatRequest {
         model: config.model.clone(),
@@ -447,17 +747,12 @@ pub async fn call_llm_streaming(
         } else {
             None
         },
+        tools: None,
+        tool_choice: None,
+        options: if is_ollama { Some(ollama_options(&config)) } else { None },
     };
 
-    let base_url = config.base_url.trim_end_matches('/');
-    // Check if base_url already contains /v1 to avoid double /v1/v1
-    let chat_url = if base_url.ends_with("/v1") {
-        format!("{}/chat/completions", base_url)
-    } else if is_ollama {
-        format!("{}/v1/chat/completions", base_url)
-    } else {
-        format!("{}/chat/completions", base_url)
-    };
+    let chat_url = resolve_chat_url(&config.base_url, is_ollama);
 
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(300))
@@ -474,6 +769,8 @@ pub async fn call_llm_streaming(
             stream_id: stream_id.clone(),
             event_type: "error".to_string(),
             content: format!("Request error: {}", e),
+            prompt_eval_count: None,
+            eval_count: None,
         });
         format!("Request error: {}", e)
     })?;
@@ -486,16 +783,49 @@ pub async fn call_llm_streaming(
             stream_id: stream_id.clone(),
             event_type: "error".to_string(),
             content: error_msg.clone(),
+            prompt_eval_count: None,
+            eval_count: None,
         });
         return Err(error_msg);
     }
 
     let mut full_response = String::new();
     let mut stream = resp.bytes_stream();
+    let mut first_chunk_received = false;
+    let mut loading_emitted = false;
+
+    loop {
+        let chunk_result = if first_chunk_received {
+            match stream.next().await {
+                Some(result) => result,
+                None => break,
+            }
+        } else {
+            tokio::select! {
+                next = stream.next() => match next {
+                    Some(result) => result,
+                    None => break,
+                },
+                // Ollama models load weights into memory on demand, so a cold
+                // model can sit silently for several seconds before its first
+                // token - let the UI show a spinner instead of looking hung.
+                _ = tokio::time::sleep(FIRST_CHUNK_LOADING_THRESHOLD), if !loading_emitted => {
+                    loading_emitted = true;
+                    let _ = window.emit("llm-stream", LLMStreamEvent {
+                        stream_id: stream_id.clone(),
+                        event_type: "loading".to_string(),
+                        content: "".to_string(),
+                        prompt_eval_count: None,
+                        eval_count: None,
+                    });
+                    continue;
+                }
+            }
+        };
 
-    while let Some(chunk_result) = stream.next().await {
         match chunk_result {
             Ok(chunk) => {
+                first_chunk_received = true;
                 let chunk_str = String::from_utf8_lossy(&chunk);
 
                 // Parse SSE format: data: {...}\n\n
@@ -516,6 +846,8 @@ pub async fn call_llm_streaming(
                                         stream_id: stream_id.clone(),
                                         event_type: "chunk".to_string(),
                                         content: content.clone(),
+                                        prompt_eval_count: None,
+                                        eval_count: None,
                                     });
                                 }
                             }
@@ -528,6 +860,8 @@ pub async fn call_llm_streaming(
                     stream_id: stream_id.clone(),
                     event_type: "error".to_string(),
                     content: format!("Stream error: {}", e),
+                    prompt_eval_count: None,
+                    eval_count: None,
                 });
                 return Err(format!("Stream error: {}", e));
             }
@@ -539,11 +873,173 @@ pub async fn call_llm_streaming(
         stream_id: stream_id.clone(),
         event_type: "done".to_string(),
         content: "".to_string(),
+        prompt_eval_count: None,
+        eval_count: None,
     });
 
     Ok(full_response)
 }
 
+/// Warm up a model ahead of the user's first real prompt. Ollama loads a
+/// model's weights into memory on first use, which can take anywhere from a
+/// couple of seconds to over a minute for large local models - this issues a
+/// minimal request just to trigger that load, and emits `llm-stream` events
+/// with `event_type: "loading"`/`"loaded"` so the UI can show a spinner
+/// instead of the user wondering why the first chat is slow.
+#[tauri::command]
+pub async fn preload_model(config: LLMConfig, window: tauri::Window) -> Result<String, String> {
+    match config.provider {
+        LLMProvider::Ollama | LLMProvider::OpenAI | LLMProvider::Custom | LLMProvider::Groq => {
+            preload_openai_compatible(&config, &window).await
+        }
+        LLMProvider::Anthropic => preload_anthropic(&config, &window).await,
+        // Replicate models run on shared remote infrastructure with no local
+        // weights to page in, so there's nothing to warm up.
+        LLMProvider::Replicate => Ok("Replicate models are hosted remotely and require no warm-up".to_string()),
+        LLMProvider::ClaudeCode => Err("Claude Code not supported for direct LLM calls".to_string()),
+    }
+}
+
+async fn preload_openai_compatible(config: &LLMConfig, window: &tauri::Window) -> Result<String, String> {
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let _ = window.emit("llm-stream", LLMStreamEvent {
+        stream_id: stream_id.clone(),
+        event_type: "loading".to_string(),
+        content: "".to_string(),
+        prompt_eval_count: None,
+        eval_count: None,
+    });
+
+    let is_ollama = is_ollama_config(config);
+    let chat_url = resolve_chat_url(&config.base_url, is_ollama);
+
+    let body = ChatRequest {
+        model: config.model.clone(),
+        // Ollama treats a message-less/empty-content chat as a load-only
+        // call: the model is paged into memory but nothing is generated.
+        messages: vec![ChatMessage::text("user", String::new())],
+        max_tokens: Some(1),
+        temperature: None,
+        stream: Some(false),
+        format: None,
+        response_format: None,
+        tools: None,
+        tool_choice: None,
+        options: if is_ollama { Some(ollama_options(config)) } else { None },
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(600))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut request = client.post(&chat_url).json(&body);
+    if let Some(ref api_key) = config.api_key {
+        request = request.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let started = std::time::Instant::now();
+    let result = request.send().await;
+    finish_preload(window, &stream_id, config, started, result).await
+}
+
+async fn preload_anthropic(config: &LLMConfig, window: &tauri::Window) -> Result<String, String> {
+    let stream_id = uuid::Uuid::new_v4().to_string();
+    let _ = window.emit("llm-stream", LLMStreamEvent {
+        stream_id: stream_id.clone(),
+        event_type: "loading".to_string(),
+        content: "".to_string(),
+        prompt_eval_count: None,
+        eval_count: None,
+    });
+
+    let api_key = config.api_key.as_ref()
+        .ok_or_else(|| "API key is required for Anthropic".to_string())?;
+    let base_url = config.base_url.trim_end_matches('/');
+
+    let body = AnthropicRequest {
+        model: config.model.clone(),
+        messages: vec![AnthropicMessage {
+            role: "user".to_string(),
+            // Anthropic rejects empty text content blocks, unlike Ollama.
+            content: Value::String(" ".to_string()),
+        }],
+        max_tokens: 1,
+        temperature: None,
+        system: None,
+        tools: None,
+        stream: None,
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(600))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let started = std::time::Instant::now();
+    let result = client
+        .post(format!("{}/messages", base_url))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", "2023-06-01")
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await;
+    finish_preload(window, &stream_id, config, started, result).await
+}
+
+/// Shared tail end of `preload_openai_compatible`/`preload_anthropic`: time
+/// the response, emit `"loaded"`/`"error"`, and report back how long the
+/// model took to become ready.
+async fn finish_preload(
+    window: &tauri::Window,
+    stream_id: &str,
+    config: &LLMConfig,
+    started: std::time::Instant,
+    result: Result<reqwest::Response, reqwest::Error>,
+) -> Result<String, String> {
+    let elapsed = started.elapsed();
+
+    let resp = match result {
+        Ok(resp) => resp,
+        Err(e) => {
+            let error_msg = format!("Preload request failed: {}", e);
+            let _ = window.emit("llm-stream", LLMStreamEvent {
+                stream_id: stream_id.to_string(),
+                event_type: "error".to_string(),
+                content: error_msg.clone(),
+                prompt_eval_count: None,
+                eval_count: None,
+            });
+            return Err(error_msg);
+        }
+    };
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        let error_msg = format!("Preload failed with status {}: {}", status, body);
+        let _ = window.emit("llm-stream", LLMStreamEvent {
+            stream_id: stream_id.to_string(),
+            event_type: "error".to_string(),
+            content: error_msg.clone(),
+            prompt_eval_count: None,
+            eval_count: None,
+        });
+        return Err(error_msg);
+    }
+
+    let _ = window.emit("llm-stream", LLMStreamEvent {
+        stream_id: stream_id.to_string(),
+        event_type: "loaded".to_string(),
+        content: format!("{:.1}", elapsed.as_secs_f32()),
+        prompt_eval_count: None,
+        eval_count: None,
+    });
+
+    Ok(format!("Model '{}' ready in {:.1}s", config.model, elapsed.as_secs_f32()))
+}
+
 /// Legacy function for backwards compatibility
 #[tauri::command]
 pub async fn call_local_llm_with_tools(
@@ -565,6 +1061,26 @@ pub async fn call_local_llm(prompt: String, system_prompt: Option<String>) -> Re
 // Internal Implementation Functions
 // ============================================================================
 
+fn is_ollama_config(config: &LLMConfig) -> bool {
+    config.base_url.contains("ollama") ||
+    config.base_url.contains("11434") ||
+    matches!(config.provider, LLMProvider::Ollama)
+}
+
+/// OpenAI-compatible `/chat/completions` URL for `base_url`, handling the
+/// case where it already ends in `/v1` (avoid a double `/v1/v1`) and the
+/// case where it's a bare Ollama host (needs `/v1` added).
+fn resolve_chat_url(base_url: &str, is_ollama: bool) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    if base_url.ends_with("/v1") {
+        format!("{}/chat/completions", base_url)
+    } else if is_ollama {
+        format!("{}/v1/chat/completions", base_url)
+    } else {
+        format!("{}/chat/completions", base_url)
+    }
+}
+
 async fn check_ollama_status_internal(config: &LLMConfig) -> Result<LLMStatus, String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(5))
@@ -574,7 +1090,12 @@ async fn check_ollama_status_internal(config: &LLMConfig) -> Result<LLMStatus, S
     let base_url = config.base_url.trim_end_matches('/');
     let tags_url = format!("{}/api/tags", base_url);
 
-    let tags_result = client.get(&tags_url).send().await;
+    let mut request = client.get(&tags_url);
+    if let Some(ref api_key) = config.api_key {
+        request = request.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let tags_result = request.send().await;
 
     match tags_result {
         Ok(resp) if resp.status().is_success() => {
@@ -704,19 +1225,26 @@ async fn check_openai_status_internal(config: &LLMConfig) -> Result<LLMStatus, S
 }
 
 async fn check_anthropic_status_internal(config: &LLMConfig) -> Result<LLMStatus, String> {
-    // Anthropic doesn't have a models list endpoint, so we just validate the API key
-    // by making a minimal request
+    // Anthropic doesn't have a models list endpoint, so we report the
+    // built-in list plus anything the user has added via the model
+    // registry (see `load_model_registry`), and validate the API key by
+    // making a minimal request.
+    let available_models: Vec<String> = vec![
+        "claude-sonnet-4-20250514".to_string(),
+        "claude-3-5-sonnet-20241022".to_string(),
+        "claude-3-5-haiku-20241022".to_string(),
+        "claude-3-opus-20240229".to_string(),
+    ]
+    .into_iter()
+    .chain(registry_model_names(&config.provider))
+    .collect();
+
     if config.api_key.is_none() {
         return Ok(LLMStatus {
             connected: false,
             provider: "Anthropic".to_string(),
             model: config.model.clone(),
-            available_models: vec![
-                "claude-sonnet-4-20250514".to_string(),
-                "claude-3-5-sonnet-20241022".to_string(),
-                "claude-3-5-haiku-20241022".to_string(),
-                "claude-3-opus-20240229".to_string(),
-            ],
+            available_models: available_models.clone(),
             error: Some("API key is required for Anthropic".to_string()),
         });
     }
@@ -733,11 +1261,13 @@ async fn check_anthropic_status_internal(config: &LLMConfig) -> Result<LLMStatus
         model: config.model.clone(),
         messages: vec![AnthropicMessage {
             role: "user".to_string(),
-            content: "Hi".to_string(),
+            content: Value::String("Hi".to_string()),
         }],
         max_tokens: 1,
         temperature: None,
         system: None,
+        tools: None,
+        stream: None,
     };
 
     let result = client
@@ -755,12 +1285,7 @@ async fn check_anthropic_status_internal(config: &LLMConfig) -> Result<LLMStatus
                 connected: true,
                 provider: "Anthropic".to_string(),
                 model: config.model.clone(),
-                available_models: vec![
-                    "claude-sonnet-4-20250514".to_string(),
-                    "claude-3-5-sonnet-20241022".to_string(),
-                    "claude-3-5-haiku-20241022".to_string(),
-                    "claude-3-opus-20240229".to_string(),
-                ],
+                available_models: available_models.clone(),
                 error: None,
             })
         }
@@ -781,12 +1306,7 @@ async fn check_anthropic_status_internal(config: &LLMConfig) -> Result<LLMStatus
                     connected: true,
                     provider: "Anthropic".to_string(),
                     model: config.model.clone(),
-                    available_models: vec![
-                        "claude-sonnet-4-20250514".to_string(),
-                        "claude-3-5-sonnet-20241022".to_string(),
-                        "claude-3-5-haiku-20241022".to_string(),
-                        "claude-3-opus-20240229".to_string(),
-                    ],
+                    available_models: available_models.clone(),
                     error: None,
                 })
             } else {
@@ -811,18 +1331,1009 @@ async fn check_anthropic_status_internal(config: &LLMConfig) -> Result<LLMStatus
     }
 }
 
-async fn call_openai_compatible(
-    config: &LLMConfig,
-    prompt: String,
-    system_prompt: Option<String>,
-    conversation_history: Vec<serde_json::Value>,
-) -> Result<String, String> {
-    let sys = system_prompt.unwrap_or_else(|| {
-        r#"You are a Kubernetes SRE assistant with the ability to execute kubectl commands.
+// ============================================================================
+// Ollama-native chat streaming (NDJSON over /api/chat)
+// ============================================================================
 
-When you need more information about a resource, you can use these tools:
-- describe_resource(kind, namespace, name): Get detailed information about a resource
-- get_logs(namespace, pod_name, container): Get logs from a pod
+#[derive(Serialize)]
+struct OllamaChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<OllamaChatMessage>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    options: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct OllamaChatStreamMessage {
+    content: String,
+}
+
+/// One line of Ollama's newline-delimited `/api/chat` stream. Every line but
+/// the last carries a `message` fragment; the final line has `done: true`
+/// and `prompt_eval_count`/`eval_count` instead - Ollama's only way to
+/// report real input/output token counts, which the OpenAI-compatible SSE
+/// shim has no equivalent for.
+#[derive(Deserialize)]
+struct OllamaChatStreamLine {
+    #[serde(default)]
+    message: Option<OllamaChatStreamMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u32>,
+    #[serde(default)]
+    eval_count: Option<u32>,
+}
+
+/// Stream a chat completion straight from Ollama's native `/api/chat`
+/// instead of its OpenAI-compatibility shim, so we get real token counts on
+/// the terminal line. Returns the full response text plus
+/// `(prompt_eval_count, eval_count)` from that terminal line.
+async fn call_ollama_streaming(
+    config: &LLMConfig,
+    prompt: String,
+    system_prompt: Option<String>,
+    conversation_history: Vec<serde_json::Value>,
+    window: &tauri::Window,
+    stream_id: &str,
+) -> Result<(String, Option<u32>, Option<u32>), String> {
+    use futures::StreamExt;
+
+    let mut messages = vec![];
+    if let Some(sys) = system_prompt {
+        messages.push(OllamaChatMessage { role: "system".to_string(), content: sys });
+    }
+
+    for msg in conversation_history {
+        if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
+            if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
+                messages.push(OllamaChatMessage { role: role.to_string(), content: content.to_string() });
+            }
+        }
+    }
+
+    messages.push(OllamaChatMessage { role: "user".to_string(), content: prompt });
+
+    let body = OllamaChatRequest {
+        model: config.model.clone(),
+        messages,
+        stream: true,
+        options: Some(ollama_options(config)),
+    };
+
+    let base_url = config.base_url.trim_end_matches('/');
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut request = client.post(format!("{}/api/chat", base_url)).json(&body);
+    if let Some(ref api_key) = config.api_key {
+        request = request.header("Authorization", format!("Bearer {}", api_key));
+    }
+
+    let resp = request.send().await.map_err(|e| format!("Request error: {}", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Ollama HTTP error {}: {}", status, body));
+    }
+
+    let mut full_response = String::new();
+    let mut prompt_eval_count = None;
+    let mut eval_count = None;
+    let mut line_buf = String::new();
+    let mut byte_stream = resp.bytes_stream();
+
+    while let Some(chunk_result) = byte_stream.next().await {
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        line_buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = line_buf.find('\n') {
+            let line = line_buf[..newline_pos].trim().to_string();
+            line_buf.drain(..=newline_pos);
+            if line.is_empty() {
+                continue;
+            }
+
+            let parsed: OllamaChatStreamLine = serde_json::from_str(&line)
+                .map_err(|e| format!("Parse error: {}", e))?;
+
+            if let Some(message) = parsed.message {
+                if !message.content.is_empty() {
+                    full_response.push_str(&message.content);
+                    let _ = window.emit("llm-stream", LLMStreamEvent {
+                        stream_id: stream_id.to_string(),
+                        event_type: "chunk".to_string(),
+                        content: message.content,
+                        prompt_eval_count: None,
+                        eval_count: None,
+                    });
+                }
+            }
+
+            if parsed.done {
+                prompt_eval_count = parsed.prompt_eval_count;
+                eval_count = parsed.eval_count;
+            }
+        }
+    }
+
+    Ok((full_response, prompt_eval_count, eval_count))
+}
+
+/// Default system prompt for the Kubernetes SRE assistant. Describes the
+/// same four investigation tools `investigation_tool_definitions` actually
+/// wires up, so the model can call them as real structured tools rather
+/// than just suggesting kubectl commands in prose.
+const DEFAULT_K8S_SYSTEM_PROMPT: &str = r#"You are a Kubernetes SRE assistant with the ability to execute kubectl commands.
+
+When you need more information about a resource, you can use these tools:
+- describe_resource(kind, namespace, name): Get detailed information about a resource
+- get_logs(namespace, pod_name, container): Get logs from a pod
+- get_events(namespace, name): Get events related to a resource
+- list_pods(namespace): List all pods in a namespace
+
+When suggesting kubectl commands, use the actual tools instead of just suggesting commands.
+Format your responses in markdown. Be concise and actionable."#;
+
+/// Fixed set of read-only Kubernetes investigation tools that
+/// `call_openai_compatible` and `call_anthropic` can call on their own,
+/// without round-tripping through the frontend the way `call_llm_with_tools`
+/// does. Each one maps to a read-only kubectl invocation in
+/// `investigation_tool_command`, gated by `is_read_only_kubectl`.
+fn investigation_tool_definitions() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            kind: "function".to_string(),
+            function: ToolFunctionDef {
+                name: "describe_resource".to_string(),
+                description: Some("Get detailed information about a Kubernetes resource".to_string()),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string", "description": "Resource kind, e.g. pod, deployment, service" },
+                        "namespace": { "type": "string" },
+                        "name": { "type": "string" }
+                    },
+                    "required": ["kind", "namespace", "name"]
+                }),
+            },
+        },
+        ToolDefinition {
+            kind: "function".to_string(),
+            function: ToolFunctionDef {
+                name: "get_logs".to_string(),
+                description: Some("Get logs from a pod".to_string()),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "namespace": { "type": "string" },
+                        "pod_name": { "type": "string" },
+                        "container": { "type": "string" }
+                    },
+                    "required": ["namespace", "pod_name"]
+                }),
+            },
+        },
+        ToolDefinition {
+            kind: "function".to_string(),
+            function: ToolFunctionDef {
+                name: "get_events".to_string(),
+                description: Some("Get events related to a resource".to_string()),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "namespace": { "type": "string" },
+                        "name": { "type": "string" }
+                    },
+                    "required": ["namespace"]
+                }),
+            },
+        },
+        ToolDefinition {
+            kind: "function".to_string(),
+            function: ToolFunctionDef {
+                name: "list_pods".to_string(),
+                description: Some("List all pods in a namespace".to_string()),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "namespace": { "type": "string" }
+                    },
+                    "required": ["namespace"]
+                }),
+            },
+        },
+    ]
+}
+
+/// Build the read-only `kubectl` argv for one of
+/// `investigation_tool_definitions`'s calls.
+fn investigation_tool_command(name: &str, input: &Value) -> Result<Vec<String>, String> {
+    let get_str = |key: &str| input.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    match name {
+        "describe_resource" => {
+            let kind = get_str("kind").ok_or("describe_resource requires 'kind'")?;
+            let namespace = get_str("namespace").ok_or("describe_resource requires 'namespace'")?;
+            let name = get_str("name").ok_or("describe_resource requires 'name'")?;
+            Ok(vec!["describe".to_string(), kind, name, "-n".to_string(), namespace])
+        }
+        "get_logs" => {
+            let namespace = get_str("namespace").ok_or("get_logs requires 'namespace'")?;
+            let pod_name = get_str("pod_name").ok_or("get_logs requires 'pod_name'")?;
+            let mut args = vec!["logs".to_string(), pod_name, "-n".to_string(), namespace, "--tail=200".to_string()];
+            if let Some(container) = get_str("container") {
+                args.push("-c".to_string());
+                args.push(container);
+            }
+            Ok(args)
+        }
+        "get_events" => {
+            let namespace = get_str("namespace").ok_or("get_events requires 'namespace'")?;
+            let mut args = vec!["get".to_string(), "events".to_string(), "-n".to_string(), namespace, "--sort-by=.lastTimestamp".to_string()];
+            if let Some(name) = get_str("name") {
+                args.push(format!("--field-selector=involvedObject.name={}", name));
+            }
+            Ok(args)
+        }
+        "list_pods" => {
+            let namespace = get_str("namespace").ok_or("list_pods requires 'namespace'")?;
+            Ok(vec!["get".to_string(), "pods".to_string(), "-n".to_string(), namespace])
+        }
+        other => Err(format!("Unknown investigation tool '{}'", other)),
+    }
+}
+
+/// Run one investigation tool call end to end: build its kubectl
+/// invocation, reject it if `is_read_only_kubectl` says it isn't read-only
+/// (the model picks the arguments, so this is the last line of defense
+/// against it smuggling in a mutating verb), then execute it.
+async fn execute_investigation_tool(name: &str, input: &Value) -> Result<String, String> {
+    let args = investigation_tool_command(name, input)?;
+    let rendered = format!("kubectl {}", args.join(" "));
+    if !is_read_only_kubectl(&rendered) {
+        return Err(format!("Refusing to run non-read-only command: {}", rendered));
+    }
+
+    let mut cmd = tokio::process::Command::new("kubectl");
+    cmd.args(&args);
+    crate::tool_env::inherit_env_tokio(&mut cmd);
+
+    let output = cmd.output().await.map_err(|e| format!("Failed to run kubectl: {}", e))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+async fn call_openai_compatible(
+    config: &LLMConfig,
+    prompt: String,
+    system_prompt: Option<String>,
+    conversation_history: Vec<serde_json::Value>,
+) -> Result<String, String> {
+    let sys = system_prompt.unwrap_or_else(|| DEFAULT_K8S_SYSTEM_PROMPT.to_string());
+
+    // Determine if JSON mode should be enabled BEFORE consuming strings
+    // Enable JSON mode when the system prompt mentions JSON output
+    let use_json_mode = sys.to_lowercase().contains("json") ||
+                        sys.contains("JSON") ||
+                        prompt.to_lowercase().contains("respond with") && prompt.to_lowercase().contains("json");
+
+    let mut messages = vec![ChatMessage::text("system", sys)];
+
+    // Add conversation history
+    for msg in conversation_history {
+        if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
+            if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
+                messages.push(ChatMessage::text(role, content.to_string()));
+            }
+        }
+    }
+
+    messages.push(ChatMessage::text("user", prompt));
+
+    let is_ollama = is_ollama_config(config);
+    let chat_url = resolve_chat_url(&config.base_url, is_ollama);
+    let tools = Some(investigation_tool_definitions());
+
+    // Increased timeout for large models (70B can take 2-3 minutes)
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let registry_entry = registry_entry_for(config);
+
+    for _step in 0..MAX_TOOL_STEPS {
+        let body = ChatRequest {
+            model: config.model.clone(),
+            messages: messages.clone(),
+            max_tokens: Some(config.max_tokens),
+            temperature: Some(config.temperature),
+            stream: None,
+            // Ollama uses "format": "json"
+            format: if is_ollama && use_json_mode { Some("json".to_string()) } else { None },
+            // OpenAI uses "response_format": {"type": "json_object"}
+            response_format: if !is_ollama && use_json_mode {
+                Some(ResponseFormat { format_type: "json_object".to_string() })
+            } else {
+                None
+            },
+            tools: tools.clone(),
+            tool_choice: None,
+            options: if is_ollama { Some(ollama_options(config)) } else { None },
+        };
+
+        let mut body_value = serde_json::to_value(&body).map_err(|e| e.to_string())?;
+        if let Some(entry) = &registry_entry {
+            body_value = merge_extra_params(body_value, &entry.extra_params);
+        }
+
+        let mut request = client.post(&chat_url).json(&body_value);
+        if let Some(ref api_key) = config.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let resp = request.send().await.map_err(|e| format!("Request error: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("LLM HTTP error {}: {}", status, body));
+        }
+
+        let parsed: ChatResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        let choice = parsed.choices.into_iter().next().ok_or("No response from model")?;
+
+        let calls = choice.message.tool_calls.unwrap_or_default();
+        if calls.is_empty() {
+            return Ok(choice.message.content.unwrap_or_else(|| "No response from model".to_string()));
+        }
+
+        messages.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: choice.message.content,
+            tool_calls: Some(calls.clone()),
+            tool_call_id: None,
+        });
+
+        for call in calls {
+            let args: Value = serde_json::from_str(&call.function.arguments).unwrap_or(Value::Null);
+            let result = execute_investigation_tool(&call.function.name, &args)
+                .await
+                .unwrap_or_else(|e| format!("Error: {}", e));
+            messages.push(ChatMessage {
+                role: "tool".to_string(),
+                content: Some(result),
+                tool_calls: None,
+                tool_call_id: Some(call.id),
+            });
+        }
+    }
+
+    Err(format!("Tool-calling loop exceeded {} steps without a final answer", MAX_TOOL_STEPS))
+}
+
+async fn call_anthropic(
+    config: &LLMConfig,
+    prompt: String,
+    system_prompt: Option<String>,
+    conversation_history: Vec<serde_json::Value>,
+) -> Result<String, String> {
+    let api_key = config.api_key.as_ref()
+        .ok_or_else(|| "API key is required for Anthropic".to_string())?;
+
+    let sys = system_prompt.unwrap_or_else(|| DEFAULT_K8S_SYSTEM_PROMPT.to_string());
+
+    let mut messages: Vec<AnthropicMessage> = vec![];
+
+    // Add conversation history
+    for msg in conversation_history {
+        if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
+            if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
+                // Anthropic only accepts 'user' and 'assistant' roles
+                let anthropic_role = match role {
+                    "system" => continue, // Skip system messages, we'll use the system field
+                    "user" => "user",
+                    _ => "assistant",
+                };
+                messages.push(AnthropicMessage {
+                    role: anthropic_role.to_string(),
+                    content: Value::String(content.to_string()),
+                });
+            }
+        }
+    }
+
+    messages.push(AnthropicMessage {
+        role: "user".to_string(),
+        content: Value::String(prompt),
+    });
+
+    // Translate the built-in investigation tools into Anthropic's flatter
+    // tool shape, same as `call_anthropic_with_tools` does for caller-supplied ones.
+    let anthropic_tools: Vec<AnthropicTool> = investigation_tool_definitions()
+        .iter()
+        .map(|t| AnthropicTool {
+            name: t.function.name.clone(),
+            description: t.function.description.clone(),
+            input_schema: t.function.parameters.clone(),
+        })
+        .collect();
+
+    let base_url = config.base_url.trim_end_matches('/');
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let registry_entry = registry_entry_for(config);
+
+    for _step in 0..MAX_TOOL_STEPS {
+        let body = AnthropicRequest {
+            model: config.model.clone(),
+            messages: messages.clone(),
+            max_tokens: config.max_tokens,
+            temperature: Some(config.temperature),
+            system: Some(sys.clone()),
+            tools: Some(anthropic_tools.clone()),
+            stream: None,
+        };
+
+        let mut body_value = serde_json::to_value(&body).map_err(|e| e.to_string())?;
+        if let Some(entry) = &registry_entry {
+            body_value = merge_extra_params(body_value, &entry.extra_params);
+        }
+
+        let resp = client
+            .post(format!("{}/messages", base_url))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&body_value)
+            .send()
+            .await
+            .map_err(|e| format!("Request error: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Anthropic API error {}: {}", status, body));
+        }
+
+        let parsed: AnthropicResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        let tool_uses: Vec<&AnthropicContentBlock> = parsed.content.iter().filter(|b| b.kind == "tool_use").collect();
+
+        if tool_uses.is_empty() {
+            let answer = parsed.content.iter().filter_map(|b| b.text.clone()).collect::<Vec<_>>().join("");
+            return Ok(if answer.is_empty() { "No response from model".to_string() } else { answer });
+        }
+
+        // Echo the assistant's turn back verbatim (text + tool_use blocks),
+        // as Anthropic requires for the tool_result turn that follows it.
+        let assistant_content = serde_json::to_value(&parsed.content).map_err(|e| e.to_string())?;
+        messages.push(AnthropicMessage { role: "assistant".to_string(), content: assistant_content });
+
+        let mut tool_results = Vec::new();
+        for block in tool_uses {
+            let id = block.id.clone().unwrap_or_default();
+            let name = block.name.clone().unwrap_or_default();
+            let input = block.input.clone().unwrap_or(Value::Null);
+            let result = execute_investigation_tool(&name, &input)
+                .await
+                .unwrap_or_else(|e| format!("Error: {}", e));
+            tool_results.push(json!({ "type": "tool_result", "tool_use_id": id, "content": result }));
+        }
+        messages.push(AnthropicMessage { role: "user".to_string(), content: Value::Array(tool_results) });
+    }
+
+    Err(format!("Tool-calling loop exceeded {} steps without a final answer", MAX_TOOL_STEPS))
+}
+
+/// One `data: ` frame of Anthropic's streaming API. Only the fields
+/// `call_anthropic_streaming` actually needs - `content_block_delta` events
+/// carry `delta.text`, everything else (`message_start`, `ping`,
+/// `content_block_start`/`stop`, `message_delta`, `message_stop`) is only
+/// inspected for its `type` so the loop knows when to stop.
+#[derive(Deserialize)]
+struct AnthropicStreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    delta: Option<AnthropicStreamDelta>,
+}
+
+/// Anthropic-specific counterpart to the OpenAI-compatible SSE loop in
+/// `call_llm_streaming`'s fallback path - Anthropic's streaming wire format
+/// (`content_block_delta` events with a nested `delta.text`) doesn't fit the
+/// OpenAI `choices[0].delta.content` shape, so it gets its own request/parse
+/// loop rather than being squeezed into the shared one. Tool calling isn't
+/// exposed here, matching how `call_llm_streaming`'s OpenAI-compatible path
+/// doesn't call tools either.
+async fn call_anthropic_streaming(
+    config: &LLMConfig,
+    prompt: String,
+    system_prompt: Option<String>,
+    conversation_history: Vec<serde_json::Value>,
+    window: &tauri::Window,
+    stream_id: &str,
+) -> Result<String, String> {
+    use futures::StreamExt;
+
+    let api_key = config.api_key.as_ref()
+        .ok_or_else(|| "API key is required for Anthropic".to_string())?;
+
+    let mut messages: Vec<AnthropicMessage> = vec![];
+    for msg in conversation_history {
+        if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
+            if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
+                let anthropic_role = match role {
+                    "system" => continue,
+                    "user" => "user",
+                    _ => "assistant",
+                };
+                messages.push(AnthropicMessage {
+                    role: anthropic_role.to_string(),
+                    content: Value::String(content.to_string()),
+                });
+            }
+        }
+    }
+    messages.push(AnthropicMessage { role: "user".to_string(), content: Value::String(prompt) });
+
+    let body = AnthropicRequest {
+        model: config.model.clone(),
+        messages,
+        max_tokens: config.max_tokens,
+        temperature: Some(config.temperature),
+        system: system_prompt,
+        tools: None,
+        stream: Some(true),
+    };
+
+    let base_url = config.base_url.trim_end_matches('/');
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let resp = client
+        .post(format!("{}/messages", base_url))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", "2023-06-01")
+        .header("content-type", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Request error: {}", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Anthropic API error {}: {}", status, body));
+    }
+
+    let mut full_response = String::new();
+    let mut stream = resp.bytes_stream();
+
+    'outer: while let Some(chunk_result) = stream.next().await {
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        let chunk_str = String::from_utf8_lossy(&chunk);
+
+        for line in chunk_str.lines() {
+            let Some(data) = line.strip_prefix("data: ") else { continue };
+            let Ok(event) = serde_json::from_str::<AnthropicStreamEvent>(data) else { continue };
+
+            match event.kind.as_str() {
+                "content_block_delta" => {
+                    if let Some(text) = event.delta.and_then(|d| d.text) {
+                        full_response.push_str(&text);
+                        let _ = window.emit("llm-stream", LLMStreamEvent {
+                            stream_id: stream_id.to_string(),
+                            event_type: "chunk".to_string(),
+                            content: text,
+                            prompt_eval_count: None,
+                            eval_count: None,
+                        });
+                    }
+                }
+                "message_stop" => break 'outer,
+                _ => {}
+            }
+        }
+    }
+
+    Ok(full_response)
+}
+
+// ============================================================================
+// Replicate - async create-and-poll prediction protocol
+// ============================================================================
+
+/// How often to poll a Replicate prediction's `urls.get` while it runs.
+const REPLICATE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Overall cap on how long a Replicate prediction may run before we give up
+/// polling it.
+const REPLICATE_POLL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[derive(Serialize)]
+struct ReplicateInput {
+    prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_prompt: Option<String>,
+    max_tokens: u32,
+    temperature: f32,
+}
+
+#[derive(Serialize)]
+struct ReplicatePredictionRequest {
+    input: ReplicateInput,
+}
+
+#[derive(Deserialize)]
+struct ReplicateUrls {
+    get: String,
+    #[serde(default)]
+    stream: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReplicatePrediction {
+    status: String,
+    urls: ReplicateUrls,
+    #[serde(default)]
+    output: Option<Value>,
+    #[serde(default)]
+    error: Option<Value>,
+}
+
+/// Replicate's `output` is model-dependent: usually a JSON array of string
+/// tokens to join into the full response, occasionally a single string.
+fn replicate_output_to_string(output: &Value) -> String {
+    match output {
+        Value::Array(items) => items.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(""),
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+async fn create_replicate_prediction(
+    client: &reqwest::Client,
+    config: &LLMConfig,
+    api_key: &str,
+    prompt: String,
+    system_prompt: Option<String>,
+) -> Result<ReplicatePrediction, String> {
+    let body = ReplicatePredictionRequest {
+        input: ReplicateInput {
+            prompt,
+            system_prompt,
+            max_tokens: config.max_tokens,
+            temperature: config.temperature,
+        },
+    };
+
+    let resp = client
+        .post(format!("https://api.replicate.com/v1/models/{}/predictions", config.model))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Request error: {}", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Replicate API error {}: {}", status, body));
+    }
+
+    resp.json().await.map_err(|e| format!("Parse error: {}", e))
+}
+
+/// Poll a Replicate prediction's `urls.get` on a fixed interval until it
+/// reaches a terminal status, then collect its `output`.
+async fn poll_replicate_prediction(
+    client: &reqwest::Client,
+    api_key: &str,
+    mut prediction: ReplicatePrediction,
+) -> Result<String, String> {
+    let started = std::time::Instant::now();
+
+    while !matches!(prediction.status.as_str(), "succeeded" | "failed" | "canceled") {
+        if started.elapsed() > REPLICATE_POLL_TIMEOUT {
+            return Err("Replicate prediction timed out".to_string());
+        }
+        tokio::time::sleep(REPLICATE_POLL_INTERVAL).await;
+
+        let resp = client
+            .get(&prediction.urls.get)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .send()
+            .await
+            .map_err(|e| format!("Poll error: {}", e))?;
+
+        prediction = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+    }
+
+    match prediction.status.as_str() {
+        "succeeded" => Ok(replicate_output_to_string(&prediction.output.unwrap_or(Value::Null))),
+        _ => {
+            let reason = prediction.error.map(|e| e.to_string()).unwrap_or_else(|| prediction.status.clone());
+            Err(format!("Replicate prediction {}: {}", prediction.status, reason))
+        }
+    }
+}
+
+async fn call_replicate(config: &LLMConfig, prompt: String, system_prompt: Option<String>) -> Result<String, String> {
+    let api_key = config.api_key.as_ref()
+        .ok_or_else(|| "API key is required for Replicate".to_string())?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let prediction = create_replicate_prediction(&client, config, api_key, prompt, system_prompt).await?;
+    poll_replicate_prediction(&client, api_key, prediction).await
+}
+
+/// Streaming counterpart of `call_replicate`. Replicate hands back a
+/// `urls.stream` SSE endpoint alongside the prediction when the model
+/// supports it; connect to that and emit tokens via the same `LLMStreamEvent`
+/// "chunk" events `call_llm_streaming` uses. Falls back to create-and-poll,
+/// emitting the whole answer as one chunk, for models that don't.
+async fn call_replicate_streaming(
+    config: &LLMConfig,
+    prompt: String,
+    system_prompt: Option<String>,
+    window: &tauri::Window,
+    stream_id: &str,
+) -> Result<String, String> {
+    use futures::StreamExt;
+
+    let api_key = config.api_key.as_ref()
+        .ok_or_else(|| "API key is required for Replicate".to_string())?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let prediction = create_replicate_prediction(&client, config, api_key, prompt, system_prompt).await?;
+
+    let Some(stream_url) = prediction.urls.stream.clone() else {
+        let full_response = poll_replicate_prediction(&client, api_key, prediction).await?;
+        let _ = window.emit("llm-stream", LLMStreamEvent {
+            stream_id: stream_id.to_string(),
+            event_type: "chunk".to_string(),
+            content: full_response.clone(),
+            prompt_eval_count: None,
+            eval_count: None,
+        });
+        return Ok(full_response);
+    };
+
+    let resp = client
+        .get(&stream_url)
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Accept", "text/event-stream")
+        .send()
+        .await
+        .map_err(|e| format!("Stream connect error: {}", e))?;
+
+    let mut full_response = String::new();
+    let mut byte_stream = resp.bytes_stream();
+    let mut event_type = "output".to_string();
+
+    while let Some(chunk_result) = byte_stream.next().await {
+        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        let chunk_str = String::from_utf8_lossy(&chunk);
+
+        for line in chunk_str.lines() {
+            if let Some(event) = line.strip_prefix("event: ") {
+                event_type = event.to_string();
+            } else if let Some(data) = line.strip_prefix("data: ") {
+                match event_type.as_str() {
+                    "output" => {
+                        full_response.push_str(data);
+                        let _ = window.emit("llm-stream", LLMStreamEvent {
+                            stream_id: stream_id.to_string(),
+                            event_type: "chunk".to_string(),
+                            content: data.to_string(),
+                            prompt_eval_count: None,
+                            eval_count: None,
+                        });
+                    }
+                    "done" => return Ok(full_response),
+                    "error" => return Err(format!("Replicate stream error: {}", data)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(full_response)
+}
+
+async fn check_replicate_status_internal(config: &LLMConfig) -> Result<LLMStatus, String> {
+    let Some(api_key) = config.api_key.as_ref() else {
+        return Ok(LLMStatus {
+            connected: false,
+            provider: "Replicate".to_string(),
+            model: config.model.clone(),
+            available_models: vec![],
+            error: Some("API key is required for Replicate".to_string()),
+        });
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let result = client
+        .get(format!("https://api.replicate.com/v1/models/{}", config.model))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .send()
+        .await;
+
+    match result {
+        Ok(resp) if resp.status().is_success() => Ok(LLMStatus {
+            connected: true,
+            provider: "Replicate".to_string(),
+            model: config.model.clone(),
+            available_models: vec![],
+            error: None,
+        }),
+        Ok(resp) => Ok(LLMStatus {
+            connected: false,
+            provider: "Replicate".to_string(),
+            model: config.model.clone(),
+            available_models: vec![],
+            error: Some(format!("Replicate API error: {}", resp.status())),
+        }),
+        Err(e) => Ok(LLMStatus {
+            connected: false,
+            provider: "Replicate".to_string(),
+            model: config.model.clone(),
+            available_models: vec![],
+            error: Some(format!("Connection error: {}", e)),
+        }),
+    }
+}
+
+// ============================================================================
+// Tool/function calling
+// ============================================================================
+
+/// Hard cap on request/tool-result round-trips in `call_llm_with_tools`, so a
+/// model that keeps calling tools instead of answering can't loop forever.
+const MAX_TOOL_STEPS: u32 = 8;
+
+/// How long to wait for the frontend to call `submit_tool_result` after a
+/// `llm-tool-call` event, before giving up on that step.
+const TOOL_RESULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Emitted on the `llm-tool-call` event for each tool call the model made.
+/// The frontend executes the call and reports back via `submit_tool_result`
+/// with this same `call_id`.
+#[derive(Clone, Serialize)]
+pub struct ToolCallEvent {
+    pub call_id: String,
+    pub name: String,
+    pub arguments: String, // JSON-encoded
+}
+
+// Pending tool calls, keyed by `call_id`, waiting on their frontend-supplied
+// result. Same `OnceLock<Mutex<...>>` process-global pattern as
+// `utils::logging::get_log_path`.
+static PENDING_TOOL_CALLS: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, tokio::sync::oneshot::Sender<String>>>> = std::sync::OnceLock::new();
+
+fn pending_tool_calls() -> &'static std::sync::Mutex<std::collections::HashMap<String, tokio::sync::oneshot::Sender<String>>> {
+    PENDING_TOOL_CALLS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Resolve a pending tool call with the result the frontend computed for it.
+#[tauri::command]
+pub fn submit_tool_result(call_id: String, result: String) -> Result<(), String> {
+    let sender = pending_tool_calls().lock().unwrap().remove(&call_id);
+    match sender {
+        Some(tx) => {
+            let _ = tx.send(result);
+            Ok(())
+        }
+        None => Err(format!("No pending tool call with id '{}'", call_id)),
+    }
+}
+
+/// Emit `llm-tool-call` for one call and block until `submit_tool_result`
+/// answers it (or it times out). `call_id` should be the id the provider
+/// assigned the call, so the frontend's result round-trips straight back
+/// into the next request as that id's `tool_result`/`tool` message.
+async fn await_tool_result(window: &tauri::Window, call_id: String, name: &str, arguments: Value) -> Result<String, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    pending_tool_calls().lock().unwrap().insert(call_id.clone(), tx);
+
+    let _ = window.emit("llm-tool-call", ToolCallEvent {
+        call_id: call_id.clone(),
+        name: name.to_string(),
+        arguments: arguments.to_string(),
+    });
+
+    match tokio::time::timeout(TOOL_RESULT_TIMEOUT, rx).await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(_)) => Err(format!("Tool call '{}' was dropped before a result arrived", name)),
+        Err(_) => {
+            pending_tool_calls().lock().unwrap().remove(&call_id);
+            Err(format!("Tool call '{}' timed out waiting for a result", name))
+        }
+    }
+}
+
+/// Call an LLM with tool/function calling enabled, looping on `tool_calls`
+/// until the model gives a plain-text answer or `MAX_TOOL_STEPS` is hit.
+/// Note: Parameters use camelCase to match JavaScript naming convention
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn call_llm_with_tools(
+    config: LLMConfig,
+    prompt: String,
+    systemPrompt: Option<String>,
+    conversationHistory: Vec<serde_json::Value>,
+    tools: Vec<ToolDefinition>,
+    window: tauri::Window,
+) -> Result<String, String> {
+    match config.provider {
+        LLMProvider::Ollama | LLMProvider::OpenAI | LLMProvider::Custom | LLMProvider::Groq => {
+            call_openai_compatible_with_tools(&config, prompt, systemPrompt, conversationHistory, tools, &window).await
+        }
+        LLMProvider::Anthropic => {
+            call_anthropic_with_tools(&config, prompt, systemPrompt, conversationHistory, tools, &window).await
+        }
+        LLMProvider::Replicate => {
+            Err("Tool calling is not supported for Replicate models".to_string())
+        }
+        LLMProvider::ClaudeCode => {
+            Err("Claude Code not supported for direct LLM calls".to_string())
+        }
+    }
+}
+
+async fn call_openai_compatible_with_tools(
+    config: &LLMConfig,
+    prompt: String,
+    system_prompt: Option<String>,
+    conversation_history: Vec<serde_json::Value>,
+    tools: Vec<ToolDefinition>,
+    window: &tauri::Window,
+) -> Result<String, String> {
+    let sys = system_prompt.unwrap_or_else(|| {
+        r#"You are a Kubernetes SRE assistant with the ability to execute kubectl commands.
+
+When you need more information about a resource, you can use these tools:
+- describe_resource(kind, namespace, name): Get detailed information about a resource
+- get_logs(namespace, pod_name, container): Get logs from a pod
 - get_events(namespace, name): Get events related to a resource
 - list_pods(namespace): List all pods in a namespace
 
@@ -830,99 +2341,88 @@ When suggesting kubectl commands, use the actual tools instead of just suggestin
 Format your responses in markdown. Be concise and actionable."#.to_string()
     });
 
-    // Determine if JSON mode should be enabled BEFORE consuming strings
-    // Enable JSON mode when the system prompt mentions JSON output
-    let use_json_mode = sys.to_lowercase().contains("json") ||
-                        sys.contains("JSON") ||
-                        prompt.to_lowercase().contains("respond with") && prompt.to_lowercase().contains("json");
-
-    let mut messages = vec![ChatMessage {
-        role: "system".to_string(),
-        content: sys,
-    }];
-
-    // Add conversation history
+    let mut messages = vec![ChatMessage::text("system", sys)];
     for msg in conversation_history {
         if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
             if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
-                messages.push(ChatMessage {
-                    role: role.to_string(),
-                    content: content.to_string(),
-                });
+                messages.push(ChatMessage::text(role, content.to_string()));
             }
         }
     }
+    messages.push(ChatMessage::text("user", prompt));
 
-    messages.push(ChatMessage {
-        role: "user".to_string(),
-        content: prompt,
-    });
-
-    let is_ollama = config.base_url.contains("ollama") ||
-                    config.base_url.contains("11434") ||
-                    matches!(config.provider, LLMProvider::Ollama);
-
-    let body = ChatRequest {
-        model: config.model.clone(),
-        messages,
-        max_tokens: Some(config.max_tokens),
-        temperature: Some(config.temperature),
-        stream: None,
-        // Ollama uses "format": "json"
-        format: if is_ollama && use_json_mode { Some("json".to_string()) } else { None },
-        // OpenAI uses "response_format": {"type": "json_object"}
-        response_format: if !is_ollama && use_json_mode {
-            Some(ResponseFormat { format_type: "json_object".to_string() })
-        } else {
-            None
-        },
-    };
-
-    let base_url = config.base_url.trim_end_matches('/');
-    // Check if base_url already contains /v1 to avoid double /v1/v1
-    let chat_url = if base_url.ends_with("/v1") {
-        format!("{}/chat/completions", base_url)
-    } else if is_ollama {
-        format!("{}/v1/chat/completions", base_url)
-    } else {
-        format!("{}/chat/completions", base_url)
-    };
+    let is_ollama = is_ollama_config(config);
+    let chat_url = resolve_chat_url(&config.base_url, is_ollama);
+    let tools = if tools.is_empty() { None } else { Some(tools) };
 
-    // Increased timeout for large models (70B can take 2-3 minutes)
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(300))
         .build()
         .map_err(|e| e.to_string())?;
 
-    let mut request = client.post(&chat_url).json(&body);
-    if let Some(ref api_key) = config.api_key {
-        request = request.header("Authorization", format!("Bearer {}", api_key));
-    }
+    for _step in 0..MAX_TOOL_STEPS {
+        let body = ChatRequest {
+            model: config.model.clone(),
+            messages: messages.clone(),
+            max_tokens: Some(config.max_tokens),
+            temperature: Some(config.temperature),
+            stream: None,
+            format: None,
+            response_format: None,
+            tools: tools.clone(),
+            tool_choice: None,
+            options: if is_ollama { Some(ollama_options(config)) } else { None },
+        };
+
+        let mut request = client.post(&chat_url).json(&body);
+        if let Some(ref api_key) = config.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
 
-    let resp = request.send().await.map_err(|e| format!("Request error: {}", e))?;
+        let resp = request.send().await.map_err(|e| format!("Request error: {}", e))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("LLM HTTP error {}: {}", status, body));
+        }
 
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        return Err(format!("LLM HTTP error {}: {}", status, body));
-    }
+        let parsed: ChatResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        let choice = parsed.choices.into_iter().next().ok_or("No response from model")?;
+
+        let calls = choice.message.tool_calls.unwrap_or_default();
+        if calls.is_empty() {
+            return Ok(choice.message.content.unwrap_or_else(|| "No response from model".to_string()));
+        }
 
-    let parsed: ChatResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        messages.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: choice.message.content,
+            tool_calls: Some(calls.clone()),
+            tool_call_id: None,
+        });
 
-    let answer = parsed
-        .choices
-        .get(0)
-        .and_then(|c| c.message.content.clone())
-        .unwrap_or_else(|| "No response from model".to_string());
+        for call in calls {
+            let args: Value = serde_json::from_str(&call.function.arguments).unwrap_or(Value::Null);
+            let result = await_tool_result(window, call.id.clone(), &call.function.name, args).await?;
+            messages.push(ChatMessage {
+                role: "tool".to_string(),
+                content: Some(result),
+                tool_calls: None,
+                tool_call_id: Some(call.id),
+            });
+        }
+    }
 
-    Ok(answer)
+    Err(format!("Tool-calling loop exceeded {} steps without a final answer", MAX_TOOL_STEPS))
 }
 
-async fn call_anthropic(
+async fn call_anthropic_with_tools(
     config: &LLMConfig,
     prompt: String,
     system_prompt: Option<String>,
     conversation_history: Vec<serde_json::Value>,
+    tools: Vec<ToolDefinition>,
+    window: &tauri::Window,
 ) -> Result<String, String> {
     let api_key = config.api_key.as_ref()
         .ok_or_else(|| "API key is required for Anthropic".to_string())?;
@@ -941,37 +2441,36 @@ Format your responses in markdown. Be concise and actionable."#.to_string()
     });
 
     let mut messages: Vec<AnthropicMessage> = vec![];
-
-    // Add conversation history
     for msg in conversation_history {
         if let Some(role) = msg.get("role").and_then(|v| v.as_str()) {
             if let Some(content) = msg.get("content").and_then(|v| v.as_str()) {
-                // Anthropic only accepts 'user' and 'assistant' roles
                 let anthropic_role = match role {
-                    "system" => continue, // Skip system messages, we'll use the system field
+                    "system" => continue,
                     "user" => "user",
                     _ => "assistant",
                 };
                 messages.push(AnthropicMessage {
                     role: anthropic_role.to_string(),
-                    content: content.to_string(),
+                    content: Value::String(content.to_string()),
                 });
             }
         }
     }
-
     messages.push(AnthropicMessage {
         role: "user".to_string(),
-        content: prompt,
+        content: Value::String(prompt),
     });
 
-    let body = AnthropicRequest {
-        model: config.model.clone(),
-        messages,
-        max_tokens: config.max_tokens,
-        temperature: Some(config.temperature),
-        system: Some(sys),
-    };
+    // Translate OpenAI-shaped tool defs into Anthropic's flatter tool shape.
+    let anthropic_tools: Vec<AnthropicTool> = tools
+        .iter()
+        .map(|t| AnthropicTool {
+            name: t.function.name.clone(),
+            description: t.function.description.clone(),
+            input_schema: t.function.parameters.clone(),
+        })
+        .collect();
+    let anthropic_tools = if anthropic_tools.is_empty() { None } else { Some(anthropic_tools) };
 
     let base_url = config.base_url.trim_end_matches('/');
     let client = reqwest::Client::builder()
@@ -979,31 +2478,131 @@ Format your responses in markdown. Be concise and actionable."#.to_string()
         .build()
         .map_err(|e| e.to_string())?;
 
-    let resp = client
-        .post(format!("{}/messages", base_url))
-        .header("x-api-key", api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("content-type", "application/json")
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| format!("Request error: {}", e))?;
+    for _step in 0..MAX_TOOL_STEPS {
+        let body = AnthropicRequest {
+            model: config.model.clone(),
+            messages: messages.clone(),
+            max_tokens: config.max_tokens,
+            temperature: Some(config.temperature),
+            system: Some(sys.clone()),
+            tools: anthropic_tools.clone(),
+            stream: None,
+        };
+
+        let resp = client
+            .post(format!("{}/messages", base_url))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("Request error: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Anthropic API error {}: {}", status, body));
+        }
 
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        return Err(format!("Anthropic API error {}: {}", status, body));
+        let parsed: AnthropicResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        let tool_uses: Vec<&AnthropicContentBlock> = parsed.content.iter().filter(|b| b.kind == "tool_use").collect();
+
+        if tool_uses.is_empty() {
+            let answer = parsed.content.iter().filter_map(|b| b.text.clone()).collect::<Vec<_>>().join("");
+            return Ok(if answer.is_empty() { "No response from model".to_string() } else { answer });
+        }
+
+        // Echo the assistant's turn back verbatim (text + tool_use blocks),
+        // as Anthropic requires for the tool_result turn that follows it.
+        let assistant_content = serde_json::to_value(&parsed.content).map_err(|e| e.to_string())?;
+        messages.push(AnthropicMessage { role: "assistant".to_string(), content: assistant_content });
+
+        let mut tool_results = Vec::new();
+        for block in tool_uses {
+            let id = block.id.clone().unwrap_or_default();
+            let name = block.name.clone().unwrap_or_default();
+            let input = block.input.clone().unwrap_or(Value::Null);
+            let result = await_tool_result(window, id.clone(), &name, input).await?;
+            tool_results.push(json!({ "type": "tool_result", "tool_use_id": id, "content": result }));
+        }
+        messages.push(AnthropicMessage { role: "user".to_string(), content: Value::Array(tool_results) });
     }
 
-    let parsed: AnthropicResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+    Err(format!("Tool-calling loop exceeded {} steps without a final answer", MAX_TOOL_STEPS))
+}
+
+// ============================================================================
+// Model registry - user-editable models beyond the built-in defaults
+// ============================================================================
+
+/// One entry in the user's `models.json` registry. Lets newly-released or
+/// self-hosted models be used without a binary rebuild, and lets
+/// provider-specific request tuning (`top_p`, stop sequences, reasoning
+/// effort, ...) be set purely through configuration via `extra_params`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRegistryEntry {
+    pub provider: LLMProvider,
+    pub name: String,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub supports_json: bool,
+    #[serde(default)]
+    pub supports_tools: bool,
+    /// Merged on top of the outgoing request body by `call_openai_compatible`
+    /// / `call_anthropic` - see `merge_extra_params`.
+    #[serde(default)]
+    pub extra_params: Value,
+}
+
+/// Load the user's model registry from `<config_dir>/lens-killer/models.json`,
+/// mirroring how `analyze_text` loads `llm-config.json` from the same
+/// directory. A missing or unparseable file just means no extra models -
+/// this is additive to `get_default_llm_config`, not a replacement.
+fn load_model_registry() -> Vec<ModelRegistryEntry> {
+    let Some(config_dir) = dirs::config_dir() else { return vec![] };
+    let path = config_dir.join("lens-killer").join("models.json");
+    let Ok(content) = std::fs::read_to_string(path) else { return vec![] };
+    serde_json::from_str(&content).unwrap_or_default()
+}
+
+/// Model names registered for `provider`, for `check_llm_status` to report
+/// alongside its built-in list.
+fn registry_model_names(provider: &LLMProvider) -> Vec<String> {
+    load_model_registry()
+        .into_iter()
+        .filter(|e| std::mem::discriminant(&e.provider) == std::mem::discriminant(provider))
+        .map(|e| e.name)
+        .collect()
+}
+
+/// The registry entry matching `config`'s provider and model, if the user
+/// has registered one.
+fn registry_entry_for(config: &LLMConfig) -> Option<ModelRegistryEntry> {
+    load_model_registry().into_iter().find(|e| {
+        std::mem::discriminant(&e.provider) == std::mem::discriminant(&config.provider) && e.name == config.model
+    })
+}
 
-    let answer = parsed
-        .content
-        .get(0)
-        .and_then(|c| c.text.clone())
-        .unwrap_or_else(|| "No response from model".to_string());
+/// Merge a registry entry's `extra_params` object on top of an
+/// already-serialized request body, letting per-model config reach fields
+/// `ChatRequest`/`AnthropicRequest` don't declare (`top_p`, `stop`, ...).
+/// Keys in `extra` win. No-ops if either side isn't a JSON object.
+fn merge_extra_params(body: Value, extra: &Value) -> Value {
+    let Value::Object(mut map) = body else { return body };
+    if let Value::Object(extra_map) = extra {
+        for (k, v) in extra_map {
+            map.insert(k.clone(), v.clone());
+        }
+    }
+    Value::Object(map)
+}
 
-    Ok(answer)
+/// List the models the user has registered via `models.json`.
+#[tauri::command]
+pub fn list_registered_models() -> Vec<ModelRegistryEntry> {
+    load_model_registry()
 }
 
 /// Get default configuration for a provider
@@ -1020,6 +2619,8 @@ pub fn get_default_llm_config(provider: String) -> LLMConfig {
             embedding_endpoint: Some(DEFAULT_OPENAI_URL.to_string()),
             temperature: 0.2,
             max_tokens: 2048,
+            num_ctx: None,
+            options: None,
         },
         "anthropic" => LLMConfig {
             provider: LLMProvider::Anthropic,
@@ -1031,6 +2632,8 @@ pub fn get_default_llm_config(provider: String) -> LLMConfig {
             embedding_endpoint: None,
             temperature: 0.2,
             max_tokens: 2048,
+            num_ctx: None,
+            options: None,
         },
         "custom" => LLMConfig {
             provider: LLMProvider::Custom,
@@ -1042,6 +2645,23 @@ pub fn get_default_llm_config(provider: String) -> LLMConfig {
             embedding_endpoint: Some("http://localhost:11434".to_string()),
             temperature: 0.2,
             max_tokens: 2048,
+            num_ctx: None,
+            options: None,
+        },
+        "replicate" => LLMConfig {
+            provider: LLMProvider::Replicate,
+            api_key: None,
+            // Replicate's predictions API is always api.replicate.com - base_url
+            // is unused for this provider but kept populated for display/consistency.
+            base_url: "https://api.replicate.com/v1".to_string(),
+            model: "meta/meta-llama-3-8b-instruct".to_string(),
+            executor_model: None,
+            embedding_model: None,
+            embedding_endpoint: None,
+            temperature: 0.2,
+            max_tokens: 2048,
+            num_ctx: None,
+            options: None,
         },
         _ => LLMConfig::default(), // Ollama
     }
@@ -1248,22 +2868,351 @@ pub async fn generate_investigation_commands(context: String) -> Result<Vec<Stri
     // Validate commands are read-only
     let safe_commands: Vec<String> = commands
         .into_iter()
-        .filter(|cmd| {
-            let lower = cmd.to_lowercase();
-            // Block mutating commands
-            !lower.contains("apply") &&
-            !lower.contains("delete") &&
-            !lower.contains("patch") &&
-            !lower.contains("edit") &&
-            !lower.contains("scale") &&
-            !lower.contains("create") &&
-            !lower.contains("replace") &&
-            !lower.contains("drain") &&
-            !lower.contains("cordon") &&
-            !lower.contains("taint")
-        })
+        .filter(|cmd| is_read_only_kubectl(cmd))
         .take(6)
         .collect();
 
     Ok(safe_commands)
 }
+
+/// Reject kubectl invocations that mutate cluster state, by a simple
+/// substring denylist on the verb. Shared by `generate_investigation_commands`,
+/// which filters LLM-suggested commands, and `execute_investigation_tool`,
+/// which uses it as a last line of defense on model-chosen tool arguments.
+fn is_read_only_kubectl(cmd: &str) -> bool {
+    let lower = cmd.to_lowercase();
+    !lower.contains("apply") &&
+    !lower.contains("delete") &&
+    !lower.contains("patch") &&
+    !lower.contains("edit") &&
+    !lower.contains("scale") &&
+    !lower.contains("create") &&
+    !lower.contains("replace") &&
+    !lower.contains("drain") &&
+    !lower.contains("cordon") &&
+    !lower.contains("taint")
+}
+
+// ============================================================================
+// Agentic investigation orchestrator - chains web_search and
+// generate_investigation_commands as tools the model can call on its own
+// ============================================================================
+
+const INVESTIGATION_SYSTEM_PROMPT: &str = r#"You are a Kubernetes SRE investigation agent. Your job is to find the root cause of the issue described by the user.
+
+You have two tools:
+- web_search(query): Search the web for a known error signature, upstream issue, or documentation.
+- run_investigation_commands(context): Generate read-only kubectl commands for the given context and run them, returning their output.
+
+Use these tools repeatedly, building on what you learn from each result, until you're confident in the root cause. Then stop calling tools and reply with a final markdown report containing:
+- A root cause summary
+- The evidence that supports it (citing the commands/searches that revealed it)
+- Suggested remediation steps
+
+Do not suggest kubectl commands yourself - call run_investigation_commands instead so they're actually executed."#;
+
+/// Tool schema for `run_investigation`'s agent loop. Maps onto the two
+/// existing investigation primitives: `web_search` as-is, and
+/// `run_investigation_commands`, which wraps `generate_investigation_commands`
+/// with execution of what it generates (the orchestrator asks the model to
+/// read command *output*, not just the command list).
+fn investigation_agent_tools() -> Vec<ToolDefinition> {
+    vec![
+        ToolDefinition {
+            kind: "function".to_string(),
+            function: ToolFunctionDef {
+                name: "web_search".to_string(),
+                description: Some("Search the web for a known error signature, upstream issue, or documentation".to_string()),
+                parameters: json!({
+                    "type": "object",
+                    "properties": { "query": { "type": "string" } },
+                    "required": ["query"]
+                }),
+            },
+        },
+        ToolDefinition {
+            kind: "function".to_string(),
+            function: ToolFunctionDef {
+                name: "run_investigation_commands".to_string(),
+                description: Some("Generate read-only kubectl commands for the given context and run them, returning their output".to_string()),
+                parameters: json!({
+                    "type": "object",
+                    "properties": { "context": { "type": "string" } },
+                    "required": ["context"]
+                }),
+            },
+        },
+    ]
+}
+
+/// Pull the actual kubectl invocation out of one
+/// `generate_investigation_commands` entry (`"Title | kubectl cmd | Purpose"`),
+/// falling back to the whole string if it isn't in that shape.
+fn extract_kubectl_command(entry: &str) -> String {
+    let parts: Vec<&str> = entry.split('|').collect();
+    if parts.len() >= 3 {
+        parts[1].trim().to_string()
+    } else {
+        entry.trim().to_string()
+    }
+}
+
+/// Run one already-validated investigation command through a shell, so the
+/// bash pipes (`grep`, `jq`, `tail`, ...) `generate_investigation_commands`
+/// is allowed to use keep working - unlike `execute_investigation_tool`,
+/// which only ever runs a single kubectl invocation built from structured
+/// arguments. `is_read_only_kubectl` is re-checked here as a second line of
+/// defense in case a malformed entry slipped past `generate_investigation_commands`'s own filter.
+async fn run_readonly_shell_command(cmd: &str) -> Result<String, String> {
+    if !is_read_only_kubectl(cmd) {
+        return Err(format!("Refusing to run non-read-only command: {}", cmd));
+    }
+
+    let mut sh = tokio::process::Command::new("sh");
+    sh.arg("-c").arg(cmd);
+    crate::tool_env::inherit_env_tokio(&mut sh);
+
+    let output = sh.output().await.map_err(|e| format!("Failed to run command: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+/// Emitted on the `investigation-step` event after each tool call the agent
+/// makes, so the UI can render the reasoning chain as it happens rather than
+/// waiting for the final report.
+#[derive(Clone, Serialize)]
+pub struct InvestigationStepEvent {
+    pub step: u32,
+    pub tool: String,
+    pub input: String,
+    pub output: String,
+}
+
+/// Dispatch one of `investigation_agent_tools`'s calls, building its running
+/// scratchpad entry. `generate_investigation_commands` returns a title/command
+/// pair per line; this executes each one via `run_readonly_shell_command` and
+/// concatenates the results so the model can read real command output.
+async fn execute_investigation_agent_tool(name: &str, input: &Value) -> Result<String, String> {
+    match name {
+        "web_search" => {
+            let query = input.get("query").and_then(|v| v.as_str()).ok_or("web_search requires 'query'")?;
+            let results = web_search(query.to_string()).await?;
+            serde_json::to_string(&results).map_err(|e| e.to_string())
+        }
+        "run_investigation_commands" => {
+            let context = input.get("context").and_then(|v| v.as_str()).ok_or("run_investigation_commands requires 'context'")?;
+            let commands = generate_investigation_commands(context.to_string()).await?;
+            if commands.is_empty() {
+                return Ok("No commands were generated for this context.".to_string());
+            }
+
+            let mut output = String::new();
+            for entry in commands {
+                let cmd = extract_kubectl_command(&entry);
+                output.push_str(&format!("$ {}\n", cmd));
+                match run_readonly_shell_command(&cmd).await {
+                    Ok(stdout) => output.push_str(&stdout),
+                    Err(e) => output.push_str(&format!("(error: {})", e)),
+                }
+                output.push_str("\n\n");
+            }
+            Ok(output)
+        }
+        other => Err(format!("Unknown investigation tool '{}'", other)),
+    }
+}
+
+/// Multi-step investigation agent: lets the model alternate between
+/// `web_search` and `run_investigation_commands` - reading each result
+/// before deciding the next step - until it emits a final markdown root-cause
+/// report. The conversation history itself is the "scratchpad" the model
+/// keeps referencing earlier findings from; each step is also surfaced to
+/// the UI via `investigation-step` so the user sees the reasoning chain live.
+#[tauri::command]
+pub async fn run_investigation(issue: String, window: tauri::Window) -> Result<String, String> {
+    let mut config = LLMConfig::default();
+    if let Some(config_dir) = dirs::config_dir() {
+        let config_path = config_dir.join("lens-killer").join("llm-config.json");
+        if let Ok(content) = std::fs::read_to_string(config_path) {
+            if let Ok(loaded) = serde_json::from_str::<LLMConfig>(&content) {
+                config = loaded;
+            }
+        }
+    }
+
+    match config.provider {
+        LLMProvider::Ollama | LLMProvider::OpenAI | LLMProvider::Custom | LLMProvider::Groq => {
+            run_investigation_openai(&config, issue, &window).await
+        }
+        LLMProvider::Anthropic => run_investigation_anthropic(&config, issue, &window).await,
+        LLMProvider::Replicate => Err("Investigation agent is not supported for Replicate models".to_string()),
+        LLMProvider::ClaudeCode => Err("Claude Code not supported for direct LLM calls".to_string()),
+    }
+}
+
+async fn run_investigation_openai(config: &LLMConfig, issue: String, window: &tauri::Window) -> Result<String, String> {
+    let is_ollama = is_ollama_config(config);
+    let chat_url = resolve_chat_url(&config.base_url, is_ollama);
+    let tools = Some(investigation_agent_tools());
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut messages = vec![
+        ChatMessage::text("system", INVESTIGATION_SYSTEM_PROMPT.to_string()),
+        ChatMessage::text("user", issue),
+    ];
+
+    for step in 0..MAX_TOOL_STEPS {
+        let body = ChatRequest {
+            model: config.model.clone(),
+            messages: messages.clone(),
+            max_tokens: Some(config.max_tokens),
+            temperature: Some(config.temperature),
+            stream: None,
+            format: None,
+            response_format: None,
+            tools: tools.clone(),
+            tool_choice: None,
+            options: if is_ollama { Some(ollama_options(config)) } else { None },
+        };
+
+        let mut request = client.post(&chat_url).json(&body);
+        if let Some(ref api_key) = config.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let resp = request.send().await.map_err(|e| format!("Request error: {}", e))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("LLM HTTP error {}: {}", status, body));
+        }
+
+        let parsed: ChatResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        let choice = parsed.choices.into_iter().next().ok_or("No response from model")?;
+
+        let calls = choice.message.tool_calls.unwrap_or_default();
+        if calls.is_empty() {
+            return Ok(choice.message.content.unwrap_or_else(|| "No response from model".to_string()));
+        }
+
+        messages.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: choice.message.content,
+            tool_calls: Some(calls.clone()),
+            tool_call_id: None,
+        });
+
+        for call in calls {
+            let args: Value = serde_json::from_str(&call.function.arguments).unwrap_or(Value::Null);
+            let result = execute_investigation_agent_tool(&call.function.name, &args)
+                .await
+                .unwrap_or_else(|e| format!("Error: {}", e));
+
+            let _ = window.emit("investigation-step", InvestigationStepEvent {
+                step,
+                tool: call.function.name.clone(),
+                input: args.to_string(),
+                output: result.clone(),
+            });
+
+            messages.push(ChatMessage {
+                role: "tool".to_string(),
+                content: Some(result),
+                tool_calls: None,
+                tool_call_id: Some(call.id),
+            });
+        }
+    }
+
+    Err(format!("Investigation exceeded {} steps without a final report", MAX_TOOL_STEPS))
+}
+
+async fn run_investigation_anthropic(config: &LLMConfig, issue: String, window: &tauri::Window) -> Result<String, String> {
+    let api_key = config.api_key.as_ref()
+        .ok_or_else(|| "API key is required for Anthropic".to_string())?;
+
+    let anthropic_tools: Vec<AnthropicTool> = investigation_agent_tools()
+        .iter()
+        .map(|t| AnthropicTool {
+            name: t.function.name.clone(),
+            description: t.function.description.clone(),
+            input_schema: t.function.parameters.clone(),
+        })
+        .collect();
+
+    let base_url = config.base_url.trim_end_matches('/');
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut messages = vec![AnthropicMessage { role: "user".to_string(), content: Value::String(issue) }];
+
+    for step in 0..MAX_TOOL_STEPS {
+        let body = AnthropicRequest {
+            model: config.model.clone(),
+            messages: messages.clone(),
+            max_tokens: config.max_tokens,
+            temperature: Some(config.temperature),
+            system: Some(INVESTIGATION_SYSTEM_PROMPT.to_string()),
+            tools: Some(anthropic_tools.clone()),
+            stream: None,
+        };
+
+        let resp = client
+            .post(format!("{}/messages", base_url))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("Request error: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Anthropic API error {}: {}", status, body));
+        }
+
+        let parsed: AnthropicResponse = resp.json().await.map_err(|e| format!("Parse error: {}", e))?;
+        let tool_uses: Vec<&AnthropicContentBlock> = parsed.content.iter().filter(|b| b.kind == "tool_use").collect();
+
+        if tool_uses.is_empty() {
+            let answer = parsed.content.iter().filter_map(|b| b.text.clone()).collect::<Vec<_>>().join("");
+            return Ok(if answer.is_empty() { "No response from model".to_string() } else { answer });
+        }
+
+        let assistant_content = serde_json::to_value(&parsed.content).map_err(|e| e.to_string())?;
+        messages.push(AnthropicMessage { role: "assistant".to_string(), content: assistant_content });
+
+        let mut tool_results = Vec::new();
+        for block in tool_uses {
+            let id = block.id.clone().unwrap_or_default();
+            let name = block.name.clone().unwrap_or_default();
+            let input = block.input.clone().unwrap_or(Value::Null);
+            let result = execute_investigation_agent_tool(&name, &input)
+                .await
+                .unwrap_or_else(|e| format!("Error: {}", e));
+
+            let _ = window.emit("investigation-step", InvestigationStepEvent {
+                step,
+                tool: name.clone(),
+                input: input.to_string(),
+                output: result.clone(),
+            });
+
+            tool_results.push(json!({ "type": "tool_result", "tool_use_id": id, "content": result }));
+        }
+        messages.push(AnthropicMessage { role: "user".to_string(), content: Value::Array(tool_results) });
+    }
+
+    Err(format!("Investigation exceeded {} steps without a final report", MAX_TOOL_STEPS))
+}
@@ -0,0 +1,143 @@
+//! Append-only audit trail for sensitive GitOps/port-forward operations
+//! (admin credential reads, port-forwards, webview opens). Modeled on the
+//! same rotate-on-size strategy as `utils::logging`, but kept as its own
+//! file and format (newline-delimited JSON records, not free-text lines) so
+//! it can be queried structurally via `get_audit_events` instead of grepped.
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use serde::{Deserialize, Serialize};
+
+/// One recorded action. `context`/`namespace`/`target` are free-form because
+/// not every event type has all three (e.g. `webview_open` has no namespace).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp_unix: i64,
+    pub event_type: String,
+    pub context: Option<String>,
+    pub namespace: Option<String>,
+    pub target: Option<String>,
+    pub outcome: String,
+    pub detail: Option<String>,
+}
+
+static AUDIT_LOG_PATH: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+fn get_audit_path() -> &'static Mutex<Option<PathBuf>> {
+    AUDIT_LOG_PATH.get_or_init(|| Mutex::new(None))
+}
+
+fn audit_dir() -> Option<PathBuf> {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()?;
+    let dir = PathBuf::from(home).join(".opspilot").join("audit");
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+fn audit_log_file() -> Option<PathBuf> {
+    let mut guard = get_audit_path().lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(audit_dir()?.join("audit.ndjson"));
+    }
+    guard.clone()
+}
+
+/// Rotate the active audit file aside once it crosses 5MB, same threshold
+/// `utils::logging::rotate_if_needed` uses, keeping the last 5 rotations -
+/// audit trails are append-only by design, so a lost day of history from
+/// over-eager pruning would defeat the point more than a large file would.
+fn rotate_if_needed(path: &PathBuf) {
+    let Ok(metadata) = fs::metadata(path) else { return; };
+    if metadata.len() <= 5 * 1024 * 1024 {
+        return;
+    }
+    let Some(dir) = path.parent() else { return; };
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let rotated = dir.join(format!("audit_{}.ndjson", timestamp));
+    let _ = fs::rename(path, &rotated);
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        let mut rotations: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.file_stem().map_or(false, |s| s.to_string_lossy().starts_with("audit_")))
+            .collect();
+        rotations.sort();
+        if rotations.len() > 5 {
+            for old in rotations.iter().take(rotations.len() - 5) {
+                let _ = fs::remove_file(old);
+            }
+        }
+    }
+}
+
+/// Append one event to the audit trail. Best-effort: a write failure (full
+/// disk, permissions) is logged to the regular app log rather than bubbled
+/// up, since callers shouldn't fail a real operation over an audit-trail
+/// hiccup.
+pub fn record(
+    event_type: &str,
+    context: Option<&str>,
+    namespace: Option<&str>,
+    target: Option<&str>,
+    outcome: &str,
+    detail: Option<&str>,
+) {
+    let Some(path) = audit_log_file() else { return; };
+    rotate_if_needed(&path);
+
+    let event = AuditEvent {
+        timestamp_unix: chrono::Utc::now().timestamp(),
+        event_type: event_type.to_string(),
+        context: context.map(str::to_string),
+        namespace: namespace.map(str::to_string),
+        target: target.map(str::to_string),
+        outcome: outcome.to_string(),
+        detail: detail.map(str::to_string),
+    };
+
+    let Ok(line) = serde_json::to_string(&event) else { return; };
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(mut file) => {
+            let _ = writeln!(file, "{}", line);
+        }
+        Err(e) => {
+            crate::utils::logging::log_to_file("audit", "ERROR", &format!("Failed to write audit event: {}", e));
+        }
+    }
+}
+
+/// Read back recorded events, newest first, optionally filtered by event
+/// type and/or a `[since_unix, until_unix]` window. Only reads the active
+/// file - rotated history is kept on disk for manual review but isn't
+/// surfaced here, matching `get_audit_events`'s "recent trail" framing
+/// rather than a full archive browser.
+#[tauri::command]
+pub fn get_audit_events(
+    event_type: Option<String>,
+    since_unix: Option<i64>,
+    until_unix: Option<i64>,
+) -> Result<Vec<AuditEvent>, String> {
+    let Some(path) = audit_log_file() else {
+        return Ok(Vec::new());
+    };
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = fs::File::open(&path).map_err(|e| format!("Failed to open audit log: {}", e))?;
+    let reader = std::io::BufReader::new(file);
+
+    let mut events: Vec<AuditEvent> = reader
+        .lines()
+        .filter_map(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<AuditEvent>(&line).ok())
+        .filter(|e| event_type.as_deref().map_or(true, |t| e.event_type == t))
+        .filter(|e| since_unix.map_or(true, |since| e.timestamp_unix >= since))
+        .filter(|e| until_unix.map_or(true, |until| e.timestamp_unix <= until))
+        .collect();
+
+    events.reverse();
+    Ok(events)
+}
@@ -0,0 +1,430 @@
+//! Embeddable Rhai automation engine for scripting multi-step remediations
+//! on top of the existing command surface (list/logs/scale/restart/apply,
+//! plus the local LLM). A script is a named `.rhai` file under
+//! `~/.opspilot/automation/` that a user can write once - e.g. "find pods in
+//! CrashLoopBackOff, fetch their last logs, ask the local LLM for a root
+//! cause, then restart if it matches a known pattern" - and re-run or share
+//! like any other file in that directory.
+//!
+//! Every run gets its own `rhai::Engine` with an operation and call-depth
+//! budget (see `MAX_OPERATIONS`/`MAX_CALL_LEVELS`) so a runaway loop in a
+//! user script can't wedge the app, and `print`/`debug` output is forwarded
+//! to the frontend live via the `automation_output` event rather than
+//! buffered until the script finishes.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use kube::{
+    api::{Api, DynamicObject, GroupVersionKind, LogParams, Patch, PatchParams},
+    Client, Discovery,
+};
+use rhai::{Dynamic, Engine, EvalAltResult, Map as RhaiMap, Scope};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::audit;
+use crate::client::create_client;
+use crate::state::AppState;
+
+/// Generous but bounded - enough for a script that loops over a few dozen
+/// pods doing log fetches and LLM calls, not enough to spin forever.
+const MAX_OPERATIONS: u64 = 2_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+
+fn automation_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".opspilot")
+        .join("automation")
+}
+
+fn script_path(name: &str) -> PathBuf {
+    automation_dir().join(format!("{}.rhai", name))
+}
+
+/// One `print`/`debug` line emitted by a running script, pushed to the
+/// frontend as it happens.
+#[derive(Clone, Serialize)]
+struct AutomationOutputEvent {
+    name: String,
+    line: String,
+}
+
+/// Save `script` under `name` in the automation directory so it can be
+/// re-run via `run_automation_script` later, or shared by copying the file.
+#[tauri::command]
+pub async fn save_automation_script(name: String, script: String) -> Result<(), String> {
+    let dir = automation_dir();
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to create automation directory: {}", e))?;
+    tokio::fs::write(script_path(&name), script)
+        .await
+        .map_err(|e| format!("Failed to save automation script '{}': {}", name, e))
+}
+
+/// Names of every saved automation script, newest-write-order unspecified -
+/// callers that care about order should sort.
+#[tauri::command]
+pub async fn list_automation_scripts() -> Result<Vec<String>, String> {
+    let dir = automation_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    let mut entries = tokio::fs::read_dir(&dir)
+        .await
+        .map_err(|e| format!("Failed to list automation scripts: {}", e))?;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| format!("Failed to list automation scripts: {}", e))?
+    {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("rhai") {
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                names.push(stem.to_string());
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Delete a saved automation script by name.
+#[tauri::command]
+pub async fn delete_automation_script(name: String) -> Result<(), String> {
+    let path = script_path(&name);
+    if path.exists() {
+        tokio::fs::remove_file(&path)
+            .await
+            .map_err(|e| format!("Failed to delete automation script '{}': {}", name, e))?;
+    }
+    Ok(())
+}
+
+/// Run the saved script `name` against the currently selected cluster
+/// context. `args` is exposed to the script as a constant `ARGS` map.
+/// Returns the script's final expression, stringified.
+#[tauri::command]
+pub async fn run_automation_script(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+    args: HashMap<String, String>,
+) -> Result<String, String> {
+    let path = script_path(&name);
+    let source = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read automation script '{}': {}", name, e))?;
+
+    let client = create_client(state.clone()).await?;
+    let context_name = state
+        .selected_context
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| "default".to_string());
+
+    audit::record(
+        "automation_run",
+        Some(&context_name),
+        None,
+        Some(&name),
+        "started",
+        None,
+    );
+
+    let rt = tokio::runtime::Handle::current();
+    let script_name = name.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        run_script_blocking(app, rt, client, &script_name, &source, args)
+    })
+    .await
+    .map_err(|e| format!("Automation script '{}' panicked: {}", name, e))?;
+
+    audit::record(
+        "automation_run",
+        Some(&context_name),
+        None,
+        Some(&name),
+        if result.is_ok() { "success" } else { "failed" },
+        result.as_ref().err().map(String::as_str),
+    );
+
+    result
+}
+
+/// Build the engine, register the Kubernetes/LLM bindings, and evaluate
+/// `source` to completion. Runs on a blocking thread because `rhai::Engine`
+/// is synchronous end-to-end; the registered functions hop back onto `rt`
+/// (the caller's tokio runtime) for every async operation they perform.
+fn run_script_blocking(
+    app: AppHandle,
+    rt: tokio::runtime::Handle,
+    client: Client,
+    name: &str,
+    source: &str,
+    args: HashMap<String, String>,
+) -> Result<String, String> {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_expr_depths(64, 64);
+
+    let print_app = app.clone();
+    let print_name = name.to_string();
+    engine.on_print(move |line| {
+        let _ = print_app.emit(
+            "automation_output",
+            AutomationOutputEvent {
+                name: print_name.clone(),
+                line: line.to_string(),
+            },
+        );
+    });
+
+    let debug_app = app.clone();
+    let debug_name = name.to_string();
+    engine.on_debug(move |line, _src, pos| {
+        let _ = debug_app.emit(
+            "automation_output",
+            AutomationOutputEvent {
+                name: debug_name.clone(),
+                line: format!("[{}] {}", pos, line),
+            },
+        );
+    });
+
+    register_k8s_api(&mut engine, rt, client);
+
+    let mut scope = Scope::new();
+    let args_map: RhaiMap = args
+        .into_iter()
+        .map(|(k, v)| (k.into(), Dynamic::from(v)))
+        .collect();
+    scope.push_constant("ARGS", args_map);
+
+    engine
+        .eval_with_scope::<Dynamic>(&mut scope, source)
+        .map(|v| v.to_string())
+        .map_err(|e: Box<EvalAltResult>| format!("Automation script '{}' failed: {}", name, e))
+}
+
+/// Register the curated set of Rhai functions scripts are allowed to call -
+/// deliberately a small, named surface (not "run arbitrary kube-rs code")
+/// so a script's capabilities stay auditable at a glance.
+fn register_k8s_api(engine: &mut Engine, rt: tokio::runtime::Handle, client: Client) {
+    let c = client.clone();
+    let h = rt.clone();
+    engine.register_fn(
+        "list_resources",
+        move |kind: &str, namespace: &str| -> Result<rhai::Array, Box<EvalAltResult>> {
+            h.block_on(list_resources_impl(&c, kind, namespace))
+                .map_err(rhai_err)
+        },
+    );
+
+    let c = client.clone();
+    let h = rt.clone();
+    engine.register_fn(
+        "get_pod_logs",
+        move |namespace: &str, name: &str| -> Result<String, Box<EvalAltResult>> {
+            h.block_on(get_pod_logs_impl(&c, namespace, name, 50))
+                .map_err(rhai_err)
+        },
+    );
+
+    let c = client.clone();
+    let h = rt.clone();
+    engine.register_fn(
+        "get_pod_logs",
+        move |namespace: &str, name: &str, tail_lines: i64| -> Result<String, Box<EvalAltResult>> {
+            h.block_on(get_pod_logs_impl(&c, namespace, name, tail_lines))
+                .map_err(rhai_err)
+        },
+    );
+
+    let c = client.clone();
+    let h = rt.clone();
+    engine.register_fn(
+        "scale_resource",
+        move |namespace: &str, kind: &str, name: &str, replicas: i64| -> Result<String, Box<EvalAltResult>> {
+            h.block_on(scale_resource_impl(&c, namespace, kind, name, replicas as i32))
+                .map_err(rhai_err)
+        },
+    );
+
+    let c = client.clone();
+    let h = rt.clone();
+    engine.register_fn(
+        "restart_resource",
+        move |namespace: &str, kind: &str, name: &str| -> Result<String, Box<EvalAltResult>> {
+            h.block_on(restart_resource_impl(&c, namespace, kind, name))
+                .map_err(rhai_err)
+        },
+    );
+
+    let c = client.clone();
+    let h = rt.clone();
+    engine.register_fn(
+        "apply_yaml",
+        move |namespace: &str, kind: &str, name: &str, yaml_content: &str| -> Result<String, Box<EvalAltResult>> {
+            h.block_on(apply_yaml_impl(&c, namespace, kind, name, yaml_content))
+                .map_err(rhai_err)
+        },
+    );
+
+    let h = rt;
+    engine.register_fn(
+        "call_local_llm",
+        move |prompt: &str| -> Result<String, Box<EvalAltResult>> {
+            let prompt = prompt.to_string();
+            h.block_on(crate::ai_local::call_local_llm(prompt, None))
+                .map_err(rhai_err)
+        },
+    );
+}
+
+fn rhai_err(message: String) -> Box<EvalAltResult> {
+    message.into()
+}
+
+async fn resolve_api(
+    client: &Client,
+    kind: &str,
+    namespace: &str,
+) -> Result<Api<DynamicObject>, String> {
+    let (group, version) = match kind {
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" => ("apps", "v1"),
+        "Pod" | "Service" | "ConfigMap" | "Secret" | "Namespace" | "PersistentVolumeClaim" => {
+            ("", "v1")
+        }
+        "Job" | "CronJob" => ("batch", if kind == "Job" { "v1" } else { "v1" }),
+        _ => ("", "v1"),
+    };
+
+    let gvk = GroupVersionKind::gvk(group, version, kind);
+    let discovery = Discovery::new(client.clone())
+        .run()
+        .await
+        .map_err(|e| e.to_string())?;
+    let (ar, _caps) = discovery
+        .resolve_gvk(&gvk)
+        .ok_or_else(|| format!("Resource kind not found: {}", kind))?;
+
+    Ok(if namespace.is_empty() {
+        Api::all_with(client.clone(), &ar)
+    } else {
+        Api::namespaced_with(client.clone(), namespace, &ar)
+    })
+}
+
+async fn list_resources_impl(
+    client: &Client,
+    kind: &str,
+    namespace: &str,
+) -> Result<rhai::Array, String> {
+    let api = resolve_api(client, kind, namespace).await?;
+    let list = api
+        .list(&kube::api::ListParams::default())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(list
+        .into_iter()
+        .map(|obj| {
+            let mut entry = RhaiMap::new();
+            entry.insert(
+                "name".into(),
+                Dynamic::from(obj.metadata.name.clone().unwrap_or_default()),
+            );
+            entry.insert(
+                "namespace".into(),
+                Dynamic::from(obj.metadata.namespace.clone().unwrap_or_default()),
+            );
+            let status = obj
+                .data
+                .get("status")
+                .and_then(|s| s.get("phase"))
+                .and_then(|p| p.as_str())
+                .unwrap_or("")
+                .to_string();
+            entry.insert("status".into(), Dynamic::from(status));
+            Dynamic::from(entry)
+        })
+        .collect())
+}
+
+async fn get_pod_logs_impl(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+    tail_lines: i64,
+) -> Result<String, String> {
+    let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client.clone(), namespace);
+    let lp = LogParams {
+        tail_lines: Some(tail_lines),
+        ..LogParams::default()
+    };
+    pods.logs(name, &lp).await.map_err(|e| e.to_string())
+}
+
+async fn scale_resource_impl(
+    client: &Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+    replicas: i32,
+) -> Result<String, String> {
+    let api = resolve_api(client, kind, namespace).await?;
+    let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+    api.patch(name, &PatchParams::apply("opspilot-automation"), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!("Scaled {}/{} to {} replicas", namespace, name, replicas))
+}
+
+async fn restart_resource_impl(
+    client: &Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+) -> Result<String, String> {
+    let api = resolve_api(client, kind, namespace).await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let patch = serde_json::json!({
+        "spec": {
+            "template": {
+                "metadata": {
+                    "annotations": { "kubectl.kubernetes.io/restartedAt": now }
+                }
+            }
+        }
+    });
+    api.patch(name, &PatchParams::apply("opspilot-automation"), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!("Restart initiated for {}/{}", namespace, name))
+}
+
+async fn apply_yaml_impl(
+    client: &Client,
+    namespace: &str,
+    kind: &str,
+    name: &str,
+    yaml_content: &str,
+) -> Result<String, String> {
+    let value: serde_json::Value =
+        serde_yaml::from_str(yaml_content).map_err(|e| format!("Invalid YAML: {}", e))?;
+    let api = resolve_api(client, kind, namespace).await?;
+    api.patch(
+        name,
+        &PatchParams::apply("opspilot-automation").force(),
+        &Patch::Apply(&value),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(format!("Applied {}/{}", namespace, name))
+}
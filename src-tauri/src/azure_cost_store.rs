@@ -0,0 +1,138 @@
+//! Embedded on-disk history for actual Azure billing data pulled from the
+//! Cost Management query API, distinct from [`crate::cost_store`]'s
+//! namespace-level `PricingProvider` estimates - this tracks real spend per
+//! AKS cluster resource, one row per `(resource_id, timestamp)` pair, with
+//! the same "lazy-open connection behind a `Mutex<Option<..>>`" idiom as
+//! `cost_store`/`metrics_store`.
+
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// How long a cost row is kept before `prune_older_than` removes it, absent
+/// an explicit override - a year of daily rows per cluster is still a small
+/// database, but nothing requires the default be kept forever.
+pub const DEFAULT_RETENTION_DAYS: i64 = 365;
+
+fn db_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".opspilot").join("azure_cost_history.db"))
+}
+
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().map_err(|e| format!("Azure cost history store lock poisoned: {}", e))?;
+
+    if guard.is_none() {
+        let path = db_path().ok_or("Could not determine home directory for Azure cost history store")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create Azure cost history store directory: {}", e))?;
+        }
+        let conn = Connection::open(&path).map_err(|e| format!("Failed to open Azure cost history store: {}", e))?;
+        init_schema(&conn).map_err(|e| format!("Failed to initialize Azure cost history store schema: {}", e))?;
+        *guard = Some(conn);
+    }
+
+    f(guard.as_ref().unwrap()).map_err(|e| format!("Azure cost history store query failed: {}", e))
+}
+
+fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS aks_cost_rows (
+            resource_id TEXT NOT NULL,
+            resource_group TEXT NOT NULL,
+            timestamp TEXT NOT NULL,
+            cost REAL NOT NULL,
+            currency TEXT NOT NULL,
+            meter_category TEXT NOT NULL,
+            PRIMARY KEY (resource_id, resource_group, timestamp, meter_category)
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// One polled cost observation, ready to persist. `timestamp` must be
+/// RFC3339 so plain string comparison against other RFC3339 timestamps
+/// (as `get_cost_history`/`prune_older_than` do) sorts and filters
+/// correctly - callers must not hand this the API's raw `YYYYMMDD` usage
+/// date as-is.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AksCostRow {
+    pub resource_id: String,
+    /// Kept distinct from `resource_id` in the row key - a poller scoped to
+    /// a whole subscription gets back one row per resource group per meter,
+    /// and without this a second resource group's cost would silently
+    /// overwrite the first's under the same `(resource_id, timestamp,
+    /// meter_category)` key.
+    pub resource_group: String,
+    pub timestamp: String,
+    pub cost: f64,
+    pub currency: String,
+    pub meter_category: String,
+}
+
+/// Insert `rows`, deduping on `(resource_id, resource_group, timestamp,
+/// meter_category)` - re-polling the same interval before it rolls out of
+/// the API's reporting window just overwrites the existing row rather than
+/// creating a duplicate.
+pub fn record_costs(rows: &[AksCostRow]) -> Result<(), String> {
+    with_connection(|conn| {
+        for row in rows {
+            conn.execute(
+                "INSERT OR REPLACE INTO aks_cost_rows (resource_id, resource_group, timestamp, cost, currency, meter_category)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![row.resource_id, row.resource_group, row.timestamp, row.cost, row.currency, row.meter_category],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// A cost row reduced to what the history chart needs.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CostHistoryPoint {
+    pub resource_group: String,
+    pub timestamp: String,
+    pub cost: f64,
+    pub currency: String,
+    pub meter_category: String,
+}
+
+/// The last `days` of cached cost rows for `resource_id`, oldest first, so
+/// the UI can chart spend trends without re-querying Azure.
+pub fn get_cost_history(resource_id: &str, days: i64) -> Result<Vec<CostHistoryPoint>, String> {
+    let since = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT resource_group, timestamp, cost, currency, meter_category FROM aks_cost_rows
+             WHERE resource_id = ?1 AND timestamp >= ?2
+             ORDER BY timestamp ASC",
+        )?;
+        let rows = stmt.query_map(params![resource_id, since], |row| {
+            Ok(CostHistoryPoint {
+                resource_group: row.get(0)?,
+                timestamp: row.get(1)?,
+                cost: row.get(2)?,
+                currency: row.get(3)?,
+                meter_category: row.get(4)?,
+            })
+        })?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    })
+}
+
+/// Drop rows older than `retention_days` so the database doesn't grow
+/// unbounded as pollers accumulate history indefinitely.
+pub fn prune_older_than(retention_days: i64) -> Result<(), String> {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(retention_days)).to_rfc3339();
+    with_connection(|conn| {
+        conn.execute("DELETE FROM aks_cost_rows WHERE timestamp < ?1", params![cutoff])?;
+        Ok(())
+    })
+}
@@ -0,0 +1,77 @@
+//! Companion CLI for `control_socket`: a thin client that reads the token
+//! written alongside the running app's control socket/pipe, sends one
+//! line-delimited JSON request, prints the JSON response, and exits. Lets
+//! shell automation/CI drive ArgoCD tunnels without the GUI window ever
+//! needing focus.
+
+use std::io::{BufRead, BufReader, Write};
+
+fn usage() -> ! {
+    eprintln!("usage: opspilotctl <start-forward argocd|stop-forward|status|open-webview>");
+    std::process::exit(2);
+}
+
+fn control_dir() -> std::path::PathBuf {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"))
+        .expect("HOME/USERPROFILE not set");
+    std::path::PathBuf::from(home).join(".opspilot")
+}
+
+fn read_token() -> String {
+    let path = control_dir().join("control.token");
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read control token at {}: {} (is opspilot running?)", path.display(), e))
+        .trim()
+        .to_string()
+}
+
+#[cfg(unix)]
+fn send_request(request: &str) -> String {
+    use std::os::unix::net::UnixStream;
+
+    let path = control_dir().join("control.sock");
+    let mut stream = UnixStream::connect(&path)
+        .unwrap_or_else(|e| panic!("Failed to connect to {}: {} (is opspilot running?)", path.display(), e));
+    stream.write_all(request.as_bytes()).expect("Failed to write request");
+
+    let mut reader = BufReader::new(stream);
+    let mut response = String::new();
+    reader.read_line(&mut response).expect("Failed to read response");
+    response
+}
+
+#[cfg(windows)]
+fn send_request(request: &str) -> String {
+    // Named pipes are opened as plain files on Windows.
+    let pipe_path = r"\\.\pipe\opspilot-control";
+    let mut stream = std::fs::OpenOptions::new().read(true).write(true).open(pipe_path)
+        .unwrap_or_else(|e| panic!("Failed to connect to {}: {} (is opspilot running?)", pipe_path, e));
+    stream.write_all(request.as_bytes()).expect("Failed to write request");
+
+    let mut reader = BufReader::new(stream);
+    let mut response = String::new();
+    reader.read_line(&mut response).expect("Failed to read response");
+    response
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        usage();
+    }
+
+    let command = args[0].clone();
+    let command_args = args[1..].to_vec();
+    let token = read_token();
+
+    let request = serde_json::json!({
+        "token": token,
+        "command": command,
+        "args": command_args,
+    });
+    let mut line = request.to_string();
+    line.push('\n');
+
+    let response = send_request(&line);
+    print!("{}", response);
+}
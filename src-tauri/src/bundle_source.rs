@@ -0,0 +1,214 @@
+//! Uniform read access over a support bundle, whether it lives as an
+//! extracted directory tree or inside a `.tar`/`.tar.gz`/`.tgz` archive.
+//!
+//! `load_support_bundle` already extracts archives up front for the bulk
+//! resource index, so this abstraction is aimed at the commands that resolve
+//! a single file by path (`read_bundle_log`, `read_bundle_resource_yaml`) and
+//! simple directory listings (`list_bundle_logs`) - the places where paying
+//! for a full extraction just to read one member is wasteful on large
+//! bundles.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct BundleEntry {
+    pub name: String,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+pub trait BundleSource {
+    fn read_dir(&self, rel_path: &str) -> Result<Vec<BundleEntry>, String>;
+    fn read_to_string(&self, rel_path: &str) -> Result<String, String>;
+    fn metadata(&self, rel_path: &str) -> Result<BundleEntry, String>;
+    /// Read up to `len` bytes starting at `offset` into the member, without
+    /// materializing the rest of it. Used for tailing/paging large logs.
+    fn read_range(&self, rel_path: &str, offset: u64, len: u64) -> Result<Vec<u8>, String>;
+}
+
+/// Backend for a bundle that's already an extracted directory tree.
+pub struct DirSource {
+    root: PathBuf,
+}
+
+impl DirSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl BundleSource for DirSource {
+    fn read_dir(&self, rel_path: &str) -> Result<Vec<BundleEntry>, String> {
+        let dir = self.root.join(rel_path);
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&dir).map_err(|e| format!("Failed to read dir {}: {}", dir.display(), e))? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let meta = entry.metadata().map_err(|e| e.to_string())?;
+            entries.push(BundleEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                is_dir: meta.is_dir(),
+                size: meta.len(),
+            });
+        }
+        Ok(entries)
+    }
+
+    fn read_to_string(&self, rel_path: &str) -> Result<String, String> {
+        fs::read_to_string(self.root.join(rel_path))
+            .map_err(|e| format!("Failed to read {}: {}", rel_path, e))
+    }
+
+    fn metadata(&self, rel_path: &str) -> Result<BundleEntry, String> {
+        let path = self.root.join(rel_path);
+        let meta = fs::metadata(&path).map_err(|e| format!("Failed to stat {}: {}", rel_path, e))?;
+        Ok(BundleEntry {
+            name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            is_dir: meta.is_dir(),
+            size: meta.len(),
+        })
+    }
+
+    fn read_range(&self, rel_path: &str, offset: u64, len: u64) -> Result<Vec<u8>, String> {
+        let path = self.root.join(rel_path);
+        let mut file = fs::File::open(&path).map_err(|e| format!("Failed to open {}: {}", rel_path, e))?;
+        let total = file.metadata().map_err(|e| e.to_string())?.len();
+        let read_len = len.min(total.saturating_sub(offset));
+        file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+        let mut buf = vec![0u8; read_len as usize];
+        file.read_exact(&mut buf).map_err(|e| format!("Failed to read {}: {}", rel_path, e))?;
+        Ok(buf)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TarEntryLocation {
+    offset: u64,
+    size: u64,
+    is_dir: bool,
+}
+
+/// Backend for a bundle that's still a tar archive. Builds an index of each
+/// member's offset/size into a seekable plain-tar file on open (one pass
+/// over the headers), so later reads seek straight to the member instead of
+/// re-scanning the archive.
+pub struct TarSource {
+    // Seekable plain-tar backing file: the archive itself for `.tar`, or a
+    // one-time fully-decompressed copy for `.tar.gz`/`.tgz` (gzip streams
+    // aren't cheaply seekable, so decompression happens once here rather
+    // than once per read).
+    backing_file: PathBuf,
+    // Keeps the decompressed copy alive for as long as this source is; unused
+    // (and `None`) for plain `.tar` archives.
+    _decompressed_dir: Option<tempfile::TempDir>,
+    index: HashMap<String, TarEntryLocation>,
+}
+
+impl TarSource {
+    pub fn open(archive_path: &Path) -> Result<Self, String> {
+        let lower = archive_path.to_string_lossy().to_lowercase();
+        let (backing_file, decompressed_dir) = if lower.ends_with(".tar") {
+            (archive_path.to_path_buf(), None)
+        } else {
+            let dir = tempfile::tempdir().map_err(|e| format!("Failed to create temp dir: {}", e))?;
+            let raw_path = dir.path().join("bundle.tar");
+            let input = fs::File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+            let mut decoder = flate2::read::GzDecoder::new(input);
+            let mut out = fs::File::create(&raw_path).map_err(|e| format!("Failed to create temp tar: {}", e))?;
+            std::io::copy(&mut decoder, &mut out).map_err(|e| format!("Failed to decompress archive: {}", e))?;
+            (raw_path, Some(dir))
+        };
+
+        let mut index = HashMap::new();
+        let file = fs::File::open(&backing_file).map_err(|e| format!("Failed to open tar: {}", e))?;
+        let mut archive = tar::Archive::new(file);
+        let entries = archive.entries().map_err(|e| format!("Failed to read tar entries: {}", e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read tar entry: {}", e))?;
+            let path = entry.path().map_err(|e| e.to_string())?.to_string_lossy().to_string();
+            let location = TarEntryLocation {
+                offset: entry.raw_file_position(),
+                size: entry.header().size().unwrap_or(0),
+                is_dir: entry.header().entry_type().is_dir(),
+            };
+            index.insert(path.trim_end_matches('/').to_string(), location);
+        }
+
+        Ok(Self { backing_file, _decompressed_dir: decompressed_dir, index })
+    }
+
+    fn read_bytes(&self, rel_path: &str) -> Result<Vec<u8>, String> {
+        let location = self.index.get(rel_path)
+            .ok_or_else(|| format!("{} not found in bundle archive", rel_path))?;
+        self.read_bytes_range(*location, 0, location.size)
+    }
+
+    fn read_bytes_range(&self, location: TarEntryLocation, offset: u64, len: u64) -> Result<Vec<u8>, String> {
+        let read_len = len.min(location.size.saturating_sub(offset));
+        let mut file = fs::File::open(&self.backing_file).map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start(location.offset + offset)).map_err(|e| e.to_string())?;
+        let mut buf = vec![0u8; read_len as usize];
+        file.read_exact(&mut buf).map_err(|e| format!("Failed to read: {}", e))?;
+        Ok(buf)
+    }
+}
+
+impl BundleSource for TarSource {
+    fn read_dir(&self, rel_path: &str) -> Result<Vec<BundleEntry>, String> {
+        let prefix = rel_path.trim_matches('/');
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for (path, location) in &self.index {
+            let rest = if prefix.is_empty() {
+                path.as_str()
+            } else if let Some(r) = path.strip_prefix(prefix).and_then(|r| r.strip_prefix('/')) {
+                r
+            } else {
+                continue;
+            };
+            if rest.is_empty() {
+                continue;
+            }
+            let name = rest.split('/').next().unwrap_or(rest).to_string();
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let is_dir = rest.contains('/') || location.is_dir;
+            entries.push(BundleEntry { name, is_dir, size: if is_dir { 0 } else { location.size } });
+        }
+        Ok(entries)
+    }
+
+    fn read_to_string(&self, rel_path: &str) -> Result<String, String> {
+        let bytes = self.read_bytes(rel_path.trim_matches('/'))?;
+        String::from_utf8(bytes).map_err(|e| format!("{} is not valid UTF-8: {}", rel_path, e))
+    }
+
+    fn metadata(&self, rel_path: &str) -> Result<BundleEntry, String> {
+        let key = rel_path.trim_matches('/');
+        let location = self.index.get(key)
+            .ok_or_else(|| format!("{} not found in bundle archive", rel_path))?;
+        let name = key.rsplit('/').next().unwrap_or(key).to_string();
+        Ok(BundleEntry { name, is_dir: location.is_dir, size: location.size })
+    }
+
+    fn read_range(&self, rel_path: &str, offset: u64, len: u64) -> Result<Vec<u8>, String> {
+        let key = rel_path.trim_matches('/');
+        let location = *self.index.get(key)
+            .ok_or_else(|| format!("{} not found in bundle archive", rel_path))?;
+        self.read_bytes_range(location, offset, len)
+    }
+}
+
+/// Open whichever backend matches `bundle_path`: a tar backend for
+/// `.tar`/`.tar.gz`/`.tgz` files, a directory backend otherwise.
+pub fn open_bundle_source(bundle_path: &str) -> Result<Box<dyn BundleSource>, String> {
+    let lower = bundle_path.to_lowercase();
+    if lower.ends_with(".tar") || lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        Ok(Box::new(TarSource::open(Path::new(bundle_path))?))
+    } else {
+        Ok(Box::new(DirSource::new(bundle_path)))
+    }
+}
@@ -1,8 +1,9 @@
 
 use tauri::State;
 use kube::{Client, config::{KubeConfigOptions, Kubeconfig}};
+use crate::internal_metrics;
 use crate::state::AppState;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // Helper to create a client based on current state - uses caching for performance
 pub async fn create_client(state: State<'_, AppState>) -> Result<Client, String> {
@@ -39,18 +40,44 @@ pub async fn create_client(state: State<'_, AppState>) -> Result<Client, String>
     let cache_key = format!("{}:{}", path.as_deref().unwrap_or("default"), context.as_deref().unwrap_or("default"));
     println!("DEBUG: create_client cache key: {}", cache_key);
 
+    let metrics_context = context.as_deref().unwrap_or("default");
+
     // Check if we have a cached client (2 minute TTL)
     {
         if let Ok(cache) = state.client_cache.try_lock() {
             if let Some((created_at, key, client)) = cache.as_ref() {
                 if key == &cache_key && created_at.elapsed() < Duration::from_secs(120) {
+                    internal_metrics::CLIENT_CACHE_HITS.inc(metrics_context);
                     return Ok(client.clone());
                 }
             }
         }
     }
+    internal_metrics::CLIENT_CACHE_MISSES.inc(metrics_context);
+
+    let client = build_client(path.as_deref(), context.as_deref()).await?;
+
+    // Cache the client for reuse
+    if let Ok(mut cache) = state.client_cache.try_lock() {
+        *cache = Some((std::time::Instant::now(), cache_key, client.clone()));
+    }
+
+    Ok(client)
+}
 
-    let kubeconfig = if let Some(p) = &path {
+/// Build a client for an explicit (kubeconfig path, context) pair, bypassing
+/// `AppState.client_cache` entirely. Used where the current command isn't
+/// necessarily operating against the user's currently-selected context, e.g.
+/// the nav-structure sweep iterating every context in the kubeconfig.
+pub async fn build_client(path: Option<&str>, context: Option<&str>) -> Result<Client, String> {
+    let started = Instant::now();
+    let result = build_client_inner(path, context).await;
+    internal_metrics::CLIENT_BUILD_DURATION.record(context.unwrap_or("default"), started.elapsed());
+    result
+}
+
+async fn build_client_inner(path: Option<&str>, context: Option<&str>) -> Result<Client, String> {
+    let kubeconfig = if let Some(p) = path {
         Kubeconfig::read_from(p).map_err(|e| format!("Failed to read kubeconfig from {}: {}", p, e))?
     } else {
         Kubeconfig::read().map_err(|e| format!("Failed to read default kubeconfig: {}", e))?
@@ -59,7 +86,7 @@ pub async fn create_client(state: State<'_, AppState>) -> Result<Client, String>
     let mut config = kube::Config::from_custom_kubeconfig(
         kubeconfig,
         &KubeConfigOptions {
-            context: context.clone(),
+            context: context.map(|c| c.to_string()),
             ..Default::default()
         }
     ).await.map_err(|e| format!("Failed to create config for context {:?}: {}", context, e))?;
@@ -69,21 +96,13 @@ pub async fn create_client(state: State<'_, AppState>) -> Result<Client, String>
     config.read_timeout = Some(Duration::from_secs(30));
     config.write_timeout = Some(Duration::from_secs(30));
 
-
     // For vcluster contexts (local proxy), we may need to accept self-signed certs
     // vcluster creates contexts with names like "vcluster_<name>_<ns>_<host>"
-    let is_vcluster = context.as_ref().map(|c| c.starts_with("vcluster_")).unwrap_or(false);
+    let is_vcluster = context.map(|c| c.starts_with("vcluster_")).unwrap_or(false);
     if is_vcluster {
         // vcluster proxy uses localhost with self-signed certs
         config.accept_invalid_certs = true;
     }
 
-    let client = Client::try_from(config).map_err(|e| format!("Failed to create Kubernetes client: {}", e))?;
-
-    // Cache the client for reuse
-    if let Ok(mut cache) = state.client_cache.try_lock() {
-        *cache = Some((std::time::Instant::now(), cache_key, client.clone()));
-    }
-
-    Ok(client)
+    Client::try_from(config).map_err(|e| format!("Failed to create Kubernetes client: {}", e))
 }
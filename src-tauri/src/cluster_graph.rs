@@ -0,0 +1,110 @@
+//! Adjacency graph over cluster resources, built in the same pass as
+//! `InitialClusterData` so topology views and blast-radius analysis ("what
+//! breaks if this node goes down?") don't need to re-fetch or re-derive
+//! relationships the flat `Vec<ResourceSummary>` lists can't express.
+//!
+//! Three relations are recorded as each resource is converted:
+//! pod -> node (`spec.nodeName`), pod -> owning workload (owner references,
+//! resolving ReplicaSet ancestry back to the owning Deployment), and
+//! service -> pod (matching `spec.selector` against pod labels).
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
+pub struct ResourceId {
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+}
+
+impl ResourceId {
+    pub fn new(kind: &str, namespace: &str, name: &str) -> Self {
+        Self { kind: kind.to_string(), namespace: namespace.to_string(), name: name.to_string() }
+    }
+
+    fn key(&self) -> String {
+        format!("{}/{}/{}", self.kind, self.namespace, self.name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeRelation {
+    RunsOn,
+    OwnedBy,
+    Selects,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Edge {
+    pub to: ResourceId,
+    pub relation: EdgeRelation,
+}
+
+/// `FxHashMap`-style adjacency structure keyed by resource identity.
+/// `std::collections::HashMap` rather than a third-party hasher, matching
+/// every other map in this codebase.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ClusterGraph {
+    edges: HashMap<String, (ResourceId, Vec<Edge>)>,
+}
+
+impl ClusterGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_edge(&mut self, from: ResourceId, to: ResourceId, relation: EdgeRelation) {
+        let key = from.key();
+        let entry = self.edges.entry(key).or_insert_with(|| (from, Vec::new()));
+        entry.1.push(Edge { to, relation });
+    }
+
+    pub fn record_pod_node(&mut self, pod: ResourceId, node: ResourceId) {
+        self.add_edge(pod, node, EdgeRelation::RunsOn);
+    }
+
+    pub fn record_pod_owner(&mut self, pod: ResourceId, owner: ResourceId) {
+        self.add_edge(pod, owner, EdgeRelation::OwnedBy);
+    }
+
+    pub fn record_service_pod(&mut self, service: ResourceId, pod: ResourceId) {
+        self.add_edge(service, pod, EdgeRelation::Selects);
+    }
+
+    /// Every pod whose `RunsOn` edge points at `node`.
+    pub fn pods_on_node(&self, node: &ResourceId) -> Vec<ResourceId> {
+        let node_key = node.key();
+        self.edges
+            .values()
+            .filter(|(from, edges)| {
+                from.kind == "Pod" && edges.iter().any(|e| e.relation == EdgeRelation::RunsOn && e.to.key() == node_key)
+            })
+            .map(|(from, _)| from.clone())
+            .collect()
+    }
+
+    /// Every pod the given service selects.
+    pub fn pods_behind_service(&self, service: &ResourceId) -> Vec<ResourceId> {
+        self.edges
+            .get(&service.key())
+            .map(|(_, edges)| edges.iter().filter(|e| e.relation == EdgeRelation::Selects).map(|e| e.to.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// The workload (Deployment, DaemonSet, StatefulSet, ...) that owns this
+    /// pod, if any.
+    pub fn workload_for_pod(&self, pod: &ResourceId) -> Option<ResourceId> {
+        let (_, edges) = self.edges.get(&pod.key())?;
+        edges.iter().find(|e| e.relation == EdgeRelation::OwnedBy).map(|e| e.to.clone())
+    }
+}
+
+/// ReplicaSet names are `<deployment-name>-<pod-template-hash>`; strip the
+/// trailing hash segment to recover the owning Deployment's name. This is
+/// the same heuristic `kubectl` itself relies on since ReplicaSets don't
+/// otherwise record their Deployment by name, only by owner reference uid.
+pub fn deployment_name_from_replicaset(replicaset_name: &str) -> Option<String> {
+    replicaset_name.rsplit_once('-').map(|(prefix, _)| prefix.to_string())
+}
@@ -13,6 +13,9 @@ use std::path::PathBuf;
 use std::env;
 use tokio::fs;
 use keyring::Entry;
+use tauri::State;
+use std::time::Duration;
+use crate::state::{AppState, ConnectionTimeouts};
 
 // ... imports remain ...
 
@@ -20,7 +23,7 @@ use keyring::Entry;
 // SECURE STORAGE HELPERS
 // =============================================================================
 
-fn get_secret(key: &str) -> Option<String> {
+pub(crate) fn get_secret(key: &str) -> Option<String> {
     match Entry::new("opspilot", key) {
         Ok(entry) => {
             match entry.get_password() {
@@ -41,7 +44,7 @@ fn get_secret(key: &str) -> Option<String> {
     }
 }
 
-fn set_secret(key: &str, value: &str) -> std::io::Result<()> {
+pub(crate) fn set_secret(key: &str, value: &str) -> std::io::Result<()> {
     let entry = Entry::new("opspilot", key)
         .map_err(|e| {
             println!("[secrets] Failed to create keychain entry for store '{}': {:?}", key, e);
@@ -57,7 +60,7 @@ fn set_secret(key: &str, value: &str) -> std::io::Result<()> {
         })
 }
 
-fn delete_secret(key: &str) -> std::io::Result<()> {
+pub(crate) fn delete_secret(key: &str) -> std::io::Result<()> {
     let entry = Entry::new("opspilot", key)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
     match entry.delete_credential() {
@@ -103,12 +106,128 @@ pub async fn get_workspace_dir() -> Result<String, String> {
 
 // ... existing code ...
 
+/// A single field-level problem found while validating a config file against
+/// the known `OpsPilotConfig`/`LLMConfig` shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigFieldError {
+    pub path: String,
+    pub issue: String,
+}
+
+/// Diagnostics produced by the most recent `load_opspilot_config` call, so the
+/// settings UI can surface "this file has a typo'd key" style warnings
+/// alongside the (defaulted-where-needed) config that was actually loaded.
+static LAST_CONFIG_DIAGNOSTICS: std::sync::Mutex<Vec<ConfigFieldError>> = std::sync::Mutex::new(Vec::new());
+
+/// Compare a raw JSON object against the known `OpsPilotConfig` fields,
+/// flagging unknown keys and keys whose value isn't a string (every field on
+/// `OpsPilotConfig` today is `Option<String>`).
+fn diagnose_opspilot_config(content: &str) -> Vec<ConfigFieldError> {
+    const KNOWN_FIELDS: &[&str] = &[
+        "agentServerUrl",
+        "claudeCliPath",
+        "embeddingEndpoint",
+        "embeddingModel",
+        "githubToken",
+        "kubeconfig",
+        "theme",
+    ];
+
+    let mut issues = Vec::new();
+
+    let value: serde_json::Value = match serde_json::from_str(content) {
+        Ok(v) => v,
+        Err(e) => {
+            issues.push(ConfigFieldError {
+                path: "$".to_string(),
+                issue: format!("invalid JSON: {}", e),
+            });
+            return issues;
+        }
+    };
+
+    let Some(obj) = value.as_object() else {
+        issues.push(ConfigFieldError {
+            path: "$".to_string(),
+            issue: "expected a JSON object".to_string(),
+        });
+        return issues;
+    };
+
+    for (key, val) in obj {
+        if !KNOWN_FIELDS.contains(&key.as_str()) {
+            issues.push(ConfigFieldError {
+                path: key.clone(),
+                issue: "unknown key".to_string(),
+            });
+            continue;
+        }
+        if !val.is_null() && !val.is_string() {
+            issues.push(ConfigFieldError {
+                path: key.clone(),
+                issue: format!("expected a string, found {}", value_type_name(val)),
+            });
+        }
+    }
+
+    issues
+}
+
+fn value_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+/// Validate a config file on disk and return structured per-field errors
+/// (unknown key, wrong type, ...) instead of discarding it silently.
+#[tauri::command]
+pub async fn validate_opspilot_config(path: String) -> Result<Vec<ConfigFieldError>, String> {
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(diagnose_opspilot_config(&content))
+}
+
+/// Return the diagnostics produced by the most recent `load_opspilot_config`
+/// call, so the settings UI can show warnings for a partially-bad file even
+/// though `load_opspilot_config` itself always returns a usable config.
+#[tauri::command]
+pub async fn get_config_diagnostics() -> Result<Vec<ConfigFieldError>, String> {
+    Ok(LAST_CONFIG_DIAGNOSTICS.lock().map(|d| d.clone()).unwrap_or_default())
+}
+
+/// Return the JSON Schema for `OpsPilotConfig` and `LLMConfig` so the settings
+/// UI can validate and autocomplete.
+#[tauri::command]
+pub async fn get_config_schema() -> Result<serde_json::Value, String> {
+    let opspilot_schema = schemars::schema_for!(OpsPilotConfig);
+    let llm_schema = schemars::schema_for!(LLMConfig);
+
+    Ok(serde_json::json!({
+        "opsPilotConfig": opspilot_schema,
+        "llmConfig": llm_schema,
+    }))
+}
+
 /// Load OpsPilot configuration from file
 /// Searches multiple locations in priority order
 #[tauri::command]
-pub async fn load_opspilot_config() -> Result<OpsPilotConfig, String> {
+pub async fn load_opspilot_config(state: State<'_, AppState>) -> Result<OpsPilotConfig, String> {
+    let config = load_opspilot_config_inner().await?;
+    *state.connection_timeouts.lock().unwrap() = parse_connection_timeouts(&config);
+    Ok(config)
+}
+
+pub(crate) async fn load_opspilot_config_inner() -> Result<OpsPilotConfig, String> {
     let paths = get_opspilot_config_paths();
     let mut config = OpsPilotConfig::default();
+    let mut diagnostics = Vec::new();
 
     // 1. Try to load from file
     for path in paths {
@@ -119,10 +238,15 @@ pub async fn load_opspilot_config() -> Result<OpsPilotConfig, String> {
                         Ok(c) => {
                             eprintln!("[config] Loaded OpsPilot config from: {:?}", path);
                             config = c;
+                            diagnostics = diagnose_opspilot_config(&content)
+                                .into_iter()
+                                .filter(|i| i.issue != "invalid JSON")
+                                .collect();
                             break; // Stop at first found
                         }
                         Err(e) => {
                             eprintln!("[config] Failed to parse {:?}: {}", path, e);
+                            diagnostics = diagnose_opspilot_config(&content);
                         }
                     }
                 }
@@ -133,11 +257,15 @@ pub async fn load_opspilot_config() -> Result<OpsPilotConfig, String> {
         }
     }
 
+    if let Ok(mut last) = LAST_CONFIG_DIAGNOSTICS.lock() {
+        *last = diagnostics;
+    }
+
     // 2. Overlay secrets from Keyring
     if let Some(token) = get_secret("github_token") {
         config.github_token = Some(token);
     }
-    
+
     // Check key for other providers if needed (e.g. jira)
     // Future: generic secret loader?
 
@@ -146,7 +274,7 @@ pub async fn load_opspilot_config() -> Result<OpsPilotConfig, String> {
 
 /// Save OpsPilot configuration to file (home directory)
 #[tauri::command]
-pub async fn save_opspilot_config(config: OpsPilotConfig) -> Result<(), String> {
+pub async fn save_opspilot_config(state: State<'_, AppState>, config: OpsPilotConfig) -> Result<(), String> {
     let config_path = dirs::home_dir()
         .ok_or_else(|| "Could not find home directory".to_string())?
         .join(".opspilot")
@@ -178,11 +306,14 @@ pub async fn save_opspilot_config(config: OpsPilotConfig) -> Result<(), String>
         .map_err(|e| format!("Failed to write config: {}", e))?;
 
     eprintln!("[config] Saved OpsPilot config to: {:?}", config_path);
+
+    *state.connection_timeouts.lock().unwrap() = parse_connection_timeouts(&config);
+
     Ok(())
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct LLMConfig {
     pub provider: String,
     pub api_key: Option<String>,
@@ -201,6 +332,11 @@ pub struct InvestigationPattern {
     pub successful_path: Vec<ToolStep>,
     pub solution: String,
     pub pattern_hash: String,
+    // Populated at write time from the configured embedding endpoint; patterns
+    // written before this field existed simply deserialize it as `None` and
+    // fall back to the keyword scorer in `find_similar_investigations`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -305,9 +441,83 @@ fn get_patterns_path() -> PathBuf {
     path
 }
 
+/// Call the configured embedding endpoint (Ollama-style `/api/embeddings`) for a
+/// block of text. Returns the raw vector, L2-normalized so callers can compute
+/// cosine similarity as a plain dot product.
+pub(crate) async fn fetch_embedding(endpoint: &str, model: &str, text: &str) -> Result<Vec<f32>, String> {
+    #[derive(Serialize)]
+    struct EmbedRequest<'a> {
+        model: &'a str,
+        prompt: &'a str,
+    }
+
+    #[derive(Deserialize)]
+    struct EmbedResponse {
+        embedding: Vec<f32>,
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let resp = client
+        .post(format!("{}/api/embeddings", endpoint.trim_end_matches('/')))
+        .json(&EmbedRequest { model, prompt: text })
+        .send()
+        .await
+        .map_err(|e| format!("Embedding endpoint unreachable: {}", e))?;
+
+    if !resp.status().is_success() {
+        return Err(format!("Embedding endpoint returned {}", resp.status()));
+    }
+
+    let body: EmbedResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse embedding response: {}", e))?;
+
+    Ok(normalize_vector(body.embedding))
+}
+
+fn normalize_vector(vec: Vec<f32>) -> Vec<f32> {
+    let norm: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vec;
+    }
+    vec.into_iter().map(|x| x / norm).collect()
+}
+
+/// Best-effort embedding of an investigation pattern's goal + outcome text,
+/// using the endpoint/model configured in `OpsPilotConfig`. Returns `None`
+/// (rather than an error) when embeddings aren't configured or the endpoint
+/// is unreachable, so pattern storage never fails because of this.
+async fn embed_pattern_text(goal: &str, solution: &str, steps: &[ToolStep]) -> Option<Vec<f32>> {
+    let config = load_opspilot_config_inner().await.ok()?;
+    let endpoint = config.embedding_endpoint?;
+    let model = config.embedding_model?;
+
+    let tool_summary: String = steps
+        .iter()
+        .map(|s| s.tool.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let text = format!("{} {} {}", goal, tool_summary, solution);
+
+    match fetch_embedding(&endpoint, &model, &text).await {
+        Ok(vec) => Some(vec),
+        Err(e) => {
+            eprintln!("[patterns] Skipping embedding (endpoint unreachable or errored): {}", e);
+            None
+        }
+    }
+}
+
 /// Store a successful investigation pattern for learning
 #[tauri::command]
-pub async fn store_investigation_pattern(pattern: InvestigationPattern) -> Result<(), String> {
+pub async fn store_investigation_pattern(mut pattern: InvestigationPattern) -> Result<(), String> {
+    pattern.embedding = embed_pattern_text(&pattern.investigation_goal, &pattern.solution, &pattern.successful_path).await;
+
     let patterns_path = get_patterns_path();
 
     // Ensure parent directory exists
@@ -337,6 +547,53 @@ pub async fn store_investigation_pattern(pattern: InvestigationPattern) -> Resul
     Ok(())
 }
 
+/// Keyword-overlap score used when either side lacks an embedding: a flat
+/// bonus for matching cluster type plus 0.1 per query word that appears in
+/// the stored pattern's goal text.
+fn keyword_score(goal: &str, cluster_type: &str, pattern: &InvestigationPattern) -> f32 {
+    let mut score = 0.0;
+
+    if pattern.cluster_type == cluster_type {
+        score += 0.5;
+    }
+
+    let goal_lower = goal.to_lowercase();
+    let pattern_goal_lower = pattern.investigation_goal.to_lowercase();
+
+    for word in goal_lower.split_whitespace() {
+        if pattern_goal_lower.contains(word) {
+            score += 0.1;
+        }
+    }
+
+    score
+}
+
+/// Score a single stored pattern against the query: cosine similarity when
+/// both the query and the pattern have an embedding, otherwise the keyword
+/// fallback - covers both "pattern predates the embedding field" and
+/// "embedding endpoint is unreachable" without erroring or mis-scoring.
+fn score_pattern(goal: &str, cluster_type: &str, query_embedding: &Option<Vec<f32>>, pattern: &InvestigationPattern) -> f32 {
+    match (query_embedding, &pattern.embedding) {
+        (Some(query_vec), Some(pattern_vec)) => {
+            let mut score = crate::embeddings::cosine_similarity(query_vec, pattern_vec);
+            if pattern.cluster_type == cluster_type {
+                score += 0.05;
+            }
+            score
+        }
+        // No query vector, no stored vector, or a pattern written before
+        // this field existed: fall back to keyword matching.
+        _ => keyword_score(goal, cluster_type, pattern),
+    }
+}
+
+/// The match bar a pattern's score needs to clear: a tighter bar for a real
+/// cosine-similarity score, a looser one for the coarser keyword fallback.
+fn match_threshold(query_embedding: &Option<Vec<f32>>, pattern: &InvestigationPattern) -> f32 {
+    if pattern.embedding.is_some() && query_embedding.is_some() { 0.5 } else { 0.3 }
+}
+
 /// Find similar investigations based on goal and cluster type
 #[tauri::command]
 pub async fn find_similar_investigations(
@@ -354,6 +611,18 @@ pub async fn find_similar_investigations(
         .await
         .map_err(|e| format!("Failed to read patterns: {}", e))?;
 
+    // Try to embed the query goal so patterns with a stored vector can be
+    // scored by real cosine similarity. If no embedding endpoint is configured
+    // or it's unreachable, `query_embedding` stays `None` and every pattern
+    // falls back to the keyword scorer below.
+    let query_embedding = match load_opspilot_config_inner().await {
+        Ok(config) => match (config.embedding_endpoint, config.embedding_model) {
+            (Some(endpoint), Some(model)) => fetch_embedding(&endpoint, &model, &goal).await.ok(),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+
     let mut results: Vec<SimilarInvestigation> = Vec::new();
 
     // Parse each line as a pattern
@@ -363,24 +632,10 @@ pub async fn find_similar_investigations(
         }
 
         if let Ok(pattern) = serde_json::from_str::<InvestigationPattern>(line) {
-            // Simple similarity: check if goals/cluster types match
-            let mut score = 0.0;
-
-            if pattern.cluster_type == cluster_type {
-                score += 0.5;
-            }
-
-            // Check keyword overlap in goals
-            let goal_lower = goal.to_lowercase();
-            let pattern_goal_lower = pattern.investigation_goal.to_lowercase();
+            let score = score_pattern(&goal, &cluster_type, &query_embedding, &pattern);
+            let threshold = match_threshold(&query_embedding, &pattern);
 
-            for word in goal_lower.split_whitespace() {
-                if pattern_goal_lower.contains(word) {
-                    score += 0.1;
-                }
-            }
-
-            if score > 0.3 {
+            if score > threshold {
                 results.push(SimilarInvestigation {
                     similarity_score: score,
                     tool_sequence: pattern
@@ -401,12 +656,196 @@ pub async fn find_similar_investigations(
     Ok(results.into_iter().take(limit).collect())
 }
 
+/// Default Ollama embedding endpoint/model used when `OpsPilotConfig` hasn't
+/// configured one, so interactive search works out of the box.
+const DEFAULT_EMBEDDING_ENDPOINT: &str = "http://127.0.0.1:11434";
+const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+/// Resolve the embedding endpoint/model to use: whatever `OpsPilotConfig`
+/// has configured, falling back to the bundled Ollama defaults so callers
+/// work out of the box.
+pub(crate) async fn resolve_embedding_config() -> (String, String) {
+    match load_opspilot_config_inner().await {
+        Ok(config) => (
+            config.embedding_endpoint.unwrap_or_else(|| DEFAULT_EMBEDDING_ENDPOINT.to_string()),
+            config.embedding_model.unwrap_or_else(|| DEFAULT_EMBEDDING_MODEL.to_string()),
+        ),
+        Err(_) => (DEFAULT_EMBEDDING_ENDPOINT.to_string(), DEFAULT_EMBEDDING_MODEL.to_string()),
+    }
+}
+
+/// Embed `query` via the configured (or default) Ollama embedding endpoint
+/// and semantically search the cached knowledge base, so callers can search
+/// with raw text instead of round-tripping through the Python agent for a
+/// pre-computed embedding. Falls back to the lexical term-overlap scorer
+/// (flagging the response `degraded: true`) when no embedding can be
+/// produced - endpoint unreachable, or its dimension doesn't match the
+/// loaded embeddings - rather than failing the search outright.
+#[tauri::command]
+pub async fn semantic_search(
+    query: String,
+    top_k: usize,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::embeddings::SemanticSearchResponse, String> {
+    let data = crate::embeddings::load_embeddings(&app_handle)?;
+    let (endpoint, model) = resolve_embedding_config().await;
+
+    match fetch_embedding(&endpoint, &model, &query).await {
+        Ok(query_embedding) if query_embedding.len() == data.dimension => {
+            return Ok(crate::embeddings::SemanticSearchResponse {
+                results: crate::embeddings::search_documents(&query_embedding, &data, top_k),
+                degraded: false,
+            });
+        }
+        Ok(query_embedding) => {
+            eprintln!(
+                "[semantic_search] Embedding dimension {} did not match loaded embeddings' {}, falling back to lexical search",
+                query_embedding.len(), data.dimension
+            );
+        }
+        Err(e) => {
+            eprintln!("[semantic_search] Embedding endpoint unreachable ({}), falling back to lexical search", e);
+        }
+    }
+
+    let results = crate::embeddings::lexical_scores(&query, &data)
+        .into_iter()
+        .take(top_k)
+        .filter_map(|(idx, score)| data.documents.get(idx).map(|doc| crate::embeddings::SemanticSearchResult {
+            id: doc.id.clone(),
+            file: doc.file.clone(),
+            title: doc.title.clone(),
+            summary: doc.summary.clone(),
+            score,
+        }))
+        .collect();
+
+    Ok(crate::embeddings::SemanticSearchResponse { results, degraded: true })
+}
+
+/// Resolve a `DocEmbedding::file` path to somewhere readable on disk: as-is
+/// if it's already absolute, otherwise relative to the user's KB directory
+/// (where the Python agent's source documents live).
+fn resolve_doc_path(file: &str) -> PathBuf {
+    let path = PathBuf::from(file);
+    if path.is_absolute() {
+        path
+    } else {
+        get_kb_directory().join(file)
+    }
+}
+
+/// Result of a `refresh_embeddings` call.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingRefreshResult {
+    pub refreshed: usize,
+    pub unchanged: usize,
+    pub failed: Vec<String>,
+}
+
+/// Re-embed only the documents in `changed_files` whose current content hash
+/// no longer matches the sidecar manifest, instead of forcing a full
+/// `kb_embeddings_cache.json` regenerate. Each entry must already have a
+/// cached `DocEmbedding` (matched by its `file` path) - a file that's
+/// unreadable, not in the cache, or fails to embed lands in `failed` rather
+/// than aborting the rest of the batch.
+#[tauri::command]
+pub async fn refresh_embeddings(
+    changed_files: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<EmbeddingRefreshResult, String> {
+    let mut data = crate::embeddings::load_embeddings(&app_handle)?;
+    let mut manifest = crate::embeddings::load_manifest();
+    let (endpoint, model) = resolve_embedding_config().await;
+
+    let mut refreshed = 0;
+    let mut unchanged = 0;
+    let mut failed = Vec::new();
+
+    for file in changed_files {
+        let content = match std::fs::read_to_string(resolve_doc_path(&file)) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("[refresh_embeddings] Failed to read {}: {}", file, e);
+                failed.push(file);
+                continue;
+            }
+        };
+        let hash = crate::embeddings::hash_content(&content);
+
+        if manifest.get(&file).map(|entry| entry.hash.as_str()) == Some(hash.as_str()) {
+            unchanged += 1;
+            continue;
+        }
+
+        let Some(doc_index) = data.documents.iter().position(|doc| doc.file == file) else {
+            eprintln!("[refresh_embeddings] {} has no cached embedding to refresh", file);
+            failed.push(file);
+            continue;
+        };
+
+        match fetch_embedding(&endpoint, &model, &content).await {
+            Ok(embedding) => {
+                let doc_id = data.documents[doc_index].id.clone();
+                crate::embeddings::apply_refreshed_embedding(&mut data, doc_index, embedding, hash.clone());
+                manifest.insert(file, crate::embeddings::EmbeddingManifestEntry { hash, embedding_id: doc_id });
+                refreshed += 1;
+            }
+            Err(e) => {
+                eprintln!("[refresh_embeddings] Failed to embed {}: {}", file, e);
+                failed.push(file);
+            }
+        }
+    }
+
+    if refreshed > 0 {
+        crate::embeddings::save_refreshed(&data, &manifest)?;
+    }
+
+    Ok(EmbeddingRefreshResult { refreshed, unchanged, failed })
+}
+
+/// Snapshot of the cached knowledge-base embeddings for the AI Settings UI's
+/// "N docs need re-embedding" badge.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingsStatus {
+    pub model: String,
+    pub dimension: usize,
+    pub total_documents: usize,
+    pub stale_documents: usize,
+}
+
+/// Report how many cached documents still need `refresh_embeddings`: those
+/// with no manifest entry (never refreshed since the manifest was
+/// introduced) or whose recorded `content_hash` doesn't match what the
+/// manifest last embedded. Cheap - it only compares already-loaded state,
+/// it doesn't re-read any source files from disk.
+#[tauri::command]
+pub async fn embeddings_status(app_handle: tauri::AppHandle) -> Result<EmbeddingsStatus, String> {
+    let data = crate::embeddings::load_embeddings(&app_handle)?;
+    let manifest = crate::embeddings::load_manifest();
+
+    let stale_documents = data.documents.iter()
+        .filter(|doc| {
+            doc.content_hash.is_empty()
+                || manifest.get(&doc.file).map(|entry| entry.hash != doc.content_hash).unwrap_or(true)
+        })
+        .count();
+
+    Ok(EmbeddingsStatus {
+        model: data.model,
+        dimension: data.dimension,
+        total_documents: data.documents.len(),
+        stale_documents,
+    })
+}
+
 // =============================================================================
 // OPSPILOT CONFIG FILE SUPPORT
 // =============================================================================
 
 /// OpsPilot configuration structure (matches frontend OpsPilotConfig interface)
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpsPilotConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -423,6 +862,49 @@ pub struct OpsPilotConfig {
     pub kubeconfig: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub theme: Option<String>,
+    /// Humantime-style duration (e.g. `"25s"`) overriding `ConnectionTimeouts::client_timeout`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kube_client_timeout: Option<String>,
+    /// Humantime-style duration overriding `ConnectionTimeouts::connect_timeout`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kube_connect_timeout: Option<String>,
+    /// Humantime-style duration overriding `ConnectionTimeouts::read_timeout`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kube_read_timeout: Option<String>,
+    /// Humantime-style duration overriding `ConnectionTimeouts::api_check_timeout`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kube_api_check_timeout: Option<String>,
+    /// Humantime-style duration overriding `ConnectionTimeouts::vcluster_disconnect_timeout`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcluster_disconnect_timeout: Option<String>,
+}
+
+/// Parse the humantime-style timeout overrides in `config` into a
+/// `ConnectionTimeouts`, falling back field-by-field to the default for
+/// anything unset or unparseable rather than rejecting the whole config.
+pub fn parse_connection_timeouts(config: &OpsPilotConfig) -> ConnectionTimeouts {
+    let defaults = ConnectionTimeouts::default();
+
+    let parse = |value: &Option<String>, default: Duration, field: &str| -> Duration {
+        match value {
+            Some(raw) => match humantime::parse_duration(raw) {
+                Ok(d) => d,
+                Err(e) => {
+                    eprintln!("[config] Ignoring invalid {} {:?}: {}", field, raw, e);
+                    default
+                }
+            },
+            None => default,
+        }
+    };
+
+    ConnectionTimeouts {
+        client_timeout: parse(&config.kube_client_timeout, defaults.client_timeout, "kubeClientTimeout"),
+        connect_timeout: parse(&config.kube_connect_timeout, defaults.connect_timeout, "kubeConnectTimeout"),
+        read_timeout: parse(&config.kube_read_timeout, defaults.read_timeout, "kubeReadTimeout"),
+        api_check_timeout: parse(&config.kube_api_check_timeout, defaults.api_check_timeout, "kubeApiCheckTimeout"),
+        vcluster_disconnect_timeout: parse(&config.vcluster_disconnect_timeout, defaults.vcluster_disconnect_timeout, "vclusterDisconnectTimeout"),
+    }
 }
 
 /// Get list of config file paths to search (in priority order)
@@ -636,3 +1118,63 @@ Full documentation: https://github.com/ankitjain-wiz/opspilot/blob/main/docs/kno
     // Return updated info
     get_kb_directory_info().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(cluster_type: &str, goal: &str, embedding: Option<Vec<f32>>) -> InvestigationPattern {
+        InvestigationPattern {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            cluster_type: cluster_type.to_string(),
+            investigation_goal: goal.to_string(),
+            successful_path: Vec::new(),
+            solution: "restarted the deployment".to_string(),
+            pattern_hash: "hash".to_string(),
+            embedding,
+        }
+    }
+
+    #[test]
+    fn falls_back_to_keyword_score_when_pattern_has_no_embedding() {
+        // Pattern written before the embedding field existed (or storage
+        // skipped it because the endpoint was down at write time).
+        let query_embedding = Some(vec![1.0, 0.0, 0.0]);
+        let p = pattern("networking", "pod crashlooping in prod", None);
+
+        let score = score_pattern("pod crashlooping", "networking", &query_embedding, &p);
+        assert_eq!(score, keyword_score("pod crashlooping", "networking", &p));
+        assert!(score > match_threshold(&query_embedding, &p), "keyword-matched pattern should clear the fallback threshold");
+    }
+
+    #[test]
+    fn falls_back_to_keyword_score_when_query_embedding_endpoint_is_down() {
+        // Endpoint unreachable at query time: query_embedding is None even
+        // though the stored pattern does have a vector.
+        let query_embedding: Option<Vec<f32>> = None;
+        let p = pattern("networking", "pod crashlooping in prod", Some(vec![0.0, 1.0, 0.0]));
+
+        let score = score_pattern("pod crashlooping", "networking", &query_embedding, &p);
+        assert_eq!(score, keyword_score("pod crashlooping", "networking", &p));
+        assert!(score > match_threshold(&query_embedding, &p), "keyword-matched pattern should clear the fallback threshold");
+    }
+
+    #[test]
+    fn uses_cosine_similarity_when_both_sides_have_an_embedding() {
+        let query_embedding = Some(vec![1.0, 0.0, 0.0]);
+        let p = pattern("networking", "totally unrelated goal text", Some(vec![1.0, 0.0, 0.0]));
+
+        let score = score_pattern("pod crashlooping", "networking", &query_embedding, &p);
+        // Identical vectors plus the matching cluster_type bonus.
+        assert!((score - 1.05).abs() < 1e-6, "expected cosine similarity + cluster bonus, got {}", score);
+    }
+
+    #[test]
+    fn fallback_threshold_is_looser_than_the_embedding_threshold() {
+        let p_with_embedding = pattern("networking", "goal", Some(vec![1.0]));
+        let p_without_embedding = pattern("networking", "goal", None);
+
+        assert_eq!(match_threshold(&Some(vec![1.0]), &p_with_embedding), 0.5);
+        assert_eq!(match_threshold(&None, &p_without_embedding), 0.3);
+    }
+}
@@ -1,19 +1,63 @@
 use tauri::State;
-use kube::api::Api;
-use k8s_openapi::api::core::v1::{Secret, Service};
+use kube::api::{Api, ListParams};
+use k8s_openapi::api::core::v1::{Pod, Secret, Service};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
 use crate::state::AppState;
 use crate::client::create_client;
-use std::process::{Command, Child, Stdio};
+use crate::commands::port_forward_manager;
+use crate::audit;
 use std::sync::Mutex;
-use std::net::TcpListener;
 use serde::Serialize;
-use std::time::Duration;
 
-/// Global state for port-forward process
-static ARGOCD_PORT_FORWARD: Mutex<Option<Child>> = Mutex::new(None);
+/// The argocd-server forward's id and the local port it landed on, as
+/// returned by `port_forward_manager::start_forward`. ArgoCD is just one
+/// preconfigured forward on top of the generic manager now, so (unlike the
+/// old fixed `ARGOCD_LOCAL_PORT`) the local port varies per launch.
+static ARGOCD_FORWARD: Mutex<Option<(String, u16)>> = Mutex::new(None);
+
+/// A one-shot credential handoff for the embedded ArgoCD webview's
+/// auto-login script. The webview renders a page served from ArgoCD's own
+/// origin, so nothing the init script embeds as a literal is safe from a
+/// malicious script on that page; instead the script only carries an opaque
+/// `token` and calls back over `fetch_argocd_autologin_credentials` to
+/// redeem it. The token is single-use: the first successful redemption
+/// takes the slot, so a page that captured the token and replayed it later
+/// gets nothing.
+static AUTOLOGIN_CHANNEL: Mutex<Option<AutologinChannel>> = Mutex::new(None);
+
+struct AutologinChannel {
+    token: String,
+    username: String,
+    password: String,
+}
+
+/// Credentials handed back to the init script over the one-shot channel.
+#[derive(Serialize)]
+pub struct ArgoCDAutologinCredentials {
+    pub username: String,
+    pub password: String,
+}
 
-/// Port used for ArgoCD port-forward
-const ARGOCD_LOCAL_PORT: u16 = 9080;
+/// Redeem the one-shot auto-login token minted by `open_argocd_webview`.
+/// Consumes the channel on any matching call - a second call with the same
+/// (or any other) token finds the slot empty and fails.
+#[tauri::command]
+pub async fn fetch_argocd_autologin_credentials(token: String) -> Result<ArgoCDAutologinCredentials, String> {
+    let mut guard = AUTOLOGIN_CHANNEL.lock().unwrap();
+    match guard.take() {
+        Some(channel) if channel.token == token => Ok(ArgoCDAutologinCredentials {
+            username: channel.username,
+            password: channel.password,
+        }),
+        Some(channel) => {
+            // Wrong token: put the real channel back so the legitimate
+            // caller can still redeem it.
+            *guard = Some(channel);
+            Err("Invalid or expired auto-login token".to_string())
+        }
+        None => Err("Invalid or expired auto-login token".to_string()),
+    }
+}
 
 /// ArgoCD server connection info
 #[derive(Serialize)]
@@ -43,7 +87,7 @@ async fn find_argocd_namespace(client: &kube::Client) -> Option<String> {
 pub async fn get_argocd_server_info(
     state: State<'_, AppState>,
 ) -> Result<ArgoCDServerInfo, String> {
-    let client = create_client(state).await?;
+    let client = create_client(state.clone()).await?;
 
     let namespace = find_argocd_namespace(&client).await
         .ok_or("ArgoCD not found in cluster. Checked namespaces: argocd, argo-cd, argocd-system")?;
@@ -61,14 +105,24 @@ pub async fn get_argocd_server_info(
         Err(_) => None
     };
 
-    let password = password.ok_or_else(|| {
-        "ArgoCD admin password not found. The 'argocd-initial-admin-secret' may have been deleted.".to_string()
-    })?;
-
-    // Check if port-forward is already running
-    let port_forward_active = {
-        let guard = ARGOCD_PORT_FORWARD.lock().unwrap();
-        guard.is_some()
+    let password = match password {
+        Some(p) => p,
+        None => {
+            audit::record("argocd_credential_access", None, Some(&namespace), Some("argocd-initial-admin-secret"), "failure", Some("secret not found"));
+            return Err("ArgoCD admin password not found. The 'argocd-initial-admin-secret' may have been deleted.".to_string());
+        }
+    };
+    audit::record("argocd_credential_access", None, Some(&namespace), Some("argocd-initial-admin-secret"), "success", None);
+
+    // Check if port-forward is already running, and on what local port -
+    // the manager allocates it dynamically, so it isn't a fixed constant
+    // anymore.
+    let (port_forward_active, local_port) = {
+        let guard = ARGOCD_FORWARD.lock().unwrap();
+        match guard.as_ref() {
+            Some((id, local_port)) => (port_forward_manager::is_forward_active(&state, id), *local_port),
+            None => (false, 0),
+        }
     };
 
     // Determine protocol based on target port
@@ -81,7 +135,7 @@ pub async fn get_argocd_server_info(
     };
 
     Ok(ArgoCDServerInfo {
-        url: format!("{}://localhost:{}", protocol, ARGOCD_LOCAL_PORT),
+        url: format!("{}://localhost:{}", protocol, local_port),
         username: "admin".to_string(),
         password,
         namespace,
@@ -89,129 +143,6 @@ pub async fn get_argocd_server_info(
     })
 }
 
-/// Check if port is available (platform-agnostic, pure Rust)
-fn is_port_available(port: u16) -> bool {
-    // Try binding to both IPv4 and IPv6
-    TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok()
-}
-
-/// Kill process by PID (platform-agnostic)
-fn kill_process(pid: u32) {
-    #[cfg(unix)]
-    {
-        let _ = Command::new("kill")
-            .args(&["-9", &pid.to_string()])
-            .stderr(Stdio::null())
-            .stdout(Stdio::null())
-            .status();
-    }
-    #[cfg(windows)]
-    {
-        let _ = Command::new("taskkill")
-            .args(&["/F", "/PID", &pid.to_string()])
-            .stderr(Stdio::null())
-            .stdout(Stdio::null())
-            .status();
-    }
-}
-
-/// Get PIDs using a specific port (platform-agnostic)
-fn get_pids_using_port(port: u16) -> Vec<u32> {
-    let mut pids = Vec::new();
-
-    #[cfg(unix)]
-    {
-        // Use lsof (available on macOS and most Linux)
-        if let Ok(output) = Command::new("lsof")
-            .args(&[&format!("-ti:{}", port)])
-            .output()
-        {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.trim().lines() {
-                if let Ok(pid) = line.trim().parse::<u32>() {
-                    pids.push(pid);
-                }
-            }
-        }
-    }
-
-    #[cfg(windows)]
-    {
-        // Use netstat on Windows
-        if let Ok(output) = Command::new("netstat")
-            .args(&["-aon"])
-            .output()
-        {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let port_str = format!(":{}", port);
-            for line in stdout.lines() {
-                if line.contains(&port_str) && line.contains("LISTENING") {
-                    // Last column is PID
-                    if let Some(pid_str) = line.split_whitespace().last() {
-                        if let Ok(pid) = pid_str.parse::<u32>() {
-                            pids.push(pid);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    pids
-}
-
-/// Kill any existing port-forward processes on the ArgoCD port - IRONCLAD version
-fn cleanup_stale_port_forwards() {
-    // Method 1: Kill by port (most reliable, platform-agnostic)
-    let pids = get_pids_using_port(ARGOCD_LOCAL_PORT);
-    for pid in &pids {
-        eprintln!("[argocd] Killing process {} using port {}", pid, ARGOCD_LOCAL_PORT);
-        kill_process(*pid);
-    }
-
-    // Method 2: Kill kubectl port-forward processes by pattern
-    #[cfg(unix)]
-    {
-        // pkill by pattern
-        let _ = Command::new("pkill")
-            .args(&["-9", "-f", &format!("kubectl.*port-forward.*{}", ARGOCD_LOCAL_PORT)])
-            .stderr(Stdio::null())
-            .stdout(Stdio::null())
-            .status();
-
-        let _ = Command::new("pkill")
-            .args(&["-9", "-f", "kubectl.*port-forward.*argocd"])
-            .stderr(Stdio::null())
-            .stdout(Stdio::null())
-            .status();
-    }
-
-    #[cfg(windows)]
-    {
-        // On Windows, we can use wmic or taskkill with filters
-        let _ = Command::new("taskkill")
-            .args(&["/F", "/IM", "kubectl.exe"])
-            .stderr(Stdio::null())
-            .stdout(Stdio::null())
-            .status();
-    }
-
-    // Wait for OS to release the port
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // Final verification - if still occupied, try once more
-    if !is_port_available(ARGOCD_LOCAL_PORT) {
-        let pids = get_pids_using_port(ARGOCD_LOCAL_PORT);
-        if !pids.is_empty() {
-            eprintln!("[argocd] Port {} still in use by PIDs: {:?}, retrying kill", ARGOCD_LOCAL_PORT, pids);
-            for pid in pids {
-                kill_process(pid);
-            }
-            std::thread::sleep(std::time::Duration::from_millis(300));
-        }
-    }
-}
-
 /// Get the HTTP port for ArgoCD server service
 async fn get_argocd_http_port(client: &kube::Client, namespace: &str) -> Result<i32, String> {
     let services: Api<Service> = Api::namespaced(client.clone(), namespace);
@@ -248,137 +179,117 @@ async fn get_argocd_http_port(client: &kube::Client, namespace: &str) -> Result<
     Ok(80)
 }
 
-/// Start port-forward to ArgoCD server
+/// Resolve a `ServicePort`'s `targetPort` against `pod`: numeric target ports
+/// pass through as-is, named ones are looked up by name in the pod's
+/// container ports (the same indirection `kubectl port-forward` and the
+/// Service proxy perform under the hood).
+fn resolve_target_port(target_port: Option<&IntOrString>, fallback_port: i32, pod: &Pod) -> Result<u16, String> {
+    match target_port {
+        Some(IntOrString::Int(port)) => Ok(*port as u16),
+        Some(IntOrString::String(name)) => pod
+            .spec
+            .as_ref()
+            .and_then(|spec| {
+                spec.containers.iter().find_map(|c| {
+                    c.ports
+                        .as_ref()?
+                        .iter()
+                        .find(|p| p.name.as_deref() == Some(name.as_str()))
+                        .map(|p| p.container_port as u16)
+                })
+            })
+            .ok_or_else(|| format!("Could not resolve named target port '{}' on pod {}", name, pod.metadata.name.clone().unwrap_or_default())),
+        None => Ok(fallback_port as u16),
+    }
+}
+
+/// Find a Ready pod matching `selector` (a map of label key/value pairs, as
+/// found on a Service's `spec.selector`).
+async fn find_ready_pod(client: &kube::Client, namespace: &str, selector: &std::collections::BTreeMap<String, String>) -> Result<Pod, String> {
+    let label_selector = selector.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(",");
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod_list = pods
+        .list(&ListParams::default().labels(&label_selector))
+        .await
+        .map_err(|e| format!("Failed to list argocd-server pods: {}", e))?;
+
+    pod_list
+        .items
+        .into_iter()
+        .find(|pod| {
+            pod.status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+                .map(|conditions| conditions.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+                .unwrap_or(false)
+        })
+        .ok_or_else(|| format!("No Ready argocd-server pod found matching selector '{}'", label_selector))
+}
+
+/// Start port-forward to ArgoCD server. ArgoCD is just one preconfigured
+/// target on top of `port_forward_manager`: this resolves which numeric pod
+/// port backs the service's "http" port, then hands off to the manager for
+/// the actual dynamically-allocated local port and connection bridging.
 #[tauri::command]
 pub async fn start_argocd_port_forward(
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    // First, stop any existing port-forward we're tracking
-    // Check if we already have a running port-forward
+    // Already forwarding?
     {
-        let mut guard = ARGOCD_PORT_FORWARD.lock().unwrap();
-        if let Some(child) = guard.as_mut() {
-            // Check if process is still alive
-            match child.try_wait() {
-                Ok(None) => {
-                    // Still running, assume it's good
-                    // We could verify the port is actually listening, but let's assume if process is alive it's ok
-                     eprintln!("[argocd] Port-forward already active");
-                    return Ok(format!("Port-forward already active on localhost:{}", ARGOCD_LOCAL_PORT));
-                }
-                Ok(Some(_)) => {
-                    // Exited, clear it
-                    *guard = None;
-                }
-                Err(_) => {
-                    // Error checking, assume dead
-                    *guard = None;
-                }
+        let guard = ARGOCD_FORWARD.lock().unwrap();
+        if let Some((id, local_port)) = guard.as_ref() {
+            if port_forward_manager::is_forward_active(&state, id) {
+                eprintln!("[argocd] Port-forward already active");
+                return Ok(format!("Port-forward already active on localhost:{}", local_port));
             }
         }
     }
 
-    // Clean up any orphaned port-forwards from previous sessions
-    cleanup_stale_port_forwards();
-
-    // Verify port is available with retries
-    let max_retries = 3;
-    for attempt in 1..=max_retries {
-        if is_port_available(ARGOCD_LOCAL_PORT) {
-            break;
-        }
-        if attempt == max_retries {
-            return Err(format!(
-                "Port {} is still in use after {} cleanup attempts. Please manually kill the process.",
-                ARGOCD_LOCAL_PORT, max_retries
-            ));
-        }
-        eprintln!("[argocd] Port {} still in use, cleanup attempt {}/{}", ARGOCD_LOCAL_PORT, attempt, max_retries);
-        cleanup_stale_port_forwards();
-    }
-
-    let client = create_client(state).await?;
+    let client = create_client(state.clone()).await?;
     let namespace = find_argocd_namespace(&client).await
         .ok_or("ArgoCD not found in cluster")?;
 
-    // Get the HTTP port from the service
-    let target_port = get_argocd_http_port(&client, &namespace).await?;
-    eprintln!("[argocd] Using target port {} for ArgoCD server", target_port);
-
-    // Start kubectl port-forward in background
-    let port_mapping = format!("{}:{}", ARGOCD_LOCAL_PORT, target_port);
-    let mut child = Command::new("kubectl")
-        .args(&[
-            "port-forward",
-            "-n", &namespace,
-            "svc/argocd-server",
-            &port_mapping,
-        ])
-        .stderr(Stdio::piped()) // Capture stderr to check for errors
-        .spawn()
-        .map_err(|e| format!("Failed to start port-forward: {}", e))?;
-
-    // Wait for port-forward to bind to the local port; if it never binds, surface an error
-    const BIND_RETRIES: u8 = 15;
-    for attempt in 1..=BIND_RETRIES {
-        if !is_port_available(ARGOCD_LOCAL_PORT) {
-            break;
-        }
-
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                // Process exited early; capture stderr for diagnostics
-                let stderr = match child.wait_with_output() {
-                    Ok(output) => String::from_utf8_lossy(&output.stderr).to_string(),
-                    Err(e) => format!("(failed to read stderr: {})", e),
-                };
-                return Err(format!(
-                    "Port-forward failed (exit {}): {}",
-                    status,
-                    stderr.trim()
-                ));
-            }
-            Ok(None) => {
-                tokio::time::sleep(Duration::from_millis(200)).await;
-            }
-            Err(e) => {
-                return Err(format!("Failed to check port-forward status: {}", e));
-            }
-        }
-
-        if attempt == BIND_RETRIES && is_port_available(ARGOCD_LOCAL_PORT) {
-            let _ = child.kill();
-            return Err(format!(
-                "Port-forward did not bind to localhost:{} after {} attempts",
-                ARGOCD_LOCAL_PORT, BIND_RETRIES
-            ));
+    let services: Api<Service> = Api::namespaced(client.clone(), &namespace);
+    let svc = services.get("argocd-server").await
+        .map_err(|e| format!("Failed to get argocd-server service: {}", e))?;
+    let spec = svc.spec.ok_or("argocd-server service has no spec")?;
+    let selector = spec.selector.ok_or("argocd-server service has no selector")?;
+    let ports = spec.ports.unwrap_or_default();
+
+    let service_port = ports.iter().find(|p| p.name.as_deref() == Some("http"))
+        .or_else(|| ports.iter().find(|p| p.port == 80))
+        .or_else(|| ports.iter().find(|p| p.port == 8080))
+        .or_else(|| ports.first())
+        .ok_or("argocd-server service exposes no ports")?;
+
+    let pod = find_ready_pod(&client, &namespace, &selector).await?;
+    let target_port = resolve_target_port(service_port.target_port.as_ref(), service_port.port, &pod)?;
+
+    let info = match port_forward_manager::start_forward(state, namespace.clone(), "argocd-server".to_string(), target_port).await {
+        Ok(info) => info,
+        Err(e) => {
+            audit::record("argocd_port_forward_start", None, Some(&namespace), Some("argocd-server"), "failure", Some(&e));
+            return Err(e);
         }
-    }
+    };
 
-    // Store the child process
     {
-        let mut guard = ARGOCD_PORT_FORWARD.lock().unwrap();
-        *guard = Some(child);
+        let mut guard = ARGOCD_FORWARD.lock().unwrap();
+        *guard = Some((info.id.clone(), info.local_port));
     }
+    audit::record("argocd_port_forward_start", None, Some(&namespace), Some("argocd-server"), "success", Some(&format!("localhost:{}", info.local_port)));
 
-    Ok(format!("Port-forward started on localhost:{}", ARGOCD_LOCAL_PORT))
+    Ok(format!("Port-forward started on localhost:{}", info.local_port))
 }
 
 /// Stop ArgoCD port-forward
 #[tauri::command]
-pub async fn stop_argocd_port_forward() -> Result<String, String> {
-    // Stop the tracked process
-    {
-        let mut guard = ARGOCD_PORT_FORWARD.lock().unwrap();
-        if let Some(mut child) = guard.take() {
-            let _ = child.kill();
-            let _ = child.wait(); // Reap the zombie process
-        }
+pub async fn stop_argocd_port_forward(state: State<'_, AppState>) -> Result<String, String> {
+    let id = ARGOCD_FORWARD.lock().unwrap().take().map(|(id, _)| id);
+    if let Some(id) = id {
+        port_forward_manager::stop_forward(state, id).await?;
     }
-
-    // Also cleanup any orphaned processes (defensive)
-    cleanup_stale_port_forwards();
-
+    audit::record("argocd_port_forward_stop", None, None, Some("argocd-server"), "success", None);
     Ok("Port-forward stopped".to_string())
 }
 
@@ -442,6 +353,7 @@ pub async fn open_argocd_webview(
         existing.show().map_err(|e| format!("Failed to show webview: {}", e))?;
         existing.set_focus().map_err(|e| format!("Failed to focus webview: {}", e))?;
 
+        audit::record("argocd_webview_open", None, None, Some("argocd-embedded"), "success", Some("reused existing webview"));
         return Ok("ArgoCD webview restored (session preserved)".to_string());
     }
 
@@ -451,20 +363,40 @@ pub async fn open_argocd_webview(
     // Create the webview URL
     let url = info.url.parse::<tauri::Url>().map_err(|e| format!("Invalid URL: {}", e))?;
 
+    // Mint a fresh one-shot token for this webview's credential handoff and
+    // stash the real credentials behind it - the init script below only
+    // ever sees the token, never the password itself.
+    let token = uuid::Uuid::new_v4().to_string();
+    *AUTOLOGIN_CHANNEL.lock().unwrap() = Some(AutologinChannel {
+        token: token.clone(),
+        username: info.username,
+        password: info.password,
+    });
+
     // Login automation script
     // We use a React-compatible input setter to ensure the state updates
     let init_script = format!(
         r#"
         const ATTEMPT_DURATION_MS = 15000;
         const START_TIME = Date.now();
+        const AUTOLOGIN_TOKEN = "{token}";
 
         function log(msg) {{
             console.log(`[OpPilot AutoLogin] ${{msg}}`);
         }}
 
+        async function fetchCredentials() {{
+            return window.__TAURI__.core.invoke("fetch_argocd_autologin_credentials", {{ token: AUTOLOGIN_TOKEN }});
+        }}
+
         window.addEventListener('DOMContentLoaded', () => {{
             log("DOM Content Loaded - Starting Auto Login attempt");
 
+            // Redeemed once the real login form shows up and the token is
+            // exchanged for credentials; cleared on any failure so a stale
+            // set of values never lingers in memory longer than needed.
+            let credentials = null;
+
             const checkAndLogin = () => {{
                 // Stop if timed out
                 if (Date.now() - START_TIME > ATTEMPT_DURATION_MS) {{
@@ -477,27 +409,27 @@ pub async fn open_argocd_webview(
                     const passwordInput = document.querySelector('input[name="password"]') || document.querySelector('input[class*="login-password"]');
                     const loginButton = document.querySelector('button[type="submit"]') || document.querySelector('button[class*="login-button"]');
 
-                    if (usernameInput && passwordInput && loginButton) {{
+                    if (usernameInput && passwordInput && loginButton && credentials) {{
                         log("Found login fields");
-                        
+
                         // Only autofill if empty (to avoid fighting with user)
                         if (usernameInput.value === "") {{
                             log("Filling credentials...");
-                            
+
                             // React 16+ hack to trigger onChange by calling native value setter
                             // granular error handling for setter discovery
                             const nativeInputValueSetter = Object.getOwnPropertyDescriptor(window.HTMLInputElement.prototype, "value").set;
-                            
+
                             if (nativeInputValueSetter) {{
-                                nativeInputValueSetter.call(usernameInput, "{}");
+                                nativeInputValueSetter.call(usernameInput, credentials.username);
                                 usernameInput.dispatchEvent(new Event('input', {{ bubbles: true }}));
 
-                                nativeInputValueSetter.call(passwordInput, "{}");
+                                nativeInputValueSetter.call(passwordInput, credentials.password);
                                 passwordInput.dispatchEvent(new Event('input', {{ bubbles: true }}));
                             }} else {{
                                 // Fallback
-                                usernameInput.value = "{}";
-                                passwordInput.value = "{}";
+                                usernameInput.value = credentials.username;
+                                passwordInput.value = credentials.password;
                             }}
 
                             log("Credentials filled, submitting in 500ms...");
@@ -514,17 +446,22 @@ pub async fn open_argocd_webview(
                 return false;
             }};
 
-            // Try immediately and then retry periodically
-            if (!checkAndLogin()) {{
-                const interval = setInterval(() => {{
-                    if (checkAndLogin() || (Date.now() - START_TIME > ATTEMPT_DURATION_MS)) {{
-                        clearInterval(interval);
+            fetchCredentials()
+                .then((creds) => {{ credentials = creds; }})
+                .catch((e) => log(`Failed to redeem auto-login token: ${{e}}`))
+                .finally(() => {{
+                    // Try immediately and then retry periodically
+                    if (!checkAndLogin()) {{
+                        const interval = setInterval(() => {{
+                            if (checkAndLogin() || (Date.now() - START_TIME > ATTEMPT_DURATION_MS)) {{
+                                clearInterval(interval);
+                            }}
+                        }}, 800);
                     }}
-                }}, 800);
-            }}
+                }});
         }});
         "#,
-        info.username, info.password, info.username, info.password
+        token = token
     );
 
     // Build a new webview window positioned at the specified location
@@ -543,8 +480,12 @@ pub async fn open_argocd_webview(
     .parent(&main_window).map_err(|e| format!("Failed to parent window: {}", e))? // Parent to main window so it moves with the app
     .initialization_script(&init_script) // Inject auto-login script
     .build()
-    .map_err(|e| format!("Failed to create webview: {}", e))?;
+    .map_err(|e| {
+        audit::record("argocd_webview_open", None, None, Some("argocd-embedded"), "failure", Some(&e.to_string()));
+        format!("Failed to create webview: {}", e)
+    })?;
 
+    audit::record("argocd_webview_open", None, None, Some("argocd-embedded"), "success", None);
     Ok("ArgoCD webview opened".to_string())
 }
 
@@ -556,6 +497,7 @@ pub async fn close_argocd_webview(app: tauri::AppHandle) -> Result<String, Strin
     if let Some(webview) = app.get_webview_window("argocd-embedded") {
         // Hide instead of close to preserve login state
         webview.hide().map_err(|e| format!("Failed to hide webview: {}", e))?;
+        audit::record("argocd_webview_close", None, None, Some("argocd-embedded"), "success", None);
         Ok("ArgoCD webview hidden".to_string())
     } else {
         Ok("No ArgoCD webview found".to_string())
@@ -2,6 +2,18 @@ use tauri::{command, Emitter, AppHandle};
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use chrono::{Utc, Duration};
+use std::sync::Arc;
+
+use super::azure_sdk::{self, ArmClient};
+use super::azure_scan;
+use super::azure_kubeconfig;
+
+/// Which backend a command should use to talk to Azure. Defaults to `Cli`
+/// so existing installs that already have `az` configured see no change;
+/// `Sdk` goes straight to the ARM REST API over `reqwest` instead.
+fn use_sdk_backend(backend: &Option<String>) -> bool {
+    matches!(backend.as_deref(), Some("sdk"))
+}
 
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -68,11 +80,75 @@ pub async fn azure_login() -> Result<String, String> {
     Ok("Logged in".to_string())
 }
 
+/// `refresh_azure_data`'s result: the subscriptions that scanned
+/// successfully (clusters empty for any that failed), plus one entry per
+/// failed subscription so the frontend can show e.g. "3 of 40
+/// subscriptions failed to scan" instead of those subscriptions quietly
+/// looking clusterless.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AzureScanResult {
+    pub subscriptions: Vec<AzureSubscription>,
+    pub errors: Vec<azure_scan::SubscriptionError>,
+}
+
+/// Fetch clusters for one subscription via the `az` CLI, as a standalone
+/// function so both `refresh_azure_data` and `detect_aks_cluster`'s CLI
+/// paths can hand it to `azure_scan::spawn_subscription_scans`.
+async fn fetch_clusters_cli(subscription_id: String) -> Result<Vec<AksCluster>, String> {
+    let output = Command::new("az")
+        .args(&["aks", "list", "--subscription", &subscription_id, "-o", "json"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run az aks list: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let raw_clusters: Vec<AzCluster> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse az aks list output: {}", e))?;
+
+    Ok(raw_clusters.into_iter().map(|c| AksCluster {
+        id: c.id,
+        name: c.name,
+        resource_group: c.resource_group,
+        location: c.location,
+        power_state: c.power_state.unwrap_or(PowerState { code: "Running".to_string() }),
+    }).collect())
+}
+
+fn az_account_to_scan_account(account: &AzAccount) -> azure_scan::ScanAccount {
+    azure_scan::ScanAccount { id: account.id.clone(), name: account.name.clone(), is_default: account.is_default }
+}
+
 #[command]
-pub async fn refresh_azure_data(app: AppHandle) -> Result<Vec<AzureSubscription>, String> {
+pub async fn refresh_azure_data(app: AppHandle, backend: Option<String>, concurrency: Option<usize>) -> Result<AzureScanResult, String> {
+    let concurrency = concurrency.unwrap_or(azure_scan::DEFAULT_SCAN_CONCURRENCY);
+
     // Emit status
     let _ = app.emit("azure:status", "Finding Azure subscriptions...");
 
+    if use_sdk_backend(&backend) {
+        let client = Arc::new(ArmClient::new().await?);
+        let arm_accounts = azure_sdk::list_subscription_accounts_sdk(&client).await?;
+        let _ = app.emit("azure:status", format!("Found {} subscriptions. Scanning for clusters...", arm_accounts.len()));
+
+        let mut rx = azure_scan::spawn_subscription_scans(arm_accounts, concurrency, Some(app.clone()), move |sub_id| {
+            let client = client.clone();
+            async move { azure_sdk::list_clusters_sdk(&client, &sub_id).await }
+        });
+
+        let mut subscriptions = Vec::new();
+        let mut errors = Vec::new();
+        while let Some(outcome) = rx.recv().await {
+            if let Some(error) = outcome.error {
+                errors.push(error);
+            }
+            subscriptions.push(outcome.subscription);
+        }
+        return Ok(AzureScanResult { subscriptions, errors });
+    }
+
     // 1. Get Accounts
     let output = Command::new("az")
         .args(&["account", "list", "--all", "-o", "json"])
@@ -86,59 +162,50 @@ pub async fn refresh_azure_data(app: AppHandle) -> Result<Vec<AzureSubscription>
 
     let accounts: Vec<AzAccount> = serde_json::from_slice(&output.stdout)
         .map_err(|e| format!("Failed to parse azure accounts: {}", e))?;
-    
-    let _ = app.emit("azure:status", format!("Found {} subscriptions. Scanning for clusters...", accounts.len()));
-
-    // 2. Fetch clusters for all accounts in parallel using spawn or join_all
-    // Since we are in an async command, we can use futures::future::join_all
-    let futures = accounts.into_iter().map(|account| {
-        let app_handle = app.clone();
-        async move {
-            let sub_id = account.id.clone();
-            
-            let cmd_res = Command::new("az")
-                .args(&["aks", "list", "--subscription", &sub_id, "-o", "json"])
-                .output()
-                .await;
-
-            let clusters = match cmd_res {
-                Ok(o) => {
-                    if o.status.success() {
-                        let raw_clusters: Vec<AzCluster> = serde_json::from_slice(&o.stdout).unwrap_or_default();
-                        raw_clusters.into_iter().map(|c| AksCluster {
-                            id: c.id,
-                            name: c.name,
-                            resource_group: c.resource_group,
-                            location: c.location,
-                            power_state: c.power_state.unwrap_or(PowerState { code: "Running".to_string() })
-                        }).collect()
-                    } else {
-                        Vec::new()
-                    }
-                }
-                Err(_) => Vec::new(),
-            };
 
-            let sub = AzureSubscription {
-                id: account.id,
-                name: account.name,
-                is_default: account.is_default,
-                clusters,
-            };
+    let _ = app.emit("azure:status", format!("Found {} subscriptions. Scanning for clusters...", accounts.len()));
 
-            // Emit update
-            let _ = app_handle.emit("azure:subscription_update", &sub);
+    // 2. Fetch clusters for all accounts, bounded to `concurrency` in flight at once
+    let scan_accounts: Vec<azure_scan::ScanAccount> = accounts.iter().map(az_account_to_scan_account).collect();
+    let mut rx = azure_scan::spawn_subscription_scans(scan_accounts, concurrency, Some(app.clone()), fetch_clusters_cli);
 
-            sub
+    let mut subscriptions = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(outcome) = rx.recv().await {
+        if let Some(error) = outcome.error {
+            errors.push(error);
         }
-    });
+        subscriptions.push(outcome.subscription);
+    }
 
-    let result = futures::future::join_all(futures).await;
-    Ok(result)
+    Ok(AzureScanResult { subscriptions, errors })
 }
 
 #[command]
-pub async fn get_aks_credentials(subscription_id: String, resource_group: String, name: String) -> Result<String, String> {
+pub async fn get_aks_credentials(
+    subscription_id: String,
+    resource_group: String,
+    name: String,
+    backend: Option<String>,
+    mode: Option<String>,
+    admin: Option<bool>,
+    kubeconfig_path: Option<String>,
+) -> Result<String, String> {
+    if use_sdk_backend(&backend) {
+        let client = ArmClient::new().await?;
+        let auth_mode = azure_kubeconfig::AksAuthMode::from_str(mode.as_deref());
+        azure_kubeconfig::merge_aks_credentials(
+            &client,
+            &subscription_id,
+            &resource_group,
+            &name,
+            auth_mode,
+            admin.unwrap_or(false),
+            &kubeconfig_path,
+        ).await?;
+        return Ok("Credentials merged natively (kubelogin exec auth wired in directly)".to_string());
+    }
+
     let output = Command::new("az")
         .args(&[
             "aks", "get-credentials",
@@ -205,9 +272,11 @@ pub struct AksMetricPoint {
     pub memory_usage_percent: Option<f64>,
 }
 
-/// Response from Azure Monitor metrics query
+/// Response from Azure Monitor metrics query. `pub(crate)` so
+/// `azure_sdk::get_metrics_sdk` can deserialize straight into the same
+/// shape the CLI path already parses below.
 #[derive(Debug, Deserialize)]
-struct AzMetricsResponse {
+pub(crate) struct AzMetricsResponse {
     value: Vec<AzMetricValue>,
 }
 
@@ -236,9 +305,36 @@ struct AzDataPoint {
     count: Option<f64>,
 }
 
+/// The same exact/contains/resource-group matching strategies
+/// `detect_aks_cluster`'s CLI path applies per-subscription, pulled out so
+/// the SDK path (which already has every subscription's clusters in hand
+/// from `list_subscriptions_sdk`) can reuse them without a parallel loop.
+fn match_cluster_to_context(clusters: &[AksCluster], ctx_lower: &str, ctx_cleaned: &str) -> Option<String> {
+    for cluster in clusters {
+        let cluster_lower = cluster.name.to_lowercase();
+
+        if ctx_lower == cluster_lower {
+            return Some(cluster.id.clone());
+        }
+        if ctx_lower.contains(&cluster_lower) || ctx_cleaned.contains(&cluster_lower) {
+            return Some(cluster.id.clone());
+        }
+        if cluster_lower.contains(ctx_lower) || cluster_lower.contains(ctx_cleaned) {
+            return Some(cluster.id.clone());
+        }
+
+        let rg_pattern = format!("{}_{}", cluster.resource_group.to_lowercase(), cluster_lower);
+        let rg_pattern2 = format!("{}-{}", cluster.resource_group.to_lowercase(), cluster_lower);
+        if ctx_lower.contains(&rg_pattern) || ctx_lower.contains(&rg_pattern2) {
+            return Some(cluster.id.clone());
+        }
+    }
+    None
+}
+
 /// Detect if the current context is an AKS cluster and return its resource ID
 #[command]
-pub async fn detect_aks_cluster(context_name: String) -> Result<Option<String>, String> {
+pub async fn detect_aks_cluster(context_name: String, backend: Option<String>) -> Result<Option<String>, String> {
     // AKS contexts can have various naming patterns:
     // - Exact cluster name: "my-cluster"
     // - With resource group: "my-rg_my-cluster"
@@ -247,6 +343,37 @@ pub async fn detect_aks_cluster(context_name: String) -> Result<Option<String>,
 
     println!("[AKS Detection] Checking context: {}", context_name);
 
+    // Normalize context name for matching
+    let ctx_lower = context_name.to_lowercase();
+    // Remove common suffixes like -admin, -user
+    let ctx_cleaned = ctx_lower
+        .trim_end_matches("-admin")
+        .trim_end_matches("-user")
+        .trim_end_matches("_admin")
+        .trim_end_matches("_user");
+
+    if use_sdk_backend(&backend) {
+        let client = Arc::new(ArmClient::new().await?);
+        let accounts = azure_sdk::list_subscription_accounts_sdk(&client).await?;
+
+        let mut rx = azure_scan::spawn_subscription_scans(accounts, azure_scan::DEFAULT_SCAN_CONCURRENCY, None, move |sub_id| {
+            let client = client.clone();
+            async move { azure_sdk::list_clusters_sdk(&client, &sub_id).await }
+        });
+
+        // Scans race in the background (spawned, not awaited one at a
+        // time); the first strong match wins and the rest are left to
+        // finish without us waiting on them.
+        while let Some(outcome) = rx.recv().await {
+            if let Some(id) = match_cluster_to_context(&outcome.subscription.clusters, &ctx_lower, ctx_cleaned) {
+                println!("[AKS Detection] Match found via SDK scan: {}", outcome.subscription.name);
+                return Ok(Some(id));
+            }
+        }
+        println!("[AKS Detection] No matching AKS cluster found for context: {}", context_name);
+        return Ok(None);
+    }
+
     // Scan all subscriptions to avoid missing clusters outside the current subscription
     let acct_out = Command::new("az")
         .args(&["account", "list", "--all", "-o", "json"])
@@ -265,61 +392,20 @@ pub async fn detect_aks_cluster(context_name: String) -> Result<Option<String>,
 
     println!("[AKS Detection] Scanning {} subscriptions for AKS clusters", accounts.len());
 
-    // Normalize context name for matching
-    let ctx_lower = context_name.to_lowercase();
-    // Remove common suffixes like -admin, -user
-    let ctx_cleaned = ctx_lower
-        .trim_end_matches("-admin")
-        .trim_end_matches("-user")
-        .trim_end_matches("_admin")
-        .trim_end_matches("_user");
-
-    // Try multiple matching strategies
-    for account in &accounts {
-        let list_out = Command::new("az")
-            .args(&["aks", "list", "--subscription", &account.id, "-o", "json"])
-            .output()
-            .await;
+    // Scan every subscription concurrently instead of one at a time, and
+    // return as soon as a strong match shows up - the rest keep scanning
+    // in the background, but nothing here waits on them.
+    let scan_accounts: Vec<azure_scan::ScanAccount> = accounts.iter().map(az_account_to_scan_account).collect();
+    let mut rx = azure_scan::spawn_subscription_scans(scan_accounts, azure_scan::DEFAULT_SCAN_CONCURRENCY, None, fetch_clusters_cli);
 
-        let clusters: Vec<AzCluster> = match list_out {
-            Ok(o) if o.status.success() => serde_json::from_slice(&o.stdout).unwrap_or_default(),
-            _ => Vec::new(),
-        };
-
-        if clusters.is_empty() {
+    while let Some(outcome) = rx.recv().await {
+        if outcome.subscription.clusters.is_empty() {
             continue;
         }
-
-        println!("[AKS Detection] Found {} AKS clusters in subscription {}", clusters.len(), account.name);
-
-        for cluster in &clusters {
-        let cluster_lower = cluster.name.to_lowercase();
-
-        // Strategy 1: Exact match
-        if ctx_lower == cluster_lower {
-            println!("[AKS Detection] Exact match found: {}", cluster.name);
-            return Ok(Some(cluster.id.clone()));
-        }
-
-        // Strategy 2: Context contains cluster name (e.g., "myaks-admin" contains "myaks")
-        if ctx_lower.contains(&cluster_lower) || ctx_cleaned.contains(&cluster_lower) {
-            println!("[AKS Detection] Context contains cluster: {} in {}", cluster.name, context_name);
-            return Ok(Some(cluster.id.clone()));
-        }
-
-        // Strategy 3: Cluster name contains context (e.g., cluster "dev-myaks" matches context "myaks")
-        if cluster_lower.contains(&ctx_lower) || cluster_lower.contains(ctx_cleaned) {
-            println!("[AKS Detection] Cluster contains context: {} contains {}", cluster.name, context_name);
-            return Ok(Some(cluster.id.clone()));
-        }
-
-        // Strategy 4: Check if context contains resource group pattern "rg_cluster" or "rg-cluster"
-        let rg_pattern = format!("{}_{}", cluster.resource_group.to_lowercase(), cluster_lower);
-        let rg_pattern2 = format!("{}-{}", cluster.resource_group.to_lowercase(), cluster_lower);
-        if ctx_lower.contains(&rg_pattern) || ctx_lower.contains(&rg_pattern2) {
-            println!("[AKS Detection] Resource group pattern match: {}", cluster.name);
-            return Ok(Some(cluster.id.clone()));
-        }
+        println!("[AKS Detection] Found {} AKS clusters in subscription {}", outcome.subscription.clusters.len(), outcome.subscription.name);
+        if let Some(id) = match_cluster_to_context(&outcome.subscription.clusters, &ctx_lower, ctx_cleaned) {
+            println!("[AKS Detection] Match found: {}", outcome.subscription.name);
+            return Ok(Some(id));
         }
     }
 
@@ -332,21 +418,45 @@ pub async fn detect_aks_cluster(context_name: String) -> Result<Option<String>,
 pub async fn get_aks_metrics_history(
     resource_id: String,
     hours: Option<i64>,
+    backend: Option<String>,
 ) -> Result<Vec<AksMetricPoint>, String> {
     let hours = hours.unwrap_or(1); // Default to 1 hour of history
     let end_time = Utc::now();
     let start_time = end_time - Duration::hours(hours);
+    let start_str = start_time.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let end_str = end_time.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+    let (cpu_mem_resp, status_resp) = if use_sdk_backend(&backend) {
+        let client = ArmClient::new().await?;
+        let cpu_mem_resp = azure_sdk::get_metrics_sdk(
+            &client, &resource_id, "node_cpu_usage_percentage,node_memory_working_set_percentage", "Average", &start_str, &end_str,
+        ).await?;
+        let status_resp = azure_sdk::get_metrics_sdk(
+            &client, &resource_id, "kube_node_status_condition,kube_pod_status_ready", "Count", &start_str, &end_str,
+        ).await?;
+        (cpu_mem_resp, status_resp)
+    } else {
+        get_aks_metrics_history_cli(&resource_id, &start_str, &end_str).await?
+    };
+
+    build_metric_points(cpu_mem_resp, status_resp)
+}
 
+async fn get_aks_metrics_history_cli(
+    resource_id: &str,
+    start_str: &str,
+    end_str: &str,
+) -> Result<(AzMetricsResponse, AzMetricsResponse), String> {
     // Query CPU/memory with Average aggregation
     let cpu_mem_out = Command::new("az")
         .args(&[
             "monitor", "metrics", "list",
-            "--resource", &resource_id,
+            "--resource", resource_id,
             "--metric", "node_cpu_usage_percentage,node_memory_working_set_percentage",
             "--aggregation", "Average",
             "--interval", "PT5M",
-            "--start-time", &start_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
-            "--end-time", &end_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            "--start-time", start_str,
+            "--end-time", end_str,
             "-o", "json"
         ])
         .output()
@@ -365,12 +475,12 @@ pub async fn get_aks_metrics_history(
     let status_out = Command::new("az")
         .args(&[
             "monitor", "metrics", "list",
-            "--resource", &resource_id,
+            "--resource", resource_id,
             "--metric", "kube_node_status_condition,kube_pod_status_ready",
             "--aggregation", "Count",
             "--interval", "PT5M",
-            "--start-time", &start_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
-            "--end-time", &end_time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            "--start-time", start_str,
+            "--end-time", end_str,
             "-o", "json"
         ])
         .output()
@@ -385,6 +495,12 @@ pub async fn get_aks_metrics_history(
     let status_resp: AzMetricsResponse = serde_json::from_slice(&status_out.stdout)
         .map_err(|e| format!("Failed to parse status metrics response: {}", e))?;
 
+    Ok((cpu_mem_resp, status_resp))
+}
+
+/// Merge the CPU/memory and status metric responses - whichever backend
+/// produced them - into one timestamp-ordered series.
+fn build_metric_points(cpu_mem_resp: AzMetricsResponse, status_resp: AzMetricsResponse) -> Result<Vec<AksMetricPoint>, String> {
     // Build a map of timestamp -> metrics
     let mut metrics_map: std::collections::HashMap<i64, AksMetricPoint> = std::collections::HashMap::new();
 
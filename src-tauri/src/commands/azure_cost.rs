@@ -0,0 +1,204 @@
+//! Actual AKS spend via the Azure Cost Management query API, complementing
+//! `get_aks_metrics_history`'s CPU/memory numbers with what each cluster
+//! costs. Polled results are cached in [`crate::azure_cost_store`] (SQLite,
+//! one row per cluster per interval) so `get_aks_cost_history` can chart
+//! trends offline instead of re-querying Azure on every UI render.
+//!
+//! Named `get_aks_cost_history` rather than `get_cost_history` to avoid
+//! colliding with `commands::cost::get_cost_history`, which reads the
+//! unrelated namespace-level `PricingProvider` snapshot history.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::{AppHandle, Emitter};
+use tokio::task::JoinHandle;
+
+use crate::azure_cost_store::{self, AksCostRow};
+use super::azure_sdk::ArmClient;
+
+#[derive(Deserialize)]
+struct CostQueryResponse {
+    properties: CostQueryProperties,
+}
+
+#[derive(Deserialize)]
+struct CostQueryProperties {
+    columns: Vec<CostQueryColumn>,
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+#[derive(Deserialize)]
+struct CostQueryColumn {
+    name: String,
+}
+
+/// Query the Cost Management API for `scope` (e.g.
+/// `/subscriptions/{id}` or a resource group scope), grouped by resource
+/// group and meter category at `granularity` ("Daily" or "Monthly").
+async fn query_costs(
+    client: &ArmClient,
+    scope: &str,
+    granularity: &str,
+) -> Result<Vec<AksCostRow>, String> {
+    let url = format!(
+        "https://management.azure.com{}/providers/Microsoft.CostManagement/query?api-version=2023-11-01",
+        scope,
+    );
+
+    let body = serde_json::json!({
+        "type": "ActualCost",
+        "timeframe": "MonthToDate",
+        "dataset": {
+            "granularity": granularity,
+            "aggregation": {
+                "totalCost": { "name": "Cost", "function": "Sum" }
+            },
+            "grouping": [
+                { "type": "Dimension", "name": "ResourceGroupName" },
+                { "type": "Dimension", "name": "MeterCategory" }
+            ]
+        }
+    });
+
+    let response: CostQueryResponse = client.post_json_with_body(&url, &body).await?;
+
+    let column_index = |name: &str| response.properties.columns.iter().position(|c| c.name == name);
+    let cost_idx = column_index("Cost").ok_or("AZURE_COST_QUERY_FAILED||Cost Management response missing Cost column|")?;
+    let currency_idx = column_index("Currency").ok_or("AZURE_COST_QUERY_FAILED||Cost Management response missing Currency column|")?;
+    let date_idx = column_index("UsageDate").ok_or("AZURE_COST_QUERY_FAILED||Cost Management response missing UsageDate column|")?;
+    let meter_idx = column_index("MeterCategory").ok_or("AZURE_COST_QUERY_FAILED||Cost Management response missing MeterCategory column|")?;
+    let rg_idx = column_index("ResourceGroupName").ok_or("AZURE_COST_QUERY_FAILED||Cost Management response missing ResourceGroupName column|")?;
+
+    let rows = response.properties.rows.into_iter().map(|row| {
+        AksCostRow {
+            resource_id: scope.to_string(),
+            resource_group: row.get(rg_idx).and_then(|v| v.as_str()).unwrap_or("unknown").to_string(),
+            timestamp: row.get(date_idx)
+                .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .and_then(usage_date_to_rfc3339)
+                .unwrap_or_default(),
+            cost: row.get(cost_idx).and_then(|v| v.as_f64()).unwrap_or(0.0),
+            currency: row.get(currency_idx).and_then(|v| v.as_str()).unwrap_or("USD").to_string(),
+            meter_category: row.get(meter_idx).and_then(|v| v.as_str()).unwrap_or("Unknown").to_string(),
+        }
+    }).collect();
+
+    Ok(rows)
+}
+
+/// Cost Management's `UsageDate` column is an 8-digit `YYYYMMDD` integer
+/// (e.g. `20260115`), not RFC3339 - convert it so it sorts and filters
+/// correctly against the RFC3339 timestamps `azure_cost_store` compares it
+/// against as plain strings.
+fn usage_date_to_rfc3339(usage_date: i64) -> Option<String> {
+    let year = usage_date / 10_000;
+    let month = (usage_date / 100) % 100;
+    let day = usage_date % 100;
+    chrono::NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc).to_rfc3339())
+}
+
+/// List each AKS cluster's cost for the current month-to-date under
+/// `subscription_id`, grouped by resource group and meter category.
+/// `scope` overrides the default `/subscriptions/{subscription_id}` scope
+/// for callers that want to narrow to a resource group or management
+/// group scope instead.
+#[tauri::command]
+pub async fn get_aks_costs(subscription_id: String, scope: Option<String>, granularity: Option<String>) -> Result<Vec<AksCostRow>, String> {
+    let client = ArmClient::new().await?;
+    let scope = scope.unwrap_or_else(|| format!("/subscriptions/{}", subscription_id));
+    let granularity = granularity.unwrap_or_else(|| "Daily".to_string());
+    query_costs(&client, &scope, &granularity).await
+}
+
+/// Cached cost history for `resource_id` over the last `days` days, read
+/// entirely from the local cache populated by `start_aks_cost_tracking` -
+/// no Azure call on the read path.
+#[tauri::command]
+pub async fn get_aks_cost_history(resource_id: String, days: Option<i64>) -> Result<Vec<azure_cost_store::CostHistoryPoint>, String> {
+    azure_cost_store::get_cost_history(&resource_id, days.unwrap_or(30))
+}
+
+/// Running pollers, keyed by `resource_id` - starting a poller for a
+/// resource that already has one aborts the old task first, same as
+/// `McpManager::spawn_health_check` replacing an existing health-check task.
+static POLLERS: LazyLock<Mutex<HashMap<String, JoinHandle<()>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Start a background poller that pulls `scope`'s AKS costs every
+/// `interval_minutes`, filters the result down to `resource_group` (since
+/// `scope` may cover a whole subscription spanning several resource
+/// groups), caches the rest under `resource_id`, prunes anything past
+/// `retention_days`, and emits `azure:cost_update` on each successful poll
+/// - mirroring the `azure:subscription_update` pattern `azure_scan`
+/// already emits for cluster discovery.
+#[tauri::command]
+pub async fn start_aks_cost_tracking(
+    app: AppHandle,
+    resource_id: String,
+    resource_group: String,
+    scope: String,
+    granularity: Option<String>,
+    interval_minutes: Option<u64>,
+    retention_days: Option<i64>,
+) -> Result<(), String> {
+    let granularity = granularity.unwrap_or_else(|| "Daily".to_string());
+    let interval = Duration::from_secs(interval_minutes.unwrap_or(60) * 60);
+    let retention_days = retention_days.unwrap_or(azure_cost_store::DEFAULT_RETENTION_DAYS);
+
+    let task_resource_id = resource_id.clone();
+    let handle = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+
+            let client = match ArmClient::new().await {
+                Ok(client) => client,
+                Err(e) => {
+                    eprintln!("[azure-cost] Failed to acquire ARM token for '{}': {}", task_resource_id, e);
+                    continue;
+                }
+            };
+
+            let rows = match query_costs(&client, &scope, &granularity).await {
+                Ok(rows) => rows.into_iter()
+                    .filter(|r| r.resource_group.eq_ignore_ascii_case(&resource_group))
+                    .map(|mut r| { r.resource_id = task_resource_id.clone(); r })
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    eprintln!("[azure-cost] Poll failed for '{}': {}", task_resource_id, e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = azure_cost_store::record_costs(&rows) {
+                eprintln!("[azure-cost] Failed to cache costs for '{}': {}", task_resource_id, e);
+                continue;
+            }
+            if let Err(e) = azure_cost_store::prune_older_than(retention_days) {
+                eprintln!("[azure-cost] Failed to prune cost history: {}", e);
+            }
+
+            let _ = app.emit("azure:cost_update", &rows);
+        }
+    });
+
+    if let Some(old) = POLLERS.lock().map_err(|e| format!("Cost poller registry lock poisoned: {}", e))?.insert(resource_id, handle) {
+        old.abort();
+    }
+
+    Ok(())
+}
+
+/// Stop the poller started by `start_aks_cost_tracking` for `resource_id`,
+/// if one is running.
+#[tauri::command]
+pub async fn stop_aks_cost_tracking(resource_id: String) -> Result<(), String> {
+    if let Some(handle) = POLLERS.lock().map_err(|e| format!("Cost poller registry lock poisoned: {}", e))?.remove(&resource_id) {
+        handle.abort();
+    }
+    Ok(())
+}
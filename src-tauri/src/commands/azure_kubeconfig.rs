@@ -0,0 +1,235 @@
+//! Native kubeconfig merge for AKS credentials, replacing the
+//! `az aks get-credentials` + `kubelogin convert-kubeconfig` shell-outs
+//! whose failures `get_aks_credentials` used to swallow with `let _ = `.
+//!
+//! AKS's `listClusterUserCredential`/`listClusterAdminCredential` ARM APIs
+//! already return a complete, ready-to-merge kubeconfig (cluster CA,
+//! server URL, and a context/user pointing at it) as a base64 blob, so
+//! there's no need to hand-assemble those fields - this module decodes
+//! that blob, rewrites its `exec` stanza to call `kubelogin get-token`
+//! directly instead of whatever `az` embedded, and merges the result into
+//! `~/.kube/config` by name, same as `context.rs::delete_context` already
+//! does for deletes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use serde::Deserialize;
+
+use super::azure_sdk::{ArmClient, ARM_BASE};
+
+/// `kubelogin`'s `-l` auth mode flag - the only three AKS supports for
+/// non-interactive-free `get-token`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AksAuthMode {
+    AzureCli,
+    DeviceCode,
+    Interactive,
+}
+
+impl AksAuthMode {
+    pub fn from_str(mode: Option<&str>) -> Self {
+        match mode {
+            Some("devicecode") => AksAuthMode::DeviceCode,
+            Some("interactive") => AksAuthMode::Interactive,
+            _ => AksAuthMode::AzureCli,
+        }
+    }
+
+    fn kubelogin_flag(self) -> &'static str {
+        match self {
+            AksAuthMode::AzureCli => "azurecli",
+            AksAuthMode::DeviceCode => "devicecode",
+            AksAuthMode::Interactive => "interactive",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CredentialResult {
+    kubeconfigs: Vec<CredentialKubeconfig>,
+}
+
+#[derive(Deserialize)]
+struct CredentialKubeconfig {
+    value: String,
+}
+
+/// Pull the cluster's kubeconfig straight from ARM - no `az` CLI involved.
+/// `admin` selects `listClusterAdminCredential` (static client cert, no
+/// exec plugin needed) over the default `listClusterUserCredential`
+/// (Azure AD exec auth, the path this module rewrites).
+async fn fetch_cluster_kubeconfig(
+    client: &ArmClient,
+    subscription_id: &str,
+    resource_group: &str,
+    name: &str,
+    admin: bool,
+) -> Result<serde_yaml::Value, String> {
+    let action = if admin { "listClusterAdminCredential" } else { "listClusterUserCredential" };
+    let url = format!(
+        "{}/subscriptions/{}/resourceGroups/{}/providers/Microsoft.ContainerService/managedClusters/{}/{}?api-version=2023-08-01",
+        ARM_BASE, subscription_id, resource_group, name, action,
+    );
+
+    let result: CredentialResult = client.post_json(&url).await?;
+    let encoded = result.kubeconfigs.into_iter().next()
+        .ok_or_else(|| format!("AZURE_SDK_REQUEST_FAILED|{}|ARM returned no kubeconfig blob|", name))?
+        .value;
+
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED|{}|Failed to decode kubeconfig blob: {}|", name, e))?;
+
+    serde_yaml::from_slice(&decoded)
+        .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED|{}|Failed to parse kubeconfig blob: {}|", name, e))
+}
+
+/// Build the `users[].user.exec` stanza `kubelogin get-token` expects,
+/// matching what `kubelogin convert-kubeconfig` would otherwise rewrite
+/// in place - done here directly so credential setup doesn't depend on
+/// `kubelogin` being invoked as a separate (and previously
+/// error-swallowed) step.
+fn build_kubelogin_exec(mode: AksAuthMode, environment: &str, server_id: &str) -> serde_yaml::Value {
+    let mapping: serde_yaml::Mapping = [
+        ("apiVersion".into(), "client.authentication.k8s.io/v1beta1".into()),
+        ("command".into(), "kubelogin".into()),
+        ("args".into(), serde_yaml::Value::Sequence(vec![
+            "get-token".into(),
+            "-l".into(), mode.kubelogin_flag().into(),
+            "--environment".into(), environment.into(),
+            "--server-id".into(), server_id.into(),
+        ])),
+        ("provideClusterInfo".into(), false.into()),
+    ].into_iter().collect();
+    serde_yaml::Value::Mapping(mapping)
+}
+
+/// Replace `users[<user_name>].user.exec` in `kubeconfig` with `exec`,
+/// refusing to write back a config whose `exec.command` would be empty
+/// rather than leaving the user with a half-written, unusable entry.
+fn rewrite_exec_for_user(kubeconfig: &mut serde_yaml::Value, user_name: &str, exec: serde_yaml::Value) -> Result<(), String> {
+    let command_present = exec.get("command").and_then(|c| c.as_str()).map(|s| !s.is_empty()).unwrap_or(false);
+    if !command_present {
+        return Err(format!("MISSING_EXEC_COMMAND|{}|exec config has no command to invoke|", user_name));
+    }
+
+    let users = kubeconfig.get_mut("users")
+        .and_then(|u| u.as_sequence_mut())
+        .ok_or_else(|| format!("MISSING_EXEC_COMMAND|{}|kubeconfig has no users section|", user_name))?;
+
+    let user_entry = users.iter_mut()
+        .find(|u| u.get("name").and_then(|n| n.as_str()) == Some(user_name))
+        .ok_or_else(|| format!("MISSING_EXEC_COMMAND|{}|user not found in kubeconfig|", user_name))?;
+
+    let user_mapping = user_entry.get_mut("user")
+        .and_then(|u| u.as_mapping_mut())
+        .ok_or_else(|| format!("MISSING_EXEC_COMMAND|{}|user entry has no user block|", user_name))?;
+    user_mapping.insert("exec".into(), exec);
+    Ok(())
+}
+
+/// Merge one entry of `incoming`'s sequence (matched by `name`) into
+/// `target`'s same-named list, overwriting an existing entry in place or
+/// appending if this is the first time this name has been seen -
+/// `delete_context`'s removal logic, run in reverse.
+fn merge_named_entry(target: &mut serde_yaml::Value, section: &str, entry: serde_yaml::Value) {
+    let name = entry.get("name").and_then(|n| n.as_str()).map(|s| s.to_string());
+
+    if target.get(section).and_then(|s| s.as_sequence()).is_none() {
+        if let Some(mapping) = target.as_mapping_mut() {
+            mapping.insert(section.into(), serde_yaml::Value::Sequence(Vec::new()));
+        }
+    }
+
+    if let Some(list) = target.get_mut(section).and_then(|s| s.as_sequence_mut()) {
+        if let Some(name) = &name {
+            list.retain(|e| e.get("name").and_then(|n| n.as_str()) != Some(name.as_str()));
+        }
+        list.push(entry);
+    }
+}
+
+fn default_kubeconfig_path(custom_path: &Option<String>) -> Result<PathBuf, String> {
+    if let Some(path) = custom_path {
+        return Ok(PathBuf::from(path));
+    }
+    let home = dirs::home_dir().ok_or("Could not find HOME directory")?;
+    Ok(home.join(".kube").join("config"))
+}
+
+fn read_kubeconfig_or_empty(path: &Path) -> Result<serde_yaml::Value, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse kubeconfig: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(serde_yaml::from_str(
+            "apiVersion: v1\nkind: Config\npreferences: {}\nclusters: []\nusers: []\ncontexts: []\ncurrent-context: \"\"\n",
+        ).unwrap()),
+        Err(e) => Err(format!("Failed to read kubeconfig: {}", e)),
+    }
+}
+
+/// Write `kubeconfig` to `path` atomically (write-to-temp, then rename)
+/// so a crash or concurrent read mid-write never observes a truncated or
+/// half-merged file - something `delete_context`'s direct
+/// `File::create`/`write_all` doesn't guard against.
+fn write_kubeconfig_atomic(path: &Path, kubeconfig: &serde_yaml::Value) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create kubeconfig directory: {}", e))?;
+    }
+    let content = serde_yaml::to_string(kubeconfig).map_err(|e| format!("Failed to serialize kubeconfig: {}", e))?;
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content).map_err(|e| format!("Failed to write kubeconfig: {}", e))?;
+    fs::rename(&tmp_path, path).map_err(|e| format!("Failed to finalize kubeconfig: {}", e))?;
+    Ok(())
+}
+
+/// Fetch an AKS cluster's kubeconfig from ARM, rewrite its exec stanza to
+/// call `kubelogin get-token` in `mode`, and merge it into
+/// `custom_path` (or `~/.kube/config`) - entirely without shelling out to
+/// `az` or `kubelogin convert-kubeconfig`.
+pub async fn merge_aks_credentials(
+    client: &ArmClient,
+    subscription_id: &str,
+    resource_group: &str,
+    name: &str,
+    mode: AksAuthMode,
+    admin: bool,
+    custom_path: &Option<String>,
+) -> Result<(), String> {
+    let environment = "AzurePublicCloud";
+    let server_id = "6dae42f8-4368-4678-94ff-3960e28e3630"; // AKS AAD server app ID, same constant kubelogin ships with
+
+    let mut incoming = fetch_cluster_kubeconfig(client, subscription_id, resource_group, name, admin).await?;
+
+    let user_name = incoming.get("users")
+        .and_then(|u| u.as_sequence())
+        .and_then(|u| u.first())
+        .and_then(|u| u.get("name"))
+        .and_then(|n| n.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("MISSING_EXEC_COMMAND|{}|ARM kubeconfig has no users entry|", name))?;
+
+    if !admin {
+        let exec = build_kubelogin_exec(mode, environment, server_id);
+        rewrite_exec_for_user(&mut incoming, &user_name, exec)?;
+    }
+
+    let kubeconfig_path = default_kubeconfig_path(custom_path)?;
+    let mut target = read_kubeconfig_or_empty(&kubeconfig_path)?;
+
+    for section in ["clusters", "users", "contexts"] {
+        if let Some(entries) = incoming.get(section).and_then(|s| s.as_sequence()) {
+            for entry in entries.clone() {
+                merge_named_entry(&mut target, section, entry);
+            }
+        }
+    }
+
+    if let Some(current) = incoming.get("current-context").and_then(|c| c.as_str()) {
+        target["current-context"] = current.into();
+    }
+
+    write_kubeconfig_atomic(&kubeconfig_path, &target)
+}
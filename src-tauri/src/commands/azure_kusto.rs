@@ -0,0 +1,127 @@
+//! Log Analytics / Kusto query engine for Container Insights, giving the
+//! event-level history (pod restarts, node pressure, OOMKilled pods) that
+//! `get_aks_metrics_history`'s platform metrics can't provide.
+//!
+//! There's no Azure Data Explorer client crate in this workspace, so this
+//! is a minimal hand-rolled client: reuse `azure_sdk`'s credential chain
+//! scoped to the Log Analytics query API's audience, POST the query to
+//! the workspace's query endpoint, and parse the `tables[].columns` /
+//! `tables[].rows` columnar shape into a generic [`KustoTable`] the
+//! frontend can render without per-query types.
+
+use serde::{Deserialize, Serialize};
+
+use super::azure_sdk;
+
+const LOG_ANALYTICS_BASE: &str = "https://api.loganalytics.io";
+const LOG_ANALYTICS_RESOURCE: &str = "https://api.loganalytics.io/.default";
+
+/// One column of a Kusto result table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnMeta {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: String,
+}
+
+/// A generic Kusto result table - deliberately untyped per-row so the
+/// frontend can render arbitrary query output (canned or raw KQL) without
+/// a matching Rust struct for every query shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KustoTable {
+    pub columns: Vec<ColumnMeta>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+#[derive(Deserialize)]
+struct KustoQueryResponse {
+    tables: Vec<KustoResponseTable>,
+}
+
+#[derive(Deserialize)]
+struct KustoResponseTable {
+    columns: Vec<ColumnMeta>,
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// Run `kql` against `workspace_id`'s Log Analytics workspace and return
+/// the first result table.
+async fn run_kusto_query(workspace_id: &str, kql: &str, timespan: &str) -> Result<KustoTable, String> {
+    let token = azure_sdk::acquire_token(LOG_ANALYTICS_BASE, LOG_ANALYTICS_RESOURCE).await?;
+    let url = format!("{}/v1/workspaces/{}/query", LOG_ANALYTICS_BASE, workspace_id);
+
+    let body = serde_json::json!({
+        "query": kql,
+        "timespan": timespan,
+    });
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("AZURE_KUSTO_QUERY_FAILED||Failed to reach Log Analytics: {}|", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        return Err(format!("AZURE_KUSTO_QUERY_FAILED||Log Analytics query returned {}: {}|", status, text));
+    }
+
+    let mut parsed: KustoQueryResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("AZURE_KUSTO_QUERY_FAILED||Failed to parse Log Analytics response: {}|", e))?;
+
+    let table = parsed.tables.drain(..).next()
+        .ok_or_else(|| "AZURE_KUSTO_QUERY_FAILED||Log Analytics returned no result tables|".to_string())?;
+
+    Ok(KustoTable { columns: table.columns, rows: table.rows })
+}
+
+/// A named, pre-written KQL query users can pick from instead of writing
+/// raw KQL. Kept small and focused on the cases platform metrics miss.
+fn canned_query(name: &str) -> Option<&'static str> {
+    match name {
+        "pod_restarts_by_namespace" => Some(
+            "KubePodInventory \
+             | where ContainerStatus != \"\" \
+             | summarize RestartCount = sum(ContainerRestartCount) by Namespace, bin(TimeGenerated, 1h) \
+             | order by TimeGenerated desc",
+        ),
+        "node_pressure_conditions" => Some(
+            "KubeNodeInventory \
+             | where Status has \"Pressure\" or Status has \"NotReady\" \
+             | project TimeGenerated, Computer, Status \
+             | order by TimeGenerated desc",
+        ),
+        "oom_killed_events" => Some(
+            "KubePodInventory \
+             | where ContainerStatusReason == \"OOMKilled\" \
+             | project TimeGenerated, Namespace, Name, ContainerName, ContainerStatusReason \
+             | order by TimeGenerated desc",
+        ),
+        _ => None,
+    }
+}
+
+/// Run a raw KQL query against a cluster's Log Analytics workspace - the
+/// escape hatch for anything the canned queries don't cover.
+#[tauri::command]
+pub async fn query_aks_insights(workspace_id: String, kql: String, timespan: Option<String>) -> Result<KustoTable, String> {
+    let timespan = timespan.unwrap_or_else(|| "PT1H".to_string());
+    run_kusto_query(&workspace_id, &kql, &timespan).await
+}
+
+/// Run one of the built-in Container Insights queries by name
+/// (`pod_restarts_by_namespace`, `node_pressure_conditions`,
+/// `oom_killed_events`).
+#[tauri::command]
+pub async fn query_aks_insights_canned(workspace_id: String, canned_name: String, timespan: Option<String>) -> Result<KustoTable, String> {
+    let kql = canned_query(&canned_name)
+        .ok_or_else(|| format!("AZURE_UNKNOWN_CANNED_QUERY||Unknown canned query: {}|", canned_name))?;
+    let timespan = timespan.unwrap_or_else(|| "PT1H".to_string());
+    run_kusto_query(&workspace_id, kql, &timespan).await
+}
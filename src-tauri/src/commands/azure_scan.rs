@@ -0,0 +1,109 @@
+//! Bounded-concurrency subscription scanning shared by `refresh_azure_data`
+//! and `detect_aks_cluster`. Both used to fan out per-subscription cluster
+//! fetches with no concurrency cap (`refresh_azure_data` via `join_all`,
+//! `detect_aks_cluster` serially in a loop), which either hammers the
+//! Azure API for large tenants or blocks the UI. This runs every fetch
+//! through a bounded semaphore instead, and streams each subscription's
+//! outcome back over a channel as soon as it's ready rather than waiting
+//! for every subscription to finish - so `detect_aks_cluster` can return
+//! on the first strong match while slower subscriptions are still being
+//! scanned in the background, and `refresh_azure_data` can keep emitting
+//! `azure:subscription_update` incrementally like it already does.
+
+use std::sync::Arc;
+use std::future::Future;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, Semaphore};
+
+use super::azure::{AksCluster, AzureSubscription};
+
+/// Default number of subscriptions scanned concurrently - enough to keep
+/// large tenants from taking minutes, without firing 40+ requests at
+/// Azure Resource Manager at once.
+pub const DEFAULT_SCAN_CONCURRENCY: usize = 8;
+
+/// A subscription whose cluster fetch failed, kept alongside the
+/// successful subscriptions rather than silently becoming an empty
+/// cluster list, so the frontend can surface "N of M subscriptions
+/// failed to scan".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionError {
+    pub subscription_id: String,
+    pub subscription_name: String,
+    pub message: String,
+}
+
+/// The minimal per-account info the scan needs - deliberately not tied to
+/// `azure::AzAccount` so this module doesn't need to know how the caller
+/// sourced its account list (CLI output or an ARM subscriptions call).
+pub struct ScanAccount {
+    pub id: String,
+    pub name: String,
+    pub is_default: bool,
+}
+
+pub struct SubscriptionScanOutcome {
+    pub subscription: AzureSubscription,
+    pub error: Option<SubscriptionError>,
+}
+
+/// Fan out `fetch_clusters` across `accounts` through a semaphore capped
+/// at `concurrency`, optionally emitting `azure:subscription_update` as
+/// each one settles. Returns the receiving half of a channel the caller
+/// drains at its own pace - draining it fully waits for every
+/// subscription, but a caller that stops partway (like `detect_aks_cluster`
+/// on its first match) leaves the rest running in the background rather
+/// than blocking on them.
+pub fn spawn_subscription_scans<F, Fut>(
+    accounts: Vec<ScanAccount>,
+    concurrency: usize,
+    app: Option<AppHandle>,
+    fetch_clusters: F,
+) -> mpsc::UnboundedReceiver<SubscriptionScanOutcome>
+where
+    F: Fn(String) -> Fut + Send + Sync + Clone + 'static,
+    Fut: Future<Output = Result<Vec<AksCluster>, String>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    for account in accounts {
+        let semaphore = semaphore.clone();
+        let tx = tx.clone();
+        let app = app.clone();
+        let fetch_clusters = fetch_clusters.clone();
+
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+
+            let result = fetch_clusters(account.id.clone()).await;
+            let (clusters, error) = match result {
+                Ok(clusters) => (clusters, None),
+                Err(message) => (
+                    Vec::new(),
+                    Some(SubscriptionError {
+                        subscription_id: account.id.clone(),
+                        subscription_name: account.name.clone(),
+                        message,
+                    }),
+                ),
+            };
+
+            let subscription = AzureSubscription {
+                id: account.id,
+                name: account.name,
+                is_default: account.is_default,
+                clusters,
+            };
+
+            if let Some(app) = &app {
+                let _ = app.emit("azure:subscription_update", &subscription);
+            }
+
+            let _ = tx.send(SubscriptionScanOutcome { subscription, error });
+        });
+    }
+
+    rx
+}
@@ -0,0 +1,297 @@
+//! Native ARM REST backend for the Azure commands in [`super::azure`], as
+//! an alternative to shelling out to the `az` CLI. Callers pick a backend
+//! per-call via the `backend: Option<String>` parameter each command
+//! exposes (`"cli"` or `"sdk"`, defaulting to `"cli"` for compatibility
+//! with existing installs) rather than a global setting, matching the
+//! `Option<T>`-with-default idiom the rest of this crate uses for tunable
+//! parameters.
+//!
+//! There's no `azure_identity`/`azure_core` dependency in this workspace,
+//! so credential acquisition mimics `DefaultAzureCredential`'s chain by
+//! hand: an `EnvironmentCredential`-style client-credentials grant when
+//! `AZURE_CLIENT_ID`/`AZURE_CLIENT_SECRET`/`AZURE_TENANT_ID` are set, then
+//! a `CliCredential`-style fallback that reads the token `az` already has
+//! cached (`az account get-access-token`) rather than spawning a full
+//! `az aks`/`az monitor` subcommand per call like the CLI backend does.
+//! Once acquired, every ARM call goes through [`ArmClient`], which attaches
+//! the bearer token the same way on every request.
+
+use serde::de::DeserializeOwned;
+use tokio::process::Command;
+
+use super::azure::{AksCluster, PowerState};
+
+pub(crate) const ARM_BASE: &str = "https://management.azure.com";
+const ARM_RESOURCE: &str = "https://management.azure.com/.default";
+
+/// Acquire an ARM bearer token, preferring an explicit service principal
+/// over the CLI's cached login so headless/CI use doesn't depend on `az`
+/// being installed at all.
+async fn acquire_arm_token() -> Result<String, String> {
+    acquire_token(ARM_BASE, ARM_RESOURCE).await
+}
+
+#[derive(serde::Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// Shared credential chain for any Azure REST audience: an
+/// `EnvironmentCredential`-style client-credentials grant against
+/// `resource_scope` when `AZURE_CLIENT_ID`/`AZURE_CLIENT_SECRET`/
+/// `AZURE_TENANT_ID` are set, falling back to whatever token `az` already
+/// has cached for `resource_audience`. Used by [`ArmClient`] for
+/// management.azure.com and by `azure_kusto` for api.loganalytics.io.
+pub(crate) async fn acquire_token(resource_audience: &str, resource_scope: &str) -> Result<String, String> {
+    if let (Ok(tenant_id), Ok(client_id), Ok(client_secret)) = (
+        std::env::var("AZURE_TENANT_ID"),
+        std::env::var("AZURE_CLIENT_ID"),
+        std::env::var("AZURE_CLIENT_SECRET"),
+    ) {
+        return acquire_token_client_credentials(&tenant_id, &client_id, &client_secret, resource_scope).await;
+    }
+
+    acquire_token_from_cli_cache(resource_audience).await
+}
+
+async fn acquire_token_client_credentials(tenant_id: &str, client_id: &str, client_secret: &str, resource_scope: &str) -> Result<String, String> {
+    let url = format!("https://login.microsoftonline.com/{}/oauth2/v2.0/token", tenant_id);
+    let params = [
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+        ("scope", resource_scope),
+        ("grant_type", "client_credentials"),
+    ];
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(&url)
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| format!("AZURE_SDK_AUTH_FAILED||Failed to reach Azure AD token endpoint: {}|", e))?;
+
+    if !resp.status().is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("AZURE_SDK_AUTH_FAILED||Azure AD rejected client-credentials login: {}|", body));
+    }
+
+    let token: TokenResponse = resp
+        .json()
+        .await
+        .map_err(|e| format!("AZURE_SDK_AUTH_FAILED||Failed to parse Azure AD token response: {}|", e))?;
+
+    Ok(token.access_token)
+}
+
+/// Fall back to whatever token `az login` already cached - this is the
+/// one place the SDK backend still touches the CLI, since there's no
+/// standalone way to read the OS credential manager's cached MSAL tokens
+/// without the `azure_identity` crate.
+async fn acquire_token_from_cli_cache(resource_audience: &str) -> Result<String, String> {
+    let output = Command::new("az")
+        .args(&["account", "get-access-token", "--resource", resource_audience, "-o", "json"])
+        .output()
+        .await
+        .map_err(|e| format!("AZURE_SDK_AUTH_FAILED||No service principal configured and az CLI unavailable: {}|", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("AZURE_LOGIN_REQUIRED||No cached Azure login found: {}|az login", stderr));
+    }
+
+    #[derive(serde::Deserialize)]
+    struct CliToken {
+        #[serde(rename = "accessToken")]
+        access_token: String,
+    }
+
+    let token: CliToken = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("AZURE_SDK_AUTH_FAILED||Failed to parse cached az token: {}|", e))?;
+
+    Ok(token.access_token)
+}
+
+/// A bearer-token-carrying ARM client. Every request goes through
+/// [`ArmClient::get_json`], which is where the token gets attached, so
+/// refresh only ever needs to change one place.
+pub struct ArmClient {
+    http: reqwest::Client,
+    token: String,
+}
+
+impl ArmClient {
+    pub async fn new() -> Result<Self, String> {
+        let token = acquire_arm_token().await?;
+        Ok(Self { http: reqwest::Client::new(), token })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        let resp = self.http
+            .get(url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .send()
+            .await
+            .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED||Failed to reach {}: {}|", url, e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("AZURE_SDK_REQUEST_FAILED||ARM request to {} returned {}: {}|", url, status, body));
+        }
+
+        resp.json::<T>()
+            .await
+            .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED||Failed to parse response from {}: {}|", url, e))
+    }
+
+    /// `POST` with an empty body, the shape AKS's `listCluster*Credential`
+    /// actions expect (ARM treats them as actions, not reads, even though
+    /// they take no request payload).
+    pub(crate) async fn post_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        let resp = self.http
+            .post(url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Content-Length", "0")
+            .send()
+            .await
+            .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED||Failed to reach {}: {}|", url, e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("AZURE_SDK_REQUEST_FAILED||ARM request to {} returned {}: {}|", url, status, body));
+        }
+
+        resp.json::<T>()
+            .await
+            .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED||Failed to parse response from {}: {}|", url, e))
+    }
+
+    /// `POST` with a JSON body - the shape query-style ARM actions like
+    /// Cost Management's `query` action expect.
+    pub(crate) async fn post_json_with_body<T: DeserializeOwned>(&self, url: &str, body: &serde_json::Value) -> Result<T, String> {
+        let resp = self.http
+            .post(url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED||Failed to reach {}: {}|", url, e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            return Err(format!("AZURE_SDK_REQUEST_FAILED||ARM request to {} returned {}: {}|", url, status, text));
+        }
+
+        resp.json::<T>()
+            .await
+            .map_err(|e| format!("AZURE_SDK_REQUEST_FAILED||Failed to parse response from {}: {}|", url, e))
+    }
+
+    pub fn raw_token(&self) -> &str {
+        &self.token
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ArmListResponse<T> {
+    value: Vec<T>,
+}
+
+#[derive(serde::Deserialize)]
+struct ArmSubscription {
+    #[serde(rename = "subscriptionId")]
+    subscription_id: String,
+    #[serde(rename = "displayName")]
+    display_name: String,
+    state: String,
+}
+
+#[derive(serde::Deserialize)]
+struct ArmManagedCluster {
+    id: String,
+    name: String,
+    location: String,
+    properties: Option<ArmManagedClusterProperties>,
+}
+
+#[derive(serde::Deserialize)]
+struct ArmManagedClusterProperties {
+    #[serde(rename = "powerState")]
+    power_state: Option<ArmPowerState>,
+}
+
+#[derive(serde::Deserialize)]
+struct ArmPowerState {
+    code: String,
+}
+
+/// `GET /subscriptions?api-version=2020-01-01`, returning just the
+/// enabled subscriptions themselves - cluster fetching is a separate,
+/// per-subscription call so callers can run it through
+/// `azure_scan::spawn_subscription_scans` instead of fetching serially.
+pub async fn list_subscription_accounts_sdk(client: &ArmClient) -> Result<Vec<super::azure_scan::ScanAccount>, String> {
+    let url = format!("{}/subscriptions?api-version=2020-01-01", ARM_BASE);
+    let resp: ArmListResponse<ArmSubscription> = client.get_json(&url).await?;
+
+    Ok(resp.value
+        .into_iter()
+        .filter(|s| s.state.eq_ignore_ascii_case("enabled"))
+        .map(|s| super::azure_scan::ScanAccount {
+            id: s.subscription_id,
+            name: s.display_name,
+            is_default: false, // ARM has no concept of the CLI's "current" subscription
+        })
+        .collect())
+}
+
+/// `GET /subscriptions/{id}/providers/Microsoft.ContainerService/managedClusters?api-version=2023-08-01`
+pub async fn list_clusters_sdk(client: &ArmClient, subscription_id: &str) -> Result<Vec<AksCluster>, String> {
+    let url = format!(
+        "{}/subscriptions/{}/providers/Microsoft.ContainerService/managedClusters?api-version=2023-08-01",
+        ARM_BASE, subscription_id
+    );
+    let resp: ArmListResponse<ArmManagedCluster> = client.get_json(&url).await?;
+
+    Ok(resp.value.into_iter().map(|c| {
+        // managedClusters IDs look like
+        // /subscriptions/{sub}/resourceGroups/{rg}/providers/Microsoft.ContainerService/managedClusters/{name}
+        let resource_group = c.id
+            .split('/')
+            .collect::<Vec<_>>()
+            .windows(2)
+            .find(|w| w[0].eq_ignore_ascii_case("resourceGroups"))
+            .map(|w| w[1].to_string())
+            .unwrap_or_default();
+
+        AksCluster {
+            id: c.id,
+            name: c.name,
+            resource_group,
+            location: c.location,
+            power_state: PowerState {
+                code: c.properties.and_then(|p| p.power_state).map(|p| p.code).unwrap_or_else(|| "Running".to_string()),
+            },
+        }
+    }).collect())
+}
+
+/// `GET {resourceId}/providers/microsoft.insights/metrics`, returning the
+/// same raw response shape `get_aks_metrics_history`'s CLI path already
+/// parses, so callers don't need a second deserialization path.
+pub async fn get_metrics_sdk(
+    client: &ArmClient,
+    resource_id: &str,
+    metric_names: &str,
+    aggregation: &str,
+    start_time: &str,
+    end_time: &str,
+) -> Result<super::azure::AzMetricsResponse, String> {
+    let url = format!(
+        "{}{}/providers/microsoft.insights/metrics?api-version=2019-07-01&metricnames={}&aggregation={}&interval=PT5M&timespan={}/{}",
+        ARM_BASE, resource_id, metric_names, aggregation, start_time, end_time
+    );
+    client.get_json(&url).await
+}
@@ -94,6 +94,7 @@ pub async fn call_claude_code(
 
     // Add the prompt
     cmd.arg(&prompt)
+       .stdin(Stdio::piped())
        .stdout(Stdio::piped())
        .stderr(Stdio::piped());
 
@@ -106,11 +107,14 @@ pub async fn call_claude_code(
 
     let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn Claude: {}", e))?;
 
+    let stdin = child.stdin.take().ok_or("Failed to capture stdin")?;
     let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
     let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    let stdin = std::sync::Arc::new(tokio::sync::Mutex::new(stdin));
 
     let app_clone = app.clone();
     let app_clone2 = app.clone();
+    let interactive = mode == "default";
 
     // Spawn task to read stdout (streaming JSON)
     let stdout_handle = tokio::spawn(async move {
@@ -118,8 +122,29 @@ pub async fn call_claude_code(
         let mut lines = reader.lines();
 
         while let Ok(Some(line)) = lines.next_line().await {
+            // In "default" mode, Claude pauses before each tool call and
+            // waits on a `control_response` over stdin - intercept those
+            // lines instead of forwarding them verbatim to `claude:stream`.
+            if interactive {
+                if let Some((request_id, tool_name, input)) = crate::commands::claude_permissions::parse_control_request(&line) {
+                    let app_for_permission = app_clone.clone();
+                    let stdin_for_permission = stdin.clone();
+                    tokio::spawn(async move {
+                        let decision = crate::commands::claude_permissions::request_permission(&app_for_permission, request_id.clone(), tool_name, input).await;
+                        let response = crate::commands::claude_permissions::control_response_line(&request_id, decision);
+                        let mut stdin = stdin_for_permission.lock().await;
+                        let _ = tokio::io::AsyncWriteExt::write_all(&mut *stdin, format!("{}\n", response).as_bytes()).await;
+                        let _ = tokio::io::AsyncWriteExt::flush(&mut *stdin).await;
+                    });
+                    continue;
+                }
+            }
+
             // Emit each JSON line as it arrives
             let _ = app_clone.emit("claude:stream", &line);
+            for event in crate::commands::claude_events::decode_line(&line) {
+                let _ = app_clone.emit("claude:event", &event);
+            }
         }
     });
 
@@ -183,7 +208,7 @@ pub async fn call_claude_code_interactive(
         }).map_err(|e| e.to_string())?;
 
         let cmd = CommandBuilder::new("claude");
-        let _child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn: {}", e))?;
+        let child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn: {}", e))?;
 
         let mut reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
         let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
@@ -207,6 +232,7 @@ pub async fn call_claude_code_interactive(
         *session_guard = Some(ShellSession {
             writer: Arc::new(Mutex::new(writer)),
             master: Arc::new(Mutex::new(pair.master)),
+            child: Arc::new(Mutex::new(child)),
         });
 
         std::thread::sleep(std::time::Duration::from_millis(500));
@@ -237,6 +263,8 @@ pub struct ClaudeSession {
     pub last_modified: u64,
     pub message_count: usize,
     pub preview: String,
+    pub total_cost_usd: f64,
+    pub tool_call_count: usize,
 }
 
 #[derive(Debug, Deserialize)]
@@ -296,12 +324,15 @@ fn parse_session_file(file_path: &PathBuf, project_path: &PathBuf) -> Result<Cla
     let mut message_count = 0;
     let mut preview = String::new();
     let mut last_user_message = String::new();
+    let mut totals = crate::commands::claude_events::SessionTotals::default();
 
     for line in reader.lines().flatten() {
         if line.trim().is_empty() {
             continue;
         }
 
+        crate::commands::claude_events::accumulate(&mut totals, &line);
+
         if let Ok(entry) = serde_json::from_str::<ClaudeMessageEntry>(&line) {
             message_count += 1;
 
@@ -358,6 +389,8 @@ fn parse_session_file(file_path: &PathBuf, project_path: &PathBuf) -> Result<Cla
         last_modified,
         message_count,
         preview,
+        total_cost_usd: totals.total_cost_usd,
+        tool_call_count: totals.tool_call_count,
     })
 }
 
@@ -426,7 +459,7 @@ pub async fn resume_claude_session(
     cmd.arg("--continue");
     cmd.cwd(full_project_path);
 
-    let _child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn: {}", e))?;
+    let child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn: {}", e))?;
 
     let mut reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
     let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
@@ -453,6 +486,7 @@ pub async fn resume_claude_session(
     *session_guard = Some(ShellSession {
         writer: Arc::new(Mutex::new(writer)),
         master: Arc::new(Mutex::new(pair.master)),
+        child: Arc::new(Mutex::new(child)),
     });
 
     // Auto-accept trust prompt
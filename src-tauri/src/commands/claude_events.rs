@@ -0,0 +1,108 @@
+/// Typed decoding of one `stream-json` line from the `claude` CLI (used both
+/// live, by `call_claude_code`'s stdout reader, and retrospectively, by
+/// `parse_session_file` reading a persisted `~/.claude/projects/*.jsonl`
+/// transcript) into a small enum instead of pushing that parsing onto the
+/// frontend or re-implementing it twice.
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClaudeEvent {
+    TextDelta { text: String },
+    ToolUse { name: String, input: Value },
+    ToolResult { output: String, is_error: bool },
+    Usage { input_tokens: u64, output_tokens: u64, cost_usd: f64 },
+    Result { duration_ms: u64 },
+}
+
+fn content_blocks(line_value: &Value, role: &str) -> Option<Vec<Value>> {
+    let message = line_value.get("message")?;
+    if message.get("role").and_then(|r| r.as_str()) != Some(role) {
+        return None;
+    }
+    message.get("content")?.as_array().cloned()
+}
+
+/// Decode one or more events out of a single `stream-json` line - a single
+/// `assistant`/`user` turn can carry several content blocks (e.g. text plus
+/// a tool call), so this returns a `Vec` rather than `Option<ClaudeEvent>`.
+pub fn decode_line(line: &str) -> Vec<ClaudeEvent> {
+    let Ok(value) = serde_json::from_str::<Value>(line) else {
+        return vec![];
+    };
+
+    let mut events = Vec::new();
+
+    if let Some(blocks) = content_blocks(&value, "assistant") {
+        for block in blocks {
+            match block.get("type").and_then(|t| t.as_str()) {
+                Some("text") => {
+                    if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                        events.push(ClaudeEvent::TextDelta { text: text.to_string() });
+                    }
+                }
+                Some("tool_use") => {
+                    if let Some(name) = block.get("name").and_then(|n| n.as_str()) {
+                        let input = block.get("input").cloned().unwrap_or(Value::Null);
+                        events.push(ClaudeEvent::ToolUse { name: name.to_string(), input });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(blocks) = content_blocks(&value, "user") {
+        for block in blocks {
+            if block.get("type").and_then(|t| t.as_str()) == Some("tool_result") {
+                let is_error = block.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false);
+                let output = match block.get("content") {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => String::new(),
+                };
+                events.push(ClaudeEvent::ToolResult { output, is_error });
+            }
+        }
+    }
+
+    if value.get("type").and_then(|t| t.as_str()) == Some("result") {
+        let cost_usd = value.get("cost_usd").or_else(|| value.get("total_cost_usd")).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let duration_ms = value.get("duration_ms").and_then(|v| v.as_u64()).unwrap_or(0);
+        let (input_tokens, output_tokens) = value
+            .get("usage")
+            .map(|u| {
+                (
+                    u.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+                    u.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+                )
+            })
+            .unwrap_or((0, 0));
+
+        events.push(ClaudeEvent::Usage { input_tokens, output_tokens, cost_usd });
+        events.push(ClaudeEvent::Result { duration_ms });
+    }
+
+    events
+}
+
+/// Sum of `cost_usd` and count of distinct `tool_use` blocks across an
+/// already-parsed transcript - what `parse_session_file` needs to surface
+/// `ClaudeSession::total_cost_usd`/`tool_call_count` without re-decoding
+/// lines it already read for the message count/preview.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionTotals {
+    pub total_cost_usd: f64,
+    pub tool_call_count: usize,
+}
+
+pub fn accumulate(totals: &mut SessionTotals, line: &str) {
+    for event in decode_line(line) {
+        match event {
+            ClaudeEvent::ToolUse { .. } => totals.tool_call_count += 1,
+            ClaudeEvent::Usage { cost_usd, .. } => totals.total_cost_usd += cost_usd,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/// Permission-approval broker for `call_claude_code`'s `"default"` mode.
+///
+/// In `"default"` mode the `claude` CLI doesn't apply its own auto-accept
+/// heuristics - instead it pauses before each tool call and emits a
+/// `control_request` line on stdout:
+///   `{"type":"control_request","request_id":"...","request":{"subtype":"can_use_tool","tool_name":"...","input":{...}}}`
+/// and blocks until a matching `control_response` line arrives on stdin:
+///   `{"type":"control_response","response":{"subtype":"can_use_tool","request_id":"...","behavior":"allow"|"deny"}}`
+/// This module owns the table of requests currently awaiting a decision
+/// from the frontend and the audit trail of how each one was resolved.
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::oneshot;
+
+/// How long a permission request waits for a frontend response before it's
+/// treated as cancelled - keeps a forgotten prompt from hanging the Claude
+/// process (and this command's caller) forever.
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionDecision {
+    Approved,
+    Denied,
+    Cancelled,
+}
+
+impl PermissionDecision {
+    fn behavior(self) -> &'static str {
+        match self {
+            PermissionDecision::Approved => "allow",
+            PermissionDecision::Denied | PermissionDecision::Cancelled => "deny",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionAuditEntry {
+    pub request_id: String,
+    pub tool_name: String,
+    pub input: Value,
+    pub decision: PermissionDecision,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionRequestEvent {
+    pub request_id: String,
+    pub tool_name: String,
+    pub input: Value,
+}
+
+static PENDING: LazyLock<Mutex<HashMap<String, oneshot::Sender<PermissionDecision>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static AUDIT_LOG: LazyLock<Mutex<Vec<PermissionAuditEntry>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Parse one stream-json line as a `can_use_tool` control request, if it is
+/// one. Every other event type (`assistant`, `result`, ...) returns `None`
+/// and is left for `call_claude_code`'s existing verbatim forwarding.
+pub fn parse_control_request(line: &str) -> Option<(String, String, Value)> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value.get("type")?.as_str()? != "control_request" {
+        return None;
+    }
+    let request_id = value.get("request_id")?.as_str()?.to_string();
+    let request = value.get("request")?;
+    if request.get("subtype")?.as_str()? != "can_use_tool" {
+        return None;
+    }
+    let tool_name = request.get("tool_name")?.as_str()?.to_string();
+    let input = request.get("input").cloned().unwrap_or(Value::Null);
+    Some((request_id, tool_name, input))
+}
+
+/// Register a pending request, emit `claude:permission_request` to the
+/// frontend, and wait for `respond_to_permission` (or the timeout) to
+/// settle it. Always resolves - never propagates an error - so one stalled
+/// prompt can't abort the whole `call_claude_code` stdout reader task.
+pub async fn request_permission(app: &AppHandle, request_id: String, tool_name: String, input: Value) -> PermissionDecision {
+    let (tx, rx) = oneshot::channel();
+    PENDING.lock().unwrap().insert(request_id.clone(), tx);
+
+    let _ = app.emit(
+        "claude:permission_request",
+        PermissionRequestEvent { request_id: request_id.clone(), tool_name: tool_name.clone(), input: input.clone() },
+    );
+
+    let decision = match tokio::time::timeout(APPROVAL_TIMEOUT, rx).await {
+        Ok(Ok(decision)) => decision,
+        // Sender dropped or we timed out - either way nobody answered.
+        Ok(Err(_)) | Err(_) => {
+            PENDING.lock().unwrap().remove(&request_id);
+            PermissionDecision::Cancelled
+        }
+    };
+
+    AUDIT_LOG.lock().unwrap().push(PermissionAuditEntry { request_id, tool_name, input, decision });
+    decision
+}
+
+/// Render the `control_response` line to write back to Claude's stdin for a
+/// decision reached by `request_permission`.
+pub fn control_response_line(request_id: &str, decision: PermissionDecision) -> String {
+    serde_json::json!({
+        "type": "control_response",
+        "response": {
+            "subtype": "can_use_tool",
+            "request_id": request_id,
+            "behavior": decision.behavior(),
+        }
+    })
+    .to_string()
+}
+
+/// Resolve a pending request from the frontend's `respond_to_permission`
+/// call. Each request has exactly one outcome - approved, denied, or the
+/// timeout's cancelled - so this can only settle it once; a duplicate or
+/// late response for an already-resolved id is a no-op.
+#[tauri::command]
+pub async fn respond_to_permission(request_id: String, decision: String) -> Result<(), String> {
+    let decision = match decision.as_str() {
+        "approve" | "allow" => PermissionDecision::Approved,
+        "deny" => PermissionDecision::Denied,
+        "cancel" => PermissionDecision::Cancelled,
+        other => return Err(format!("Unknown permission decision: {}", other)),
+    };
+
+    let sender = PENDING.lock().unwrap().remove(&request_id);
+    match sender {
+        Some(tx) => {
+            let _ = tx.send(decision);
+            Ok(())
+        }
+        None => Err(format!("No pending permission request: {}", request_id)),
+    }
+}
+
+#[tauri::command]
+pub async fn list_permission_audit_log() -> Result<Vec<PermissionAuditEntry>, String> {
+    Ok(AUDIT_LOG.lock().unwrap().clone())
+}
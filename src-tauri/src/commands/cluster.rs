@@ -1,24 +1,36 @@
 use tauri::State;
-use kube::api::{Api, ListParams, DynamicObject};
+use kube::api::{Api, ListParams, DynamicObject, EvictParams, Patch, PatchParams};
 use crate::state::AppState;
-use crate::models::{ClusterStats, ClusterCockpitData, NodeHealth, NodeCondition, PodStatusBreakdown, DeploymentHealth, NamespaceUsage, ClusterMetricsSnapshot, MetricsHistoryBuffer, InitialClusterData};
+use crate::models::{ClusterStats, ClusterCockpitData, NodeHealth, NodeCondition, PodStatusBreakdown, DeploymentHealth, NamespaceUsage, ClusterMetricsSnapshot, MetricsHistoryBuffer, InitialClusterData, MetricsSummary, SeriesStats, RateStat, FetchConfig};
 use crate::client::create_client;
 use crate::utils::{parse_cpu_to_milli, parse_memory_to_bytes};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-#[tauri::command]
-pub async fn get_cluster_stats(state: State<'_, AppState>) -> Result<ClusterStats, String> {
-    // Check cache first (15 second TTL for stats)
-    if let Ok(cache) = state.cluster_stats_cache.try_lock() {
-        if let Some((timestamp, cached_stats)) = &*cache {
-            if timestamp.elapsed().as_secs() < 15 {
-                return Ok(cached_stats.clone());
-            }
-        }
+// Nodes with an in-flight drain_node call. There's no server-side field for
+// this (unlike `spec.unschedulable`), so we track it client-side for the
+// duration of the eviction loop and surface it on NodeHealth as `draining`.
+static DRAINING_NODES: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+fn is_draining(name: &str) -> bool {
+    DRAINING_NODES.lock().unwrap().iter().any(|n| n == name)
+}
+
+fn mark_draining(name: &str) {
+    let mut nodes = DRAINING_NODES.lock().unwrap();
+    if !nodes.iter().any(|n| n == name) {
+        nodes.push(name.to_string());
     }
+}
 
-    let client = create_client(state.clone()).await?;
+fn unmark_draining(name: &str) {
+    DRAINING_NODES.lock().unwrap().retain(|n| n != name);
+}
 
+/// Fetch `ClusterStats` for an arbitrary client, with no `AppState` caching.
+/// Factored out of `get_cluster_stats` so other callers (e.g. the vcluster
+/// fan-out query) can reuse the same counts against a client that isn't
+/// necessarily the app's currently-selected context.
+pub(crate) async fn compute_cluster_stats(client: &kube::Client) -> Result<ClusterStats, String> {
     let nodes: Api<k8s_openapi::api::core::v1::Node> = Api::all(client.clone());
     let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::all(client.clone());
     let deployments: Api<k8s_openapi::api::apps::v1::Deployment> = Api::all(client.clone());
@@ -42,13 +54,28 @@ pub async fn get_cluster_stats(state: State<'_, AppState>) -> Result<ClusterStat
     let services_count = services_res.map(|l| l.items.len()).unwrap_or(0);
     let namespaces_count = namespaces_res.map(|l| l.items.len()).unwrap_or(0);
 
-    let stats = ClusterStats {
+    Ok(ClusterStats {
         nodes: nodes_count,
         pods: pods_count,
         deployments: deployments_count,
         services: services_count,
         namespaces: namespaces_count,
-    };
+    })
+}
+
+#[tauri::command]
+pub async fn get_cluster_stats(state: State<'_, AppState>) -> Result<ClusterStats, String> {
+    // Check cache first (15 second TTL for stats)
+    if let Ok(cache) = state.cluster_stats_cache.try_lock() {
+        if let Some((timestamp, cached_stats)) = &*cache {
+            if timestamp.elapsed().as_secs() < 15 {
+                return Ok(cached_stats.clone());
+            }
+        }
+    }
+
+    let client = create_client(state.clone()).await?;
+    let stats = compute_cluster_stats(&client).await?;
 
     // Update cache
     if let Ok(mut cache) = state.cluster_stats_cache.try_lock() {
@@ -136,10 +163,20 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
     let mut total_mem_allocatable = 0;
     let mut total_mem_usage = 0;
     let mut total_pods_capacity = 0;
+    let mut total_ephemeral_storage_capacity = 0;
+    let mut total_ephemeral_storage_allocatable = 0;
 
     let mut pod_calc_cpu_usage = 0; // fallback if metrics missing
     let mut pod_calc_mem_usage = 0;
 
+    // Pods running per node, for NodeHealth.pods_running
+    let mut pods_per_node: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+    for pod in &pods_items {
+        if let Some(node_name) = pod.spec.as_ref().and_then(|s| s.node_name.clone()) {
+            *pods_per_node.entry(node_name).or_insert(0) += 1;
+        }
+    }
+
     // Pod Stats
     for pod in &pods_items {
         let phase = pod.status.as_ref().and_then(|s| s.phase.as_ref()).map(|s| s.as_str()).unwrap_or("Unknown");
@@ -183,12 +220,18 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
 
     // Node Stats
     let mut healthy_nodes = 0;
+    // Nodes that are NotReady or flagged with DiskPressure/PIDPressure - a
+    // node under disk pressure but still Ready still counts as a warning.
+    let mut flagged_nodes = 0;
     for node in &nodes_items {
         let name = node.metadata.name.clone().unwrap_or_default();
         
         // Status checks
         let mut status = "Unknown".to_string();
         let mut conditions = Vec::new();
+        let mut disk_pressure = false;
+        let mut memory_pressure = false;
+        let mut pid_pressure = false;
         if let Some(node_status) = &node.status {
              if let Some(conds) = &node_status.conditions {
                  for c in conds {
@@ -200,6 +243,12 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
                      if c.type_ == "Ready" {
                          status = if c.status == "True" { "Ready".to_string() } else { "NotReady".to_string() };
                      }
+                     match c.type_.as_str() {
+                         "DiskPressure" => disk_pressure = c.status == "True",
+                         "MemoryPressure" => memory_pressure = c.status == "True",
+                         "PIDPressure" => pid_pressure = c.status == "True",
+                         _ => {}
+                     }
                  }
              }
         }
@@ -211,36 +260,61 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
         let cpu_cap = capacity.and_then(|m| m.get("cpu")).map(|q| parse_cpu_to_milli(&q.0)).unwrap_or(0);
         let mem_cap = capacity.and_then(|m| m.get("memory")).map(|q| parse_memory_to_bytes(&q.0)).unwrap_or(0);
         let pods_cap = capacity.and_then(|m| m.get("pods")).and_then(|q| q.0.parse::<u32>().ok()).unwrap_or(110);
-        
+        let storage_cap = capacity.and_then(|m| m.get("ephemeral-storage")).map(|q| parse_memory_to_bytes(&q.0)).unwrap_or(0);
+
         let cpu_alloc = allocatable.and_then(|m| m.get("cpu")).map(|q| parse_cpu_to_milli(&q.0)).unwrap_or(0);
         let mem_alloc = allocatable.and_then(|m| m.get("memory")).map(|q| parse_memory_to_bytes(&q.0)).unwrap_or(0);
+        let storage_alloc = allocatable.and_then(|m| m.get("ephemeral-storage")).map(|q| parse_memory_to_bytes(&q.0)).unwrap_or(0);
 
         total_cpu_capacity += cpu_cap;
         total_mem_capacity += mem_cap;
         total_cpu_allocatable += cpu_alloc;
         total_mem_allocatable += mem_alloc;
         total_pods_capacity += pods_cap;
+        total_ephemeral_storage_capacity += storage_cap;
+        total_ephemeral_storage_allocatable += storage_alloc;
 
-        // Usage from metrics or fallback? 
+        // Usage from metrics or fallback?
         // Note: For node list, we usually use metrics for usage.
         let (used_cpu, used_mem) = node_metrics_map.get(&name).cloned().unwrap_or((0, 0));
-        
+
         if metrics_available {
             total_cpu_usage += used_cpu;
             total_mem_usage += used_mem;
         }
 
+        let pods_running = pods_per_node.get(&name).copied().unwrap_or(0);
+
+        if status != "Ready" || disk_pressure || pid_pressure { flagged_nodes += 1; }
+
+        let scheduling_disabled = node.spec.as_ref().and_then(|s| s.unschedulable).unwrap_or(false);
+        let draining = is_draining(&name);
+        // Mirror kubectl's "Ready,SchedulingDisabled" combined status string
+        // rather than overloading the base Ready/NotReady condition.
+        let display_status = if scheduling_disabled {
+            format!("{},SchedulingDisabled", status)
+        } else {
+            status
+        };
+
         nodes_health.push(NodeHealth {
             name,
-            status,
+            status: display_status,
             cpu_capacity: cpu_cap,
             cpu_allocatable: cpu_alloc,
             cpu_usage: used_cpu,
             memory_capacity: mem_cap,
             memory_allocatable: mem_alloc,
             memory_usage: used_mem,
+            ephemeral_storage_capacity: storage_cap,
+            ephemeral_storage_allocatable: storage_alloc,
             pods_capacity: pods_cap,
-            pods_running: 0, // Need to count per node if required, skipping for brevity
+            pods_running,
+            disk_pressure,
+            memory_pressure,
+            pid_pressure,
+            scheduling_disabled,
+            draining,
             conditions,
             taints: node.spec.as_ref().and_then(|s| s.taints.clone()).map(|t| t.into_iter().map(|tx| tx.key).collect()).unwrap_or_default(),
         });
@@ -275,13 +349,14 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
     let mut top_ns: Vec<NamespaceUsage> = ns_usage.into_values().collect();
     top_ns.sort_by(|a, b| b.pod_count.cmp(&a.pod_count)); // sort by pods for now
     
-    let warning_count = unhealthy_deps.len() + (nodes_items.len() - healthy_nodes); // Simple heuristic
+    let warning_count = unhealthy_deps.len() + flagged_nodes;
     let critical_count = pods_breakdown.failed;
 
     // Capture pod breakdown values for history before moving into struct
     let running_pods_count = pods_breakdown.running;
     let pending_pods_count = pods_breakdown.pending;
     let failed_pods_count = pods_breakdown.failed;
+    let unhealthy_deployments_count = unhealthy_deps.len();
 
     let data = ClusterCockpitData {
         total_nodes: nodes_items.len(),
@@ -297,6 +372,8 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
         total_memory_allocatable: total_mem_allocatable,
         total_memory_usage: total_mem_usage,
         total_pods_capacity,
+        total_ephemeral_storage_capacity,
+        total_ephemeral_storage_allocatable,
         pod_status: pods_breakdown.clone(),
         nodes: nodes_health,
         unhealthy_deployments: unhealthy_deps,
@@ -328,10 +405,16 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
         pending_pods: pending_pods_count,
         failed_pods: failed_pods_count,
         total_deployments: deployments_items.len(),
+        unhealthy_deployments: unhealthy_deployments_count,
         cpu_usage_percent: cpu_pct,
         memory_usage_percent: mem_pct,
     };
 
+    // Fire-and-forget persist into the on-disk store so the timeline
+    // survives restarts and context switches; the ring buffer below stays
+    // the hot path for anything reading in-process.
+    crate::metrics_store::insert_snapshot_async(current_ctx.clone(), snapshot.clone());
+
     // Use current_ctx captured AT START of function
     if let Ok(mut history) = state.metrics_history.try_lock() {
         match &mut *history {
@@ -351,21 +434,99 @@ pub async fn get_cluster_cockpit(state: State<'_, AppState>) -> Result<ClusterCo
     Ok(data)
 }
 
-/// Get the metrics history for timeline charts
+/// Get the metrics history for timeline charts. Reads raw-resolution rows
+/// from the on-disk metrics store (the last hour, matching the retention
+/// window for raw samples) rather than just the in-memory ring buffer, so
+/// the timeline survives app restarts.
 #[tauri::command]
 pub async fn get_metrics_history(state: State<'_, AppState>) -> Result<Vec<ClusterMetricsSnapshot>, String> {
     let current_ctx = state.selected_context.lock().unwrap().clone().unwrap_or_default();
 
-    if let Ok(history) = state.metrics_history.try_lock() {
-        if let Some(buffer) = &*history {
-            if buffer.context == current_ctx {
-                return Ok(buffer.snapshots.clone());
-            }
-        }
+    let now = crate::metrics_store::now_secs();
+    let rows = crate::metrics_store::query_range(&current_ctx, now - 3600, now, crate::metrics_store::MetricsResolution::Raw)?;
+    Ok(rows.into_iter().map(ClusterMetricsSnapshot::from).collect())
+}
+
+/// Get metrics history over an arbitrary window at a given resolution, so
+/// the UI can zoom out over long windows without loading thousands of raw
+/// rows. `resolution` is one of "raw", "five_minute", "hourly".
+#[tauri::command]
+pub async fn get_metrics_history_range(
+    from_ts: i64,
+    to_ts: i64,
+    resolution: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::metrics_store::MetricsRollupRow>, String> {
+    let current_ctx = state.selected_context.lock().unwrap().clone().unwrap_or_default();
+    let resolution = crate::metrics_store::MetricsResolution::parse(&resolution)?;
+    crate::metrics_store::query_range(&current_ctx, from_ts, to_ts, resolution)
+}
+
+/// Headline min/max/avg/p95 for cpu/mem usage plus Prometheus-style rate()
+/// for failed-pods and unhealthy-deployments over a trailing window, so the
+/// UI can render sparkline numbers without pulling the whole raw buffer.
+#[tauri::command]
+pub async fn get_metrics_summary(window_secs: i64, state: State<'_, AppState>) -> Result<MetricsSummary, String> {
+    let current_ctx = state.selected_context.lock().unwrap().clone().unwrap_or_default();
+    let now = crate::metrics_store::now_secs();
+    let rows = crate::metrics_store::query_range(&current_ctx, now - window_secs, now, crate::metrics_store::MetricsResolution::Raw)?;
+
+    if rows.is_empty() {
+        return Ok(MetricsSummary {
+            window_secs,
+            sample_count: 0,
+            cpu_usage_percent: SeriesStats { min: 0.0, max: 0.0, avg: 0.0, p95: 0.0 },
+            memory_usage_percent: SeriesStats { min: 0.0, max: 0.0, avg: 0.0, p95: 0.0 },
+            failed_pods_per_minute: RateStat { per_minute: 0.0, reset_detected: false },
+            unhealthy_deployments_per_minute: RateStat { per_minute: 0.0, reset_detected: false },
+        });
+    }
+
+    let first = rows.first().unwrap();
+    let last = rows.last().unwrap();
+
+    Ok(MetricsSummary {
+        window_secs,
+        sample_count: rows.len(),
+        cpu_usage_percent: series_stats(rows.iter().map(|r| r.cpu_usage_percent)),
+        memory_usage_percent: series_stats(rows.iter().map(|r| r.memory_usage_percent)),
+        failed_pods_per_minute: rate_per_minute(first.timestamp, first.failed_pods as f64, last.timestamp, last.failed_pods as f64),
+        unhealthy_deployments_per_minute: rate_per_minute(
+            first.timestamp,
+            first.unhealthy_deployments as f64,
+            last.timestamp,
+            last.unhealthy_deployments as f64,
+        ),
+    })
+}
+
+fn series_stats(values: impl Iterator<Item = f64>) -> SeriesStats {
+    let mut sorted: Vec<f64> = values.collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    let avg = sorted.iter().sum::<f64>() / sorted.len() as f64;
+    let p95_idx = (((sorted.len() as f64) * 0.95).ceil() as usize).saturating_sub(1).min(sorted.len() - 1);
+    let p95 = sorted[p95_idx];
+
+    SeriesStats { min, max, avg, p95 }
+}
+
+/// Prometheus-style `rate()`: `(last - first) / (last_ts - first_ts)`,
+/// scaled to a per-minute figure. A zero/negative interval or a decreasing
+/// counter (the history buffer was reset by a context switch) falls back to
+/// the raw last value instead of a meaningless or negative rate.
+fn rate_per_minute(first_ts: i64, first_val: f64, last_ts: i64, last_val: f64) -> RateStat {
+    let interval = last_ts - first_ts;
+    let decreased = last_val < first_val;
+
+    if interval <= 0 || decreased {
+        return RateStat { per_minute: last_val, reset_detected: decreased };
     }
 
-    // No history for this context yet
-    Ok(Vec::new())
+    let rate_per_sec = (last_val - first_val) / interval as f64;
+    RateStat { per_minute: rate_per_sec * 60.0, reset_detected: false }
 }
 
 /// Clear metrics history (useful on context switch)
@@ -377,13 +538,81 @@ pub async fn clear_metrics_history(state: State<'_, AppState>) -> Result<(), Str
     Ok(())
 }
 
+/// Start the local Prometheus exposition server so an external
+/// Prometheus/Grafana stack can scrape cockpit gauges from `/metrics`.
+/// Returns the port actually bound. A no-op if already running.
+#[tauri::command]
+pub async fn start_metrics_server(port: Option<u16>, app_handle: tauri::AppHandle) -> Result<u16, String> {
+    crate::metrics_server::start(app_handle, port).await
+}
+
+#[tauri::command]
+pub async fn stop_metrics_server() -> Result<(), String> {
+    crate::metrics_server::stop();
+    Ok(())
+}
+
+/// The port the metrics server is currently listening on, if it's running.
+#[tauri::command]
+pub async fn get_metrics_server_status() -> Result<Option<u16>, String> {
+    Ok(crate::metrics_server::running_port())
+}
+
+/// Sleeps just long enough that at least `min_interval` has passed since the
+/// last list call, so a burst of calls in `get_initial_cluster_data` doesn't
+/// hammer the apiserver back-to-back. No-op on the very first call.
+async fn throttle(last_call_at: &mut Option<std::time::Instant>, min_interval: std::time::Duration) {
+    if let Some(last) = *last_call_at {
+        let elapsed = last.elapsed();
+        if elapsed < min_interval {
+            tokio::time::sleep(min_interval - elapsed).await;
+        }
+    }
+    *last_call_at = Some(std::time::Instant::now());
+}
+
+/// Lists a resource kind with a minimum interval enforced since the previous
+/// call (shared across all resource kinds via `last_call_at`) and retries
+/// with exponential backoff on any error, up to `config.max_retries` - the
+/// same crawler etiquette you'd use against a rate-limited registry.
+async fn list_with_backoff<K>(
+    api: &Api<K>,
+    lp: &ListParams,
+    config: &FetchConfig,
+    last_call_at: &mut Option<std::time::Instant>,
+) -> Result<kube::core::ObjectList<K>, kube::Error>
+where
+    K: Clone + serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let mut attempt = 0u32;
+    loop {
+        throttle(last_call_at, config.min_interval()).await;
+        match api.list(lp).await {
+            Ok(list) => return Ok(list),
+            Err(e) => {
+                attempt += 1;
+                if attempt > config.max_retries {
+                    return Err(e);
+                }
+                let is_rate_limited = matches!(&e, kube::Error::Api(ae) if ae.code == 429);
+                eprintln!(
+                    "[get_initial_cluster_data] list call failed (attempt {}/{}, rate_limited={}): {} - retrying",
+                    attempt, config.max_retries, is_rate_limited, e
+                );
+                tokio::time::sleep(config.base_backoff() * 2u32.pow(attempt - 1)).await;
+            }
+        }
+    }
+}
+
 #[tauri::command]
-pub async fn get_initial_cluster_data(state: State<'_, AppState>) -> Result<InitialClusterData, String> {
+pub async fn get_initial_cluster_data(state: State<'_, AppState>, config: Option<FetchConfig>) -> Result<InitialClusterData, String> {
     let client = create_client(state.clone()).await?;
-    
+    let config = config.unwrap_or_default();
+
     // We want to fetch everything needed for the first dashboard load
     // This includes: stats (calculated locally), namespaces, and the first few resource lists
-    
+
     // 1. Fetch Resources Needed for Cockpit
     // Instead of calling get_cluster_stats (which does its own list calls),
     // and then calling list_resources (which does the same list calls),
@@ -397,20 +626,16 @@ pub async fn get_initial_cluster_data(state: State<'_, AppState>) -> Result<Init
 
     let lp = ListParams::default();
 
-    let (nodes_res, pods_res, deployments_res, services_res, namespaces_res) = tokio::join!(
-        nodes.list(&lp),
-        pods.list(&lp),
-        deployments.list(&lp),
-        services.list(&lp),
-        namespaces.list(&lp)
-    );
-
-    // Filter results
-    let nodes_list = nodes_res.map_err(|e| format!("Failed to list nodes: {}", e))?;
-    let pods_list = pods_res.map_err(|e| format!("Failed to list pods: {}", e))?;
-    let deploy_list = deployments_res.map_err(|e| format!("Failed to list deployments: {}", e))?;
-    let svc_list = services_res.map_err(|e| format!("Failed to list services: {}", e))?;
-    let ns_list = namespaces_res.map_err(|e| format!("Failed to list namespaces: {}", e))?;
+    // Issued sequentially (rather than the old tokio::join!) so the
+    // min-interval throttle and per-call backoff below actually mean
+    // something - large clusters shouldn't get 5 concurrent list calls
+    // fired at the apiserver on every poll.
+    let mut last_call_at: Option<std::time::Instant> = None;
+    let nodes_list = list_with_backoff(&nodes, &lp, &config, &mut last_call_at).await.map_err(|e| format!("Failed to list nodes: {}", e))?;
+    let pods_list = list_with_backoff(&pods, &lp, &config, &mut last_call_at).await.map_err(|e| format!("Failed to list pods: {}", e))?;
+    let deploy_list = list_with_backoff(&deployments, &lp, &config, &mut last_call_at).await.map_err(|e| format!("Failed to list deployments: {}", e))?;
+    let svc_list = list_with_backoff(&services, &lp, &config, &mut last_call_at).await.map_err(|e| format!("Failed to list services: {}", e))?;
+    let ns_list = list_with_backoff(&namespaces, &lp, &config, &mut last_call_at).await.map_err(|e| format!("Failed to list namespaces: {}", e))?;
 
     // 2. Calculate Stats Locally
     let stats = ClusterStats {
@@ -534,6 +759,8 @@ pub async fn get_initial_cluster_data(state: State<'_, AppState>) -> Result<Init
         }
     };
 
+    let graph = build_cluster_graph(&pods_list.items, &svc_list.items, &deploy_list.items);
+
     let pod_summaries: Vec<crate::models::ResourceSummary> = pods_list.items.into_iter().map(to_summary_pods).collect();
     let node_summaries: Vec<crate::models::ResourceSummary> = nodes_list.items.into_iter().map(convert_nodes).collect();
     let deploy_summaries: Vec<crate::models::ResourceSummary> = deploy_list.items.into_iter().map(convert_deployments).collect();
@@ -547,5 +774,226 @@ pub async fn get_initial_cluster_data(state: State<'_, AppState>) -> Result<Init
         nodes: node_summaries,
         deployments: deploy_summaries,
         services: svc_summaries,
+        graph,
     })
 }
+
+/// Fetch the current cluster state and diff it against the hashes recorded
+/// on the last call, so the UI only has to re-render what actually changed
+/// between polls instead of the whole resource list every time.
+#[tauri::command]
+pub async fn get_cluster_delta(state: State<'_, AppState>) -> Result<crate::models::ClusterDelta, String> {
+    let data = get_initial_cluster_data(state.clone(), None).await?;
+
+    let mut prev = state.resource_snapshot.lock().unwrap();
+    let mut seen = std::collections::HashSet::new();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+
+    for summary in data.pods.iter().chain(data.nodes.iter()).chain(data.deployments.iter()).chain(data.services.iter()) {
+        seen.insert(summary.id.clone());
+        let hash = summary.content_hash();
+        match prev.get(&summary.id) {
+            None => added.push(summary.clone()),
+            Some(&old_hash) if old_hash != hash => changed.push(summary.clone()),
+            _ => {} // unchanged, skip
+        }
+        prev.insert(summary.id.clone(), hash);
+    }
+
+    let removed: Vec<String> = prev.keys().filter(|id| !seen.contains(*id)).cloned().collect();
+    for id in &removed {
+        prev.remove(id);
+    }
+
+    Ok(crate::models::ClusterDelta { added, removed, changed })
+}
+
+/// Build the pod->node, pod->owner, and service->pod edges that back
+/// topology/blast-radius queries. Run before the raw lists are consumed by
+/// `to_summary_*`/`convert_*` above.
+fn build_cluster_graph(
+    pods: &[k8s_openapi::api::core::v1::Pod],
+    services: &[k8s_openapi::api::core::v1::Service],
+    deployments: &[k8s_openapi::api::apps::v1::Deployment],
+) -> crate::cluster_graph::ClusterGraph {
+    use crate::cluster_graph::{deployment_name_from_replicaset, ClusterGraph, ResourceId};
+
+    let mut graph = ClusterGraph::new();
+
+    let deployment_names: std::collections::HashSet<(String, String)> = deployments
+        .iter()
+        .map(|d| (d.metadata.namespace.clone().unwrap_or_default(), d.metadata.name.clone().unwrap_or_default()))
+        .collect();
+
+    for pod in pods {
+        let pod_name = match &pod.metadata.name {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let pod_ns = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+        let pod_id = ResourceId::new("Pod", &pod_ns, &pod_name);
+
+        if let Some(node_name) = pod.spec.as_ref().and_then(|s| s.node_name.clone()) {
+            graph.record_pod_node(pod_id.clone(), ResourceId::new("Node", "-", &node_name));
+        }
+
+        if let Some(owner) = pod.metadata.owner_references.as_ref().and_then(|refs| refs.first()) {
+            if owner.kind == "ReplicaSet" {
+                if let Some(deployment_name) = deployment_name_from_replicaset(&owner.name) {
+                    if deployment_names.contains(&(pod_ns.clone(), deployment_name.clone())) {
+                        graph.record_pod_owner(pod_id.clone(), ResourceId::new("Deployment", &pod_ns, &deployment_name));
+                    }
+                }
+            } else {
+                graph.record_pod_owner(pod_id.clone(), ResourceId::new(&owner.kind, &pod_ns, &owner.name));
+            }
+        }
+    }
+
+    for svc in services {
+        let svc_name = match &svc.metadata.name {
+            Some(n) => n.clone(),
+            None => continue,
+        };
+        let svc_ns = svc.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+        let selector = svc.spec.as_ref().and_then(|s| s.selector.clone()).unwrap_or_default();
+        if selector.is_empty() {
+            continue;
+        }
+
+        let svc_id = ResourceId::new("Service", &svc_ns, &svc_name);
+        for pod in pods {
+            let pod_ns = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+            if pod_ns != svc_ns {
+                continue;
+            }
+            let pod_name = match &pod.metadata.name {
+                Some(n) => n.clone(),
+                None => continue,
+            };
+            let labels = pod.metadata.labels.as_ref();
+            let matches = selector.iter().all(|(k, v)| labels.and_then(|l| l.get(k)) == Some(v));
+            if matches {
+                graph.record_service_pod(svc_id.clone(), ResourceId::new("Pod", &pod_ns, &pod_name));
+            }
+        }
+    }
+
+    graph
+}
+
+/// Mark a node unschedulable (`kubectl cordon`). Existing pods are left
+/// running; only new scheduling is blocked.
+#[tauri::command]
+pub async fn cordon_node(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    set_unschedulable(state, name, true).await
+}
+
+/// Clear a node's unschedulable flag (`kubectl uncordon`).
+#[tauri::command]
+pub async fn uncordon_node(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    set_unschedulable(state, name, false).await
+}
+
+async fn set_unschedulable(state: State<'_, AppState>, name: String, unschedulable: bool) -> Result<(), String> {
+    let client = create_client(state).await?;
+    let nodes: Api<k8s_openapi::api::core::v1::Node> = Api::all(client);
+
+    let patch = serde_json::json!({ "spec": { "unschedulable": unschedulable } });
+    nodes
+        .patch(&name, &PatchParams::apply("opspilot"), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Cordon a node and evict its pods through the `pods/eviction` subresource
+/// so PodDisruptionBudgets are respected, mirroring `kubectl drain`.
+/// Mirror/static pods and (by default) DaemonSet-owned pods are left alone
+/// since evicting them is either impossible or pointless - the kubelet or
+/// the DaemonSet controller immediately restarts them on the same node.
+#[tauri::command]
+pub async fn drain_node(
+    state: State<'_, AppState>,
+    name: String,
+    grace_seconds: Option<i64>,
+    ignore_daemonsets: bool,
+) -> Result<String, String> {
+    let client = create_client(state.clone()).await?;
+
+    set_unschedulable(state.clone(), name.clone(), true).await?;
+    mark_draining(&name);
+
+    let result = drain_pods_on_node(client, &name, grace_seconds, ignore_daemonsets).await;
+    unmark_draining(&name);
+    result
+}
+
+async fn drain_pods_on_node(
+    client: kube::Client,
+    node_name: &str,
+    grace_seconds: Option<i64>,
+    ignore_daemonsets: bool,
+) -> Result<String, String> {
+    let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::all(client.clone());
+    let lp = ListParams::default().fields(&format!("spec.nodeName={}", node_name));
+    let pod_list = pods.list(&lp).await.map_err(|e| e.to_string())?;
+
+    let mut evicted = 0;
+    let mut skipped = 0;
+
+    for pod in pod_list.items {
+        let pod_name = match pod.metadata.name.clone() {
+            Some(n) => n,
+            None => continue,
+        };
+        let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+
+        let owner_kinds: Vec<String> = pod
+            .metadata
+            .owner_references
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|o| o.kind)
+            .collect();
+
+        // Mirror pods (managed directly by the kubelet) can't be evicted at
+        // all; DaemonSet pods are skipped by default since they'll just be
+        // rescheduled onto the same node once it's uncordoned.
+        let is_mirror = pod
+            .metadata
+            .annotations
+            .as_ref()
+            .map(|a| a.contains_key("kubernetes.io/config.mirror"))
+            .unwrap_or(false);
+        let is_daemonset = owner_kinds.iter().any(|k| k == "DaemonSet");
+
+        if is_mirror || (ignore_daemonsets && is_daemonset) {
+            skipped += 1;
+            continue;
+        }
+
+        let pod_api: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client.clone(), &namespace);
+
+        let mut ep = EvictParams::default();
+        if let Some(g) = grace_seconds {
+            ep.delete_options = Some(kube::api::DeleteParams {
+                grace_period_seconds: Some(g.max(0) as u32),
+                ..Default::default()
+            });
+        }
+
+        pod_api.evict(&pod_name, &ep).await.map_err(|e| {
+            format!("Failed to evict pod {}/{}: {}", namespace, pod_name, e)
+        })?;
+        evicted += 1;
+    }
+
+    Ok(format!(
+        "Drained node {}: {} pod(s) evicted, {} skipped (mirror/DaemonSet)",
+        node_name, evicted, skipped
+    ))
+}
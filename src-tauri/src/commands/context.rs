@@ -10,24 +10,53 @@ use std::path::PathBuf;
 
 #[tauri::command]
 pub async fn list_contexts(custom_path: Option<String>) -> Result<Vec<KubeContext>, String> {
-    let kubeconfig = if let Some(path) = custom_path {
-        Kubeconfig::read_from(path).map_err(|e| e.to_string())?
+    let kubeconfig = if let Some(path) = &custom_path {
+        Kubeconfig::read_from(path).map_err(|e| e.to_string())
     } else {
-        Kubeconfig::read().map_err(|e| e.to_string())?
+        Kubeconfig::read().map_err(|e| e.to_string())
     };
 
-    let contexts = kubeconfig.contexts.into_iter().map(|c| {
-        let ctx = c.context.unwrap_or_default();
-        KubeContext {
-            name: c.name,
-            cluster: ctx.cluster,
-            user: ctx.user,
-        }
-    }).collect();
+    // No kubeconfig is normal when running as a pod - only propagate the
+    // read error if there's no in-cluster fallback to offer instead.
+    let mut contexts: Vec<KubeContext> = match kubeconfig {
+        Ok(kc) => kc.contexts.into_iter().map(|c| {
+            let ctx = c.context.unwrap_or_default();
+            KubeContext {
+                name: c.name,
+                cluster: ctx.cluster,
+                user: ctx.user,
+            }
+        }).collect(),
+        Err(e) if !in_cluster_available() => return Err(e),
+        Err(_) => Vec::new(),
+    };
+
+    if in_cluster_available() {
+        contexts.push(KubeContext {
+            name: IN_CLUSTER_CONTEXT.to_string(),
+            cluster: "in-cluster".to_string(),
+            user: "service-account".to_string(),
+        });
+    }
 
     Ok(contexts)
 }
 
+/// Pseudo context name representing in-cluster (ServiceAccount) credentials,
+/// selectable from `list_contexts`/`set_kube_config` like any kubeconfig
+/// context even though it isn't backed by a kubeconfig entry.
+pub const IN_CLUSTER_CONTEXT: &str = "in-cluster";
+
+/// Whether the standard in-cluster ServiceAccount mount and Kubernetes
+/// service env vars are present, i.e. whether `kube::Config::incluster()`
+/// stands a chance of succeeding. Used both to decide whether to surface the
+/// `IN_CLUSTER_CONTEXT` pseudo-context and as the fallback when no
+/// kubeconfig file exists at all.
+fn in_cluster_available() -> bool {
+    std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+        && PathBuf::from("/var/run/secrets/kubernetes.io/serviceaccount/token").exists()
+}
+
 #[tauri::command]
 pub async fn delete_context(context_name: String, custom_path: Option<String>) -> Result<(), String> {
     // Get the kubeconfig path
@@ -151,12 +180,14 @@ pub async fn set_kube_config(
         }
     };
 
+    let timeouts = *state.connection_timeouts.lock().unwrap();
+
     // If switching FROM a vcluster context to a different context, disconnect first
     if let Some(ref curr_ctx) = current_context {
         if curr_ctx.starts_with("vcluster_") {
             // Run vcluster disconnect in background with timeout
             let _ = tokio::time::timeout(
-                Duration::from_secs(5), 
+                timeouts.vcluster_disconnect_timeout,
                 tokio::process::Command::new("vcluster")
                     .arg("disconnect")
                     .output()
@@ -213,32 +244,66 @@ pub async fn set_kube_config(
     // Verify the connection by creating a client and making a simple API call
     let context_name = context.clone().unwrap_or_else(|| "default".to_string());
 
-    // Load kubeconfig and create client
-    let kubeconfig = if let Some(p) = &path {
-        Kubeconfig::read_from(p).map_err(|e| format!("Cannot read kubeconfig from {}: {}", p, e))?
-    } else {
-        Kubeconfig::read().map_err(|e| format!("Cannot read default kubeconfig: {}", e))?
-    };
+    // Either the caller explicitly asked for the in-cluster pseudo-context, or
+    // no context/path was given and there's no kubeconfig to fall back to -
+    // the situation when OpsPilot runs as a pod with no ~/.kube/config.
+    let use_in_cluster = context_name == IN_CLUSTER_CONTEXT
+        || (path.is_none() && context.is_none() && Kubeconfig::read().is_err());
+
+    let (context_name, mut config) = if use_in_cluster {
+        if !in_cluster_available() {
+            return Err(format!(
+                "UNKNOWN_ERROR|{}|No kubeconfig found and no in-cluster ServiceAccount credentials are mounted.|",
+                context_name
+            ));
+        }
 
-    let config_res = tokio::time::timeout(
-        Duration::from_secs(25),
-        kube::Config::from_custom_kubeconfig(
-            kubeconfig,
-            &KubeConfigOptions {
-                context: context.clone(),
-                ..Default::default()
-            }
-        )
-    ).await;
+        let config = kube::Config::incluster()
+            .map_err(|e| format!("Failed to build in-cluster config: {}", e))?;
+        (IN_CLUSTER_CONTEXT.to_string(), config)
+    } else {
+        // Load kubeconfig and create client
+        let kubeconfig = if let Some(p) = &path {
+            Kubeconfig::read_from(p).map_err(|e| format!("Cannot read kubeconfig from {}: {}", p, e))?
+        } else {
+            Kubeconfig::read().map_err(|e| format!("Cannot read default kubeconfig: {}", e))?
+        };
 
-    let mut config = match config_res {
-        Ok(res) => res.map_err(|e| format!("Invalid context '{}': {}", context_name, e))?,
-        Err(_) => return Err(format!("CONNECTION_TIMEOUT|{}|Authentication timed out (25s). Check your cloud credentials (e.g. az login).", context_name)),
+        // Catch a broken/missing exec auth plugin (kubelogin, gke-gcloud-auth-plugin,
+        // aws, ...) before spending the 25s client-creation timeout on it below -
+        // kube-rs would reject the same config with "command must be specified to
+        // use exec authentication plugin", but only once it's already deep into
+        // `Client::try_from`.
+        validate_exec_plugin(&kubeconfig, &context_name)?;
+
+        let config_res = tokio::time::timeout(
+            timeouts.client_timeout,
+            kube::Config::from_custom_kubeconfig(
+                kubeconfig,
+                &KubeConfigOptions {
+                    context: context.clone(),
+                    ..Default::default()
+                }
+            )
+        ).await;
+
+        let config = match config_res {
+            Ok(res) => res.map_err(|e| format!("Invalid context '{}': {}", context_name, e))?,
+            Err(_) => return Err(format!("CONNECTION_TIMEOUT|{}|Authentication timed out ({}). Check your cloud credentials (e.g. az login).", context_name, humantime::format_duration(timeouts.client_timeout))),
+        };
+        (context_name, config)
     };
 
+    // Fallback into the in-cluster pseudo-context resolves a `None` `context`
+    // param to `"in-cluster"` - make sure that's what later reads of
+    // `selected_context` (e.g. `get_current_context_name`) see too.
+    if let Ok(mut context_guard) = state.selected_context.try_lock() {
+        *context_guard = Some(context_name.clone());
+    }
+
     // Set aggressive timeouts for connection test
-    config.connect_timeout = Some(Duration::from_secs(5));
-    config.read_timeout = Some(Duration::from_secs(5));
+    config.connect_timeout = Some(timeouts.connect_timeout);
+    config.read_timeout = Some(timeouts.read_timeout);
 
     // For vcluster contexts (local proxy), accept self-signed certs
     if context_name.starts_with("vcluster_") {
@@ -260,7 +325,7 @@ pub async fn set_kube_config(
     // Let's assume it might hang. We use spawn_blocking to wrap it so we can time it out.
     let config_clone = config.clone();
     let client_res = tokio::time::timeout(
-        Duration::from_secs(25),
+        timeouts.client_timeout,
         tokio::task::spawn_blocking(move || {
             Client::try_from(config_clone)
         })
@@ -276,7 +341,7 @@ pub async fn set_kube_config(
         },
         Err(_) => {
              // Timeout happened!
-             println!("DEBUG: Client creation timed out after 25s");
+             println!("DEBUG: Client creation timed out after {}", humantime::format_duration(timeouts.client_timeout));
              // Return the Azure Login Required error directly to trigger the UI
              return Err(format!("AZURE_LOGIN_REQUIRED|{}|Azure authentication timed out (client creation). Please log in.|az login", context_name));
         }
@@ -284,16 +349,20 @@ pub async fn set_kube_config(
 
     // Verify connection with a lightweight API call (with timeout)
     let api_check = tokio::time::timeout(
-        Duration::from_secs(8),
+        timeouts.api_check_timeout,
         client.list_api_groups()
     ).await;
 
     match api_check {
         Ok(Ok(_)) => {
-            // Success! Persist connection change to kubeconfig file so CLI tools (helm, vcluster) see it
-            if let Err(e) = persist_context_change(&path, &context_name) {
-                println!("Warning: Failed to persist context change to kubeconfig: {}", e);
-                // Don't fail the connections, just warn
+            // Success! Persist connection change to kubeconfig file so CLI tools
+            // (helm, vcluster) see it - not applicable to the in-cluster
+            // pseudo-context, which has no kubeconfig entry to point at.
+            if context_name != IN_CLUSTER_CONTEXT {
+                if let Err(e) = persist_context_change(&path, &context_name) {
+                    println!("Warning: Failed to persist context change to kubeconfig: {}", e);
+                    // Don't fail the connections, just warn
+                }
             }
             Ok(format!("Connected to {}", context_name))
         },
@@ -366,6 +435,72 @@ fn extract_between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str>
     Some(&remaining[..end_idx])
 }
 
+/// Best-effort install hint for a known exec plugin binary, used when the
+/// kubeconfig itself doesn't carry one via `exec.install_hint`.
+fn default_install_hint(binary: &str) -> &'static str {
+    match binary {
+        "kubelogin" => "Install via `az aks install-cli` or `brew install Azure/kubelogin/kubelogin`",
+        "gke-gcloud-auth-plugin" => "Install via `gcloud components install gke-gcloud-auth-plugin`",
+        "aws" => "Install via `pip install awscli` or `brew install awscli`",
+        _ => "Install the exec plugin binary referenced by this context and ensure it's on PATH",
+    }
+}
+
+/// Whether `binary` resolves to an executable file somewhere on the current
+/// process `PATH` (already augmented with Homebrew/asdf/etc by `tool_env` at
+/// startup, so this doesn't need to repeat that search itself).
+fn binary_on_path(binary: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(binary).is_file()))
+        .unwrap_or(false)
+}
+
+/// Validate the `exec` auth provider (if any) of `context_name`'s user
+/// before attempting a connection. Mirrors kube-rs's own "command must be
+/// specified to use exec authentication plugin" check, but as a structured
+/// `EXEC_MISSING_COMMAND|...`/`EXEC_PLUGIN_NOT_INSTALLED|...` error the UI
+/// can render remediation for instead of a generic timeout.
+fn validate_exec_plugin(kubeconfig: &Kubeconfig, context_name: &str) -> Result<(), String> {
+    let Some(user_name) = kubeconfig
+        .contexts
+        .iter()
+        .find(|c| c.name == context_name)
+        .and_then(|c| c.context.as_ref())
+        .and_then(|c| c.user.as_ref())
+    else {
+        return Ok(());
+    };
+
+    let Some(auth_info) = kubeconfig
+        .auth_infos
+        .iter()
+        .find(|a| &a.name == user_name)
+        .map(|a| &a.auth_info)
+    else {
+        return Ok(());
+    };
+
+    let Some(exec) = auth_info.exec.as_ref() else {
+        return Ok(());
+    };
+
+    let Some(command) = exec.command.as_ref().filter(|c| !c.is_empty()) else {
+        return Err(format!(
+            "EXEC_MISSING_COMMAND|{}|This context's exec auth provider has no command configured|",
+            context_name
+        ));
+    };
+
+    if !binary_on_path(command) {
+        return Err(format!(
+            "EXEC_PLUGIN_NOT_INSTALLED|{}|{} is required but not installed|{}",
+            context_name, command, default_install_hint(command)
+        ));
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn reset_state(state: State<'_, AppState>) -> Result<(), String> {
     // Clear ALL caches
@@ -402,12 +537,16 @@ pub async fn get_current_context_name(state: State<'_, AppState>, custom_path: O
     };
 
     let kubeconfig = if let Some(p) = &path {
-        Kubeconfig::read_from(p).map_err(|e| e.to_string())?
+        Kubeconfig::read_from(p).map_err(|e| e.to_string())
     } else {
-        Kubeconfig::read().map_err(|e| e.to_string())?
+        Kubeconfig::read().map_err(|e| e.to_string())
     };
-    
-    Ok(kubeconfig.current_context.unwrap_or_else(|| "default".to_string()))
+
+    match kubeconfig {
+        Ok(kc) => Ok(kc.current_context.unwrap_or_else(|| "default".to_string())),
+        Err(_) if in_cluster_available() => Ok(IN_CLUSTER_CONTEXT.to_string()),
+        Err(e) => Err(e),
+    }
 }
 
 // Helper to persist context change to kubeconfig file
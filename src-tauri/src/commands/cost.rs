@@ -1,29 +1,86 @@
 
 use tauri::State;
-use kube::api::{Api, ListParams};
+use kube::api::{Api, ListParams, DynamicObject};
 use crate::state::AppState;
 use crate::models::{ClusterCostReport, ResourceCost, NamespaceCost};
 use crate::client::create_client;
 use crate::utils::{parse_cpu_to_milli, parse_memory_to_bytes};
+use crate::cost_store::{self, CostDelta, CostHistoryPoint};
+use crate::pricing::{self, PricingProvider};
 
-// Azure pricing constants (East US, Linux, D-series VMs as baseline)
-// Based on Azure D2s v3: $0.096/hour for 2 vCPU + 8GB RAM
-// CPU: ~$0.048 per vCPU per hour
-// Memory: ~$0.006 per GB per hour (derived from VM pricing)
-const AZURE_CPU_PRICE_PER_CORE_HOUR: f64 = 0.048;
-const AZURE_MEMORY_PRICE_PER_GB_HOUR: f64 = 0.006;
 const HOURS_PER_MONTH: f64 = 730.0; // Average hours in a month
 
+/// Usage figures for one pod, aggregated across its containers from
+/// metrics-server's `PodMetrics`. Same `DynamicObject` + `metrics.k8s.io`
+/// approach as `commands::resources::get_resource_metrics`, since there's no
+/// typed crate for the metrics API in this tree.
+async fn fetch_pod_usage(client: &kube::Client) -> std::collections::HashMap<(String, String), (f64, f64)> {
+    let api_resource = kube::discovery::ApiResource {
+        group: "metrics.k8s.io".to_string(),
+        version: "v1beta1".to_string(),
+        api_version: "metrics.k8s.io/v1beta1".to_string(),
+        kind: "PodMetrics".to_string(),
+        plural: "pods".to_string(),
+    };
+    let api: Api<DynamicObject> = Api::all_with(client.clone(), &api_resource);
+
+    let list = match api.list(&ListParams::default()).await {
+        Ok(list) => list,
+        Err(_) => return std::collections::HashMap::new(),
+    };
+
+    let mut usage = std::collections::HashMap::new();
+    for item in list.items {
+        let name = match item.metadata.name.clone() {
+            Some(n) => n,
+            None => continue,
+        };
+        let namespace = item.metadata.namespace.clone().unwrap_or_default();
+        let containers = match item.data.get("containers").and_then(|c| c.as_array()) {
+            Some(c) => c,
+            None => continue,
+        };
+
+        let mut cpu_milli: u64 = 0;
+        let mut mem_bytes: u64 = 0;
+        for c in containers {
+            if let Some(usage_obj) = c.get("usage") {
+                if let Some(cpu) = usage_obj.get("cpu").and_then(|v| v.as_str()) {
+                    cpu_milli += parse_cpu_to_milli(cpu);
+                }
+                if let Some(mem) = usage_obj.get("memory").and_then(|v| v.as_str()) {
+                    mem_bytes += parse_memory_to_bytes(mem);
+                }
+            }
+        }
+
+        usage.insert((namespace, name), (cpu_milli as f64 / 1000.0, mem_bytes as f64 / (1024.0 * 1024.0 * 1024.0)));
+    }
+    usage
+}
+
 #[tauri::command]
-pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<ClusterCostReport, String> {
+pub async fn get_cluster_cost_report(state: State<'_, AppState>, use_actual_usage: Option<bool>) -> Result<ClusterCostReport, String> {
     let client = create_client(state.clone()).await?;
     let pods_api: Api<k8s_openapi::api::core::v1::Pod> = Api::all(client.clone());
 
+    let provider_name = state.pricing_provider.lock().unwrap().clone();
+    let provider = pricing::provider_for(&provider_name);
+    let cpu_price = provider.cpu_price_per_core_hour();
+    let memory_price = provider.memory_price_per_gb_hour();
+
+    let use_actual_usage = use_actual_usage.unwrap_or(false);
+    let pod_usage = if use_actual_usage {
+        fetch_pod_usage(&client).await
+    } else {
+        std::collections::HashMap::new()
+    };
+
     // We list all pods to calculate resource requests
     let pods = pods_api.list(&ListParams::default()).await.map_err(|e| e.to_string())?;
 
     // Aggregate costs by namespace
-    let mut namespace_costs: std::collections::HashMap<String, (f64, f64, u32, Vec<ResourceCost>)> = std::collections::HashMap::new();
+    let mut namespace_costs: std::collections::HashMap<String, (f64, f64, u32, Vec<ResourceCost>, f64)> = std::collections::HashMap::new();
 
     for pod in pods.items {
         let namespace = pod.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
@@ -59,8 +116,8 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
         let memory_gb = pod_memory_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
 
         // Calculate monthly costs based on requests
-        let cpu_cost = cpu_cores * AZURE_CPU_PRICE_PER_CORE_HOUR * HOURS_PER_MONTH;
-        let memory_cost = memory_gb * AZURE_MEMORY_PRICE_PER_GB_HOUR * HOURS_PER_MONTH;
+        let cpu_cost = cpu_cores * cpu_price * HOURS_PER_MONTH;
+        let memory_cost = memory_gb * memory_price * HOURS_PER_MONTH;
         let total_cost = cpu_cost + memory_cost;
 
         // Get owner reference to group by deployment/replicaset
@@ -70,6 +127,16 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
             .map(|r| r.name.clone())
             .unwrap_or_else(|| pod_name.clone());
 
+        let (cpu_used_cores, memory_used_gb, total_cost_used_monthly, efficiency) =
+            match pod_usage.get(&(namespace.clone(), pod_name.clone())) {
+                Some(&(used_cpu_cores, used_memory_gb)) => {
+                    let used_cost = used_cpu_cores * cpu_price * HOURS_PER_MONTH + used_memory_gb * memory_price * HOURS_PER_MONTH;
+                    let eff = if total_cost > 0.0 { Some(used_cost / total_cost) } else { None };
+                    (Some(used_cpu_cores), Some(used_memory_gb), Some(used_cost), eff)
+                }
+                None => (None, None, None, None),
+            };
+
         let resource_cost = ResourceCost {
             name: owner_name,
             namespace: namespace.clone(),
@@ -80,21 +147,28 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
             memory_cost_monthly: memory_cost,
             total_cost_monthly: total_cost,
             pod_count: 1,
+            cpu_used_cores,
+            memory_used_gb,
+            total_cost_used_monthly,
+            efficiency,
         };
 
-        let entry = namespace_costs.entry(namespace.clone()).or_insert((0.0, 0.0, 0, Vec::new()));
+        let entry = namespace_costs.entry(namespace.clone()).or_insert((0.0, 0.0, 0, Vec::new(), 0.0));
         entry.0 += total_cost;
         entry.1 += cpu_cost;
         entry.2 += 1;
+        entry.4 += total_cost_used_monthly.unwrap_or(0.0);
         entry.3.push(resource_cost);
     }
 
     let mut total_monthly_cost = 0.0;
+    let mut total_used_monthly_cost = 0.0;
     let mut breakdown: Vec<NamespaceCost> = Vec::new();
 
-    for (ns, (total, cpu, count, resources)) in namespace_costs {
+    for (ns, (total, cpu, count, resources, used_total)) in namespace_costs {
         total_monthly_cost += total;
-        
+        total_used_monthly_cost += used_total;
+
         // Group resources by owner to simplify report
         let mut grouped_resources: std::collections::HashMap<String, ResourceCost> = std::collections::HashMap::new();
         for r in resources {
@@ -108,6 +182,10 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
                 memory_cost_monthly: 0.0,
                 total_cost_monthly: 0.0,
                 pod_count: 0,
+                cpu_used_cores: Some(0.0),
+                memory_used_gb: Some(0.0),
+                total_cost_used_monthly: Some(0.0),
+                efficiency: None,
             });
             entry.cpu_cores += r.cpu_cores;
             entry.memory_gb += r.memory_gb;
@@ -115,11 +193,27 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
             entry.memory_cost_monthly += r.memory_cost_monthly;
             entry.total_cost_monthly += r.total_cost_monthly;
             entry.pod_count += 1;
+            if use_actual_usage {
+                *entry.cpu_used_cores.get_or_insert(0.0) += r.cpu_used_cores.unwrap_or(0.0);
+                *entry.memory_used_gb.get_or_insert(0.0) += r.memory_used_gb.unwrap_or(0.0);
+                *entry.total_cost_used_monthly.get_or_insert(0.0) += r.total_cost_used_monthly.unwrap_or(0.0);
+            } else {
+                entry.cpu_used_cores = None;
+                entry.memory_used_gb = None;
+                entry.total_cost_used_monthly = None;
+            }
+        }
+
+        for r in grouped_resources.values_mut() {
+            r.efficiency = r.total_cost_used_monthly.filter(|_| r.total_cost_monthly > 0.0).map(|used| used / r.total_cost_monthly);
         }
 
         let mut top_resources: Vec<ResourceCost> = grouped_resources.into_values().collect();
         top_resources.sort_by(|a, b| b.total_cost_monthly.partial_cmp(&a.total_cost_monthly).unwrap());
 
+        let namespace_used_total = if use_actual_usage { Some(used_total) } else { None };
+        let namespace_efficiency = namespace_used_total.filter(|_| total > 0.0).map(|used| used / total);
+
         breakdown.push(NamespaceCost {
             namespace: ns,
             total_cost_monthly: total,
@@ -129,12 +223,17 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
             cpu_cores: 0.0, // Should be calculated but setting default for now
             memory_gb: 0.0,
             top_resources: top_resources.into_iter().take(10).collect(),
+            total_cost_used_monthly: namespace_used_total,
+            efficiency: namespace_efficiency,
         });
     }
 
     breakdown.sort_by(|a, b| b.total_cost_monthly.partial_cmp(&a.total_cost_monthly).unwrap());
 
-    Ok(ClusterCostReport {
+    let cluster_used_total = if use_actual_usage { Some(total_used_monthly_cost) } else { None };
+    let cluster_efficiency = cluster_used_total.filter(|_| total_monthly_cost > 0.0).map(|used| used / total_monthly_cost);
+
+    let report = ClusterCostReport {
         total_cost_monthly: total_monthly_cost,
         cpu_cost_monthly: 0.0, // Fill these if needed
         memory_cost_monthly: 0.0,
@@ -142,10 +241,60 @@ pub async fn get_cluster_cost_report(state: State<'_, AppState>) -> Result<Clust
         total_memory_gb: 0.0,
         total_pods: 0,
         namespaces: breakdown,
-        cpu_price_per_core_hour: AZURE_CPU_PRICE_PER_CORE_HOUR,
-        memory_price_per_gb_hour: AZURE_MEMORY_PRICE_PER_GB_HOUR,
-        provider: "Azure".to_string(),
-        currency: "USD".to_string(),
+        cpu_price_per_core_hour: cpu_price,
+        memory_price_per_gb_hour: memory_price,
+        provider: provider.name().to_string(),
+        currency: provider.currency().to_string(),
+        total_cost_used_monthly: cluster_used_total,
+        efficiency: cluster_efficiency,
         generated_at: chrono::Utc::now().to_rfc3339(),
-    })
+    };
+
+    if let Err(e) = cost_store::save_snapshot_if_changed(&report) {
+        eprintln!("[cost] Failed to persist cost snapshot: {}", e);
+    }
+
+    Ok(report)
+}
+
+/// Time series of stored cost snapshots at or after `since` (an RFC3339
+/// timestamp), optionally narrowed to a single namespace's totals.
+#[tauri::command]
+pub async fn get_cost_history(namespace: Option<String>, since: String) -> Result<Vec<CostHistoryPoint>, String> {
+    cost_store::get_cost_history(namespace.as_deref(), &since)
+}
+
+/// Diffs the latest stored snapshot against the closest one at or before
+/// `since` (an RFC3339 timestamp, e.g. "7 days ago") so the UI can show
+/// something like "namespace X is up $120/mo week-over-week".
+#[tauri::command]
+pub async fn get_cost_delta(namespace: Option<String>, since: String) -> Result<Option<CostDelta>, String> {
+    cost_store::get_cost_delta(namespace.as_deref(), &since)
+}
+
+/// The most recently persisted cost snapshot, if any - lets the frontend
+/// show trends immediately on startup instead of waiting for the first live
+/// `get_cluster_cost_report` call to complete.
+#[tauri::command]
+pub async fn get_last_cost_snapshot() -> Result<Option<ClusterCostReport>, String> {
+    cost_store::latest_snapshot()
+}
+
+/// Which cloud's pricing constants `get_cluster_cost_report` currently uses
+/// ("azure", "aws", or "gcp").
+#[tauri::command]
+pub async fn get_pricing_provider(state: State<'_, AppState>) -> Result<String, String> {
+    Ok(state.pricing_provider.lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub async fn set_pricing_provider(state: State<'_, AppState>, provider: String) -> Result<(), String> {
+    // Validated up front so a typo fails loudly instead of silently falling
+    // back to Azure inside `pricing::provider_for` on the next report.
+    let normalized = provider.to_ascii_lowercase();
+    if !["azure", "aws", "gcp"].contains(&normalized.as_str()) {
+        return Err(format!("Unknown pricing provider: {} (expected azure, aws, or gcp)", provider));
+    }
+    *state.pricing_provider.lock().unwrap() = normalized;
+    Ok(())
 }
@@ -2,20 +2,123 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Health of a dependency relative to its minimum required version.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyHealth {
+    Missing,
+    Outdated,
+    Ok,
+    /// Installed, but the version couldn't be parsed - don't block on it,
+    /// but flag it for the user.
+    Unknown,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DependencyStatus {
     pub name: String,
     pub installed: bool,
     pub version: Option<String>,
     pub path: Option<String>,
+    /// Minimum required version for this tool, if one is tracked (e.g. "v1.28.0").
+    pub required: Option<String>,
+    /// `None` when there's no required-version constraint for this tool, or
+    /// the installed version couldn't be parsed.
+    pub satisfies: Option<bool>,
+    pub status: DependencyHealth,
+}
+
+/// Minimum required version per tool, as `vX.Y.Z`. Tools with no entry have
+/// no enforced floor.
+fn required_version(name: &str) -> Option<&'static str> {
+    match name {
+        "kubectl" => Some("v1.28.0"),
+        "helm" => Some("v3.12.0"),
+        _ => None,
+    }
+}
+
+/// Parse a `vX.Y.Z[-prerelease][+build]`-ish string into a comparable
+/// `semver::Version`, stripping the leading `v`.
+fn parse_semver(version: &str) -> Option<semver::Version> {
+    semver::Version::parse(version.trim_start_matches('v')).ok()
 }
 
-/// Custom tool paths configured by the user (Windows only feature)
+/// Compare an installed version against this tool's required minimum and
+/// derive `(satisfies, status)`. Assumes the tool is installed; callers
+/// handle the `Missing` case separately.
+fn evaluate_version(version: Option<&str>, required: Option<&str>) -> (Option<bool>, DependencyHealth) {
+    let Some(required) = required else {
+        return (None, DependencyHealth::Ok);
+    };
+    let Some(version) = version else {
+        return (None, DependencyHealth::Unknown);
+    };
+
+    match (parse_semver(version), parse_semver(required)) {
+        (Some(actual), Some(min)) => {
+            let ok = actual >= min;
+            (Some(ok), if ok { DependencyHealth::Ok } else { DependencyHealth::Outdated })
+        }
+        _ => (None, DependencyHealth::Unknown),
+    }
+}
+
+/// Build a `DependencyStatus`, filling in `required`/`satisfies`/`status`
+/// from the tool's version constraint.
+fn build_status(name: &str, installed: bool, version: Option<String>, path: Option<String>) -> DependencyStatus {
+    let required = required_version(name).map(|s| s.to_string());
+
+    let (satisfies, status) = if !installed {
+        (None, DependencyHealth::Missing)
+    } else {
+        evaluate_version(version.as_deref(), required.as_deref())
+    };
+
+    DependencyStatus { name: name.to_string(), installed, version, path, required, satisfies, status }
+}
+
+/// A cheap fingerprint of a tool executable, used to decide whether a cached
+/// detection result is still valid without re-spawning `--version`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct ToolFingerprint {
+    modified_secs: u64,
+    size: u64,
+}
+
+impl ToolFingerprint {
+    fn for_path(path: &str) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified_secs = metadata
+            .modified()
+            .ok()?
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        Some(Self { modified_secs, size: metadata.len() })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedDetection {
+    status: DependencyStatus,
+    fingerprint: ToolFingerprint,
+}
+
+/// Custom tool paths configured by the user, plus a detection cache so
+/// `check_dependencies` doesn't have to re-spawn `--version` for every tool
+/// on every call. Applies on every platform - manual overrides for
+/// out-of-PATH installs aren't a Windows-only problem (Homebrew casks, asdf
+/// shims, corporate install dirs hit macOS/Linux just as often).
 #[derive(Debug, Serialize, Deserialize, Default)]
 struct CustomToolPaths {
     paths: HashMap<String, String>,
+    #[serde(default)]
+    detection_cache: HashMap<String, CachedDetection>,
 }
 
 /// Get the path to the custom tool paths config file
@@ -51,6 +154,116 @@ fn save_custom_paths(paths: &CustomToolPaths) -> Result<(), String> {
     Ok(())
 }
 
+/// Return a cached `DependencyStatus` for `name` if its executable at `path`
+/// hasn't changed (same modified-time/size) since it was last detected.
+fn cached_status(custom_paths: &CustomToolPaths, name: &str, path: &str) -> Option<DependencyStatus> {
+    let fingerprint = ToolFingerprint::for_path(path)?;
+    let cached = custom_paths.detection_cache.get(name)?;
+    if cached.fingerprint == fingerprint && cached.status.path.as_deref() == Some(path) {
+        Some(cached.status.clone())
+    } else {
+        None
+    }
+}
+
+/// Persist a fresh detection result into the on-disk cache so the next
+/// `check_dependencies` call can skip re-spawning `--version`.
+fn store_cached_status(status: &DependencyStatus) {
+    let Some(path) = &status.path else { return };
+    let Some(fingerprint) = ToolFingerprint::for_path(path) else { return };
+
+    let mut custom_paths = load_custom_paths();
+    custom_paths.detection_cache.insert(status.name.clone(), CachedDetection { status: status.clone(), fingerprint });
+    let _ = save_custom_paths(&custom_paths);
+}
+
+/// Registry-based tool discovery, consulted before the hardcoded common
+/// paths below. Avoids hardcoding per-version WinGet package GUIDs by
+/// reading the same locations the Windows shell itself uses to resolve an
+/// executable name.
+#[cfg(target_os = "windows")]
+mod windows_registry {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    /// Map a tool's canonical name to its Windows executable name and a
+    /// fragment to match against `Uninstall` entries' `DisplayName`.
+    fn registry_hints(name: &str) -> Option<(&'static str, &'static str)> {
+        match name {
+            "kubectl" => Some(("kubectl.exe", "kubernetes")),
+            "helm" => Some(("helm.exe", "helm")),
+            "vcluster" => Some(("vcluster.exe", "vcluster")),
+            "ollama" => Some(("ollama.exe", "ollama")),
+            "az" => Some(("az.cmd", "azure cli")),
+            _ => None,
+        }
+    }
+
+    fn hives_and_views() -> [(isize, u32); 4] {
+        [
+            (HKEY_LOCAL_MACHINE, KEY_WOW64_64KEY),
+            (HKEY_LOCAL_MACHINE, KEY_WOW64_32KEY),
+            (HKEY_CURRENT_USER, KEY_WOW64_64KEY),
+            (HKEY_CURRENT_USER, KEY_WOW64_32KEY),
+        ]
+    }
+
+    /// `HKLM/HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\<exe>`:
+    /// the key's default value is the full executable path.
+    fn app_paths_lookup(exe_name: &str) -> Option<String> {
+        let subkey = format!(r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\{}", exe_name);
+
+        for (hive, view) in hives_and_views() {
+            let root = RegKey::predef(hive);
+            let Ok(key) = root.open_subkey_with_flags(&subkey, KEY_READ | view) else { continue };
+            let Ok(default_value) = key.get_value::<String, _>("") else { continue };
+
+            if std::path::Path::new(&default_value).exists() {
+                return Some(default_value);
+            }
+        }
+        None
+    }
+
+    /// Enumerate `SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall\*` and
+    /// match `DisplayName` against `name_fragment`, then join the entry's
+    /// `InstallLocation` with the expected exe name.
+    fn uninstall_lookup(name_fragment: &str, exe_name: &str) -> Option<String> {
+        for (hive, view) in hives_and_views() {
+            let root = RegKey::predef(hive);
+            let Ok(uninstall) = root.open_subkey_with_flags(
+                r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+                KEY_READ | view,
+            ) else { continue };
+
+            for subkey_name in uninstall.enum_keys().flatten() {
+                let Ok(entry) = uninstall.open_subkey(&subkey_name) else { continue };
+                let display_name: String = entry.get_value("DisplayName").unwrap_or_default();
+                if !display_name.to_lowercase().contains(name_fragment) {
+                    continue;
+                }
+
+                let install_location: String = match entry.get_value("InstallLocation") {
+                    Ok(loc) => loc,
+                    Err(_) => continue,
+                };
+                let candidate = std::path::Path::new(&install_location).join(exe_name);
+                if candidate.exists() {
+                    return Some(candidate.to_string_lossy().into_owned());
+                }
+            }
+        }
+        None
+    }
+
+    /// Resolve `name` via the registry, trying `App Paths` first (cheap,
+    /// exact) and falling back to scanning `Uninstall` entries.
+    pub fn find_tool_path(name: &str) -> Option<String> {
+        let (exe_name, display_fragment) = registry_hints(name)?;
+        app_paths_lookup(exe_name).or_else(|| uninstall_lookup(display_fragment, exe_name))
+    }
+}
+
 /// Get common install locations for tools on Windows
 #[cfg(target_os = "windows")]
 fn get_windows_common_paths(name: &str) -> Vec<String> {
@@ -86,11 +299,17 @@ fn get_windows_common_paths(name: &str) -> Vec<String> {
     }
 }
 
-/// Check a tool at a specific path and get its version
-fn check_tool_at_path(name: &str, path: &str, version_args: &[&str]) -> DependencyStatus {
+/// Check a tool at a specific path and get its version, reusing a cached
+/// result if the executable's fingerprint hasn't changed.
+fn check_tool_at_path(name: &str, path: &str, version_args: &[&str], custom_paths: &CustomToolPaths) -> DependencyStatus {
+    if let Some(status) = cached_status(custom_paths, name, path) {
+        return status;
+    }
+
     let version = if !version_args.is_empty() {
         let mut cmd = Command::new(path);
         cmd.args(version_args);
+        crate::tool_env::inherit_env(&mut cmd);
 
         #[cfg(target_os = "windows")]
         {
@@ -111,12 +330,9 @@ fn check_tool_at_path(name: &str, path: &str, version_args: &[&str]) -> Dependen
         None
     };
 
-    DependencyStatus {
-        name: name.to_string(),
-        installed: true,
-        version,
-        path: Some(path.to_string()),
-    }
+    let status = build_status(name, true, version, Some(path.to_string()));
+    store_cached_status(&status);
+    status
 }
 
 /// Check if a command exists in PATH and get its version
@@ -124,23 +340,31 @@ fn check_tool(name: &str, version_args: &[&str], custom_paths: &CustomToolPaths)
     // First check if user has configured a custom path for this tool
     if let Some(custom_path) = custom_paths.paths.get(name) {
         if std::path::Path::new(custom_path).exists() {
-            return check_tool_at_path(name, custom_path, version_args);
+            return check_tool_at_path(name, custom_path, version_args, custom_paths);
         }
     }
 
-    // Check if the command exists in PATH
+    // Check if the command exists in PATH (the augmented one, so tools in
+    // Homebrew/asdf/mise/Scoop/WinGet locations are found even if the
+    // process's own PATH wasn't patched for some reason).
     let which_result = if cfg!(target_os = "windows") {
         #[cfg(target_os = "windows")]
         {
             use std::os::windows::process::CommandExt;
-            Command::new("where").arg(name).creation_flags(0x08000000).output()
+            let mut cmd = Command::new("where");
+            cmd.arg(name);
+            crate::tool_env::inherit_env(&mut cmd);
+            cmd.creation_flags(0x08000000).output()
         }
         #[cfg(not(target_os = "windows"))]
         {
             Command::new("where").arg(name).output()
         }
     } else {
-        Command::new("which").arg(name).output()
+        let mut cmd = Command::new("which");
+        cmd.arg(name);
+        crate::tool_env::inherit_env(&mut cmd);
+        cmd.output()
     };
 
     #[allow(unused_mut)]
@@ -152,7 +376,13 @@ fn check_tool(name: &str, version_args: &[&str], custom_paths: &CustomToolPaths)
         _ => None,
     };
 
-    // On Windows, also check common install locations if not found in PATH
+    // On Windows, consult the registry before falling back to the hardcoded
+    // common install locations - this survives WinGet package GUID changes.
+    #[cfg(target_os = "windows")]
+    if path.is_none() {
+        path = windows_registry::find_tool_path(name);
+    }
+
     #[cfg(target_os = "windows")]
     if path.is_none() {
         for common_path in get_windows_common_paths(name) {
@@ -163,21 +393,27 @@ fn check_tool(name: &str, version_args: &[&str], custom_paths: &CustomToolPaths)
         }
     }
 
+    // Last resort on every platform: scan the same augmented search
+    // directories applied to the process-wide PATH.
     if path.is_none() {
-        return DependencyStatus {
-            name: name.to_string(),
-            installed: false,
-            version: None,
-            path: None,
-        };
+        path = crate::tool_env::find_in_search_dirs(name).map(|p| p.to_string_lossy().into_owned());
+    }
+
+    if path.is_none() {
+        return build_status(name, false, None, None);
     }
 
     let path_ref = path.as_ref().unwrap();
 
+    if let Some(status) = cached_status(custom_paths, name, path_ref) {
+        return status;
+    }
+
     // Get version - use the full path if we found one (important for Windows non-PATH installs)
     let version = if !version_args.is_empty() {
         let mut cmd = Command::new(path_ref);
         cmd.args(version_args);
+        crate::tool_env::inherit_env(&mut cmd);
 
         #[cfg(target_os = "windows")]
         {
@@ -200,12 +436,9 @@ fn check_tool(name: &str, version_args: &[&str], custom_paths: &CustomToolPaths)
         None
     };
 
-    DependencyStatus {
-        name: name.to_string(),
-        installed: true,
-        version,
-        path,
-    }
+    let status = build_status(name, true, version, path);
+    store_cached_status(&status);
+    status
 }
 
 /// Extract version number from command output
@@ -251,23 +484,13 @@ async fn check_agent_server() -> DependencyStatus {
     if let Some(client) = client {
         match client.get("http://127.0.0.1:8765/health").send().await {
             Ok(resp) if resp.status().is_success() => {
-                return DependencyStatus {
-                    name: "agent-server".to_string(),
-                    installed: true,
-                    version: Some("running".to_string()),
-                    path: Some("http://127.0.0.1:8765".to_string()),
-                };
+                return build_status("agent-server", true, Some("running".to_string()), Some("http://127.0.0.1:8765".to_string()));
             }
             _ => {}
         }
     }
 
-    DependencyStatus {
-        name: "agent-server".to_string(),
-        installed: false,
-        version: None,
-        path: None,
-    }
+    build_status("agent-server", false, None, None)
 }
 
 #[tauri::command]
@@ -287,7 +510,8 @@ pub async fn check_dependencies() -> Result<Vec<DependencyStatus>, String> {
     Ok(results)
 }
 
-/// Set a custom path for a tool (Windows only feature for tools not in PATH)
+/// Set a custom path for a tool not found in PATH. Available on every
+/// platform - out-of-PATH installs aren't unique to Windows.
 #[tauri::command]
 pub async fn set_tool_path(tool_name: String, tool_path: String) -> Result<DependencyStatus, String> {
     // Validate the path exists
@@ -301,14 +525,156 @@ pub async fn set_tool_path(tool_name: String, tool_path: String) -> Result<Depen
     save_custom_paths(&custom_paths)?;
 
     // Return the status of the tool at this path
-    let version_args: &[&str] = match tool_name.as_str() {
+    Ok(check_tool_at_path(&tool_name, &tool_path, version_args_for(&tool_name), &custom_paths))
+}
+
+/// The CLI flags used to print a tool's version, keyed by canonical tool name.
+fn version_args_for(name: &str) -> &'static [&'static str] {
+    match name {
         "kubectl" => &["version", "--client", "--short"],
         "helm" => &["version", "--short"],
         "vcluster" | "ollama" => &["--version"],
         _ => &[],
-    };
+    }
+}
+
+/// The platform-appropriate command (and args) to install a missing tool.
+/// Package IDs match the WinGet packages already referenced in
+/// `get_windows_common_paths`.
+fn install_command(name: &str) -> Option<(&'static str, Vec<String>)> {
+    #[cfg(target_os = "windows")]
+    {
+        let package_id = match name {
+            "kubectl" => "Kubernetes.kubectl",
+            "helm" => "Helm.Helm",
+            "vcluster" => "loft-sh.vcluster",
+            "ollama" => "Ollama.Ollama",
+            _ => return None,
+        };
+        return Some(("winget", vec!["install".to_string(), "-e".to_string(), "--id".to_string(), package_id.to_string()]));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let formula = match name {
+            "kubectl" => "kubectl",
+            "helm" => "helm",
+            "vcluster" => "vcluster",
+            "ollama" => "ollama",
+            _ => return None,
+        };
+        return Some(("brew", vec!["install".to_string(), formula.to_string()]));
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        match name {
+            "kubectl" => Some(("sh", vec!["-c".to_string(), "curl -fsSL -o /tmp/kubectl \"https://dl.k8s.io/release/$(curl -fsSL https://dl.k8s.io/release/stable.txt)/bin/linux/amd64/kubectl\" && sudo install -m 0755 /tmp/kubectl /usr/local/bin/kubectl".to_string()])),
+            "helm" => Some(("sh", vec!["-c".to_string(), "curl -fsSL https://raw.githubusercontent.com/helm/helm/main/scripts/get-helm-3 | bash".to_string()])),
+            "vcluster" => Some(("sh", vec!["-c".to_string(), "curl -fsSL -o /tmp/vcluster \"https://github.com/loft-sh/vcluster/releases/latest/download/vcluster-linux-amd64\" && sudo install -m 0755 /tmp/vcluster /usr/local/bin/vcluster".to_string()])),
+            "ollama" => Some(("sh", vec!["-c".to_string(), "curl -fsSL https://ollama.com/install.sh | sh".to_string()])),
+            _ => None,
+        }
+    }
+}
+
+/// Suggest the install command for a missing tool without running it, so the
+/// UI can show it to the user (e.g. "Run: brew install kubectl").
+#[tauri::command]
+pub async fn suggest_install(tool_name: String) -> Result<String, String> {
+    let (program, args) = install_command(&tool_name)
+        .ok_or_else(|| format!("No known install command for '{}'", tool_name))?;
+    Ok(format!("{} {}", program, args.join(" ")))
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallProgressEvent {
+    pub tool_name: String,
+    pub line: String,
+    pub stream: String, // "stdout" | "stderr"
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallProgressDone {
+    pub tool_name: String,
+    pub success: bool,
+}
+
+/// Install a missing tool with the platform-appropriate command, streaming
+/// output back as `dependency://install-progress` events, then re-check the
+/// tool and persist the discovered path as a custom path.
+#[tauri::command]
+pub async fn install_tool(tool_name: String, app_handle: AppHandle) -> Result<DependencyStatus, String> {
+    let (program, args) = install_command(&tool_name)
+        .ok_or_else(|| format!("No known install command for '{}'", tool_name))?;
+
+    let mut cmd = tokio::process::Command::new(program);
+    cmd.args(&args);
+    crate::tool_env::inherit_env_tokio(&mut cmd);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn install command for {}: {}", tool_name, e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to capture install stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture install stderr")?;
+
+    let stdout_app = app_handle.clone();
+    let stdout_tool = tool_name.clone();
+    let stdout_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = stdout_app.emit(
+                "dependency://install-progress",
+                InstallProgressEvent { tool_name: stdout_tool.clone(), line, stream: "stdout".to_string() },
+            );
+        }
+    });
+
+    let stderr_app = app_handle.clone();
+    let stderr_tool = tool_name.clone();
+    let stderr_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = stderr_app.emit(
+                "dependency://install-progress",
+                InstallProgressEvent { tool_name: stderr_tool.clone(), line, stream: "stderr".to_string() },
+            );
+        }
+    });
+
+    let status = child.wait().await.map_err(|e| format!("Failed to wait on install command: {}", e))?;
+    let _ = stdout_task.await;
+    let _ = stderr_task.await;
+
+    let _ = app_handle.emit(
+        "dependency://install-progress",
+        InstallProgressDone { tool_name: tool_name.clone(), success: status.success() },
+    );
+
+    if !status.success() {
+        return Err(format!("Install command for {} exited with a failure status", tool_name));
+    }
+
+    // Re-check the tool now that installation finished.
+    let custom_paths = load_custom_paths();
+    let result = check_tool(&tool_name, version_args_for(&tool_name), &custom_paths);
+
+    if let Some(found_path) = &result.path {
+        let mut custom_paths = load_custom_paths();
+        custom_paths.paths.insert(tool_name.clone(), found_path.clone());
+        save_custom_paths(&custom_paths)?;
+    }
 
-    Ok(check_tool_at_path(&tool_name, &tool_path, version_args))
+    Ok(result)
 }
 
 /// Clear a custom tool path
@@ -316,6 +682,7 @@ pub async fn set_tool_path(tool_name: String, tool_path: String) -> Result<Depen
 pub async fn clear_tool_path(tool_name: String) -> Result<(), String> {
     let mut custom_paths = load_custom_paths();
     custom_paths.paths.remove(&tool_name);
+    custom_paths.detection_cache.remove(&tool_name);
     save_custom_paths(&custom_paths)?;
     Ok(())
 }
@@ -8,32 +8,36 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
-// Helper function for cache path
-fn get_discovery_cache_path(context: &str) -> Option<PathBuf> {
+// Shared `.kube/cache/opspilot/` dir other cache files (nav sweep status,
+// per-context discovery snapshots) live under.
+pub(crate) fn get_opspilot_cache_dir() -> Option<PathBuf> {
     let home = if cfg!(target_os = "windows") {
         std::env::var("USERPROFILE").ok()
     } else {
         std::env::var("HOME").ok()
     };
 
-    if let Some(h) = home {
-        let mut p = PathBuf::from(h);
-        p.push(".kube");
-        p.push("cache");
-        p.push("opspilot");
-        if let Err(_) = fs::create_dir_all(&p) {
-            return None;
-        }
-        // Sanitize context name for filename
-        let safe_ctx = context.replace(|c: char| !c.is_alphanumeric() && c != '-' && c != '_', "_");
-        p.push(format!("discovery_{}.json", safe_ctx));
-        Some(p)
-    } else {
-        None
+    let h = home?;
+    let mut p = PathBuf::from(h);
+    p.push(".kube");
+    p.push("cache");
+    p.push("opspilot");
+    if fs::create_dir_all(&p).is_err() {
+        return None;
     }
+    Some(p)
+}
+
+// Helper function for cache path
+pub(crate) fn get_discovery_cache_path(context: &str) -> Option<PathBuf> {
+    let mut p = get_opspilot_cache_dir()?;
+    // Sanitize context name for filename
+    let safe_ctx = context.replace(|c: char| !c.is_alphanumeric() && c != '-' && c != '_', "_");
+    p.push(format!("discovery_{}.json", safe_ctx));
+    Some(p)
 }
 
-fn load_cached_nav_structure(context: &str) -> Option<Vec<NavGroup>> {
+pub(crate) fn load_cached_nav_structure(context: &str) -> Option<Vec<NavGroup>> {
     if let Some(path) = get_discovery_cache_path(context) {
         if let Ok(file) = fs::File::open(&path) {
             // Check file age (e.g. 1 hour)
@@ -57,7 +61,7 @@ fn load_cached_nav_structure(context: &str) -> Option<Vec<NavGroup>> {
     None
 }
 
-fn save_cached_nav_structure(context: &str, groups: &Vec<NavGroup>) {
+pub(crate) fn save_cached_nav_structure(context: &str, groups: &Vec<NavGroup>) {
     if groups.is_empty() { return; }
     if let Some(path) = get_discovery_cache_path(context) {
         if let Ok(file) = fs::File::create(&path) {
@@ -104,12 +108,18 @@ pub async fn get_cached_discovery(state: &State<'_, AppState>, client: Client) -
         }
     };
 
+    let context_name = get_current_context_name(state.clone(), None).await.unwrap_or("default".to_string());
+
     if let Some(discovery) = cached {
+        crate::internal_metrics::DISCOVERY_CACHE_HITS.inc(&context_name);
         return Ok(discovery);
     }
+    crate::internal_metrics::DISCOVERY_CACHE_MISSES.inc(&context_name);
 
     // Refresh cache
+    let started = std::time::Instant::now();
     let discovery = std::sync::Arc::new(Discovery::new(client).run().await.map_err(|e| e.to_string())?);
+    crate::internal_metrics::DISCOVERY_RUN_DURATION.record(&context_name, started.elapsed());
 
     // Update cache using try_lock
     if let Ok(mut cache) = state.discovery_cache.try_lock() {
@@ -128,23 +138,45 @@ pub async fn clear_all_caches(state: State<'_, AppState>) -> Result<(), String>
     if let Ok(mut cache) = state.pod_limits_cache.try_lock() { *cache = None; }
     if let Ok(mut cache) = state.client_cache.try_lock() { *cache = None; }
     if let Ok(mut cache) = state.initial_data_cache.try_lock() { *cache = None; }
+    crate::internal_metrics::record_cache_clear();
     Ok(())
 }
 
+/// Prometheus text exposition format for OpsPilot's own cache/client
+/// behavior (not cluster metrics - see `get_cluster_cockpit` for those).
+#[tauri::command]
+pub fn metrics_text() -> String {
+    crate::internal_metrics::render()
+}
+
 // 1. DISCOVERY ENGINE: Dynamically finds what your cluster supports
 #[tauri::command]
 pub async fn discover_api_resources(state: State<'_, AppState>) -> Result<Vec<NavGroup>, String> {
     let context_name = get_current_context_name(state.clone(), None).await.unwrap_or("default".to_string());
-    
+
     // Try load cache
     if let Some(cached) = load_cached_nav_structure(&context_name) {
         println!("Loaded discovery from cache for {}", context_name);
+        crate::internal_metrics::DISCOVERY_CACHE_HITS.inc(&context_name);
         return Ok(cached);
     }
     println!("Cache miss for {}, running fresh discovery...", context_name);
+    crate::internal_metrics::DISCOVERY_CACHE_MISSES.inc(&context_name);
 
     let client = create_client(state.clone()).await?;
+    let result = build_nav_structure(client, &context_name).await?;
+    save_cached_nav_structure(&context_name, &result);
+    Ok(result)
+}
+
+/// Run discovery + CRD listing against `client` and assemble the categorized
+/// nav structure. Split out of `discover_api_resources` so the nav-sweep
+/// worker can rebuild the same structure for an arbitrary context without
+/// going through `AppState`'s currently-selected context. `context` is only
+/// used to label internal metrics.
+pub(crate) async fn build_nav_structure(client: Client, context: &str) -> Result<Vec<NavGroup>, String> {
     let client2 = client.clone();
+    let crd_started = std::time::Instant::now();
 
     // Parallel Execution: Run Discovery and CRD Listing concurrently
     let (discovery_result, crd_result) = tokio::join!(
@@ -160,6 +192,11 @@ pub async fn discover_api_resources(state: State<'_, AppState>) -> Result<Vec<Na
         }
     );
 
+    crate::internal_metrics::CRD_LIST_DURATION.record(context, crd_started.elapsed());
+    if let Ok(crds) = &crd_result {
+        crate::internal_metrics::record_crds_listed(context, crds.items.len() as u64);
+    }
+
     let discovery = match discovery_result {
         Ok(d) => {
             println!("Discovery success. Found {} groups.", d.groups().count());
@@ -274,6 +311,5 @@ pub async fn discover_api_resources(state: State<'_, AppState>) -> Result<Vec<Na
         result.push(NavGroup { title: "Custom Resources".to_string(), items: custom_resources });
     }
 
-    save_cached_nav_structure(&context_name, &result);
     Ok(result)
 }
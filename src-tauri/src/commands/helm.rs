@@ -1,5 +1,84 @@
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HelmProgressEvent {
+    pub operation_id: String,
+    pub line: String,
+    pub stream: String, // "stdout" | "stderr"
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HelmProgressDone {
+    pub operation_id: String,
+    pub success: bool,
+}
+
+/// Spawn a helm subcommand, emitting `helm://progress` events as stdout/stderr
+/// lines arrive instead of buffering everything until exit, then resolve once
+/// the process actually exits.
+async fn run_helm_streaming(app: &AppHandle, operation_id: &str, args: &[&str]) -> Result<String, String> {
+    let mut child = tokio::process::Command::new("helm")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn helm: {}", e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to capture helm stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture helm stderr")?;
+
+    let mut stdout_lines = Vec::new();
+    let mut stderr_lines = Vec::new();
+
+    let stdout_app = app.clone();
+    let stdout_op = operation_id.to_string();
+    let stdout_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stdout).lines();
+        let mut collected = Vec::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = stdout_app.emit(
+                "helm://progress",
+                HelmProgressEvent { operation_id: stdout_op.clone(), line: line.clone(), stream: "stdout".to_string() },
+            );
+            collected.push(line);
+        }
+        collected
+    });
+
+    let stderr_app = app.clone();
+    let stderr_op = operation_id.to_string();
+    let stderr_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut collected = Vec::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = stderr_app.emit(
+                "helm://progress",
+                HelmProgressEvent { operation_id: stderr_op.clone(), line: line.clone(), stream: "stderr".to_string() },
+            );
+            collected.push(line);
+        }
+        collected
+    });
+
+    let status = child.wait().await.map_err(|e| format!("Failed to wait on helm: {}", e))?;
+    stdout_lines.extend(stdout_task.await.unwrap_or_default());
+    stderr_lines.extend(stderr_task.await.unwrap_or_default());
+
+    let _ = app.emit(
+        "helm://progress",
+        HelmProgressDone { operation_id: operation_id.to_string(), success: status.success() },
+    );
+
+    if !status.success() {
+        return Err(format!("helm {} failed: {}", args.join(" "), stderr_lines.join("\n")));
+    }
+
+    Ok(stdout_lines.join("\n"))
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HelmRelease {
@@ -39,25 +118,71 @@ pub async fn helm_list() -> Result<Vec<HelmRelease>, String> {
 }
 
 #[tauri::command]
-pub async fn helm_uninstall(namespace: String, name: String) -> Result<String, String> {
-    let output = Command::new("helm")
-        .args(["uninstall", &name, "-n", &namespace])
-        .output()
-        .map_err(|e| format!("Failed to execute helm command: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("helm uninstall failed: {}", stderr));
-    }
-
+pub async fn helm_uninstall(app: AppHandle, namespace: String, name: String, operation_id: String) -> Result<String, String> {
+    run_helm_streaming(&app, &operation_id, &["uninstall", &name, "-n", &namespace]).await?;
     Ok(format!("Successfully uninstalled {} from {}", name, namespace))
 }
 
+/// Where a release's chart came from, so the frontend can render provenance
+/// and the outdated check knows which version source to query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ChartSource {
+    HttpRepo { url: String },
+    Oci { reference: String },
+    Unknown,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HelmReleaseDetails {
     pub info: serde_json::Value,
     pub manifest: String,
     pub values: serde_json::Value,
+    pub chart_source: ChartSource,
+}
+
+/// Resolve where a release's chart came from. `helm get metadata` surfaces
+/// the chart's `sources`/`home` fields from Chart.yaml when present; an
+/// `oci://` prefix there (or in the chart name itself, for charts installed
+/// directly from a registry) means we're dealing with an OCI reference.
+fn resolve_chart_source(namespace: &str, name: &str) -> ChartSource {
+    let output = Command::new("helm")
+        .args(["get", "metadata", name, "-n", namespace, "-o", "json"])
+        .output();
+
+    let Ok(output) = output else {
+        return ChartSource::Unknown;
+    };
+    if !output.status.success() {
+        return ChartSource::Unknown;
+    }
+
+    let Ok(metadata) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return ChartSource::Unknown;
+    };
+
+    let candidates = ["source", "home"]
+        .iter()
+        .filter_map(|field| metadata.get(field).and_then(|v| v.as_str()))
+        .chain(
+            metadata
+                .get("sources")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str()),
+        );
+
+    for candidate in candidates {
+        if let Some(reference) = candidate.strip_prefix("oci://") {
+            return ChartSource::Oci { reference: reference.to_string() };
+        }
+        if candidate.starts_with("http://") || candidate.starts_with("https://") {
+            return ChartSource::HttpRepo { url: candidate.to_string() };
+        }
+    }
+
+    ChartSource::Unknown
 }
 
 #[tauri::command]
@@ -91,6 +216,7 @@ pub async fn helm_get_details(namespace: String, name: String) -> Result<HelmRel
         info: status_json.get("info").cloned().unwrap_or(serde_json::Value::Null),
         manifest: status_json.get("manifest").and_then(|v| v.as_str()).unwrap_or("").to_string(),
         values: values_json,
+        chart_source: resolve_chart_source(&namespace, &name),
     })
 }
 
@@ -216,17 +342,615 @@ pub async fn helm_get_resources(namespace: String, name: String) -> Result<Vec<H
     Ok(resources)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HelmOutdated {
+    pub name: String,
+    pub namespace: String,
+    pub current_version: String,
+    pub latest_version: String,
+    pub outdated: bool,
+}
+
+/// Strip an OCI-style `v`/`V` version prefix, e.g. `v1.2.3` -> `1.2.3`.
+fn strip_v_prefix(s: &str) -> &str {
+    s.strip_prefix(['v', 'V']).unwrap_or(s)
+}
+
+/// The dot-separated numeric core of a version string, with any
+/// pre-release/build suffix and leading `v`/`V` removed.
+fn version_core(version: &str) -> &str {
+    strip_v_prefix(version.split(['-', '+']).next().unwrap_or(version))
+}
+
+/// Whether `version`'s core is made up entirely of dot-separated numeric
+/// segments, e.g. `v1.2.3` or `1.2`. Used to filter out non-version tags
+/// (`latest`, `main`, `stable`) before they're sorted alongside real
+/// versions - otherwise they'd all parse to the same all-zero key and
+/// could win arbitrarily on `max_by`'s last-wins tie-break.
+fn looks_like_semver(version: &str) -> bool {
+    let core = version_core(version);
+    !core.is_empty() && core.split('.').all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Split a version string into numeric/non-numeric dot-separated parts and
+/// compare them component-by-component so non-strict semver (e.g. `1.2`)
+/// still orders sensibly against `1.2.0` or `1.10.0`. Callers should filter
+/// inputs with `looks_like_semver` first - non-numeric segments here parse
+/// to `0`, which only gives a sensible ordering among tags already known to
+/// be version-shaped.
+fn version_compare(a: &str, b: &str) -> std::cmp::Ordering {
+    let a_core = version_core(a);
+    let b_core = version_core(b);
+
+    let a_parts: Vec<u64> = a_core.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+    let b_parts: Vec<u64> = b_core.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let a_val = a_parts.get(i).copied().unwrap_or(0);
+        let b_val = b_parts.get(i).copied().unwrap_or(0);
+        match a_val.cmp(&b_val) {
+            std::cmp::Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+
+    std::cmp::Ordering::Equal
+}
+
+fn is_prerelease(version: &str) -> bool {
+    version.contains('-')
+}
+
+/// Chart name as `helm search repo` expects it, resolved from a release's
+/// chart string (e.g. `nginx-1.2.3` -> best-effort `nginx`) combined with the
+/// repo it was installed from, when known. Releases with no discoverable
+/// repo are reported as "unknown" rather than failing the whole batch.
+fn chart_repo_name(chart: &str) -> Option<String> {
+    // `chart` looks like "<name>-<version>"; the app_version/revision don't
+    // tell us the source repo, so we fall back to searching by the chart
+    // name across all configured repos via `helm search repo <name>`.
+    let name = chart.rsplit_once('-').map(|(n, _)| n).unwrap_or(chart);
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+fn latest_available_version(search_results: &[serde_json::Value], include_prerelease: bool) -> Option<String> {
+    search_results
+        .iter()
+        .filter_map(|v| v.get("version").and_then(|v| v.as_str()))
+        .filter(|v| include_prerelease || !is_prerelease(v))
+        .max_by(|a, b| version_compare(a, b))
+        .map(|v| v.to_string())
+}
+
+/// Query an OCI registry's tag list for a chart reference like
+/// `registry.example.com/charts/nginx` and return the newest semver-looking
+/// tag. Anonymous/unauthenticated registries only; authenticated registries
+/// fall back to "unknown" rather than failing the whole batch.
+async fn latest_oci_version(reference: &str) -> Option<String> {
+    let (registry, repo) = reference.split_once('/')?;
+    let url = format!("https://{}/v2/{}/tags/list", registry, repo);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .ok()?;
+
+    let resp = client.get(&url).send().await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+
+    #[derive(Deserialize)]
+    struct TagsList {
+        tags: Vec<String>,
+    }
+
+    let tags: TagsList = resp.json().await.ok()?;
+    tags.tags
+        .into_iter()
+        .filter(|t| !is_prerelease(t) && looks_like_semver(t))
+        .max_by(|a, b| version_compare(a, b))
+}
+
+/// For each installed release, resolve the available chart versions -
+/// via `helm search repo <chart> --versions -o json` for classic repos, or
+/// the registry's tag list for `oci://` references - and compare them
+/// against the installed version to flag releases running behind.
+#[tauri::command]
+pub async fn helm_check_outdated(refresh_repos: Option<bool>) -> Result<Vec<HelmOutdated>, String> {
+    if refresh_repos.unwrap_or(false) {
+        // Best-effort; an unreachable repo shouldn't fail the whole check.
+        let _ = Command::new("helm").args(["repo", "update"]).output();
+    }
+
+    let releases = helm_list().await?;
+    let mut results = Vec::with_capacity(releases.len());
+
+    for release in releases {
+        let current_version = release
+            .chart
+            .rsplit_once('-')
+            .map(|(_, v)| v.to_string())
+            .unwrap_or_else(|| release.chart.clone());
+
+        let source = resolve_chart_source(&release.namespace, &release.name);
+
+        let latest_version = match &source {
+            ChartSource::Oci { reference } => latest_oci_version(reference).await,
+            ChartSource::HttpRepo { .. } | ChartSource::Unknown => {
+                match chart_repo_name(&release.chart) {
+                    Some(chart_name) => {
+                        let search_output = Command::new("helm")
+                            .args(["search", "repo", &chart_name, "--versions", "-o", "json"])
+                            .output();
+
+                        match search_output {
+                            Ok(output) if output.status.success() => {
+                                let results: Vec<serde_json::Value> =
+                                    serde_json::from_slice(&output.stdout).unwrap_or_default();
+                                latest_available_version(&results, false)
+                            }
+                            _ => None,
+                        }
+                    }
+                    None => None,
+                }
+            }
+        };
+
+        let (latest_version, outdated) = match latest_version {
+            Some(latest) => {
+                let outdated = version_compare(&latest, &current_version) == std::cmp::Ordering::Greater;
+                (latest, outdated)
+            }
+            None => ("unknown".to_string(), false),
+        };
+
+        results.push(HelmOutdated {
+            name: release.name,
+            namespace: release.namespace,
+            current_version,
+            latest_version,
+            outdated,
+        });
+    }
+
+    Ok(results)
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct HelmUpgradeOptions {
+    #[serde(default)]
+    pub values: Option<serde_json::Value>,
+    #[serde(default)]
+    pub atomic: bool,
+    #[serde(default)]
+    pub wait: bool,
+    #[serde(default)]
+    pub timeout_seconds: Option<u32>,
+}
+
+#[tauri::command]
+pub async fn helm_upgrade(
+    app: AppHandle,
+    namespace: String,
+    name: String,
+    chart: String,
+    version: Option<String>,
+    options: Option<HelmUpgradeOptions>,
+    operation_id: String,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let mut args = vec!["upgrade".to_string(), name.clone(), chart, "-n".to_string(), namespace];
+
+    if let Some(version) = &version {
+        args.push("--version".to_string());
+        args.push(version.clone());
+    }
+    if options.atomic {
+        args.push("--atomic".to_string());
+    }
+    if options.wait {
+        args.push("--wait".to_string());
+    }
+    if let Some(timeout) = options.timeout_seconds {
+        args.push("--timeout".to_string());
+        args.push(format!("{}s", timeout));
+    }
+
+    let values_file = if let Some(values) = &options.values {
+        let path = std::env::temp_dir().join(format!("opspilot-helm-values-{}-{}.yaml", name, std::process::id()));
+        let yaml = serde_yaml::to_string(values).map_err(|e| format!("Failed to serialize values: {}", e))?;
+        std::fs::write(&path, yaml).map_err(|e| format!("Failed to write values file: {}", e))?;
+        args.push("-f".to_string());
+        args.push(path.to_string_lossy().into_owned());
+        Some(path)
+    } else {
+        None
+    };
+
+    let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    let result = run_helm_streaming(&app, &operation_id, &arg_refs).await;
+
+    if let Some(path) = values_file {
+        let _ = std::fs::remove_file(path);
+    }
+
+    result
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HelmDiffEntry {
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub change: String, // "added" | "removed" | "changed" | "unchanged"
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HelmDiffPreview {
+    pub entries: Vec<HelmDiffEntry>,
+    pub rendered_manifest: String,
+    pub raw_diff: Option<String>, // populated when the helm-diff plugin is available
+}
+
+fn resource_key(r: &HelmResource) -> String {
+    format!("{}/{}/{}", r.kind, r.namespace.as_deref().unwrap_or(""), r.name)
+}
+
+/// Render what `helm upgrade --dry-run` would produce and diff it (by
+/// kind+name, since we don't template the live values into the same
+/// normalized form) against what's currently deployed per `helm_get_resources`.
+/// When the `helm-diff` plugin is installed, also runs `helm diff upgrade`
+/// for a human-readable unified diff.
 #[tauri::command]
-pub async fn helm_rollback(namespace: String, name: String, revision: i64) -> Result<String, String> {
+pub async fn helm_diff_preview(
+    namespace: String,
+    name: String,
+    chart: String,
+    version: Option<String>,
+    options: Option<HelmUpgradeOptions>,
+) -> Result<HelmDiffPreview, String> {
+    let options = options.unwrap_or_default();
+    let mut dry_run_args = vec![
+        "upgrade".to_string(),
+        name.clone(),
+        chart.clone(),
+        "-n".to_string(),
+        namespace.clone(),
+        "--dry-run".to_string(),
+    ];
+    if let Some(version) = &version {
+        dry_run_args.push("--version".to_string());
+        dry_run_args.push(version.clone());
+    }
+
+    let values_file = if let Some(values) = &options.values {
+        let path = std::env::temp_dir().join(format!("opspilot-helm-values-{}-{}.yaml", name, std::process::id()));
+        let yaml = serde_yaml::to_string(values).map_err(|e| format!("Failed to serialize values: {}", e))?;
+        std::fs::write(&path, yaml).map_err(|e| format!("Failed to write values file: {}", e))?;
+        dry_run_args.push("-f".to_string());
+        dry_run_args.push(path.to_string_lossy().into_owned());
+        Some(path)
+    } else {
+        None
+    };
+
+    let dry_run_output = Command::new("helm").args(&dry_run_args).output();
+
+    if let Some(path) = &values_file {
+        let _ = std::fs::remove_file(path);
+    }
+
+    let dry_run_output = dry_run_output.map_err(|e| format!("Failed to execute helm upgrade --dry-run: {}", e))?;
+    if !dry_run_output.status.success() {
+        return Err(format!("helm upgrade --dry-run failed: {}", String::from_utf8_lossy(&dry_run_output.stderr)));
+    }
+
+    let rendered_manifest = String::from_utf8_lossy(&dry_run_output.stdout).into_owned();
+
+    let rendered_resources = parse_manifest_resources(&rendered_manifest);
+    let deployed_resources = helm_get_resources(namespace.clone(), name.clone()).await.unwrap_or_default();
+
+    let rendered_keys: std::collections::HashMap<String, HelmResource> =
+        rendered_resources.into_iter().map(|r| (resource_key(&r), r)).collect();
+    let deployed_keys: std::collections::HashSet<String> = deployed_resources.iter().map(resource_key).collect();
+
+    let mut entries = Vec::new();
+    for (key, resource) in &rendered_keys {
+        let change = if deployed_keys.contains(key) { "changed" } else { "added" };
+        entries.push(HelmDiffEntry {
+            kind: resource.kind.clone(),
+            name: resource.name.clone(),
+            namespace: resource.namespace.clone(),
+            change: change.to_string(),
+        });
+    }
+    for resource in &deployed_resources {
+        if !rendered_keys.contains_key(&resource_key(resource)) {
+            entries.push(HelmDiffEntry {
+                kind: resource.kind.clone(),
+                name: resource.name.clone(),
+                namespace: resource.namespace.clone(),
+                change: "removed".to_string(),
+            });
+        }
+    }
+
+    // Best-effort: only present when the helm-diff plugin is installed.
+    let raw_diff = {
+        let mut diff_args = vec!["diff".to_string(), "upgrade".to_string(), name, chart, "-n".to_string(), namespace];
+        if let Some(version) = version {
+            diff_args.push("--version".to_string());
+            diff_args.push(version);
+        }
+        Command::new("helm")
+            .args(&diff_args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+    };
+
+    Ok(HelmDiffPreview { entries, rendered_manifest, raw_diff })
+}
+
+fn parse_manifest_resources(manifest: &str) -> Vec<HelmResource> {
+    let mut resources = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(manifest) {
+        let Ok(yaml_value) = serde_yaml::Value::deserialize(document) else { continue };
+        if yaml_value.is_null() {
+            continue;
+        }
+        let Some(serde_yaml::Value::String(kind)) = yaml_value.get("kind") else { continue };
+        let Some(metadata) = yaml_value.get("metadata") else { continue };
+        let name = match metadata.get("name") {
+            Some(serde_yaml::Value::String(n)) => n.clone(),
+            _ => "unknown".to_string(),
+        };
+        let namespace = match metadata.get("namespace") {
+            Some(serde_yaml::Value::String(n)) => Some(n.clone()),
+            _ => None,
+        };
+        resources.push(HelmResource {
+            kind: kind.clone(),
+            name,
+            namespace,
+            api_version: yaml_value.get("apiVersion").and_then(|v| v.as_str()).unwrap_or("v1").to_string(),
+        });
+    }
+    resources
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HelmRepo {
+    pub name: String,
+    pub url: String,
+}
+
+#[tauri::command]
+pub async fn helm_repo_list() -> Result<Vec<HelmRepo>, String> {
     let output = Command::new("helm")
-        .args(["rollback", &name, &revision.to_string(), "-n", &namespace])
+        .args(["repo", "list", "-o", "json"])
         .output()
-        .map_err(|e| format!("Failed to execute helm rollback: {}", e))?;
+        .map_err(|e| format!("Failed to execute helm repo list: {}", e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("helm rollback failed: {}", stderr));
+        if stderr.contains("no repositories") {
+            return Ok(Vec::new());
+        }
+        return Err(format!("helm repo list failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let repos: Vec<HelmRepo> = serde_json::from_str(&stdout)
+        .map_err(|e| format!("Failed to parse helm repo list JSON: {}", e))?;
+
+    Ok(repos)
+}
+
+#[tauri::command]
+pub async fn helm_repo_add(
+    name: String,
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<String, String> {
+    let mut args = vec!["repo".to_string(), "add".to_string(), name.clone(), url];
+
+    if let Some(username) = &username {
+        args.push("--username".to_string());
+        args.push(username.clone());
+    }
+    if let Some(password) = &password {
+        args.push("--password".to_string());
+        args.push(password.clone());
+    }
+
+    let output = Command::new("helm")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to execute helm repo add: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("helm repo add failed: {}", String::from_utf8_lossy(&output.stderr)));
     }
 
+    Ok(format!("Added repo {}", name))
+}
+
+#[tauri::command]
+pub async fn helm_repo_remove(name: String) -> Result<String, String> {
+    let output = Command::new("helm")
+        .args(["repo", "remove", &name])
+        .output()
+        .map_err(|e| format!("Failed to execute helm repo remove: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("helm repo remove failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(format!("Removed repo {}", name))
+}
+
+#[tauri::command]
+pub async fn helm_repo_update() -> Result<String, String> {
+    let output = Command::new("helm")
+        .args(["repo", "update"])
+        .output()
+        .map_err(|e| format!("Failed to execute helm repo update: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("helm repo update failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HelmReleaseAudit {
+    pub release: HelmRelease,
+    pub outdated: HelmOutdated,
+    pub history: Vec<HelmHistoryEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HelmAuditReport {
+    pub generated_at: String,
+    pub total_releases: usize,
+    pub outdated_count: usize,
+    pub pass: bool,
+    pub releases: Vec<HelmReleaseAudit>,
+}
+
+fn render_html_report(report: &HelmAuditReport) -> String {
+    let mut rows = String::new();
+    for audit in &report.releases {
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            audit.release.name,
+            audit.release.namespace,
+            audit.release.chart,
+            audit.outdated.current_version,
+            audit.outdated.latest_version,
+            if audit.outdated.outdated { "YES" } else { "no" },
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Helm Release Audit</title></head><body>\n\
+        <h1>Helm Release Audit</h1>\n\
+        <p>Generated at {}</p>\n\
+        <p>{} of {} releases outdated - overall: {}</p>\n\
+        <table border=\"1\" cellpadding=\"4\" cellspacing=\"0\">\n\
+        <tr><th>Name</th><th>Namespace</th><th>Chart</th><th>Current</th><th>Latest</th><th>Outdated</th></tr>\n\
+        {}\n\
+        </table>\n\
+        </body></html>",
+        report.generated_at,
+        report.outdated_count,
+        report.total_releases,
+        if report.pass { "PASS" } else { "FAIL" },
+        rows,
+    )
+}
+
+/// Aggregate `helm_list`, per-release outdated status, and revision history
+/// into a single exportable report, serialized as YAML, JSON, or a
+/// self-contained HTML table. `fail_on_outdated` drives `pass` so this can
+/// back a CI-style "fail if anything is outdated" check.
+#[tauri::command]
+pub async fn helm_report(format: String, fail_on_outdated: Option<bool>) -> Result<String, String> {
+    let releases = helm_list().await?;
+    let outdated = helm_check_outdated(Some(false)).await.unwrap_or_default();
+
+    let mut audits = Vec::with_capacity(releases.len());
+    for release in releases {
+        let outdated_entry = outdated
+            .iter()
+            .find(|o| o.name == release.name && o.namespace == release.namespace)
+            .cloned()
+            .unwrap_or_else(|| HelmOutdated {
+                name: release.name.clone(),
+                namespace: release.namespace.clone(),
+                current_version: release.chart.clone(),
+                latest_version: "unknown".to_string(),
+                outdated: false,
+            });
+
+        let history = helm_history(release.namespace.clone(), release.name.clone()).await.unwrap_or_default();
+
+        audits.push(HelmReleaseAudit { release, outdated: outdated_entry, history });
+    }
+
+    let outdated_count = audits.iter().filter(|a| a.outdated.outdated).count();
+    let pass = if fail_on_outdated.unwrap_or(false) { outdated_count == 0 } else { true };
+
+    let report = HelmAuditReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        total_releases: audits.len(),
+        outdated_count,
+        pass,
+        releases: audits,
+    };
+
+    match format.to_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(&report).map_err(|e| format!("Failed to serialize report as JSON: {}", e)),
+        "html" => Ok(render_html_report(&report)),
+        "yaml" | _ => serde_yaml::to_string(&report).map_err(|e| format!("Failed to serialize report as YAML: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub async fn helm_rollback(app: AppHandle, namespace: String, name: String, revision: i64, operation_id: String) -> Result<String, String> {
+    let revision_str = revision.to_string();
+    run_helm_streaming(&app, &operation_id, &["rollback", &name, &revision_str, "-n", &namespace]).await?;
     Ok(format!("Successfully rolled back {} to revision {}", name, revision))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_compare_orders_v_prefixed_semver_by_major_version() {
+        assert_eq!(version_compare("v1.2.3", "v2.0.0"), std::cmp::Ordering::Less);
+        assert_eq!(version_compare("v2.0.0", "v1.2.3"), std::cmp::Ordering::Greater);
+    }
+
+    #[test]
+    fn looks_like_semver_accepts_v_prefixed_and_bare_versions() {
+        assert!(looks_like_semver("v1.2.3"));
+        assert!(looks_like_semver("1.2.3"));
+        assert!(looks_like_semver("1.2"));
+    }
+
+    #[test]
+    fn looks_like_semver_rejects_non_version_tags() {
+        for tag in ["latest", "main", "stable", ""] {
+            assert!(!looks_like_semver(tag), "expected {:?} to be rejected", tag);
+        }
+    }
+
+    #[test]
+    fn oci_tag_filtering_excludes_non_version_tags_before_sorting() {
+        // Mirrors latest_oci_version's filter+max_by pipeline without the
+        // network round trip: non-version tags must never win the max_by
+        // over a real version, even under last-wins tie-breaking.
+        let tags = ["latest", "v1.2.3", "main", "v2.0.0", "stable"];
+        let latest = tags
+            .iter()
+            .filter(|t| !is_prerelease(t) && looks_like_semver(t))
+            .max_by(|a, b| version_compare(a, b));
+        assert_eq!(latest, Some(&"v2.0.0"));
+    }
+}
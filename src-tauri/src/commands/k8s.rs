@@ -0,0 +1,327 @@
+/**
+ * Kubernetes integration layer
+ *
+ * Builds a `kube::Client` directly from the resolved kubeconfig path
+ * (OpsPilotConfig.kubeconfig -> KUBECONFIG env var -> ~/.kube/config) rather
+ * than relying on the shell-out commands the knowledge base examples use.
+ * Exposes a handful of read-oriented commands that return structured data
+ * so the agent can reason over pod/event state programmatically.
+ */
+
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Event, Pod};
+use kube::api::{Api, ListParams, LogParams, Patch, PatchParams};
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use kube::Client;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::commands::ai_utilities::load_opspilot_config_inner;
+
+/// Resolve the kubeconfig path with the same precedence the rest of the app uses:
+/// explicit config field, then `KUBECONFIG`, then the default `~/.kube/config`.
+async fn resolve_kubeconfig_path() -> Option<String> {
+    if let Ok(config) = load_opspilot_config_inner().await {
+        if let Some(path) = config.kubeconfig {
+            if !path.trim().is_empty() {
+                return Some(path);
+            }
+        }
+    }
+
+    if let Ok(path) = std::env::var("KUBECONFIG") {
+        if !path.trim().is_empty() {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+async fn build_client(context: Option<String>) -> Result<Client, String> {
+    let path = resolve_kubeconfig_path().await;
+
+    let kubeconfig = if let Some(p) = &path {
+        Kubeconfig::read_from(p).map_err(|e| format!("Failed to read kubeconfig from {}: {}", p, e))?
+    } else {
+        Kubeconfig::read().map_err(|e| format!("Failed to read default kubeconfig: {}", e))?
+    };
+
+    let config = kube::Config::from_custom_kubeconfig(
+        kubeconfig,
+        &KubeConfigOptions {
+            context,
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| format!("Failed to build kube config: {}", e))?;
+
+    Client::try_from(config).map_err(|e| format!("Failed to create Kubernetes client: {}", e))
+}
+
+#[derive(Serialize)]
+pub struct K8sContextInfo {
+    pub name: String,
+    pub cluster: String,
+    pub user: String,
+    pub is_current: bool,
+}
+
+#[tauri::command]
+pub async fn k8s_list_contexts() -> Result<Vec<K8sContextInfo>, String> {
+    let path = resolve_kubeconfig_path().await;
+
+    let kubeconfig = if let Some(p) = &path {
+        Kubeconfig::read_from(p).map_err(|e| format!("Failed to read kubeconfig from {}: {}", p, e))?
+    } else {
+        Kubeconfig::read().map_err(|e| format!("Failed to read default kubeconfig: {}", e))?
+    };
+
+    let current = kubeconfig.current_context.clone();
+
+    Ok(kubeconfig
+        .contexts
+        .into_iter()
+        .filter_map(|named| {
+            let context = named.context?;
+            Some(K8sContextInfo {
+                is_current: current.as_deref() == Some(named.name.as_str()),
+                name: named.name,
+                cluster: context.cluster,
+                user: context.user,
+            })
+        })
+        .collect())
+}
+
+/// Validate that a context exists and a client can be built from it.
+/// The app's global "current context" lives in `AppState`/`set_kube_config`;
+/// this just confirms the candidate context is usable before the caller
+/// switches to it.
+#[tauri::command]
+pub async fn k8s_set_context(context: String) -> Result<(), String> {
+    build_client(Some(context)).await.map(|_| ())
+}
+
+#[derive(Serialize)]
+pub struct K8sPodInfo {
+    pub name: String,
+    pub namespace: String,
+    pub phase: String,
+    pub ready_containers: u32,
+    pub total_containers: u32,
+    pub restart_count: i32,
+    pub node: Option<String>,
+}
+
+#[tauri::command]
+pub async fn k8s_get_pods(context: Option<String>, namespace: Option<String>) -> Result<Vec<K8sPodInfo>, String> {
+    let client = build_client(context).await?;
+    let pods: Api<Pod> = match &namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+
+    let list = pods
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list pods: {}", e))?;
+
+    Ok(list
+        .items
+        .into_iter()
+        .map(|pod| {
+            let name = pod.metadata.name.clone().unwrap_or_default();
+            let ns = pod.metadata.namespace.clone().unwrap_or_default();
+            let status = pod.status.clone().unwrap_or_default();
+            let phase = status.phase.unwrap_or_else(|| "Unknown".to_string());
+            let container_statuses = status.container_statuses.unwrap_or_default();
+            let ready_containers = container_statuses.iter().filter(|c| c.ready).count() as u32;
+            let total_containers = container_statuses.len() as u32;
+            let restart_count = container_statuses.iter().map(|c| c.restart_count).sum();
+            let node = pod.spec.and_then(|s| s.node_name);
+
+            K8sPodInfo {
+                name,
+                namespace: ns,
+                phase,
+                ready_containers,
+                total_containers,
+                restart_count,
+                node,
+            }
+        })
+        .collect())
+}
+
+#[derive(Serialize)]
+pub struct K8sPodDescription {
+    pub name: String,
+    pub namespace: String,
+    pub phase: String,
+    pub node: Option<String>,
+    pub pod_ip: Option<String>,
+    pub start_time: Option<String>,
+    pub container_statuses: Vec<K8sContainerStatus>,
+    pub recent_events: Vec<K8sEventInfo>,
+}
+
+#[derive(Serialize)]
+pub struct K8sContainerStatus {
+    pub name: String,
+    pub ready: bool,
+    pub restart_count: i32,
+    pub state: String,
+}
+
+#[tauri::command]
+pub async fn k8s_describe_pod(context: Option<String>, namespace: String, name: String) -> Result<K8sPodDescription, String> {
+    let client = build_client(context).await?;
+    let pods: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+
+    let pod = pods
+        .get(&name)
+        .await
+        .map_err(|e| format!("Failed to get pod {}/{}: {}", namespace, name, e))?;
+
+    let status = pod.status.clone().unwrap_or_default();
+    let container_statuses = status
+        .container_statuses
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| {
+            let state = c
+                .state
+                .as_ref()
+                .map(|s| {
+                    if s.running.is_some() {
+                        "Running".to_string()
+                    } else if let Some(waiting) = &s.waiting {
+                        format!("Waiting: {}", waiting.reason.clone().unwrap_or_default())
+                    } else if let Some(terminated) = &s.terminated {
+                        format!("Terminated: {}", terminated.reason.clone().unwrap_or_default())
+                    } else {
+                        "Unknown".to_string()
+                    }
+                })
+                .unwrap_or_else(|| "Unknown".to_string());
+
+            K8sContainerStatus {
+                name: c.name,
+                ready: c.ready,
+                restart_count: c.restart_count,
+                state,
+            }
+        })
+        .collect();
+
+    let field_selector = format!("involvedObject.name={}", name);
+    let recent_events = k8s_get_events(None, Some(namespace.clone()), Some(field_selector))
+        .await
+        .unwrap_or_default();
+
+    Ok(K8sPodDescription {
+        name: pod.metadata.name.unwrap_or_default(),
+        namespace: pod.metadata.namespace.unwrap_or_default(),
+        phase: status.phase.unwrap_or_else(|| "Unknown".to_string()),
+        node: pod.spec.and_then(|s| s.node_name),
+        pod_ip: status.pod_ip,
+        start_time: status.start_time.map(|t| t.0.to_rfc3339()),
+        container_statuses,
+        recent_events,
+    })
+}
+
+#[derive(Serialize)]
+pub struct K8sEventInfo {
+    pub reason: String,
+    pub message: String,
+    pub type_: String,
+    pub count: i32,
+    pub last_timestamp: Option<String>,
+}
+
+#[tauri::command]
+pub async fn k8s_get_events(
+    context: Option<String>,
+    namespace: Option<String>,
+    field_selector: Option<String>,
+) -> Result<Vec<K8sEventInfo>, String> {
+    let client = build_client(context).await?;
+    let events: Api<Event> = match &namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+
+    let mut lp = ListParams::default();
+    if let Some(fs) = field_selector {
+        lp = lp.fields(&fs);
+    }
+
+    let list = events
+        .list(&lp)
+        .await
+        .map_err(|e| format!("Failed to list events: {}", e))?;
+
+    Ok(list
+        .items
+        .into_iter()
+        .map(|e| K8sEventInfo {
+            reason: e.reason.unwrap_or_default(),
+            message: e.message.unwrap_or_default(),
+            type_: e.type_.unwrap_or_default(),
+            count: e.count.unwrap_or(0),
+            last_timestamp: e.last_timestamp.map(|t| t.0.to_rfc3339()),
+        })
+        .collect())
+}
+
+/// Tail of a single container's logs, independent of `commands::resources::get_pod_logs`
+/// (which goes through `AppState`'s cached client) so this layer's callers -
+/// currently `mcp::server` - don't need a Tauri `State` to use it.
+#[tauri::command]
+pub async fn k8s_pod_logs(
+    context: Option<String>,
+    namespace: String,
+    name: String,
+    container: Option<String>,
+    tail_lines: Option<i64>,
+) -> Result<String, String> {
+    let client = build_client(context).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+
+    let lp = LogParams {
+        container,
+        tail_lines: Some(tail_lines.unwrap_or(200)),
+        timestamps: false,
+        ..Default::default()
+    };
+
+    pods.logs(&name, &lp)
+        .await
+        .map_err(|e| format!("Failed to fetch logs for {}/{}: {}", namespace, name, e))
+}
+
+/// Scale a `Deployment`'s replica count via a strategic merge patch, the
+/// same patch shape `commands::resources::scale_resource` uses for the
+/// generic `DynamicObject` case but specialized to the one kind `mcp::server`
+/// exposes as a tool.
+#[tauri::command]
+pub async fn k8s_scale_deployment(
+    context: Option<String>,
+    namespace: String,
+    name: String,
+    replicas: i32,
+) -> Result<(), String> {
+    let client = build_client(context).await?;
+    let deployments: Api<Deployment> = Api::namespaced(client, &namespace);
+
+    let patch = json!({ "spec": { "replicas": replicas } });
+    deployments
+        .patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| format!("Failed to scale deployment {}/{}: {}", namespace, name, e))?;
+
+    Ok(())
+}
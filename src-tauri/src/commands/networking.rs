@@ -1,20 +1,339 @@
 
 use tauri::{State, Emitter};
-use kube::api::Api;
-use crate::state::{AppState, PortForwardSession};
+use kube::{Client, api::{Api, ListParams}};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use crate::state::{AppState, PortForwardSession, PortForwardStatus, PortForwardCounters};
 use crate::client::create_client;
+use serde::Serialize;
+use std::pin::Pin;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::AsyncWrite;
+
+/// `AsyncWrite` passthrough that feeds every successful write's byte count
+/// into a callback, used to tally `PortForwardCounters::bytes_up`/`bytes_down`
+/// as traffic flows rather than only after the whole copy finishes.
+struct CountingWriter<W, F> {
+    inner: W,
+    on_write: F,
+}
+
+impl<W: AsyncWrite + Unpin, F: Fn(u64) + Unpin> AsyncWrite for CountingWriter<W, F> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = &poll {
+            (this.on_write)(*n as u64);
+        }
+        poll
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Bridge `socket` (the local accepted connection) and `upstream` (the pod
+/// port-forward stream), tallying bytes in each direction into `counters`.
+/// Ends as soon as either direction's copy finishes or errors, the same
+/// "whichever side closes first wins" behavior `copy_bidirectional` has.
+/// Borrows `socket` (via `TcpStream::split`, not the owning `tokio::io::split`)
+/// so the caller gets it back to retry against a fresh `upstream` on failure.
+async fn bridge_with_counters<U>(
+    socket: &mut tokio::net::TcpStream,
+    upstream: U,
+    counters: &Arc<PortForwardCounters>,
+) -> std::io::Result<()>
+where
+    U: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (mut socket_rd, socket_wr) = socket.split();
+    let (mut upstream_rd, upstream_wr) = tokio::io::split(upstream);
+
+    let up_counters = counters.clone();
+    let mut wr_to_upstream = CountingWriter {
+        inner: upstream_wr,
+        on_write: move |n: u64| { up_counters.bytes_up.fetch_add(n, Ordering::Relaxed); },
+    };
+    let down_counters = counters.clone();
+    let mut wr_to_socket = CountingWriter {
+        inner: socket_wr,
+        on_write: move |n: u64| { down_counters.bytes_down.fetch_add(n, Ordering::Relaxed); },
+    };
+
+    tokio::select! {
+        r = tokio::io::copy(&mut socket_rd, &mut wr_to_upstream) => r.map(|_| ()),
+        r = tokio::io::copy(&mut upstream_rd, &mut wr_to_socket) => r.map(|_| ()),
+    }
+}
+
+/// Result of `start_port_forward`: the session id to pass to
+/// `stop_port_forward`/`list_port_forwards`, plus the local port that ended
+/// up bound - the same value the caller passed in, unless it asked for
+/// `local_port: 0` ("pick any free port").
+#[derive(Serialize, Clone)]
+pub struct PortForwardHandle {
+    pub session_id: String,
+    pub local_port: u16,
+}
+
+/// Payload for the `pf_status` event, emitted on every connection-health
+/// transition of a `PortForwardSession`.
+#[derive(Serialize, Clone)]
+struct PfStatusEvent {
+    session_id: String,
+    #[serde(flatten)]
+    status: PortForwardStatus,
+}
+
+const RECONNECT_BASE: Duration = Duration::from_millis(250);
+const RECONNECT_CAP: Duration = Duration::from_secs(8);
+const MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Decrements `PortForwardCounters::active_connections` when a connection
+/// task ends, regardless of which of its several return points it takes.
+struct ActiveConnectionGuard(Arc<PortForwardCounters>);
+
+impl Drop for ActiveConnectionGuard {
+    fn drop(&mut self) {
+        self.0.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn set_status(app: &tauri::AppHandle, session_id: &str, status: &Arc<Mutex<PortForwardStatus>>, next: PortForwardStatus) {
+    *status.lock().unwrap() = next.clone();
+    let _ = app.emit("pf_status", PfStatusEvent { session_id: session_id.to_string(), status: next });
+}
+
+/// What `start_port_forward`'s `name` argument identifies. `Pod` forwards
+/// directly to the named pod; the others resolve to a currently-Ready pod
+/// backing that resource, re-resolved on every reconnect so a pod that
+/// disappeared mid-session is swapped for another Ready one transparently.
+#[derive(Clone, Copy)]
+enum ForwardTarget {
+    Pod,
+    Service,
+    Deployment,
+    StatefulSet,
+}
+
+impl ForwardTarget {
+    fn parse(kind: &str) -> Result<Self, String> {
+        match kind {
+            "Pod" => Ok(Self::Pod),
+            "Service" => Ok(Self::Service),
+            "Deployment" => Ok(Self::Deployment),
+            "StatefulSet" => Ok(Self::StatefulSet),
+            other => Err(format!("Unsupported port-forward target kind '{}' (expected Pod, Service, Deployment or StatefulSet)", other)),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Pod => "Pod",
+            Self::Service => "Service",
+            Self::Deployment => "Deployment",
+            Self::StatefulSet => "StatefulSet",
+        }
+    }
+}
+
+/// How long to wait for a workload/service to have a Ready backing pod
+/// before giving up - covers the gap right after a rollout where none is
+/// Ready yet.
+const POD_READY_WAIT: Duration = Duration::from_secs(15);
+const POD_READY_POLL: Duration = Duration::from_millis(500);
+
+fn pod_is_ready(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .map(|conditions| conditions.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+        .unwrap_or(false)
+}
+
+async fn find_ready_pod_by_selector(client: &Client, namespace: &str, selector: &std::collections::BTreeMap<String, String>) -> Option<Pod> {
+    let label_selector = selector.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(",");
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let list = pods.list(&ListParams::default().labels(&label_selector)).await.ok()?;
+    list.items.into_iter().find(pod_is_ready)
+}
+
+/// Resolve a Service's `target_port` (which may be a named container port)
+/// against the pod actually backing it.
+fn resolve_service_target_port(pod: &Pod, svc_port: &k8s_openapi::api::core::v1::ServicePort) -> Result<u16, String> {
+    match &svc_port.target_port {
+        Some(IntOrString::Int(p)) => Ok(*p as u16),
+        Some(IntOrString::String(port_name)) => pod
+            .spec
+            .as_ref()
+            .and_then(|spec| {
+                spec.containers.iter().find_map(|c| {
+                    c.ports
+                        .as_ref()?
+                        .iter()
+                        .find(|p| p.name.as_deref() == Some(port_name.as_str()))
+                        .map(|p| p.container_port as u16)
+                })
+            })
+            .ok_or_else(|| format!("Named target port '{}' not found on backing pod", port_name)),
+        None => Ok(svc_port.port as u16),
+    }
+}
+
+/// Find a Ready pod owned (directly, or for a Deployment via its ReplicaSet)
+/// by the named workload. Returns `Ok(None)` rather than erroring when none
+/// is Ready yet, so the caller's poll loop can keep waiting out a rollout.
+async fn find_ready_pod_for_workload(client: &Client, namespace: &str, target: ForwardTarget, name: &str) -> Result<Option<Pod>, String> {
+    let owner_uid = match target {
+        ForwardTarget::StatefulSet => {
+            let api: Api<StatefulSet> = Api::namespaced(client.clone(), namespace);
+            api.get(name)
+                .await
+                .map_err(|e| format!("Failed to get StatefulSet '{}': {}", name, e))?
+                .metadata
+                .uid
+                .ok_or_else(|| format!("StatefulSet '{}' has no uid", name))?
+        }
+        ForwardTarget::Deployment => {
+            let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+            let deploy_uid = deployments
+                .get(name)
+                .await
+                .map_err(|e| format!("Failed to get Deployment '{}': {}", name, e))?
+                .metadata
+                .uid
+                .ok_or_else(|| format!("Deployment '{}' has no uid", name))?;
+
+            // A Deployment doesn't own Pods directly - it owns the ReplicaSet
+            // that owns them, so find that first.
+            let replica_sets: Api<ReplicaSet> = Api::namespaced(client.clone(), namespace);
+            let rs_list = replica_sets
+                .list(&ListParams::default())
+                .await
+                .map_err(|e| format!("Failed to list ReplicaSets: {}", e))?;
+            rs_list
+                .items
+                .into_iter()
+                .find(|rs| {
+                    rs.metadata
+                        .owner_references
+                        .as_ref()
+                        .map(|refs| refs.iter().any(|r| r.uid == deploy_uid))
+                        .unwrap_or(false)
+                })
+                .and_then(|rs| rs.metadata.uid)
+                .ok_or_else(|| format!("No ReplicaSet found owned by Deployment '{}'", name))?
+        }
+        ForwardTarget::Pod | ForwardTarget::Service => unreachable!("handled by their own branch in resolve_forward_target"),
+    };
+
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod_list = pods
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list pods: {}", e))?;
+
+    Ok(pod_list.items.into_iter().find(|pod| {
+        pod_is_ready(pod)
+            && pod
+                .metadata
+                .owner_references
+                .as_ref()
+                .map(|refs| refs.iter().any(|r| r.uid == owner_uid))
+                .unwrap_or(false)
+    }))
+}
+
+/// Resolve `(target, name)` to a currently-Ready backing pod and the
+/// container port to forward `port` to. Called once up front and again on
+/// every reconnect attempt, so a pod that disappeared mid-session is
+/// transparently swapped for another Ready one.
+async fn resolve_forward_target(
+    client: &Client,
+    namespace: &str,
+    target: ForwardTarget,
+    name: &str,
+    port: u16,
+) -> Result<(String, u16), String> {
+    match target {
+        ForwardTarget::Pod => Ok((name.to_string(), port)),
+        ForwardTarget::Service => {
+            let services: Api<Service> = Api::namespaced(client.clone(), namespace);
+            let svc = services.get(name).await.map_err(|e| format!("Failed to get Service '{}': {}", name, e))?;
+            let spec = svc.spec.ok_or_else(|| format!("Service '{}' has no spec", name))?;
+            let selector = spec.selector.ok_or_else(|| format!("Service '{}' has no selector", name))?;
+            let svc_port = spec
+                .ports
+                .as_ref()
+                .and_then(|ports| ports.iter().find(|p| p.port as u16 == port))
+                .ok_or_else(|| format!("Service '{}' has no port {}", name, port))?
+                .clone();
+
+            let deadline = tokio::time::Instant::now() + POD_READY_WAIT;
+            loop {
+                if let Some(pod) = find_ready_pod_by_selector(client, namespace, &selector).await {
+                    let target_port = resolve_service_target_port(&pod, &svc_port)?;
+                    let pod_name = pod.metadata.name.ok_or_else(|| format!("Pod backing Service '{}' has no name", name))?;
+                    return Ok((pod_name, target_port));
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(format!("No Ready pod found backing Service '{}'", name));
+                }
+                tokio::time::sleep(POD_READY_POLL).await;
+            }
+        }
+        ForwardTarget::Deployment | ForwardTarget::StatefulSet => {
+            let deadline = tokio::time::Instant::now() + POD_READY_WAIT;
+            loop {
+                if let Some(pod) = find_ready_pod_for_workload(client, namespace, target, name).await? {
+                    let pod_name = pod.metadata.name.ok_or_else(|| format!("Pod backing {} '{}' has no name", target.label(), name))?;
+                    return Ok((pod_name, port));
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(format!("No Ready pod found backing {} '{}'", target.label(), name));
+                }
+                tokio::time::sleep(POD_READY_POLL).await;
+            }
+        }
+    }
+}
 
 #[tauri::command]
 pub async fn start_port_forward(
     app: tauri::AppHandle,
     state: State<'_, AppState>,
     namespace: String,
+    kind: String,
     name: String,
     local_port: u16,
     pod_port: u16
-) -> Result<String, String> {
+) -> Result<PortForwardHandle, String> {
+    let target = ForwardTarget::parse(&kind)?;
     let client = create_client(state.clone()).await?;
-    let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client, &namespace);
+
+    // Resolve once up front so a bad Service/Deployment/StatefulSet name
+    // fails fast, before a local port is even bound.
+    resolve_forward_target(&client, &namespace, target, &name, pod_port).await?;
+
+    // Bind first (0 asks the OS for any free port) so the session_id below
+    // is keyed by the port that actually ended up bound, not the caller's
+    // request - closes the race between the "already exists" check and the
+    // bind call that plagued the fixed-port version of this command.
+    let addr = format!("127.0.0.1:{}", local_port);
+    let listener = tokio::net::TcpListener::bind(&addr).await
+        .map_err(|e| format!("Failed to bind to {}: {}", addr, e))?;
+    let local_port = listener.local_addr()
+        .map_err(|e| format!("Failed to read bound local address: {}", e))?
+        .port();
 
     let session_id = format!("{}-{}-{}", namespace, name, local_port);
 
@@ -26,44 +345,89 @@ pub async fn start_port_forward(
         }
     }
 
-    let pods_clone = pods.clone();
+    let client_clone = client.clone();
+    let namespace_clone = namespace.clone();
     let name_clone = name.clone();
-    let _session_id_clone = session_id.clone();
-    let app_handle = app.clone();
+    let status = Arc::new(Mutex::new(PortForwardStatus::Connected));
+    let counters = Arc::new(PortForwardCounters::default());
 
     // Spawn the listener task
+    let status_for_task = status.clone();
+    let app_for_task = app.clone();
+    let session_id_for_task = session_id.clone();
+    let counters_for_task = counters.clone();
     let handle = tokio::spawn(async move {
-        let addr = format!("127.0.0.1:{}", local_port);
-        let listener = match tokio::net::TcpListener::bind(&addr).await {
-            Ok(l) => l,
-            Err(e) => {
-                let _ = app_handle.emit("pf_error", format!("Failed to bind to {}: {}", addr, e));
-                return;
-            }
-        };
-
         loop {
             match listener.accept().await {
                 Ok((mut socket, _)) => {
-                    let pods = pods_clone.clone();
+                    let client = client_clone.clone();
+                    let namespace = namespace_clone.clone();
                     let name = name_clone.clone();
-                    
+                    let status = status_for_task.clone();
+                    let app = app_for_task.clone();
+                    let session_id = session_id_for_task.clone();
+                    let counters = counters_for_task.clone();
+
                     tokio::spawn(async move {
-                        let mut pf = match pods.portforward(&name, &[pod_port]).await {
-                            Ok(pf) => pf,
-                            Err(e) => {
-                                eprintln!("Failed to start port forward: {}", e);
-                                return;
+                        counters.active_connections.fetch_add(1, Ordering::Relaxed);
+                        let _guard = ActiveConnectionGuard(counters.clone());
+
+                        let mut attempt: u32 = 0;
+                        loop {
+                            // Re-resolve on every attempt - if the pod we were
+                            // using disappeared (rollout, eviction, ...) this
+                            // self-heals onto another Ready one.
+                            let resolved = resolve_forward_target(&client, &namespace, target, &name, pod_port).await;
+                            let established = match resolved {
+                                Ok((pod_name, resolved_port)) => {
+                                    let pods: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+                                    match pods.portforward(&pod_name, &[resolved_port]).await {
+                                        Ok(mut pf) => pf.take_stream(resolved_port),
+                                        Err(e) => {
+                                            eprintln!("Failed to start port forward: {}", e);
+                                            None
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    eprintln!("Failed to resolve port forward target: {}", e);
+                                    None
+                                }
+                            };
+
+                            let Some(upstream) = established else {
+                                attempt += 1;
+                                if attempt > MAX_RECONNECT_ATTEMPTS {
+                                    set_status(&app, &session_id, &status, PortForwardStatus::Failed {
+                                        reason: "Exceeded max reconnect attempts".to_string(),
+                                    });
+                                    return;
+                                }
+                                set_status(&app, &session_id, &status, PortForwardStatus::Reconnecting { attempt });
+                                let backoff = (RECONNECT_BASE * 2u32.pow(attempt.min(5))).min(RECONNECT_CAP);
+                                tokio::time::sleep(backoff).await;
+                                continue;
+                            };
+
+                            attempt = 0;
+                            set_status(&app, &session_id, &status, PortForwardStatus::Connected);
+
+                            match bridge_with_counters(&mut socket, upstream, &counters).await {
+                                Ok(_) => return,
+                                Err(e) => {
+                                    eprintln!("Port forward connection error: {}", e);
+                                    attempt += 1;
+                                    if attempt > MAX_RECONNECT_ATTEMPTS {
+                                        set_status(&app, &session_id, &status, PortForwardStatus::Failed {
+                                            reason: e.to_string(),
+                                        });
+                                        return;
+                                    }
+                                    set_status(&app, &session_id, &status, PortForwardStatus::Reconnecting { attempt });
+                                    let backoff = (RECONNECT_BASE * 2u32.pow(attempt.min(5))).min(RECONNECT_CAP);
+                                    tokio::time::sleep(backoff).await;
+                                }
                             }
-                        };
-                        
-                        let mut upstream = match pf.take_stream(pod_port) {
-                            Some(s) => s,
-                            None => return,
-                        };
-
-                        if let Err(e) = tokio::io::copy_bidirectional(&mut socket, &mut upstream).await {
-                            eprintln!("Port forward connection error: {}", e);
                         }
                     });
                 }
@@ -74,18 +438,31 @@ pub async fn start_port_forward(
         }
     });
 
+    let _ = crate::session_store::save_session(&crate::session_store::SessionDescriptor {
+        id: session_id.clone(),
+        namespace: namespace.clone(),
+        kind: kind.clone(),
+        name: name.clone(),
+        local_port,
+        pod_port,
+        autoreconnect: true,
+    });
+
     let session = PortForwardSession {
         id: session_id.clone(),
         pod_name: name,
+        kind,
         namespace,
         local_port,
         pod_port,
         handle,
+        status,
+        counters,
     };
 
     state.port_forwards.lock().unwrap().insert(session_id.clone(), session);
 
-    Ok(session_id)
+    Ok(PortForwardHandle { session_id, local_port })
 }
 
 #[tauri::command]
@@ -94,6 +471,8 @@ pub async fn stop_port_forward(state: State<'_, AppState>, session_id: String) -
     if let Some(session) = pfs.remove(&session_id) {
         session.handle.abort();
     }
+    drop(pfs);
+    crate::session_store::remove_session(&session_id)?;
     Ok(())
 }
 
@@ -104,9 +483,14 @@ pub async fn list_port_forwards(state: State<'_, AppState>) -> Result<Vec<serde_
         serde_json::json!({
             "id": s.id,
             "pod_name": s.pod_name,
+            "kind": s.kind,
             "namespace": s.namespace,
             "local_port": s.local_port,
             "pod_port": s.pod_port,
+            "status": &*s.status.lock().unwrap(),
+            "bytes_up": s.counters.bytes_up.load(Ordering::Relaxed),
+            "bytes_down": s.counters.bytes_down.load(Ordering::Relaxed),
+            "active_connections": s.counters.active_connections.load(Ordering::Relaxed),
         })
     }).collect();
     Ok(list)
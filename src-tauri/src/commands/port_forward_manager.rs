@@ -0,0 +1,195 @@
+use tauri::State;
+use kube::api::{Api, ListParams};
+use k8s_openapi::api::core::v1::{Pod, Service};
+use crate::state::{AppState, ServiceForwardSession};
+use crate::client::create_client;
+use serde::Serialize;
+use std::net::TcpListener;
+
+/// Forward info surfaced to the frontend: one dynamically-allocated local
+/// port proxying to a Ready pod backing `service`/`namespace`, reused by
+/// whichever caller asked for this `(namespace, service, target_port)`
+/// triple first (see `forward_key`).
+#[derive(Serialize, Clone)]
+pub struct ForwardInfo {
+    pub id: String,
+    pub namespace: String,
+    pub service: String,
+    pub target_port: u16,
+    pub local_port: u16,
+    pub active: bool,
+}
+
+/// Unique key for a forward: one `(namespace, service, target_port)` triple
+/// maps to at most one active local port, so repeated `start_forward` calls
+/// for the same target reuse the existing tunnel instead of colliding.
+fn forward_key(namespace: &str, service: &str, target_port: u16) -> String {
+    format!("{}/{}/{}", namespace, service, target_port)
+}
+
+/// Bind an ephemeral port and release it immediately so a forward can be
+/// bound to it next. Small window for another process to steal the port
+/// between this call and the listener bind below - the same race every
+/// dynamic-port allocator in this codebase accepts (see
+/// `agent_sidecar::allocate_port`).
+fn allocate_local_port() -> Result<u16, String> {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|l| l.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| format!("Failed to allocate a local port: {}", e))
+}
+
+/// Find a Ready pod backing `service` in `namespace`, resolved via the
+/// Service's label selector the same way `kube-proxy` would route a
+/// request to it.
+async fn find_ready_pod_for_service(client: &kube::Client, namespace: &str, service: &str) -> Result<Pod, String> {
+    let services: Api<Service> = Api::namespaced(client.clone(), namespace);
+    let svc = services.get(service).await
+        .map_err(|e| format!("Failed to get service '{}': {}", service, e))?;
+    let selector = svc.spec.and_then(|spec| spec.selector)
+        .ok_or_else(|| format!("Service '{}' has no selector", service))?;
+
+    let label_selector = selector.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(",");
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod_list = pods
+        .list(&ListParams::default().labels(&label_selector))
+        .await
+        .map_err(|e| format!("Failed to list pods backing service '{}': {}", service, e))?;
+
+    pod_list
+        .items
+        .into_iter()
+        .find(|pod| {
+            pod.status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+                .map(|conditions| conditions.iter().any(|c| c.type_ == "Ready" && c.status == "True"))
+                .unwrap_or(false)
+        })
+        .ok_or_else(|| format!("No Ready pod found backing service '{}' (selector '{}')", service, label_selector))
+}
+
+fn to_info(session: &ServiceForwardSession) -> ForwardInfo {
+    ForwardInfo {
+        id: session.id.clone(),
+        namespace: session.namespace.clone(),
+        service: session.service.clone(),
+        target_port: session.target_port,
+        local_port: session.local_port,
+        active: !session.handle.is_finished(),
+    }
+}
+
+/// Whether `id` names a forward that's still running. Used by callers that
+/// built their own forward on top of this manager (e.g. `commands::argocd`)
+/// to check liveness without reaching into `AppState` directly.
+pub fn is_forward_active(state: &State<'_, AppState>, id: &str) -> bool {
+    state.service_forwards.lock().unwrap().get(id).map(|s| !s.handle.is_finished()).unwrap_or(false)
+}
+
+/// Start (or reuse an already-active) port-forward from a dynamically
+/// allocated local port to `target_port` on a Ready pod backing `service`.
+/// Each accepted local connection opens a fresh pod port-forward stream and
+/// bridges the two with `copy_bidirectional`, the same shape
+/// `commands::networking::start_port_forward` uses for a single named pod.
+#[tauri::command]
+pub async fn start_forward(
+    state: State<'_, AppState>,
+    namespace: String,
+    service: String,
+    target_port: u16,
+) -> Result<ForwardInfo, String> {
+    let key = forward_key(&namespace, &service, target_port);
+
+    {
+        let forwards = state.service_forwards.lock().unwrap();
+        if let Some(session) = forwards.get(&key) {
+            if !session.handle.is_finished() {
+                return Ok(to_info(session));
+            }
+        }
+    }
+
+    let client = create_client(state.clone()).await?;
+    let pod = find_ready_pod_for_service(&client, &namespace, &service).await?;
+    let pod_name = pod.metadata.name.clone()
+        .ok_or_else(|| format!("Pod backing service '{}' has no name", service))?;
+
+    let local_port = allocate_local_port()?;
+    let addr = format!("127.0.0.1:{}", local_port);
+    let listener = tokio::net::TcpListener::bind(&addr).await
+        .map_err(|e| format!("Failed to bind to {}: {}", addr, e))?;
+
+    eprintln!(
+        "[port-forward] Forwarding localhost:{} -> {}/{} (svc {}) port {}",
+        local_port, namespace, pod_name, service, target_port
+    );
+
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+    let handle = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((mut socket, _)) => {
+                    let pods = pods.clone();
+                    let pod_name = pod_name.clone();
+
+                    tokio::spawn(async move {
+                        let mut pf = match pods.portforward(&pod_name, &[target_port]).await {
+                            Ok(pf) => pf,
+                            Err(e) => {
+                                eprintln!("[port-forward] Failed to start pod port-forward: {}", e);
+                                return;
+                            }
+                        };
+
+                        let mut upstream = match pf.take_stream(target_port) {
+                            Some(s) => s,
+                            None => return,
+                        };
+
+                        if let Err(e) = tokio::io::copy_bidirectional(&mut socket, &mut upstream).await {
+                            eprintln!("[port-forward] Connection error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    eprintln!("[port-forward] Listener accept error: {}", e);
+                }
+            }
+        }
+    });
+
+    let session = ServiceForwardSession {
+        id: key.clone(),
+        namespace,
+        service,
+        target_port,
+        local_port,
+        handle,
+    };
+    let info = to_info(&session);
+    state.service_forwards.lock().unwrap().insert(key, session);
+
+    Ok(info)
+}
+
+/// Stop a forward by the id returned from `start_forward`/`list_forwards`.
+#[tauri::command]
+pub async fn stop_forward(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    if let Some(session) = state.service_forwards.lock().unwrap().remove(&id) {
+        session.handle.abort();
+    }
+    Ok(())
+}
+
+/// All forwards started through this manager, active or not.
+#[tauri::command]
+pub async fn list_forwards(state: State<'_, AppState>) -> Result<Vec<ForwardInfo>, String> {
+    Ok(state.service_forwards.lock().unwrap().values().map(to_info).collect())
+}
+
+/// Whether the forward named by `id` is still running.
+#[tauri::command]
+pub async fn forward_status(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+    Ok(is_forward_active(&state, &id))
+}
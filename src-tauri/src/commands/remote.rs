@@ -0,0 +1,180 @@
+/// Clusters reachable only through a jump host. Rather than implementing
+/// the SSH transport protocol ourselves, this shells out to the system
+/// `ssh`/`scp` binaries the same way `commands::vcluster` shells out to the
+/// `vcluster` CLI - `connect_remote_host` just validates the host is
+/// reachable and uploads the helper binary; `start_remote_shell` drives the
+/// PTY session through `ssh -tt` using the exact `portable_pty` pattern
+/// `commands::terminal::start_local_shell` uses for a local shell.
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::state::{AppState, RemoteAuth, RemoteHost, ShellSession};
+
+const HELPER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+fn ssh_target(host: &RemoteHost) -> String {
+    format!("{}@{}", host.user, host.host)
+}
+
+/// Build the base `ssh` args shared by connectivity checks, helper upload,
+/// and interactive sessions: identity file (if key-based auth) plus
+/// `BatchMode=yes` so a missing/rejected key fails fast instead of hanging
+/// on a password prompt we have no way to answer yet.
+fn ssh_base_args(host: &RemoteHost) -> Vec<String> {
+    let mut args = vec!["-o".to_string(), "BatchMode=yes".to_string()];
+    if let RemoteAuth::Key { path } = &host.auth {
+        args.push("-i".to_string());
+        args.push(path.clone());
+    }
+    args
+}
+
+#[tauri::command]
+pub async fn connect_remote_host(
+    state: State<'_, AppState>,
+    alias: String,
+    host: String,
+    user: String,
+    key_path: Option<String>,
+) -> Result<(), String> {
+    let auth = match key_path {
+        Some(path) => RemoteAuth::Key { path },
+        None => RemoteAuth::Password,
+    };
+    if matches!(auth, RemoteAuth::Password) {
+        return Err("Password auth isn't supported yet - register a host with an SSH key (key_path)".to_string());
+    }
+
+    let mut remote_host = RemoteHost { alias: alias.clone(), host, user, auth, helper_version: None };
+
+    // Sanity check: can we actually reach it before storing the descriptor.
+    let target = ssh_target(&remote_host);
+    let mut check = tokio::process::Command::new("ssh");
+    check.args(ssh_base_args(&remote_host)).arg(&target).arg("true");
+    let status = check
+        .status()
+        .await
+        .map_err(|e| format!("Failed to run ssh: {}", e))?;
+    if !status.success() {
+        return Err(format!("Could not reach {} over SSH", target));
+    }
+
+    remote_host.helper_version = upload_helper_if_stale(&remote_host).await.ok();
+
+    state.remote_hosts.lock().unwrap().insert(alias, remote_host);
+    Ok(())
+}
+
+/// Upload the running OpsPilot binary to the remote host as the helper,
+/// skipping the `scp` if the remote already reports the current version -
+/// mirrors the "cache keyed by version so re-uploads are skipped" ask.
+async fn upload_helper_if_stale(host: &RemoteHost) -> Result<String, String> {
+    let target = ssh_target(host);
+    let remote_path = "~/.opspilot/opspilot-helper";
+
+    let mut version_check = tokio::process::Command::new("ssh");
+    version_check
+        .args(ssh_base_args(host))
+        .arg(&target)
+        .arg(format!("{} --version 2>/dev/null || true", remote_path));
+    let output = version_check.output().await.map_err(|e| e.to_string())?;
+    let remote_version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if remote_version == HELPER_VERSION {
+        return Ok(HELPER_VERSION.to_string());
+    }
+
+    let local_binary = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut mkdir = tokio::process::Command::new("ssh");
+    mkdir.args(ssh_base_args(host)).arg(&target).arg("mkdir -p ~/.opspilot");
+    mkdir.status().await.map_err(|e| e.to_string())?;
+
+    let mut scp = tokio::process::Command::new("scp");
+    if let RemoteAuth::Key { path } = &host.auth {
+        scp.arg("-i").arg(path);
+    }
+    scp.arg(&local_binary).arg(format!("{}:{}", target, remote_path));
+    let status = scp.status().await.map_err(|e| e.to_string())?;
+    if !status.success() {
+        return Err("Failed to upload OpsPilot helper binary".to_string());
+    }
+
+    Ok(HELPER_VERSION.to_string())
+}
+
+#[tauri::command]
+pub async fn list_remote_hosts(state: State<'_, AppState>) -> Result<Vec<RemoteHost>, String> {
+    Ok(state.remote_hosts.lock().unwrap().values().cloned().collect())
+}
+
+#[tauri::command]
+pub async fn disconnect_remote_host(state: State<'_, AppState>, alias: String) -> Result<(), String> {
+    state.remote_hosts.lock().unwrap().remove(&alias);
+    Ok(())
+}
+
+/// Interactive shell on a registered remote host, streamed through the same
+/// `agent:terminal:data`-style events as `start_local_shell` - the frontend
+/// doesn't need to know the session is remote.
+#[tauri::command]
+pub async fn start_remote_shell(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+    alias: String,
+) -> Result<(), String> {
+    let host = state
+        .remote_hosts
+        .lock()
+        .unwrap()
+        .get(&alias)
+        .cloned()
+        .ok_or_else(|| format!("Unknown remote host: {}", alias))?;
+
+    let pty_system = NativePtySystem::default();
+    let mut cmd = CommandBuilder::new("ssh");
+    cmd.arg("-tt");
+    for arg in ssh_base_args(&host) {
+        cmd.arg(arg);
+    }
+    cmd.arg(ssh_target(&host));
+
+    let pair = pty_system
+        .openpty(PtySize { rows: 24, cols: 80, pixel_width: 0, pixel_height: 0 })
+        .map_err(|e| e.to_string())?;
+
+    let reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
+    let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
+
+    let app_handle = app.clone();
+    let sid = session_id.clone();
+    std::thread::spawn(move || {
+        let mut reader = reader;
+        let mut buffer = [0u8; 4096];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(n) if n > 0 => {
+                    let data = String::from_utf8_lossy(&buffer[..n]).to_string();
+                    let _ = app_handle.emit(&format!("shell_output:{}", sid), data);
+                }
+                _ => {
+                    let _ = app_handle.emit(&format!("shell_closed:{}", sid), ());
+                    break;
+                }
+            }
+        }
+    });
+
+    let child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn ssh: {}", e))?;
+
+    let session = Arc::new(ShellSession {
+        writer: Arc::new(Mutex::new(writer)),
+        master: Arc::new(Mutex::new(pair.master)),
+        child: Arc::new(Mutex::new(child)),
+    });
+    state.shell_sessions.lock().unwrap().insert(session_id, session);
+
+    Ok(())
+}
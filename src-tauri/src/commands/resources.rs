@@ -1,22 +1,64 @@
 
 use tauri::{State, Emitter};
 use kube::{
-    api::{Api, ListParams, DeleteParams, LogParams, DynamicObject, GroupVersionKind, Patch, PatchParams},
+    api::{Api, ListParams, DeleteParams, LogParams, DynamicObject, GroupVersionKind, Patch, PatchParams, WatchParams, WatchEvent},
     runtime::watcher::{watcher, Config as WatcherConfig, Event as WatcherEvent},
     Discovery,
 };
-use crate::state::AppState;
-use crate::models::{ResourceRequest, ResourceSummary, ResourceWatchEvent, K8sEvent, K8sEventSource, K8sEventMetadata};
+use crate::state::{AppState, WatchStoreEntry};
+use crate::models::{ResourceRequest, ResourceSummary, ResourceWatchEvent, K8sEvent, K8sEventSource, K8sEventMetadata, StatusRule};
 use crate::client::create_client;
 use crate::commands::discovery::get_cached_discovery;
 use futures::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::time::Duration;
+use std::collections::HashMap;
+
+/// Key `AppState::status_rules` by GVK so a rule only applies to the kind it
+/// was registered for.
+fn gvk_key(group: &str, version: &str, kind: &str) -> String {
+    format!("{}/{}/{}", group, version, kind)
+}
+
+/// Walk a JSONPath-like path ("phase" or "conditions[type=Ready].status")
+/// against a status-shaped JSON value. Plain segments are field lookups;
+/// a `[key=value]` suffix on a segment picks the array element whose
+/// `key` field equals `value` - the common CRD idiom for finding a specific
+/// condition without knowing its position.
+fn resolve_status_path(value: &serde_json::Value, path: &str) -> Option<String> {
+    let mut current = value;
+    for segment in path.split('.') {
+        if let Some(bracket) = segment.find('[') {
+            let field = &segment[..bracket];
+            let filter = segment[bracket..].trim_start_matches('[').trim_end_matches(']');
+            let (filter_key, filter_value) = filter.split_once('=')?;
+
+            current = if field.is_empty() { current } else { current.get(field)? };
+            let items = current.as_array()?;
+            current = items.iter().find(|item| {
+                item.get(filter_key).and_then(|v| v.as_str()) == Some(filter_value)
+            })?;
+        } else {
+            current = current.get(segment)?;
+        }
+    }
+    current.as_str().map(|s| s.to_string())
+        .or_else(|| current.as_bool().map(|b| b.to_string()))
+        .or_else(|| current.as_i64().map(|n| n.to_string()))
+}
 
 /// Extract meaningful status from a Kubernetes resource based on its actual YAML structure.
 /// This avoids generic fallbacks and instead shows accurate status from the resource's status section.
-fn extract_status(obj: &DynamicObject, kind: &str) -> String {
+fn extract_status(obj: &DynamicObject, kind: &str, rule: Option<&StatusRule>) -> String {
     let status_obj = obj.data.get("status");
     let spec_obj = obj.data.get("spec");
 
+    if let Some(rule) = rule {
+        if let Some(raw) = status_obj.and_then(|s| resolve_status_path(s, &rule.path)) {
+            return rule.value_map.get(&raw).cloned().unwrap_or(raw);
+        }
+    }
+
     match kind {
         // Workload resources with replica-based status
         "Deployment" => {
@@ -358,7 +400,7 @@ fn extract_status(obj: &DynamicObject, kind: &str) -> String {
 }
 
 // Helper to convert DynamicObject to ResourceSummary
-fn to_summary(obj: DynamicObject, req_kind: &str, req_group: &str, req_version: &str, include_raw: bool) -> ResourceSummary {
+fn to_summary(obj: DynamicObject, req_kind: &str, req_group: &str, req_version: &str, include_raw: bool, status_rule: Option<&StatusRule>) -> ResourceSummary {
     let name = obj.metadata.name.clone().unwrap_or_default();
     let namespace = obj.metadata.namespace.clone().unwrap_or("-".into());
 
@@ -372,7 +414,7 @@ fn to_summary(obj: DynamicObject, req_kind: &str, req_group: &str, req_version:
     let status = if is_terminating {
         "Terminating".to_string()
     } else {
-        extract_status(&obj, req_kind)
+        extract_status(&obj, req_kind, status_rule)
     };
 
     let (ready, restarts, node, ip) = if req_kind.to_lowercase() == "pod" {
@@ -487,14 +529,76 @@ pub async fn list_resources(state: State<'_, AppState>, req: ResourceRequest) ->
     let version = ar.version.clone();
     // Default false, can be passed
     let include_raw = req.include_raw.unwrap_or(false);
+    let status_rule = state.status_rules.lock().unwrap().get(&gvk_key(&group, &version, &kind)).cloned();
 
     let summaries = list.into_iter().map(|obj| {
-        to_summary(obj, &kind, &group, &version, include_raw)
+        to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref())
     }).collect();
 
     Ok(summaries)
 }
 
+/// Per-kind/per-namespace rollup for one `resource_index` GVK: how many
+/// objects, bucketed by the same status `extract_status` would show for a
+/// single resource.
+#[derive(serde::Serialize)]
+pub struct IndexEntry {
+    pub kind: String,
+    pub namespace: String,
+    pub total: u32,
+    pub by_status: HashMap<String, u32>,
+}
+
+/// Cheap counts-only rollup across many GVKs, for a cluster-overview
+/// dashboard. Lists metadata and status only - no `ResourceSummary` field
+/// extraction or raw payloads - and groups the counts server-side, so large
+/// clusters stay responsive compared to shipping every object via
+/// `list_resources`. A GVK this cluster doesn't have is skipped rather than
+/// failing the whole rollup.
+#[tauri::command]
+pub async fn resource_index(state: State<'_, AppState>, reqs: Vec<ResourceRequest>) -> Result<Vec<IndexEntry>, String> {
+    let client = create_client(state.clone()).await?;
+    let discovery = get_cached_discovery(&state, client.clone()).await?;
+
+    let mut entries: Vec<IndexEntry> = Vec::new();
+
+    for req in reqs {
+        let gvk = GroupVersionKind::gvk(&req.group, &req.version, &req.kind);
+        let ar = match discovery.resolve_gvk(&gvk) {
+            Some((res, _caps)) => res,
+            None => continue,
+        };
+
+        let api: Api<DynamicObject> = if let Some(ns) = req.namespace.clone() {
+            Api::namespaced_with(client.clone(), &ns, &ar)
+        } else {
+            Api::all_with(client.clone(), &ar)
+        };
+
+        let list = match api.list(&ListParams::default()).await {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+
+        let status_rule = state.status_rules.lock().unwrap().get(&gvk_key(&req.group, &req.version, &req.kind)).cloned();
+        let mut by_namespace: HashMap<String, (u32, HashMap<String, u32>)> = HashMap::new();
+
+        for obj in &list.items {
+            let namespace = obj.metadata.namespace.clone().unwrap_or_default();
+            let status = extract_status(obj, &req.kind, status_rule.as_ref());
+            let bucket = by_namespace.entry(namespace).or_insert_with(|| (0, HashMap::new()));
+            bucket.0 += 1;
+            *bucket.1.entry(status).or_insert(0) += 1;
+        }
+
+        for (namespace, (total, by_status)) in by_namespace {
+            entries.push(IndexEntry { kind: req.kind.clone(), namespace, total, by_status });
+        }
+    }
+
+    Ok(entries)
+}
+
 #[tauri::command]
 pub async fn delete_resource(state: State<'_, AppState>, req: ResourceRequest, name: String) -> Result<(), String> {
     let client = create_client(state).await?;
@@ -512,8 +616,16 @@ pub async fn delete_resource(state: State<'_, AppState>, req: ResourceRequest, n
     Ok(())
 }
 
+/// Object YAML plus the `resourceVersion` it was read at, so a later
+/// `apply_resource` call can send that version back as a precondition.
+#[derive(serde::Serialize)]
+pub struct ResourceDetails {
+    pub yaml: String,
+    pub resource_version: Option<String>,
+}
+
 #[tauri::command]
-pub async fn get_resource_details(state: State<'_, AppState>, req: ResourceRequest, name: String) -> Result<String, String> {
+pub async fn get_resource_details(state: State<'_, AppState>, req: ResourceRequest, name: String) -> Result<ResourceDetails, String> {
     let client = create_client(state.clone()).await?;
     let gvk = GroupVersionKind::gvk(&req.group, &req.version, &req.kind);
     let discovery = get_cached_discovery(&state, client.clone()).await?;
@@ -547,7 +659,93 @@ pub async fn get_resource_details(state: State<'_, AppState>, req: ResourceReque
     };
 
     let obj = api.get(&name).await.map_err(|e| e.to_string())?;
-    Ok(serde_yaml::to_string(&obj).unwrap_or_default())
+    let resource_version = obj.metadata.resource_version.clone();
+    Ok(ResourceDetails {
+        yaml: serde_yaml::to_string(&obj).unwrap_or_default(),
+        resource_version,
+    })
+}
+
+/// Returned (as a JSON-encoded string, matching how every other command
+/// here reports errors) when `apply_resource`'s resourceVersion precondition
+/// doesn't match the object currently on the server, so the frontend can
+/// offer a three-way merge/reload instead of just showing an error string.
+#[derive(serde::Serialize)]
+pub struct ResourceConflict {
+    pub server_version: String,
+    pub your_base_version: String,
+}
+
+/// Distinguish a genuine resourceVersion-precondition 409 from a 409 caused
+/// by server-side apply rejecting a field another manager owns. Both are
+/// HTTP 409 from the API server; the only signal telling them apart is the
+/// error message text, which kube-rs passes through verbatim from the
+/// apiserver's wording ("field manager" / "conflicts with" only appear in
+/// the field-ownership case). That coupling to exact apiserver wording is
+/// brittle - it would silently stop matching on a wording change - so it's
+/// unit-tested directly below rather than only exercised indirectly through
+/// `apply_resource`.
+fn is_resource_version_conflict(status_code: u16, message: &str) -> bool {
+    status_code == 409 && !message.contains("field manager") && !message.contains("conflicts with")
+}
+
+/// Apply a YAML edit with an optimistic-concurrency precondition: the
+/// `resource_version` the caller read the object at (from `get_resource_details`)
+/// is sent back as part of the applied object, so a concurrent edit in
+/// between causes the server to reject this one with a 409 instead of
+/// silently clobbering it. Uses server-side-apply with a stable field
+/// manager ("opspilot") and does NOT force, so a field-ownership conflict
+/// from another manager surfaces as its own error rather than being masked.
+#[tauri::command]
+pub async fn apply_resource(
+    state: State<'_, AppState>,
+    req: ResourceRequest,
+    name: String,
+    yaml_content: String,
+    resource_version: String,
+) -> Result<String, String> {
+    let client = create_client(state.clone()).await?;
+    let discovery = get_cached_discovery(&state, client.clone()).await?;
+    let gvk = GroupVersionKind::gvk(&req.group, &req.version, &req.kind);
+    let (ar, _) = discovery.resolve_gvk(&gvk).ok_or("Resource not found")?;
+
+    let api: Api<DynamicObject> = if let Some(ns) = &req.namespace {
+        Api::namespaced_with(client, ns, &ar)
+    } else {
+        Api::all_with(client, &ar)
+    };
+
+    let mut data: serde_json::Value = serde_yaml::from_str(&yaml_content).map_err(|e| format!("Invalid YAML: {}", e))?;
+    if let Some(metadata) = data.get_mut("metadata") {
+        if let Some(obj) = metadata.as_object_mut() {
+            obj.remove("managedFields");
+            obj.insert("resourceVersion".to_string(), serde_json::Value::String(resource_version.clone()));
+        }
+    }
+
+    let obj: DynamicObject = serde_json::from_value(data).map_err(|e| e.to_string())?;
+    let pp = PatchParams::apply("opspilot");
+
+    match api.patch(&name, &pp, &Patch::Apply(&obj)).await {
+        Ok(patched) => {
+            let mut patched_json = serde_json::to_value(&patched).map_err(|e| e.to_string())?;
+            if let Some(metadata) = patched_json.get_mut("metadata") {
+                if let Some(obj) = metadata.as_object_mut() {
+                    obj.remove("managedFields");
+                }
+            }
+            serde_yaml::to_string(&patched_json).map_err(|e| e.to_string())
+        }
+        Err(kube::Error::Api(ae)) if is_resource_version_conflict(ae.code, &ae.message) => {
+            let current = api.get(&name).await.map_err(|e| e.to_string())?;
+            let conflict = ResourceConflict {
+                server_version: current.metadata.resource_version.unwrap_or_default(),
+                your_base_version: resource_version,
+            };
+            Err(serde_json::to_string(&conflict).unwrap_or_else(|_| "Resource conflict".to_string()))
+        }
+        Err(e) => Err(e.to_string()),
+    }
 }
 
 #[tauri::command]
@@ -645,6 +843,164 @@ pub async fn stop_log_stream(state: State<'_, AppState>, session_id: String) ->
     Ok(())
 }
 
+/// One tagged chunk from `start_workload_log_stream`'s fan-out, so the UI can
+/// color/filter the merged stream by source pod/container.
+#[derive(serde::Serialize, Clone)]
+pub struct WorkloadLogChunk {
+    pub pod: String,
+    pub container: Option<String>,
+    pub data: String,
+}
+
+/// Like `start_log_stream`, but for a whole workload: resolves the matching
+/// Pods via a label selector (either given directly, or read off a
+/// Deployment/StatefulSet/DaemonSet's `spec.selector.matchLabels`), opens a
+/// `log_stream` per pod/container, and multiplexes all of them into the one
+/// `log_stream:{session_id}` channel. Re-runs the selector every 30s so pods
+/// created by a rollout are picked up without restarting the session; each
+/// per-pod tail is a child task the supervisor aborts on cancellation, so
+/// `stop_log_stream` (which only needs to cancel the one sender stored under
+/// `session_id`) tears the whole fan-out down.
+#[tauri::command]
+pub async fn start_workload_log_stream(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    namespace: String,
+    workload_kind: Option<String>,
+    workload_name: Option<String>,
+    label_selector: Option<String>,
+    container: Option<String>,
+    session_id: String,
+    tail_lines: Option<i64>,
+) -> Result<(), String> {
+    {
+        let mut streams = state.log_streams.lock().unwrap();
+        if let Some(cancel_tx) = streams.remove(&session_id) {
+            let _ = cancel_tx.send(());
+        }
+    }
+
+    let client = create_client(state.clone()).await?;
+
+    let selector = if let Some(sel) = label_selector {
+        sel
+    } else {
+        let kind = workload_kind.ok_or("Must provide either workload_kind/workload_name or label_selector")?;
+        let wname = workload_name.ok_or("Must provide either workload_kind/workload_name or label_selector")?;
+        let match_labels = match kind.as_str() {
+            "Deployment" => {
+                let api: Api<k8s_openapi::api::apps::v1::Deployment> = Api::namespaced(client.clone(), &namespace);
+                api.get(&wname).await.map_err(|e| e.to_string())?.spec.map(|s| s.selector.match_labels)
+            }
+            "StatefulSet" => {
+                let api: Api<k8s_openapi::api::apps::v1::StatefulSet> = Api::namespaced(client.clone(), &namespace);
+                api.get(&wname).await.map_err(|e| e.to_string())?.spec.map(|s| s.selector.match_labels)
+            }
+            "DaemonSet" => {
+                let api: Api<k8s_openapi::api::apps::v1::DaemonSet> = Api::namespaced(client.clone(), &namespace);
+                api.get(&wname).await.map_err(|e| e.to_string())?.spec.map(|s| s.selector.match_labels)
+            }
+            other => return Err(format!("Unsupported workload kind for log aggregation: {}", other)),
+        }.flatten().ok_or("Workload has no label selector")?;
+
+        match_labels.into_iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join(",")
+    };
+
+    let (cancel_tx, mut cancel_rx) = tokio::sync::oneshot::channel::<()>();
+    {
+        let mut streams = state.log_streams.lock().unwrap();
+        streams.insert(session_id.clone(), cancel_tx);
+    }
+
+    let log_streams = state.log_streams.clone();
+    let sid = session_id.clone();
+    let pods_api: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client, &namespace);
+    let tail = tail_lines.unwrap_or(500);
+
+    tokio::spawn(async move {
+        const RESELECT_INTERVAL: Duration = Duration::from_secs(30);
+        let mut child_handles: Vec<(String, tokio::task::JoinHandle<()>)> = Vec::new();
+        let mut reselect = tokio::time::interval(RESELECT_INTERVAL);
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut cancel_rx => break,
+                _ = reselect.tick() => {
+                    child_handles.retain(|(_, h)| !h.is_finished());
+
+                    let lp = ListParams::default().labels(&selector);
+                    let pods = match pods_api.list(&lp).await {
+                        Ok(list) => list.items,
+                        Err(_) => continue,
+                    };
+
+                    for pod in pods {
+                        let Some(pod_name) = pod.metadata.name.clone() else { continue };
+                        if child_handles.iter().any(|(name, _)| name == &pod_name) { continue; }
+
+                        let container_names: Vec<Option<String>> = if let Some(c) = &container {
+                            vec![Some(c.clone())]
+                        } else {
+                            pod.spec.as_ref()
+                                .map(|s| s.containers.iter().map(|c| Some(c.name.clone())).collect())
+                                .unwrap_or_else(|| vec![None])
+                        };
+
+                        for container_name in container_names {
+                            let pods_api = pods_api.clone();
+                            let app = app.clone();
+                            let sid = sid.clone();
+                            let pod_name_task = pod_name.clone();
+
+                            let handle = tokio::spawn(async move {
+                                use futures::AsyncReadExt;
+                                let lp = LogParams {
+                                    container: container_name.clone(),
+                                    tail_lines: Some(tail),
+                                    follow: true,
+                                    ..LogParams::default()
+                                };
+                                let stream = match pods_api.log_stream(&pod_name_task, &lp).await {
+                                    Ok(s) => s,
+                                    Err(_) => return,
+                                };
+                                let mut stream = Box::pin(stream);
+                                let mut buf = vec![0u8; 16384];
+                                loop {
+                                    match stream.read(&mut buf).await {
+                                        Ok(0) => break,
+                                        Ok(n) => {
+                                            let data = String::from_utf8_lossy(&buf[..n]).to_string();
+                                            let chunk = WorkloadLogChunk { pod: pod_name_task.clone(), container: container_name.clone(), data };
+                                            let _ = app.emit(&format!("log_stream:{}", sid), chunk);
+                                        }
+                                        Err(_) => break,
+                                    }
+                                }
+                            });
+
+                            child_handles.push((pod_name.clone(), handle));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (_, handle) in child_handles {
+            handle.abort();
+        }
+
+        {
+            let mut streams = log_streams.lock().unwrap();
+            streams.remove(&sid);
+        }
+        let _ = app.emit(&format!("log_stream_end:{}", sid), ());
+    });
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn start_resource_watch(
     app: tauri::AppHandle,
@@ -674,6 +1030,7 @@ pub async fn start_resource_watch(
     let group = req.group.clone();
     let version = ar.version.clone();
     let include_raw = req.include_raw.unwrap_or(false);
+    let status_rule = state.status_rules.lock().unwrap().get(&gvk_key(&group, &version, &kind)).cloned();
 
     let api: Api<DynamicObject> = if let Some(ns) = req.namespace.clone() {
         Api::namespaced_with(client.clone(), &ns, &ar)
@@ -690,58 +1047,109 @@ pub async fn start_resource_watch(
         watches.insert(watch_id.clone(), cancel_tx);
     }
 
+    // Seed the reflector store for this watch; rebuilt on every Init event.
+    {
+        let mut store = state.watch_store.lock().unwrap();
+        store.insert(watch_id.clone(), WatchStoreEntry {
+            objects: HashMap::new(),
+            kind: kind.clone(),
+            group: group.clone(),
+            version: version.clone(),
+            include_raw,
+        });
+    }
+
     let watch_streams = state.watch_streams.clone();
+    let watch_store = state.watch_store.clone();
     let watch_id_clone = watch_id.clone();
     let watch_id_for_cleanup = watch_id.clone();
 
     tokio::spawn(async move {
-        let watcher_config = WatcherConfig::default();
-        let mut stream = watcher(api, watcher_config).boxed();
-
-        loop {
-            tokio::select! {
-                biased;
-                // Check for cancellation first
-                _ = &mut cancel_rx => {
-                    // Cancelled - clean exit
-                    break;
-                }
-                // Process watch events
-                result = stream.try_next() => {
-                    match result {
-                        Ok(Some(event)) => {
-                            let watch_event = match event {
-                                WatcherEvent::Apply(obj) => {
-                                    ResourceWatchEvent {
-                                        event_type: "MODIFIED".to_string(),
-                                        resource: to_summary(obj, &kind, &group, &version, include_raw),
+        const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+        let mut backoff = INITIAL_BACKOFF;
+
+        'reconnect: loop {
+            let watcher_config = WatcherConfig::default();
+            let mut stream = watcher(api.clone(), watcher_config).boxed();
+
+            loop {
+                tokio::select! {
+                    biased;
+                    // Check for cancellation first
+                    _ = &mut cancel_rx => {
+                        // Cancelled - clean exit
+                        break 'reconnect;
+                    }
+                    // Process watch events
+                    result = stream.try_next() => {
+                        match result {
+                            Ok(Some(event)) => {
+                                let watch_event = match event {
+                                    WatcherEvent::Apply(obj) => {
+                                        let uid = obj.metadata.uid.clone().unwrap_or_default();
+                                        if let Some(entry) = watch_store.lock().unwrap().get_mut(&watch_id_clone) {
+                                            entry.objects.insert(uid, obj.clone());
+                                        }
+                                        ResourceWatchEvent {
+                                            event_type: "MODIFIED".to_string(),
+                                            resource: to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref()),
+                                        }
                                     }
-                                }
-                                WatcherEvent::Delete(obj) => {
-                                    ResourceWatchEvent {
-                                        event_type: "DELETED".to_string(),
-                                        resource: to_summary(obj, &kind, &group, &version, include_raw),
+                                    WatcherEvent::Delete(obj) => {
+                                        let uid = obj.metadata.uid.clone().unwrap_or_default();
+                                        if let Some(entry) = watch_store.lock().unwrap().get_mut(&watch_id_clone) {
+                                            entry.objects.remove(&uid);
+                                        }
+                                        ResourceWatchEvent {
+                                            event_type: "DELETED".to_string(),
+                                            resource: to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref()),
+                                        }
                                     }
-                                }
-                                WatcherEvent::Init => { continue; }
-                                WatcherEvent::InitApply(obj) => {
-                                    ResourceWatchEvent {
-                                        event_type: "ADDED".to_string(),
-                                        resource: to_summary(obj, &kind, &group, &version, include_raw),
+                                    WatcherEvent::Init => {
+                                        // (Re)relisting - drop whatever the store had so it
+                                        // only ever reflects one consistent point in time.
+                                        if let Some(entry) = watch_store.lock().unwrap().get_mut(&watch_id_clone) {
+                                            entry.objects.clear();
+                                        }
+                                        continue;
                                     }
-                                }
-                                WatcherEvent::InitDone => {
-                                    let _ = app.emit(&format!("resource_watch_sync:{}", watch_id_clone), "SYNC_COMPLETE");
-                                    continue;
-                                }
-                            };
-                            let _ = app.emit(&format!("resource_watch:{}", watch_id_clone), watch_event);
+                                    WatcherEvent::InitApply(obj) => {
+                                        let uid = obj.metadata.uid.clone().unwrap_or_default();
+                                        if let Some(entry) = watch_store.lock().unwrap().get_mut(&watch_id_clone) {
+                                            entry.objects.insert(uid, obj.clone());
+                                        }
+                                        ResourceWatchEvent {
+                                            event_type: "ADDED".to_string(),
+                                            resource: to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref()),
+                                        }
+                                    }
+                                    WatcherEvent::InitDone => {
+                                        // A full relist completed cleanly - reset backoff so a
+                                        // later disconnect starts retrying fast again.
+                                        backoff = INITIAL_BACKOFF;
+                                        let _ = app.emit(&format!("resource_watch_sync:{}", watch_id_clone), "SYNC_COMPLETE");
+                                        continue;
+                                    }
+                                };
+                                let _ = app.emit(&format!("resource_watch:{}", watch_id_clone), watch_event);
+                            }
+                            Ok(None) => break, // Stream ended - reconnect below
+                            Err(_) => break, // Stream error - reconnect below
                         }
-                        Ok(None) => break, // Stream ended
-                        Err(_) => break, // Stream error
                     }
                 }
             }
+
+            // Wait out the current backoff (or exit immediately on cancellation),
+            // then reconnect with a fresh watcher instead of dying silently.
+            tokio::select! {
+                biased;
+                _ = &mut cancel_rx => { break 'reconnect; }
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+            let _ = app.emit(&format!("resource_watch_resync:{}", watch_id_clone), ());
         }
 
         // Cleanup: remove from tracking on exit
@@ -749,6 +1157,10 @@ pub async fn start_resource_watch(
             let mut watches = watch_streams.lock().unwrap();
             watches.remove(&watch_id_for_cleanup);
         }
+        {
+            let mut store = watch_store.lock().unwrap();
+            store.remove(&watch_id_for_cleanup);
+        }
         let _ = app.emit(&format!("resource_watch_end:{}", watch_id_for_cleanup), ());
     });
 
@@ -764,6 +1176,242 @@ pub async fn stop_resource_watch(state: State<'_, AppState>, watch_id: String) -
     Ok(())
 }
 
+/// Current materialized contents of a `start_resource_watch` reflector
+/// store, so the frontend can diff against a fresh snapshot (e.g. after a
+/// `resource_watch_resync` event) instead of having to reconstruct state
+/// purely from the event stream.
+#[tauri::command]
+pub async fn get_watch_snapshot(state: State<'_, AppState>, watch_id: String) -> Result<Vec<ResourceSummary>, String> {
+    let store = state.watch_store.lock().unwrap();
+    let entry = store.get(&watch_id).ok_or_else(|| format!("No active watch store for {}", watch_id))?;
+    let status_rule = state.status_rules.lock().unwrap().get(&gvk_key(&entry.group, &entry.version, &entry.kind)).cloned();
+    Ok(entry.objects.values().cloned().map(|obj| {
+        to_summary(obj, &entry.kind, &entry.group, &entry.version, entry.include_raw, status_rule.as_ref())
+    }).collect())
+}
+
+/// Like `start_resource_watch`, but driven off the low-level `Api::watch`
+/// instead of `watcher()`: it accepts a `resource_version` to resume from
+/// (so a reconnect streams only what changed since the caller's last
+/// bookmark instead of paying for a full relist), coalesces bursts of
+/// events into one emit per debounce window, and periodically emits a
+/// `resource_watch_bookmark:{watch_id}` event with the current
+/// resourceVersion so the UI can persist its position across reconnects.
+/// Stop it the same way as `start_resource_watch` - via `stop_watch`/
+/// `stop_resource_watch`, which share the same `watch_streams` registry.
+#[tauri::command]
+pub async fn watch_resources(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    req: ResourceRequest,
+    watch_id: String,
+    resource_version: Option<String>,
+) -> Result<(), String> {
+    {
+        let mut watches = state.watch_streams.lock().unwrap();
+        if let Some(cancel_tx) = watches.remove(&watch_id) {
+            let _ = cancel_tx.send(());
+        }
+    }
+
+    let client = create_client(state.clone()).await?;
+    let gvk = GroupVersionKind::gvk(&req.group, &req.version, &req.kind);
+    let discovery = get_cached_discovery(&state, client.clone()).await?;
+
+    let ar = discovery.resolve_gvk(&gvk).map(|(res, _caps)| res)
+        .ok_or_else(|| format!("Resource kind not found: {}/{}/{}", req.group, req.version, req.kind))?;
+
+    let kind = req.kind.clone();
+    let group = req.group.clone();
+    let version = ar.version.clone();
+    let include_raw = req.include_raw.unwrap_or(false);
+    let status_rule = state.status_rules.lock().unwrap().get(&gvk_key(&group, &version, &kind)).cloned();
+
+    let api: Api<DynamicObject> = if let Some(ns) = req.namespace.clone() {
+        Api::namespaced_with(client.clone(), &ns, &ar)
+    } else {
+        Api::all_with(client.clone(), &ar)
+    };
+
+    let start_version = resource_version.clone().unwrap_or_else(|| "0".to_string());
+    let wp = WatchParams::default();
+    let mut stream = api.watch(&wp, &start_version).await.map_err(|e| e.to_string())?.boxed();
+
+    let (cancel_tx, mut cancel_rx) = tokio::sync::oneshot::channel::<()>();
+    {
+        let mut watches = state.watch_streams.lock().unwrap();
+        watches.insert(watch_id.clone(), cancel_tx);
+    }
+
+    let watch_streams = state.watch_streams.clone();
+    let watch_id_clone = watch_id.clone();
+    let watch_id_for_cleanup = watch_id.clone();
+
+    tokio::spawn(async move {
+        const DEBOUNCE: Duration = Duration::from_millis(200);
+        const BOOKMARK_INTERVAL: Duration = Duration::from_secs(30);
+
+        let mut last_resource_version = resource_version;
+        let mut pending: Vec<ResourceWatchEvent> = Vec::new();
+        let mut flush_interval = tokio::time::interval(DEBOUNCE);
+        let mut bookmark_interval = tokio::time::interval(BOOKMARK_INTERVAL);
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut cancel_rx => break,
+                result = stream.try_next() => {
+                    match result {
+                        Ok(Some(event)) => match event {
+                            WatchEvent::Added(obj) => {
+                                last_resource_version = obj.metadata.resource_version.clone();
+                                pending.push(ResourceWatchEvent {
+                                    event_type: "ADDED".to_string(),
+                                    resource: to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref()),
+                                });
+                            }
+                            WatchEvent::Modified(obj) => {
+                                last_resource_version = obj.metadata.resource_version.clone();
+                                pending.push(ResourceWatchEvent {
+                                    event_type: "MODIFIED".to_string(),
+                                    resource: to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref()),
+                                });
+                            }
+                            WatchEvent::Deleted(obj) => {
+                                last_resource_version = obj.metadata.resource_version.clone();
+                                pending.push(ResourceWatchEvent {
+                                    event_type: "DELETED".to_string(),
+                                    resource: to_summary(obj, &kind, &group, &version, include_raw, status_rule.as_ref()),
+                                });
+                            }
+                            WatchEvent::Bookmark(bm) => {
+                                last_resource_version = Some(bm.metadata.resource_version);
+                            }
+                            WatchEvent::Error(e) => {
+                                eprintln!("[watch_resources] Watch error for {}: {:?}", watch_id_clone, e);
+                                break;
+                            }
+                        },
+                        Ok(None) => break,
+                        Err(_) => break,
+                    }
+                }
+                _ = flush_interval.tick() => {
+                    if !pending.is_empty() {
+                        let batch = std::mem::take(&mut pending);
+                        let _ = app.emit(&format!("resource_watch:{}", watch_id_clone), batch);
+                    }
+                }
+                _ = bookmark_interval.tick() => {
+                    if let Some(rv) = &last_resource_version {
+                        let _ = app.emit(&format!("resource_watch_bookmark:{}", watch_id_clone), rv.clone());
+                    }
+                }
+            }
+        }
+
+        if !pending.is_empty() {
+            let _ = app.emit(&format!("resource_watch:{}", watch_id_clone), pending);
+        }
+
+        {
+            let mut watches = watch_streams.lock().unwrap();
+            watches.remove(&watch_id_for_cleanup);
+        }
+        let _ = app.emit(&format!("resource_watch_end:{}", watch_id_for_cleanup), ());
+    });
+
+    Ok(())
+}
+
+/// Thin alias of `stop_resource_watch` with the name matching `watch_resources`
+/// - both watch flavors share the same `watch_streams` registry, so stopping
+/// one by `watch_id` is the same operation regardless of which started it.
+#[tauri::command]
+pub async fn stop_watch(state: State<'_, AppState>, watch_id: String) -> Result<(), String> {
+    stop_resource_watch(state, watch_id).await
+}
+
+/// Register per-GVK status extraction rules (keyed by "group/version/kind"),
+/// consulted by `extract_status` ahead of its built-in heuristics. Replaces
+/// the full rule set - callers should send the whole map they want in effect,
+/// not just the entries they're adding.
+#[tauri::command]
+pub async fn set_status_rules(state: State<'_, AppState>, rules: HashMap<String, StatusRule>) -> Result<(), String> {
+    let mut status_rules = state.status_rules.lock().unwrap();
+    *status_rules = rules;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_status_rules(state: State<'_, AppState>) -> Result<HashMap<String, StatusRule>, String> {
+    Ok(state.status_rules.lock().unwrap().clone())
+}
+
+fn core_event_matches(e: &k8s_openapi::api::core::v1::Event, name: &str, namespace: &str, uid: Option<&str>) -> bool {
+    let involved = &e.involved_object;
+    let name_match = involved.name.as_deref().map_or(false, |n| n == name);
+    let ns_match = involved.namespace.as_deref().map_or(true, |ns| ns == namespace);
+    let uid_match = uid.map_or(false, |u| involved.uid.as_deref() == Some(u));
+    (name_match && ns_match) || uid_match
+}
+
+fn to_k8s_event_core(e: k8s_openapi::api::core::v1::Event) -> K8sEvent {
+    let metadata = e.metadata.clone();
+    K8sEvent {
+        message: e.message.unwrap_or_default(),
+        reason: e.reason.unwrap_or_default(),
+        type_: e.type_.unwrap_or_default(),
+        age: e.last_timestamp.clone().map(|t| t.0.to_rfc3339()).unwrap_or_else(|| e.event_time.clone().map(|t| t.0.to_rfc3339()).unwrap_or_default()),
+        last_timestamp: e.last_timestamp.clone().map(|t| t.0.to_rfc3339()),
+        first_timestamp: e.first_timestamp.clone().map(|t| t.0.to_rfc3339()),
+        event_time: e.event_time.clone().map(|t| t.0.to_rfc3339()),
+        count: e.count.unwrap_or(1),
+        source: e.source.clone().map(|s| K8sEventSource {
+            component: s.component,
+            host: s.host,
+        }),
+        metadata: Some(K8sEventMetadata {
+            name: metadata.name,
+            namespace: metadata.namespace,
+            uid: metadata.uid,
+        }),
+    }
+}
+
+fn events_v1_event_matches(e: &k8s_openapi::api::events::v1::Event, name: &str, namespace: &str, uid: Option<&str>) -> bool {
+    let regarding = &e.regarding;
+    let name_match = regarding.as_ref().and_then(|r| r.name.as_ref()).map_or(false, |n| n == name);
+    let ns_match = regarding.as_ref().and_then(|r| r.namespace.as_ref()).map_or(true, |ns| ns == namespace);
+    let uid_match = if let (Some(r), Some(wanted)) = (regarding, uid) { r.uid.as_deref() == Some(wanted) } else { false };
+    (name_match && ns_match) || uid_match
+}
+
+fn to_k8s_event_new(e: k8s_openapi::api::events::v1::Event) -> K8sEvent {
+    let metadata = e.metadata.clone();
+    K8sEvent {
+        message: e.note.unwrap_or_default(),
+        reason: e.reason.unwrap_or_default(),
+        type_: e.type_.unwrap_or_default(),
+        age: e.event_time.clone().map(|t| t.0.to_rfc3339()).unwrap_or_else(||
+            e.deprecated_last_timestamp.clone().map(|t| t.0.to_rfc3339()).unwrap_or_default()
+        ),
+        last_timestamp: e.deprecated_last_timestamp.clone().map(|t| t.0.to_rfc3339()),
+        first_timestamp: e.deprecated_first_timestamp.clone().map(|t| t.0.to_rfc3339()),
+        event_time: e.event_time.clone().map(|t| t.0.to_rfc3339()),
+        count: e.deprecated_count.unwrap_or(e.series.as_ref().map(|s| s.count).unwrap_or(1)),
+        source: e.deprecated_source.clone().map(|s| K8sEventSource {
+            component: s.component,
+            host: s.host,
+        }),
+        metadata: Some(K8sEventMetadata {
+            name: metadata.name,
+            namespace: metadata.namespace,
+            uid: metadata.uid,
+        }),
+    }
+}
+
 #[tauri::command]
 pub async fn list_events(state: State<'_, AppState>, namespace: String, name: String, uid: Option<String>) -> Result<Vec<K8sEvent>, String> {
     let client = create_client(state).await?;
@@ -773,68 +1421,18 @@ pub async fn list_events(state: State<'_, AppState>, namespace: String, name: St
     let lp = ListParams::default();
 
     let core_events = match core_api.list(&lp).await {
-        Ok(list) => list.into_iter().filter_map(|e| {
-            let involved = &e.involved_object;
-            let name_match = involved.name.as_deref().map_or(false, |n| n == name);
-            let ns_match = involved.namespace.as_deref().map_or(true, |ns| ns == namespace);
-            let uid_match = uid.as_ref().map_or(false, |u| involved.uid.as_deref() == Some(u.as_str()));
-            if (name_match && ns_match) || uid_match {
-                let metadata = e.metadata.clone();
-                Some(K8sEvent {
-                    message: e.message.unwrap_or_default(),
-                    reason: e.reason.unwrap_or_default(),
-                    type_: e.type_.unwrap_or_default(),
-                    age: e.last_timestamp.clone().map(|t| t.0.to_rfc3339()).unwrap_or_else(|| e.event_time.clone().map(|t| t.0.to_rfc3339()).unwrap_or_default()),
-                    last_timestamp: e.last_timestamp.clone().map(|t| t.0.to_rfc3339()),
-                    first_timestamp: e.first_timestamp.clone().map(|t| t.0.to_rfc3339()),
-                    event_time: e.event_time.clone().map(|t| t.0.to_rfc3339()),
-                    count: e.count.unwrap_or(1),
-                    source: e.source.clone().map(|s| K8sEventSource {
-                        component: s.component,
-                        host: s.host,
-                    }),
-                    metadata: Some(K8sEventMetadata {
-                        name: metadata.name,
-                        namespace: metadata.namespace,
-                        uid: metadata.uid,
-                    }),
-                })
-            } else { None }
-        }).collect::<Vec<_>>(),
+        Ok(list) => list.into_iter()
+            .filter(|e| core_event_matches(e, &name, &namespace, uid.as_deref()))
+            .map(to_k8s_event_core)
+            .collect::<Vec<_>>(),
         Err(_) => vec![]
     };
 
     let new_events = match new_api.list(&lp).await {
-        Ok(list) => list.into_iter().filter_map(|e| {
-            let regarding = &e.regarding;
-            let name_match = regarding.as_ref().and_then(|r| r.name.as_ref()).map_or(false, |n| n == &name);
-            let ns_match = regarding.as_ref().and_then(|r| r.namespace.as_ref()).map_or(true, |ns| ns == &namespace);
-            let uid_match = if let (Some(r), Some(wanted)) = (regarding, uid.as_ref()) { r.uid.as_deref() == Some(wanted.as_str()) } else { false };
-            if (name_match && ns_match) || uid_match {
-                let metadata = e.metadata.clone();
-                Some(K8sEvent {
-                    message: e.note.unwrap_or_default(),
-                    reason: e.reason.unwrap_or_default(),
-                    type_: e.type_.unwrap_or_default(),
-                    age: e.event_time.clone().map(|t| t.0.to_rfc3339()).unwrap_or_else(||
-                        e.deprecated_last_timestamp.clone().map(|t| t.0.to_rfc3339()).unwrap_or_default()
-                    ),
-                    last_timestamp: e.deprecated_last_timestamp.clone().map(|t| t.0.to_rfc3339()),
-                    first_timestamp: e.deprecated_first_timestamp.clone().map(|t| t.0.to_rfc3339()),
-                    event_time: e.event_time.clone().map(|t| t.0.to_rfc3339()),
-                    count: e.deprecated_count.unwrap_or(e.series.as_ref().map(|s| s.count).unwrap_or(1)),
-                    source: e.deprecated_source.clone().map(|s| K8sEventSource {
-                        component: s.component,
-                        host: s.host,
-                    }),
-                    metadata: Some(K8sEventMetadata {
-                        name: metadata.name,
-                        namespace: metadata.namespace,
-                        uid: metadata.uid,
-                    }),
-                })
-            } else { None }
-        }).collect::<Vec<_>>(),
+        Ok(list) => list.into_iter()
+            .filter(|e| events_v1_event_matches(e, &name, &namespace, uid.as_deref()))
+            .map(to_k8s_event_new)
+            .collect::<Vec<_>>(),
         Err(_) => vec![]
     };
 
@@ -844,6 +1442,103 @@ pub async fn list_events(state: State<'_, AppState>, namespace: String, name: St
     Ok(all)
 }
 
+/// Push-delivery complement to `list_events`: watches both the legacy
+/// `core/v1.Event` and the newer `events/v1.Event` APIs for objects matching
+/// `name`/`namespace`/`uid`, normalizing each through the same
+/// `to_k8s_event_core`/`to_k8s_event_new` conversions `list_events` uses, and
+/// emits every new-or-updated match over `event_watch:{watch_id}` as it
+/// arrives. Tracks already-emitted event uids for the lifetime of the watch
+/// so a relist after a reconnect doesn't re-push events the caller has
+/// already seen.
+#[tauri::command]
+pub async fn start_event_watch(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    namespace: String,
+    name: String,
+    uid: Option<String>,
+    watch_id: String,
+) -> Result<(), String> {
+    {
+        let mut watches = state.watch_streams.lock().unwrap();
+        if let Some(cancel_tx) = watches.remove(&watch_id) {
+            let _ = cancel_tx.send(());
+        }
+    }
+
+    let client = create_client(state.clone()).await?;
+    let core_api: Api<k8s_openapi::api::core::v1::Event> = if namespace == "-" { Api::all(client.clone()) } else { Api::namespaced(client.clone(), &namespace) };
+    let new_api: Api<k8s_openapi::api::events::v1::Event> = if namespace == "-" { Api::all(client.clone()) } else { Api::namespaced(client.clone(), &namespace) };
+
+    let (cancel_tx, mut cancel_rx) = tokio::sync::oneshot::channel::<()>();
+    {
+        let mut watches = state.watch_streams.lock().unwrap();
+        watches.insert(watch_id.clone(), cancel_tx);
+    }
+
+    let watch_streams = state.watch_streams.clone();
+    let watch_id_clone = watch_id.clone();
+    let watch_id_for_cleanup = watch_id.clone();
+
+    tokio::spawn(async move {
+        let mut core_stream = watcher(core_api, WatcherConfig::default()).boxed();
+        let mut new_stream = watcher(new_api, WatcherConfig::default()).boxed();
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut cancel_rx => break,
+                result = core_stream.try_next() => {
+                    let obj = match result {
+                        Ok(Some(WatcherEvent::Apply(obj))) | Ok(Some(WatcherEvent::InitApply(obj))) => Some(obj),
+                        _ => None,
+                    };
+                    if let Some(e) = obj {
+                        if core_event_matches(&e, &name, &namespace, uid.as_deref()) {
+                            let event_uid = e.metadata.uid.clone().unwrap_or_default();
+                            if seen.insert(event_uid) {
+                                let _ = app.emit(&format!("event_watch:{}", watch_id_clone), to_k8s_event_core(e));
+                            }
+                        }
+                    }
+                }
+                result = new_stream.try_next() => {
+                    let obj = match result {
+                        Ok(Some(WatcherEvent::Apply(obj))) | Ok(Some(WatcherEvent::InitApply(obj))) => Some(obj),
+                        _ => None,
+                    };
+                    if let Some(e) = obj {
+                        if events_v1_event_matches(&e, &name, &namespace, uid.as_deref()) {
+                            let event_uid = e.metadata.uid.clone().unwrap_or_default();
+                            if seen.insert(event_uid) {
+                                let _ = app.emit(&format!("event_watch:{}", watch_id_clone), to_k8s_event_new(e));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        {
+            let mut watches = watch_streams.lock().unwrap();
+            watches.remove(&watch_id_for_cleanup);
+        }
+        let _ = app.emit(&format!("event_watch_end:{}", watch_id_for_cleanup), ());
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_event_watch(state: State<'_, AppState>, watch_id: String) -> Result<(), String> {
+    let mut watches = state.watch_streams.lock().unwrap();
+    if let Some(cancel_tx) = watches.remove(&watch_id) {
+        let _ = cancel_tx.send(());
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn apply_yaml(state: State<'_, AppState>, namespace: String, kind: String, name: String, yaml_content: String) -> Result<String, String> {
     let client = create_client(state).await?;
@@ -893,6 +1588,100 @@ pub async fn apply_yaml(state: State<'_, AppState>, namespace: String, kind: Str
     Ok(yaml_result)
 }
 
+/// Outcome of one document within an `apply_yaml_batch` call.
+#[derive(serde::Serialize)]
+pub struct BatchApplyResult {
+    pub index: usize,
+    pub kind: String,
+    pub name: String,
+    pub namespace: String,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// Apply every document in a `---`-separated YAML stream, reporting a
+/// per-document result instead of aborting the whole batch on the first
+/// failure (unlike `apply_yaml`, which only handles one object). Documents
+/// for CustomResourceDefinitions and Namespaces are applied first - in their
+/// original relative order - since other documents in the same batch may
+/// depend on them existing already; everything else keeps its original
+/// order after that. Pass `dry_run: true` to preview the batch (server-side
+/// apply with `dryRun=All`) without mutating anything.
+#[tauri::command]
+pub async fn apply_yaml_batch(state: State<'_, AppState>, yaml_content: String, dry_run: Option<bool>) -> Result<Vec<BatchApplyResult>, String> {
+    let client = create_client(state).await?;
+    let discovery = Discovery::new(client.clone()).run().await.map_err(|e| e.to_string())?;
+    let dry_run = dry_run.unwrap_or(false);
+
+    let docs: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(&yaml_content)
+        .map(serde_yaml::Value::deserialize)
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Invalid YAML: {}", e))?;
+
+    let mut ordered: Vec<usize> = (0..docs.len()).filter(|&i| !docs[i].is_null()).collect();
+    ordered.sort_by_key(|&i| {
+        let kind = docs[i].get("kind").and_then(|k| k.as_str()).unwrap_or("");
+        let priority = if kind == "CustomResourceDefinition" || kind == "Namespace" { 0 } else { 1 };
+        (priority, i)
+    });
+
+    let mut results = Vec::with_capacity(ordered.len());
+
+    for index in ordered {
+        let mut data: serde_json::Value = match serde_json::to_value(&docs[index]) {
+            Ok(v) => v,
+            Err(e) => {
+                results.push(BatchApplyResult { index, kind: String::new(), name: String::new(), namespace: String::new(), status: "Failed".to_string(), error: Some(e.to_string()) });
+                continue;
+            }
+        };
+
+        let kind = data.get("kind").and_then(|k| k.as_str()).unwrap_or_default().to_string();
+        let name = data.get("metadata").and_then(|m| m.get("name")).and_then(|n| n.as_str()).unwrap_or_default().to_string();
+        let namespace = data.get("metadata").and_then(|m| m.get("namespace")).and_then(|n| n.as_str()).unwrap_or_default().to_string();
+
+        let outcome: Result<(), String> = async {
+            let api_version = data.get("apiVersion").and_then(|v| v.as_str()).map(|s| s.to_string()).ok_or("Missing apiVersion in YAML")?;
+            let (group, version) = if api_version.contains('/') {
+                let parts: Vec<&str> = api_version.split('/').collect();
+                (parts[0].to_string(), parts[1].to_string())
+            } else {
+                ("".to_string(), api_version)
+            };
+
+            let gvk = GroupVersionKind::gvk(&group, &version, &kind);
+            let (ar, _) = discovery.resolve_gvk(&gvk).ok_or("Resource not found")?;
+
+            let api: Api<DynamicObject> = if !namespace.is_empty() {
+                Api::namespaced_with(client.clone(), &namespace, &ar)
+            } else {
+                Api::all_with(client.clone(), &ar)
+            };
+
+            if let Some(metadata) = data.get_mut("metadata") {
+                if let Some(obj) = metadata.as_object_mut() {
+                    obj.remove("managedFields");
+                    obj.remove("resourceVersion");
+                }
+            }
+
+            let obj: DynamicObject = serde_json::from_value(data).map_err(|e| e.to_string())?;
+            let mut pp = PatchParams::apply("opspilot-batchapply").force();
+            pp.dry_run = dry_run;
+
+            api.patch(&name, &pp, &Patch::Apply(&obj)).await.map_err(|e| e.to_string())?;
+            Ok(())
+        }.await;
+
+        match outcome {
+            Ok(()) => results.push(BatchApplyResult { index, kind, name, namespace, status: "Applied".to_string(), error: None }),
+            Err(e) => results.push(BatchApplyResult { index, kind, name, namespace, status: "Failed".to_string(), error: Some(e) }),
+        }
+    }
+
+    Ok(results)
+}
+
 #[tauri::command]
 pub async fn patch_resource(
     state: State<'_, AppState>,
@@ -1036,10 +1825,10 @@ pub async fn get_resource_metrics(state: State<'_, AppState>, kind: Option<Strin
         plural: api_plural.to_string(),
     };
 
-    let api: Api<DynamicObject> = if let Some(ns) = namespace {
-        Api::namespaced_with(client, &ns, &api_resource)
+    let api: Api<DynamicObject> = if let Some(ns) = namespace.clone() {
+        Api::namespaced_with(client.clone(), &ns, &api_resource)
     } else {
-        Api::all_with(client, &api_resource)
+        Api::all_with(client.clone(), &api_resource)
     };
 
     let items = if let Some(resource_name) = name {
@@ -1058,8 +1847,55 @@ pub async fn get_resource_metrics(state: State<'_, AppState>, kind: Option<Strin
         let list = api.list(&ListParams::default()).await.map_err(|e| e.to_string())?;
         list.items
     };
-    
-    // let list = api.list(&ListParams::default()).await.map_err(|e| e.to_string())?;
+
+    // Cross-reference spec limits/allocatable so cpu_percent/memory_percent aren't always None.
+    // Node: one cluster-wide list for status.allocatable. Pod: one list per distinct namespace
+    // seen in `items`, matched back up by name, so we don't issue a GET per pod.
+    let node_allocatable: HashMap<String, (u64, u64)> = if k.eq_ignore_ascii_case("Node") {
+        let nodes: Api<k8s_openapi::api::core::v1::Node> = Api::all(client.clone());
+        match nodes.list(&ListParams::default()).await {
+            Ok(list) => list.items.into_iter().filter_map(|n| {
+                let node_name = n.metadata.name?;
+                let allocatable = n.status.as_ref()?.allocatable.as_ref()?;
+                let cpu = allocatable.get("cpu").map(|q| crate::utils::parse_cpu_to_milli(&q.0) * 1_000_000).unwrap_or(0);
+                let mem = allocatable.get("memory").map(|q| crate::utils::parse_memory_to_bytes(&q.0)).unwrap_or(0);
+                Some((node_name, (cpu, mem)))
+            }).collect(),
+            Err(_) => HashMap::new(),
+        }
+    } else {
+        HashMap::new()
+    };
+
+    let mut pod_limits: HashMap<(String, String), (u64, u64)> = HashMap::new();
+    if !k.eq_ignore_ascii_case("Node") {
+        let namespaces: std::collections::HashSet<String> = items.iter()
+            .filter_map(|item| item.metadata.namespace.clone())
+            .collect();
+        for ns in namespaces {
+            let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client.clone(), &ns);
+            if let Ok(list) = pods.list(&ListParams::default()).await {
+                for pod in list.items {
+                    let Some(pod_name) = pod.metadata.name.clone() else { continue };
+                    let mut cpu_limit_milli: u64 = 0;
+                    let mut mem_limit_bytes: u64 = 0;
+                    if let Some(spec) = &pod.spec {
+                        for c in &spec.containers {
+                            if let Some(limits) = c.resources.as_ref().and_then(|r| r.limits.as_ref()) {
+                                if let Some(cpu) = limits.get("cpu") {
+                                    cpu_limit_milli += crate::utils::parse_cpu_to_milli(&cpu.0);
+                                }
+                                if let Some(mem) = limits.get("memory") {
+                                    mem_limit_bytes += crate::utils::parse_memory_to_bytes(&mem.0);
+                                }
+                            }
+                        }
+                    }
+                    pod_limits.insert((ns.clone(), pod_name), (cpu_limit_milli * 1_000_000, mem_limit_bytes));
+                }
+            }
+        }
+    }
 
     let metrics = items.into_iter().filter_map(|item| {
         let name = item.metadata.name?;
@@ -1096,6 +1932,15 @@ pub async fn get_resource_metrics(state: State<'_, AppState>, kind: Option<Strin
             (cpu_fmt, mem_fmt, total_cpu_nano, total_mem_bytes)
         };
 
+        let (cpu_limit_nano, memory_limit_bytes) = if k.eq_ignore_ascii_case("Node") {
+            node_allocatable.get(&name).copied().unzip()
+        } else {
+            pod_limits.get(&(ns.clone(), name.clone())).copied().unzip()
+        };
+
+        let cpu_percent = cpu_limit_nano.filter(|&c| c > 0).map(|c| (cpu_nano as f64 / c as f64) * 100.0);
+        let memory_percent = memory_limit_bytes.filter(|&m| m > 0).map(|m| (mem_bytes as f64 / m as f64) * 100.0);
+
         Some(crate::models::ResourceMetrics {
             name,
             namespace: ns,
@@ -1103,13 +1948,131 @@ pub async fn get_resource_metrics(state: State<'_, AppState>, kind: Option<Strin
             memory,
             cpu_nano,
             memory_bytes: mem_bytes,
-            cpu_limit_nano: None, // Hard to get without cross-referencing Pod specs
-            memory_limit_bytes: None,
-            cpu_percent: None,
-            memory_percent: None,
+            cpu_limit_nano,
+            memory_limit_bytes,
+            cpu_percent,
+            memory_percent,
             timestamp,
         })
     }).collect();
 
     Ok(metrics)
 }
+
+/// A single mutation to apply as part of a `batch_mutate_resources` call.
+#[derive(serde::Deserialize)]
+pub struct BatchResourceItem {
+    /// Caller-chosen identifier echoed back in the matching `BatchItemResult`
+    /// so the UI can correlate results without relying on array order.
+    pub id: String,
+    pub req: ResourceRequest,
+    pub name: String,
+    pub op: BatchResourceOp,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum BatchResourceOp {
+    Delete,
+    Patch { patch_data: serde_json::Value },
+    Apply { yaml_content: String },
+}
+
+/// Outcome of a single `BatchResourceItem`, keyed by its `id`.
+#[derive(serde::Serialize)]
+pub struct BatchItemResult {
+    pub id: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+/// Apply/delete/patch many resources in one call, executing them
+/// concurrently (bounded, so we don't hammer the API server) and reporting a
+/// per-item result instead of failing the whole batch on the first error.
+/// Useful for bulk-deleting evicted pods or relabeling a selection at once.
+#[tauri::command]
+pub async fn batch_mutate_resources(
+    state: State<'_, AppState>,
+    items: Vec<BatchResourceItem>,
+) -> Result<Vec<BatchItemResult>, String> {
+    const MAX_CONCURRENT: usize = 8;
+
+    let client = create_client(state.clone()).await?;
+    let discovery = get_cached_discovery(&state, client.clone()).await?;
+
+    let results = futures::stream::iter(items.into_iter().map(|item| {
+        let client = client.clone();
+        let discovery = discovery.clone();
+        async move {
+            let id = item.id.clone();
+            match apply_batch_resource_item(client, &discovery, item).await {
+                Ok(()) => BatchItemResult { id, ok: true, error: None },
+                Err(error) => BatchItemResult { id, ok: false, error: Some(error) },
+            }
+        }
+    }))
+    .buffer_unordered(MAX_CONCURRENT)
+    .collect::<Vec<_>>()
+    .await;
+
+    Ok(results)
+}
+
+async fn apply_batch_resource_item(
+    client: kube::Client,
+    discovery: &kube::Discovery,
+    item: BatchResourceItem,
+) -> Result<(), String> {
+    let gvk = GroupVersionKind::gvk(&item.req.group, &item.req.version, &item.req.kind);
+    let (ar, _) = discovery.resolve_gvk(&gvk).ok_or("Resource not found")?;
+
+    let api: Api<DynamicObject> = if let Some(ns) = &item.req.namespace {
+        Api::namespaced_with(client, ns, &ar)
+    } else {
+        Api::all_with(client, &ar)
+    };
+
+    match item.op {
+        BatchResourceOp::Delete => {
+            api.delete(&item.name, &DeleteParams::default()).await.map_err(|e| e.to_string())?;
+        }
+        BatchResourceOp::Patch { patch_data } => {
+            let pp = PatchParams::apply("opspilot");
+            api.patch(&item.name, &pp, &Patch::Merge(&patch_data)).await.map_err(|e| e.to_string())?;
+        }
+        BatchResourceOp::Apply { yaml_content } => {
+            let data: serde_json::Value = serde_yaml::from_str(&yaml_content).map_err(|e| format!("Invalid YAML: {}", e))?;
+            let obj: DynamicObject = serde_json::from_value(data).map_err(|e| e.to_string())?;
+            let pp = PatchParams::apply("opspilot-yamleditor").force();
+            api.patch(&item.name, &pp, &Patch::Apply(&obj)).await.map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_version_mismatch_is_a_conflict() {
+        assert!(is_resource_version_conflict(409, "Operation cannot be fulfilled: the object has been modified; please apply your changes to the latest version and try again"));
+    }
+
+    #[test]
+    fn field_manager_ownership_rejection_is_not_a_conflict() {
+        assert!(!is_resource_version_conflict(409, "Apply failed with 1 conflict: .spec.replicas conflicts with \"kubectl-client-side-apply\" using apps/v1"));
+    }
+
+    #[test]
+    fn field_manager_wording_variant_is_not_a_conflict() {
+        assert!(!is_resource_version_conflict(409, "field manager \"opspilot\" conflict"));
+    }
+
+    #[test]
+    fn non_409_status_is_never_a_conflict() {
+        assert!(!is_resource_version_conflict(500, "internal error"));
+        assert!(!is_resource_version_conflict(404, "not found"));
+    }
+}
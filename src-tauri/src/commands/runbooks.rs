@@ -0,0 +1,75 @@
+//! Links detected cluster problems to embedded knowledge-base runbooks.
+//!
+//! `models::ClusterIssue` records *what* is wrong; this module answers
+//! *where to look*, embedding the issue's text the same way
+//! `ai_utilities::semantic_search` embeds a free-text query, then reusing
+//! `embeddings::search_documents` to find the closest runbooks.
+
+use serde::Serialize;
+
+use crate::commands::ai_utilities::{fetch_embedding, resolve_embedding_config};
+use crate::embeddings::{load_embeddings, search_documents, SemanticSearchResult};
+use crate::models::{ClusterIssue, UnhealthyReport};
+
+/// Runbook matches below this cosine score are dropped: a low-confidence
+/// guess is worse than no suggestion when the cockpit presents these as
+/// "related runbook" links.
+const MIN_RUNBOOK_SCORE: f32 = 0.5;
+
+/// Number of runbook suggestions attached to each issue.
+const SUGGESTIONS_PER_ISSUE: usize = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterIssueWithRunbooks {
+    pub issue: ClusterIssue,
+    pub suggestions: Vec<SemanticSearchResult>,
+}
+
+/// `ClusterIssue` has no `reason` field of its own (that lives on
+/// `PodIssue`/`DeploymentIssue`), so the query text is built from what it
+/// does carry: the kind of resource and the recorded message.
+fn issue_query_text(issue: &ClusterIssue) -> String {
+    format!("{} {}", issue.resource_kind, issue.message)
+}
+
+/// Embed `issue`'s text and attach the top KB runbooks scoring at or above
+/// `MIN_RUNBOOK_SCORE`. Returns the issue with no suggestions (rather than
+/// an error) when no embedding could be produced, so one unreachable
+/// endpoint doesn't fail the whole report.
+async fn link_issue_to_runbooks(
+    issue: ClusterIssue,
+    data: &crate::embeddings::EmbeddingsData,
+    endpoint: &str,
+    model: &str,
+) -> ClusterIssueWithRunbooks {
+    let suggestions = match fetch_embedding(endpoint, model, &issue_query_text(&issue)).await {
+        Ok(query_embedding) if query_embedding.len() == data.dimension => {
+            search_documents(&query_embedding, data, SUGGESTIONS_PER_ISSUE)
+                .into_iter()
+                .filter(|r| r.score >= MIN_RUNBOOK_SCORE)
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    ClusterIssueWithRunbooks { issue, suggestions }
+}
+
+/// Annotate every issue in `report` with its likely remediation runbooks,
+/// so the cockpit UI can show "related runbook" links next to each
+/// critical/warning item.
+#[tauri::command]
+pub async fn link_unhealthy_report_to_runbooks(
+    report: UnhealthyReport,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<ClusterIssueWithRunbooks>, String> {
+    let data = load_embeddings(&app_handle)?;
+    let (endpoint, model) = resolve_embedding_config().await;
+
+    let mut results = Vec::with_capacity(report.issues.len());
+    for issue in report.issues {
+        results.push(link_issue_to_runbooks(issue, &data, &endpoint, &model).await);
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,91 @@
+/// Tauri-facing view over `session_store`'s persisted descriptors, joined
+/// against whichever of them are actually live in `AppState.port_forwards`
+/// right now. `restore_persisted_sessions` (called once from `lib.rs`'s
+/// `setup`) is what actually reconnects on startup; the commands here are
+/// for the user to inspect/control that afterwards.
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::commands::networking::start_port_forward;
+use crate::session_store::{self, SessionDescriptor};
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionInfo {
+    #[serde(flatten)]
+    pub descriptor: SessionDescriptor,
+    pub connected: bool,
+}
+
+#[tauri::command]
+pub async fn list_sessions(state: State<'_, AppState>) -> Result<Vec<SessionInfo>, String> {
+    let descriptors = session_store::list_sessions()?;
+    let live = state.port_forwards.lock().unwrap();
+    Ok(descriptors
+        .into_iter()
+        .map(|descriptor| {
+            let connected = live.contains_key(&descriptor.id);
+            SessionInfo { descriptor, connected }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn restart_session(app: AppHandle, state: State<'_, AppState>, session_id: String) -> Result<(), String> {
+    let descriptor = session_store::list_sessions()?
+        .into_iter()
+        .find(|d| d.id == session_id)
+        .ok_or_else(|| format!("No persisted session: {}", session_id))?;
+
+    reconnect_descriptor(&app, &state, descriptor).await
+}
+
+#[tauri::command]
+pub async fn set_session_autoreconnect(session_id: String, autoreconnect: bool) -> Result<(), String> {
+    session_store::set_autoreconnect(&session_id, autoreconnect)
+}
+
+async fn reconnect_descriptor(app: &AppHandle, state: &State<'_, AppState>, descriptor: SessionDescriptor) -> Result<(), String> {
+    let _ = app.emit("session:reconnecting", &descriptor.id);
+
+    match start_port_forward(
+        app.clone(),
+        state.clone(),
+        descriptor.namespace.clone(),
+        descriptor.kind.clone(),
+        descriptor.name.clone(),
+        descriptor.local_port,
+        descriptor.pod_port,
+    )
+    .await
+    {
+        Ok(_) => {
+            let _ = app.emit("session:restored", &descriptor.id);
+            Ok(())
+        }
+        Err(e) => {
+            let _ = app.emit("session:failed", serde_json::json!({ "id": descriptor.id, "error": e }));
+            Err(e)
+        }
+    }
+}
+
+/// Reload every persisted descriptor with `autoreconnect` set and try to
+/// re-establish it, so port-forwards survive an app restart rather than
+/// silently vanishing. Called once from `lib.rs`'s `setup`; failures are
+/// logged (and surfaced via `session:failed`) rather than aborting startup.
+pub async fn restore_persisted_sessions(app: AppHandle, state: State<'_, AppState>) {
+    let descriptors = match session_store::list_sessions() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("[session_manager] Failed to load persisted sessions: {}", e);
+            return;
+        }
+    };
+
+    for descriptor in descriptors.into_iter().filter(|d| d.autoreconnect) {
+        if let Err(e) = reconnect_descriptor(&app, &state, descriptor.clone()).await {
+            eprintln!("[session_manager] Failed to restore session {}: {}", descriptor.id, e);
+        }
+    }
+}
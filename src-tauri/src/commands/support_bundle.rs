@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::fs;
-use std::path::Path;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use crate::commands::ai_utilities::LLMConfig;
+use crate::bundle_source::{open_bundle_source, BundleSource};
 
 // ============================================================================
 // Data Types
@@ -17,6 +21,9 @@ pub struct SupportBundle {
     pub has_logs: bool,
     pub has_alerts: bool,
     pub timestamp: Option<String>,
+    // How many indexed resources share their content hash with an earlier
+    // one (e.g. the same ConfigMap copied into several namespaces).
+    pub duplicate_resource_count: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +36,26 @@ pub struct BundleResource {
     pub status_phase: Option<String>,
     pub conditions: Vec<ResourceCondition>,
     pub file_path: String,
+    // Hash of the raw file content, used to spot identical objects
+    // duplicated across namespaces (common for copied ConfigMaps/Secrets).
+    pub content_hash: u64,
+    // Pod-specific: per-container status, used to catch containers that are
+    // crash-looping or OOMKilled while the pod's own phase still says "Running".
+    pub container_statuses: Vec<ContainerStatusInfo>,
+    // Deployment-specific replica counts (status.readyReplicas, status.replicas,
+    // spec.replicas respectively).
+    pub ready_replicas: Option<i32>,
+    pub current_replicas: Option<i32>,
+    pub desired_replicas: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerStatusInfo {
+    pub name: String,
+    pub restart_count: i32,
+    pub waiting_reason: Option<String>,
+    pub last_terminated_reason: Option<String>,
+    pub last_terminated_exit_code: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +95,28 @@ pub struct BundleAlerts {
     pub warning: Vec<BundleAlert>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BundleLogRangeOptions {
+    #[serde(default)]
+    pub offset_bytes: Option<u64>,
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    #[serde(default)]
+    pub tail_lines: Option<usize>,
+    #[serde(default)]
+    pub grep: Option<String>,
+    #[serde(default)]
+    pub since_ts: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleLogRangeResult {
+    pub content: String,
+    pub next_offset: u64,
+    pub total_bytes: u64,
+    pub matched_lines: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BundleLogFile {
     pub namespace: String,
@@ -122,6 +171,19 @@ pub struct BundleNodeInfo {
     pub os_image: Option<String>,
     pub kernel_version: Option<String>,
     pub container_runtime: Option<String>,
+    // Normalized units + derived utilization, computed by summing each
+    // scheduled pod's container resource requests against this node's
+    // allocatable capacity.
+    pub cpu_capacity_cores: f64,
+    pub cpu_allocatable_cores: f64,
+    pub memory_capacity_bytes: i64,
+    pub memory_allocatable_bytes: i64,
+    pub cpu_requested_cores: f64,
+    pub memory_requested_bytes: i64,
+    pub pods_requested: usize,
+    pub cpu_utilization_percent: f64,
+    pub memory_utilization_percent: f64,
+    pub pods_utilization_percent: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -146,23 +208,53 @@ pub struct BundleSearchResult {
 // Index Structure (for fast queries)
 // ============================================================================
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BundleIndex {
     pub resources: Vec<BundleResource>,
     pub events: Vec<BundleEvent>,
+    // Derived lookup tables, rebuilt from `resources` after every load
+    // (including a cache hit) rather than persisted themselves.
+    #[serde(skip)]
     pub by_kind: HashMap<String, Vec<usize>>,
+    #[serde(skip)]
     pub by_namespace: HashMap<String, Vec<usize>>,
+    #[serde(skip)]
     pub by_name: HashMap<String, usize>,
+    #[serde(skip)]
     pub by_status: HashMap<String, Vec<usize>>,
     pub health_summary: Option<BundleHealthSummary>,
+    pub duplicate_resource_count: usize,
+}
+
+/// Populate `by_kind`/`by_namespace`/`by_name`/`by_status` from `resources`.
+/// Used both while indexing a namespace directory and after a cache hit,
+/// since those lookup tables aren't persisted (see `BundleIndex`).
+fn rebuild_secondary_indices(index: &mut BundleIndex) {
+    index.by_kind.clear();
+    index.by_namespace.clear();
+    index.by_name.clear();
+    index.by_status.clear();
+    for (idx, resource) in index.resources.iter().enumerate() {
+        index.by_kind.entry(resource.kind.clone()).or_default().push(idx);
+        if let Some(ref ns) = resource.namespace {
+            index.by_namespace.entry(ns.clone()).or_default().push(idx);
+        }
+        index.by_name.insert(resource.name.clone(), idx);
+        if let Some(ref status) = resource.status_phase {
+            index.by_status.entry(status.clone()).or_default().push(idx);
+        }
+    }
 }
 
 // Global index storage
 use std::sync::{Mutex, OnceLock};
 
-static BUNDLE_INDEX: OnceLock<Mutex<Option<(String, BundleIndex)>>> = OnceLock::new();
+// The `TempDir` is kept alongside the index purely so its directory stays
+// alive (and gets removed on drop) for as long as the bundle is loaded; it's
+// `None` when `path` already pointed at an extracted directory.
+static BUNDLE_INDEX: OnceLock<Mutex<Option<(String, BundleIndex, Option<tempfile::TempDir>)>>> = OnceLock::new();
 
-fn get_bundle_index() -> &'static Mutex<Option<(String, BundleIndex)>> {
+fn get_bundle_index() -> &'static Mutex<Option<(String, BundleIndex, Option<tempfile::TempDir>)>> {
     BUNDLE_INDEX.get_or_init(|| Mutex::new(None))
 }
 
@@ -173,14 +265,25 @@ fn get_bundle_index() -> &'static Mutex<Option<(String, BundleIndex)>> {
 /// Load and index a support bundle
 #[tauri::command]
 pub async fn load_support_bundle(path: String) -> Result<SupportBundle, String> {
-    let bundle_path = Path::new(&path);
+    let input_path = Path::new(&path);
 
-    if !bundle_path.exists() {
+    if !input_path.exists() {
         return Err(format!("Bundle path does not exist: {}", path));
     }
 
+    // Support bundles are usually shipped as `.tar`/`.tar.gz`/`.tgz` archives;
+    // transparently extract those to a managed temp directory and index the
+    // extracted root instead of requiring the user to unpack it first.
+    let (path, temp_dir) = if is_archive_path(input_path) {
+        let (extracted, temp_dir) = extract_bundle_archive(input_path)?;
+        (extracted.to_string_lossy().to_string(), Some(temp_dir))
+    } else {
+        (path, None)
+    };
+    let bundle_path = Path::new(&path);
+
     if !bundle_path.is_dir() {
-        return Err("Bundle path must be a directory".to_string());
+        return Err("Bundle path must be a directory (or a .tar/.tar.gz/.tgz archive)".to_string());
     }
 
     // Discover namespaces (folders that aren't special directories)
@@ -227,10 +330,11 @@ pub async fn load_support_bundle(path: String) -> Result<SupportBundle, String>
 
     // Build the index
     let index = build_bundle_index(&path).await?;
+    let duplicate_resource_count = index.duplicate_resource_count;
 
     // Store in global state
     let mut guard = get_bundle_index().lock().map_err(|e| e.to_string())?;
-    *guard = Some((path.clone(), index));
+    *guard = Some((path.clone(), index, temp_dir));
 
     namespaces.sort();
 
@@ -243,6 +347,7 @@ pub async fn load_support_bundle(path: String) -> Result<SupportBundle, String>
         has_logs,
         has_alerts,
         timestamp,
+        duplicate_resource_count,
     })
 }
 
@@ -518,7 +623,7 @@ pub async fn get_bundle_health_summary(bundle_path: String) -> Result<BundleHeal
     // Check if we have cached health summary
     {
         let guard = get_bundle_index().lock().map_err(|e| e.to_string())?;
-        if let Some((indexed_path, index)) = guard.as_ref() {
+        if let Some((indexed_path, index, _)) = guard.as_ref() {
             if indexed_path == &bundle_path {
                 if let Some(ref summary) = index.health_summary {
                     let result: BundleHealthSummary = summary.clone();
@@ -545,7 +650,7 @@ pub async fn search_bundle(
 
     let guard = get_bundle_index().lock().map_err(|e| e.to_string())?;
 
-    if let Some((indexed_path, index)) = guard.as_ref() {
+    if let Some((indexed_path, index, _)) = guard.as_ref() {
         if indexed_path == &bundle_path {
             for resource in &index.resources {
                 // Apply filters
@@ -608,7 +713,7 @@ pub async fn get_bundle_pods_by_status(
 
     let status_lower = status.to_lowercase();
 
-    if let Some((indexed_path, index)) = guard.as_ref() {
+    if let Some((indexed_path, index, _)) = guard.as_ref() {
         if indexed_path == &bundle_path {
             let pods: Vec<BundleResource> = index.resources.iter()
                 .filter(|r| {
@@ -631,7 +736,7 @@ pub async fn get_all_bundle_resources(
 ) -> Result<HashMap<String, Vec<BundleResource>>, String> {
     let guard = get_bundle_index().lock().map_err(|e| e.to_string())?;
 
-    if let Some((indexed_path, index)) = guard.as_ref() {
+    if let Some((indexed_path, index, _)) = guard.as_ref() {
         if indexed_path == &bundle_path {
             let mut by_namespace: HashMap<String, Vec<BundleResource>> = HashMap::new();
 
@@ -648,7 +753,8 @@ pub async fn get_all_bundle_resources(
     Ok(HashMap::new())
 }
 
-/// Close/unload a bundle
+/// Close/unload a bundle. If it was extracted from an archive, dropping the
+/// stored `TempDir` here removes the extracted files too.
 #[tauri::command]
 pub async fn close_support_bundle() -> Result<(), String> {
     let mut guard = get_bundle_index().lock().map_err(|e| e.to_string())?;
@@ -660,6 +766,159 @@ pub async fn close_support_bundle() -> Result<(), String> {
 // Helper Functions
 // ============================================================================
 
+/// Parses a Kubernetes CPU quantity ("250m", "2", "1.5") into whole cores. A
+/// trailing `m` means millicores; anything else (including scientific
+/// notation like "1e-1") is already in cores.
+fn parse_cpu_quantity_cores(cpu: &str) -> f64 {
+    let cpu = cpu.trim();
+    if cpu.is_empty() {
+        return 0.0;
+    }
+    if let Some(milli) = cpu.strip_suffix('m') {
+        return milli.parse::<f64>().unwrap_or(0.0) / 1000.0;
+    }
+    cpu.parse::<f64>().unwrap_or(0.0)
+}
+
+/// Parses a Kubernetes memory quantity ("16Gi", "512Mi", "2000000", "1e9")
+/// into bytes. A trailing lowercase `i` distinguishes binary suffixes
+/// (Ki/Mi/Gi/Ti/Pi/Ei, base 1024) from decimal ones (k/M/G/T/P/E, base 1000).
+fn parse_memory_quantity_bytes(memory: &str) -> i64 {
+    let memory = memory.trim();
+    if memory.is_empty() {
+        return 0;
+    }
+
+    const BINARY_SUFFIXES: [(&str, f64); 6] = [
+        ("Ki", 1024.0),
+        ("Mi", 1024.0 * 1024.0),
+        ("Gi", 1024.0 * 1024.0 * 1024.0),
+        ("Ti", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("Pi", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("Ei", 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0),
+    ];
+    const DECIMAL_SUFFIXES: [(&str, f64); 6] = [
+        ("k", 1_000.0),
+        ("M", 1_000_000.0),
+        ("G", 1_000_000_000.0),
+        ("T", 1_000_000_000_000.0),
+        ("P", 1_000_000_000_000_000.0),
+        ("E", 1_000_000_000_000_000_000.0),
+    ];
+
+    for (suffix, multiplier) in BINARY_SUFFIXES {
+        if let Some(value) = memory.strip_suffix(suffix) {
+            return (value.parse::<f64>().unwrap_or(0.0) * multiplier) as i64;
+        }
+    }
+    for (suffix, multiplier) in DECIMAL_SUFFIXES {
+        if let Some(value) = memory.strip_suffix(suffix) {
+            return (value.parse::<f64>().unwrap_or(0.0) * multiplier) as i64;
+        }
+    }
+
+    // Bare byte count, possibly in scientific notation.
+    memory.parse::<f64>().unwrap_or(0.0) as i64
+}
+
+/// Sums each pod's container `resources.requests` (CPU in cores, memory in
+/// bytes) by the node it's scheduled to (`spec.nodeName`), so
+/// `get_bundle_nodes` can report per-node utilization without the bundle
+/// ever having shipped that aggregation itself.
+fn sum_pod_requests_by_node(bundle_path: &Path) -> HashMap<String, (f64, i64, usize)> {
+    let mut by_node: HashMap<String, (f64, i64, usize)> = HashMap::new();
+    let skip_dirs = ["alerts", "current-logs", "cluster-scope-resources", "service-metrics", ".DS_Store"];
+
+    let Ok(top_entries) = fs::read_dir(bundle_path) else {
+        return by_node;
+    };
+
+    for ns_entry in top_entries.flatten() {
+        let ns_path = ns_entry.path();
+        let ns_name = ns_entry.file_name().to_string_lossy().to_string();
+        if !ns_path.is_dir() || skip_dirs.contains(&ns_name.as_str()) || ns_name.starts_with('.') {
+            continue;
+        }
+
+        let pods_dir = ns_path.join("pods");
+        let Ok(pod_files) = fs::read_dir(&pods_dir) else {
+            continue;
+        };
+
+        for pod_entry in pod_files.flatten() {
+            let path = pod_entry.path();
+            if !path.extension().map_or(false, |e| e == "yaml" || e == "yml") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(&content) else {
+                continue;
+            };
+            let pod = if yaml.get("object").is_some() { yaml.get("object").unwrap() } else { &yaml };
+
+            let Some(node_name) = pod.get("spec").and_then(|s| s.get("nodeName")).and_then(|n| n.as_str()) else {
+                continue;
+            };
+
+            let mut cpu_cores = 0.0;
+            let mut mem_bytes: i64 = 0;
+            if let Some(containers) = pod.get("spec").and_then(|s| s.get("containers")).and_then(|c| c.as_sequence()) {
+                for container in containers {
+                    let Some(requests) = container.get("resources").and_then(|r| r.get("requests")) else {
+                        continue;
+                    };
+                    if let Some(cpu) = requests.get("cpu").and_then(|v| v.as_str()) {
+                        cpu_cores += parse_cpu_quantity_cores(cpu);
+                    }
+                    if let Some(mem) = requests.get("memory").and_then(|v| v.as_str()) {
+                        mem_bytes += parse_memory_quantity_bytes(mem);
+                    }
+                }
+            }
+
+            let entry = by_node.entry(node_name.to_string()).or_insert((0.0, 0, 0));
+            entry.0 += cpu_cores;
+            entry.1 += mem_bytes;
+            entry.2 += 1;
+        }
+    }
+
+    by_node
+}
+
+fn is_archive_path(path: &Path) -> bool {
+    let name = path.to_string_lossy().to_lowercase();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".tar")
+}
+
+/// Streams a `.tar`/`.tar.gz`/`.tgz` support bundle archive into a fresh temp
+/// directory so the rest of `load_support_bundle` can treat it exactly like
+/// an already-extracted bundle. The `TempDir` must be kept alive (stored in
+/// `BUNDLE_INDEX`) for as long as the bundle stays loaded.
+fn extract_bundle_archive(archive_path: &Path) -> Result<(std::path::PathBuf, tempfile::TempDir), String> {
+    let file = fs::File::open(archive_path)
+        .map_err(|e| format!("Failed to open bundle archive: {}", e))?;
+    let temp_dir = tempfile::tempdir()
+        .map_err(|e| format!("Failed to create temp dir for bundle extraction: {}", e))?;
+
+    let name = archive_path.to_string_lossy().to_lowercase();
+    if name.ends_with(".tar") {
+        tar::Archive::new(file)
+            .unpack(temp_dir.path())
+            .map_err(|e| format!("Failed to extract bundle archive: {}", e))?;
+    } else {
+        let decoder = flate2::read::GzDecoder::new(file);
+        tar::Archive::new(decoder)
+            .unpack(temp_dir.path())
+            .map_err(|e| format!("Failed to extract bundle archive: {}", e))?;
+    }
+
+    let root = temp_dir.path().to_path_buf();
+    Ok((root, temp_dir))
+}
+
 fn get_bundle_timestamp(events_path: &Path) -> Option<String> {
     let content = fs::read_to_string(events_path).ok()?;
     let events: Vec<serde_json::Value> = serde_json::from_str(&content).ok()?;
@@ -677,6 +936,9 @@ fn get_bundle_timestamp(events_path: &Path) -> Option<String> {
 
 fn parse_resource_file(path: &Path, namespace: Option<String>) -> Result<BundleResource, String> {
     let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    let content_hash = hasher.finish();
     let yaml: serde_yaml::Value = serde_yaml::from_str(&content).map_err(|e| e.to_string())?;
 
     // Handle the "object:" wrapper format
@@ -754,6 +1016,48 @@ fn parse_resource_file(path: &Path, namespace: Option<String>) -> Result<BundleR
         }
     }
 
+    let container_statuses = if kind == "Pod" {
+        obj.get("status")
+            .and_then(|s| s.get("containerStatuses"))
+            .and_then(|cs| cs.as_sequence())
+            .map(|seq| {
+                seq.iter()
+                    .map(|c| ContainerStatusInfo {
+                        name: c.get("name").and_then(|n| n.as_str()).unwrap_or("unknown").to_string(),
+                        restart_count: c.get("restartCount").and_then(|r| r.as_i64()).unwrap_or(0) as i32,
+                        waiting_reason: c.get("state")
+                            .and_then(|st| st.get("waiting"))
+                            .and_then(|w| w.get("reason"))
+                            .and_then(|r| r.as_str())
+                            .map(|s| s.to_string()),
+                        last_terminated_reason: c.get("lastState")
+                            .and_then(|ls| ls.get("terminated"))
+                            .and_then(|t| t.get("reason"))
+                            .and_then(|r| r.as_str())
+                            .map(|s| s.to_string()),
+                        last_terminated_exit_code: c.get("lastState")
+                            .and_then(|ls| ls.get("terminated"))
+                            .and_then(|t| t.get("exitCode"))
+                            .and_then(|e| e.as_i64())
+                            .map(|e| e as i32),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let (ready_replicas, current_replicas, desired_replicas) = if kind == "Deployment" {
+        (
+            obj.get("status").and_then(|s| s.get("readyReplicas")).and_then(|v| v.as_i64()).map(|v| v as i32),
+            obj.get("status").and_then(|s| s.get("replicas")).and_then(|v| v.as_i64()).map(|v| v as i32),
+            obj.get("spec").and_then(|s| s.get("replicas")).and_then(|v| v.as_i64()).map(|v| v as i32),
+        )
+    } else {
+        (None, None, None)
+    };
+
     Ok(BundleResource {
         api_version,
         kind,
@@ -763,6 +1067,11 @@ fn parse_resource_file(path: &Path, namespace: Option<String>) -> Result<BundleR
         status_phase,
         conditions,
         file_path: path.to_string_lossy().to_string(),
+        content_hash,
+        container_statuses,
+        ready_replicas,
+        current_replicas,
+        desired_replicas,
     })
 }
 
@@ -809,6 +1118,10 @@ fn parse_alert(alert: &serde_json::Value) -> Option<BundleAlert> {
 }
 
 async fn build_bundle_index(bundle_path: &str) -> Result<BundleIndex, String> {
+    if let Some(cached) = load_cached_bundle_index(bundle_path) {
+        return Ok(cached);
+    }
+
     let base = Path::new(bundle_path);
     let mut index = BundleIndex::default();
 
@@ -844,9 +1157,85 @@ async fn build_bundle_index(bundle_path: &str) -> Result<BundleIndex, String> {
     // Compute health summary
     index.health_summary = Some(compute_health_from_index(&index));
 
+    // Count resources whose content is byte-for-byte identical to an
+    // earlier one (e.g. a ConfigMap/Secret copied into several namespaces).
+    let mut hash_counts: HashMap<u64, usize> = HashMap::new();
+    for resource in &index.resources {
+        *hash_counts.entry(resource.content_hash).or_insert(0) += 1;
+    }
+    index.duplicate_resource_count = hash_counts.values().filter(|&&c| c > 1).map(|&c| c - 1).sum();
+
+    save_cached_bundle_index(bundle_path, &index);
+
     Ok(index)
 }
 
+// ============================================================================
+// Persistent Bundle Index Cache
+// ============================================================================
+//
+// `build_bundle_index` does one recursive walk+parse of the bundle on first
+// open; this sidecar cache lets a later `load_support_bundle` call for the
+// same bundle (same session or a fresh app start) skip that walk entirely as
+// long as the bundle's top-level directory hasn't been modified since.
+
+#[derive(Deserialize)]
+struct CachedBundleIndex {
+    bundle_path: String,
+    top_level_mtime_secs: u64,
+    index: BundleIndex,
+}
+
+fn bundle_index_cache_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".opspilot")
+        .join("bundle-index-cache")
+}
+
+fn bundle_index_cache_path(bundle_path: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    bundle_path.hash(&mut hasher);
+    bundle_index_cache_dir().join(format!("{:016x}.json", hasher.finish()))
+}
+
+fn bundle_top_level_mtime_secs(bundle_path: &str) -> Option<u64> {
+    fs::metadata(bundle_path).ok()?
+        .modified().ok()?
+        .duration_since(std::time::UNIX_EPOCH).ok()
+        .map(|d| d.as_secs())
+}
+
+fn load_cached_bundle_index(bundle_path: &str) -> Option<BundleIndex> {
+    let current_mtime = bundle_top_level_mtime_secs(bundle_path)?;
+    let content = fs::read_to_string(bundle_index_cache_path(bundle_path)).ok()?;
+    let cached: CachedBundleIndex = serde_json::from_str(&content).ok()?;
+
+    if cached.bundle_path != bundle_path || cached.top_level_mtime_secs != current_mtime {
+        return None;
+    }
+
+    let mut index = cached.index;
+    rebuild_secondary_indices(&mut index);
+    Some(index)
+}
+
+fn save_cached_bundle_index(bundle_path: &str, index: &BundleIndex) {
+    let Some(mtime) = bundle_top_level_mtime_secs(bundle_path) else { return };
+    let cache_dir = bundle_index_cache_dir();
+    if fs::create_dir_all(&cache_dir).is_err() {
+        return;
+    }
+    let payload = serde_json::json!({
+        "bundle_path": bundle_path,
+        "top_level_mtime_secs": mtime,
+        "index": index,
+    });
+    if let Ok(json) = serde_json::to_string(&payload) {
+        let _ = fs::write(bundle_index_cache_path(bundle_path), json);
+    }
+}
+
 fn index_namespace(ns_path: &Path, namespace: Option<String>, index: &mut BundleIndex) -> Result<(), String> {
     for type_entry in fs::read_dir(ns_path).map_err(|e| e.to_string())? {
         let type_entry = type_entry.map_err(|e| e.to_string())?;
@@ -898,19 +1287,36 @@ fn compute_health_from_index(index: &BundleIndex) -> BundleHealthSummary {
         match resource.kind.as_str() {
             "Pod" => {
                 let status = resource.status_phase.as_deref().unwrap_or("");
+                let restart_count: i32 = resource.container_statuses.iter().map(|c| c.restart_count).sum();
+
+                // A container crash-looping or OOMKilled can leave the pod's own
+                // phase at "Running" - catch that even when `status_phase` looks healthy.
+                let container_issue = resource.container_statuses.iter().find_map(|c| {
+                    c.waiting_reason.as_deref()
+                        .filter(|r| matches!(*r, "CrashLoopBackOff" | "ImagePullBackOff" | "ErrImagePull" | "CreateContainerConfigError"))
+                        .or_else(|| c.last_terminated_reason.as_deref().filter(|r| matches!(*r, "OOMKilled" | "Error")))
+                        .map(|r| (r.to_string(), c.last_terminated_exit_code))
+                });
+
                 let is_unhealthy = matches!(status,
                     "CrashLoopBackOff" | "ImagePullBackOff" | "ErrImagePull" |
                     "Error" | "Failed" | "OOMKilled" | "Pending"
-                );
+                ) || container_issue.is_some();
 
                 if is_unhealthy {
-                    // Try to get restart count from conditions or status
+                    let reason = container_issue
+                        .map(|(reason, exit_code)| match exit_code {
+                            Some(code) => format!("{} (exit code {})", reason, code),
+                            None => reason,
+                        })
+                        .or_else(|| resource.conditions.first().and_then(|c| c.reason.clone()));
+
                     failing_pods.push(PodHealthInfo {
                         name: resource.name.clone(),
                         namespace: resource.namespace.clone().unwrap_or_default(),
-                        status: status.to_string(),
-                        restart_count: 0, // Would need deeper parsing
-                        reason: resource.conditions.first().and_then(|c| c.reason.clone()),
+                        status: if status.is_empty() { "Unknown".to_string() } else { status.to_string() },
+                        restart_count,
+                        reason,
                     });
                 }
             }
@@ -925,8 +1331,8 @@ fn compute_health_from_index(index: &BundleIndex) -> BundleHealthSummary {
                     unhealthy_deployments.push(DeploymentHealthInfo {
                         name: resource.name.clone(),
                         namespace: resource.namespace.clone().unwrap_or_default(),
-                        ready_replicas: 0,
-                        desired_replicas: 0,
+                        ready_replicas: resource.ready_replicas.unwrap_or(0),
+                        desired_replicas: resource.desired_replicas.or(resource.current_replicas).unwrap_or(0),
                     });
                 }
             }
@@ -961,7 +1367,7 @@ async fn compute_health_summary(bundle_path: &str) -> Result<BundleHealthSummary
     let _bundle = load_support_bundle(bundle_path.to_string()).await?;
 
     let guard = get_bundle_index().lock().map_err(|e| e.to_string())?;
-    if let Some((_, index)) = guard.as_ref() {
+    if let Some((_, index, _)) = guard.as_ref() {
         if let Some(ref summary) = index.health_summary {
             let result: BundleHealthSummary = summary.clone();
             return Ok(result);
@@ -987,6 +1393,7 @@ pub async fn get_bundle_nodes(bundle_path: String) -> Result<Vec<BundleNodeInfo>
     }
 
     let mut nodes = Vec::new();
+    let pod_requests = sum_pod_requests_by_node(Path::new(&bundle_path));
 
     if let Ok(entries) = fs::read_dir(&nodes_dir) {
         for entry in entries.flatten() {
@@ -1051,23 +1458,24 @@ pub async fn get_bundle_nodes(bundle_path: String) -> Result<Vec<BundleNodeInfo>
                                 .unwrap_or("0")
                                 .to_string();
 
-                            // Get allocatable
+                            // Get allocatable, falling back to capacity when the
+                            // bundle's node YAML doesn't carry an allocatable section.
                             let allocatable = status_val.and_then(|s| s.get("allocatable"));
                             let cpu_allocatable = allocatable
                                 .and_then(|a| a.get("cpu"))
                                 .and_then(|v| v.as_str())
-                                .unwrap_or("0")
-                                .to_string();
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| cpu_capacity.clone());
                             let memory_allocatable = allocatable
                                 .and_then(|a| a.get("memory"))
                                 .and_then(|v| v.as_str())
-                                .unwrap_or("0")
-                                .to_string();
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| memory_capacity.clone());
                             let pods_allocatable = allocatable
                                 .and_then(|a| a.get("pods"))
                                 .and_then(|v| v.as_str())
-                                .unwrap_or("0")
-                                .to_string();
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| pods_capacity.clone());
 
                             // Get conditions
                             let conditions: Vec<NodeCondition> = status_val
@@ -1132,6 +1540,17 @@ pub async fn get_bundle_nodes(bundle_path: String) -> Result<Vec<BundleNodeInfo>
                                 .and_then(|v| v.as_str())
                                 .map(|s| s.to_string());
 
+                            let cpu_capacity_cores = parse_cpu_quantity_cores(&cpu_capacity);
+                            let cpu_allocatable_cores = parse_cpu_quantity_cores(&cpu_allocatable);
+                            let memory_capacity_bytes = parse_memory_quantity_bytes(&memory_capacity);
+                            let memory_allocatable_bytes = parse_memory_quantity_bytes(&memory_allocatable);
+                            let pods_allocatable_count = pods_allocatable.parse::<i64>().unwrap_or(0);
+
+                            let (cpu_requested_cores, memory_requested_bytes, pods_requested) =
+                                pod_requests.get(&name).copied().unwrap_or((0.0, 0, 0));
+
+                            let percent_of = |used: f64, total: f64| if total > 0.0 { (used / total) * 100.0 } else { 0.0 };
+
                             nodes.push(BundleNodeInfo {
                                 name,
                                 status,
@@ -1150,6 +1569,16 @@ pub async fn get_bundle_nodes(bundle_path: String) -> Result<Vec<BundleNodeInfo>
                                 os_image,
                                 kernel_version,
                                 container_runtime,
+                                cpu_capacity_cores,
+                                cpu_allocatable_cores,
+                                memory_capacity_bytes,
+                                memory_allocatable_bytes,
+                                cpu_requested_cores,
+                                memory_requested_bytes,
+                                pods_requested,
+                                cpu_utilization_percent: percent_of(cpu_requested_cores, cpu_allocatable_cores),
+                                memory_utilization_percent: percent_of(memory_requested_bytes as f64, memory_allocatable_bytes as f64),
+                                pods_utilization_percent: percent_of(pods_requested as f64, pods_allocatable_count as f64),
                             });
                         }
                     }
@@ -1161,6 +1590,51 @@ pub async fn get_bundle_nodes(bundle_path: String) -> Result<Vec<BundleNodeInfo>
     Ok(nodes)
 }
 
+/// Whether a persisted `LLMConfig` should route bundle analysis through the
+/// direct-HTTP `ai_local::call_llm` dispatcher instead of the Claude CLI
+/// subprocess. Ollama needs no API key, so any config pointing at it is
+/// honored as-is; every other provider falls back to the CLI unless an API
+/// key is present, and "claude-code" always means "use the CLI".
+fn should_use_configured_llm(llm_config: &Option<LLMConfig>) -> bool {
+    match llm_config {
+        None => false,
+        Some(config) => match config.provider.as_str() {
+            "claude-code" => false,
+            "ollama" => true,
+            _ => config.api_key.is_some(),
+        },
+    }
+}
+
+/// Adapt the persisted bundle-analysis `LLMConfig` (plain-string provider,
+/// used for on-disk/keyring persistence) to `ai_local::LLMConfig` (enum
+/// provider, used by the `call_llm` dispatcher) so the two config shapes
+/// don't need to be unified.
+fn to_ai_local_config(config: &LLMConfig) -> crate::ai_local::LLMConfig {
+    let provider = match config.provider.as_str() {
+        "openai" => crate::ai_local::LLMProvider::OpenAI,
+        "anthropic" => crate::ai_local::LLMProvider::Anthropic,
+        "custom" => crate::ai_local::LLMProvider::Custom,
+        "groq" => crate::ai_local::LLMProvider::Groq,
+        "replicate" => crate::ai_local::LLMProvider::Replicate,
+        "claude-code" => crate::ai_local::LLMProvider::ClaudeCode,
+        _ => crate::ai_local::LLMProvider::Ollama,
+    };
+    crate::ai_local::LLMConfig {
+        provider,
+        api_key: config.api_key.clone(),
+        base_url: config.base_url.clone(),
+        model: config.model.clone(),
+        executor_model: config.executor_model.clone(),
+        embedding_model: None,
+        embedding_endpoint: None,
+        temperature: config.temperature,
+        max_tokens: if config.max_tokens > 0 { config.max_tokens as u32 } else { 8192 },
+        num_ctx: None,
+        options: None,
+    }
+}
+
 /// Find Claude CLI binary path
 fn find_claude_binary() -> Option<String> {
     use std::process::Command;
@@ -1205,22 +1679,22 @@ fn find_claude_binary() -> Option<String> {
     None
 }
 
-/// AI-powered bundle analysis using Claude CLI (uses your Claude subscription)
-/// Parameters match frontend: bundlePath, query, context
+/// AI-powered bundle analysis. Routes through whichever LLM backend
+/// `llm_config` resolves to (see `should_use_configured_llm`), falling back
+/// to the local Claude CLI (uses your Claude subscription) when no config is
+/// supplied or the configured provider needs an API key that isn't set.
+/// Parameters match frontend: bundlePath, query, context, llmConfig
 #[tauri::command]
 pub async fn ai_analyze_bundle(
     bundle_path: String,
     query: String,
-    context: String
+    context: String,
+    llm_config: Option<LLMConfig>,
 ) -> Result<String, String> {
     use std::process::Stdio;
     use tokio::process::Command;
     use tokio::io::{AsyncBufReadExt, BufReader};
 
-    // Find Claude CLI
-    let claude_bin = find_claude_binary()
-        .ok_or_else(|| "Claude CLI not found. Please install it with: npm install -g @anthropic-ai/claude-code".to_string())?;
-
     // Build the system prompt for bundle analysis
     let system_prompt = format!(r#"You are an expert Kubernetes SRE assistant analyzing a support bundle.
 Your role is to help identify issues, explain problems, and provide actionable recommendations.
@@ -1236,6 +1710,20 @@ Provide specific kubectl commands when helpful.
 
 Bundle path: {}"#, bundle_path);
 
+    if should_use_configured_llm(&llm_config) {
+        let config = to_ai_local_config(llm_config.as_ref().unwrap());
+        let user_prompt = if context.is_empty() {
+            query
+        } else {
+            format!("{}\n\n---\nBundle Context:\n{}", query, context)
+        };
+        return crate::ai_local::call_llm(config, user_prompt, Some(system_prompt), vec![]).await;
+    }
+
+    // Find Claude CLI
+    let claude_bin = find_claude_binary()
+        .ok_or_else(|| "Claude CLI not found. Please install it with: npm install -g @anthropic-ai/claude-code".to_string())?;
+
     let full_prompt = format!("{}\n\n---\n\nUser question: {}", system_prompt, query);
 
     // Add bundle context if provided
@@ -1321,67 +1809,227 @@ Bundle path: {}"#, bundle_path);
     Ok(response_text)
 }
 
+/// Streaming variant of `ai_analyze_bundle`: spawns the Claude CLI the same
+/// way, but emits each decoded text delta to the frontend as
+/// `ai_analysis_delta:{stream_id}` as soon as it arrives instead of buffering
+/// the whole response, plus `ai_analysis_tool_use:{stream_id}` /
+/// `ai_analysis_thinking:{stream_id}` for non-text blocks and a terminal
+/// `ai_analysis_done:{stream_id}` / `ai_analysis_error:{stream_id}` event.
+/// Mirrors the fire-and-forget `start_log_stream` pattern: this command
+/// returns as soon as the CLI process is spawned, and the caller listens on
+/// the above events for the rest. When `llm_config` resolves to a non-CLI
+/// provider (see `should_use_configured_llm`), the request is instead sent
+/// once via `ai_local::call_llm` and the whole answer is delivered as a
+/// single delta followed immediately by the done event, since that
+/// dispatcher doesn't support incremental streaming.
+#[tauri::command]
+pub async fn ai_analyze_bundle_stream(
+    bundle_path: String,
+    query: String,
+    context: String,
+    stream_id: String,
+    llm_config: Option<LLMConfig>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    use tauri::Emitter;
+    use std::process::Stdio;
+    use tokio::process::Command;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let system_prompt = format!(r#"You are an expert Kubernetes SRE assistant analyzing a support bundle.
+Your role is to help identify issues, explain problems, and provide actionable recommendations.
+Be concise and practical. Focus on the most critical issues first.
+When analyzing the bundle data, look for patterns such as:
+- Pods in CrashLoopBackOff, ImagePullBackOff, or Error states
+- Pending pods that may indicate resource constraints
+- Warning events that suggest configuration issues
+- Critical alerts that need immediate attention
+- Node health issues
+- Resource pressure (memory, CPU, disk)
+Provide specific kubectl commands when helpful.
+
+Bundle path: {}"#, bundle_path);
+
+    if should_use_configured_llm(&llm_config) {
+        let config = to_ai_local_config(llm_config.as_ref().unwrap());
+        let user_prompt = if context.is_empty() {
+            query
+        } else {
+            format!("{}\n\n---\nBundle Context:\n{}", query, context)
+        };
+        tokio::spawn(async move {
+            match crate::ai_local::call_llm(config, user_prompt, Some(system_prompt), vec![]).await {
+                Ok(text) => {
+                    let _ = app_handle.emit(&format!("ai_analysis_delta:{}", stream_id), &text);
+                    let _ = app_handle.emit(&format!("ai_analysis_done:{}", stream_id), text);
+                }
+                Err(e) => {
+                    let _ = app_handle.emit(&format!("ai_analysis_error:{}", stream_id), e);
+                }
+            }
+        });
+        return Ok(());
+    }
+
+    let claude_bin = find_claude_binary()
+        .ok_or_else(|| "Claude CLI not found. Please install it with: npm install -g @anthropic-ai/claude-code".to_string())?;
+
+    let full_prompt = format!("{}\n\n---\n\nUser question: {}", system_prompt, query);
+    let final_prompt = if !context.is_empty() {
+        format!("{}\n\n---\nBundle Context:\n{}", full_prompt, context)
+    } else {
+        full_prompt
+    };
+
+    let mut cmd = Command::new(&claude_bin);
+    cmd.arg("-p")
+        .arg("--output-format")
+        .arg("stream-json")
+        .arg("--verbose")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn()
+        .map_err(|e| format!("Failed to start Claude CLI: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        use tokio::io::AsyncWriteExt;
+        stdin.write_all(final_prompt.as_bytes()).await
+            .map_err(|e| format!("Failed to write to Claude CLI: {}", e))?;
+        stdin.shutdown().await
+            .map_err(|e| format!("Failed to close stdin: {}", e))?;
+    }
+
+    let stdout = child.stdout.take()
+        .ok_or_else(|| "Failed to capture stdout".to_string())?;
+
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(stdout).lines();
+        let mut response_text = String::new();
+
+        loop {
+            let line = match reader.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = app_handle.emit(&format!("ai_analysis_error:{}", stream_id), format!("Failed to read output: {}", e));
+                    return;
+                }
+            };
+
+            let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) else { continue };
+
+            match json.get("type").and_then(|t| t.as_str()) {
+                Some("assistant") => {
+                    if let Some(content) = json.get("message").and_then(|m| m.get("content")).and_then(|c| c.as_array()) {
+                        for block in content {
+                            match block.get("type").and_then(|t| t.as_str()) {
+                                Some("text") => {
+                                    if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                                        response_text.push_str(text);
+                                        let _ = app_handle.emit(&format!("ai_analysis_delta:{}", stream_id), text);
+                                    }
+                                }
+                                Some("thinking") => {
+                                    if let Some(text) = block.get("thinking").and_then(|t| t.as_str()) {
+                                        let _ = app_handle.emit(&format!("ai_analysis_thinking:{}", stream_id), text);
+                                    }
+                                }
+                                Some("tool_use") => {
+                                    let _ = app_handle.emit(&format!("ai_analysis_tool_use:{}", stream_id), block.clone());
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                Some("content_block_delta") => {
+                    if let Some(delta) = json.get("delta") {
+                        match delta.get("type").and_then(|t| t.as_str()) {
+                            Some("thinking_delta") => {
+                                if let Some(text) = delta.get("thinking").and_then(|t| t.as_str()) {
+                                    let _ = app_handle.emit(&format!("ai_analysis_thinking:{}", stream_id), text);
+                                }
+                            }
+                            _ => {
+                                if let Some(text) = delta.get("text").and_then(|t| t.as_str()) {
+                                    response_text.push_str(text);
+                                    let _ = app_handle.emit(&format!("ai_analysis_delta:{}", stream_id), text);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let status = child.wait().await;
+        match status {
+            Ok(s) if !s.success() && response_text.is_empty() => {
+                let _ = app_handle.emit(&format!("ai_analysis_error:{}", stream_id), "Claude CLI failed. Make sure you're logged in with 'claude login'.".to_string());
+            }
+            _ if response_text.is_empty() => {
+                let _ = app_handle.emit(&format!("ai_analysis_error:{}", stream_id), "No response from Claude CLI. Please try again.".to_string());
+            }
+            _ => {
+                let _ = app_handle.emit(&format!("ai_analysis_done:{}", stream_id), response_text);
+            }
+        }
+    });
+
+    Ok(())
+}
+
 /// List all log files in the bundle
 #[tauri::command]
 pub async fn list_bundle_logs(bundle_path: String) -> Result<Vec<BundleLogFile>, String> {
-    let logs_base = Path::new(&bundle_path).join("current-logs");
+    let source = open_bundle_source(&bundle_path)?;
 
-    if !logs_base.exists() {
+    let Ok(ns_entries) = source.read_dir("current-logs") else {
         return Ok(vec![]);
-    }
+    };
 
     let mut log_files = Vec::new();
 
     // Walk through namespace directories
-    for ns_entry in fs::read_dir(&logs_base).map_err(|e| e.to_string())? {
-        let ns_entry = ns_entry.map_err(|e| e.to_string())?;
-        let ns_path = ns_entry.path();
-
-        if !ns_path.is_dir() {
-            continue;
-        }
-
-        let namespace = ns_entry.file_name().to_string_lossy().to_string();
-        if namespace.starts_with('.') {
+    for ns_entry in ns_entries {
+        if !ns_entry.is_dir || ns_entry.name.starts_with('.') {
             continue;
         }
+        let namespace = ns_entry.name;
+        let ns_rel = format!("current-logs/{}", namespace);
 
         // Walk through pod directories
-        for pod_entry in fs::read_dir(&ns_path).map_err(|e| e.to_string())? {
-            let pod_entry = pod_entry.map_err(|e| e.to_string())?;
-            let pod_path = pod_entry.path();
-
-            if !pod_path.is_dir() {
-                continue;
-            }
-
-            let pod = pod_entry.file_name().to_string_lossy().to_string();
-            if pod.starts_with('.') {
+        let Ok(pod_entries) = source.read_dir(&ns_rel) else { continue };
+        for pod_entry in pod_entries {
+            if !pod_entry.is_dir || pod_entry.name.starts_with('.') {
                 continue;
             }
+            let pod = pod_entry.name;
+            let pod_rel = format!("{}/{}", ns_rel, pod);
 
             // Find log files
-            if let Ok(log_entries) = fs::read_dir(&pod_path) {
-                for log_entry in log_entries.flatten() {
-                    let log_path = log_entry.path();
-                    if log_path.extension().map(|e| e == "log").unwrap_or(false) {
-                        let container = log_path.file_stem()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .to_string();
-                        let size_bytes = fs::metadata(&log_path)
-                            .map(|m| m.len())
-                            .unwrap_or(0);
-
-                        log_files.push(BundleLogFile {
-                            namespace: namespace.clone(),
-                            pod: pod.clone(),
-                            container,
-                            file_path: log_path.to_string_lossy().to_string(),
-                            size_bytes,
-                        });
-                    }
+            let Ok(log_entries) = source.read_dir(&pod_rel) else { continue };
+            for log_entry in log_entries {
+                if log_entry.is_dir || !log_entry.name.ends_with(".log") {
+                    continue;
                 }
+                let container = log_entry.name.trim_end_matches(".log").to_string();
+                let file_path = Path::new(&bundle_path)
+                    .join("current-logs")
+                    .join(&namespace)
+                    .join(&pod)
+                    .join(&log_entry.name);
+
+                log_files.push(BundleLogFile {
+                    namespace: namespace.clone(),
+                    pod: pod.clone(),
+                    container,
+                    file_path: file_path.to_string_lossy().to_string(),
+                    size_bytes: log_entry.size,
+                });
             }
         }
     }
@@ -1394,24 +2042,179 @@ pub async fn list_bundle_logs(bundle_path: String) -> Result<Vec<BundleLogFile>,
     Ok(log_files)
 }
 
-/// Read a log file by path
+// ============================================================================
+// Path Safety
+// ============================================================================
+
+/// Confirm `target` is really inside `base` before any command reads it.
+///
+/// A plain `target.starts_with(base)` (the old check here) compares raw,
+/// un-normalized paths, so a `target` built from a `file_path` containing
+/// `..` segments - or a symlink inside the bundle pointing elsewhere on
+/// disk - can pass that check while resolving outside the bundle. This
+/// canonicalizes both sides and requires the canonical target to still be a
+/// descendant of the canonical base, which resolves `..` segments and
+/// follows (and so also catches) symlinks in one step.
+///
+/// `base` may be an extracted directory or a `.tar`/`.tar.gz`/`.tgz` archive
+/// file; in the archive case `target` is a virtual path that only exists
+/// inside the archive, so there's nothing on disk to canonicalize past the
+/// archive file itself - instead the relative portion is checked lexically
+/// for `..`/root components.
+pub fn validate_within_bundle(base: &str, target: &str) -> Result<PathBuf, String> {
+    let base_path = Path::new(base);
+    let target_path = Path::new(target);
+
+    if !target_path.starts_with(base_path) {
+        return Err("Invalid path: outside bundle".to_string());
+    }
+
+    let canonical_base = fs::canonicalize(base_path)
+        .map_err(|e| format!("Failed to resolve bundle path: {}", e))?;
+
+    if canonical_base.is_file() {
+        let rel = target_path.strip_prefix(base_path)
+            .map_err(|_| "Invalid path: outside bundle".to_string())?;
+        for component in rel.components() {
+            match component {
+                std::path::Component::ParentDir | std::path::Component::RootDir | std::path::Component::Prefix(_) => {
+                    return Err("Invalid path: escapes bundle".to_string());
+                }
+                _ => {}
+            }
+        }
+        return Ok(target_path.to_path_buf());
+    }
+
+    let canonical_target = fs::canonicalize(target_path)
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+
+    if !canonical_target.starts_with(&canonical_base) {
+        return Err("Invalid path: escapes bundle".to_string());
+    }
+
+    Ok(canonical_target)
+}
+
+/// Read a log file by path. Works whether `bundle_path` is an extracted
+/// directory or a tar archive - `log_path` is still expected to be the
+/// bundle-prefixed path returned by `list_bundle_logs`/`get_bundle_logs`,
+/// and is made relative to `bundle_path` before going through `BundleSource`.
 #[tauri::command]
 pub async fn read_bundle_log(bundle_path: String, log_path: String) -> Result<String, String> {
-    // Validate the log path is within the bundle
     let bundle_base = Path::new(&bundle_path);
     let log_file = Path::new(&log_path);
 
-    // Read the file
-    if !log_file.exists() {
-        return Err(format!("Log file not found: {}", log_path));
+    validate_within_bundle(&bundle_path, &log_path).map_err(|_| "Invalid log path".to_string())?;
+
+    let rel_path = log_file.strip_prefix(bundle_base)
+        .map_err(|_| "Invalid log path".to_string())?
+        .to_string_lossy()
+        .to_string();
+
+    open_bundle_source(&bundle_path)?.read_to_string(&rel_path)
+}
+
+/// Paged/tailing/filtered reads over a (possibly huge) bundle log, so the
+/// frontend never has to pull the whole file the way `read_bundle_log` does.
+/// `tail_lines` and the `offset_bytes`/`max_bytes` range are mutually
+/// exclusive ways of choosing which part of the file to return; `grep` and
+/// `since_ts` then filter the resulting lines server-side.
+#[tauri::command]
+pub async fn read_bundle_log_range(
+    bundle_path: String,
+    log_path: String,
+    opts: BundleLogRangeOptions,
+) -> Result<BundleLogRangeResult, String> {
+    const DEFAULT_MAX_BYTES: u64 = 1024 * 1024;
+
+    let bundle_base = Path::new(&bundle_path);
+    let log_file = Path::new(&log_path);
+
+    validate_within_bundle(&bundle_path, &log_path).map_err(|_| "Invalid log path".to_string())?;
+    let rel_path = log_file.strip_prefix(bundle_base)
+        .map_err(|_| "Invalid log path".to_string())?
+        .to_string_lossy()
+        .to_string();
+
+    let source = open_bundle_source(&bundle_path)?;
+    let total_bytes = source.metadata(&rel_path)?.size;
+
+    let (bytes, next_offset) = if let Some(lines) = opts.tail_lines {
+        tail_log_bytes(source.as_ref(), &rel_path, total_bytes, lines)?
+    } else {
+        let offset = opts.offset_bytes.unwrap_or(0).min(total_bytes);
+        let max_bytes = opts.max_bytes.unwrap_or(DEFAULT_MAX_BYTES);
+        let bytes = source.read_range(&rel_path, offset, max_bytes)?;
+        let next_offset = offset + bytes.len() as u64;
+        (bytes, next_offset)
+    };
+
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    let mut lines: Vec<&str> = text.lines().collect();
+
+    // Container logs are timestamp-prefixed (e.g. "2024-01-01T12:00:00Z
+    // stdout F ..."), so an RFC3339 `since_ts` can be compared lexically.
+    if let Some(since) = opts.since_ts.as_deref() {
+        lines.retain(|line| line.split_whitespace().next().map(|ts| ts >= since).unwrap_or(true));
     }
 
-    // Safety check - ensure path is within bundle
-    if !log_file.starts_with(bundle_base) {
-        return Err("Invalid log path".to_string());
+    if let Some(pattern) = opts.grep.as_deref() {
+        if let Ok(re) = regex::Regex::new(pattern) {
+            lines.retain(|line| re.is_match(line));
+        } else {
+            // Not a valid regex - fall back to a plain substring match.
+            lines.retain(|line| line.contains(pattern));
+        }
     }
 
-    fs::read_to_string(log_file).map_err(|e| format!("Failed to read log: {}", e))
+    let matched_lines = lines.len();
+    let content = lines.join("\n");
+
+    Ok(BundleLogRangeResult { content, next_offset, total_bytes, matched_lines })
+}
+
+/// Seek from the end of the log and scan backward for newline boundaries
+/// until at least `lines` lines have been collected, reading in 64KiB chunks
+/// so multi-hundred-MB logs don't need a full read just to find the tail.
+fn tail_log_bytes(
+    source: &dyn BundleSource,
+    rel_path: &str,
+    total_bytes: u64,
+    lines: usize,
+) -> Result<(Vec<u8>, u64), String> {
+    const CHUNK: u64 = 64 * 1024;
+
+    let mut collected: Vec<u8> = Vec::new();
+    let mut pos = total_bytes;
+    let mut newline_count = 0usize;
+
+    while pos > 0 && newline_count <= lines {
+        let chunk_len = CHUNK.min(pos);
+        pos -= chunk_len;
+        let chunk = source.read_range(rel_path, pos, chunk_len)?;
+        newline_count += chunk.iter().filter(|&&b| b == b'\n').count();
+        let mut combined = chunk;
+        combined.extend_from_slice(&collected);
+        collected = combined;
+    }
+
+    // Trim down to exactly the last `lines` lines (the loop above may have
+    // pulled in extra leading lines just to make sure enough were present).
+    let mut start_idx = 0;
+    let mut seen = 0;
+    for (i, &b) in collected.iter().enumerate().rev() {
+        if b == b'\n' {
+            seen += 1;
+            if seen == lines {
+                start_idx = i + 1;
+                break;
+            }
+        }
+    }
+
+    let next_offset = pos + start_idx as u64;
+    Ok((collected[start_idx..].to_vec(), next_offset))
 }
 
 /// Get ArgoCD applications from the bundle
@@ -1648,20 +2451,257 @@ pub async fn get_bundle_service_metrics(bundle_path: String) -> Result<HashMap<S
     Ok(metrics)
 }
 
-/// Read raw YAML content of a resource file
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricSample {
+    pub name: String,
+    pub labels: HashMap<String, String>,
+    pub value: f64,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceMetrics {
+    pub service: String,
+    pub samples: Vec<MetricSample>,
+}
+
+/// Same service-metrics XML files as `get_bundle_service_metrics`, but parsed
+/// into typed samples instead of handed to the frontend as raw blobs.
+#[tauri::command]
+pub async fn get_bundle_service_metrics_parsed(bundle_path: String) -> Result<Vec<ServiceMetrics>, String> {
+    let metrics_dir = Path::new(&bundle_path).join("service-metrics");
+
+    if !metrics_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+
+    for entry in fs::read_dir(&metrics_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+
+        if !path.extension().map(|e| e == "xml").unwrap_or(false) {
+            continue;
+        }
+
+        let service = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("[service-metrics] Failed to read {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        match parse_service_metrics_xml(&content) {
+            Ok(samples) => results.push(ServiceMetrics { service, samples }),
+            Err(e) => {
+                eprintln!("[service-metrics] Skipping malformed {}: {}", path.display(), e);
+                continue;
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Parse a service-metrics XML file into samples. Supports both layouts seen
+/// in these bundles: explicit `<metric name=".." value=".."><label .../></metric>`
+/// elements, and a Prometheus-exposition-format text body (e.g. wrapped in a
+/// `<metrics><![CDATA[...]]></metrics>` root) embedded as the element text.
+fn parse_service_metrics_xml(content: &str) -> Result<Vec<MetricSample>, String> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(true);
+
+    let mut samples = Vec::new();
+    let mut buf = Vec::new();
+    let mut doc_timestamp: Option<String> = None;
+    let mut in_metric = false;
+    let mut cur_name = String::new();
+    let mut cur_value: Option<f64> = None;
+    let mut cur_timestamp: Option<String> = None;
+    let mut cur_labels: HashMap<String, String> = HashMap::new();
+
+    loop {
+        let event = reader.read_event_into(&mut buf).map_err(|e| e.to_string())?;
+        let is_empty = matches!(event, Event::Empty(_));
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let tag = std::str::from_utf8(e.name().as_ref()).unwrap_or("").to_string();
+                let mut attrs: HashMap<String, String> = HashMap::new();
+                for attr in e.attributes().flatten() {
+                    let key = std::str::from_utf8(attr.key.as_ref()).unwrap_or("").to_string();
+                    let value = attr.decode_and_unescape_value(&reader).unwrap_or_default().to_string();
+                    attrs.insert(key, value);
+                }
+
+                match tag.as_str() {
+                    "metrics" | "root" => {
+                        if let Some(ts) = attrs.get("timestamp") {
+                            doc_timestamp = Some(normalize_metric_timestamp(ts));
+                        }
+                    }
+                    "metric" => {
+                        in_metric = true;
+                        cur_name = attrs.get("name").cloned().unwrap_or_default();
+                        cur_value = attrs.get("value").and_then(|v| v.parse::<f64>().ok());
+                        cur_timestamp = attrs.get("timestamp").map(|t| normalize_metric_timestamp(t));
+                        cur_labels = HashMap::new();
+                    }
+                    "label" => {
+                        if in_metric {
+                            if let (Some(name), Some(value)) = (attrs.get("name"), attrs.get("value")) {
+                                cur_labels.insert(name.clone(), value.clone());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+
+                // Self-closing <metric .../> never gets a matching End event,
+                // so emit it right away instead of waiting for one.
+                if is_empty && tag == "metric" {
+                    if let Some(value) = cur_value {
+                        samples.push(MetricSample {
+                            name: cur_name.clone(),
+                            labels: cur_labels.clone(),
+                            value,
+                            timestamp: cur_timestamp.clone().or_else(|| doc_timestamp.clone()).unwrap_or_default(),
+                        });
+                    }
+                    in_metric = false;
+                }
+            }
+            Event::End(ref e) => {
+                let tag = std::str::from_utf8(e.name().as_ref()).unwrap_or("");
+                if tag == "metric" && in_metric {
+                    if let Some(value) = cur_value {
+                        samples.push(MetricSample {
+                            name: cur_name.clone(),
+                            labels: cur_labels.clone(),
+                            value,
+                            timestamp: cur_timestamp.clone().or_else(|| doc_timestamp.clone()).unwrap_or_default(),
+                        });
+                    }
+                    in_metric = false;
+                }
+            }
+            Event::Text(ref e) => {
+                let text = e.unescape().map_err(|e| e.to_string())?.to_string();
+                if !text.trim().is_empty() {
+                    samples.extend(parse_prometheus_exposition_text(&text, doc_timestamp.as_deref()));
+                }
+            }
+            Event::CData(ref e) => {
+                let text = String::from_utf8_lossy(e.as_ref()).to_string();
+                if !text.trim().is_empty() {
+                    samples.extend(parse_prometheus_exposition_text(&text, doc_timestamp.as_deref()));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(samples)
+}
+
+/// Parse a Prometheus-exposition-format body (`name{label="value"} 1.23 <ms>`
+/// per line, `#`-prefixed comments ignored) into samples, falling back to
+/// `fallback_timestamp` when a line has no timestamp field of its own.
+fn parse_prometheus_exposition_text(text: &str, fallback_timestamp: Option<&str>) -> Vec<MetricSample> {
+    let mut samples = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (head, rest) = match line.split_once(' ') {
+            Some(parts) => parts,
+            None => continue,
+        };
+
+        let mut fields = rest.split_whitespace();
+        let value = match fields.next().and_then(|v| v.parse::<f64>().ok()) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let timestamp = fields.next()
+            .and_then(|ms| ms.parse::<i64>().ok())
+            .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms))
+            .map(|dt| dt.to_rfc3339())
+            .or_else(|| fallback_timestamp.map(|t| t.to_string()))
+            .unwrap_or_default();
+
+        let (name, labels) = match head.find('{') {
+            Some(brace) if head.ends_with('}') => {
+                let name = head[..brace].to_string();
+                let labels = parse_exposition_labels(&head[brace + 1..head.len() - 1]);
+                (name, labels)
+            }
+            _ => (head.to_string(), HashMap::new()),
+        };
+
+        samples.push(MetricSample { name, labels, value, timestamp });
+    }
+
+    samples
+}
+
+fn parse_exposition_labels(raw: &str) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+    for pair in raw.split(',') {
+        let pair = pair.trim();
+        if let Some((key, value)) = pair.split_once('=') {
+            labels.insert(key.trim().to_string(), value.trim().trim_matches('"').to_string());
+        }
+    }
+    labels
+}
+
+/// Best-effort normalization of a metric timestamp attribute to RFC3339:
+/// already-RFC3339 strings pass through, epoch millis/seconds are converted,
+/// and anything unrecognized is returned as-is so the UI still has something.
+fn normalize_metric_timestamp(raw: &str) -> String {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+        return dt.to_rfc3339();
+    }
+    if let Ok(ms) = raw.parse::<i64>() {
+        let dt = if ms > 1_000_000_000_000 {
+            chrono::DateTime::from_timestamp_millis(ms)
+        } else {
+            chrono::DateTime::from_timestamp(ms, 0)
+        };
+        if let Some(dt) = dt {
+            return dt.to_rfc3339();
+        }
+    }
+    raw.to_string()
+}
+
+/// Read raw YAML content of a resource file. Works whether `bundle_path` is
+/// an extracted directory or a tar archive - see `read_bundle_log`.
 #[tauri::command]
 pub async fn read_bundle_resource_yaml(bundle_path: String, file_path: String) -> Result<String, String> {
     let bundle_base = Path::new(&bundle_path);
     let resource_file = Path::new(&file_path);
 
-    // Safety check - ensure path is within bundle
-    if !resource_file.starts_with(bundle_base) {
-        return Err("Invalid resource path".to_string());
-    }
+    validate_within_bundle(&bundle_path, &file_path).map_err(|_| "Invalid resource path".to_string())?;
 
-    if !resource_file.exists() {
-        return Err(format!("Resource file not found: {}", file_path));
-    }
+    let rel_path = resource_file.strip_prefix(bundle_base)
+        .map_err(|_| "Invalid resource path".to_string())?
+        .to_string_lossy()
+        .to_string();
 
-    fs::read_to_string(resource_file).map_err(|e| format!("Failed to read resource: {}", e))
+    open_bundle_source(&bundle_path)?.read_to_string(&rel_path)
 }
@@ -0,0 +1,222 @@
+/**
+ * S3-compatible remote sync for investigation patterns and the knowledge base
+ *
+ * Mirrors `investigation-patterns.jsonl` and the `.jsonl` files under
+ * `~/.opspilot/knowledge` to/from an S3-compatible object store so teams can
+ * share learned patterns across machines. Endpoint/bucket/region are plain
+ * config; the access/secret keys go through the same keyring-backed secret
+ * store used everywhere else (`store_secret`/`retrieve_secret`), never the
+ * config file.
+ */
+
+use aws_sdk_s3::config::{Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tokio::fs;
+
+use super::ai_utilities::{get_secret, InvestigationPattern};
+
+const ACCESS_KEY_SECRET: &str = "sync_s3_access_key";
+const SECRET_KEY_SECRET: &str = "sync_s3_secret_key";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3SyncConfig {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncReport {
+    pub added: usize,
+    pub skipped: usize,
+}
+
+fn patterns_path() -> PathBuf {
+    let mut path = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("lens-killer");
+    path.push("investigation-patterns.jsonl");
+    path
+}
+
+fn kb_directory() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".opspilot")
+        .join("knowledge")
+}
+
+async fn build_s3_client(config: &S3SyncConfig) -> Result<S3Client, String> {
+    let access_key = get_secret(ACCESS_KEY_SECRET).ok_or("No S3 access key stored; call store_secret(\"sync_s3_access_key\", ...) first")?;
+    let secret_key = get_secret(SECRET_KEY_SECRET).ok_or("No S3 secret key stored; call store_secret(\"sync_s3_secret_key\", ...) first")?;
+
+    let creds = Credentials::new(access_key, secret_key, None, None, "opspilot-sync");
+
+    let s3_config = aws_sdk_s3::config::Builder::new()
+        .endpoint_url(&config.endpoint)
+        .region(Region::new(config.region.clone()))
+        .credentials_provider(creds)
+        // Self-hosted/S3-compatible stores (MinIO, Ceph, etc.) generally require
+        // path-style addressing rather than virtual-hosted-style buckets.
+        .force_path_style(true)
+        .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
+        .build();
+
+    Ok(S3Client::from_conf(s3_config))
+}
+
+fn object_key(config: &S3SyncConfig, name: &str) -> String {
+    match &config.prefix {
+        Some(prefix) if !prefix.is_empty() => format!("{}/{}", prefix.trim_end_matches('/'), name),
+        _ => name.to_string(),
+    }
+}
+
+/// Push the local investigation patterns and KB `.jsonl` files to the
+/// configured S3-compatible bucket, overwriting whatever is there.
+#[tauri::command]
+pub async fn sync_push(config: S3SyncConfig) -> Result<SyncReport, String> {
+    let client = build_s3_client(&config).await?;
+    let mut added = 0;
+    let mut skipped = 0;
+
+    let patterns_path = patterns_path();
+    if patterns_path.exists() {
+        let content = fs::read(&patterns_path).await.map_err(|e| format!("Failed to read patterns: {}", e))?;
+        client
+            .put_object()
+            .bucket(&config.bucket)
+            .key(object_key(&config, "investigation-patterns.jsonl"))
+            .body(ByteStream::from(content))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to upload patterns: {}", e))?;
+        added += 1;
+    } else {
+        skipped += 1;
+    }
+
+    let kb_dir = kb_directory();
+    if kb_dir.exists() {
+        let mut entries = fs::read_dir(&kb_dir).await.map_err(|e| format!("Failed to read KB directory: {}", e))?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let content = fs::read(&path).await.map_err(|e| format!("Failed to read {}: {}", file_name, e))?;
+            client
+                .put_object()
+                .bucket(&config.bucket)
+                .key(object_key(&config, &format!("knowledge/{}", file_name)))
+                .body(ByteStream::from(content))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to upload {}: {}", file_name, e))?;
+            added += 1;
+        }
+    }
+
+    Ok(SyncReport { added, skipped })
+}
+
+/// Pull remote investigation patterns and KB files down, merging patterns by
+/// `pattern_hash` so a pull never duplicates an entry that already exists
+/// locally.
+#[tauri::command]
+pub async fn sync_pull(config: S3SyncConfig) -> Result<SyncReport, String> {
+    let client = build_s3_client(&config).await?;
+    let mut added = 0;
+    let mut skipped = 0;
+
+    // Merge patterns.
+    let remote_patterns = client
+        .get_object()
+        .bucket(&config.bucket)
+        .key(object_key(&config, "investigation-patterns.jsonl"))
+        .send()
+        .await;
+
+    if let Ok(output) = remote_patterns {
+        let bytes = output.body.collect().await.map_err(|e| e.to_string())?.into_bytes();
+        let remote_content = String::from_utf8_lossy(&bytes).into_owned();
+
+        let local_path = patterns_path();
+        let local_content = if local_path.exists() {
+            fs::read_to_string(&local_path).await.unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        let mut seen_hashes: HashSet<String> = local_content
+            .lines()
+            .filter_map(|l| serde_json::from_str::<InvestigationPattern>(l).ok())
+            .map(|p| p.pattern_hash)
+            .collect();
+
+        let mut merged = local_content;
+        for line in remote_content.lines() {
+            let Ok(pattern) = serde_json::from_str::<InvestigationPattern>(line) else {
+                continue;
+            };
+            if seen_hashes.contains(&pattern.pattern_hash) {
+                skipped += 1;
+                continue;
+            }
+            seen_hashes.insert(pattern.pattern_hash.clone());
+            if !merged.is_empty() && !merged.ends_with('\n') {
+                merged.push('\n');
+            }
+            merged.push_str(line);
+            merged.push('\n');
+            added += 1;
+        }
+
+        if let Some(parent) = local_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+        }
+        fs::write(&local_path, merged).await.map_err(|e| format!("Failed to write merged patterns: {}", e))?;
+    }
+
+    // Mirror remote KB files under the "knowledge/" prefix.
+    let kb_dir = kb_directory();
+    let list = client
+        .list_objects_v2()
+        .bucket(&config.bucket)
+        .prefix(object_key(&config, "knowledge/"))
+        .send()
+        .await;
+
+    if let Ok(list) = list {
+        fs::create_dir_all(&kb_dir).await.map_err(|e| e.to_string())?;
+        for object in list.contents() {
+            let Some(key) = object.key() else { continue };
+            let Some(file_name) = key.rsplit('/').next() else { continue };
+            if file_name.is_empty() || !file_name.ends_with(".jsonl") {
+                continue;
+            }
+
+            let output = client
+                .get_object()
+                .bucket(&config.bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to download {}: {}", file_name, e))?;
+            let bytes = output.body.collect().await.map_err(|e| e.to_string())?.into_bytes();
+
+            fs::write(kb_dir.join(file_name), bytes.as_ref())
+                .await
+                .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+            added += 1;
+        }
+    }
+
+    Ok(SyncReport { added, skipped })
+}
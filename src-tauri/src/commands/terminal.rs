@@ -1,8 +1,55 @@
 use tauri::{AppHandle, Emitter, State};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::io::{Read, Write};
 use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
 use crate::state::{AppState, ShellSession};
+use crate::recording::Recording;
+
+/// Drains a PTY's reader on the shared Tokio blocking-thread pool rather
+/// than a dedicated `std::thread::spawn` per session - `Read::read` on a
+/// PTY master still blocks, but `spawn_blocking` at least reuses a bounded
+/// pool instead of leaking one OS thread per terminal for its whole
+/// lifetime. Emits `data_event` per chunk and, once the child exits,
+/// `closed_event` carrying its real exit code.
+///
+/// Also looks up `session_id` in `recordings` on every chunk (rather than
+/// capturing a fixed recorder at spawn time) so `start_recording`/
+/// `stop_recording` can toggle capture mid-session without touching this
+/// loop.
+fn spawn_pty_reader(
+    app: AppHandle,
+    data_event: String,
+    closed_event: String,
+    mut reader: Box<dyn Read + Send>,
+    child: Arc<Mutex<Box<dyn portable_pty::Child + Send + Sync>>>,
+    session_id: String,
+    recordings: Arc<Mutex<HashMap<String, Arc<Recording>>>>,
+) {
+    tokio::task::spawn_blocking(move || {
+        let mut buffer = [0u8; 4096];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(n) if n > 0 => {
+                    let data = String::from_utf8_lossy(&buffer[..n]).to_string();
+                    if let Some(rec) = recordings.lock().unwrap().get(&session_id) {
+                        rec.record_output(&data);
+                    }
+                    let _ = app.emit(&data_event, data);
+                }
+                _ => break,
+            }
+        }
+
+        let exit_code = child
+            .lock()
+            .unwrap()
+            .wait()
+            .map(|status| status.exit_code())
+            .unwrap_or(0);
+        let _ = app.emit(&closed_event, serde_json::json!({ "exit_code": exit_code }));
+    });
+}
 
 // --- Terminal Agent Commands (New) ---
 
@@ -34,33 +81,27 @@ pub async fn start_terminal_agent(
     let reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
     let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
 
-    // 4. Spawn Reader Thread
-    let app_handle = app.clone();
-    std::thread::spawn(move || {
-        let mut reader = reader;
-        let mut buffer = [0u8; 4096];
-        loop {
-            match reader.read(&mut buffer) {
-                Ok(n) if n > 0 => {
-                    let data = String::from_utf8_lossy(&buffer[..n]).to_string();
-                    // Emit to the specific event expected by TerminalBlock
-                    let _ = app_handle.emit("agent:terminal:data", data);
-                }
-                _ => break, // EOF or error
-            }
-        }
-        // Emit exit/closed event?
-        let _ = app_handle.emit("agent:terminal:closed", ());
-    });
-    
-    // 5. Spawn Child Process
-    let _child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn claude: {}", e))?;
+    // 4. Spawn Child Process
+    let child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn claude: {}", e))?;
+    let child = Arc::new(Mutex::new(child));
+
+    // 5. Spawn reader task (shared blocking pool, not a dedicated thread)
+    spawn_pty_reader(
+        app.clone(),
+        "agent:terminal:data".to_string(),
+        "agent:terminal:closed".to_string(),
+        reader,
+        child.clone(),
+        "claude-agent".to_string(),
+        state.recordings.clone(),
+    );
 
     // 6. Store Session
     // We reuse the shell_sessions map, but use a reserved ID "claude-agent"
     let session = Arc::new(ShellSession {
         writer: Arc::new(Mutex::new(writer)),
         master: Arc::new(Mutex::new(pair.master)),
+        child,
     });
 
     state.shell_sessions.lock().unwrap().insert("claude-agent".to_string(), session.clone());
@@ -128,6 +169,9 @@ pub fn resize_agent_terminal(
             }).map_err(|e| e.to_string())?;
         }
     }
+    if let Some(rec) = state.recordings.lock().unwrap().get("claude-agent") {
+        rec.record_resize(cols, rows);
+    }
     Ok(())
 }
 
@@ -157,36 +201,26 @@ pub async fn start_local_shell(
     let reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
     let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
 
-    // Spawn thread to read PTY and emit events
-    let app_handle = app.clone();
-    let sid = session_id.clone();
-
-    std::thread::spawn(move || {
-        let mut reader = reader;
-        let mut buffer = [0u8; 4096];
-        loop {
-            match reader.read(&mut buffer) {
-                Ok(n) if n > 0 => {
-                    let data = String::from_utf8_lossy(&buffer[..n]).to_string();
-                    // Emit with the event name the frontend expects
-                    let _ = app_handle.emit(&format!("shell_output:{}", sid), data);
-                }
-                _ => {
-                    // Emit closed event
-                    let _ = app_handle.emit(&format!("shell_closed:{}", sid), ());
-                    break;
-                }
-            }
-        }
-    });
-
     // Spawn shell
-    let _child = pair.slave.spawn_command(cmd).map_err(|e| e.to_string())?;
+    let child = pair.slave.spawn_command(cmd).map_err(|e| e.to_string())?;
+    let child = Arc::new(Mutex::new(child));
+
+    // Spawn reader task (shared blocking pool) to read PTY and emit events
+    spawn_pty_reader(
+        app.clone(),
+        format!("shell_output:{}", session_id),
+        format!("shell_closed:{}", session_id),
+        reader,
+        child.clone(),
+        session_id.clone(),
+        state.recordings.clone(),
+    );
 
     // Store session
     let session = Arc::new(ShellSession {
         writer: Arc::new(Mutex::new(writer)),
         master: Arc::new(Mutex::new(pair.master)),
+        child,
     });
 
     state.shell_sessions.lock().unwrap().insert(session_id, session);
@@ -225,15 +259,63 @@ pub fn resize_shell(
             }).map_err(|e| e.to_string())?;
         }
     }
+    if let Some(rec) = state.recordings.lock().unwrap().get(&session_id) {
+        rec.record_resize(cols, rows);
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub fn stop_local_shell(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
-    state.shell_sessions.lock().unwrap().remove(&session_id);
+    if let Some(session) = state.shell_sessions.lock().unwrap().remove(&session_id) {
+        let _ = session.child.lock().unwrap().kill();
+    }
+    state.recordings.lock().unwrap().remove(&session_id);
     Ok(())
 }
 
+/// Kill any tracked session's process without waiting for it to exit -
+/// `stop_local_shell`/`disconnect_remote_host` also drop the session's map
+/// entry; this is for a session the caller wants to keep around (to read
+/// `wait_session`'s exit code) but needs gone right now.
+#[tauri::command]
+pub fn kill_session(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
+    let session = state
+        .shell_sessions
+        .lock()
+        .unwrap()
+        .get(&session_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown session: {}", session_id))?;
+    session.child.lock().unwrap().kill().map_err(|e| e.to_string())
+}
+
+/// Block (on the shared blocking pool) until a session's process exits,
+/// returning its real exit code instead of the previous behavior of never
+/// surfacing one at all.
+#[tauri::command]
+pub async fn wait_session(state: State<'_, AppState>, session_id: String) -> Result<i32, String> {
+    let session = state
+        .shell_sessions
+        .lock()
+        .unwrap()
+        .get(&session_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown session: {}", session_id))?;
+
+    tokio::task::spawn_blocking(move || {
+        session
+            .child
+            .lock()
+            .unwrap()
+            .wait()
+            .map(|status| status.exit_code() as i32)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 // --- Exec Commands (kubectl exec into pod) ---
 
 #[tauri::command]
@@ -261,33 +343,26 @@ pub async fn start_exec(
     let reader = pair.master.try_clone_reader().map_err(|e| e.to_string())?;
     let writer = pair.master.take_writer().map_err(|e| e.to_string())?;
 
-    // Spawn reader thread
-    let app_handle = app.clone();
-    let sid = session_id.clone();
-    std::thread::spawn(move || {
-        let mut reader = reader;
-        let mut buffer = [0u8; 4096];
-        loop {
-            match reader.read(&mut buffer) {
-                Ok(n) if n > 0 => {
-                    let data = String::from_utf8_lossy(&buffer[..n]).to_string();
-                    let _ = app_handle.emit(&format!("term_output:{}", sid), data);
-                }
-                _ => {
-                    let _ = app_handle.emit(&format!("term_closed:{}", sid), ());
-                    break;
-                }
-            }
-        }
-    });
-
     // Spawn command
-    let _child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn kubectl exec: {}", e))?;
+    let child = pair.slave.spawn_command(cmd).map_err(|e| format!("Failed to spawn kubectl exec: {}", e))?;
+    let child = Arc::new(Mutex::new(child));
+
+    // Spawn reader task (shared blocking pool)
+    spawn_pty_reader(
+        app.clone(),
+        format!("term_output:{}", session_id),
+        format!("term_closed:{}", session_id),
+        reader,
+        child.clone(),
+        session_id.clone(),
+        state.recordings.clone(),
+    );
 
     // Store session
     let session = Arc::new(ShellSession {
         writer: Arc::new(Mutex::new(writer)),
         master: Arc::new(Mutex::new(pair.master)),
+        child,
     });
 
     state.shell_sessions.lock().unwrap().insert(session_id, session);
@@ -326,5 +401,64 @@ pub fn resize_exec(
             }).map_err(|e| e.to_string())?;
         }
     }
+    if let Some(rec) = state.recordings.lock().unwrap().get(&session_id) {
+        rec.record_resize(cols, rows);
+    }
     Ok(())
 }
+
+// --- Session Recording (asciicast v2) ---
+
+/// Info about one active recording, for `list_recordings`. There's no fixed
+/// recordings directory (the caller picks `path` per `start_recording`
+/// call), so this reflects sessions currently being captured rather than
+/// scanning disk.
+#[derive(serde::Serialize)]
+pub struct RecordingInfo {
+    pub session_id: String,
+    pub path: String,
+}
+
+/// Starts capturing `session_id`'s PTY output to `path` in asciicast v2
+/// format. Works for any session already tracked in `shell_sessions`
+/// (`start_local_shell`, `start_exec`, or the reserved `"claude-agent"` id
+/// from `start_terminal_agent`) - recording is opt-in and can be toggled on
+/// mid-session since the reader loop re-checks `recordings` per chunk.
+#[tauri::command]
+pub fn start_recording(
+    state: State<'_, AppState>,
+    session_id: String,
+    path: String,
+) -> Result<(), String> {
+    if !state.shell_sessions.lock().unwrap().contains_key(&session_id) {
+        return Err(format!("Unknown session: {}", session_id));
+    }
+
+    let recording = Recording::start(std::path::PathBuf::from(&path), None, None)?;
+    state
+        .recordings
+        .lock()
+        .unwrap()
+        .insert(session_id, Arc::new(recording));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_recording(state: State<'_, AppState>, session_id: String) -> Result<(), String> {
+    state.recordings.lock().unwrap().remove(&session_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_recordings(state: State<'_, AppState>) -> Result<Vec<RecordingInfo>, String> {
+    Ok(state
+        .recordings
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(session_id, rec)| RecordingInfo {
+            session_id: session_id.clone(),
+            path: rec.path.display().to_string(),
+        })
+        .collect())
+}
@@ -1,8 +1,11 @@
 use log::{info, warn, error};
 use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::sync::Arc;
 use tauri::{Emitter, State};
+use tokio::sync::Mutex as TokioMutex;
 use crate::AppState;
+use crate::models::ClusterStats;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VCluster {
@@ -44,7 +47,7 @@ impl VClusterError {
         }
     }
 
-    fn cluster_unreachable(details: &str) -> Self {
+    pub(crate) fn cluster_unreachable(details: &str) -> Self {
         Self {
             code: "CLUSTER_UNREACHABLE".to_string(),
             message: "vcluster connected but API server is unreachable".to_string(),
@@ -62,7 +65,7 @@ impl VClusterError {
         }
     }
 
-    fn command_failed(cmd: &str, stderr: &str) -> Self {
+    pub(crate) fn command_failed(cmd: &str, stderr: &str) -> Self {
         Self {
             code: "COMMAND_FAILED".to_string(),
             message: format!("Command '{}' failed", cmd),
@@ -70,6 +73,17 @@ impl VClusterError {
             suggestion: None,
         }
     }
+
+    /// The supervised `vcluster connect` child exited on its own (i.e. not
+    /// because we killed it to disconnect or reconnect).
+    fn connect_process_died(stderr: &str) -> Self {
+        Self {
+            code: "CONNECT_PROCESS_DIED".to_string(),
+            message: "vcluster connect process exited unexpectedly".to_string(),
+            details: Some(stderr.to_string()),
+            suggestion: Some("The proxy connection was lost. Try connecting again.".to_string()),
+        }
+    }
 }
 
 impl std::fmt::Display for VClusterError {
@@ -80,8 +94,65 @@ impl std::fmt::Display for VClusterError {
 
 
 
+/// Runs a pre-built `vcluster`/`kubectl` `Command` and captures its output.
+/// The injection point for the JSON-parsing branches in `list_vclusters`
+/// and `check_vcluster_status`, which otherwise only ever see whatever a
+/// real binary on the host happens to print: a mock implementation driven
+/// by recorded fixtures can exercise the "not ready", "CLUSTER_UNREACHABLE"
+/// and malformed-JSON paths without a live cluster.
+pub trait CommandRunner: Send + Sync {
+    fn output(&self, cmd: Command) -> std::io::Result<std::process::Output>;
+}
+
+/// The runner every `#[tauri::command]` entry point uses: just shells out.
+pub struct RealCommandRunner;
+
+impl CommandRunner for RealCommandRunner {
+    fn output(&self, mut cmd: Command) -> std::io::Result<std::process::Output> {
+        cmd.output()
+    }
+}
+
+/// A canned `CommandRunner` that ignores whatever command it's handed and
+/// always returns the given stdout/stderr/exit status, for exercising
+/// `list_vclusters_with`/`check_vcluster_status`'s JSON-parsing branches
+/// (PascalCase vs camelCase keys, the `items`-wrapper shape, empty output,
+/// "not found" stderr, malformed JSON) without a live `vcluster` binary.
+/// See the `tests` module at the bottom of this file for the fixtures.
+pub struct MockCommandRunner {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub success: bool,
+}
+
+#[cfg(unix)]
+fn mock_exit_status(success: bool) -> std::process::ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    std::process::ExitStatus::from_raw(if success { 0 } else { 1 })
+}
+
+#[cfg(windows)]
+fn mock_exit_status(success: bool) -> std::process::ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    std::process::ExitStatus::from_raw(if success { 0 } else { 1 })
+}
+
+impl CommandRunner for MockCommandRunner {
+    fn output(&self, _cmd: Command) -> std::io::Result<std::process::Output> {
+        Ok(std::process::Output {
+            status: mock_exit_status(self.success),
+            stdout: self.stdout.clone(),
+            stderr: self.stderr.clone(),
+        })
+    }
+}
+
 #[tauri::command]
 pub async fn list_vclusters() -> Result<Vec<VCluster>, String> {
+    list_vclusters_with(&RealCommandRunner)
+}
+
+fn list_vclusters_with(runner: &dyn CommandRunner) -> Result<Vec<VCluster>, String> {
     // Check if vcluster binary exists first
     let mut ver_cmd = Command::new("vcluster");
     ver_cmd.arg("--version");
@@ -90,27 +161,41 @@ pub async fn list_vclusters() -> Result<Vec<VCluster>, String> {
         use std::os::windows::process::CommandExt;
         ver_cmd.creation_flags(0x08000000);
     }
-    match ver_cmd.output() {
+    match runner.output(ver_cmd) {
         Ok(_) => {}, // Binary exists
         Err(e) => {
             if e.kind() == std::io::ErrorKind::NotFound {
                 return Err("VCLUSTER_NOT_INSTALLED".to_string());
             }
-            // For other errors, we try to proceed or just log? 
+            // For other errors, we try to proceed or just log?
             // Better to fail if we can't even run version.
             return Err(format!("Failed to execute vcluster command: {}", e));
         }
     }
 
     // Run "vcluster list --output json"
+    let default_list_args: Vec<String> = ["list", "--output", "json"].into_iter().map(str::to_string).collect();
+
+    #[cfg(feature = "scripting")]
+    let list_args = crate::scripting::apply_connect_args_hook(
+        default_list_args,
+        &crate::scripting::ConnectContext {
+            name: String::new(),
+            namespace: String::new(),
+            status: "listing".to_string(),
+        },
+    );
+    #[cfg(not(feature = "scripting"))]
+    let list_args = default_list_args;
+
     let mut cmd = Command::new("vcluster");
-    cmd.args(["list", "--output", "json"]);
+    cmd.args(&list_args);
     #[cfg(target_os = "windows")]
     {
         use std::os::windows::process::CommandExt;
         cmd.creation_flags(0x08000000);
     }
-    let output = cmd.output()
+    let output = runner.output(cmd)
         .map_err(|e| format!("Failed to execute vcluster command: {}", e))?;
 
     if !output.status.success() {
@@ -123,7 +208,7 @@ pub async fn list_vclusters() -> Result<Vec<VCluster>, String> {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    
+
     if stdout.trim().is_empty() {
         return Ok(Vec::new());
     }
@@ -169,6 +254,15 @@ pub struct VClusterConnectProgress {
     pub suggestion: Option<String>,
 }
 
+/// One line of raw `vcluster connect` output, streamed to the frontend as
+/// it's produced instead of being written to a temp file and read back later.
+#[derive(Debug, Clone, Serialize)]
+pub struct VClusterConnectLog {
+    pub stream: String, // "stdout" | "stderr"
+    pub line: String,
+    pub timestamp_millis: i64,
+}
+
 /// Helper to create a vcluster command with augmented PATH
 fn create_vcluster_command() -> Command {
     let mut cmd = Command::new("vcluster");
@@ -196,11 +290,11 @@ fn create_vcluster_command() -> Command {
 }
 
 /// Check if vcluster CLI is installed and return version
-fn check_vcluster_installed() -> Result<String, VClusterError> {
+fn check_vcluster_installed(runner: &dyn CommandRunner) -> Result<String, VClusterError> {
     let mut cmd = create_vcluster_command();
     cmd.arg("--version");
 
-    match cmd.output() {
+    match runner.output(cmd) {
         Ok(output) => {
             if output.status.success() {
                 let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -220,11 +314,11 @@ fn check_vcluster_installed() -> Result<String, VClusterError> {
 }
 
 /// Check if vcluster is in a ready state before attempting connection
-fn check_vcluster_status(name: &str, namespace: &str) -> Result<String, VClusterError> {
+fn check_vcluster_status(runner: &dyn CommandRunner, name: &str, namespace: &str) -> Result<String, VClusterError> {
     let mut cmd = create_vcluster_command();
     cmd.args(["list", "--output", "json"]);
 
-    match cmd.output() {
+    match runner.output(cmd) {
         Ok(output) => {
             if !output.status.success() {
                 return Ok("unknown".to_string()); // Can't check status, proceed anyway
@@ -259,10 +353,165 @@ fn check_vcluster_status(name: &str, namespace: &str) -> Result<String, VCluster
     }
 }
 
-/// Kill any stale vcluster processes
+/// Set `current-context` in the default kubeconfig, mirroring what `kubectl
+/// config use-context` does, without spawning a subprocess. Follows the same
+/// raw-YAML read/mutate/write approach as `context::delete_context`, since
+/// `kube::Config`'s typed kubeconfig API is read-only.
+fn set_current_context(context_name: &str) -> Result<(), String> {
+    let home = dirs::home_dir().ok_or("Could not find HOME directory")?;
+    let kubeconfig_path = home.join(".kube").join("config");
+
+    let content = std::fs::read_to_string(&kubeconfig_path)
+        .map_err(|e| format!("Failed to read kubeconfig: {}", e))?;
+
+    let mut config: serde_yaml::Value = serde_yaml::from_str(&content)
+        .map_err(|e| format!("Failed to parse kubeconfig: {}", e))?;
+
+    config["current-context"] = serde_yaml::Value::String(context_name.to_string());
+
+    let new_content = serde_yaml::to_string(&config)
+        .map_err(|e| format!("Failed to serialize kubeconfig: {}", e))?;
+
+    std::fs::write(&kubeconfig_path, new_content)
+        .map_err(|e| format!("Failed to write kubeconfig: {}", e))?;
+
+    Ok(())
+}
+
+/// Remove every kubeconfig context whose name starts with `prefix` (used to
+/// clean up the `vcluster_{name}_{namespace}` context `connect_vcluster`
+/// leaves behind once a vcluster is disconnected). Follows the same
+/// raw-YAML read/mutate/write approach as `context::delete_context`, but
+/// deliberately skips that function's cluster/user orphan cleanup: a
+/// vcluster's cluster/user entries are never shared with another context,
+/// so leaving them behind costs nothing and keeps this simple.
+fn delete_contexts_by_prefix(prefix: &str) -> Result<(), String> {
+    let home = dirs::home_dir().ok_or("Could not find HOME directory")?;
+    let kubeconfig_path = home.join(".kube").join("config");
+
+    let content = std::fs::read_to_string(&kubeconfig_path)
+        .map_err(|e| format!("Failed to read kubeconfig: {}", e))?;
+
+    let mut config: serde_yaml::Value = serde_yaml::from_str(&content)
+        .map_err(|e| format!("Failed to parse kubeconfig: {}", e))?;
+
+    if let Some(contexts) = config.get_mut("contexts").and_then(|c| c.as_sequence_mut()) {
+        contexts.retain(|c| {
+            c.get("name")
+                .and_then(|n| n.as_str())
+                .map(|n| !n.starts_with(prefix))
+                .unwrap_or(true)
+        });
+    }
+
+    let new_content = serde_yaml::to_string(&config)
+        .map_err(|e| format!("Failed to serialize kubeconfig: {}", e))?;
+
+    std::fs::write(&kubeconfig_path, new_content)
+        .map_err(|e| format!("Failed to write kubeconfig: {}", e))?;
+
+    Ok(())
+}
+
+/// Classify a `kube::Error` from the Stage 5 verify probe into the
+/// `VClusterError` shape, distinguishing RBAC failures (fail fast, no point
+/// retrying) from transient/transport errors (keep retrying).
+fn classify_kube_error(e: &kube::Error) -> VClusterError {
+    let is_forbidden = matches!(e, kube::Error::Api(ae) if ae.code == 401 || ae.code == 403);
+    if is_forbidden {
+        VClusterError {
+            code: "PERMISSION_DENIED".to_string(),
+            message: "Permission denied to access vcluster".to_string(),
+            details: Some(e.to_string()),
+            suggestion: Some("Check your RBAC permissions for accessing the vcluster namespace.".to_string()),
+        }
+    } else {
+        VClusterError::cluster_unreachable(&e.to_string())
+    }
+}
+
+/// A vcluster discovered on the host cluster by its StatefulSet/Pod labels,
+/// rather than by parsing `kubectl config get-contexts` output.
+#[derive(Debug, Clone, Serialize)]
+pub struct VClusterDiscovery {
+    pub name: String,
+    pub namespace: String,
+    pub ready: bool,
+    pub ready_replicas: i32,
+    pub replicas: i32,
+    pub version: Option<String>,
+}
+
+/// Standard labels a vcluster's StatefulSet carries: `app=vcluster` plus
+/// `release=<name>` identifying which vcluster release it belongs to.
+fn vcluster_label_selector(name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("app=vcluster,release={}", name),
+        None => "app=vcluster".to_string(),
+    }
+}
+
+fn statefulset_to_discovery(sts: &k8s_openapi::api::apps::v1::StatefulSet, fallback_name: &str) -> VClusterDiscovery {
+    let labels = sts.metadata.labels.as_ref();
+    let name = labels.and_then(|l| l.get("release")).cloned().unwrap_or_else(|| fallback_name.to_string());
+    let namespace = sts.metadata.namespace.clone().unwrap_or_default();
+    let version = labels.and_then(|l| l.get("app.kubernetes.io/version").or_else(|| l.get("helm.sh/chart"))).cloned();
+
+    let status = sts.status.as_ref();
+    let replicas = status.map(|s| s.replicas).unwrap_or(0);
+    let ready_replicas = status.and_then(|s| s.ready_replicas).unwrap_or(0);
+
+    VClusterDiscovery {
+        name,
+        namespace,
+        ready: replicas > 0 && ready_replicas == replicas,
+        ready_replicas,
+        replicas,
+        version,
+    }
+}
+
+/// Check the readiness of a single vcluster's StatefulSet on the host
+/// cluster, by `app=vcluster,release=<name>` labels in its namespace.
+async fn discover_vcluster_readiness(host_client: &kube::Client, name: &str, namespace: &str) -> Result<VClusterDiscovery, String> {
+    let statefulsets: kube::Api<k8s_openapi::api::apps::v1::StatefulSet> = kube::Api::namespaced(host_client.clone(), namespace);
+    let lp = kube::api::ListParams::default().labels(&vcluster_label_selector(Some(name)));
+    let list = statefulsets.list(&lp).await.map_err(|e| e.to_string())?;
+
+    let sts = list.items.first()
+        .ok_or_else(|| format!("no vcluster StatefulSet found for '{}' in namespace '{}'", name, namespace))?;
+
+    Ok(statefulset_to_discovery(sts, name))
+}
+
+/// List every vcluster visible on the host cluster via its `app=vcluster`
+/// StatefulSet, across all namespaces (or a single one if given). Lets the
+/// UI populate a connect menu without the user already knowing vcluster names.
+#[tauri::command]
+pub async fn discover_vclusters(state: State<'_, AppState>, namespace: Option<String>) -> Result<Vec<VClusterDiscovery>, String> {
+    let client = crate::client::create_client(state).await?;
+    let statefulsets: kube::Api<k8s_openapi::api::apps::v1::StatefulSet> = match &namespace {
+        Some(ns) => kube::Api::namespaced(client, ns),
+        None => kube::Api::all(client),
+    };
+
+    let lp = kube::api::ListParams::default().labels(&vcluster_label_selector(None));
+    let list = statefulsets.list(&lp).await.map_err(|e| e.to_string())?;
+
+    Ok(list.items.iter().map(|sts| {
+        let fallback_name = sts.metadata.name.clone().unwrap_or_else(|| "unknown".to_string());
+        statefulset_to_discovery(sts, &fallback_name)
+    }).collect())
+}
+
+/// Kill any stale vcluster processes. There's no port to target here -
+/// `vcluster connect` is identified by command line, not a socket we know
+/// about - so this still has to match by process name/pattern; it's the
+/// port-forward it spawns underneath that `kill_stale_port_forward_for_context`
+/// below can target precisely instead.
 fn kill_stale_vcluster_processes() {
     let debug_log_path = std::env::temp_dir().join("vcluster-debug.log");
-    
+
     #[cfg(target_os = "windows")]
     {
         let _ = Command::new("taskkill")
@@ -277,11 +526,6 @@ fn kill_stale_vcluster_processes() {
             .args(["-f", "vcluster connect"])
             .output();
 
-        // Also kill any port-forward processes related to vcluster
-        let out2 = Command::new("/usr/bin/pkill")
-            .args(["-f", "kubectl.*port-forward.*vcluster"])
-            .output();
-
         // Log pkill results
         if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(&debug_log_path) {
              use std::io::Write;
@@ -289,9 +533,58 @@ fn kill_stale_vcluster_processes() {
              if let Ok(o) = out1 {
                  let _ = writeln!(f, "pkill vcluster: {}", o.status);
              }
-             if let Ok(o) = out2 {
-                 let _ = writeln!(f, "pkill port-forward: {}", o.status);
-             }
+        }
+    }
+}
+
+/// Extract the loopback port a kubeconfig context's cluster entry points at,
+/// e.g. `https://127.0.0.1:12345` -> `Some(12345)`. Returns `None` for
+/// non-loopback clusters (real API servers we have no business touching).
+fn loopback_port_from_server_url(server: &str) -> Option<u16> {
+    let url = server.trim().parse::<tauri::Url>().ok()?;
+    let host = url.host_str()?;
+    if host != "127.0.0.1" && host != "localhost" && host != "::1" {
+        return None;
+    }
+    url.port()
+}
+
+/// Kill whatever process is still listening on `context`'s loopback port,
+/// found via `pids_listening_on_port_native`'s socket-table lookup rather
+/// than the old `pkill -f kubectl.*port-forward.*vcluster` sweep - that
+/// pattern would just as happily kill an unrelated `kubectl port-forward`
+/// a user started by hand, since it only matched on command line text.
+fn kill_stale_port_forward_for_context(context: &str) {
+    let mut cmd = Command::new("kubectl");
+    cmd.args(["config", "view", "--minify", "--raw", "--context", context, "-o", "jsonpath={.clusters[0].cluster.server}"]);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000);
+    }
+
+    let Ok(output) = cmd.output() else { return; };
+    if !output.status.success() {
+        return;
+    }
+
+    let server = String::from_utf8_lossy(&output.stdout);
+    let Some(port) = loopback_port_from_server_url(&server) else { return; };
+
+    for pid in crate::agent_sidecar::pids_listening_on_port_native(port) {
+        info!("[vcluster] Killing stale port-forward for context '{}': pid {} on port {}", context, pid, port);
+        #[cfg(not(target_os = "windows"))]
+        unsafe { libc::kill(pid as i32, libc::SIGKILL); }
+        #[cfg(target_os = "windows")]
+        {
+            use windows::Win32::Foundation::CloseHandle;
+            use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+            unsafe {
+                if let Ok(handle) = OpenProcess(PROCESS_TERMINATE, false, pid) {
+                    let _ = TerminateProcess(handle, 1);
+                    let _ = CloseHandle(handle);
+                }
+            }
         }
     }
 }
@@ -401,9 +694,144 @@ async fn verify_cluster_connection(max_attempts: u32, delay_secs: u64) -> Result
     Err(last_error)
 }
 
+fn emit_connect_log(app: &tauri::AppHandle, stream: &str, line: String) {
+    let _ = app.emit("vcluster-connect-log", VClusterConnectLog {
+        stream: stream.to_string(),
+        line,
+        timestamp_millis: chrono::Utc::now().timestamp_millis(),
+    });
+}
+
+/// Streams the connect child's stdout/stderr to the frontend line-by-line as
+/// they're produced, polling both pipes concurrently so output interleaves in
+/// real time rather than being buffered to a file and read back on failure.
+/// Stderr lines are also appended to `captured_stderr` for the context-wait
+/// timeout and `supervise_connect_process`'s unexpected-exit diagnostics.
+async fn stream_connect_output(
+    stdout: Option<tokio::process::ChildStdout>,
+    stderr: Option<tokio::process::ChildStderr>,
+    captured_stderr: Arc<TokioMutex<String>>,
+    app: tauri::AppHandle,
+) {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let mut stdout_lines = stdout.map(|s| BufReader::new(s).lines());
+    let mut stderr_lines = stderr.map(|s| BufReader::new(s).lines());
+
+    while stdout_lines.is_some() || stderr_lines.is_some() {
+        tokio::select! {
+            result = async {
+                match stdout_lines.as_mut() {
+                    Some(lines) => lines.next_line().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                match result {
+                    Ok(Some(line)) => emit_connect_log(&app, "stdout", line),
+                    _ => stdout_lines = None,
+                }
+            }
+            result = async {
+                match stderr_lines.as_mut() {
+                    Some(lines) => lines.next_line().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                match result {
+                    Ok(Some(line)) => {
+                        {
+                            let mut buf = captured_stderr.lock().await;
+                            buf.push_str(&line);
+                            buf.push('\n');
+                        }
+                        emit_connect_log(&app, "stderr", line);
+                    }
+                    _ => stderr_lines = None,
+                }
+            }
+        }
+    }
+}
+
+/// Send a graceful termination signal to a running `vcluster connect` child
+/// (SIGTERM on Unix, a non-forceful `taskkill` on Windows), giving it a
+/// chance to tear down its port-forward before `supervise_connect_process`
+/// escalates to a hard kill.
+fn send_graceful_terminate(pid: Option<u32>) {
+    let Some(pid) = pid else { return; };
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = Command::new("kill").args(["-TERM", &pid.to_string()]).output();
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill").args(["/PID", &pid.to_string()]).output();
+    }
+}
+
+/// Owns the `vcluster connect` child for as long as it runs. Awaits its exit
+/// alongside a cancel signal so `disconnect_vcluster` can kill it cleanly;
+/// if the process exits on its own instead, that means the proxy connection
+/// was lost, so we surface it to the UI as a `CONNECT_PROCESS_DIED` error.
+async fn supervise_connect_process(
+    child_store: Arc<TokioMutex<Option<tokio::process::Child>>>,
+    cancel_store: Arc<std::sync::Mutex<Option<tokio::sync::mpsc::UnboundedSender<()>>>>,
+    captured_stderr: Arc<TokioMutex<String>>,
+    mut cancel_rx: tokio::sync::mpsc::UnboundedReceiver<()>,
+    app: tauri::AppHandle,
+    name: String,
+    namespace: String,
+) {
+    let mut guard = child_store.lock().await;
+    let cancelled = loop {
+        let Some(child) = guard.as_mut() else { return; };
+        tokio::select! {
+            biased;
+            _ = cancel_rx.recv() => {
+                send_graceful_terminate(child.id());
+                // Give it a few seconds to exit on its own before escalating
+                // to a hard kill.
+                let exited_gracefully = tokio::select! {
+                    _ = child.wait() => true,
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(5)) => false,
+                };
+                if !exited_gracefully {
+                    let _ = child.kill().await;
+                }
+                break true;
+            }
+            _ = child.wait() => {
+                break false;
+            }
+        }
+    };
+    *guard = None;
+    drop(guard);
+
+    if let Ok(mut slot) = cancel_store.lock() {
+        *slot = None;
+    }
+
+    if cancelled {
+        info!("[vcluster] Connect process for {}/{} was cancelled", namespace, name);
+        return;
+    }
+
+    let stderr = captured_stderr.lock().await.clone();
+    warn!("[vcluster] Connect process for {}/{} exited unexpectedly: {}", namespace, name, stderr.trim());
+    let err = VClusterError::connect_process_died(&stderr);
+    let _ = app.emit("vcluster-connect-progress", VClusterConnectProgress {
+        stage: "connect".to_string(),
+        message: err.message.clone(),
+        progress: 0,
+        is_error: true,
+        error_code: Some(err.code.clone()),
+        suggestion: err.suggestion.clone(),
+    });
+}
+
 #[tauri::command]
 pub async fn connect_vcluster(name: String, namespace: String, state: State<'_, AppState>, app: tauri::AppHandle) -> Result<String, String> {
-    use std::thread;
     use std::time::Duration;
     use tokio::time::sleep;
 
@@ -433,7 +861,7 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
     emit_ok("preflight", "Checking vcluster CLI...", 2);
 
     // Check vcluster is installed
-    match check_vcluster_installed() {
+    match check_vcluster_installed(&RealCommandRunner) {
         Ok(version) => {
             info!("[vcluster] CLI version: {}", version);
             emit_ok("preflight", &format!("vcluster CLI found: {}", version.lines().next().unwrap_or(&version)), 5);
@@ -447,7 +875,9 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
 
     // Check vcluster status
     emit_ok("preflight", "Checking vcluster status...", 8);
-    match check_vcluster_status(&name, &namespace) {
+    #[cfg_attr(not(feature = "scripting"), allow(unused_mut, unused_assignments))]
+    let mut vcluster_status = "unknown".to_string();
+    match check_vcluster_status(&RealCommandRunner, &name, &namespace) {
         Ok(status) => {
             info!("[vcluster] Status: {}", status);
             if status.to_lowercase() != "running" && status != "unknown" {
@@ -457,6 +887,7 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
                 return Err(err.to_string());
             }
             emit_ok("preflight", &format!("vcluster status: {}", status), 10);
+            vcluster_status = status;
         }
         Err(e) => {
             warn!("[vcluster] Could not check status: {:?}", e);
@@ -495,6 +926,11 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
         let contexts = String::from_utf8_lossy(&output.stdout);
         for ctx in contexts.lines() {
             if ctx.starts_with(&context_prefix) {
+                // Kill the port-forward backing this context, if any, before
+                // dropping the context itself - precisely targeted, unlike
+                // the old blanket `pkill -f kubectl.*port-forward.*vcluster`.
+                kill_stale_port_forward_for_context(ctx);
+
                 // Delete this stale context
                 let mut del_cmd = Command::new("kubectl");
                 del_cmd.args(["config", "delete-context", ctx]);
@@ -524,105 +960,126 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
 
     let vcluster_name = name.clone();
     let vcluster_ns = namespace.clone();
-    let pid_store = state.vcluster_pid.clone();
-
-    // Spawn the vcluster connect command in a separate thread
-    let connect_handle = thread::spawn(move || {
-        use std::fs::File;
-        use std::process::Stdio;
-
-        let temp_dir = std::env::temp_dir();
-        let stdout_path = temp_dir.join("vcluster-connect.out");
-        let stderr_path = temp_dir.join("vcluster-connect.err");
-
-        // Clear previous log files
-        let _ = std::fs::remove_file(&stdout_path);
-        let _ = std::fs::remove_file(&stderr_path);
-
-        let stdout_file = File::create(&stdout_path).unwrap_or_else(|_| {
-            #[cfg(target_os = "windows")]
-            { File::create("NUL").unwrap() }
-            #[cfg(not(target_os = "windows"))]
-            { File::create("/dev/null").unwrap() }
-        });
-        let stderr_file = File::create(&stderr_path).unwrap_or_else(|_| {
-            #[cfg(target_os = "windows")]
-            { File::create("NUL").unwrap() }
-            #[cfg(not(target_os = "windows"))]
-            { File::create("/dev/null").unwrap() }
-        });
 
-        // AUGMENT PATH: macOS apps don't inherit shell PATH, so we must inject common paths
-        let current_path = std::env::var("PATH").unwrap_or_default();
-        let new_path = if cfg!(target_os = "windows") {
-             current_path.clone()
-        } else {
-             // Basic paths often missing in macOS .app bundles
-             let extra_paths = "/opt/homebrew/bin:/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin";
-             if current_path.is_empty() {
-                 extra_paths.to_string()
-             } else {
-                 format!("{}:{}", current_path, extra_paths)
-             }
-        };
+    use std::process::Stdio;
+
+    let temp_dir = std::env::temp_dir();
+
+    // AUGMENT PATH: macOS apps don't inherit shell PATH, so we must inject common paths
+    let current_path = std::env::var("PATH").unwrap_or_default();
+    let new_path = if cfg!(target_os = "windows") {
+         current_path.clone()
+    } else {
+         // Basic paths often missing in macOS .app bundles
+         let extra_paths = "/opt/homebrew/bin:/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin";
+         if current_path.is_empty() {
+             extra_paths.to_string()
+         } else {
+             format!("{}:{}", current_path, extra_paths)
+         }
+    };
 
-        // Log diagnostics to specific file for debugging
-        let debug_log_path = temp_dir.join("vcluster-debug.log");
-        if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(&debug_log_path) {
-             use std::io::Write;
-             let _ = writeln!(f, "\n--- SPAWNING CONNECT PROCESS ---");
-             let _ = writeln!(f, "User PATH: {}", current_path);
-             let _ = writeln!(f, "Augmented PATH: {}", new_path);
-        }
+    // Log diagnostics to specific file for debugging
+    let debug_log_path = temp_dir.join("vcluster-debug.log");
+    if let Ok(mut f) = std::fs::OpenOptions::new().create(true).append(true).open(&debug_log_path) {
+         use std::io::Write;
+         let _ = writeln!(f, "\n--- SPAWNING CONNECT PROCESS ---");
+         let _ = writeln!(f, "User PATH: {}", current_path);
+         let _ = writeln!(f, "Augmented PATH: {}", new_path);
+    }
 
-        let mut connect_cmd = Command::new("vcluster");
-        connect_cmd.args(["connect", &vcluster_name, "-n", &vcluster_ns, "--background-proxy=false", "--address", "127.0.0.1"])
-            .env("PATH", &new_path)
-            .stdin(Stdio::null())
-            .stdout(stdout_file)
-            .stderr(stderr_file);
+    let default_connect_args: Vec<String> = ["connect", &vcluster_name, "-n", &vcluster_ns, "--background-proxy=false", "--address", "127.0.0.1"]
+        .into_iter().map(str::to_string).collect();
 
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            connect_cmd.creation_flags(0x08000000);
-        }
+    #[cfg(feature = "scripting")]
+    let connect_args = crate::scripting::apply_connect_args_hook(
+        default_connect_args,
+        &crate::scripting::ConnectContext {
+            name: vcluster_name.clone(),
+            namespace: vcluster_ns.clone(),
+            status: vcluster_status.clone(),
+        },
+    );
+    #[cfg(not(feature = "scripting"))]
+    let connect_args = default_connect_args;
 
-        match connect_cmd.spawn() {
-            Ok(child) => {
-                let pid = child.id();
-                if let Ok(mut pid_guard) = pid_store.lock() {
-                    *pid_guard = Some(pid);
-                }
-                info!("[vcluster] Connect process started with PID: {}", pid);
+    let mut connect_cmd = tokio::process::Command::new("vcluster");
+    connect_cmd.args(&connect_args)
+        .env("PATH", &new_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
 
-                // Don't wait for the process - it runs continuously
-                // The process will be managed by the OS
-                Some(pid)
-            }
-            Err(e) => {
-                error!("[vcluster] Failed to spawn vcluster connect: {}", e);
-                None
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        connect_cmd.creation_flags(0x08000000);
+    }
+
+    // Captured stderr from the child's piped stream, kept live for the
+    // duration of this connect attempt so both the context-wait timeout below
+    // and the supervisor's unexpected-exit diagnostics can read it.
+    let captured_stderr = Arc::new(TokioMutex::new(String::new()));
+
+    // Spawn the vcluster connect child and hand it to a supervisor task that
+    // owns it for as long as it runs, instead of recording a bare PID that
+    // outlives our ability to manage it cleanly.
+    let pid = match connect_cmd.spawn() {
+        Ok(mut child) => {
+            let pid = child.id().unwrap_or(0);
+            let stdout = child.stdout.take();
+            let stderr = child.stderr.take();
+
+            tauri::async_runtime::spawn(stream_connect_output(
+                stdout,
+                stderr,
+                captured_stderr.clone(),
+                app.clone(),
+            ));
+
+            {
+                let mut guard = state.vcluster_child.lock().await;
+                *guard = Some(child);
             }
-        }
-    });
 
-    // Wait a moment for the process to start
-    sleep(Duration::from_millis(500)).await;
+            let (cancel_tx, cancel_rx) = tokio::sync::mpsc::unbounded_channel();
+            if let Ok(mut slot) = state.vcluster_connect_cancel.lock() {
+                *slot = Some(cancel_tx);
+            }
 
-    // Check if spawn succeeded
-    match connect_handle.join() {
-        Ok(Some(pid)) => {
-            emit_ok("connect", &format!("Connection process started (PID: {})", pid), 30);
+            info!("[vcluster] Connect process started with PID: {}", pid);
+            let supervised_app = app.clone();
+            let supervised_child = state.vcluster_child.clone();
+            let supervised_cancel = state.vcluster_cancel_connect.clone();
+            let supervised_stderr = captured_stderr.clone();
+            let supervised_name = vcluster_name.clone();
+            let supervised_ns = vcluster_ns.clone();
+            tauri::async_runtime::spawn(async move {
+                supervise_connect_process(
+                    supervised_child,
+                    supervised_cancel,
+                    supervised_stderr,
+                    cancel_rx,
+                    supervised_app,
+                    supervised_name,
+                    supervised_ns,
+                ).await;
+            });
+
+            pid
         }
-        Ok(None) | Err(_) => {
-            let err_path = std::env::temp_dir().join("vcluster-connect.err");
-            let err_log = std::fs::read_to_string(&err_path).unwrap_or_default();
-            let err = VClusterError::command_failed("vcluster connect", &err_log);
+        Err(e) => {
+            error!("[vcluster] Failed to spawn vcluster connect: {}", e);
+            let err = VClusterError::command_failed("vcluster connect", &e.to_string());
             emit_err("connect", &err);
             return Err(err.to_string());
         }
-    }
+    };
+
+    // Wait a moment for the process to start
+    sleep(Duration::from_millis(500)).await;
+
+    emit_ok("connect", &format!("Connection process started (PID: {})", pid), 30);
 
     // ========== Stage 3: Wait for context to appear ==========
     emit_ok("context", "Waiting for vcluster context...", 35);
@@ -631,8 +1088,14 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
     let context_timeout = 30; // seconds
     let mut found_context = String::new();
     let start = std::time::Instant::now();
+    let context_prefix = format!("vcluster_{}_{}", name, namespace);
+
+    // Host-cluster StatefulSet/Pod readiness drives the progress updates
+    // (far less brittle than string-matching context names); once the
+    // vcluster's workload is actually ready we do one kubeconfig read to
+    // pick up the context name vcluster's connect process created for it.
+    let host_client = crate::client::create_client(state.clone()).await.ok();
 
-    // Poll for context with progress updates
     loop {
         let elapsed = start.elapsed().as_secs();
         if elapsed >= context_timeout {
@@ -641,25 +1104,31 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
 
         // Update progress (35 -> 70 over 30 seconds)
         let progress = 35 + ((elapsed as u8 * 35) / context_timeout as u8).min(35);
-        emit_ok("context", &format!("Waiting for context... ({}/{}s)", elapsed, context_timeout), progress);
 
-        // Check for context
-        let mut cmd = Command::new("kubectl");
-        cmd.args(["config", "get-contexts", "-o", "name"]);
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            cmd.creation_flags(0x08000000);
-        }
-
-        if let Ok(output) = cmd.output() {
-            if output.status.success() {
-                let contexts = String::from_utf8_lossy(&output.stdout);
-                let context_prefix = format!("vcluster_{}_{}", name, namespace);
+        let is_ready = if let Some(client) = &host_client {
+            match discover_vcluster_readiness(client, &name, &namespace).await {
+                Ok(discovery) => {
+                    emit_ok("context", &format!(
+                        "Waiting for vcluster pods to become ready... ({}/{} ready, {}/{}s)",
+                        discovery.ready_replicas, discovery.replicas, elapsed, context_timeout
+                    ), progress);
+                    discovery.ready
+                }
+                Err(_) => {
+                    emit_ok("context", &format!("Waiting for context... ({}/{}s)", elapsed, context_timeout), progress);
+                    false
+                }
+            }
+        } else {
+            emit_ok("context", &format!("Waiting for context... ({}/{}s)", elapsed, context_timeout), progress);
+            false
+        };
 
-                if let Some(ctx) = contexts.lines().find(|c| c.starts_with(&context_prefix)) {
-                    found_context = ctx.to_string();
-                    info!("[vcluster] Found context: {}", found_context);
+        if is_ready {
+            if let Ok(kubeconfig) = kube::config::Kubeconfig::read() {
+                if let Some(ctx) = kubeconfig.contexts.iter().find(|c| c.name.starts_with(&context_prefix)) {
+                    found_context = ctx.name.clone();
+                    info!("[vcluster] vcluster ready, found context: {}", found_context);
                     break;
                 }
             }
@@ -669,10 +1138,10 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
     }
 
     if found_context.is_empty() {
-        // Read error log for details
-        let err_path = std::env::temp_dir().join("vcluster-connect.err");
-        let err_log = std::fs::read_to_string(&err_path).unwrap_or_default();
-        
+        // Read captured stderr for details (populated once the child's
+        // stderr pipe closes, i.e. the process has already exited)
+        let err_log = captured_stderr.lock().await.clone();
+
         // Also read debug log
         let debug_log_path = std::env::temp_dir().join("vcluster-debug.log");
         let debug_log = std::fs::read_to_string(&debug_log_path).unwrap_or_default();
@@ -713,30 +1182,12 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
     emit_ok("switch", "Switching to vcluster context...", 78);
     info!("[vcluster] Stage 4: Switching to context: {}", found_context);
 
-    let mut switch_cmd = Command::new("kubectl");
-    switch_cmd.args(["config", "use-context", &found_context]);
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        switch_cmd.creation_flags(0x08000000);
-    }
-
-    match switch_cmd.output() {
-        Ok(output) => {
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                let err = VClusterError::command_failed("kubectl config use-context", &stderr);
-                emit_err("switch", &err);
-                return Err(err.to_string());
-            }
-            emit_ok("switch", "Context switched successfully", 82);
-        }
-        Err(e) => {
-            let err = VClusterError::command_failed("kubectl config use-context", &e.to_string());
-            emit_err("switch", &err);
-            return Err(err.to_string());
-        }
+    if let Err(e) = set_current_context(&found_context) {
+        let err = VClusterError::command_failed("switch kubeconfig context", &e);
+        emit_err("switch", &err);
+        return Err(err.to_string());
     }
+    emit_ok("switch", "Context switched successfully", 82);
 
     // ========== Stage 5: Verify cluster connection ==========
     emit_ok("verify", "Verifying cluster connection...", 85);
@@ -748,37 +1199,28 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
     for attempt in 1..=max_verify_attempts {
         emit_ok("verify", &format!("Verifying connection (attempt {}/{})", attempt, max_verify_attempts), 85 + (attempt as u8 * 2).min(10));
 
-        let mut verify_cmd = Command::new("kubectl");
-        verify_cmd.args(["get", "ns", "--request-timeout=5s"]);
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            verify_cmd.creation_flags(0x08000000);
-        }
-
-        match verify_cmd.output() {
-            Ok(output) => {
-                if output.status.success() {
-                    info!("[vcluster] Connection verified on attempt {}", attempt);
-                    emit_ok("verify", "Cluster connection verified!", 95);
-                    break;
-                }
-
-                if attempt == max_verify_attempts {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    let err = VClusterError::cluster_unreachable(&stderr);
-                    error!("[vcluster] Verification failed after {} attempts", max_verify_attempts);
-                    emit_err("verify", &err);
-                    return Err(err.to_string());
-                }
-            }
-            Err(e) => {
-                if attempt == max_verify_attempts {
-                    let err = VClusterError::cluster_unreachable(&e.to_string());
-                    emit_err("verify", &err);
-                    return Err(err.to_string());
+        let verify_err = match crate::client::build_client(None, Some(&found_context)).await {
+            Ok(client) => {
+                let namespaces: kube::Api<k8s_openapi::api::core::v1::Namespace> = kube::Api::all(client);
+                match namespaces.list(&kube::api::ListParams::default().limit(1)).await {
+                    Ok(_) => None,
+                    Err(e) => Some(classify_kube_error(&e)),
                 }
             }
+            Err(e) => Some(VClusterError::cluster_unreachable(&e)),
+        };
+
+        let Some(err) = verify_err else {
+            info!("[vcluster] Connection verified on attempt {}", attempt);
+            emit_ok("verify", "Cluster connection verified!", 95);
+            break;
+        };
+
+        let is_permission_denied = err.code == "PERMISSION_DENIED";
+        if is_permission_denied || attempt == max_verify_attempts {
+            error!("[vcluster] Verification failed after {} attempt(s)", attempt);
+            emit_err("verify", &err);
+            return Err(err.to_string());
         }
 
         warn!("[vcluster] Verification attempt {} failed, retrying in {}s...", attempt, verify_delay);
@@ -802,6 +1244,14 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
         *ctx = Some(found_context.clone());
     }
 
+    // Register this vcluster's client for fan-out queries, independent of
+    // whatever ends up as the single `selected_context`.
+    if let Ok(client) = crate::client::build_client(None, Some(&found_context)).await {
+        if let Ok(mut clients) = state.vcluster_clients.lock() {
+            clients.insert(vcluster_client_key(&name, &namespace), client);
+        }
+    }
+
     emit_ok("complete", &format!("Connected to vcluster '{}'", name), 100);
     info!("[vcluster] Successfully connected to vcluster {}/{}", namespace, name);
 
@@ -809,7 +1259,37 @@ pub async fn connect_vcluster(name: String, namespace: String, state: State<'_,
 }
 
 #[tauri::command]
-pub async fn disconnect_vcluster(state: State<'_, AppState>) -> Result<String, String> {
+pub async fn disconnect_vcluster(name: String, namespace: String, state: State<'_, AppState>, app: tauri::AppHandle) -> Result<String, String> {
+    let emit_progress = |stage: &str, message: &str, progress: u8, is_error: bool, error_code: Option<&str>, suggestion: Option<&str>| {
+        let _ = app.emit("vcluster-disconnect-progress", VClusterConnectProgress {
+            stage: stage.to_string(),
+            message: message.to_string(),
+            progress,
+            is_error,
+            error_code: error_code.map(|s| s.to_string()),
+            suggestion: suggestion.map(|s| s.to_string()),
+        });
+    };
+    let emit_ok = |stage: &str, message: &str, progress: u8| {
+        emit_progress(stage, message, progress, false, None, None);
+    };
+
+    info!("[vcluster] Disconnecting from {}/{}", namespace, name);
+    emit_ok("teardown", "Stopping vcluster connect process...", 10);
+
+    // Kill any supervised `vcluster connect` child cleanly rather than
+    // leaving it for `kill_stale_vcluster_processes`'s pkill fallback. The
+    // supervisor itself performs the graceful-SIGTERM-then-kill escalation
+    // (see `supervise_connect_process`) since it, not us, holds the child's
+    // lock for its whole lifetime.
+    if let Ok(mut slot) = state.vcluster_cancel_connect.lock() {
+        if let Some(tx) = slot.take() {
+            let _ = tx.send(());
+        }
+    }
+
+    emit_ok("teardown", "Running vcluster disconnect...", 30);
+
     // Run "vcluster disconnect" with timeout
     let output_result = tokio::time::timeout(
         std::time::Duration::from_secs(5),
@@ -818,25 +1298,44 @@ pub async fn disconnect_vcluster(state: State<'_, AppState>) -> Result<String, S
 
     let output = match output_result {
         Ok(Ok(out)) => out,
-        Ok(Err(e)) => return Err(format!("Failed to execute vcluster command: {}", e)),
-        Err(_) => return Err("vcluster disconnect timed out".to_string()),
+        Ok(Err(e)) => {
+            let err = VClusterError::command_failed("vcluster disconnect", &e.to_string());
+            emit_progress("teardown", &err.message, 0, true, Some(&err.code), err.suggestion.as_deref());
+            return Err(err.to_string());
+        }
+        Err(_) => {
+            let err = VClusterError::command_failed("vcluster disconnect", "timed out after 5s");
+            emit_progress("teardown", &err.message, 0, true, Some(&err.code), err.suggestion.as_deref());
+            return Err(err.to_string());
+        }
     };
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to disconnect: {}", stderr));
+        let err = VClusterError::command_failed("vcluster disconnect", &stderr);
+        emit_progress("teardown", &err.message, 0, true, Some(&err.code), err.suggestion.as_deref());
+        return Err(err.to_string());
+    }
+
+    // Drop the client we were keeping around for fan-out queries and clean
+    // up the kubeconfig context `connect_vcluster` created for this vcluster.
+    emit_ok("cleanup", "Removing vcluster context...", 60);
+    if let Ok(mut clients) = state.vcluster_clients.lock() {
+        clients.remove(&vcluster_client_key(&name, &namespace));
+    }
+    let context_prefix = format!("vcluster_{}_{}", name, namespace);
+    if let Err(e) = delete_contexts_by_prefix(&context_prefix) {
+        warn!("[vcluster] Failed to clean up context(s) prefixed '{}': {}", context_prefix, e);
     }
 
     // After disconnect, vcluster CLI switches context back to the host.
     // We need to verify what the current context is now.
-    let context_output = Command::new("kubectl")
-        .args(["config", "current-context"])
-        .output()
-        .map_err(|e| format!("Failed to get current context: {}", e))?;
-
-    if context_output.status.success() {
-         let new_context = String::from_utf8_lossy(&context_output.stdout).trim().to_string();
-         
+    emit_ok("cleanup", "Restoring host context...", 80);
+    let current_context = kube::config::Kubeconfig::read()
+        .ok()
+        .and_then(|kc| kc.current_context);
+
+    if let Some(new_context) = current_context {
          // Update state with the restored context
          if let Ok(mut ctx) = state.selected_context.lock() {
              *ctx = Some(new_context.clone());
@@ -850,8 +1349,377 @@ pub async fn disconnect_vcluster(state: State<'_, AppState>) -> Result<String, S
          if let Ok(mut cache) = state.client_cache.try_lock() { *cache = None; }
          if let Ok(mut cache) = state.initial_data_cache.try_lock() { *cache = None; }
 
+         emit_ok("complete", &format!("Disconnected. Switched to context: {}", new_context), 100);
          return Ok(format!("Disconnected. Switched to context: {}", new_context));
     }
 
+    emit_ok("complete", "Disconnected from vcluster", 100);
     Ok("Disconnected from vcluster".to_string())
 }
+
+// ========== Multi-vcluster fan-out ==========
+//
+// `selected_context`/`client_cache` only ever track one "active" vcluster.
+// `state.vcluster_clients` (populated by `connect_vcluster`) keeps a client
+// per connected vcluster so a query can be dispatched to several of them at
+// once. Result combination follows redis-rs's cluster response policies.
+
+fn vcluster_client_key(name: &str, namespace: &str) -> String {
+    format!("{}/{}", name, namespace)
+}
+
+/// How to combine per-vcluster results from a fan-out query.
+/// Modeled after redis-rs's cluster response policies.
+#[derive(Debug, Clone, Deserialize)]
+pub enum ResponsePolicy {
+    /// Fail the whole call if any target errors.
+    AllSucceeded,
+    /// Return as soon as the first target succeeds; the rest keep running in the background.
+    FirstSuccess,
+    /// Collect every target's outcome, success or failure, for the UI to render side-by-side.
+    Aggregate,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VClusterTarget {
+    pub name: String,
+    pub namespace: String,
+}
+
+#[derive(Clone, Serialize)]
+pub struct VClusterStatsResult {
+    pub name: String,
+    pub namespace: String,
+    pub stats: Option<ClusterStats>,
+    pub error: Option<VClusterError>,
+}
+
+async fn fetch_vcluster_stats(state: &State<'_, AppState>, target: &VClusterTarget) -> VClusterStatsResult {
+    let client = state.vcluster_clients.lock().ok()
+        .and_then(|clients| clients.get(&vcluster_client_key(&target.name, &target.namespace)).cloned());
+
+    let Some(client) = client else {
+        return VClusterStatsResult {
+            name: target.name.clone(),
+            namespace: target.namespace.clone(),
+            stats: None,
+            error: Some(VClusterError {
+                code: "VCLUSTER_NOT_CONNECTED".to_string(),
+                message: format!("vcluster '{}' in namespace '{}' is not connected", target.name, target.namespace),
+                details: None,
+                suggestion: Some("Call connect_vcluster for this vcluster first.".to_string()),
+            }),
+        };
+    };
+
+    match super::cluster::compute_cluster_stats(&client).await {
+        Ok(stats) => VClusterStatsResult { name: target.name.clone(), namespace: target.namespace.clone(), stats: Some(stats), error: None },
+        Err(e) => VClusterStatsResult { name: target.name.clone(), namespace: target.namespace.clone(), stats: None, error: Some(VClusterError::cluster_unreachable(&e)) },
+    }
+}
+
+/// Dispatch a cluster-stats query to several connected vclusters concurrently
+/// and combine the results per `policy`.
+#[tauri::command]
+pub async fn fanout_cluster_stats(
+    state: State<'_, AppState>,
+    targets: Vec<VClusterTarget>,
+    policy: ResponsePolicy,
+) -> Result<Vec<VClusterStatsResult>, String> {
+    match policy {
+        ResponsePolicy::AllSucceeded => {
+            let results = futures::future::join_all(targets.iter().map(|t| fetch_vcluster_stats(&state, t))).await;
+            if let Some(failed) = results.iter().find(|r| r.error.is_some()) {
+                let err = failed.error.clone().unwrap();
+                return Err(format!("{}/{}: {}", failed.namespace, failed.name, err.message));
+            }
+            Ok(results)
+        }
+        ResponsePolicy::FirstSuccess => {
+            let mut futs: Vec<_> = targets.iter().map(|t| Box::pin(fetch_vcluster_stats(&state, t))).collect();
+            let mut last_err = None;
+            while !futs.is_empty() {
+                let (result, _index, remaining) = futures::future::select_all(futs).await;
+                if result.error.is_none() {
+                    return Ok(vec![result]);
+                }
+                last_err = result.error;
+                futs = remaining;
+            }
+            Err(last_err.map(|e| e.message).unwrap_or_else(|| "No vcluster targets given".to_string()))
+        }
+        ResponsePolicy::Aggregate => {
+            Ok(futures::future::join_all(targets.iter().map(|t| fetch_vcluster_stats(&state, t))).await)
+        }
+    }
+}
+
+// ========== Exec / port-forward into connected vclusters ==========
+//
+// Built on top of `state.vcluster_clients` (populated by `connect_vcluster`)
+// and the `kube` crate's websocket-backed `exec`/`portforward`, the same
+// feature `networking::start_port_forward` already relies on.
+
+fn get_vcluster_client(state: &State<'_, AppState>, name: &str, namespace: &str) -> Result<kube::Client, VClusterError> {
+    state.vcluster_clients.lock().ok()
+        .and_then(|clients| clients.get(&vcluster_client_key(name, namespace)).cloned())
+        .ok_or_else(|| VClusterError {
+            code: "VCLUSTER_NOT_CONNECTED".to_string(),
+            message: format!("vcluster '{}' in namespace '{}' is not connected", name, namespace),
+            details: None,
+            suggestion: Some("Call connect_vcluster for this vcluster first.".to_string()),
+        })
+}
+
+/// One line of combined stdout/stderr from `vcluster_exec`, streamed to the
+/// frontend as it's produced (same event shape as `VClusterConnectLog`).
+#[derive(Debug, Clone, Serialize)]
+pub struct VClusterExecOutput {
+    pub stream: String, // "stdout" | "stderr"
+    pub line: String,
+}
+
+fn emit_exec_output(app: &tauri::AppHandle, stream: &str, line: String) {
+    let _ = app.emit("vcluster-exec-output", VClusterExecOutput { stream: stream.to_string(), line });
+}
+
+fn emit_exec_error(app: &tauri::AppHandle, err: &VClusterError) {
+    let _ = app.emit("vcluster-exec-error", err.clone());
+}
+
+/// Exec a command in a pod inside a connected vcluster, streaming combined
+/// stdout/stderr back to the frontend as `vcluster-exec-output` events.
+#[tauri::command]
+pub async fn vcluster_exec(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    vcluster_name: String,
+    vcluster_namespace: String,
+    pod_name: String,
+    pod_namespace: String,
+    container: Option<String>,
+    command: Vec<String>,
+) -> Result<(), String> {
+    let client = match get_vcluster_client(&state, &vcluster_name, &vcluster_namespace) {
+        Ok(c) => c,
+        Err(e) => { emit_exec_error(&app, &e); return Err(e.to_string()); }
+    };
+
+    let pods: kube::Api<k8s_openapi::api::core::v1::Pod> = kube::Api::namespaced(client, &pod_namespace);
+
+    let mut ap = kube::api::AttachParams::default().stdout(true).stderr(true);
+    if let Some(container) = &container {
+        ap = ap.container(container);
+    }
+
+    let mut attached = match pods.exec(&pod_name, command, &ap).await {
+        Ok(a) => a,
+        Err(e) => {
+            let err = classify_kube_error(&e);
+            emit_exec_error(&app, &err);
+            return Err(err.to_string());
+        }
+    };
+
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    let mut stdout_lines = attached.stdout().map(|s| BufReader::new(s).lines());
+    let mut stderr_lines = attached.stderr().map(|s| BufReader::new(s).lines());
+
+    while stdout_lines.is_some() || stderr_lines.is_some() {
+        tokio::select! {
+            result = async {
+                match &mut stdout_lines {
+                    Some(lines) => lines.next_line().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                match result {
+                    Ok(Some(line)) => emit_exec_output(&app, "stdout", line),
+                    _ => stdout_lines = None,
+                }
+            }
+            result = async {
+                match &mut stderr_lines {
+                    Some(lines) => lines.next_line().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                match result {
+                    Ok(Some(line)) => emit_exec_output(&app, "stderr", line),
+                    _ => stderr_lines = None,
+                }
+            }
+        }
+    }
+
+    if let Some(status) = attached.take_status() {
+        if let Some(status) = status.await {
+            if status.status.as_deref() == Some("Failure") {
+                let err = VClusterError::command_failed(
+                    &format!("exec in pod {}/{}", pod_namespace, pod_name),
+                    status.message.as_deref().unwrap_or("command failed"),
+                );
+                emit_exec_error(&app, &err);
+                return Err(err.to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Open a port-forward to a pod inside a connected vcluster and return the
+/// local address it's bound to (`127.0.0.1:<port>`), mirroring
+/// `networking::start_port_forward` but sourced from the vcluster's own client.
+#[tauri::command]
+pub async fn vcluster_port_forward(
+    state: State<'_, AppState>,
+    vcluster_name: String,
+    vcluster_namespace: String,
+    pod_name: String,
+    pod_namespace: String,
+    pod_port: u16,
+) -> Result<String, String> {
+    let client = get_vcluster_client(&state, &vcluster_name, &vcluster_namespace).map_err(|e| e.to_string())?;
+    let pods: kube::Api<k8s_openapi::api::core::v1::Pod> = kube::Api::namespaced(client, &pod_namespace);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await
+        .map_err(|e| VClusterError::command_failed("bind local port-forward socket", &e.to_string()).to_string())?;
+    let local_addr = listener.local_addr().map_err(|e| e.to_string())?;
+    let session_id = format!("vcluster-{}-{}-{}-{}", vcluster_name, pod_namespace, pod_name, local_addr.port());
+    let session_pod_name = pod_name.clone();
+
+    let handle = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((mut socket, _)) => {
+                    let pods = pods.clone();
+                    let pod_name = pod_name.clone();
+                    tokio::spawn(async move {
+                        let mut pf = match pods.portforward(&pod_name, &[pod_port]).await {
+                            Ok(pf) => pf,
+                            Err(e) => { warn!("[vcluster] port-forward to {} failed: {}", pod_name, e); return; }
+                        };
+                        let Some(mut upstream) = pf.take_stream(pod_port) else { return; };
+                        if let Err(e) = tokio::io::copy_bidirectional(&mut socket, &mut upstream).await {
+                            warn!("[vcluster] port-forward connection error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => warn!("[vcluster] port-forward listener accept error: {}", e),
+            }
+        }
+    });
+
+    let session = crate::state::PortForwardSession {
+        id: session_id.clone(),
+        pod_name: session_pod_name,
+        kind: "Pod".to_string(),
+        namespace: pod_namespace,
+        local_port: local_addr.port(),
+        pod_port,
+        handle,
+        status: Arc::new(std::sync::Mutex::new(crate::state::PortForwardStatus::Connected)),
+        counters: Arc::new(crate::state::PortForwardCounters::default()),
+    };
+    state.port_forwards.lock().unwrap().insert(session_id, session);
+
+    Ok(local_addr.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `CommandRunner` that fails every call with the given `io::Error`,
+    /// for simulating a `vcluster` binary that can't reach its backing
+    /// cluster (connection refused, timed out, etc.) rather than one that's
+    /// merely missing.
+    struct ErroringCommandRunner {
+        kind: std::io::ErrorKind,
+    }
+
+    impl CommandRunner for ErroringCommandRunner {
+        fn output(&self, _cmd: Command) -> std::io::Result<std::process::Output> {
+            Err(std::io::Error::new(self.kind, "connection refused"))
+        }
+    }
+
+    // Fixture: `vcluster list --output json` succeeds but reports the
+    // target vcluster as still spinning up. Expected: `check_vcluster_status`
+    // returns its raw status string so the preflight check in
+    // `connect_vcluster` can turn it into `VCLUSTER_NOT_READY`.
+    const FIXTURE_LIST_PENDING: &str = r#"[
+        {"Name": "dev", "Namespace": "team-a", "Status": "Pending", "Created": "2026-01-01T00:00:00Z"}
+    ]"#;
+
+    // Fixture: `vcluster list --output json` returns truncated/invalid JSON
+    // (e.g. the CLI crashed mid-write). Expected: `list_vclusters_with`
+    // surfaces a parse error instead of silently dropping vclusters.
+    const FIXTURE_LIST_MALFORMED: &str = r#"[{"Name": "dev", "Namespace":"#;
+
+    // Fixture: the `items`-wrapper shape some `vcluster` CLI versions emit
+    // instead of a bare array.
+    const FIXTURE_LIST_ITEMS_WRAPPER: &str = r#"{"items": [
+        {"name": "dev", "namespace": "team-a", "status": "Running", "created": "2026-01-01T00:00:00Z"}
+    ]}"#;
+
+    fn mock(stdout: &str, success: bool) -> MockCommandRunner {
+        MockCommandRunner {
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: Vec::new(),
+            success,
+        }
+    }
+
+    #[test]
+    fn check_vcluster_status_reports_not_ready_status() {
+        let runner = mock(FIXTURE_LIST_PENDING, true);
+        let status = check_vcluster_status(&runner, "dev", "team-a").expect("status lookup should succeed");
+        assert_eq!(status, "Pending");
+        assert_ne!(status.to_lowercase(), "running");
+    }
+
+    #[test]
+    fn check_vcluster_status_reports_unknown_for_unlisted_vcluster() {
+        let runner = mock(FIXTURE_LIST_PENDING, true);
+        let status = check_vcluster_status(&runner, "other", "team-b").expect("status lookup should succeed");
+        assert_eq!(status, "unknown");
+    }
+
+    #[test]
+    fn list_vclusters_with_rejects_malformed_json() {
+        let runner = mock(FIXTURE_LIST_MALFORMED, true);
+        let err = list_vclusters_with(&runner).expect_err("malformed JSON should not parse");
+        assert!(err.contains("Failed to parse vcluster JSON"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn list_vclusters_with_parses_items_wrapper_shape() {
+        let runner = mock(FIXTURE_LIST_ITEMS_WRAPPER, true);
+        let vclusters = list_vclusters_with(&runner).expect("items-wrapper shape should parse");
+        assert_eq!(vclusters.len(), 1);
+        assert_eq!(vclusters[0].name, "dev");
+        assert_eq!(vclusters[0].status, "Running");
+    }
+
+    #[test]
+    fn list_vclusters_with_surfaces_unreachable_backend() {
+        // Neither `list_vclusters_with` nor `check_vcluster_status` can turn
+        // an `io::Error` into `VClusterError::cluster_unreachable` directly
+        // (that conversion lives in `classify_kube_error`, downstream of the
+        // kube-rs client rather than the vcluster CLI), but both must still
+        // fail loudly instead of reporting an empty list when the binary
+        // can't be reached at all.
+        let runner = ErroringCommandRunner { kind: std::io::ErrorKind::ConnectionRefused };
+        let err = list_vclusters_with(&runner).expect_err("unreachable backend should error, not return empty");
+        assert!(err.contains("Failed to execute vcluster command"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn cluster_unreachable_error_carries_details_and_code() {
+        let err = VClusterError::cluster_unreachable("dial tcp: connection refused");
+        assert_eq!(err.code, "CLUSTER_UNREACHABLE");
+        assert_eq!(err.details.as_deref(), Some("dial tcp: connection refused"));
+    }
+}
@@ -0,0 +1,254 @@
+//! Re-exposes a connected vcluster's API server beyond this machine.
+//!
+//! `vcluster connect` already stands up a local proxy bound to
+//! `127.0.0.1` (see `commands::vcluster::connect_vcluster`), which is why
+//! only the machine running OpsPilot can reach it. This module adds a
+//! tunnel on top: a plain TCP relay that listens on every interface and
+//! forwards each connection to that existing local proxy, plus a
+//! generated kubeconfig pointing at the relay's advertised address so a
+//! teammate can `kubectl --kubeconfig ...` straight into it.
+//!
+//! This is a network-level relay, not a re-authenticating one: the
+//! security boundary is still whatever client cert/token is already
+//! baked into the vcluster's kubeconfig context, the same as today -
+//! opening the tunnel just widens who can reach that same door.
+
+use log::{info, warn};
+use serde::Serialize;
+use std::net::SocketAddr;
+use tauri::{Emitter, State};
+use tokio::net::{TcpListener, TcpStream};
+use crate::AppState;
+use crate::commands::vcluster::VClusterError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VClusterTunnelProgress {
+    pub stage: String,
+    pub message: String,
+    pub progress: u8,
+    pub is_error: bool,
+    pub error_code: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VClusterTunnelInfo {
+    pub id: String,
+    pub connection_string: String,
+    pub kubeconfig_path: String,
+}
+
+fn tunnel_id(name: &str, namespace: &str) -> String {
+    format!("{}/{}", name, namespace)
+}
+
+/// Best-effort discovery of this machine's LAN-facing IP: connecting a UDP
+/// socket doesn't send any packets, it just asks the OS which local
+/// interface/address would be used to reach the given remote. Falls back
+/// to the loopback address (tunnel still works for same-machine testing).
+fn local_advertised_ip() -> std::net::IpAddr {
+    std::net::UdpSocket::bind("0.0.0.0:0")
+        .and_then(|sock| {
+            sock.connect("8.8.8.8:80")?;
+            sock.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST))
+}
+
+/// Find the `vcluster_{name}_{namespace}*` context in the default
+/// kubeconfig and return the loopback `(host, port)` its cluster entry's
+/// `server` points at, along with the context/cluster/user YAML blocks
+/// needed to build a standalone kubeconfig for the tunnel.
+fn find_vcluster_kubeconfig_entry(
+    name: &str,
+    namespace: &str,
+) -> Result<(SocketAddr, serde_yaml::Value, serde_yaml::Value, serde_yaml::Value), String> {
+    let config = kube::config::Kubeconfig::read().map_err(|e| format!("Failed to read kubeconfig: {}", e))?;
+    let prefix = format!("vcluster_{}_{}", name, namespace);
+
+    let named_context = config.contexts.iter()
+        .find(|c| c.name.starts_with(&prefix))
+        .ok_or_else(|| format!("No kubeconfig context found for vcluster {}/{}", name, namespace))?;
+    let context_ref = named_context.context.as_ref()
+        .ok_or_else(|| format!("Context '{}' has no context data", named_context.name))?;
+
+    let cluster_name = context_ref.cluster.clone();
+    let user_name = context_ref.user.clone();
+
+    let named_cluster = config.clusters.iter()
+        .find(|c| c.name == cluster_name)
+        .ok_or_else(|| format!("No cluster entry named '{}'", cluster_name))?;
+    let cluster_ref = named_cluster.cluster.as_ref()
+        .ok_or_else(|| format!("Cluster '{}' has no cluster data", cluster_name))?;
+
+    let server = cluster_ref.server.clone()
+        .ok_or_else(|| format!("Cluster '{}' has no server URL", cluster_name))?;
+    let host_port = server.trim_start_matches("https://").trim_start_matches("http://").trim_end_matches('/');
+    let addr: SocketAddr = host_port.parse()
+        .map_err(|e| format!("Server URL '{}' did not resolve to a loopback host:port: {}", server, e))?;
+    if !addr.ip().is_loopback() {
+        return Err(format!("Refusing to tunnel a non-local vcluster server address: {}", server));
+    }
+
+    let named_user = config.auth_infos.iter()
+        .find(|u| u.name == user_name)
+        .ok_or_else(|| format!("No user entry named '{}'", user_name))?;
+
+    let context_yaml = serde_yaml::to_value(named_context).map_err(|e| e.to_string())?;
+    let cluster_yaml = serde_yaml::to_value(named_cluster).map_err(|e| e.to_string())?;
+    let user_yaml = serde_yaml::to_value(named_user).map_err(|e| e.to_string())?;
+
+    Ok((addr, context_yaml, cluster_yaml, user_yaml))
+}
+
+/// Write a standalone kubeconfig for the tunnel: the vcluster's existing
+/// context/user entries, but with the cluster's `server` rewritten to the
+/// relay's advertised address instead of the local loopback proxy.
+fn write_tunnel_kubeconfig(
+    name: &str,
+    namespace: &str,
+    context_yaml: serde_yaml::Value,
+    mut cluster_yaml: serde_yaml::Value,
+    user_yaml: serde_yaml::Value,
+    advertised_addr: SocketAddr,
+) -> Result<std::path::PathBuf, String> {
+    if let Some(cluster) = cluster_yaml.get_mut("cluster") {
+        cluster["server"] = serde_yaml::Value::String(format!("https://{}", advertised_addr));
+    }
+
+    let doc = serde_yaml::Mapping::from_iter([
+        (serde_yaml::Value::String("apiVersion".into()), serde_yaml::Value::String("v1".into())),
+        (serde_yaml::Value::String("kind".into()), serde_yaml::Value::String("Config".into())),
+        (serde_yaml::Value::String("current-context".into()), context_yaml.get("name").cloned().unwrap_or(serde_yaml::Value::Null)),
+        (serde_yaml::Value::String("contexts".into()), serde_yaml::Value::Sequence(vec![context_yaml])),
+        (serde_yaml::Value::String("clusters".into()), serde_yaml::Value::Sequence(vec![cluster_yaml])),
+        (serde_yaml::Value::String("users".into()), serde_yaml::Value::Sequence(vec![user_yaml])),
+    ]);
+
+    let home = dirs::home_dir().ok_or("Could not find HOME directory")?;
+    let tunnels_dir = home.join(".opspilot").join("tunnels");
+    std::fs::create_dir_all(&tunnels_dir).map_err(|e| format!("Failed to create tunnels directory: {}", e))?;
+    let path = tunnels_dir.join(format!("{}-{}.yaml", name, namespace));
+
+    let content = serde_yaml::to_string(&serde_yaml::Value::Mapping(doc))
+        .map_err(|e| format!("Failed to serialize tunnel kubeconfig: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write tunnel kubeconfig: {}", e))?;
+
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn start_vcluster_tunnel(
+    name: String,
+    namespace: String,
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<VClusterTunnelInfo, String> {
+    let emit_progress = |stage: &str, message: &str, progress: u8, is_error: bool, error_code: Option<&str>, suggestion: Option<&str>| {
+        let _ = app.emit("vcluster-tunnel-progress", VClusterTunnelProgress {
+            stage: stage.to_string(),
+            message: message.to_string(),
+            progress,
+            is_error,
+            error_code: error_code.map(|s| s.to_string()),
+            suggestion: suggestion.map(|s| s.to_string()),
+        });
+    };
+    let emit_ok = |stage: &str, message: &str, progress: u8| emit_progress(stage, message, progress, false, None, None);
+    let emit_err = |stage: &str, err: &VClusterError| emit_progress(stage, &err.message, 0, true, Some(&err.code), err.suggestion.as_deref());
+
+    let id = tunnel_id(&name, &namespace);
+    if state.vcluster_tunnels.lock().unwrap().contains_key(&id) {
+        return Err(format!("A tunnel for vcluster {}/{} is already running", name, namespace));
+    }
+
+    emit_ok("registering", &format!("Looking up local proxy for vcluster '{}'...", name), 10);
+    let (local_addr, context_yaml, cluster_yaml, user_yaml) = match find_vcluster_kubeconfig_entry(&name, &namespace) {
+        Ok(v) => v,
+        Err(e) => {
+            let err = VClusterError::cluster_unreachable(&e);
+            emit_err("registering", &err);
+            return Err(err.to_string());
+        }
+    };
+
+    emit_ok("authenticating", "Carrying over the vcluster's existing credentials...", 40);
+    let advertised_ip = local_advertised_ip();
+
+    let listener = match TcpListener::bind("0.0.0.0:0").await {
+        Ok(l) => l,
+        Err(e) => {
+            let err = VClusterError::command_failed("bind tunnel listener", &e.to_string());
+            emit_err("authenticating", &err);
+            return Err(err.to_string());
+        }
+    };
+    let bind_port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let advertised_addr = SocketAddr::new(advertised_ip, bind_port);
+
+    let kubeconfig_path = match write_tunnel_kubeconfig(&name, &namespace, context_yaml, cluster_yaml, user_yaml, advertised_addr) {
+        Ok(p) => p,
+        Err(e) => {
+            let err = VClusterError::command_failed("write tunnel kubeconfig", &e);
+            emit_err("authenticating", &err);
+            return Err(err.to_string());
+        }
+    };
+
+    emit_ok("established", &format!("Tunnel listening on {}", advertised_addr), 80);
+    info!("[vcluster-tunnel] Relaying {} -> {} for {}/{}", advertised_addr, local_addr, namespace, name);
+
+    let handle = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((mut inbound, peer)) => {
+                    tokio::spawn(async move {
+                        let mut outbound = match TcpStream::connect(local_addr).await {
+                            Ok(s) => s,
+                            Err(e) => {
+                                warn!("[vcluster-tunnel] Failed to reach local proxy {}: {}", local_addr, e);
+                                return;
+                            }
+                        };
+                        if let Err(e) = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await {
+                            warn!("[vcluster-tunnel] Connection from {} dropped: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!("[vcluster-tunnel] Listener accept error: {}", e);
+                }
+            }
+        }
+    });
+
+    state.vcluster_tunnels.lock().unwrap().insert(id.clone(), crate::state::VClusterTunnelSession {
+        id: id.clone(),
+        name: name.clone(),
+        namespace: namespace.clone(),
+        bind_addr: advertised_addr,
+        kubeconfig_path: kubeconfig_path.clone(),
+        handle,
+    });
+
+    emit_ok("complete", &format!("Tunnel established for vcluster '{}'", name), 100);
+
+    Ok(VClusterTunnelInfo {
+        id,
+        connection_string: format!("https://{}", advertised_addr),
+        kubeconfig_path: kubeconfig_path.to_string_lossy().into_owned(),
+    })
+}
+
+#[tauri::command]
+pub async fn stop_vcluster_tunnel(state: State<'_, AppState>, name: String, namespace: String) -> Result<(), String> {
+    let id = tunnel_id(&name, &namespace);
+    let session = state.vcluster_tunnels.lock().unwrap().remove(&id);
+    if let Some(session) = session {
+        session.handle.abort();
+        let _ = std::fs::remove_file(&session.kubeconfig_path);
+        info!("[vcluster-tunnel] Stopped tunnel for {}/{}", namespace, name);
+    }
+    Ok(())
+}
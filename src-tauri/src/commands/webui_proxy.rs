@@ -0,0 +1,81 @@
+use tauri::State;
+use serde::Serialize;
+use crate::state::{AppState, WebUiProxyEntry};
+use crate::proxy::webui;
+
+/// A running proxy as surfaced to the frontend: the local port it listens
+/// on and the one-time auth token to append as `?t=` on the first
+/// navigation into it - see `proxy::webui::AUTH_COOKIE`.
+#[derive(Serialize, Clone)]
+pub struct WebUiProxyInfo {
+    pub id: String,
+    pub profile_id: String,
+    pub local_port: u16,
+    pub auth_token: String,
+}
+
+fn to_info(entry: &WebUiProxyEntry) -> WebUiProxyInfo {
+    WebUiProxyInfo {
+        id: entry.id.clone(),
+        profile_id: entry.profile_id.clone(),
+        local_port: entry.local_port,
+        auth_token: entry.auth_token.clone(),
+    }
+}
+
+/// Start (or reuse an already-running) reverse proxy for `profile_id`
+/// against a dashboard already reachable on `target_port` (typically one
+/// opened via `commands::port_forward_manager::start_forward`). `username`/
+/// `password` are required only for profiles with a `login_path`, e.g.
+/// ArgoCD - pass `None` for Grafana/Prometheus/Kiali.
+#[tauri::command]
+pub async fn start_webui_proxy(
+    state: State<'_, AppState>,
+    id: String,
+    profile_id: String,
+    target_port: u16,
+    protocol: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<WebUiProxyInfo, String> {
+    let profile = webui::builtin_profile(&profile_id)
+        .ok_or_else(|| format!("Unknown web UI proxy profile '{}'", profile_id))?;
+    let creds = match (username, password) {
+        (Some(u), Some(p)) => Some((u, p)),
+        _ => None,
+    };
+
+    let (local_port, auth_token) = webui::start_proxy(
+        &id,
+        target_port,
+        protocol.as_deref().unwrap_or("http"),
+        profile,
+        creds,
+    ).await?;
+
+    let entry = WebUiProxyEntry {
+        id: id.clone(),
+        profile_id,
+        local_port,
+        auth_token,
+    };
+    let info = to_info(&entry);
+    state.webui_proxies.lock().unwrap().insert(id, entry);
+
+    Ok(info)
+}
+
+/// Stop a proxy started via `start_webui_proxy`, identified by the `id` it
+/// was started with.
+#[tauri::command]
+pub async fn stop_webui_proxy(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    webui::stop_proxy(&id);
+    state.webui_proxies.lock().unwrap().remove(&id);
+    Ok(())
+}
+
+/// Every web UI proxy started through this module, active or not.
+#[tauri::command]
+pub async fn list_webui_proxies(state: State<'_, AppState>) -> Result<Vec<WebUiProxyInfo>, String> {
+    Ok(state.webui_proxies.lock().unwrap().values().map(to_info).collect())
+}
@@ -0,0 +1,30 @@
+use tauri::State;
+use crate::models::NavSweepStatus;
+use crate::state::AppState;
+use crate::workers::WorkerStatus;
+
+#[tauri::command]
+pub async fn list_workers(state: State<'_, AppState>) -> Result<Vec<WorkerStatus>, String> {
+    Ok(state.worker_manager.list_statuses())
+}
+
+#[tauri::command]
+pub async fn control_worker(state: State<'_, AppState>, name: String, action: String) -> Result<(), String> {
+    state.worker_manager.control(&name, &action)
+}
+
+#[tauri::command]
+pub async fn get_sweep_status(state: State<'_, AppState>) -> Result<NavSweepStatus, String> {
+    Ok(state.nav_sweep_status.lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub async fn set_tranquility(state: State<'_, AppState>, tranquility: f64) -> Result<(), String> {
+    if !tranquility.is_finite() || tranquility < 0.0 {
+        return Err("Tranquility must be a non-negative number".to_string());
+    }
+    let mut status = state.nav_sweep_status.lock().unwrap();
+    status.tranquility = tranquility;
+    crate::workers::save_sweep_status(&status);
+    Ok(())
+}
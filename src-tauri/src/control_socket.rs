@@ -0,0 +1,214 @@
+//! Loopback control server for the companion CLI (`src/bin/opspilotctl.rs`):
+//! a Unix domain socket on Linux/macOS, a named pipe on Windows, carrying
+//! line-delimited JSON requests so ArgoCD tunnels can be scripted from shell
+//! automation/CI without the Tauri window ever needing focus. Every request
+//! must present the token written to `control.token` (mode 0600) at startup;
+//! dispatch reuses the exact same command handlers the GUI's `invoke_handler`
+//! calls, so there's one source of truth for forward state either way.
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::commands::argocd::{check_argocd_exists, open_argocd_webview, start_argocd_port_forward, stop_argocd_port_forward};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+struct ControlRequest {
+    token: String,
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ControlResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ControlResponse {
+    fn ok(data: serde_json::Value) -> Self {
+        Self { ok: true, data: Some(data), error: None }
+    }
+
+    fn err(message: impl Into<String>) -> Self {
+        Self { ok: false, data: None, error: Some(message.into()) }
+    }
+}
+
+fn control_dir() -> Option<std::path::PathBuf> {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).ok()?;
+    let dir = std::path::PathBuf::from(home).join(".opspilot");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// Where the companion CLI finds the socket/pipe to connect to.
+pub fn socket_path() -> Option<std::path::PathBuf> {
+    #[cfg(windows)]
+    {
+        let _ = control_dir(); // still used for the token file below
+        Some(std::path::PathBuf::from(r"\\.\pipe\opspilot-control"))
+    }
+    #[cfg(not(windows))]
+    {
+        Some(control_dir()?.join("control.sock"))
+    }
+}
+
+fn token_path() -> Option<std::path::PathBuf> {
+    Some(control_dir()?.join("control.token"))
+}
+
+/// Mint a fresh token for this app run and write it to a 0600 file (Unix) -
+/// Windows has no equivalent bit, so the named pipe's own ACL plus the file
+/// living under the user's profile is the access control there.
+fn write_token() -> Option<String> {
+    let token = uuid::Uuid::new_v4().to_string();
+    let path = token_path()?;
+    std::fs::write(&path, &token).ok()?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    Some(token)
+}
+
+async fn dispatch(app: &AppHandle, token: &str, expected_token: &str, command: &str, args: &[String]) -> ControlResponse {
+    if token != expected_token {
+        return ControlResponse::err("invalid token");
+    }
+
+    let state = app.state::<AppState>();
+
+    match command {
+        "start-forward" => {
+            if args.first().map(String::as_str) != Some("argocd") {
+                return ControlResponse::err("unknown forward target (only 'argocd' is supported)");
+            }
+            match start_argocd_port_forward(state).await {
+                Ok(message) => ControlResponse::ok(serde_json::json!({ "message": message })),
+                Err(e) => ControlResponse::err(e),
+            }
+        }
+        "stop-forward" => match stop_argocd_port_forward(state).await {
+            Ok(message) => ControlResponse::ok(serde_json::json!({ "message": message })),
+            Err(e) => ControlResponse::err(e),
+        },
+        "status" => match check_argocd_exists(state).await {
+            Ok(exists) => ControlResponse::ok(serde_json::json!({ "argocd_exists": exists })),
+            Err(e) => ControlResponse::err(e),
+        },
+        "open-webview" => {
+            // Headless callers have no window geometry of their own; fall
+            // back to a reasonable default size in the corner of the
+            // primary display rather than requiring one over the wire.
+            match open_argocd_webview(app.clone(), state, 0.0, 0.0, 1200.0, 800.0).await {
+                Ok(message) => ControlResponse::ok(serde_json::json!({ "message": message })),
+                Err(e) => ControlResponse::err(e),
+            }
+        }
+        other => ControlResponse::err(format!("unknown command '{}'", other)),
+    }
+}
+
+async fn handle_connection<S>(app: AppHandle, expected_token: String, stream: S)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(req) => dispatch(&app, &req.token, &expected_token, &req.command, &req.args).await,
+            Err(e) => ControlResponse::err(format!("malformed request: {}", e)),
+        };
+
+        let Ok(mut payload) = serde_json::to_string(&response) else { continue; };
+        payload.push('\n');
+        if writer.write_all(payload.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn serve(app: AppHandle, expected_token: String, socket_path: std::path::PathBuf) {
+    let _ = std::fs::remove_file(&socket_path); // drop a stale socket from a prior crash
+    let listener = match tokio::net::UnixListener::bind(&socket_path) {
+        Ok(l) => l,
+        Err(e) => {
+            warn!("[control-socket] Failed to bind {}: {}", socket_path.display(), e);
+            return;
+        }
+    };
+
+    info!("[control-socket] Listening on {}", socket_path.display());
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let app = app.clone();
+                let token = expected_token.clone();
+                tokio::spawn(async move { handle_connection(app, token, stream).await });
+            }
+            Err(e) => {
+                warn!("[control-socket] Accept error: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+async fn serve(app: AppHandle, expected_token: String, pipe_name: std::path::PathBuf) {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let pipe_name = pipe_name.to_string_lossy().to_string();
+    let mut first = true;
+    loop {
+        let server = match ServerOptions::new().first_pipe_instance(first).create(&pipe_name) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!("[control-socket] Failed to create named pipe {}: {}", pipe_name, e);
+                return;
+            }
+        };
+        first = false;
+
+        if server.connect().await.is_err() {
+            continue;
+        }
+
+        let app = app.clone();
+        let token = expected_token.clone();
+        tokio::spawn(async move { handle_connection(app, token, server).await });
+    }
+}
+
+/// Start the control server as a background task. Best-effort: if the
+/// socket/pipe can't be bound (e.g. stale lock held by another instance),
+/// the GUI still runs fine - this only disables the headless companion CLI.
+pub fn spawn(app: AppHandle) {
+    let Some(path) = socket_path() else {
+        warn!("[control-socket] Could not resolve a socket path; companion CLI will be unavailable");
+        return;
+    };
+    let Some(token) = write_token() else {
+        warn!("[control-socket] Could not write control token; companion CLI will be unavailable");
+        return;
+    };
+
+    tauri::async_runtime::spawn(serve(app, token, path));
+}
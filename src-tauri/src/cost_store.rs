@@ -0,0 +1,204 @@
+//! Embedded on-disk history for cluster cost reports, backed by SQLite via
+//! rusqlite - the same "lazy-open connection behind a `Mutex<Option<..>>`"
+//! idiom as `metrics_store`. Each row stores a full `ClusterCostReport` as a
+//! JSON blob keyed by `generated_at`, which is simpler than `metrics_store`'s
+//! wide per-field schema since cost reports are read back whole (for the
+//! latest-snapshot-on-startup case) rather than queried column-by-column.
+
+use rusqlite::{params, Connection};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::models::ClusterCostReport;
+
+fn db_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".opspilot").join("cost_history.db"))
+}
+
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().map_err(|e| format!("Cost history store lock poisoned: {}", e))?;
+
+    if guard.is_none() {
+        let path = db_path().ok_or("Could not determine home directory for cost history store")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create cost history store directory: {}", e))?;
+        }
+        let conn = Connection::open(&path).map_err(|e| format!("Failed to open cost history store: {}", e))?;
+        init_schema(&conn).map_err(|e| format!("Failed to initialize cost history store schema: {}", e))?;
+        *guard = Some(conn);
+    }
+
+    f(guard.as_ref().unwrap()).map_err(|e| format!("Cost history store query failed: {}", e))
+}
+
+fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cost_snapshots (
+            generated_at TEXT PRIMARY KEY,
+            total_cost_monthly REAL NOT NULL,
+            namespace_totals TEXT NOT NULL,
+            report_json TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// A stable, order-independent fingerprint of per-namespace totals, used to
+/// decide whether a new report actually changed anything worth a new row.
+fn namespace_fingerprint(report: &ClusterCostReport) -> String {
+    let mut totals: Vec<(String, i64)> = report
+        .namespaces
+        .iter()
+        .map(|n| (n.namespace.clone(), (n.total_cost_monthly * 100.0).round() as i64))
+        .collect();
+    totals.sort();
+    totals.into_iter().map(|(ns, cents)| format!("{}={}", ns, cents)).collect::<Vec<_>>().join(",")
+}
+
+/// Persist `report` as a new snapshot, but only if its per-namespace totals
+/// differ from the most recently stored snapshot - avoids a row per call
+/// when nothing in the cluster actually changed. Returns whether a row was
+/// written.
+pub fn save_snapshot_if_changed(report: &ClusterCostReport) -> Result<bool, String> {
+    let fingerprint = namespace_fingerprint(report);
+
+    let last_fingerprint: Option<String> = with_connection(|conn| {
+        conn.query_row(
+            "SELECT namespace_totals FROM cost_snapshots ORDER BY generated_at DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .map(Some)
+        .or_else(|e| if matches!(e, rusqlite::Error::QueryReturnedNoRows) { Ok(None) } else { Err(e) })
+    })?;
+
+    if last_fingerprint.as_deref() == Some(fingerprint.as_str()) {
+        return Ok(false);
+    }
+
+    let report_json = serde_json::to_string(report).map_err(|e| format!("Failed to serialize cost report: {}", e))?;
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO cost_snapshots (generated_at, total_cost_monthly, namespace_totals, report_json)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![report.generated_at, report.total_cost_monthly, fingerprint, report_json],
+        )?;
+        Ok(())
+    })?;
+
+    Ok(true)
+}
+
+/// The most recently stored snapshot, if any - used to restore trends across
+/// restarts before the first live report has been generated.
+pub fn latest_snapshot() -> Result<Option<ClusterCostReport>, String> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT report_json FROM cost_snapshots ORDER BY generated_at DESC LIMIT 1",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .map(Some)
+        .or_else(|e| if matches!(e, rusqlite::Error::QueryReturnedNoRows) { Ok(None) } else { Err(e) })
+    })
+    .map(|json| json.and_then(|j| serde_json::from_str(&j).ok()))
+}
+
+/// A cost snapshot reduced to what the history timeline needs: the total (or
+/// a single namespace's total, if `namespace` was set when loading) plus its
+/// timestamp.
+#[derive(Clone, serde::Serialize)]
+pub struct CostHistoryPoint {
+    pub generated_at: String,
+    pub total_cost_monthly: f64,
+}
+
+/// Load stored snapshots at or after `since` (an RFC3339 timestamp), as a
+/// time series. When `namespace` is set, each point is that namespace's
+/// total rather than the cluster-wide total.
+pub fn get_cost_history(namespace: Option<&str>, since: &str) -> Result<Vec<CostHistoryPoint>, String> {
+    let rows: Vec<(String, String)> = with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT generated_at, report_json FROM cost_snapshots WHERE generated_at >= ?1 ORDER BY generated_at ASC",
+        )?;
+        let rows = stmt.query_map(params![since], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    })?;
+
+    let mut points = Vec::with_capacity(rows.len());
+    for (generated_at, report_json) in rows {
+        let report: ClusterCostReport = match serde_json::from_str(&report_json) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let total = match namespace {
+            Some(ns) => report.namespaces.iter().find(|n| n.namespace == ns).map(|n| n.total_cost_monthly).unwrap_or(0.0),
+            None => report.total_cost_monthly,
+        };
+        points.push(CostHistoryPoint { generated_at, total_cost_monthly: total });
+    }
+    Ok(points)
+}
+
+/// The difference between the latest snapshot and the closest snapshot at or
+/// before `since` (an RFC3339 timestamp, typically "N days ago"), e.g. so the
+/// UI can show "namespace X is up $120/mo week-over-week".
+#[derive(Clone, serde::Serialize)]
+pub struct CostDelta {
+    pub namespace: Option<String>,
+    pub current_total_monthly: f64,
+    pub previous_total_monthly: f64,
+    pub delta_monthly: f64,
+    pub current_generated_at: String,
+    pub previous_generated_at: Option<String>,
+}
+
+pub fn get_cost_delta(namespace: Option<&str>, since: &str) -> Result<Option<CostDelta>, String> {
+    let current = match latest_snapshot()? {
+        Some(report) => report,
+        None => return Ok(None),
+    };
+
+    let previous_row: Option<(String, String)> = with_connection(|conn| {
+        conn.query_row(
+            "SELECT generated_at, report_json FROM cost_snapshots WHERE generated_at <= ?1 ORDER BY generated_at DESC LIMIT 1",
+            params![since],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map(Some)
+        .or_else(|e| if matches!(e, rusqlite::Error::QueryReturnedNoRows) { Ok(None) } else { Err(e) })
+    })?;
+
+    let current_total = match namespace {
+        Some(ns) => current.namespaces.iter().find(|n| n.namespace == ns).map(|n| n.total_cost_monthly).unwrap_or(0.0),
+        None => current.total_cost_monthly,
+    };
+
+    let (previous_total, previous_generated_at) = match &previous_row {
+        Some((generated_at, report_json)) => {
+            let report: ClusterCostReport = serde_json::from_str(report_json).map_err(|e| format!("Failed to deserialize cost snapshot: {}", e))?;
+            let total = match namespace {
+                Some(ns) => report.namespaces.iter().find(|n| n.namespace == ns).map(|n| n.total_cost_monthly).unwrap_or(0.0),
+                None => report.total_cost_monthly,
+            };
+            (total, Some(generated_at.clone()))
+        }
+        None => (0.0, None),
+    };
+
+    Ok(Some(CostDelta {
+        namespace: namespace.map(|s| s.to_string()),
+        current_total_monthly: current_total,
+        previous_total_monthly: previous_total,
+        delta_monthly: current_total - previous_total,
+        current_generated_at: current.generated_at,
+        previous_generated_at,
+    }))
+}
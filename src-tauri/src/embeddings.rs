@@ -7,9 +7,13 @@
 //! The fastembed dependency has been removed to reduce binary size.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use tauri::Manager;
 
+use crate::hnsw;
+
 /// Pre-computed embedding for a document
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DocEmbedding {
@@ -19,6 +23,23 @@ pub struct DocEmbedding {
     #[serde(default)]
     pub summary: String,  // Clean summary for display
     pub embedding: Vec<f32>,
+    /// Binary sketch (1 bit/dim, sign vs. the dataset mean, packed into
+    /// `u64` words) used to cheaply shortlist candidates before the exact
+    /// cosine re-rank in `search_documents_quantized`. `None` until
+    /// `load_embeddings` derives it, or already set by a pipeline that
+    /// precomputes sketches (see `EmbeddingsData::quantization`).
+    #[serde(default)]
+    pub sketch: Option<Vec<u64>>,
+    /// L2 norm of `embedding`, computed alongside `sketch`.
+    #[serde(default)]
+    pub norm: Option<f32>,
+    /// Hash of the source document's text at the time this embedding was
+    /// computed, so `refresh_embeddings` can tell whether the file has
+    /// changed since without re-embedding it. Empty for embeddings written
+    /// before this field existed, which `embeddings_status` reports as
+    /// stale until the next refresh fills it in.
+    #[serde(default)]
+    pub content_hash: String,
 }
 
 /// Pre-computed embedding for a tool
@@ -27,6 +48,12 @@ pub struct ToolEmbedding {
     pub name: String,
     pub description: String,
     pub embedding: Vec<f32>,
+    /// See `DocEmbedding::sketch`.
+    #[serde(default)]
+    pub sketch: Option<Vec<u64>>,
+    /// See `DocEmbedding::norm`.
+    #[serde(default)]
+    pub norm: Option<f32>,
 }
 
 /// Container for all pre-computed embeddings
@@ -36,11 +63,45 @@ pub struct EmbeddingsData {
     pub dimension: usize,
     pub documents: Vec<DocEmbedding>,
     pub tools: Vec<ToolEmbedding>,
+    /// Tag identifying how document/tool `sketch`/`norm` fields were
+    /// produced (e.g. `"binary-v1"`), or `None` if the embeddings file
+    /// predates quantization and `load_embeddings` must derive sketches
+    /// itself before caching the data.
+    #[serde(default)]
+    pub quantization: Option<String>,
 }
 
+/// One entry in the sidecar embeddings manifest: the content hash a
+/// document's cached embedding was computed from, and which `DocEmbedding`
+/// (by `id`) holds it, so `refresh_embeddings` can patch the right entry in
+/// `EmbeddingsData::documents` without a linear search keyed on content.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EmbeddingManifestEntry {
+    pub hash: String,
+    pub embedding_id: String,
+}
+
+/// Sidecar manifest mapping source file path -> content hash / embedding id,
+/// persisted next to `kb_embeddings_cache.json` as
+/// `kb_embeddings_manifest.json`. Lets `refresh_embeddings` and
+/// `embeddings_status` tell which cached documents are stale without
+/// re-embedding (or even re-reading) every file.
+pub type EmbeddingManifest = HashMap<String, EmbeddingManifestEntry>;
+
 /// Global embeddings cache
 static EMBEDDINGS_CACHE: Mutex<Option<EmbeddingsData>> = Mutex::new(None);
 
+/// HNSW index over `EMBEDDINGS_CACHE`'s document vectors, built the first
+/// time `load_embeddings` populates the cache. Stays `None` below
+/// `hnsw::BRUTE_FORCE_THRESHOLD` documents, where a linear scan is cheaper
+/// than walking the graph.
+static DOC_INDEX_CACHE: Mutex<Option<hnsw::HnswIndex>> = Mutex::new(None);
+
+/// Per-dataset mean vector the binary sketches in `EMBEDDINGS_CACHE` were
+/// sign-split against, needed to sketch a query embedding the same way at
+/// search time. Populated alongside `derive_sketches` in `load_embeddings`.
+static SKETCH_MEAN_CACHE: Mutex<Option<Vec<f32>>> = Mutex::new(None);
+
 /// Load pre-computed embeddings from bundled resources or user cache
 ///
 /// Note: As of v0.2.6+, KB embeddings are generated at runtime via the Python
@@ -57,21 +118,11 @@ pub fn load_embeddings(app_handle: &tauri::AppHandle) -> Result<EmbeddingsData,
     let resource_path = app_handle.path().resource_dir().map_err(|e| e.to_string())?;
     let embeddings_path = resource_path.join("kb_embeddings.json");
 
-    // User cache path - platform appropriate location:
-    // - Linux: ~/.local/share/opspilot/kb_embeddings_cache.json
-    // - macOS: ~/Library/Application Support/opspilot/kb_embeddings_cache.json
-    // - Windows: C:\Users\<user>\AppData\Local\opspilot\kb_embeddings_cache.json
-    // Fallback to ~/.opspilot for compatibility with Python agent
-    let user_cache_path = dirs::data_local_dir()
-        .map(|d| d.join("opspilot").join("kb_embeddings_cache.json"))
-        .or_else(|| dirs::home_dir().map(|h| h.join(".opspilot").join("kb_embeddings_cache.json")))
-        .unwrap_or_default();
-
     // Multiple fallback paths for development and production
     let cwd = std::env::current_dir().unwrap_or_default();
     let search_paths = vec![
         // User cache has priority (most recent)
-        user_cache_path,
+        cache_path(),
         // Bundled resources
         embeddings_path.clone(),
         cwd.join("src-tauri/resources/kb_embeddings.json"),
@@ -92,20 +143,142 @@ pub fn load_embeddings(app_handle: &tauri::AppHandle) -> Result<EmbeddingsData,
     let path = found_path.ok_or("kb_embeddings.json not found (this is OK - using keyword search fallback)")?;
     let content = std::fs::read_to_string(&path)
         .map_err(|e| format!("Failed to read embeddings: {}", e))?;
-    let data: EmbeddingsData = serde_json::from_str(&content)
+    let mut data: EmbeddingsData = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse embeddings: {}", e))?;
 
-    // Cache the loaded data
-    if let Ok(mut cache) = EMBEDDINGS_CACHE.lock() {
-        *cache = Some(data.clone());
+    // Derive binary sketches when the file doesn't already carry
+    // precomputed ones, so `search_documents_quantized` always has
+    // something to rank against.
+    if data.quantization.is_none() {
+        let mean = derive_sketches(&mut data);
+        data.quantization = Some("binary-v1".to_string());
+        if let Ok(mut mean_cache) = SKETCH_MEAN_CACHE.lock() {
+            *mean_cache = Some(mean);
+        }
     }
 
+    // Cache the loaded data and build (or clear) the ANN index to match.
+    cache_and_index(&data);
+
     eprintln!("[DEBUG] Loaded {} KB embeddings (model: {}, dim: {})",
         data.documents.len(), data.model, data.dimension);
 
     Ok(data)
 }
 
+/// Platform-appropriate directory embeddings state (cache + manifest) is
+/// written to, falling back to `~/.opspilot` for compatibility with the
+/// Python agent:
+/// - Linux: ~/.local/share/opspilot/
+/// - macOS: ~/Library/Application Support/opspilot/
+/// - Windows: C:\Users\<user>\AppData\Local\opspilot\
+fn user_data_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|d| d.join("opspilot"))
+        .or_else(|| dirs::home_dir().map(|h| h.join(".opspilot")))
+        .unwrap_or_default()
+}
+
+fn cache_path() -> PathBuf {
+    user_data_dir().join("kb_embeddings_cache.json")
+}
+
+fn manifest_path() -> PathBuf {
+    user_data_dir().join("kb_embeddings_manifest.json")
+}
+
+/// Store `data` in `EMBEDDINGS_CACHE` and rebuild `DOC_INDEX_CACHE` to match
+/// it, same as `load_embeddings` does on first load. Shared with
+/// `refresh_embeddings` so a refresh is visible to the next search without
+/// waiting for a process restart.
+fn cache_and_index(data: &EmbeddingsData) {
+    if let Ok(mut cache) = EMBEDDINGS_CACHE.lock() {
+        *cache = Some(data.clone());
+    }
+    if let Ok(mut index_cache) = DOC_INDEX_CACHE.lock() {
+        *index_cache = if data.documents.len() >= hnsw::BRUTE_FORCE_THRESHOLD {
+            let vectors: Vec<Vec<f32>> = data.documents.iter().map(|d| d.embedding.clone()).collect();
+            eprintln!("[DEBUG] Building HNSW index over {} documents", vectors.len());
+            Some(hnsw::HnswIndex::build(&vectors, hnsw::HnswParams::default()))
+        } else {
+            None
+        };
+    }
+}
+
+/// Write `bytes` to `path` atomically: write to a sibling `.tmp` file, then
+/// rename over the target, so a crash mid-write never leaves the next
+/// `load_embeddings`/`load_manifest` call looking at a half-written file.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, bytes)
+        .map_err(|e| format!("Failed to write {:?}: {}", tmp_path, e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| format!("Failed to replace {:?}: {}", path, e))
+}
+
+/// Stable hash of a document's text, used to tell whether a previously
+/// embedded file has actually changed. Not cryptographic - it only needs to
+/// change when the content does.
+pub fn hash_content(text: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+/// Load the sidecar manifest written by `refresh_embeddings`. Returns an
+/// empty manifest if it hasn't been written yet (e.g. the cache predates
+/// this feature, or nothing has been refreshed since).
+pub fn load_manifest() -> EmbeddingManifest {
+    std::fs::read_to_string(manifest_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(manifest: &EmbeddingManifest) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    write_atomic(&manifest_path(), json.as_bytes())
+}
+
+/// Overwrite `data.documents[doc_index]`'s vector in place with a freshly
+/// computed embedding, re-packing its binary sketch against the cached
+/// sketch mean (deriving one from the whole dataset first if
+/// `load_embeddings` hasn't already) so `search_documents_quantized` stays
+/// consistent with the refreshed vector.
+pub(crate) fn apply_refreshed_embedding(
+    data: &mut EmbeddingsData,
+    doc_index: usize,
+    embedding: Vec<f32>,
+    content_hash: String,
+) {
+    let mean = SKETCH_MEAN_CACHE.lock().ok().and_then(|c| c.clone())
+        .unwrap_or_else(|| derive_sketches(data));
+    let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let sketch = pack_sketch(&embedding, &mean);
+
+    let doc = &mut data.documents[doc_index];
+    doc.embedding = embedding;
+    doc.content_hash = content_hash;
+    doc.norm = Some(norm);
+    doc.sketch = Some(sketch);
+}
+
+/// Atomically persist `data`/`manifest` to their cache files and refresh the
+/// in-memory caches so the next search sees the refreshed vectors.
+pub(crate) fn save_refreshed(data: &EmbeddingsData, manifest: &EmbeddingManifest) -> Result<(), String> {
+    let cache_json = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    write_atomic(&cache_path(), cache_json.as_bytes())?;
+    save_manifest(manifest)?;
+
+    cache_and_index(data);
+    Ok(())
+}
+
 /// Compute cosine similarity between two vectors
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
@@ -123,6 +296,61 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     dot / (mag_a * mag_b)
 }
 
+/// Number of candidates the Hamming-distance prefilter keeps for each
+/// requested result in `search_documents_quantized`, before the exact
+/// cosine re-rank narrows that shortlist down to `top_k`.
+const QUANTIZED_SHORTLIST_FACTOR: usize = 4;
+
+/// Pack a binary sketch for `vector`: bit `i` is set when `vector[i]` is at
+/// or above `mean[i]`, packed 64 dimensions per `u64` word.
+fn pack_sketch(vector: &[f32], mean: &[f32]) -> Vec<u64> {
+    let mut words = vec![0u64; (vector.len() + 63) / 64];
+    for (i, (&v, &m)) in vector.iter().zip(mean.iter()).enumerate() {
+        if v >= m {
+            words[i / 64] |= 1u64 << (i % 64);
+        }
+    }
+    words
+}
+
+/// Hamming distance between two packed binary sketches: popcount over the
+/// XOR of their words.
+fn hamming_distance(a: &[u64], b: &[u64]) -> u32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+/// Fill in `sketch`/`norm` on every document and tool embedding, computing
+/// the per-dataset mean vector from documents (the larger, search-hot set)
+/// and reusing it for tools so both sketches stay comparable. Returns the
+/// mean so the caller can cache it for sketching query vectors the same way.
+fn derive_sketches(data: &mut EmbeddingsData) -> Vec<f32> {
+    let dim = data.dimension;
+    if data.documents.is_empty() {
+        return vec![0.0; dim];
+    }
+    let mut mean = vec![0.0f32; dim];
+    for doc in &data.documents {
+        for (m, v) in mean.iter_mut().zip(doc.embedding.iter()) {
+            *m += v;
+        }
+    }
+    let count = data.documents.len() as f32;
+    for m in mean.iter_mut() {
+        *m /= count;
+    }
+
+    for doc in data.documents.iter_mut() {
+        doc.sketch = Some(pack_sketch(&doc.embedding, &mean));
+        doc.norm = Some(doc.embedding.iter().map(|x| x * x).sum::<f32>().sqrt());
+    }
+    for tool in data.tools.iter_mut() {
+        tool.sketch = Some(pack_sketch(&tool.embedding, &mean));
+        tool.norm = Some(tool.embedding.iter().map(|x| x * x).sum::<f32>().sqrt());
+    }
+
+    mean
+}
+
 /// Result from semantic search
 #[derive(Debug, Clone, Serialize)]
 pub struct SemanticSearchResult {
@@ -133,6 +361,15 @@ pub struct SemanticSearchResult {
     pub score: f32,
 }
 
+/// Response from the `semantic_search` Tauri command. `degraded` is set when
+/// no query embedding could be produced (no endpoint configured, or it was
+/// unreachable) and `results` came from the lexical scorer instead.
+#[derive(Debug, Clone, Serialize)]
+pub struct SemanticSearchResponse {
+    pub results: Vec<SemanticSearchResult>,
+    pub degraded: bool,
+}
+
 /// Search documents by semantic similarity (requires pre-computed query embedding)
 pub fn search_documents(query_embedding: &[f32], embeddings: &EmbeddingsData, top_k: usize) -> Vec<SemanticSearchResult> {
     let mut results: Vec<_> = embeddings.documents
@@ -152,6 +389,179 @@ pub fn search_documents(query_embedding: &[f32], embeddings: &EmbeddingsData, to
     results
 }
 
+/// Search documents via the cached HNSW index when one is available for
+/// `embeddings` (i.e. `load_embeddings` found at least
+/// `hnsw::BRUTE_FORCE_THRESHOLD` documents), otherwise falls back to the
+/// exact brute-force scan in `search_documents`. Callers that don't go
+/// through `load_embeddings` (e.g. tests constructing `EmbeddingsData`
+/// directly) always get the brute-force path, since there's no cached
+/// index to use.
+pub fn search_documents_ann(query_embedding: &[f32], embeddings: &EmbeddingsData, top_k: usize) -> Vec<SemanticSearchResult> {
+    if embeddings.documents.len() < hnsw::BRUTE_FORCE_THRESHOLD {
+        return search_documents(query_embedding, embeddings, top_k);
+    }
+
+    let cache = match DOC_INDEX_CACHE.lock() {
+        Ok(cache) => cache,
+        Err(_) => return search_documents(query_embedding, embeddings, top_k),
+    };
+    let Some(index) = cache.as_ref() else {
+        return search_documents(query_embedding, embeddings, top_k);
+    };
+
+    index.search(query_embedding, top_k)
+        .into_iter()
+        .filter_map(|(idx, score)| embeddings.documents.get(idx).map(|doc| SemanticSearchResult {
+            id: doc.id.clone(),
+            file: doc.file.clone(),
+            title: doc.title.clone(),
+            summary: doc.summary.clone(),
+            score,
+        }))
+        .collect()
+}
+
+/// Search documents using the precomputed binary sketches: rank every
+/// document cheaply by Hamming distance between the query's sketch and the
+/// document's (a popcount over a few `u64` XORs, far cheaper than a cosine
+/// over 768 floats), take the closest `4 * top_k` candidates, then re-rank
+/// only that shortlist with exact cosine similarity on the full vectors.
+/// Falls back to `search_documents` when no sketch/mean is available (e.g.
+/// `embeddings` wasn't produced by `load_embeddings`, or a document is
+/// missing its sketch).
+pub fn search_documents_quantized(query_embedding: &[f32], embeddings: &EmbeddingsData, top_k: usize) -> Vec<SemanticSearchResult> {
+    let mean = match SKETCH_MEAN_CACHE.lock() {
+        Ok(cache) => cache.clone(),
+        Err(_) => None,
+    };
+    let Some(mean) = mean else {
+        return search_documents(query_embedding, embeddings, top_k);
+    };
+    if embeddings.documents.iter().any(|d| d.sketch.is_none()) {
+        return search_documents(query_embedding, embeddings, top_k);
+    }
+
+    let query_sketch = pack_sketch(query_embedding, &mean);
+    let shortlist_size = (top_k * QUANTIZED_SHORTLIST_FACTOR).max(top_k);
+
+    let mut by_distance: Vec<(usize, u32)> = embeddings.documents
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, doc)| doc.sketch.as_ref().map(|s| (idx, hamming_distance(&query_sketch, s))))
+        .collect();
+    by_distance.sort_by_key(|(_, dist)| *dist);
+    by_distance.truncate(shortlist_size);
+
+    let mut results: Vec<SemanticSearchResult> = by_distance
+        .into_iter()
+        .filter_map(|(idx, _)| embeddings.documents.get(idx).map(|doc| SemanticSearchResult {
+            id: doc.id.clone(),
+            file: doc.file.clone(),
+            title: doc.title.clone(),
+            summary: doc.summary.clone(),
+            score: cosine_similarity(query_embedding, &doc.embedding),
+        }))
+        .collect();
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    results.truncate(top_k);
+    results
+}
+
+/// Reciprocal Rank Fusion constant from the original RRF paper; large enough
+/// that a document's exact rank matters less than *which* methods found it.
+const RRF_K: f32 = 60.0;
+
+/// Score documents by a simple tokenized term-frequency match over
+/// `title`/`summary`, for queries that are exact tool names or error codes
+/// (e.g. "CrashLoopBackOff") where semantic search alone can under-rank an
+/// exact lexical hit. Returns `(doc index, score)` only for docs with at
+/// least one matching term, sorted by score descending.
+pub(crate) fn lexical_scores(query_text: &str, embeddings: &EmbeddingsData) -> Vec<(usize, f32)> {
+    let query_terms: Vec<String> = tokenize(query_text);
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scores: Vec<(usize, f32)> = embeddings.documents
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, doc)| {
+            let doc_terms = tokenize(&format!("{} {}", doc.title, doc.summary));
+            let matches = query_terms.iter().filter(|qt| doc_terms.contains(qt)).count();
+            if matches == 0 {
+                None
+            } else {
+                Some((idx, matches as f32))
+            }
+        })
+        .collect();
+
+    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    scores
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Combine lexical and semantic search via Reciprocal Rank Fusion: each
+/// method ranks the documents it considers a candidate independently, and a
+/// doc's fused score is `Σ weight_i / (RRF_K + rank_i)` over the methods
+/// that ranked it (1-based rank), so a doc found by both methods outranks
+/// one found by only the stronger of the two. `semantic_weight`/
+/// `lexical_weight` default to 1.0 (pass `None` for either to bias toward
+/// the other). This keeps exact keyword matches (tool names, error codes)
+/// reliable while still surfacing paraphrased, semantically-close results.
+pub fn search_documents_hybrid(
+    query_text: &str,
+    query_embedding: &[f32],
+    embeddings: &EmbeddingsData,
+    top_k: usize,
+    semantic_weight: Option<f32>,
+    lexical_weight: Option<f32>,
+) -> Vec<SemanticSearchResult> {
+    let semantic_weight = semantic_weight.unwrap_or(1.0);
+    let lexical_weight = lexical_weight.unwrap_or(1.0);
+
+    let mut semantic_ranked: Vec<(usize, f32)> = embeddings.documents
+        .iter()
+        .enumerate()
+        .map(|(idx, doc)| (idx, cosine_similarity(query_embedding, &doc.embedding)))
+        .collect();
+    semantic_ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let lexical_ranked = lexical_scores(query_text, embeddings);
+
+    let mut fused_scores = vec![0.0f32; embeddings.documents.len()];
+    for (rank, (idx, _)) in semantic_ranked.iter().enumerate() {
+        fused_scores[*idx] += semantic_weight / (RRF_K + (rank + 1) as f32);
+    }
+    for (rank, (idx, _)) in lexical_ranked.iter().enumerate() {
+        fused_scores[*idx] += lexical_weight / (RRF_K + (rank + 1) as f32);
+    }
+
+    let mut results: Vec<SemanticSearchResult> = embeddings.documents
+        .iter()
+        .enumerate()
+        .map(|(idx, doc)| SemanticSearchResult {
+            id: doc.id.clone(),
+            file: doc.file.clone(),
+            title: doc.title.clone(),
+            summary: doc.summary.clone(),
+            score: fused_scores[idx],
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    results.truncate(top_k);
+    results
+}
+
 /// Tool suggestion from semantic search
 #[derive(Debug, Clone, Serialize)]
 pub struct ToolSuggestion {
@@ -262,6 +672,9 @@ mod tests {
                     title: "CrashLoopBackOff".to_string(),
                     summary: "Pod keeps crashing in a loop".to_string(),
                     embedding: vec![1.0, 0.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
                 },
                 DocEmbedding {
                     id: "doc2".to_string(),
@@ -269,9 +682,13 @@ mod tests {
                     title: "Networking".to_string(),
                     summary: "Network connectivity issues".to_string(),
                     embedding: vec![0.0, 1.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
                 },
             ],
             tools: vec![],
+            quantization: None,
         };
 
         let query = vec![0.9, 0.1, 0.0];
@@ -282,6 +699,147 @@ mod tests {
         assert!(results[0].score > results[1].score);
     }
 
+    #[test]
+    fn test_search_documents_ann_falls_back_below_threshold() {
+        // With fewer documents than `hnsw::BRUTE_FORCE_THRESHOLD`, no index
+        // is built, so `search_documents_ann` should return exactly what
+        // the brute-force `search_documents` does.
+        let embeddings = EmbeddingsData {
+            model: "test".to_string(),
+            dimension: 3,
+            documents: vec![
+                DocEmbedding {
+                    id: "doc1".to_string(),
+                    file: "doc1.json".to_string(),
+                    title: "CrashLoopBackOff".to_string(),
+                    summary: "Pod keeps crashing in a loop".to_string(),
+                    embedding: vec![1.0, 0.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+                DocEmbedding {
+                    id: "doc2".to_string(),
+                    file: "doc2.json".to_string(),
+                    title: "Networking".to_string(),
+                    summary: "Network connectivity issues".to_string(),
+                    embedding: vec![0.0, 1.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+            ],
+            tools: vec![],
+            quantization: None,
+        };
+
+        let query = vec![0.9, 0.1, 0.0];
+        let brute_force = search_documents(&query, &embeddings, 2);
+        let ann = search_documents_ann(&query, &embeddings, 2);
+
+        assert_eq!(ann.len(), brute_force.len());
+        for (a, b) in ann.iter().zip(brute_force.iter()) {
+            assert_eq!(a.id, b.id);
+            assert!((a.score - b.score).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_derive_sketches_and_quantized_search_match_brute_force_top_result() {
+        let mut embeddings = EmbeddingsData {
+            model: "test".to_string(),
+            dimension: 3,
+            documents: vec![
+                DocEmbedding {
+                    id: "doc1".to_string(),
+                    file: "doc1.json".to_string(),
+                    title: "CrashLoopBackOff".to_string(),
+                    summary: "Pod keeps crashing in a loop".to_string(),
+                    embedding: vec![1.0, 0.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+                DocEmbedding {
+                    id: "doc2".to_string(),
+                    file: "doc2.json".to_string(),
+                    title: "Networking".to_string(),
+                    summary: "Network connectivity issues".to_string(),
+                    embedding: vec![0.0, 1.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+                DocEmbedding {
+                    id: "doc3".to_string(),
+                    file: "doc3.json".to_string(),
+                    title: "Storage".to_string(),
+                    summary: "Persistent volume issues".to_string(),
+                    embedding: vec![0.0, 0.0, 1.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+            ],
+            tools: vec![],
+            quantization: None,
+        };
+
+        let mean = derive_sketches(&mut embeddings);
+        assert!(embeddings.documents.iter().all(|d| d.sketch.is_some() && d.norm.is_some()));
+
+        if let Ok(mut mean_cache) = SKETCH_MEAN_CACHE.lock() {
+            *mean_cache = Some(mean);
+        }
+
+        let query = vec![0.9, 0.1, 0.0];
+        let brute_force = search_documents(&query, &embeddings, 1);
+        let quantized = search_documents_quantized(&query, &embeddings, 1);
+
+        assert_eq!(quantized.len(), 1);
+        assert_eq!(quantized[0].id, brute_force[0].id, "Shortlist + exact re-rank should agree with brute force on the top hit");
+    }
+
+    #[test]
+    fn test_search_documents_hybrid_favors_exact_keyword_match() {
+        let embeddings = EmbeddingsData {
+            model: "test".to_string(),
+            dimension: 3,
+            documents: vec![
+                DocEmbedding {
+                    id: "doc1".to_string(),
+                    file: "doc1.json".to_string(),
+                    title: "CrashLoopBackOff".to_string(),
+                    summary: "Pod keeps crashing in a loop".to_string(),
+                    // Deliberately far from the query embedding, so only the
+                    // lexical scorer should rank it highly.
+                    embedding: vec![0.0, 0.0, 1.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+                DocEmbedding {
+                    id: "doc2".to_string(),
+                    file: "doc2.json".to_string(),
+                    title: "Networking".to_string(),
+                    summary: "Network connectivity issues".to_string(),
+                    embedding: vec![0.9, 0.1, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
+                },
+            ],
+            tools: vec![],
+            quantization: None,
+        };
+
+        let query_embedding = vec![0.9, 0.1, 0.0];
+        let results = search_documents_hybrid("CrashLoopBackOff", &query_embedding, &embeddings, 2, None, None);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "doc1", "Exact keyword match should be pulled to the top via RRF even with a weak embedding score");
+    }
+
     #[test]
     fn test_suggest_tools_ranking() {
         let embeddings = EmbeddingsData {
@@ -293,13 +851,20 @@ mod tests {
                     name: "GET_LOGS".to_string(),
                     description: "pod logs".to_string(),
                     embedding: vec![1.0, 0.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
                 },
                 ToolEmbedding {
                     name: "DESCRIBE".to_string(),
                     description: "describe".to_string(),
                     embedding: vec![0.0, 1.0, 0.0],
+                    sketch: None,
+                    norm: None,
+                    content_hash: String::new(),
                 },
             ],
+            quantization: None,
         };
 
         let query = vec![0.95, 0.05, 0.0];
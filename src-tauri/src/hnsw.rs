@@ -0,0 +1,294 @@
+//! In-memory HNSW (Hierarchical Navigable Small World) approximate-nearest-
+//! neighbor index over cosine similarity.
+//!
+//! `embeddings::search_documents`/`suggest_tools` scan every vector on each
+//! query, which is fine for a handful of KB docs but won't scale. This
+//! builds a multi-layer proximity graph once after the embeddings are
+//! loaded: each inserted vector is assigned a random top layer (higher
+//! layers exponentially sparser), greedily descended from the graph's
+//! entry point to find a good starting neighborhood, then connected to its
+//! `M` nearest neighbors at each layer it participates in. Search repeats
+//! the same greedy descent on the upper layers, then does a wider beam
+//! search of the base layer to return the closest `top_k` vectors.
+//!
+//! Below `BRUTE_FORCE_THRESHOLD` vectors, building and walking the graph
+//! costs more than a linear scan saves, so callers should keep using the
+//! brute-force path instead.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::embeddings::cosine_similarity;
+
+/// Below this many vectors, skip building/using the index entirely.
+pub const BRUTE_FORCE_THRESHOLD: usize = 256;
+
+/// Tunable HNSW construction/search parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct HnswParams {
+    /// Neighbors per node at layers above the base layer (the base layer
+    /// keeps `2*m`, per the original HNSW paper's recommendation).
+    pub m: usize,
+    /// Candidate list size while inserting; larger values build a higher
+    /// quality (but slower to construct) graph.
+    pub ef_construction: usize,
+    /// Candidate list size while searching; larger values trade search
+    /// time for recall.
+    pub ef_search: usize,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self { m: 16, ef_construction: 200, ef_search: 64 }
+    }
+}
+
+struct Node {
+    vector: Vec<f32>,
+    // Neighbor ids per layer; `layers[0]` is the base layer every node has.
+    layers: Vec<Vec<usize>>,
+}
+
+#[derive(Clone, Copy)]
+struct Candidate {
+    id: usize,
+    score: f32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+impl Eq for Candidate {}
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.score.partial_cmp(&other.score)
+    }
+}
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+}
+
+pub struct HnswIndex {
+    nodes: Vec<Node>,
+    entry_point: Option<usize>,
+    max_layer: usize,
+    params: HnswParams,
+    // 1 / ln(M): the level-assignment exponential distribution's mean.
+    ml: f64,
+    rng_state: u64,
+}
+
+impl HnswIndex {
+    pub fn build(vectors: &[Vec<f32>], params: HnswParams) -> Self {
+        let ml = 1.0 / (params.m.max(2) as f64).ln();
+        let mut index = Self {
+            nodes: Vec::with_capacity(vectors.len()),
+            entry_point: None,
+            max_layer: 0,
+            params,
+            ml,
+            // Any fixed non-zero seed is fine: layer assignment only needs
+            // to be roughly geometric, not cryptographically random.
+            rng_state: 0x9E3779B97F4A7C15 ^ (vectors.len() as u64).wrapping_add(1),
+        };
+        for v in vectors {
+            index.insert(v.clone());
+        }
+        index
+    }
+
+    /// xorshift64star: good enough spread for level assignment without
+    /// pulling in a `rand` dependency for one call site.
+    fn next_unit_f64(&mut self) -> f64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        // Map to (0, 1], never 0, so `ln()` below stays finite.
+        ((x >> 11) as f64 + 1.0) / ((1u64 << 53) as f64 + 1.0)
+    }
+
+    fn random_level(&mut self) -> usize {
+        let r = self.next_unit_f64();
+        (-r.ln() * self.ml).floor() as usize
+    }
+
+    /// Greedy best-first search of one layer starting from `entry_points`,
+    /// keeping the `ef` best candidates found. Returns them sorted by
+    /// descending similarity.
+    fn search_layer(&self, query: &[f32], entry_points: &[usize], ef: usize, layer: usize) -> Vec<Candidate> {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut candidates: BinaryHeap<Candidate> = BinaryHeap::new();
+        let mut found: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+
+        for &ep in entry_points {
+            if visited.insert(ep) {
+                let score = cosine_similarity(query, &self.nodes[ep].vector);
+                candidates.push(Candidate { id: ep, score });
+                found.push(Reverse(Candidate { id: ep, score }));
+            }
+        }
+
+        while let Some(current) = candidates.pop() {
+            let worst_found = found.peek().map(|Reverse(c)| c.score).unwrap_or(f32::NEG_INFINITY);
+            if found.len() >= ef && current.score < worst_found {
+                break;
+            }
+
+            if let Some(neighbors) = self.nodes[current.id].layers.get(layer) {
+                for &neighbor_id in neighbors {
+                    if !visited.insert(neighbor_id) {
+                        continue;
+                    }
+                    let score = cosine_similarity(query, &self.nodes[neighbor_id].vector);
+                    let worst = found.peek().map(|Reverse(c)| c.score).unwrap_or(f32::NEG_INFINITY);
+                    if found.len() < ef || score > worst {
+                        candidates.push(Candidate { id: neighbor_id, score });
+                        found.push(Reverse(Candidate { id: neighbor_id, score }));
+                        if found.len() > ef {
+                            found.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut result: Vec<Candidate> = found.into_iter().map(|Reverse(c)| c).collect();
+        result.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        result
+    }
+
+    fn insert(&mut self, vector: Vec<f32>) -> usize {
+        let id = self.nodes.len();
+        let level = self.random_level();
+        self.nodes.push(Node { vector: vector.clone(), layers: vec![Vec::new(); level + 1] });
+
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(id);
+            self.max_layer = level;
+            return id;
+        };
+
+        let mut current_nearest = vec![entry_point];
+
+        // Descend greedily (ef=1) from the top layer down to one above
+        // this node's top layer, to find a good starting neighborhood.
+        for layer in (level + 1..=self.max_layer).rev() {
+            let found = self.search_layer(&vector, &current_nearest, 1, layer);
+            if let Some(best) = found.first() {
+                current_nearest = vec![best.id];
+            }
+        }
+
+        // From this node's top layer down to the base layer, find
+        // candidate neighbors and connect bidirectionally, pruning each
+        // neighbor's edge list back down to its layer's cap.
+        for layer in (0..=level.min(self.max_layer)).rev() {
+            let found = self.search_layer(&vector, &current_nearest, self.params.ef_construction, layer);
+            let m_layer = if layer == 0 { self.params.m * 2 } else { self.params.m };
+            let selected: Vec<usize> = found.iter().take(m_layer).map(|c| c.id).collect();
+
+            self.nodes[id].layers[layer] = selected.clone();
+
+            for &neighbor_id in &selected {
+                if self.nodes[neighbor_id].layers.len() <= layer {
+                    continue;
+                }
+                self.nodes[neighbor_id].layers[layer].push(id);
+                if self.nodes[neighbor_id].layers[layer].len() > m_layer {
+                    let neighbor_vector = self.nodes[neighbor_id].vector.clone();
+                    let mut scored: Vec<(usize, f32)> = self.nodes[neighbor_id].layers[layer].iter()
+                        .map(|&nid| (nid, cosine_similarity(&neighbor_vector, &self.nodes[nid].vector)))
+                        .collect();
+                    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+                    scored.truncate(m_layer);
+                    self.nodes[neighbor_id].layers[layer] = scored.into_iter().map(|(nid, _)| nid).collect();
+                }
+            }
+
+            current_nearest = found.iter().map(|c| c.id).collect();
+        }
+
+        if level > self.max_layer {
+            self.max_layer = level;
+            self.entry_point = Some(id);
+        }
+
+        id
+    }
+
+    /// Returns up to `top_k` `(vector index, cosine similarity)` pairs
+    /// closest to `query`, sorted by descending similarity.
+    pub fn search(&self, query: &[f32], top_k: usize) -> Vec<(usize, f32)> {
+        let Some(entry_point) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let mut current_nearest = vec![entry_point];
+        for layer in (1..=self.max_layer).rev() {
+            let found = self.search_layer(query, &current_nearest, 1, layer);
+            if let Some(best) = found.first() {
+                current_nearest = vec![best.id];
+            }
+        }
+
+        let mut found = self.search_layer(query, &current_nearest, self.params.ef_search.max(top_k), 0);
+        found.truncate(top_k);
+        found.into_iter().map(|c| (c.id, c.score)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn axis_vector(dim: usize, idx: usize) -> Vec<f32> {
+        let mut v = vec![0.0; dim];
+        v[idx] = 1.0;
+        v
+    }
+
+    #[test]
+    fn finds_exact_match_among_orthogonal_vectors() {
+        let dim = 8;
+        let vectors: Vec<Vec<f32>> = (0..dim).map(|i| axis_vector(dim, i)).collect();
+        let index = HnswIndex::build(&vectors, HnswParams::default());
+
+        let query = axis_vector(dim, 3);
+        let results = index.search(&query, 1);
+
+        assert_eq!(results.first().map(|(idx, _)| *idx), Some(3));
+    }
+
+    #[test]
+    fn ranks_nearest_neighbors_above_far_ones() {
+        let mut vectors = Vec::new();
+        // A tight cluster around (1, 0, 0) plus one clear outlier.
+        for i in 0..20 {
+            let jitter = (i as f32) * 0.001;
+            vectors.push(vec![1.0 - jitter, jitter, 0.0]);
+        }
+        vectors.push(vec![0.0, 0.0, 1.0]);
+
+        let index = HnswIndex::build(&vectors, HnswParams { m: 8, ef_construction: 64, ef_search: 32 });
+        let query = vec![1.0, 0.0, 0.0];
+        let results = index.search(&query, 5);
+
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|(idx, _)| *idx != 20), "the orthogonal outlier should not be in the top 5");
+    }
+
+    #[test]
+    fn empty_index_returns_no_results() {
+        let index = HnswIndex::build(&[], HnswParams::default());
+        assert!(index.search(&[1.0, 0.0], 5).is_empty());
+    }
+}
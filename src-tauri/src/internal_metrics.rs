@@ -0,0 +1,126 @@
+//! Lightweight counters for OpsPilot's own cache/client behavior - not
+//! cluster metrics (see `metrics_server` / `get_cluster_cockpit` for those).
+//! Incremented at the existing cache-check and discovery sites and rendered
+//! as Prometheus text exposition format by the `metrics_text` command, so
+//! power users can spot a thrashing cache or a slow cluster's discovery
+//! latency the same way they'd scrape any other Prometheus target.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+pub(crate) struct CounterMap(OnceLock<Mutex<HashMap<String, u64>>>);
+
+impl CounterMap {
+    const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    fn map(&self) -> &Mutex<HashMap<String, u64>> {
+        self.0.get_or_init(|| Mutex::new(HashMap::new()))
+    }
+
+    pub(crate) fn inc(&self, context: &str) {
+        *self.map().lock().unwrap().entry(context.to_string()).or_insert(0) += 1;
+    }
+
+    fn snapshot(&self) -> Vec<(String, u64)> {
+        let mut rows: Vec<(String, u64)> = self.map().lock().unwrap().iter().map(|(k, v)| (k.clone(), *v)).collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        rows
+    }
+}
+
+pub(crate) struct DurationStatMap(OnceLock<Mutex<HashMap<String, (u64, u64)>>>); // context -> (sum_millis, count)
+
+impl DurationStatMap {
+    const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    fn map(&self) -> &Mutex<HashMap<String, (u64, u64)>> {
+        self.0.get_or_init(|| Mutex::new(HashMap::new()))
+    }
+
+    pub(crate) fn record(&self, context: &str, duration: Duration) {
+        let mut map = self.map().lock().unwrap();
+        let entry = map.entry(context.to_string()).or_insert((0, 0));
+        entry.0 += duration.as_millis() as u64;
+        entry.1 += 1;
+    }
+
+    fn snapshot(&self) -> Vec<(String, u64, u64)> {
+        let mut rows: Vec<(String, u64, u64)> = self.map().lock().unwrap().iter().map(|(k, (s, c))| (k.clone(), *s, *c)).collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        rows
+    }
+}
+
+pub static DISCOVERY_CACHE_HITS: CounterMap = CounterMap::new();
+pub static DISCOVERY_CACHE_MISSES: CounterMap = CounterMap::new();
+pub static DISCOVERY_RUN_DURATION: DurationStatMap = DurationStatMap::new();
+pub static CRD_LIST_DURATION: DurationStatMap = DurationStatMap::new();
+pub static CRDS_LISTED: CounterMap = CounterMap::new();
+pub static CLIENT_CACHE_HITS: CounterMap = CounterMap::new();
+pub static CLIENT_CACHE_MISSES: CounterMap = CounterMap::new();
+pub static CLIENT_BUILD_DURATION: DurationStatMap = DurationStatMap::new();
+static CACHE_CLEARS: AtomicU64 = AtomicU64::new(0);
+
+pub fn record_cache_clear() {
+    CACHE_CLEARS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Increment `counts_by_context`'s entry for `context` by `n` CRDs seen in
+/// one successful listing.
+pub fn record_crds_listed(context: &str, n: u64) {
+    let mut map = CRDS_LISTED.map().lock().unwrap();
+    *map.entry(context.to_string()).or_insert(0) += n;
+}
+
+fn write_counter_help(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+}
+
+fn write_counter_map(out: &mut String, name: &str, help: &str, map: &CounterMap) {
+    write_counter_help(out, name, help);
+    for (context, value) in map.snapshot() {
+        let _ = writeln!(out, "{}{{context=\"{}\"}} {}", name, escape_label(&context), value);
+    }
+}
+
+fn write_duration_stat_map(out: &mut String, name: &str, help: &str, map: &DurationStatMap) {
+    let _ = writeln!(out, "# HELP {}_milliseconds {}", name, help);
+    let _ = writeln!(out, "# TYPE {}_milliseconds summary", name);
+    for (context, sum_millis, count) in map.snapshot() {
+        let _ = writeln!(out, "{}_milliseconds_sum{{context=\"{}\"}} {}", name, escape_label(&context), sum_millis);
+        let _ = writeln!(out, "{}_milliseconds_count{{context=\"{}\"}} {}", name, escape_label(&context), count);
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render every internal counter/histogram as Prometheus text exposition
+/// format, labeled by context name where applicable.
+pub fn render() -> String {
+    let mut out = String::new();
+
+    write_counter_map(&mut out, "opspilot_discovery_cache_hits_total", "Discovery cache hits", &DISCOVERY_CACHE_HITS);
+    write_counter_map(&mut out, "opspilot_discovery_cache_misses_total", "Discovery cache misses", &DISCOVERY_CACHE_MISSES);
+    write_duration_stat_map(&mut out, "opspilot_discovery_run_duration", "Time spent in Discovery::run", &DISCOVERY_RUN_DURATION);
+    write_duration_stat_map(&mut out, "opspilot_crd_list_duration", "Time spent listing CustomResourceDefinitions", &CRD_LIST_DURATION);
+    write_counter_map(&mut out, "opspilot_crds_listed_total", "Total CRDs seen across all successful listings", &CRDS_LISTED);
+    write_counter_map(&mut out, "opspilot_client_cache_hits_total", "Kubernetes client cache hits", &CLIENT_CACHE_HITS);
+    write_counter_map(&mut out, "opspilot_client_cache_misses_total", "Kubernetes client cache misses", &CLIENT_CACHE_MISSES);
+    write_duration_stat_map(&mut out, "opspilot_client_build_duration", "Time spent building a new Kubernetes client (not served from cache)", &CLIENT_BUILD_DURATION);
+
+    let _ = writeln!(out, "# HELP opspilot_cache_clears_total Times clear_all_caches has been invoked");
+    let _ = writeln!(out, "# TYPE opspilot_cache_clears_total counter");
+    let _ = writeln!(out, "opspilot_cache_clears_total {}", CACHE_CLEARS.load(Ordering::Relaxed));
+
+    out
+}
@@ -2,9 +2,477 @@ use std::fs;
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 use tauri::Manager;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::path::PathBuf;
 use crate::embeddings;
 
+// =============================================================================
+// BM25 INVERTED INDEX
+// =============================================================================
+
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+/// Expanded (synonym) terms contribute less signal than a term the user
+/// actually typed, so they get a reduced IDF weight.
+const SYNONYM_IDF_WEIGHT: f32 = 0.5;
+
+#[derive(Clone)]
+struct IndexedDoc {
+    filename: String,
+    content: String,
+    tokens: Vec<String>,
+    quick_fix: Option<String>,
+    recommended_tools: Option<Vec<String>>,
+}
+
+#[derive(Clone, Default)]
+struct KnowledgeIndex {
+    docs: Vec<IndexedDoc>,
+    // term -> Vec<(doc_id, term_frequency)>
+    postings: HashMap<String, Vec<(usize, usize)>>,
+    doc_freq: HashMap<String, usize>,
+    avg_doc_len: f32,
+    indexed_dir: Option<PathBuf>,
+    // Vocabulary bucketed by character length, for fast typo-candidate
+    // pruning (see `typo_candidates`).
+    vocab_by_length: HashMap<usize, Vec<String>>,
+}
+
+static KNOWLEDGE_INDEX: Mutex<Option<KnowledgeIndex>> = Mutex::new(None);
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.len() >= 2)
+        .map(|t| normalize_term(t))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn resolve_knowledge_dir(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    let resource_path = app_handle.path().resource_dir().ok()?;
+    let knowledge_path = resource_path.join("knowledge");
+
+    let search_paths = [knowledge_path, std::env::current_dir().unwrap_or_default().join("knowledge")];
+
+    search_paths.into_iter().find(|p| p.exists())
+}
+
+fn load_doc(entry: &walkdir::DirEntry) -> Option<IndexedDoc> {
+    let ext = entry.path().extension().and_then(|e| e.to_str()).unwrap_or("");
+    let filename = entry.file_name().to_string_lossy().to_string();
+
+    let mut quick_fix: Option<String> = None;
+    let mut recommended_tools: Option<Vec<String>> = None;
+
+    let content = if ext == "md" {
+        fs::read_to_string(entry.path()).unwrap_or_default()
+    } else if ext == "json" {
+        let file_content = fs::read_to_string(entry.path()).unwrap_or_default();
+        let json: serde_json::Value = serde_json::from_str(&file_content).unwrap_or(serde_json::Value::Null);
+
+        if let Some(fix) = json.get("quick_fix").and_then(|v| v.as_str()) {
+            quick_fix = Some(fix.to_string());
+        }
+        if let Some(tools) = json.get("recommended_tools").and_then(|v| v.as_array()) {
+            recommended_tools = Some(tools.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect());
+        }
+
+        extract_text_from_json(&json)
+    } else {
+        return None;
+    };
+
+    if content.is_empty() {
+        return None;
+    }
+
+    let tokens = tokenize(&content);
+
+    Some(IndexedDoc { filename, content, tokens, quick_fix, recommended_tools })
+}
+
+/// Walk the knowledge dir once and build the inverted index: term -> list of
+/// (doc_id, term_frequency), plus per-doc length and document frequency, so
+/// queries no longer re-read and re-scan every file.
+fn build_index(dir: &PathBuf) -> KnowledgeIndex {
+    let mut docs = Vec::new();
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        if entry.file_type().is_file() {
+            if let Some(doc) = load_doc(&entry) {
+                docs.push(doc);
+            }
+        }
+    }
+
+    let mut postings: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    let mut doc_freq: HashMap<String, usize> = HashMap::new();
+    let mut total_len = 0usize;
+
+    for (doc_id, doc) in docs.iter().enumerate() {
+        total_len += doc.tokens.len();
+
+        let mut term_counts: HashMap<&str, usize> = HashMap::new();
+        for token in &doc.tokens {
+            *term_counts.entry(token.as_str()).or_insert(0) += 1;
+        }
+
+        for (term, tf) in term_counts {
+            postings.entry(term.to_string()).or_default().push((doc_id, tf));
+            *doc_freq.entry(term.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    let avg_doc_len = if docs.is_empty() { 0.0 } else { total_len as f32 / docs.len() as f32 };
+
+    let mut vocab_by_length: HashMap<usize, Vec<String>> = HashMap::new();
+    for term in doc_freq.keys() {
+        vocab_by_length.entry(term.chars().count()).or_default().push(term.clone());
+    }
+
+    KnowledgeIndex { docs, postings, doc_freq, avg_doc_len, indexed_dir: Some(dir.clone()), vocab_by_length }
+}
+
+fn get_or_build_index(app_handle: &tauri::AppHandle) -> Option<KnowledgeIndex> {
+    let dir = resolve_knowledge_dir(app_handle)?;
+
+    if let Ok(cache) = KNOWLEDGE_INDEX.lock() {
+        if let Some(index) = cache.as_ref() {
+            if index.indexed_dir.as_ref() == Some(&dir) {
+                return Some(index.clone());
+            }
+        }
+    }
+
+    let index = build_index(&dir);
+    if let Ok(mut cache) = KNOWLEDGE_INDEX.lock() {
+        *cache = Some(index.clone());
+    }
+    Some(index)
+}
+
+/// Rebuild the knowledge base index from disk. Call this after adding or
+/// editing `.md`/`.json` files in the knowledge directory.
+#[tauri::command]
+pub async fn reindex_knowledge_base(app_handle: tauri::AppHandle) -> Result<usize, String> {
+    let dir = resolve_knowledge_dir(&app_handle).ok_or("Knowledge base directory not found")?;
+    let index = build_index(&dir);
+    let count = index.docs.len();
+    if let Ok(mut cache) = KNOWLEDGE_INDEX.lock() {
+        *cache = Some(index);
+    }
+    Ok(count)
+}
+
+fn idf(index: &KnowledgeIndex, term: &str) -> f32 {
+    let n = index.docs.len() as f32;
+    let df = *index.doc_freq.get(term).unwrap_or(&0) as f32;
+    ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+}
+
+fn bm25_term_score(index: &KnowledgeIndex, term: &str, doc_id: usize) -> f32 {
+    let Some(postings) = index.postings.get(term) else { return 0.0 };
+    let Some(&(_, tf)) = postings.iter().find(|(id, _)| *id == doc_id) else { return 0.0 };
+
+    let tf = tf as f32;
+    let doc_len = index.docs[doc_id].tokens.len() as f32;
+    let idf = idf(index, term);
+
+    idf * (tf * (BM25_K1 + 1.0)) / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / index.avg_doc_len.max(1.0)))
+}
+
+// =============================================================================
+// TYPO TOLERANCE
+// =============================================================================
+
+/// Maximum edit distance tolerated for a query term of the given length,
+/// modeled on MeiliSearch's typo ranking rule.
+fn typo_budget(term_len: usize) -> usize {
+    if term_len <= 4 {
+        0
+    } else if term_len <= 8 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Bounded Levenshtein distance with an early exit once a row's running
+/// minimum already exceeds `max_d` - the rest of that row can only grow.
+fn levenshtein_bounded(a: &[char], b: &[char], max_d: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max_d {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut cur = vec![0usize; b.len() + 1];
+        cur[0] = i + 1;
+        let mut row_min = cur[0];
+
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j + 1] + 1).min(cur[j] + 1).min(prev[j] + cost);
+            row_min = row_min.min(cur[j + 1]);
+        }
+
+        if row_min > max_d {
+            return None;
+        }
+        prev = cur;
+    }
+
+    let distance = prev[b.len()];
+    (distance <= max_d).then_some(distance)
+}
+
+/// Find vocabulary terms within this query term's typo budget. Candidates are
+/// pruned first by length bucket (length difference ≤ D) and a shared
+/// prefix, and only the survivors pay for the full edit-distance DP.
+fn typo_candidates(index: &KnowledgeIndex, term: &str) -> Vec<(String, usize)> {
+    let term_chars: Vec<char> = term.chars().collect();
+    let max_d = typo_budget(term_chars.len());
+    if max_d == 0 {
+        return vec![];
+    }
+
+    let min_len = term_chars.len().saturating_sub(max_d);
+    let max_len = term_chars.len() + max_d;
+
+    let mut candidates = Vec::new();
+    for len in min_len..=max_len {
+        let Some(bucket) = index.vocab_by_length.get(&len) else { continue };
+        for candidate in bucket {
+            if candidate == term {
+                continue;
+            }
+            let candidate_chars: Vec<char> = candidate.chars().collect();
+
+            // No cheap same-length prune here: a rotation or a shifted
+            // delete+insert can make every position mismatch while the true
+            // edit distance stays small (e.g. "kubernetes" vs "ubernetesk"
+            // is a distance-2 rotation but mismatches at all 10 positions),
+            // so a Hamming-style bound is unsound. The DP's own
+            // `row_min > max_d` early exit already bounds the cost.
+            if let Some(distance) = levenshtein_bounded(&term_chars, &candidate_chars, max_d) {
+                if distance > 0 {
+                    candidates.push((candidate.clone(), distance));
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Typo matches at distance 1 still outrank distance 2, but both stay below
+/// an exact/synonym hit.
+fn typo_weight(distance: usize) -> f32 {
+    match distance {
+        1 => 0.7,
+        _ => 0.4,
+    }
+}
+
+// =============================================================================
+// PROXIMITY
+// =============================================================================
+
+/// Minimum number of tokens spanning a window that contains at least one
+/// occurrence of every term in `terms`, using the classic minimum-window
+/// two-pointer sweep over the positions where any of those terms occur.
+/// Returns `None` if `terms` is empty or not every term occurs in `tokens`.
+fn min_span_window(tokens: &[String], terms: &HashSet<&str>) -> Option<usize> {
+    if terms.is_empty() {
+        return None;
+    }
+
+    let positions: Vec<(usize, &str)> = tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| terms.contains(t.as_str()).then_some((i, t.as_str())))
+        .collect();
+
+    if positions.is_empty() {
+        return None;
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut distinct = 0;
+    let mut left = 0;
+    let mut best: Option<usize> = None;
+
+    for right in 0..positions.len() {
+        let term = positions[right].1;
+        let count = counts.entry(term).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            distinct += 1;
+        }
+
+        while distinct == terms.len() {
+            let window = positions[right].0 - positions[left].0 + 1;
+            best = Some(best.map_or(window, |b| b.min(window)));
+
+            let left_term = positions[left].1;
+            let count = counts.get_mut(left_term).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                distinct -= 1;
+            }
+            left += 1;
+        }
+    }
+
+    best
+}
+
+/// Documents where the matched terms co-occur tightly should rank above ones
+/// where they're scattered across an otherwise-dense document.
+fn proximity_bonus(matched_term_count: usize, min_window: Option<usize>) -> f32 {
+    match min_window {
+        Some(window) => (matched_term_count as f32).powi(2) / (1.0 + window as f32),
+        None => 0.0,
+    }
+}
+
+// =============================================================================
+// RANKING RULES PIPELINE
+// =============================================================================
+//
+// Modeled on MeiliSearch's ranking-rule design: an ordered list of rules is
+// applied as a cascade of stable bucket sorts (last rule first, so earlier
+// rules end up primary and later rules only break ties within a tier).
+
+/// Per-document signals collected by `keyword_search`, consumed by the
+/// ranking rules to produce the final ordering.
+struct RankingCandidate {
+    doc_id: usize,
+    bm25_score: f32,
+    matched_terms: usize,
+    typo_distance_sum: usize,
+    proximity_bonus: f32,
+    filename_matches: usize,
+    tag_matches: usize,
+    exact_matches: usize,
+}
+
+trait RankingRule {
+    fn name(&self) -> &'static str;
+    /// Higher tier sorts first.
+    fn tier(&self, candidate: &RankingCandidate) -> i64;
+}
+
+struct WordsRule;
+impl RankingRule for WordsRule {
+    fn name(&self) -> &'static str { "words" }
+    fn tier(&self, c: &RankingCandidate) -> i64 { c.matched_terms as i64 }
+}
+
+struct TypoRule;
+impl RankingRule for TypoRule {
+    fn name(&self) -> &'static str { "typo" }
+    // Fewer (and smaller) typo corrections is better; an exact match scores 0.
+    fn tier(&self, c: &RankingCandidate) -> i64 { -(c.typo_distance_sum as i64) }
+}
+
+struct ProximityRule;
+impl RankingRule for ProximityRule {
+    fn name(&self) -> &'static str { "proximity" }
+    fn tier(&self, c: &RankingCandidate) -> i64 { (c.proximity_bonus * 1000.0) as i64 }
+}
+
+struct FilenameBoostRule;
+impl RankingRule for FilenameBoostRule {
+    fn name(&self) -> &'static str { "filename" }
+    fn tier(&self, c: &RankingCandidate) -> i64 { c.filename_matches as i64 }
+}
+
+struct TagsRule;
+impl RankingRule for TagsRule {
+    fn name(&self) -> &'static str { "tags" }
+    fn tier(&self, c: &RankingCandidate) -> i64 { c.tag_matches as i64 }
+}
+
+struct ExactnessRule;
+impl RankingRule for ExactnessRule {
+    fn name(&self) -> &'static str { "exactness" }
+    fn tier(&self, c: &RankingCandidate) -> i64 { c.exact_matches as i64 }
+}
+
+fn rule_from_name(name: &str) -> Option<Box<dyn RankingRule>> {
+    match name {
+        "words" => Some(Box::new(WordsRule)),
+        "typo" => Some(Box::new(TypoRule)),
+        "proximity" => Some(Box::new(ProximityRule)),
+        "filename" => Some(Box::new(FilenameBoostRule)),
+        "tags" => Some(Box::new(TagsRule)),
+        "exactness" => Some(Box::new(ExactnessRule)),
+        _ => None,
+    }
+}
+
+const DEFAULT_RANKING_RULES: &[&str] = &["words", "typo", "proximity", "filename", "tags", "exactness"];
+
+fn ranking_rules_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".opspilot")
+        .join("ranking_rules.json")
+}
+
+fn load_ranking_rules() -> Vec<String> {
+    let path = ranking_rules_path();
+    if let Ok(content) = fs::read_to_string(&path) {
+        if let Ok(rules) = serde_json::from_str::<Vec<String>>(&content) {
+            if !rules.is_empty() && rules.iter().all(|r| rule_from_name(r).is_some()) {
+                return rules;
+            }
+        }
+    }
+    DEFAULT_RANKING_RULES.iter().map(|s| s.to_string()).collect()
+}
+
+/// Get the active ranking-rule order (e.g. to prioritize filename matches
+/// over proximity for a command-reference KB).
+#[tauri::command]
+pub async fn get_ranking_rules() -> Result<Vec<String>, String> {
+    Ok(load_ranking_rules())
+}
+
+/// Persist a new ranking-rule order used by `search_knowledge_base`. Unknown
+/// rule names are rejected; valid names are `words`, `typo`, `proximity`,
+/// `filename`, `tags`, `exactness`.
+#[tauri::command]
+pub async fn set_ranking_rules(rules: Vec<String>) -> Result<(), String> {
+    if rules.is_empty() {
+        return Err("Ranking rule order cannot be empty".to_string());
+    }
+    if let Some(unknown) = rules.iter().find(|r| rule_from_name(r).is_none()) {
+        return Err(format!("Unknown ranking rule: {}", unknown));
+    }
+
+    let path = ranking_rules_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&rules).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write ranking rules: {}", e))?;
+
+    Ok(())
+}
+
+/// Cascade the rule order as stable bucket sorts, last rule first, so the
+/// first rule in `order` ends up the primary sort key.
+fn apply_ranking_pipeline(candidates: &mut [RankingCandidate], order: &[String]) {
+    for name in order.iter().rev() {
+        if let Some(rule) = rule_from_name(name) {
+            candidates.sort_by(|a, b| rule.tier(b).cmp(&rule.tier(a)));
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SearchResult {
     pub file: String,
@@ -58,6 +526,155 @@ fn get_term_expansions(term: &str) -> Vec<&'static str> {
     }
 }
 
+// =============================================================================
+// SYNONYM SETTINGS
+// =============================================================================
+//
+// `get_term_expansions` above is a compile-time table of Kubernetes/Crossplane
+// vocabulary. Teams with domain-specific terms (internal CRDs, Terraform
+// modules, service names) extend it at runtime via `synonyms.json` in the
+// knowledge resource dir, merged on top of the built-in table.
+
+/// Custom synonym settings, merged with (not replacing) the built-in
+/// `get_term_expansions` table. `one_way` expands a term to extra terms
+/// without the reverse being true (e.g. "xplane" -> "crossplane"); `groups`
+/// are bidirectional equivalence sets where every member expands to every
+/// other member.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SynonymSettings {
+    #[serde(default)]
+    pub one_way: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub groups: Vec<Vec<String>>,
+}
+
+struct SynonymCache {
+    settings: SynonymSettings,
+    dir: PathBuf,
+}
+
+static SYNONYM_CACHE: Mutex<Option<SynonymCache>> = Mutex::new(None);
+
+fn synonyms_file_path(dir: &PathBuf) -> PathBuf {
+    dir.join("synonyms.json")
+}
+
+fn load_synonyms_from_disk(dir: &PathBuf) -> SynonymSettings {
+    fs::read_to_string(synonyms_file_path(dir))
+        .ok()
+        .and_then(|content| serde_json::from_str::<SynonymSettings>(&content).ok())
+        .unwrap_or_default()
+}
+
+fn get_or_load_synonyms(app_handle: &tauri::AppHandle) -> SynonymSettings {
+    let Some(dir) = resolve_knowledge_dir(app_handle) else {
+        return SynonymSettings::default();
+    };
+
+    if let Ok(cache) = SYNONYM_CACHE.lock() {
+        if let Some(c) = cache.as_ref() {
+            if c.dir == dir {
+                return c.settings.clone();
+            }
+        }
+    }
+
+    let settings = load_synonyms_from_disk(&dir);
+    if let Ok(mut cache) = SYNONYM_CACHE.lock() {
+        *cache = Some(SynonymCache { settings: settings.clone(), dir });
+    }
+    settings
+}
+
+/// Read the custom synonym settings (the built-in `get_term_expansions` table
+/// is always merged in on top of this and isn't part of the payload).
+#[tauri::command]
+pub async fn get_synonyms(app_handle: tauri::AppHandle) -> Result<SynonymSettings, String> {
+    Ok(get_or_load_synonyms(&app_handle))
+}
+
+/// Persist custom synonym settings to the knowledge resource dir and
+/// hot-reload the in-memory map used by `search_knowledge_base`.
+#[tauri::command]
+pub async fn set_synonyms(settings: SynonymSettings, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let dir = resolve_knowledge_dir(&app_handle).ok_or("Knowledge base directory not found")?;
+
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(synonyms_file_path(&dir), json).map_err(|e| format!("Failed to write synonyms: {}", e))?;
+
+    if let Ok(mut cache) = SYNONYM_CACHE.lock() {
+        *cache = Some(SynonymCache { settings, dir });
+    }
+
+    Ok(())
+}
+
+/// Expand `term` with both the built-in table and any custom synonym
+/// settings (one-way expansions plus bidirectional equivalence groups).
+fn expand_with_synonyms(term: &str, custom: &SynonymSettings) -> Vec<String> {
+    let mut result: HashSet<String> = get_term_expansions(term).into_iter().map(|s| s.to_string()).collect();
+
+    if let Some(extra) = custom.one_way.get(term) {
+        result.extend(extra.iter().cloned());
+    }
+    for group in &custom.groups {
+        if group.iter().any(|member| member == term) {
+            result.extend(group.iter().cloned());
+        }
+    }
+
+    result.into_iter().collect()
+}
+
+/// Embedder configuration so the hybrid keyword/semantic path knows what
+/// vector space `semantic_search_knowledge_base`'s caller-supplied matches
+/// live in, matching the repo's autoembedding-style config blocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbedderSettings {
+    pub model: String,
+    pub dimension: usize,
+    pub agent_generates_embeddings: bool,
+}
+
+impl Default for EmbedderSettings {
+    fn default() -> Self {
+        Self {
+            model: "nomic-embed-text".to_string(),
+            dimension: 768,
+            agent_generates_embeddings: true,
+        }
+    }
+}
+
+fn embedder_settings_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".opspilot")
+        .join("embedder.json")
+}
+
+#[tauri::command]
+pub async fn get_embedder_settings() -> Result<EmbedderSettings, String> {
+    if let Ok(content) = fs::read_to_string(embedder_settings_path()) {
+        if let Ok(settings) = serde_json::from_str(&content) {
+            return Ok(settings);
+        }
+    }
+    Ok(EmbedderSettings::default())
+}
+
+#[tauri::command]
+pub async fn set_embedder_settings(settings: EmbedderSettings) -> Result<(), String> {
+    let path = embedder_settings_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write embedder settings: {}", e))?;
+    Ok(())
+}
+
 /// Extract tags from content for better categorization
 fn extract_tags(content: &str) -> Vec<String> {
     let mut tags = HashSet::new();
@@ -175,7 +792,22 @@ fn normalize_term(term: &str) -> String {
 
 #[tauri::command]
 pub async fn search_knowledge_base(query: String, app_handle: tauri::AppHandle) -> Result<Vec<SearchResult>, String> {
-    let mut results = Vec::new();
+    let mut results = keyword_search(&query, &app_handle)?;
+    results.truncate(8);
+    Ok(results)
+}
+
+/// BM25 keyword search over the full knowledge base, sorted by score
+/// descending with no cap on the result count. `search_knowledge_base` and
+/// `semantic_search_knowledge_base` both build on this.
+fn keyword_search(query: &str, app_handle: &tauri::AppHandle) -> Result<Vec<SearchResult>, String> {
+    let Some(index) = get_or_build_index(app_handle) else {
+        return Ok(vec![]); // No knowledge base found
+    };
+
+    if index.docs.is_empty() {
+        return Ok(vec![]);
+    }
 
     // Clean and normalize query - remove punctuation, lowercase
     let query_clean: String = query.chars()
@@ -183,166 +815,138 @@ pub async fn search_knowledge_base(query: String, app_handle: tauri::AppHandle)
         .collect();
     let query_lower = query_clean.to_lowercase();
 
-    // Split and normalize terms
+    // Split and normalize terms (these are the terms actually typed by the user)
     let query_terms: Vec<String> = query_lower
         .split_whitespace()
         .filter(|t| t.len() >= 2) // Skip very short terms
         .map(|t| normalize_term(t))
         .collect();
 
-    // Also keep original terms for exact matching
     let original_terms: Vec<&str> = query_lower.split_whitespace().collect();
 
-    // Expand query terms with synonyms
+    // Expand query terms with synonyms (built-in table merged with any
+    // runtime-loaded custom settings) - these get a reduced IDF weight below
+    // since they weren't actually typed by the user.
+    let custom_synonyms = get_or_load_synonyms(app_handle);
     let mut expanded_terms: HashSet<String> = HashSet::new();
     for term in &query_terms {
-        expanded_terms.insert(term.clone());
-        // Try both the normalized term and the original for expansion lookup
-        for expansion in get_term_expansions(term) {
-            expanded_terms.insert(expansion.to_string());
+        for expansion in expand_with_synonyms(term, &custom_synonyms) {
+            expanded_terms.insert(expansion);
         }
     }
     for term in &original_terms {
-        for expansion in get_term_expansions(term) {
-            expanded_terms.insert(expansion.to_string());
-        }
-    }
-
-    // Resolve the knowledge directory relative to the resource path
-    let resource_path = app_handle.path().resource_dir().map_err(|e| e.to_string())?;
-    let knowledge_path = resource_path.join("knowledge");
-
-    // Fallback for development if resource dir doesn't have it
-    let search_paths = vec![
-        knowledge_path.clone(),
-        std::env::current_dir().unwrap_or_default().join("knowledge"),
-    ];
-
-    let mut found_path = None;
-    for path in search_paths {
-        if path.exists() {
-            found_path = Some(path);
-            break;
+        for expansion in expand_with_synonyms(term, &custom_synonyms) {
+            expanded_terms.insert(expansion);
         }
     }
+    expanded_terms.retain(|t| !query_terms.contains(t));
+
+    // Query terms with no exact match anywhere in the index fall back to
+    // bounded typo matching so misspellings and morphological variants still
+    // hit (e.g. "deployemnt" -> "deployment", "crashlooping" -> "crashloop").
+    let typo_terms: HashMap<&String, Vec<(String, usize)>> = query_terms
+        .iter()
+        .filter(|t| !index.doc_freq.contains_key(t.as_str()))
+        .map(|t| (t, typo_candidates(&index, t)))
+        .collect();
 
-    let search_dir = match found_path {
-        Some(p) => p,
-        None => return Ok(vec![]), // No knowledge base found
-    };
-
-    for entry in WalkDir::new(search_dir).into_iter().filter_map(|e| e.ok()) {
-        if entry.file_type().is_file() {
-            let ext = entry.path().extension().and_then(|e| e.to_str()).unwrap_or("");
-            let filename = entry.file_name().to_string_lossy().to_string();
-
-            let mut quick_fix: Option<String> = None;
-            let mut recommended_tools: Option<Vec<String>> = None;
-
-            let content = if ext == "md" {
-                fs::read_to_string(entry.path()).unwrap_or_default()
-            } else if ext == "json" {
-                let file_content = fs::read_to_string(entry.path()).unwrap_or_default();
-                let json: serde_json::Value = serde_json::from_str(&file_content).unwrap_or(serde_json::Value::Null);
-                
-                if let Some(fix) = json.get("quick_fix").and_then(|v| v.as_str()) {
-                    quick_fix = Some(fix.to_string());
-                }
-                if let Some(tools) = json.get("recommended_tools").and_then(|v| v.as_array()) {
-                    recommended_tools = Some(tools.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect());
-                }
-
-                extract_text_from_json(&json)
-            } else {
-                continue;
-            };
-
-            if content.is_empty() {
-                continue;
-            }
+    let mut candidates = Vec::new();
+    let mut built: HashMap<usize, SearchResult> = HashMap::new();
 
-            let content_lower = content.to_lowercase();
-            let filename_lower = filename.to_lowercase();
+    for (doc_id, doc) in index.docs.iter().enumerate() {
+        let mut score = 0.0;
+        let mut matched_query_terms = 0;
+        let mut typo_distance_sum = 0;
+        let mut matched_term_set: HashSet<&str> = HashSet::new();
 
-            let mut score = 0.0;
-            let mut direct_matches = 0;
-            let mut expanded_matches = 0;
-
-            // Score normalized query terms
-            for term in &query_terms {
-                // Check for substring match (handles crashloop matching crashloopbackoff)
-                if content_lower.contains(term.as_str()) {
-                    direct_matches += 1;
-                    score += 2.0;
-                }
-                // Bonus for filename match
-                if filename_lower.contains(term.as_str()) {
-                    score += 3.0;
-                }
+        for term in &query_terms {
+            let term_score = bm25_term_score(&index, term, doc_id);
+            if term_score > 0.0 {
+                matched_query_terms += 1;
+                matched_term_set.insert(term.as_str());
             }
-
-            // Score expanded terms
-            for term in &expanded_terms {
-                if !query_terms.contains(term) && content_lower.contains(term.as_str()) {
-                    expanded_matches += 1;
-                    score += 0.5;
+            score += term_score;
+
+            if let Some(typo_matches) = typo_terms.get(term) {
+                for (candidate, distance) in typo_matches {
+                    let typo_score = bm25_term_score(&index, candidate, doc_id) * typo_weight(*distance);
+                    if typo_score > 0.0 {
+                        matched_query_terms += 1;
+                        typo_distance_sum += distance;
+                    }
+                    score += typo_score;
                 }
             }
+        }
 
-            // Bonus for original terms (exact match)
-            for term in &original_terms {
-                if content_lower.contains(*term) {
-                    score += 1.0;
-                }
-            }
+        for term in &expanded_terms {
+            score += bm25_term_score(&index, term, doc_id) * SYNONYM_IDF_WEIGHT;
+        }
 
-            // Bonus for matching multiple terms (relevance boost)
-            if direct_matches > 1 {
-                score += (direct_matches as f32) * 1.5;
-            }
-            if expanded_matches > 2 {
-                score += 1.0; // Bonus for multiple synonym matches
-            }
+        // Filename match is a strong signal BM25 alone can't see (it only
+        // scores the indexed body text), so keep it as a flat bonus.
+        let filename_lower = doc.filename.to_lowercase();
+        let filename_matches = query_terms.iter().filter(|t| filename_lower.contains(t.as_str())).count();
+        score += filename_matches as f32 * 1.5;
 
-            // Extract tags and check for tag matches
-            let tags = extract_tags(&content);
-            let category = determine_category(&filename, &content);
+        let tags = extract_tags(&doc.content);
+        let tag_matches = query_terms.iter().filter(|t| tags.iter().any(|tag| tag.contains(t.as_str()))).count();
+        score += tag_matches as f32 * 1.0;
 
-            // Bonus for tag matches with query
-            for term in &query_terms {
-                if tags.iter().any(|t| t.contains(term.as_str())) {
-                    score += 2.0;
-                }
-            }
+        if matched_query_terms > 1 {
+            score += matched_query_terms as f32 * 0.25;
+        }
 
-            if score > 0.0 {
-                // Extract a better snippet with more context
-                let snippet = extract_structured_snippet(&content, &query_terms, &expanded_terms);
-
-                results.push(SearchResult {
-                    file: filename,
-                    content: snippet,
-                    score,
-                    tags,
-                    category,
-                    quick_fix: quick_fix.clone(),
-                    recommended_tools: recommended_tools.clone(),
-                });
-            }
+        let min_window = min_span_window(&doc.tokens, &matched_term_set);
+        let proximity = proximity_bonus(matched_term_set.len(), min_window);
+        score += proximity;
+
+        let content_lower = doc.content.to_lowercase();
+        let exact_matches = original_terms.iter().filter(|t| content_lower.contains(**t)).count();
+
+        if score > 0.0 {
+            let category = determine_category(&doc.filename, &doc.content);
+            let snippet = extract_structured_snippet(&doc.content, &query_terms, &expanded_terms);
+
+            built.insert(doc_id, SearchResult {
+                file: doc.filename.clone(),
+                content: snippet,
+                score,
+                tags,
+                category,
+                quick_fix: doc.quick_fix.clone(),
+                recommended_tools: doc.recommended_tools.clone(),
+            });
+
+            candidates.push(RankingCandidate {
+                doc_id,
+                bm25_score: score,
+                matched_terms: matched_query_terms,
+                typo_distance_sum,
+                proximity_bonus: proximity,
+                filename_matches,
+                tag_matches,
+                exact_matches,
+            });
         }
     }
 
-    // Sort by score descending
-    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    let rule_order = load_ranking_rules();
+    apply_ranking_pipeline(&mut candidates, &rule_order);
 
-    // Return top 8 for better coverage
-    Ok(results.into_iter().take(8).collect())
+    let results = candidates
+        .into_iter()
+        .filter_map(|c| built.remove(&c.doc_id).map(|mut r| { r.score = c.bm25_score; r }))
+        .collect();
+
+    Ok(results)
 }
 
 /// Extract a structured snippet with better context
 fn extract_structured_snippet(content: &str, direct_terms: &[String], expanded_terms: &HashSet<String>) -> String {
     let lines: Vec<&str> = content.lines().collect();
-    let mut snippets: Vec<(usize, String, usize)> = Vec::new(); // (start_idx, text, term_count)
+    // (start_idx, text, term_count, combined score including proximity)
+    let mut snippets: Vec<(usize, String, usize, f32)> = Vec::new();
 
     // Use larger window of 10 lines for better context
     let window_size = 10;
@@ -367,12 +971,19 @@ fn extract_structured_snippet(content: &str, direct_terms: &[String], expanded_t
         let total_score = direct_count * 2 + expanded_count;
 
         if total_score > 0 {
-            snippets.push((i, window_text, total_score));
+            // Prefer the passage where the matched terms actually co-occur,
+            // not just the window with the highest raw match count.
+            let window_tokens = tokenize(&window_text);
+            let matched: HashSet<&str> = direct_terms.iter().map(|t| t.as_str()).collect();
+            let min_window = min_span_window(&window_tokens, &matched);
+            let combined = total_score as f32 + proximity_bonus(direct_count, min_window);
+
+            snippets.push((i, window_text, total_score, combined));
         }
     }
 
-    // Sort by score descending
-    snippets.sort_by(|a, b| b.2.cmp(&a.2));
+    // Sort by combined score (raw match count + proximity) descending
+    snippets.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal));
 
     if snippets.is_empty() {
         // Fallback to first 10 lines
@@ -475,18 +1086,94 @@ fn extract_text_from_json(value: &serde_json::Value) -> String {
     text
 }
 
-/// Hybrid semantic + keyword search for best results
-/// As of v0.2.6+, fastembed has been removed. This function now falls back to
-/// keyword search since runtime embedding generation is handled by the Python agent.
+/// A single (file, similarity) hit from an embedding-based nearest-neighbor
+/// search, supplied by the caller (the Python agent computes embeddings;
+/// fastembed was removed from the Rust side).
+#[derive(Debug, Deserialize, Clone)]
+pub struct SemanticMatch {
+    pub file: String,
+    pub similarity: f32,
+}
+
+/// Reciprocal Rank Fusion constant. Larger k flattens the influence of rank
+/// position; 60 is the standard value from the TREC literature.
+const RRF_K: f32 = 60.0;
+
+fn bare_result_from_doc(doc: &IndexedDoc) -> SearchResult {
+    let category = determine_category(&doc.filename, &doc.content);
+    let tags = extract_tags(&doc.content);
+    let snippet: String = doc.content.lines().take(10).collect::<Vec<&str>>().join("\n");
+
+    SearchResult {
+        file: doc.filename.clone(),
+        content: snippet,
+        score: 0.0,
+        tags,
+        category,
+        quick_fix: doc.quick_fix.clone(),
+        recommended_tools: doc.recommended_tools.clone(),
+    }
+}
+
+/// Hybrid semantic + keyword search. Runs the BM25 keyword path and fuses it
+/// with `semantic_results` (embedding similarity pairs the Python agent
+/// already computed, since fastembed was removed from the Rust side) using
+/// Reciprocal Rank Fusion, so the two rankings don't need comparable scales.
+/// `semantic_weight` (0.0-1.0, default 0.5) biases the fusion toward keyword
+/// or vector results; when `semantic_results` is omitted this degrades to a
+/// plain keyword search.
 #[tauri::command]
 pub async fn semantic_search_knowledge_base(
     query: String,
-    app_handle: tauri::AppHandle
+    app_handle: tauri::AppHandle,
+    semantic_results: Option<Vec<SemanticMatch>>,
+    semantic_weight: Option<f32>,
 ) -> Result<Vec<SearchResult>, String> {
-    // fastembed has been removed - embeddings are now generated by Python agent
-    // Fall back to keyword search for all queries from Rust side
-    eprintln!("[DEBUG] semantic_search_knowledge_base: using keyword search (fastembed removed)");
-    search_knowledge_base(query, app_handle).await
+    let keyword_results = keyword_search(&query, &app_handle)?;
+
+    let Some(mut semantic) = semantic_results else {
+        return Ok(keyword_results.into_iter().take(8).collect());
+    };
+    semantic.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
+
+    let semantic_weight = semantic_weight.unwrap_or(0.5).clamp(0.0, 1.0);
+    let keyword_weight = 1.0 - semantic_weight;
+
+    let mut rrf_scores: HashMap<String, f32> = HashMap::new();
+    let mut by_file: HashMap<String, SearchResult> = HashMap::new();
+
+    for (rank, result) in keyword_results.into_iter().enumerate() {
+        *rrf_scores.entry(result.file.clone()).or_insert(0.0) += keyword_weight / (RRF_K + (rank + 1) as f32);
+        by_file.insert(result.file.clone(), result);
+    }
+
+    // Only needed to materialize a SearchResult for a semantic-only hit that
+    // has no keyword entry yet.
+    let index = get_or_build_index(&app_handle);
+
+    for (rank, m) in semantic.iter().enumerate() {
+        *rrf_scores.entry(m.file.clone()).or_insert(0.0) += semantic_weight / (RRF_K + (rank + 1) as f32);
+        if !by_file.contains_key(&m.file) {
+            if let Some(index) = &index {
+                if let Some(doc) = index.docs.iter().find(|d| d.filename == m.file) {
+                    by_file.insert(m.file.clone(), bare_result_from_doc(doc));
+                }
+            }
+        }
+    }
+
+    let mut fused: Vec<SearchResult> = by_file
+        .into_iter()
+        .filter_map(|(file, mut result)| {
+            let score = *rrf_scores.get(&file)?;
+            result.score = score;
+            Some(result)
+        })
+        .collect();
+
+    fused.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(fused.into_iter().take(8).collect())
 }
 
 /// Get tool suggestions based on query (keyword matching only, fastembed removed)
@@ -542,3 +1229,154 @@ pub async fn suggest_tools_for_query(
 
     Ok(suggestions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a `KnowledgeIndex` from raw doc bodies without touching disk,
+    /// following the same tokenize -> postings/doc_freq/avg_doc_len steps as
+    /// `build_index`.
+    fn index_from_docs(docs: &[&str]) -> KnowledgeIndex {
+        let mut index = KnowledgeIndex::default();
+        let mut total_len = 0usize;
+
+        for content in docs {
+            let tokens = tokenize(content);
+            total_len += tokens.len();
+            index.docs.push(IndexedDoc {
+                filename: "fixture.md".to_string(),
+                content: content.to_string(),
+                tokens,
+                quick_fix: None,
+                recommended_tools: None,
+            });
+        }
+
+        for (doc_id, doc) in index.docs.iter().enumerate() {
+            let mut term_counts: HashMap<&str, usize> = HashMap::new();
+            for token in &doc.tokens {
+                *term_counts.entry(token.as_str()).or_insert(0) += 1;
+            }
+            for (term, tf) in term_counts {
+                index.postings.entry(term.to_string()).or_default().push((doc_id, tf));
+                *index.doc_freq.entry(term.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        index.avg_doc_len = if index.docs.is_empty() { 0.0 } else { total_len as f32 / index.docs.len() as f32 };
+        index
+    }
+
+    /// Builds just the `vocab_by_length` bucketing `typo_candidates` reads,
+    /// from a flat vocabulary list, without needing full docs/postings.
+    fn index_with_vocab(vocab: &[&str]) -> KnowledgeIndex {
+        let mut index = KnowledgeIndex::default();
+        for term in vocab {
+            index.vocab_by_length.entry(term.chars().count()).or_default().push(term.to_string());
+        }
+        index
+    }
+
+    #[test]
+    fn typo_candidates_finds_a_transposition_within_budget() {
+        // "deployemnt" vs "deployment": one transposed pair, edit distance 2,
+        // within the length-10 budget of 2.
+        let index = index_with_vocab(&["deployment", "unrelated"]);
+        let candidates = typo_candidates(&index, "deployemnt");
+        assert!(
+            candidates.iter().any(|(term, _)| term == "deployment"),
+            "expected 'deployment' among candidates, got {:?}", candidates
+        );
+    }
+
+    #[test]
+    fn typo_candidates_finds_a_same_length_rotation_within_budget() {
+        // "ubernetesk" is "kubernetes" rotated by one character
+        // (a[1:] + a[0]): same length, edit distance 2 (delete the leading
+        // char, insert it at the end), but every position mismatches. A
+        // Hamming-style same-length prune would wrongly reject this before
+        // the DP ever runs.
+        let index = index_with_vocab(&["kubernetes", "unrelated"]);
+        let candidates = typo_candidates(&index, "ubernetesk");
+        assert!(
+            candidates.iter().any(|(term, distance)| term == "kubernetes" && *distance == 2),
+            "expected 'kubernetes' at distance 2, got {:?}", candidates
+        );
+    }
+
+    #[test]
+    fn typo_candidates_finds_a_single_deletion_within_budget() {
+        // "crashlop" (8 chars, budget 1) vs "crashloop" (9 chars): one
+        // missing 'o', edit distance 1.
+        let index = index_with_vocab(&["crashloop", "unrelated"]);
+        let candidates = typo_candidates(&index, "crashlop");
+        assert!(
+            candidates.iter().any(|(term, distance)| term == "crashloop" && *distance == 1),
+            "expected 'crashloop' at distance 1, got {:?}", candidates
+        );
+    }
+
+    #[test]
+    fn typo_candidates_excludes_terms_outside_budget() {
+        let index = index_with_vocab(&["deployment", "completely_different_word"]);
+        let candidates = typo_candidates(&index, "deployemnt");
+        assert!(candidates.iter().all(|(term, _)| term != "completely_different_word"));
+    }
+
+    #[test]
+    fn levenshtein_bounded_does_not_reject_a_distance_exactly_at_budget() {
+        let a: Vec<char> = "deployemnt".chars().collect();
+        let b: Vec<char> = "deployment".chars().collect();
+        assert_eq!(levenshtein_bounded(&a, &b, 2), Some(2));
+    }
+
+    #[test]
+    fn levenshtein_bounded_rejects_a_distance_one_over_budget() {
+        let a: Vec<char> = "deployemnt".chars().collect();
+        let b: Vec<char> = "deployment".chars().collect();
+        assert_eq!(levenshtein_bounded(&a, &b, 1), None);
+    }
+
+    #[test]
+    fn bm25_ranks_higher_term_frequency_above_single_mention() {
+        let index = index_from_docs(&[
+            "crashloopbackoff crashloopbackoff crashloopbackoff pod restart",
+            "pod restart crashloopbackoff once",
+        ]);
+        let high_tf = bm25_term_score(&index, "crashloopbackoff", 0);
+        let low_tf = bm25_term_score(&index, "crashloopbackoff", 1);
+        assert!(high_tf > low_tf, "doc with higher term frequency should score higher: {} vs {}", high_tf, low_tf);
+    }
+
+    #[test]
+    fn bm25_penalizes_longer_documents_for_equal_term_frequency() {
+        let index = index_from_docs(&[
+            "oomkilled container memory limit",
+            "oomkilled container memory limit padding padding padding padding padding padding padding padding",
+        ]);
+        let short_doc_score = bm25_term_score(&index, "oomkill", 0);
+        let long_doc_score = bm25_term_score(&index, "oomkill", 1);
+        assert!(
+            short_doc_score > long_doc_score,
+            "shorter doc with the same term frequency should outrank a longer one under avg_doc_len normalization: {} vs {}",
+            short_doc_score, long_doc_score
+        );
+    }
+
+    #[test]
+    fn bm25_term_score_is_zero_for_a_term_not_in_the_document() {
+        let index = index_from_docs(&["pod crash loop"]);
+        assert_eq!(bm25_term_score(&index, "nonexistent", 0), 0.0);
+    }
+
+    #[test]
+    fn idf_is_higher_for_rarer_terms() {
+        let index = index_from_docs(&[
+            "common term appears everywhere",
+            "common term appears again",
+            "common term rare appears once",
+        ]);
+        assert!(idf(&index, "rare") > idf(&index, "common"));
+    }
+}
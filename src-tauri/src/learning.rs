@@ -9,6 +9,8 @@ use std::path::PathBuf;
 use std::fs;
 use tauri::Manager;
 use crate::embeddings;
+use crate::learning_store;
+use crate::resolution_model;
 
 // =============================================================================
 // DATA STRUCTURES
@@ -59,6 +61,17 @@ pub struct LearnedPattern {
     pub avg_confidence: f32,
     pub occurrence_count: usize,
     pub embedding: Vec<f32>,  // Average embedding of similar questions
+    /// Running weight backing `occurrence_count` and the weighted averages
+    /// above - unlike `occurrence_count`, this decays over time (see
+    /// `PATTERN_DECAY_HALF_LIFE_SECS`), so a pattern nothing has reinforced
+    /// in a while carries less influence the next time it's merged into.
+    #[serde(default)]
+    pub weight: f32,
+    /// Unix timestamp (seconds) this pattern was last merged into; decay is
+    /// computed off the time elapsed since this, not `occurrence_count`'s
+    /// original outcomes.
+    #[serde(default)]
+    pub last_updated: i64,
 }
 
 /// Container for all learning data
@@ -88,46 +101,57 @@ fn get_learning_data_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, Stri
     Ok(app_data_dir.join("learning_data.json"))
 }
 
-/// Load learning data from disk
+/// Load learning data. Outcomes come straight from the SQLite-backed
+/// `learning_store` on every call (no cache to invalidate, so a
+/// just-recorded outcome is visible immediately); patterns/version are
+/// small and change rarely, so those stay cached in memory and backed by
+/// the JSON file this module used to store everything in.
 pub fn load_learning_data(app_handle: &tauri::AppHandle) -> Result<LearningData, String> {
-    // Check cache first
+    let outcomes = learning_store::list_outcomes()?;
+
     if let Ok(cache) = LEARNING_DATA.lock() {
         if let Some(ref data) = *cache {
-            return Ok(data.clone());
+            return Ok(LearningData {
+                outcomes,
+                patterns: data.patterns.clone(),
+                version: data.version.clone(),
+            });
         }
     }
 
     let path = get_learning_data_path(app_handle)?;
 
-    if !path.exists() {
-        // Return empty data if file doesn't exist
-        let data = LearningData {
-            outcomes: Vec::new(),
-            patterns: Vec::new(),
-            version: "1.0".to_string(),
-        };
-        return Ok(data);
-    }
-
-    let content = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read learning data: {}", e))?;
-
-    let data: LearningData = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse learning data: {}", e))?;
+    let (patterns, version) = if path.exists() {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read learning data: {}", e))?;
+        let on_disk: LearningData = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse learning data: {}", e))?;
+        (on_disk.patterns, on_disk.version)
+    } else {
+        (Vec::new(), "1.0".to_string())
+    };
 
-    // Cache the loaded data
     if let Ok(mut cache) = LEARNING_DATA.lock() {
-        *cache = Some(data.clone());
+        *cache = Some(LearningData { outcomes: Vec::new(), patterns: patterns.clone(), version: version.clone() });
     }
 
-    Ok(data)
+    Ok(LearningData { outcomes, patterns, version })
 }
 
-/// Save learning data to disk
+/// Save the derived pattern set and format version to disk. Outcomes no
+/// longer round-trip through this file - they're written incrementally to
+/// `learning_store` by `record_investigation_outcome` - so `data.outcomes`
+/// is ignored here rather than being rewritten wholesale on every save.
 pub fn save_learning_data(app_handle: &tauri::AppHandle, data: &LearningData) -> Result<(), String> {
     let path = get_learning_data_path(app_handle)?;
 
-    let content = serde_json::to_string_pretty(data)
+    let on_disk = LearningData {
+        outcomes: Vec::new(),
+        patterns: data.patterns.clone(),
+        version: data.version.clone(),
+    };
+
+    let content = serde_json::to_string_pretty(&on_disk)
         .map_err(|e| format!("Failed to serialize learning data: {}", e))?;
 
     fs::write(&path, content)
@@ -135,12 +159,132 @@ pub fn save_learning_data(app_handle: &tauri::AppHandle, data: &LearningData) ->
 
     // Update cache
     if let Ok(mut cache) = LEARNING_DATA.lock() {
-        *cache = Some(data.clone());
+        *cache = Some(on_disk);
     }
 
     Ok(())
 }
 
+// =============================================================================
+// HYBRID SCORING
+// =============================================================================
+
+/// Blend ratio for `hybrid_scores` when callers don't override it: half the
+/// fused score comes from semantic (cosine) similarity, half from BM25
+/// keyword overlap.
+const DEFAULT_SEMANTIC_RATIO: f32 = 0.5;
+
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Lightweight in-memory BM25 index over a set of `question` strings,
+/// rebuilt fresh for each query rather than persisted - `LearningData`
+/// caps at 500 outcomes, so indexing on the fly is cheap and avoids
+/// keeping a second structure in sync with `LearningData`.
+struct Bm25Index {
+    doc_term_freqs: Vec<std::collections::HashMap<String, usize>>,
+    doc_lengths: Vec<usize>,
+    avg_doc_length: f32,
+    doc_freq: std::collections::HashMap<String, usize>,
+    num_docs: usize,
+}
+
+impl Bm25Index {
+    fn build(documents: &[&str]) -> Self {
+        let mut doc_term_freqs = Vec::with_capacity(documents.len());
+        let mut doc_lengths = Vec::with_capacity(documents.len());
+        let mut doc_freq: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+        for doc in documents {
+            let tokens = tokenize(doc);
+            doc_lengths.push(tokens.len());
+
+            let mut term_freq: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+            for token in tokens {
+                *term_freq.entry(token).or_insert(0) += 1;
+            }
+            for term in term_freq.keys() {
+                *doc_freq.entry(term.clone()).or_insert(0) += 1;
+            }
+            doc_term_freqs.push(term_freq);
+        }
+
+        let num_docs = documents.len();
+        let avg_doc_length = if num_docs == 0 {
+            0.0
+        } else {
+            doc_lengths.iter().sum::<usize>() as f32 / num_docs as f32
+        };
+
+        Self { doc_term_freqs, doc_lengths, avg_doc_length, doc_freq, num_docs }
+    }
+
+    /// BM25 score of document `idx` against `query_tokens`.
+    fn score(&self, idx: usize, query_tokens: &[String]) -> f32 {
+        if self.num_docs == 0 || self.avg_doc_length <= 0.0 {
+            return 0.0;
+        }
+
+        let term_freqs = &self.doc_term_freqs[idx];
+        let doc_length = self.doc_lengths[idx] as f32;
+
+        query_tokens.iter().fold(0.0, |score, term| {
+            let Some(&tf) = term_freqs.get(term) else { return score };
+            let df = *self.doc_freq.get(term).unwrap_or(&0) as f32;
+            let idf = ((self.num_docs as f32 - df + 0.5) / (df + 0.5) + 1.0).ln();
+            let tf = tf as f32;
+            let numer = tf * (BM25_K1 + 1.0);
+            let denom = tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_length / self.avg_doc_length);
+            score + idf * numer / denom
+        })
+    }
+}
+
+/// Min-max normalize `scores` into `[0, 1]`. A zero-spread set (all equal,
+/// or empty) normalizes to all zeros rather than dividing by zero.
+fn min_max_normalize(scores: &[f32]) -> Vec<f32> {
+    let min = scores.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    if !(max - min > f32::EPSILON) {
+        return vec![0.0; scores.len()];
+    }
+    scores.iter().map(|&s| (s - min) / (max - min)).collect()
+}
+
+/// Fuse BM25 keyword scores with cosine-similarity semantic scores the way
+/// Meilisearch's hybrid search does: min-max normalize each into `[0, 1]`
+/// across `candidates`, then blend as `ratio * semantic + (1 - ratio) *
+/// keyword`. Catches exact-token matches (error codes, pod names, stack
+/// traces) that a sentence embedding alone blurs together.
+fn hybrid_scores(query: &str, query_embedding: &[f32], candidates: &[&InvestigationOutcome], semantic_ratio: f32) -> Vec<f32> {
+    let questions: Vec<&str> = candidates.iter().map(|o| o.question.as_str()).collect();
+    let bm25 = Bm25Index::build(&questions);
+    let query_tokens = tokenize(query);
+
+    let semantic: Vec<f32> = candidates
+        .iter()
+        .map(|o| embeddings::cosine_similarity(query_embedding, &o.question_embedding))
+        .collect();
+    let keyword: Vec<f32> = (0..candidates.len()).map(|i| bm25.score(i, &query_tokens)).collect();
+
+    let semantic_norm = min_max_normalize(&semantic);
+    let keyword_norm = min_max_normalize(&keyword);
+
+    semantic_norm
+        .iter()
+        .zip(keyword_norm.iter())
+        .map(|(s, k)| semantic_ratio * s + (1.0 - semantic_ratio) * k)
+        .collect()
+}
+
 // =============================================================================
 // LEARNING OPERATIONS
 // =============================================================================
@@ -158,13 +302,21 @@ pub async fn record_investigation_outcome(
     hypotheses_refuted: Vec<String>,
     app_handle: tauri::AppHandle,
 ) -> Result<String, String> {
-    // Generate embedding for the question
-    let question_embedding = match embeddings::embed_query(&question) {
-        Ok(emb) => emb,
-        Err(e) => {
-            eprintln!("[Learning] Failed to embed question: {}, using empty", e);
-            Vec::new()
-        }
+    // Reuse a cached embedding for this exact question text instead of
+    // re-embedding and re-storing an identical vector.
+    let question_digest = embeddings::hash_content(&question);
+    let question_embedding = match learning_store::get_cached_embedding(&question_digest)? {
+        Some(cached) => cached,
+        None => match embeddings::embed_query(&question) {
+            Ok(emb) => {
+                learning_store::put_embedding(&question_digest, &emb)?;
+                emb
+            }
+            Err(e) => {
+                eprintln!("[Learning] Failed to embed question: {}, using empty", e);
+                Vec::new()
+            }
+        },
     };
 
     let resolution_type = match resolution.to_lowercase().as_str() {
@@ -188,54 +340,58 @@ pub async fn record_investigation_outcome(
         hypotheses_refuted,
     };
 
-    // Load existing data
-    let mut data = load_learning_data(&app_handle)?;
-
     let outcome_id = outcome.id.clone();
-    data.outcomes.push(outcome);
-
-    // Limit to last 500 outcomes to prevent unbounded growth
-    if data.outcomes.len() > 500 {
-        data.outcomes = data.outcomes.split_off(data.outcomes.len() - 500);
-    }
 
-    // Save updated data
-    save_learning_data(&app_handle, &data)?;
+    // Incremental insert - unlike the old full-file rewrite, this doesn't
+    // touch any of the other rows already stored, so the cost no longer
+    // grows with the total outcome count.
+    learning_store::insert_outcome(&outcome, &question_digest)?;
 
     // Try to detect patterns after saving
-    let _ = detect_and_save_patterns(&app_handle).await;
+    let _ = detect_and_save_patterns(&app_handle, DEFAULT_DBSCAN_EPS, DEFAULT_DBSCAN_MIN_PTS).await;
+
+    // Lazily retrain the resolution-prediction model once enough new
+    // outcomes have accumulated; this is a no-op most of the time.
+    if let Ok(all_outcomes) = learning_store::list_outcomes() {
+        let _ = resolution_model::maybe_retrain(&app_handle, &all_outcomes);
+    }
 
     Ok(outcome_id)
 }
 
-/// Find similar past investigations using semantic search
+/// Find similar past investigations using hybrid keyword+semantic search
 #[tauri::command]
 pub async fn find_similar_investigations(
     question: String,
     top_k: usize,
+    semantic_ratio: Option<f32>,
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<SimilarInvestigation>, String> {
+    let semantic_ratio = semantic_ratio.unwrap_or(DEFAULT_SEMANTIC_RATIO).clamp(0.0, 1.0);
     let query_embedding = embeddings::embed_query(&question)?;
     let data = load_learning_data(&app_handle)?;
 
-    let mut results: Vec<SimilarInvestigation> = data.outcomes
+    let candidates: Vec<&InvestigationOutcome> = data.outcomes
         .iter()
         .filter(|o| !o.question_embedding.is_empty())
-        .map(|outcome| {
-            let similarity = embeddings::cosine_similarity(&query_embedding, &outcome.question_embedding);
-            SimilarInvestigation {
-                id: outcome.id.clone(),
-                question: outcome.question.clone(),
-                similarity,
-                resolution: format!("{:?}", outcome.resolution),
-                root_cause: outcome.root_cause.clone(),
-                tools_used: outcome.tools_used.iter().map(|t| t.tool.clone()).collect(),
-                confidence_score: outcome.confidence_score,
-            }
+        .collect();
+    let fused = hybrid_scores(&question, &query_embedding, &candidates, semantic_ratio);
+
+    let mut results: Vec<SimilarInvestigation> = candidates
+        .iter()
+        .zip(fused.iter())
+        .map(|(outcome, &similarity)| SimilarInvestigation {
+            id: outcome.id.clone(),
+            question: outcome.question.clone(),
+            similarity,
+            resolution: format!("{:?}", outcome.resolution),
+            root_cause: outcome.root_cause.clone(),
+            tools_used: outcome.tools_used.iter().map(|t| t.tool.clone()).collect(),
+            confidence_score: outcome.confidence_score,
         })
         .collect();
 
-    // Sort by similarity descending
+    // Sort by fused similarity descending
     results.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
     results.truncate(top_k);
 
@@ -261,23 +417,27 @@ pub struct SimilarInvestigation {
 #[tauri::command]
 pub async fn get_learned_tool_recommendations(
     question: String,
+    semantic_ratio: Option<f32>,
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<LearnedToolRecommendation>, String> {
+    let semantic_ratio = semantic_ratio.unwrap_or(DEFAULT_SEMANTIC_RATIO).clamp(0.0, 1.0);
     let query_embedding = embeddings::embed_query(&question)?;
     let data = load_learning_data(&app_handle)?;
 
-    // Find similar successful investigations
-    let similar_successful: Vec<_> = data.outcomes
+    // Find similar successful investigations, ranked by fused keyword+semantic score
+    let candidates: Vec<&InvestigationOutcome> = data.outcomes
         .iter()
         .filter(|o| {
             !o.question_embedding.is_empty() &&
             o.resolution == ResolutionType::Solved &&
             o.confidence_score >= 55.0
         })
-        .map(|o| {
-            let sim = embeddings::cosine_similarity(&query_embedding, &o.question_embedding);
-            (o, sim)
-        })
+        .collect();
+    let fused = hybrid_scores(&question, &query_embedding, &candidates, semantic_ratio);
+
+    let similar_successful: Vec<_> = candidates
+        .into_iter()
+        .zip(fused)
         .filter(|(_, sim)| *sim > 0.6)
         .collect();
 
@@ -305,12 +465,44 @@ pub async fn get_learned_tool_recommendations(
         })
         .collect();
 
-    recommendations.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    // Once a resolution model has been trained, rank by how much each
+    // tool's own marginal lift raises the predicted solve probability
+    // rather than by similarity-weighted occurrence alone - a tool that's
+    // common but not actually discriminative should sink in the list.
+    let lifts: Vec<Option<f32>> = recommendations
+        .iter()
+        .map(|r| resolution_model::marginal_lift(&app_handle, &r.tool, &query_embedding))
+        .collect();
+
+    if lifts.iter().any(Option::is_some) {
+        let mut ranked: Vec<_> = recommendations.into_iter().zip(lifts).collect();
+        ranked.sort_by(|a, b| {
+            b.1.unwrap_or(f32::MIN).partial_cmp(&a.1.unwrap_or(f32::MIN)).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        recommendations = ranked.into_iter().map(|(r, _)| r).collect();
+    } else {
+        recommendations.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
     recommendations.truncate(5);
 
     Ok(recommendations)
 }
 
+/// Predict the probability that an investigation using `tools_planned`
+/// resolves as solved, from the lazily trained resolution model. Returns
+/// a neutral 0.5 if no model has been trained yet (fewer than
+/// `MIN_TRAINING_OUTCOMES` recorded outcomes).
+#[tauri::command]
+pub async fn predict_resolution(
+    question: String,
+    tools_planned: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<f32, String> {
+    let query_embedding = embeddings::embed_query(&question)?;
+    Ok(resolution_model::predict_resolution(&app_handle, &tools_planned, &query_embedding))
+}
+
 /// Learned tool recommendation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LearnedToolRecommendation {
@@ -324,8 +516,99 @@ pub struct LearnedToolRecommendation {
 // PATTERN DETECTION
 // =============================================================================
 
-/// Detect patterns from investigation outcomes and save
-async fn detect_and_save_patterns(app_handle: &tauri::AppHandle) -> Result<(), String> {
+/// Default DBSCAN parameters for `detect_and_save_patterns`: two questions
+/// count as neighbors above 0.75 cosine similarity, and a point needs at
+/// least 3 neighbors to seed (or join) a dense cluster.
+const DEFAULT_DBSCAN_EPS: f32 = 0.75;
+const DEFAULT_DBSCAN_MIN_PTS: usize = 3;
+
+/// A freshly detected cluster merges into an existing `LearnedPattern`
+/// when their average embeddings are at least this similar; otherwise it
+/// becomes a new pattern. Stricter than `DEFAULT_DBSCAN_EPS` since this
+/// decides pattern identity across merges, not cluster membership within
+/// one run.
+const PATTERN_MATCH_THRESHOLD: f32 = 0.85;
+
+/// Half-life, in seconds, of a `LearnedPattern`'s accumulated `weight` -
+/// ~30 days, so a playbook nothing has reinforced in a month carries about
+/// half its original influence the next time it's merged into, letting
+/// the system forget playbooks an infra change broke.
+const PATTERN_DECAY_HALF_LIFE_SECS: f64 = 30.0 * 24.0 * 60.0 * 60.0;
+
+fn pattern_decay_factor(elapsed_secs: f64) -> f32 {
+    if elapsed_secs <= 0.0 {
+        return 1.0;
+    }
+    0.5f64.powf(elapsed_secs / PATTERN_DECAY_HALF_LIFE_SECS) as f32
+}
+
+/// The eps-neighborhood of every point in `embeddings_list`: for point `i`,
+/// the indices of every other point with cosine similarity to it above
+/// `eps`.
+fn eps_neighbors(embeddings_list: &[&[f32]], eps: f32) -> Vec<Vec<usize>> {
+    embeddings_list
+        .iter()
+        .enumerate()
+        .map(|(i, emb)| {
+            embeddings_list
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| *j != i && embeddings::cosine_similarity(emb, other) > eps)
+                .map(|(j, _)| j)
+                .collect()
+        })
+        .collect()
+}
+
+/// DBSCAN over `neighbors` (the eps-neighborhood of each of `n` points):
+/// expand a cluster from each unvisited core point (>= `min_pts`
+/// neighbors) through its reachable neighbors, absorbing border points
+/// along the way. Points that never join a cluster are noise and are left
+/// out of the result. Order-independent, unlike seeding a cluster from
+/// whichever point happens to be visited first and only checking
+/// similarity against that one seed.
+fn dbscan_cluster(n: usize, neighbors: &[Vec<usize>], min_pts: usize) -> Vec<Vec<usize>> {
+    const UNASSIGNED: i32 = -1;
+    let mut cluster_of = vec![UNASSIGNED; n];
+    let mut visited = vec![false; n];
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+    for i in 0..n {
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+
+        if neighbors[i].len() < min_pts {
+            continue; // not a core point - may still be absorbed as a border point below
+        }
+
+        let cluster_id = clusters.len();
+        clusters.push(vec![i]);
+        cluster_of[i] = cluster_id as i32;
+
+        let mut seeds: std::collections::VecDeque<usize> = neighbors[i].iter().copied().collect();
+        while let Some(j) = seeds.pop_front() {
+            if !visited[j] {
+                visited[j] = true;
+                if neighbors[j].len() >= min_pts {
+                    seeds.extend(&neighbors[j]);
+                }
+            }
+
+            if cluster_of[j] == UNASSIGNED {
+                cluster_of[j] = cluster_id as i32;
+                clusters[cluster_id].push(j);
+            }
+        }
+    }
+
+    clusters
+}
+
+/// Detect patterns from investigation outcomes via density-based (DBSCAN)
+/// clustering of `question_embedding`, and save them.
+async fn detect_and_save_patterns(app_handle: &tauri::AppHandle, eps: f32, min_pts: usize) -> Result<(), String> {
     let mut data = load_learning_data(app_handle)?;
 
     // Need at least 5 outcomes to detect patterns
@@ -333,55 +616,38 @@ async fn detect_and_save_patterns(app_handle: &tauri::AppHandle) -> Result<(), S
         return Ok(());
     }
 
-    // Group similar questions using embeddings
-    let successful_outcomes: Vec<_> = data.outcomes
+    // Cluster over every outcome with an embedding that wasn't abandoned
+    // part-way through - Partial/Inconclusive outcomes still carry useful
+    // signal about what *didn't* fully work, which `success_rate` below
+    // needs to reflect honestly instead of assuming every pattern is 100%
+    // effective.
+    let candidate_outcomes: Vec<_> = data.outcomes
         .iter()
         .filter(|o| {
             !o.question_embedding.is_empty() &&
-            o.resolution == ResolutionType::Solved
+            o.resolution != ResolutionType::UserAborted
         })
         .collect();
 
-    if successful_outcomes.len() < 3 {
+    if candidate_outcomes.len() <= min_pts {
         return Ok(());
     }
 
-    // Simple clustering: find questions with >0.75 similarity
-    let mut clusters: Vec<Vec<&InvestigationOutcome>> = Vec::new();
-    let mut assigned: std::collections::HashSet<String> = std::collections::HashSet::new();
-
-    for outcome in &successful_outcomes {
-        if assigned.contains(&outcome.id) {
-            continue;
-        }
-
-        let mut cluster = vec![*outcome];
-        assigned.insert(outcome.id.clone());
-
-        for other in &successful_outcomes {
-            if assigned.contains(&other.id) {
-                continue;
-            }
-
-            let sim = embeddings::cosine_similarity(&outcome.question_embedding, &other.question_embedding);
-            if sim > 0.75 {
-                cluster.push(*other);
-                assigned.insert(other.id.clone());
-            }
-        }
-
-        if cluster.len() >= 3 {
-            clusters.push(cluster);
-        }
-    }
+    let embeddings_list: Vec<&[f32]> = candidate_outcomes.iter().map(|o| o.question_embedding.as_slice()).collect();
+    let neighbors = eps_neighbors(&embeddings_list, eps);
+    let clusters: Vec<Vec<&InvestigationOutcome>> = dbscan_cluster(candidate_outcomes.len(), &neighbors, min_pts)
+        .into_iter()
+        .map(|indices| indices.into_iter().map(|i| candidate_outcomes[i]).collect())
+        .collect();
 
-    // Convert clusters to patterns
-    let mut new_patterns = Vec::new();
+    let now = chrono::Utc::now().timestamp();
 
     for cluster in clusters {
         // Find common tools (appear in >50% of cluster)
         let mut tool_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         let mut total_confidence = 0.0;
+        let mut solved = 0.0f32;
+        let mut partial = 0.0f32;
 
         for outcome in &cluster {
             for tool in &outcome.tools_used {
@@ -390,6 +656,11 @@ async fn detect_and_save_patterns(app_handle: &tauri::AppHandle) -> Result<(), S
                 }
             }
             total_confidence += outcome.confidence_score;
+            match outcome.resolution {
+                ResolutionType::Solved => solved += 1.0,
+                ResolutionType::Partial => partial += 1.0,
+                ResolutionType::Inconclusive | ResolutionType::UserAborted => {}
+            }
         }
 
         let threshold = cluster.len() / 2;
@@ -421,24 +692,81 @@ async fn detect_and_save_patterns(app_handle: &tauri::AppHandle) -> Result<(), S
             .map(|o| o.question.clone())
             .unwrap_or_default();
 
-        new_patterns.push(LearnedPattern {
-            id: uuid::Uuid::new_v4().to_string(),
-            question_pattern: representative,
-            common_tools,
-            success_rate: 1.0,  // All outcomes in cluster were successful
-            avg_confidence: total_confidence / cluster.len() as f32,
-            occurrence_count: cluster.len(),
-            embedding: avg_embedding,
-        });
+        let cluster_weight = cluster.len() as f32;
+        // Partial credit for partially-resolved investigations, instead of
+        // the old hardcoded 1.0 that assumed every cluster was all-Solved.
+        let cluster_success_rate = (solved + 0.5 * partial) / cluster_weight;
+        let cluster_avg_confidence = total_confidence / cluster_weight;
+
+        // Merge into whichever existing pattern this cluster's average
+        // embedding is closest to, rather than discarding everything
+        // learned so far - a fresh `new_patterns` list every run meant
+        // `occurrence_count` could never accumulate real statistical
+        // weight.
+        let existing = data.patterns
+            .iter()
+            .position(|p| !p.embedding.is_empty() && embeddings::cosine_similarity(&p.embedding, &avg_embedding) > PATTERN_MATCH_THRESHOLD);
+
+        match existing {
+            Some(idx) => {
+                let pattern = &mut data.patterns[idx];
+                let decayed_weight = pattern.weight.max(0.0) * pattern_decay_factor((now - pattern.last_updated).max(0) as f64);
+                let merged_weight = decayed_weight + cluster_weight;
+
+                let mut merged_embedding = vec![0.0f32; dim];
+                for i in 0..dim {
+                    let old_val = pattern.embedding.get(i).copied().unwrap_or(0.0);
+                    merged_embedding[i] = (decayed_weight * old_val + cluster_weight * avg_embedding[i]) / merged_weight;
+                }
+
+                pattern.success_rate = (decayed_weight * pattern.success_rate + cluster_weight * cluster_success_rate) / merged_weight;
+                pattern.avg_confidence = (decayed_weight * pattern.avg_confidence + cluster_weight * cluster_avg_confidence) / merged_weight;
+                pattern.embedding = merged_embedding;
+                pattern.common_tools = common_tools;
+                pattern.question_pattern = representative;
+                pattern.weight = merged_weight;
+                pattern.occurrence_count = merged_weight.round().max(1.0) as usize;
+                pattern.last_updated = now;
+            }
+            None => {
+                data.patterns.push(LearnedPattern {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    question_pattern: representative,
+                    common_tools,
+                    success_rate: cluster_success_rate,
+                    avg_confidence: cluster_avg_confidence,
+                    occurrence_count: cluster.len(),
+                    embedding: avg_embedding,
+                    weight: cluster_weight,
+                    last_updated: now,
+                });
+            }
+        }
     }
 
-    // Update patterns (merge with existing or replace)
-    data.patterns = new_patterns;
     save_learning_data(app_handle, &data)?;
 
     Ok(())
 }
 
+/// Re-run DBSCAN pattern detection on demand with tunable `eps`/`min_pts`,
+/// instead of waiting for the next `record_investigation_outcome` call to
+/// trigger it with the defaults. Returns the number of patterns found.
+#[tauri::command]
+pub async fn detect_patterns(
+    eps: Option<f32>,
+    min_pts: Option<usize>,
+    app_handle: tauri::AppHandle,
+) -> Result<usize, String> {
+    let eps = eps.unwrap_or(DEFAULT_DBSCAN_EPS);
+    let min_pts = min_pts.unwrap_or(DEFAULT_DBSCAN_MIN_PTS);
+
+    detect_and_save_patterns(&app_handle, eps, min_pts).await?;
+
+    let data = load_learning_data(&app_handle)?;
+    Ok(data.patterns.len())
+}
+
 /// Get learned patterns for a query
 #[tauri::command]
 pub async fn get_learned_patterns(
@@ -510,3 +838,235 @@ pub struct LearningStats {
     pub avg_confidence: f32,
     pub top_tools: Vec<(String, usize)>,
 }
+
+// =============================================================================
+// PLAYBOOK GENERATION
+// =============================================================================
+
+/// One step in an auto-generated playbook: a tool to run, with stats mined
+/// from the cluster of past outcomes it was ordered from.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybookStep {
+    pub tool: String,
+    pub median_duration_ms: u64,
+    pub usefulness_rate: f32,
+    pub hypotheses_confirmed: Vec<String>,
+    pub hypotheses_refuted: Vec<String>,
+}
+
+/// An ordered tool sequence the UI can render as a runnable checklist,
+/// mined from the best-matching `LearnedPattern`'s cluster of past outcomes.
+#[derive(Debug, Clone, Serialize)]
+pub struct Playbook {
+    pub question_pattern: String,
+    pub steps: Vec<PlaybookStep>,
+    pub confidence: f32,
+    pub based_on_occurrences: usize,
+}
+
+/// Order `tools` by mined precedence: for every pair, count how often one
+/// tool's successful use preceded the other's within the same outcome
+/// (not necessarily adjacently), take the dominant direction as a directed
+/// edge, then topologically sort via Kahn's algorithm. Ties - and any
+/// leftover cycle from noisy, inconsistent orderings across outcomes - are
+/// broken by picking the remaining tool with the fewest unresolved
+/// incoming edges, alphabetically if that's still tied, so the result is
+/// deterministic.
+fn order_tools(cluster: &[&InvestigationOutcome], tools: &[String]) -> Vec<String> {
+    let allowed: std::collections::HashSet<&str> = tools.iter().map(|t| t.as_str()).collect();
+    let mut precedence_counts: std::collections::HashMap<(String, String), usize> = std::collections::HashMap::new();
+
+    for outcome in cluster {
+        let sequence: Vec<&str> = outcome.tools_used
+            .iter()
+            .filter(|t| t.useful && allowed.contains(t.tool.as_str()))
+            .map(|t| t.tool.as_str())
+            .collect();
+
+        for i in 0..sequence.len() {
+            for j in (i + 1)..sequence.len() {
+                if sequence[i] == sequence[j] {
+                    continue;
+                }
+                *precedence_counts.entry((sequence[i].to_string(), sequence[j].to_string())).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut out_edges: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    let mut in_degree: std::collections::HashMap<String, usize> = tools.iter().map(|t| (t.clone(), 0)).collect();
+
+    for i in 0..tools.len() {
+        for j in (i + 1)..tools.len() {
+            let (a, b) = (&tools[i], &tools[j]);
+            let forward = *precedence_counts.get(&(a.clone(), b.clone())).unwrap_or(&0);
+            let backward = *precedence_counts.get(&(b.clone(), a.clone())).unwrap_or(&0);
+
+            if forward == 0 && backward == 0 {
+                continue; // no evidence either way in this cluster
+            }
+
+            let (from, to) = if forward >= backward { (a, b) } else { (b, a) };
+            out_edges.entry(from.clone()).or_default().push(to.clone());
+            *in_degree.entry(to.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut remaining: std::collections::HashSet<String> = tools.iter().cloned().collect();
+    let mut order = Vec::with_capacity(tools.len());
+
+    while !remaining.is_empty() {
+        let mut ready: Vec<&String> = remaining.iter().filter(|t| in_degree.get(*t).copied().unwrap_or(0) == 0).collect();
+        ready.sort();
+
+        let next = match ready.first() {
+            Some(t) => (*t).clone(),
+            None => remaining
+                .iter()
+                .min_by_key(|t| (in_degree.get(*t).copied().unwrap_or(0), (*t).clone()))
+                .unwrap()
+                .clone(),
+        };
+
+        remaining.remove(&next);
+        if let Some(targets) = out_edges.get(&next) {
+            for target in targets {
+                if let Some(d) = in_degree.get_mut(target) {
+                    *d = d.saturating_sub(1);
+                }
+            }
+        }
+        order.push(next);
+    }
+
+    order
+}
+
+/// Generate an ordered auto-playbook for `question` from the best-matching
+/// learned pattern. Returns `None` if nothing matches closely enough or
+/// the pattern has no contributing outcomes left to mine a sequence from.
+#[tauri::command]
+pub async fn generate_playbook(
+    question: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<Playbook>, String> {
+    let query_embedding = embeddings::embed_query(&question)?;
+    let data = load_learning_data(&app_handle)?;
+
+    let best_pattern = data.patterns
+        .iter()
+        .filter(|p| !p.embedding.is_empty())
+        .map(|p| (p, embeddings::cosine_similarity(&query_embedding, &p.embedding)))
+        .filter(|(_, sim)| *sim > 0.6)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let Some((pattern, _)) = best_pattern else {
+        return Ok(None);
+    };
+
+    // Re-derive the pattern's contributing outcomes: any outcome close
+    // enough to the pattern's embedding to be the kind of thing
+    // `detect_and_save_patterns` would have absorbed into it.
+    let cluster: Vec<&InvestigationOutcome> = data.outcomes
+        .iter()
+        .filter(|o| !o.question_embedding.is_empty() && embeddings::cosine_similarity(&o.question_embedding, &pattern.embedding) > DEFAULT_DBSCAN_EPS)
+        .collect();
+
+    if cluster.is_empty() {
+        return Ok(None);
+    }
+
+    let ordered_tools = order_tools(&cluster, &pattern.common_tools);
+
+    let steps: Vec<PlaybookStep> = ordered_tools
+        .into_iter()
+        .map(|tool| {
+            let records: Vec<&ToolRecord> = cluster.iter().flat_map(|o| o.tools_used.iter()).filter(|t| t.tool == tool).collect();
+
+            let mut durations: Vec<u64> = records.iter().map(|t| t.duration_ms).collect();
+            durations.sort_unstable();
+            let median_duration_ms = durations.get(durations.len() / 2).copied().unwrap_or(0);
+
+            let useful_count = records.iter().filter(|t| t.useful).count();
+            let usefulness_rate = if records.is_empty() { 0.0 } else { useful_count as f32 / records.len() as f32 };
+
+            let mut hypotheses_confirmed = Vec::new();
+            let mut hypotheses_refuted = Vec::new();
+            for outcome in &cluster {
+                if outcome.tools_used.iter().any(|t| t.tool == tool && t.useful) {
+                    hypotheses_confirmed.extend(outcome.hypotheses_confirmed.iter().cloned());
+                    hypotheses_refuted.extend(outcome.hypotheses_refuted.iter().cloned());
+                }
+            }
+            hypotheses_confirmed.sort();
+            hypotheses_confirmed.dedup();
+            hypotheses_refuted.sort();
+            hypotheses_refuted.dedup();
+
+            PlaybookStep { tool, median_duration_ms, usefulness_rate, hypotheses_confirmed, hypotheses_refuted }
+        })
+        .collect();
+
+    Ok(Some(Playbook {
+        question_pattern: pattern.question_pattern.clone(),
+        steps,
+        confidence: pattern.success_rate,
+        based_on_occurrences: pattern.occurrence_count,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dbscan_cluster_separates_two_dense_clusters_from_noise() {
+        // Two tight clusters of near-axis-aligned vectors, plus one point
+        // orthogonal to both that should be left as noise.
+        let embeddings: Vec<Vec<f32>> = vec![
+            vec![1.0, 0.01, 0.0],
+            vec![0.99, 0.02, 0.0],
+            vec![1.0, -0.01, 0.0],
+            vec![0.0, 1.0, 0.01],
+            vec![0.01, 0.99, 0.0],
+            vec![-0.01, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let refs: Vec<&[f32]> = embeddings.iter().map(|v| v.as_slice()).collect();
+
+        let neighbors = eps_neighbors(&refs, 0.9);
+        let clusters = dbscan_cluster(refs.len(), &neighbors, 2);
+
+        let mut sets: Vec<Vec<usize>> = clusters.into_iter().map(|mut c| { c.sort(); c }).collect();
+        sets.sort();
+        assert_eq!(sets, vec![vec![0, 1, 2], vec![3, 4, 5]], "expected the two known dense clusters, got {:?}", sets);
+
+        let clustered: std::collections::HashSet<usize> = sets.into_iter().flatten().collect();
+        assert!(!clustered.contains(&6), "the orthogonal outlier should be left unclustered as noise");
+    }
+
+    #[test]
+    fn eps_neighbors_only_links_points_within_the_similarity_threshold() {
+        let embeddings: Vec<Vec<f32>> = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.99, 0.01, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let refs: Vec<&[f32]> = embeddings.iter().map(|v| v.as_slice()).collect();
+
+        let neighbors = eps_neighbors(&refs, 0.9);
+
+        assert_eq!(neighbors[0], vec![1]);
+        assert_eq!(neighbors[1], vec![0]);
+        assert!(neighbors[2].is_empty(), "the orthogonal point should have no neighbors within eps");
+    }
+
+    #[test]
+    fn dbscan_cluster_leaves_isolated_points_as_noise() {
+        // No point has enough neighbors to be a core point, so nothing
+        // should be clustered.
+        let neighbors = vec![vec![], vec![], vec![]];
+        let clusters = dbscan_cluster(3, &neighbors, 2);
+        assert!(clusters.is_empty());
+    }
+}
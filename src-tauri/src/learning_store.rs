@@ -0,0 +1,207 @@
+//! SQLite-backed store for investigation outcomes, replacing the full-file
+//! JSON rewrite `learning::save_learning_data` used to do on every
+//! `record_investigation_outcome` call - the same lazy-open-connection
+//! idiom as `metrics_store`/`cost_store`.
+//!
+//! Outcomes get one row each in `outcomes`, written incrementally via
+//! `insert_outcome` rather than rewritten wholesale. Question embeddings
+//! live in a separate `embeddings` table keyed by a content digest of the
+//! question text (`embeddings::hash_content`), so two outcomes asking the
+//! same or a near-identical question share one stored vector instead of
+//! each paying to re-embed and re-store it.
+
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::learning::{InvestigationOutcome, ResolutionType};
+
+fn db_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".opspilot").join("learning.db"))
+}
+
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().map_err(|e| format!("Learning store lock poisoned: {}", e))?;
+
+    if guard.is_none() {
+        let path = db_path().ok_or("Could not determine home directory for learning store")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create learning store directory: {}", e))?;
+        }
+        let conn = Connection::open(&path).map_err(|e| format!("Failed to open learning store: {}", e))?;
+        init_schema(&conn).map_err(|e| format!("Failed to initialize learning store schema: {}", e))?;
+        *guard = Some(conn);
+    }
+
+    f(guard.as_ref().unwrap()).map_err(|e| format!("Learning store query failed: {}", e))
+}
+
+fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS embeddings (
+            digest TEXT PRIMARY KEY,
+            vector BLOB NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS outcomes (
+            id TEXT PRIMARY KEY,
+            timestamp INTEGER NOT NULL,
+            question TEXT NOT NULL,
+            question_digest TEXT NOT NULL,
+            tools_used TEXT NOT NULL,
+            resolution TEXT NOT NULL,
+            root_cause TEXT,
+            confidence_score REAL NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            hypotheses_confirmed TEXT NOT NULL,
+            hypotheses_refuted TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS outcomes_digest_idx ON outcomes (question_digest)", [])?;
+    Ok(())
+}
+
+fn embedding_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn blob_to_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes.chunks_exact(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect()
+}
+
+/// Look up a previously stored embedding by content digest.
+pub fn get_cached_embedding(digest: &str) -> Result<Option<Vec<f32>>, String> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT vector FROM embeddings WHERE digest = ?1",
+            params![digest],
+            |row| row.get::<_, Vec<u8>>(0),
+        )
+        .map(|bytes| Some(blob_to_embedding(&bytes)))
+        .or_else(|e| if matches!(e, rusqlite::Error::QueryReturnedNoRows) { Ok(None) } else { Err(e) })
+    })
+}
+
+/// Cache `vector` under `digest` for future reuse.
+pub fn put_embedding(digest: &str, vector: &[f32]) -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO embeddings (digest, vector) VALUES (?1, ?2)",
+            params![digest, embedding_to_blob(vector)],
+        )?;
+        Ok(())
+    })
+}
+
+/// Insert one outcome row, keyed to its embedding by `question_digest`.
+/// Incremental - unlike the JSON file this replaces, existing rows aren't
+/// touched.
+pub fn insert_outcome(outcome: &InvestigationOutcome, question_digest: &str) -> Result<(), String> {
+    let tools_used = serde_json::to_string(&outcome.tools_used).map_err(|e| format!("Failed to serialize tools_used: {}", e))?;
+    let resolution = serde_json::to_string(&outcome.resolution).map_err(|e| format!("Failed to serialize resolution: {}", e))?;
+    let hypotheses_confirmed = serde_json::to_string(&outcome.hypotheses_confirmed).map_err(|e| format!("Failed to serialize hypotheses_confirmed: {}", e))?;
+    let hypotheses_refuted = serde_json::to_string(&outcome.hypotheses_refuted).map_err(|e| format!("Failed to serialize hypotheses_refuted: {}", e))?;
+
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO outcomes (
+                id, timestamp, question, question_digest, tools_used, resolution,
+                root_cause, confidence_score, duration_ms, hypotheses_confirmed, hypotheses_refuted
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                outcome.id,
+                outcome.timestamp,
+                outcome.question,
+                question_digest,
+                tools_used,
+                resolution,
+                outcome.root_cause,
+                outcome.confidence_score,
+                outcome.duration_ms as i64,
+                hypotheses_confirmed,
+                hypotheses_refuted,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Every outcome plus its embedding, oldest first. Used by pattern
+/// detection, which needs every embedding to cluster - for ranking a
+/// specific candidate set, prefer `embeddings_for_ids` instead.
+pub fn list_outcomes() -> Result<Vec<InvestigationOutcome>, String> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT o.id, o.timestamp, o.question, o.tools_used, o.resolution, o.root_cause,
+                    o.confidence_score, o.duration_ms, o.hypotheses_confirmed, o.hypotheses_refuted, e.vector
+             FROM outcomes o
+             LEFT JOIN embeddings e ON e.digest = o.question_digest
+             ORDER BY o.timestamp ASC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let tools_used: String = row.get(3)?;
+            let resolution: String = row.get(4)?;
+            let hypotheses_confirmed: String = row.get(8)?;
+            let hypotheses_refuted: String = row.get(9)?;
+            let vector: Option<Vec<u8>> = row.get(10)?;
+
+            Ok(InvestigationOutcome {
+                id: row.get(0)?,
+                timestamp: row.get(1)?,
+                question: row.get(2)?,
+                question_embedding: vector.map(|bytes| blob_to_embedding(&bytes)).unwrap_or_default(),
+                tools_used: serde_json::from_str(&tools_used).unwrap_or_default(),
+                resolution: serde_json::from_str(&resolution).unwrap_or(ResolutionType::Inconclusive),
+                root_cause: row.get(5)?,
+                confidence_score: row.get(6)?,
+                duration_ms: row.get::<_, i64>(7)? as u64,
+                hypotheses_confirmed: serde_json::from_str(&hypotheses_confirmed).unwrap_or_default(),
+                hypotheses_refuted: serde_json::from_str(&hypotheses_refuted).unwrap_or_default(),
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    })
+}
+
+/// Batch-fetch embeddings for exactly the outcome ids in `ids`, instead of
+/// loading every vector in the store - for a candidate set that's already
+/// been narrowed down (e.g. by a keyword prefilter).
+pub fn embeddings_for_ids(ids: Vec<String>) -> Result<HashMap<String, Vec<f32>>, String> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    with_connection(|conn| {
+        let placeholders = vec!["?"; ids.len()].join(",");
+        let sql = format!(
+            "SELECT o.id, e.vector FROM outcomes o
+             JOIN embeddings e ON e.digest = o.question_digest
+             WHERE o.id IN ({})",
+            placeholders
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(ids.iter()), |row| {
+            let vector: Vec<u8> = row.get(1)?;
+            Ok((row.get::<_, String>(0)?, blob_to_embedding(&vector)))
+        })?;
+
+        let mut results = HashMap::new();
+        for row in rows {
+            let (id, vector) = row?;
+            results.insert(id, vector);
+        }
+        Ok(results)
+    })
+}
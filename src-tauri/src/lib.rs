@@ -1,13 +1,38 @@
-use tauri::{Builder, Manager};
+use tauri::{Builder, Manager, Emitter};
 use crate::state::AppState;
 
 mod models;
 mod state;
 mod utils;
+mod bundle_source;
+mod tool_env;
+mod metrics_store;
+mod session_store;
+mod cost_store;
+mod azure_cost_store;
+mod learning;
+mod learning_store;
+mod pricing;
+mod resolution_model;
+mod recording;
+mod metrics_server;
+mod cluster_graph;
 mod client;
+mod internal_metrics;
+mod workers;
+#[cfg(feature = "scripting")]
+mod scripting;
 mod ai_local;
 mod agent_sidecar;
+mod audit;
+mod control_socket;
+mod proxy {
+    pub mod webui;
+}
+mod sidecar_watch;
+mod automation;
 mod embeddings;
+mod hnsw;
 mod mcp;
 mod commands {
     pub mod context;
@@ -19,36 +44,86 @@ mod commands {
     pub mod cost;
     pub mod ai_utilities;
     pub mod vcluster;
+    pub mod vcluster_tunnel;
     pub mod azure;
+    pub mod azure_sdk;
+    pub mod azure_kusto;
+    pub mod azure_scan;
+    pub mod azure_kubeconfig;
+    pub mod azure_cost;
     pub mod helm;
     pub mod argocd;
+    pub mod port_forward_manager;
+    pub mod webui_proxy;
     pub mod dependencies;
     pub mod support_bundle;
+    pub mod runbooks;
+    pub mod k8s;
+    pub mod sync;
+    pub mod workers;
+    pub mod remote;
+    pub mod session_manager;
+    pub mod claude;
+    pub mod claude_permissions;
+    pub mod claude_events;
 }
 
 use commands::context::{list_contexts, delete_context, set_kube_config, reset_state, get_current_context_name};
-use commands::discovery::{discover_api_resources, clear_discovery_cache, clear_all_caches};
-use commands::resources::{list_resources, delete_resource, get_resource_details, get_pod_logs, start_log_stream, stop_log_stream, start_resource_watch, stop_resource_watch, list_events, apply_yaml, get_resource_metrics, patch_resource, restart_resource, scale_resource};
-use commands::terminal::{start_local_shell, send_shell_input, resize_shell, stop_local_shell, send_exec_input, resize_exec, start_exec, execute_agent_command, start_terminal_agent, send_agent_input, resize_agent_terminal};
+use commands::discovery::{discover_api_resources, clear_discovery_cache, clear_all_caches, metrics_text};
+use commands::resources::{list_resources, delete_resource, get_resource_details, get_pod_logs, start_log_stream, stop_log_stream, start_workload_log_stream, start_resource_watch, stop_resource_watch, get_watch_snapshot, watch_resources, stop_watch, list_events, start_event_watch, stop_event_watch, apply_yaml, apply_yaml_batch, apply_resource, get_resource_metrics, patch_resource, restart_resource, scale_resource, batch_mutate_resources, resource_index, set_status_rules, get_status_rules};
+use commands::terminal::{start_local_shell, send_shell_input, resize_shell, stop_local_shell, send_exec_input, resize_exec, start_exec, execute_agent_command, start_terminal_agent, send_agent_input, resize_agent_terminal, kill_session, wait_session, start_recording, stop_recording, list_recordings};
+use commands::remote::{connect_remote_host, list_remote_hosts, disconnect_remote_host, start_remote_shell};
+use commands::session_manager::{list_sessions, restart_session, set_session_autoreconnect, restore_persisted_sessions};
+use commands::claude::{check_claude_code_status, call_claude_code, call_claude_code_interactive, list_claude_sessions, get_claude_session_messages, resume_claude_session};
+use commands::claude_permissions::{respond_to_permission, list_permission_audit_log};
 use commands::networking::{start_port_forward, stop_port_forward, list_port_forwards};
-use commands::cluster::{get_cluster_stats, get_cluster_cockpit, get_metrics_history, clear_metrics_history, get_initial_cluster_data};
-use commands::cost::get_cluster_cost_report;
-use commands::ai_utilities::{load_llm_config, save_llm_config, store_investigation_pattern, find_similar_investigations, load_opspilot_config, save_opspilot_config, get_env_var, get_opspilot_env_vars, get_kb_directory_info, init_kb_directory, store_secret, retrieve_secret, remove_secret, get_workspace_dir, read_server_info_file};
-use commands::vcluster::{list_vclusters, connect_vcluster, disconnect_vcluster};
+use commands::cluster::{get_cluster_stats, get_cluster_cockpit, get_metrics_history, get_metrics_history_range, get_metrics_summary, clear_metrics_history, get_initial_cluster_data, get_cluster_delta, start_metrics_server, stop_metrics_server, get_metrics_server_status, cordon_node, uncordon_node, drain_node};
+use commands::cost::{get_cluster_cost_report, get_cost_history, get_cost_delta, get_last_cost_snapshot, get_pricing_provider, set_pricing_provider};
+use commands::ai_utilities::{load_llm_config, save_llm_config, store_investigation_pattern, find_similar_investigations, semantic_search, refresh_embeddings, embeddings_status, load_opspilot_config, save_opspilot_config, get_env_var, get_opspilot_env_vars, get_kb_directory_info, init_kb_directory, store_secret, retrieve_secret, remove_secret, get_workspace_dir, read_server_info_file, get_config_schema, validate_opspilot_config, get_config_diagnostics};
+use commands::vcluster::{list_vclusters, connect_vcluster, disconnect_vcluster, fanout_cluster_stats, discover_vclusters, vcluster_exec, vcluster_port_forward};
+use commands::vcluster_tunnel::{start_vcluster_tunnel, stop_vcluster_tunnel};
 use commands::azure::{azure_login, refresh_azure_data, get_aks_credentials, detect_aks_cluster, get_aks_metrics_history};
-use commands::helm::{helm_list, helm_uninstall, helm_get_details, helm_history, helm_get_resources, helm_rollback};
-use commands::argocd::{get_argocd_server_info, start_argocd_port_forward, stop_argocd_port_forward, check_argocd_exists, open_argocd_webview, close_argocd_webview, force_close_argocd_webview, is_argocd_webview_active, update_argocd_webview_bounds};
-use commands::dependencies::check_dependencies;
+use commands::azure_kusto::{query_aks_insights, query_aks_insights_canned};
+use commands::azure_cost::{get_aks_costs, get_aks_cost_history, start_aks_cost_tracking, stop_aks_cost_tracking};
+use commands::helm::{helm_list, helm_uninstall, helm_get_details, helm_history, helm_get_resources, helm_rollback, helm_check_outdated, helm_upgrade, helm_diff_preview, helm_repo_list, helm_repo_add, helm_repo_remove, helm_repo_update, helm_report};
+use commands::argocd::{get_argocd_server_info, start_argocd_port_forward, stop_argocd_port_forward, check_argocd_exists, open_argocd_webview, close_argocd_webview, force_close_argocd_webview, is_argocd_webview_active, update_argocd_webview_bounds, fetch_argocd_autologin_credentials};
+use commands::port_forward_manager::{start_forward, stop_forward, list_forwards, forward_status};
+use commands::webui_proxy::{start_webui_proxy, stop_webui_proxy, list_webui_proxies};
+use commands::dependencies::{check_dependencies, suggest_install, install_tool};
 use commands::support_bundle::{load_support_bundle, get_bundle_resource_types, get_bundle_resources, get_bundle_resource_yaml, get_bundle_events, get_bundle_log_files, get_bundle_logs, get_bundle_alerts, get_bundle_health_summary, search_bundle, get_bundle_pods_by_status, close_support_bundle};
+use commands::runbooks::link_unhealthy_report_to_runbooks;
+use commands::k8s::{k8s_list_contexts, k8s_set_context, k8s_get_pods, k8s_describe_pod, k8s_get_events, k8s_pod_logs, k8s_scale_deployment};
+use commands::sync::{sync_push, sync_pull};
+use commands::workers::{list_workers, control_worker, get_sweep_status, set_tranquility};
+use workers::{DiscoveryRefreshWorker, LogRotationWorker, NavSweepWorker};
 
-use ai_local::{check_llm_status, check_ollama_status, create_ollama_model, call_llm, call_llm_streaming, call_local_llm_with_tools, call_local_llm, get_system_specs, analyze_text, auto_start_ollama};
-use agent_sidecar::{AgentSidecarState, start_agent, stop_agent, check_agent_status, supervise_agent, start_agent_sidecar};
+use ai_local::{check_llm_status, check_ollama_status, create_ollama_model, call_llm, call_llm_streaming, preload_model, list_llm_presets, apply_preset, list_registered_models, call_llm_with_tools, submit_tool_result, call_local_llm_with_tools, call_local_llm, get_system_specs, analyze_text, auto_start_ollama, run_investigation};
+use agent_sidecar::{AgentSidecarState, start_agent, stop_agent, check_agent_status, get_agent_state, reset_agent_breaker, get_agent_config, update_agent_config, supervise_agent, start_agent_sidecar, DEFAULT_SIDECAR_ID};
 use embeddings::{check_embedding_model_status, init_embedding_model};
-use mcp::commands::{connect_mcp_server, disconnect_mcp_server, list_mcp_tools, list_connected_mcp_servers, call_mcp_tool, check_command_exists, install_mcp_presets, install_uvx};
+use mcp::commands::{connect_mcp_server, disconnect_mcp_server, list_mcp_tools, list_connected_mcp_servers, list_mcp_allowed_tool_names, get_mcp_server_status, reconnect_mcp_server, mcp_connection_status, call_mcp_tool, check_command_exists, install_mcp_presets, install_uvx, set_mcp_log_level};
 use mcp::manager::McpManager;
+use audit::get_audit_events;
+use automation::{save_automation_script, list_automation_scripts, delete_automation_script, run_automation_script};
+
+/// Entry point for `opspilot --mcp-server`: run as an MCP *server* over
+/// stdio instead of launching the Tauri GUI, so another MCP-capable client
+/// (including OpsPilot's own `mcp::client`) can drive cluster operations
+/// through this binary. See `mcp::server`.
+pub fn run_mcp_server() {
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to start MCP server runtime");
+    if let Err(e) = runtime.block_on(mcp::server::run_stdio_server()) {
+        eprintln!("[mcp::server] exited with error: {}", e);
+        std::process::exit(1);
+    }
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Augment PATH with Homebrew/asdf/mise/Scoop/WinGet/registry locations so
+    // tools installed outside the GUI-launched app's inherited PATH are
+    // still found (covers what used to be a macOS-only shim here).
+    tool_env::apply_to_process_env();
+
     Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -58,31 +133,70 @@ pub fn run() {
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_opener::init())
         .setup(|app| {
+            mcp::logging::init(app.handle().clone());
+
             let app_state = AppState::new();
             app.manage(app_state);
 
+            // Pre-warm the discovery cache and keep the log file rotated
+            // without blocking any Tauri command on it.
+            let worker_manager = app.state::<AppState>().worker_manager.clone();
+            let nav_sweep_status = app.state::<AppState>().nav_sweep_status.clone();
+            worker_manager.spawn(DiscoveryRefreshWorker::new(app.handle().clone()));
+            worker_manager.spawn(LogRotationWorker);
+            worker_manager.spawn(NavSweepWorker::new(nav_sweep_status));
+            if let Some(hot_reload) = sidecar_watch::SidecarHotReloadWorker::new(app.handle().clone()) {
+                worker_manager.spawn(hot_reload);
+            }
+
             // Initialize agent sidecar state
             let agent_state = AgentSidecarState::new();
             app.manage(agent_state);
 
             // Initialize MCP manager
             let mcp_manager = McpManager::new();
+            let mut mcp_status_rx = mcp_manager.subscribe_status();
             app.manage(mcp_manager);
 
+            // Forward per-server health-check status changes to the
+            // frontend as they happen, so a health badge doesn't need to
+            // poll `get_mcp_server_status` on a timer.
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                while let Ok(event) = mcp_status_rx.recv().await {
+                    let _ = app_handle.emit("mcp:server_status", &event);
+                }
+            });
+
             // Start the agent sidecar automatically
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = start_agent_sidecar(&app_handle).await {
+                if let Err(e) = start_agent_sidecar(&app_handle, DEFAULT_SIDECAR_ID).await {
                     eprintln!("[startup] Failed to start agent sidecar: {}", e);
                 }
             });
 
+            // Restore port-forwards that survived restart. Needs the cluster
+            // connection (`set_kube_config`) to already be usable, so this
+            // may emit `session:failed` for sessions that can't reconnect
+            // until the user reconnects a context - that's surfaced to the
+            // UI rather than treated as a startup error.
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app_handle.state::<AppState>();
+                restore_persisted_sessions(app_handle.clone(), state).await;
+            });
+
             // Start background supervisor to keep agent healthy
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                supervise_agent(app_handle).await;
+                supervise_agent(app_handle, DEFAULT_SIDECAR_ID.to_string()).await;
             });
 
+            // Periodically roll up raw metrics-history rows into 5m/1h
+            // buckets and prune anything past retention.
+            tauri::async_runtime::spawn(metrics_store::run_compaction_loop());
+
             // Auto-start Ollama if installed but not running
             tauri::async_runtime::spawn(async move {
                 if let Err(e) = auto_start_ollama().await {
@@ -91,6 +205,9 @@ pub fn run() {
                 }
             });
 
+            // Headless control socket for the companion CLI
+            control_socket::spawn(app.handle().clone());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -105,30 +222,48 @@ pub fn run() {
             discover_api_resources,
             clear_discovery_cache,
             clear_all_caches,
+            metrics_text,
             
             // Resources
             list_resources,
+            resource_index,
             delete_resource,
             get_resource_details,
             apply_yaml,
+            apply_yaml_batch,
+            apply_resource,
             get_resource_metrics,
             patch_resource,
             restart_resource,
             scale_resource,
+            batch_mutate_resources,
+            set_status_rules,
+            get_status_rules,
             
             // Logs & Events
             get_pod_logs,
             start_log_stream,
             stop_log_stream,
+            start_workload_log_stream,
             start_resource_watch,
             stop_resource_watch,
+            get_watch_snapshot,
+            watch_resources,
+            stop_watch,
             list_events,
-            
+            start_event_watch,
+            stop_event_watch,
+
             // Terminal & Exec
             start_local_shell,
             send_shell_input,
             resize_shell,
             stop_local_shell,
+            kill_session,
+            wait_session,
+            start_recording,
+            stop_recording,
+            list_recordings,
             start_exec,
             send_exec_input,
             resize_exec,
@@ -138,19 +273,63 @@ pub fn run() {
             start_terminal_agent,
             send_agent_input,
             resize_agent_terminal,
-            
+
+            // Claude Code
+            check_claude_code_status,
+            call_claude_code,
+            call_claude_code_interactive,
+            respond_to_permission,
+            list_permission_audit_log,
+            list_claude_sessions,
+            get_claude_session_messages,
+            resume_claude_session,
+
+            // Remote (SSH bastion) hosts
+            connect_remote_host,
+            list_remote_hosts,
+            disconnect_remote_host,
+            start_remote_shell,
+
             // Networking
             start_port_forward,
             stop_port_forward,
             list_port_forwards,
-            
+            list_sessions,
+            restart_session,
+            set_session_autoreconnect,
+
+            // Port Forward Manager (generic, multi-service)
+            start_forward,
+            stop_forward,
+            list_forwards,
+            forward_status,
+
+            // Web UI Proxy (ArgoCD/Grafana/Prometheus/Kiali dashboards)
+            start_webui_proxy,
+            stop_webui_proxy,
+            list_webui_proxies,
+
             // Cluster Insights
             get_cluster_stats,
             get_cluster_cockpit,
             get_initial_cluster_data,
+            get_cluster_delta,
             get_cluster_cost_report,
+            get_cost_history,
+            get_cost_delta,
+            get_last_cost_snapshot,
+            get_pricing_provider,
+            set_pricing_provider,
             get_metrics_history,
+            get_metrics_history_range,
+            get_metrics_summary,
             clear_metrics_history,
+            start_metrics_server,
+            stop_metrics_server,
+            get_metrics_server_status,
+            cordon_node,
+            uncordon_node,
+            drain_node,
 
             // AI Local
             check_llm_status,
@@ -158,6 +337,12 @@ pub fn run() {
             create_ollama_model,
             call_llm,
             call_llm_streaming,
+            preload_model,
+            list_llm_presets,
+            apply_preset,
+            list_registered_models,
+            call_llm_with_tools,
+            submit_tool_result,
             call_local_llm_with_tools,
             call_local_llm,
             get_system_specs,
@@ -167,14 +352,21 @@ pub fn run() {
             save_llm_config,
             store_investigation_pattern,
             find_similar_investigations,
+            semantic_search,
+            refresh_embeddings,
+            embeddings_status,
             analyze_text,
+            run_investigation,
 
             // OpsPilot Configuration
             load_opspilot_config,
             save_opspilot_config,
             get_env_var,
             get_opspilot_env_vars,
-            
+            get_config_schema,
+            validate_opspilot_config,
+            get_config_diagnostics,
+
             // Secrets Management
             store_secret,
             retrieve_secret,
@@ -190,6 +382,12 @@ pub fn run() {
             list_vclusters,
             connect_vcluster,
             disconnect_vcluster,
+            fanout_cluster_stats,
+            discover_vclusters,
+            vcluster_exec,
+            vcluster_port_forward,
+            start_vcluster_tunnel,
+            stop_vcluster_tunnel,
 
             // Azure
             azure_login,
@@ -197,6 +395,12 @@ pub fn run() {
             get_aks_credentials,
             detect_aks_cluster,
             get_aks_metrics_history,
+            query_aks_insights,
+            query_aks_insights_canned,
+            get_aks_costs,
+            get_aks_cost_history,
+            start_aks_cost_tracking,
+            stop_aks_cost_tracking,
 
 
 
@@ -204,6 +408,10 @@ pub fn run() {
             start_agent,
             stop_agent,
             check_agent_status,
+            get_agent_state,
+            reset_agent_breaker,
+            get_agent_config,
+            update_agent_config,
 
             // Embeddings (KB)
             check_embedding_model_status,
@@ -216,13 +424,26 @@ pub fn run() {
             helm_history,
             helm_get_resources,
             helm_rollback,
+            helm_check_outdated,
+            helm_upgrade,
+            helm_diff_preview,
+            helm_repo_list,
+            helm_repo_add,
+            helm_repo_remove,
+            helm_repo_update,
+            helm_report,
 
             // MCP (Model Context Protocol)
             connect_mcp_server,
             disconnect_mcp_server,
             list_mcp_tools,
             list_connected_mcp_servers,
+            list_mcp_allowed_tool_names,
+            get_mcp_server_status,
+            reconnect_mcp_server,
+            mcp_connection_status,
             call_mcp_tool,
+            set_mcp_log_level,
             check_command_exists,
             install_mcp_presets,
             install_uvx,
@@ -233,13 +454,25 @@ pub fn run() {
             stop_argocd_port_forward,
             check_argocd_exists,
             open_argocd_webview,
+            fetch_argocd_autologin_credentials,
             close_argocd_webview,
             force_close_argocd_webview,
             is_argocd_webview_active,
             update_argocd_webview_bounds,
 
+            // Audit trail
+            get_audit_events,
+
+            // Automation (Rhai scripting)
+            save_automation_script,
+            list_automation_scripts,
+            delete_automation_script,
+            run_automation_script,
+
             // Dependencies
             check_dependencies,
+            suggest_install,
+            install_tool,
 
             // Support Bundle
             load_support_bundle,
@@ -253,7 +486,29 @@ pub fn run() {
             get_bundle_health_summary,
             search_bundle,
             get_bundle_pods_by_status,
-            close_support_bundle
+            close_support_bundle,
+
+            // Knowledge-base runbook linking
+            link_unhealthy_report_to_runbooks,
+
+            // Kubernetes integration layer
+            k8s_list_contexts,
+            k8s_set_context,
+            k8s_get_pods,
+            k8s_describe_pod,
+            k8s_get_events,
+            k8s_pod_logs,
+            k8s_scale_deployment,
+
+            // Remote sync
+            sync_push,
+            sync_pull,
+
+            // Background workers
+            list_workers,
+            control_worker,
+            get_sweep_status,
+            set_tranquility
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
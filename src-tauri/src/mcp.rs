@@ -0,0 +1,8 @@
+pub mod client;
+pub mod commands;
+pub mod core;
+pub mod logging;
+pub mod manager;
+pub mod policy;
+pub mod server;
+pub mod transport;
@@ -1,205 +1,652 @@
 use std::collections::HashMap;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, Command};
-use tokio::sync::{Mutex, oneshot};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
+use std::time::Duration;
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::{broadcast, mpsc, Mutex, oneshot};
+use tokio_util::sync::CancellationToken;
 use serde_json::{Value, json};
 use crate::mcp::core::*;
+use crate::mcp::logging::{log_event, McpLogLevel};
+use crate::mcp::transport::{self, StdioTransport, Transport};
+
+type PendingRequests = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, String>>>>>;
+
+/// A callback answering one server-initiated request method (e.g.
+/// `sampling/createMessage`). Takes the request's `params` and returns the
+/// `result` value to send back, or an error message turned into a JSON-RPC
+/// error response.
+type RequestHandler = Arc<dyn Fn(Value) -> Result<Value, String> + Send + Sync>;
+type RequestHandlers = Arc<Mutex<HashMap<String, RequestHandler>>>;
+
+/// Delay before respawning the server after it exits unexpectedly, giving a
+/// flaky server time to release its resources (port, lockfile, ...) before
+/// the next attempt.
+const RESTART_PERIOD: Duration = Duration::from_secs(2);
+
+/// Default cap on unexpected restarts before the supervisor gives up and
+/// leaves the client `Failed` rather than restart-looping forever. Override
+/// with `McpClient::set_max_restarts`.
+const DEFAULT_MAX_RESTARTS: u32 = 5;
+
+/// Backlog `McpClient::subscribe` receivers can fall behind by before they
+/// start missing notifications (`broadcast::error::RecvError::Lagged`).
+const NOTIFICATION_BUFFER: usize = 256;
+
+/// Timeout `request()` uses; `request_with_opts` lets a caller override this
+/// per-call.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Lifecycle of the supervised MCP server child process, readable via
+/// `McpClient::status()` without going through a request/timeout. Only the
+/// stdio transport's process-restart supervisor drives this past `Running`;
+/// clients built over other transports stay `Running` once connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpClientStatus {
+    /// The initial process is still being spawned.
+    Starting,
+    /// A process is up and believed healthy.
+    Running,
+    /// The previous process exited unexpectedly; a respawn is in flight.
+    Restarting,
+    /// Restarts exhausted `max_restarts`, or a respawn attempt itself failed;
+    /// the client will not try again.
+    Failed,
+}
+
+impl McpClientStatus {
+    fn from_u8(v: u8) -> Self {
+        match v {
+            0 => Self::Starting,
+            1 => Self::Running,
+            2 => Self::Restarting,
+            _ => Self::Failed,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Self::Starting => 0,
+            Self::Running => 1,
+            Self::Restarting => 2,
+            Self::Failed => 3,
+        }
+    }
+}
 
 pub struct McpClient {
-    stdin: Arc<Mutex<tokio::process::ChildStdin>>,
-    next_id: AtomicU64,
-    pending_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, String>>>>>,
+    transport: Arc<dyn Transport>,
+    next_id: Arc<AtomicU64>,
+    pending_requests: PendingRequests,
     tools: Arc<Mutex<Vec<McpTool>>>,
-    child: Arc<Mutex<Child>>,
+    handlers: RequestHandlers,
+    // Fan-out for decoded server notifications; `subscribe()` hands out more
+    // receivers against the same sender. Kept alive here so the channel
+    // doesn't close while zero receivers are subscribed.
+    notifications: broadcast::Sender<McpNotification>,
+    // Gates `request()`/`notify()` until the initialize handshake completes;
+    // `initialize`/`notifications/initialized` bypass it by going through
+    // `send_request`/`send_notify` directly instead of these methods.
+    ready: Arc<AtomicBool>,
+    ready_notify: Arc<tokio::sync::Notify>,
+    status: Arc<AtomicU8>,
+    restart_count: Arc<AtomicU32>,
+    max_restarts: Arc<AtomicU32>,
+    shutting_down: Arc<AtomicBool>,
+    // `Some` only when built over `StdioTransport` via `new` - that's the
+    // only transport with a process for a supervisor to watch/respawn.
+    child: Option<Arc<Mutex<Child>>>,
 }
 
-impl McpClient {
-    pub async fn new(command: &str, args: &[String], env: &HashMap<String, String>) -> Result<Self, String> {
-        eprintln!("[MCP] Spawning: {} {:?}", command, args);
-        eprintln!("[MCP] PATH: {}", env.get("PATH").unwrap_or(&"<not set>".to_string()));
-
-        let mut cmd = Command::new(command);
-        cmd.args(args);
-        cmd.envs(env);
-        cmd.stdin(Stdio::piped());
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped()); // Capture stderr to log it
-
-        // CORE SECURITY: Final choke point to prevent unauthorized execution
-        // This runs for ALL McpClients, regardless of who created them.
-        let full_cmd_str = format!("{} {}", command, args.join(" ")).to_lowercase();
-        if full_cmd_str.contains("calculator") || 
-           full_cmd_str.contains("calc.exe") || 
-           full_cmd_str.contains("calc.app") ||
-           (command.to_lowercase() == "open" && full_cmd_str.contains("calc")) {
-            return Err(format!("SECURITY BLOCKED: Attempted to spawn unsafe process: {}", full_cmd_str));
-        }
+struct SpawnedProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+}
 
-        let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn MCP server: {}", e))?;
-        eprintln!("[MCP] Process spawned successfully");
+/// CORE SECURITY: Final choke point to prevent unauthorized execution. Runs
+/// for every spawn, including supervisor respawns, regardless of who created
+/// this `McpClient`.
+fn check_command_safety(command: &str, args: &[String]) -> Result<(), String> {
+    let full_cmd_str = format!("{} {}", command, args.join(" ")).to_lowercase();
+    if full_cmd_str.contains("calculator")
+        || full_cmd_str.contains("calc.exe")
+        || full_cmd_str.contains("calc.app")
+        || (command.to_lowercase() == "open" && full_cmd_str.contains("calc"))
+    {
+        return Err(format!("SECURITY BLOCKED: Attempted to spawn unsafe process: {}", full_cmd_str));
+    }
+    Ok(())
+}
 
-        let stdin = child.stdin.take().ok_or("Failed to open stdin")?;
-        let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
-        let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
+async fn spawn_mcp_process(command: &str, args: &[String], env: &HashMap<String, String>) -> Result<SpawnedProcess, String> {
+    log_event(McpLogLevel::Debug, format!("[MCP] Spawning: {} {:?}", command, args));
+    log_event(McpLogLevel::Debug, format!("[MCP] PATH: {}", env.get("PATH").unwrap_or(&"<not set>".to_string())));
 
-        // Spawn stderr reader to log server errors
-        tokio::spawn(async move {
-            let mut reader = BufReader::new(stderr);
-            let mut line = String::new();
-            while let Ok(n) = reader.read_line(&mut line).await {
-                if n == 0 { break; }
-                eprintln!("[MCP stderr] {}", line.trim());
-                line.clear();
-            }
-        });
+    check_command_safety(command, args)?;
 
-        let pending_requests: Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, String>>>>> = Arc::new(Mutex::new(HashMap::new()));
-        let pending_clone = pending_requests.clone();
+    let mut cmd = Command::new(command);
+    cmd.args(args);
+    cmd.envs(env);
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped()); // Capture stderr to log it
 
-        // Spawn stdout reader loop
-        tokio::spawn(async move {
-            let mut reader = BufReader::new(stdout);
-            let mut line = String::new();
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn MCP server: {}", e))?;
+    log_event(McpLogLevel::Debug, "[MCP] Process spawned successfully".to_string());
 
-            while let Ok(n) = reader.read_line(&mut line).await {
-                if n == 0 {
-                    eprintln!("[MCP] EOF received from server");
-                    break;
-                }
+    let stdin = child.stdin.take().ok_or("Failed to open stdin")?;
+    let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to open stderr")?;
 
-                let text = line.trim();
-                if !text.is_empty() {
-                    eprintln!("[MCP] Received: {}", &text[..text.len().min(200)]);
-                    // Try parsing as JSON-RPC response
-                    match serde_json::from_str::<JsonRpcResponse>(text) {
-                        Ok(response) => {
-                            if let Some(id) = response.id {
-                                let mut pending = pending_clone.lock().await;
-                                if let Some(tx) = pending.remove(&id) {
-                                    // Send result or error
-                                    let res = if let Some(err) = response.error {
-                                        Err(format!("MCP Error {}: {}", err.code, err.message))
-                                    } else {
-                                        Ok(response.result.unwrap_or(Value::Null))
-                                    };
-                                    let _ = tx.send(res);
-                                } else {
-                                    eprintln!("[MCP] No pending request for id {}", id);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("[MCP] Failed to parse response: {}", e);
+    transport::spawn_stderr_reader(stderr);
+
+    Ok(SpawnedProcess { child, stdin, stdout })
+}
+
+/// Answer one server-initiated request by looking up a registered handler
+/// for its method and writing the result (or an error) back as a JSON-RPC
+/// response carrying the same `id`.
+async fn handle_incoming_request(req: JsonRpcRequest, transport: &Arc<dyn Transport>, handlers: &RequestHandlers) {
+    let Some(id) = req.id else { return };
+
+    let handler = handlers.lock().await.get(&req.method).cloned();
+    let outcome = match handler {
+        Some(handler) => handler(req.params.unwrap_or(Value::Null)),
+        None => Err(format!("No handler registered for server request '{}'", req.method)),
+    };
+
+    let response = match outcome {
+        Ok(result) => JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(result),
+            error: None,
+            id: Some(id),
+        },
+        Err(message) => JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(JsonRpcError { code: -32603, message, data: None }),
+            id: Some(id),
+        },
+    };
+
+    let json = match serde_json::to_string(&response) {
+        Ok(json) => json,
+        Err(e) => {
+            log_event(McpLogLevel::Error, format!("[MCP] Failed to serialize response to '{}': {}", req.method, e));
+            return;
+        }
+    };
+
+    if let Err(e) = transport.send_line(json).await {
+        log_event(McpLogLevel::Error, format!("[MCP] Failed to send response to '{}': {}", req.method, e));
+    }
+}
+
+/// React to a server notification. Only `notifications/tools/list_changed`
+/// currently has a built-in reaction (re-fetch `tools/list`); anything else
+/// is just logged, matching how unsolicited server events are otherwise
+/// surfaced in this module.
+async fn handle_incoming_notification(
+    notif: JsonRpcNotification,
+    transport: &Arc<dyn Transport>,
+    pending_requests: &PendingRequests,
+    next_id: &AtomicU64,
+    tools: &Arc<Mutex<Vec<McpTool>>>,
+    notifications: &broadcast::Sender<McpNotification>,
+) {
+    log_event(McpLogLevel::Debug, format!("[MCP] Notification: {}", notif.method));
+
+    if notif.method == "notifications/tools/list_changed" {
+        match send_request(transport, pending_requests, next_id, "tools/list", None).await {
+            Ok(tools_res) => match serde_json::from_value::<McpListToolsResult>(tools_res) {
+                Ok(result) => *tools.lock().await = result.tools,
+                Err(e) => log_event(McpLogLevel::Warn, format!("[MCP] Malformed tools/list result after list_changed: {}", e)),
+            },
+            Err(e) => log_event(McpLogLevel::Warn, format!("[MCP] Failed to refresh tools after list_changed: {}", e)),
+        }
+    }
+
+    // Ok(_) just means "at least one receiver", Err means none are
+    // subscribed right now - neither is an error worth logging.
+    let _ = notifications.send(notif.into());
+}
+
+/// Dispatch JSON-RPC messages pulled off `inbound` for the client's whole
+/// lifetime - this loop is transport-agnostic and is spawned exactly once
+/// regardless of whether the underlying transport ever reconnects. Besides
+/// responses to our own requests, a compliant server can also send its own
+/// requests (routed to `handlers`) and notifications (acted on by
+/// `handle_incoming_notification`).
+fn spawn_dispatch_loop(
+    mut inbound: mpsc::UnboundedReceiver<String>,
+    transport: Arc<dyn Transport>,
+    pending_requests: PendingRequests,
+    next_id: Arc<AtomicU64>,
+    tools: Arc<Mutex<Vec<McpTool>>>,
+    handlers: RequestHandlers,
+    notifications: broadcast::Sender<McpNotification>,
+) {
+    tokio::spawn(async move {
+        while let Some(text) = inbound.recv().await {
+            log_event(McpLogLevel::Trace, format!("[MCP] Received: {}", &text[..text.len().min(200)]));
+            match IncomingMessage::parse(&text) {
+                Ok(IncomingMessage::Response(response)) => {
+                    if let Some(id) = response.id {
+                        let mut pending = pending_requests.lock().await;
+                        if let Some(tx) = pending.remove(&id) {
+                            let res = if let Some(err) = response.error {
+                                Err(format!("MCP Error {}: {}", err.code, err.message))
+                            } else {
+                                Ok(response.result.unwrap_or(Value::Null))
+                            };
+                            let _ = tx.send(res);
+                        } else {
+                            log_event(McpLogLevel::Warn, format!("[MCP] No pending request for id {}", id));
                         }
                     }
                 }
-                line.clear();
+                Ok(IncomingMessage::Request(req)) => {
+                    handle_incoming_request(req, &transport, &handlers).await;
+                }
+                Ok(IncomingMessage::Notification(notif)) => {
+                    handle_incoming_notification(notif, &transport, &pending_requests, &next_id, &tools, &notifications).await;
+                }
+                Err(e) => {
+                    log_event(McpLogLevel::Warn, format!("[MCP] Failed to parse message: {}", e));
+                }
             }
-            eprintln!("[MCP] Server process exited");
-        });
+        }
+        log_event(McpLogLevel::Info, "[MCP] Dispatch loop ending: inbound channel closed".to_string());
+    });
+}
 
-        // Give the reader task and server process time to start
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+/// Block until `ready` is set, using the double-checked pattern `Notify`
+/// recommends for a one-shot readiness flag: register interest in a
+/// notification *before* re-checking the flag, so a `notify_waiters()` that
+/// lands between the first check and the await isn't missed.
+async fn wait_until_ready(ready: &AtomicBool, ready_notify: &tokio::sync::Notify) {
+    loop {
+        if ready.load(Ordering::SeqCst) {
+            return;
+        }
+        let notified = ready_notify.notified();
+        if ready.load(Ordering::SeqCst) {
+            return;
+        }
+        notified.await;
+    }
+}
 
-        Ok(Self {
-            stdin: Arc::new(Mutex::new(stdin)),
-            next_id: AtomicU64::new(1),
-            pending_requests,
-            tools: Arc::new(Mutex::new(Vec::new())),
-            child: Arc::new(Mutex::new(child)),
-        })
+fn mark_ready(ready: &AtomicBool, ready_notify: &tokio::sync::Notify) {
+    ready.store(true, Ordering::SeqCst);
+    ready_notify.notify_waiters();
+}
+
+async fn send_request(
+    transport: &Arc<dyn Transport>,
+    pending_requests: &PendingRequests,
+    next_id: &AtomicU64,
+    method: &str,
+    params: Option<Value>,
+) -> Result<Value, String> {
+    send_request_with_opts(
+        transport,
+        pending_requests,
+        next_id,
+        method,
+        params,
+        DEFAULT_REQUEST_TIMEOUT,
+        &CancellationToken::new(),
+    )
+    .await
+}
+
+/// Tell the server to stop working on `id` - sent on both the timeout and
+/// the caller-cancellation paths, since either way the pending slot is about
+/// to be dropped and the server would otherwise keep executing orphaned.
+/// Best-effort: a failure to notify doesn't change the error already being
+/// returned to the caller.
+async fn send_cancelled(transport: &Arc<dyn Transport>, id: u64, reason: &str) {
+    let params = json!({ "requestId": id, "reason": reason });
+    if let Err(e) = send_notify(transport, "notifications/cancelled", Some(params)).await {
+        log_event(McpLogLevel::Warn, format!("[MCP] Failed to send cancellation for request {}: {}", id, e));
     }
+}
 
-    pub async fn shutdown(&self) {
-        if let Ok(mut child) = self.child.try_lock() {
-             let _ = child.kill().await;
-        }
+async fn send_request_with_opts(
+    transport: &Arc<dyn Transport>,
+    pending_requests: &PendingRequests,
+    next_id: &AtomicU64,
+    method: &str,
+    params: Option<Value>,
+    timeout: Duration,
+    cancel: &CancellationToken,
+) -> Result<Value, String> {
+    let id = next_id.fetch_add(1, Ordering::SeqCst);
+
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        method: method.to_string(),
+        params,
+        id: Some(id),
+    };
+
+    let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    log_event(McpLogLevel::Trace, format!("[MCP] Sending request: {}", &json[..json.len().min(200)]));
+
+    let (tx, rx) = oneshot::channel();
+    {
+        let mut pending = pending_requests.lock().await;
+        pending.insert(id, tx);
     }
 
-    pub async fn request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
-        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
-        
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            method: method.to_string(),
-            params,
-            id: Some(id),
-        };
+    transport.send_line(json).await?;
+
+    enum Outcome {
+        Done(Result<Value, String>),
+        TimedOut,
+        Cancelled,
+    }
 
-        let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-        eprintln!("[MCP] Sending request: {}", &json[..json.len().min(200)]);
+    let outcome = tokio::select! {
+        res = rx => Outcome::Done(res.unwrap_or_else(|_| Err("Request cancelled or server died".to_string()))),
+        _ = tokio::time::sleep(timeout) => Outcome::TimedOut,
+        _ = cancel.cancelled() => Outcome::Cancelled,
+    };
 
-        let (tx, rx) = oneshot::channel();
-        
-        {
-            let mut pending = self.pending_requests.lock().await;
-            pending.insert(id, tx);
+    match outcome {
+        Outcome::Done(res) => res,
+        Outcome::TimedOut => {
+            pending_requests.lock().await.remove(&id);
+            send_cancelled(transport, id, "timed out").await;
+            Err(format!("MCP request timed out after {:?}", timeout))
         }
+        Outcome::Cancelled => {
+            pending_requests.lock().await.remove(&id);
+            send_cancelled(transport, id, "cancelled by caller").await;
+            Err("Request cancelled by caller".to_string())
+        }
+    }
+}
 
-        let mut stdin = self.stdin.lock().await;
-        stdin.write_all(json.as_bytes()).await.map_err(|e| e.to_string())?;
-        stdin.write_all(b"\n").await.map_err(|e| e.to_string())?;
-        stdin.flush().await.map_err(|e| e.to_string())?;
+async fn send_notify(transport: &Arc<dyn Transport>, method: &str, params: Option<Value>) -> Result<(), String> {
+    let notification = JsonRpcNotification {
+        jsonrpc: "2.0".to_string(),
+        method: method.to_string(),
+        params,
+    };
 
-        // Wait for response with 30 second timeout
-        match tokio::time::timeout(std::time::Duration::from_secs(30), rx).await {
-            Ok(Ok(res)) => res,
-            Ok(Err(_)) => Err("Request cancelled or server died".to_string()),
-            Err(_) => Err("MCP request timed out after 30 seconds".to_string()),
-        }
+    let json = serde_json::to_string(&notification).map_err(|e| e.to_string())?;
+    transport.send_line(json).await
+}
+
+async fn run_initialize(
+    transport: &Arc<dyn Transport>,
+    pending_requests: &PendingRequests,
+    next_id: &AtomicU64,
+    tools: &Arc<Mutex<Vec<McpTool>>>,
+) -> Result<(), String> {
+    let params = InitializeParams {
+        protocol_version: "2024-11-05".to_string(),
+        capabilities: ClientCapabilities {
+            roots: Some(json!({
+                "listChanged": true
+            })),
+            sampling: Some(json!({})),
+        },
+        client_info: McpClientInfo {
+            name: "opspilot-client".to_string(),
+            version: "0.1.0".to_string(),
+        },
+    };
+
+    let _res = send_request(transport, pending_requests, next_id, "initialize", Some(json!(params))).await?;
+
+    // Notify initialized - MUST use notify (no ID)
+    send_notify(transport, "notifications/initialized", None).await?;
+
+    // Fetch tools
+    let tools_res = send_request(transport, pending_requests, next_id, "tools/list", None).await?;
+
+    if let Ok(result) = serde_json::from_value::<McpListToolsResult>(tools_res) {
+        let mut tools = tools.lock().await;
+        *tools = result.tools;
     }
 
-    pub async fn notify(&self, method: &str, params: Option<Value>) -> Result<(), String> {
-        let notification = JsonRpcNotification {
-            jsonrpc: "2.0".to_string(),
-            method: method.to_string(),
-            params,
+    Ok(())
+}
+
+impl McpClient {
+    /// Connect over any `Transport` - the stdio-specific `new` is just a
+    /// convenience wrapper around this that also wires up the crash
+    /// supervisor. Request/response/initialize bookkeeping here is
+    /// identical no matter which transport produced `inbound`.
+    pub async fn with_transport(transport: Arc<dyn Transport>, inbound: mpsc::UnboundedReceiver<String>) -> Result<Self, String> {
+        let pending_requests: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+        let next_id = Arc::new(AtomicU64::new(1));
+        let tools = Arc::new(Mutex::new(Vec::new()));
+        let handlers: RequestHandlers = Arc::new(Mutex::new(HashMap::new()));
+        let (notifications, _) = broadcast::channel(NOTIFICATION_BUFFER);
+
+        spawn_dispatch_loop(inbound, transport.clone(), pending_requests.clone(), next_id.clone(), tools.clone(), handlers.clone(), notifications.clone());
+
+        // Give the reader task and server process time to start
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let client = Self {
+            transport,
+            next_id,
+            pending_requests,
+            tools,
+            handlers,
+            notifications,
+            ready: Arc::new(AtomicBool::new(false)),
+            ready_notify: Arc::new(tokio::sync::Notify::new()),
+            status: Arc::new(AtomicU8::new(McpClientStatus::Starting.as_u8())),
+            restart_count: Arc::new(AtomicU32::new(0)),
+            max_restarts: Arc::new(AtomicU32::new(DEFAULT_MAX_RESTARTS)),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            child: None,
         };
 
-        let json = serde_json::to_string(&notification).map_err(|e| e.to_string())?;
-        
-        let mut stdin = self.stdin.lock().await;
-        stdin.write_all(json.as_bytes()).await.map_err(|e| e.to_string())?;
-        stdin.write_all(b"\n").await.map_err(|e| e.to_string())?;
-        stdin.flush().await.map_err(|e| e.to_string())?;
-        Ok(())
+        client.status.store(McpClientStatus::Running.as_u8(), Ordering::SeqCst);
+
+        Ok(client)
     }
 
-    pub async fn initialize(&self) -> Result<(), String> {
-        let params = InitializeParams {
-            protocol_version: "2024-11-05".to_string(),
-            capabilities: ClientCapabilities {
-                roots: Some(json!({
-                    "listChanged": true
-                })),
-                sampling: Some(json!({})),
-            },
-            client_info: McpClientInfo {
-                name: "opspilot-client".to_string(),
-                version: "0.1.0".to_string(),
-            },
-        };
+    /// Spawn `command` as a child process and talk JSON-RPC over its
+    /// stdin/stdout (the original MCP transport). Also starts the crash
+    /// supervisor, which is specific to having an actual process to watch
+    /// and respawn - other transports don't get one.
+    pub async fn new(command: &str, args: &[String], env: &HashMap<String, String>) -> Result<Self, String> {
+        let spawned = spawn_mcp_process(command, args, env).await?;
+        let stdio = Arc::new(StdioTransport::new(spawned.stdin));
+
+        let (tx, inbound) = mpsc::unbounded_channel();
+        transport::pump_lines(spawned.stdout, tx.clone(), "stdout");
+
+        let mut client = Self::with_transport(stdio.clone(), inbound).await?;
+        client.child = Some(Arc::new(Mutex::new(spawned.child)));
+
+        client.spawn_supervisor(stdio, tx, command.to_string(), args.to_vec(), env.clone());
+
+        Ok(client)
+    }
+
+    /// Override the default restart budget (`DEFAULT_MAX_RESTARTS`) the
+    /// supervisor enforces before giving up on a crash-looping server.
+    pub fn set_max_restarts(&self, max_restarts: u32) {
+        self.max_restarts.store(max_restarts, Ordering::SeqCst);
+    }
+
+    pub fn status(&self) -> McpClientStatus {
+        McpClientStatus::from_u8(self.status.load(Ordering::SeqCst))
+    }
+
+    /// Register a handler for a server-initiated request method (e.g.
+    /// `sampling/createMessage`, `roots/list`). Replaces any handler
+    /// previously registered for the same method. Survives supervisor
+    /// respawns, since `handlers` is one of the `Arc`s shared with the
+    /// dispatch loop across restarts.
+    pub async fn register_request_handler<F>(&self, method: impl Into<String>, handler: F)
+    where
+        F: Fn(Value) -> Result<Value, String> + Send + Sync + 'static,
+    {
+        self.handlers.lock().await.insert(method.into(), Arc::new(handler));
+    }
+
+    /// Watch the child via `child.wait()`; on an exit that wasn't requested
+    /// by `shutdown()`, fail every pending request (the connection they were
+    /// waiting on is gone), then respawn the same command/args/env, plug the
+    /// new process's stdin/stdout into the existing transport/dispatch loop,
+    /// and re-run `initialize()` to repopulate `tools`. Gives up after
+    /// `max_restarts` consecutive unexpected exits. Only ever called from
+    /// `new`, so `self.child` is always `Some` here.
+    fn spawn_supervisor(&self, stdio: Arc<StdioTransport>, tx: mpsc::UnboundedSender<String>, command: String, args: Vec<String>, env: HashMap<String, String>) {
+        let child = self.child.clone().expect("spawn_supervisor requires a stdio-backed child");
+        let pending_requests = self.pending_requests.clone();
+        let next_id = self.next_id.clone();
+        let tools = self.tools.clone();
+        let transport: Arc<dyn Transport> = self.transport.clone();
+        let ready = self.ready.clone();
+        let ready_notify = self.ready_notify.clone();
+        let status = self.status.clone();
+        let restart_count = self.restart_count.clone();
+        let max_restarts = self.max_restarts.clone();
+        let shutting_down = self.shutting_down.clone();
 
-        let _res = self.request("initialize", Some(json!(params))).await?;
-        
-        // Notify initialized - MUST use notify (no ID)
-        self.notify("notifications/initialized", None).await?;
-
-        // Fetch tools
-        let tools_res = self.request("tools/list", None).await?;
-        
-        if let Ok(result) = serde_json::from_value::<McpListToolsResult>(tools_res) {
-            let mut tools = self.tools.lock().await;
-            *tools = result.tools;
+        tokio::spawn(async move {
+            loop {
+                let exit = {
+                    let mut guard = child.lock().await;
+                    guard.wait().await
+                };
+
+                if shutting_down.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                match &exit {
+                    Ok(code) => log_event(McpLogLevel::Warn, format!("[MCP] Server process exited unexpectedly: {:?}", code)),
+                    Err(e) => log_event(McpLogLevel::Warn, format!("[MCP] Failed to wait on server process: {}", e)),
+                }
+
+                // Every in-flight request was waiting on a connection that's
+                // now gone - fail them clearly rather than leaving them to
+                // hang until their own 30s timeout.
+                {
+                    let mut pending = pending_requests.lock().await;
+                    for (_, tx) in pending.drain() {
+                        let _ = tx.send(Err("MCP server restarted".to_string()));
+                    }
+                }
+
+                // New requests must wait for the re-run handshake below, same
+                // as the initial connection.
+                ready.store(false, Ordering::SeqCst);
+
+                let attempt = restart_count.fetch_add(1, Ordering::SeqCst) + 1;
+                let budget = max_restarts.load(Ordering::SeqCst);
+                if attempt > budget {
+                    log_event(McpLogLevel::Error, format!("[MCP] Server crash-looped {} times (max {}), giving up", attempt - 1, budget));
+                    status.store(McpClientStatus::Failed.as_u8(), Ordering::SeqCst);
+                    return;
+                }
+
+                status.store(McpClientStatus::Restarting.as_u8(), Ordering::SeqCst);
+                log_event(McpLogLevel::Warn, format!("[MCP] Restarting server (attempt {}/{}) in {:?}", attempt, budget, RESTART_PERIOD));
+                tokio::time::sleep(RESTART_PERIOD).await;
+
+                let spawned = match spawn_mcp_process(&command, &args, &env).await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        log_event(McpLogLevel::Error, format!("[MCP] Restart attempt {} failed to spawn: {}", attempt, e));
+                        status.store(McpClientStatus::Failed.as_u8(), Ordering::SeqCst);
+                        return;
+                    }
+                };
+
+                stdio.replace_stdin(spawned.stdin).await;
+                transport::pump_lines(spawned.stdout, tx.clone(), "stdout");
+                *child.lock().await = spawned.child;
+
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                match run_initialize(&transport, &pending_requests, &next_id, &tools).await {
+                    Ok(()) => {
+                        log_event(McpLogLevel::Info, format!("[MCP] Restart attempt {} succeeded", attempt));
+                        status.store(McpClientStatus::Running.as_u8(), Ordering::SeqCst);
+                        mark_ready(&ready, &ready_notify);
+                    }
+                    Err(e) => {
+                        log_event(McpLogLevel::Error, format!("[MCP] Restart attempt {} re-initialize failed: {}", attempt, e));
+                        status.store(McpClientStatus::Failed.as_u8(), Ordering::SeqCst);
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    pub async fn shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        if let Some(child) = &self.child {
+            if let Ok(mut child) = child.try_lock() {
+                let _ = child.kill().await;
+            }
         }
+    }
+
+    pub async fn request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
+        wait_until_ready(&self.ready, &self.ready_notify).await;
+        send_request(&self.transport, &self.pending_requests, &self.next_id, method, params).await
+    }
 
+    /// Like `request`, but with a caller-chosen timeout and a
+    /// `CancellationToken` the caller can fire to abort early (e.g. the UI
+    /// dropped the tool call, or a supervising task wants to bound several
+    /// requests with one shared deadline). Either way out emits
+    /// `notifications/cancelled` to the server so it can stop the now-
+    /// orphaned work instead of continuing to run it unobserved.
+    pub async fn request_with_opts(
+        &self,
+        method: &str,
+        params: Option<Value>,
+        timeout: Duration,
+        cancel: CancellationToken,
+    ) -> Result<Value, String> {
+        wait_until_ready(&self.ready, &self.ready_notify).await;
+        send_request_with_opts(&self.transport, &self.pending_requests, &self.next_id, method, params, timeout, &cancel).await
+    }
+
+    pub async fn notify(&self, method: &str, params: Option<Value>) -> Result<(), String> {
+        wait_until_ready(&self.ready, &self.ready_notify).await;
+        send_notify(&self.transport, method, params).await
+    }
+
+    pub async fn initialize(&self) -> Result<(), String> {
+        run_initialize(&self.transport, &self.pending_requests, &self.next_id, &self.tools).await?;
+        mark_ready(&self.ready, &self.ready_notify);
         Ok(())
     }
-    
+
     pub async fn get_tools(&self) -> Vec<McpTool> {
         self.tools.lock().await.clone()
     }
+
+    /// Subscribe to decoded server notifications (progress, log messages,
+    /// `list_changed`, ...) as they arrive. Each call hands out an
+    /// independent receiver starting from "now" - a receiver that falls more
+    /// than `NOTIFICATION_BUFFER` notifications behind gets
+    /// `RecvError::Lagged` on its next `recv()` rather than blocking the
+    /// dispatch loop for every other subscriber. Survives supervisor
+    /// respawns, since this sender is one of the `Arc`-backed fields shared
+    /// with the dispatch loop across restarts.
+    pub fn subscribe(&self) -> broadcast::Receiver<McpNotification> {
+        self.notifications.subscribe()
+    }
 }
@@ -1,7 +1,13 @@
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use std::collections::HashMap;
+use std::time::Instant;
+use futures::StreamExt;
+use serde::Serialize;
 use serde_json::Value;
-use crate::mcp::manager::McpManager;
+use crate::mcp::client::{McpClient, McpClientStatus};
+use crate::mcp::logging::{self, log_event, McpLogLevel};
+use crate::mcp::manager::{McpManager, McpConnectionStatus, mcp_token_key};
+use crate::mcp::policy;
 use tokio::process::Command;
 
 const UVX_INSTALL_SCRIPT: &str = "curl -LsSf https://astral.sh/uv/install.sh | sh";
@@ -92,8 +98,37 @@ pub async fn connect_mcp_server(
     command: String,
     args: Vec<String>,
     env: HashMap<String, String>,
+    transport: Option<String>,
+    url: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    // Bearer/OAuth token for a remote server, kept out of `headers`/`env` so
+    // it never round-trips through plain config: stored in the OS keychain
+    // via `crate::commands::ai_utilities::set_secret` and merged into the
+    // `Authorization` header on every (re)connect instead.
+    token: Option<String>,
     state: State<'_, McpManager>
 ) -> Result<(), String> {
+    // "http" connects to an already-running MCP endpoint over streamable-HTTP/SSE
+    // instead of spawning a local process; `command`/`args`/`env` are ignored in
+    // that case. Default to "stdio" so existing callers that don't know about
+    // `transport` yet keep working unchanged.
+    if transport.as_deref() == Some("http") {
+        let url = url.ok_or_else(|| "transport \"http\" requires a url".to_string())?;
+        if let Some(token) = &token {
+            crate::commands::ai_utilities::set_secret(&mcp_token_key(&name), token)
+                .map_err(|e| format!("Failed to store token for {}: {}", name, e))?;
+        }
+
+        return match state.add_http_server(name.clone(), url.clone(), headers.unwrap_or_default(), token.is_some()).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                let err_msg = format!("Failed to connect to {} ({}): {}", name, url, e);
+                log_event(McpLogLevel::Error, format!("[MCP] Error: {}", err_msg));
+                Err(err_msg)
+            }
+        };
+    }
+
     let mut env_aug = env.clone();
     let augmented_path = augment_path(&env);
     env_aug.insert("PATH".into(), augmented_path.clone());
@@ -111,35 +146,21 @@ pub async fn connect_mcp_server(
         try_find_command(&command, &env_aug).unwrap_or(command.clone())
     };
 
-    // SECURITY: Absolute hard block on "open" and "calculator" to prevent abuse
-    // This overrides any frontend logic or configuration.
-    let lower_cmd = final_command.to_lowercase();
-    if lower_cmd == "open" || lower_cmd.contains("calculator") || lower_cmd.ends_with(".app") {
-        let err = format!("Blocked execution of unsafe command: {}", final_command);
-        println!("[MCP] SECURITY ALERT: {}", err);
-        return Err(err);
-    }
-
-    // SECURITY: Check arguments as well
-    for arg in &args {
-        let lower_arg = arg.to_lowercase();
-        // Check for specific dangerous keywords in arguments
-        if lower_arg.contains("calculator") || 
-           lower_arg == "open" || 
-           (lower_arg.contains("calc") && (lower_arg.ends_with(".app") || lower_arg.ends_with(".exe"))) {
-            let err = format!("Blocked execution of unsafe argument: {}", arg);
-            println!("[MCP] SECURITY ALERT: {}", err);
-            return Err(err);
-        }
+    // SECURITY: Evaluate the resolved command/args/env against the
+    // configurable policy (see `mcp::policy`) before ever spawning it. This
+    // overrides any frontend logic or configuration.
+    if let Err(denied) = policy::load().evaluate(&final_command, &args, &env_aug) {
+        log_event(McpLogLevel::Error, format!("[MCP] SECURITY ALERT: {}", denied));
+        return Err(denied.to_string());
     }
 
-    println!("[MCP] Connecting to {} using command: {}", name, final_command);
+    log_event(McpLogLevel::Info, format!("[MCP] Connecting to {} using command: {}", name, final_command));
 
     match state.add_server(name.clone(), final_command.clone(), args.clone(), env_aug).await {
         Ok(_) => Ok(()),
         Err(e) => {
             let err_msg = format!("Failed to connect to {}: {}", name, e);
-            println!("[MCP] Error: {}", err_msg);
+            log_event(McpLogLevel::Error, format!("[MCP] Error: {}", err_msg));
             Err(err_msg)
         }
     }
@@ -168,6 +189,50 @@ pub async fn list_connected_mcp_servers(
     Ok(state.list_connected_servers().await)
 }
 
+/// Per-server `Connected`/`Reconnecting`/`Failed`-equivalent health, for a
+/// dashboard badge. `McpManager`'s health-check task keeps this current even
+/// between tool calls; listen for `mcp:server_status` events for push-based
+/// updates instead of polling this on a timer.
+#[tauri::command]
+pub async fn get_mcp_server_status(
+    state: State<'_, McpManager>
+) -> Result<HashMap<String, McpClientStatus>, String> {
+    Ok(state.server_status().await)
+}
+
+/// Force a remote (HTTP-transport) server to re-handshake right away instead
+/// of waiting for the health-check task's own backoff, e.g. after the user
+/// fixes an expired token. No-op target (an error) for stdio servers - those
+/// already have `McpClient`'s own process supervisor.
+#[tauri::command]
+pub async fn reconnect_mcp_server(
+    name: String,
+    state: State<'_, McpManager>
+) -> Result<(), String> {
+    state.reconnect_server(&name).await
+}
+
+/// Live connection health for a remote server, for the UI's health badge -
+/// `Connected`/`Reconnecting`/`Failed` plus the last transport error and how
+/// many reconnect attempts have been made since the last success.
+#[tauri::command]
+pub async fn mcp_connection_status(
+    name: String,
+    state: State<'_, McpManager>
+) -> Result<Option<McpConnectionStatus>, String> {
+    Ok(state.connection_status(&name).await)
+}
+
+/// Tool names formatted for `call_claude_code`'s `allowed_tools` argument,
+/// so discovered MCP tools can be granted to a Claude Code run the same way
+/// built-in tools are.
+#[tauri::command]
+pub async fn list_mcp_allowed_tool_names(
+    state: State<'_, McpManager>
+) -> Result<Vec<String>, String> {
+    Ok(state.allowed_tool_names().await)
+}
+
 #[tauri::command]
 pub async fn call_mcp_tool(
     server_name: String,
@@ -175,36 +240,53 @@ pub async fn call_mcp_tool(
     args: Value,
     state: State<'_, McpManager>
 ) -> Result<Value, String> {
-    if let Some(client) = state.get_client(&server_name).await {
-        // tool_name might be "get_issue" but client expects just "get_issue"
-        // Wrapper logic handled the namespacing.
-        
-        // MCP protocol: tools/call
-        // Request params: { name: tool_name, arguments: args }
-        
-        let params = serde_json::json!({
-            "name": tool_name,
-            "arguments": args
-        });
-        
-        let res = client.request("tools/call", Some(params)).await?;
-        
-        // Protocol: response result = { content: ... }
-        // We return the result content
-        Ok(res)
+    let start = Instant::now();
+    let arg_bytes = serde_json::to_vec(&args).map(|b| b.len()).unwrap_or(0);
+
+    let result = if let Some(client) = state.get_client(&server_name).await {
+        if let Err(e) = state.validate_tool_args(&server_name, &tool_name, &args).await {
+            Err(e)
+        } else {
+            // tool_name might be "get_issue" but client expects just "get_issue"
+            // Wrapper logic handled the namespacing.
+
+            // MCP protocol: tools/call
+            // Request params: { name: tool_name, arguments: args }
+
+            let params = serde_json::json!({
+                "name": tool_name,
+                "arguments": args
+            });
+
+            // Protocol: response result = { content: ... }
+            // We return the result content
+            client.request("tools/call", Some(params)).await
+        }
     } else {
         Err(format!("Server {} not found", server_name))
-    }
+    };
+
+    logging::record_tool_call(&server_name, &tool_name, arg_bytes, start, &result);
+    result
+}
+
+/// Raise or lower how much MCP transport chatter (raw request/response
+/// bytes, per-line dispatch) also reaches the `mcp://log` event stream at
+/// runtime, for a UI log-level picker. `Error`/`Warn`/`Info` records always
+/// go through regardless of this setting - see `logging::McpLogLevel`.
+#[tauri::command]
+pub fn set_mcp_log_level(level: String) -> Result<(), String> {
+    logging::set_level(logging::McpLogLevel::parse(&level)?);
+    Ok(())
 }
 
 // Simple preflight to see if a binary is available on PATH
 #[tauri::command]
 pub async fn check_command_exists(command: String) -> Result<bool, String> {
-    let lower = command.to_lowercase();
-    if lower == "open" || lower.contains("calculator") || lower.contains("calc.exe") {
-        return Err("Security: Cannot check status of unsafe command".to_string());
+    if let Err(denied) = policy::load().evaluate(&command, &[], &HashMap::new()) {
+        return Err(format!("Security: Cannot check status of unsafe command: {}", denied));
     }
-    
+
     let path = augment_path(&HashMap::new());
     let mut env_map = HashMap::new();
     env_map.insert("PATH".to_string(), path.clone());
@@ -216,9 +298,96 @@ pub async fn check_command_exists(command: String) -> Result<bool, String> {
     }
 }
 
-// Install a set of MCP servers via uvx (best-effort; network required)
+/// How many packages `install_mcp_presets` installs/probes at once - bounds
+/// concurrent `uvx` processes the same way `batch_mutate_resources` bounds
+/// concurrent cluster writes.
+const MAX_CONCURRENT_PRESET_INSTALLS: usize = 4;
+
+/// One `mcp:preset_progress` event per package per stage, so the UI can
+/// render a real install dashboard instead of waiting on one final string.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresetStage {
+    Started,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetProgressEvent {
+    pub package: String,
+    pub stage: PresetStage,
+    pub stderr: Option<String>,
+}
+
+/// Final, per-package outcome returned from `install_mcp_presets`, so the
+/// frontend can render a result table without re-parsing a log string.
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetResult {
+    pub package: String,
+    pub ok: bool,
+    pub tool_count: Option<usize>,
+    pub error: Option<String>,
+}
+
+fn emit_preset_progress(app: &AppHandle, package: &str, stage: PresetStage, stderr: Option<String>) {
+    let _ = app.emit("mcp:preset_progress", &PresetProgressEvent { package: package.to_string(), stage, stderr });
+}
+
+/// Install one preset via `uvx <pkg> --help`, then actually probe it: spawn
+/// it for real and run the MCP `initialize`/`tools/list` handshake, since a
+/// zero exit from `--help` only proves the package unpacked, not that it
+/// speaks the protocol.
+async fn install_and_probe_preset(app: &AppHandle, path: String, pkg: String) -> PresetResult {
+    emit_preset_progress(app, &pkg, PresetStage::Started, None);
+
+    let install = match Command::new("uvx").arg(&pkg).arg("--help").env("PATH", &path).output().await {
+        Ok(output) => output,
+        Err(e) => {
+            let error = format!("Failed to install {}: {}", pkg, e);
+            emit_preset_progress(app, &pkg, PresetStage::Failed, Some(error.clone()));
+            return PresetResult { package: pkg, ok: false, tool_count: None, error: Some(error) };
+        }
+    };
+
+    if !install.status.success() {
+        let stderr = String::from_utf8_lossy(&install.stderr).to_string();
+        emit_preset_progress(app, &pkg, PresetStage::Failed, Some(stderr.clone()));
+        return PresetResult { package: pkg, ok: false, tool_count: None, error: Some(stderr) };
+    }
+
+    let mut env = HashMap::new();
+    env.insert("PATH".to_string(), path);
+
+    let client = match McpClient::new("uvx", &[pkg.clone()], &env).await {
+        Ok(client) => client,
+        Err(e) => {
+            emit_preset_progress(app, &pkg, PresetStage::Failed, Some(e.clone()));
+            return PresetResult { package: pkg, ok: false, tool_count: None, error: Some(e) };
+        }
+    };
+
+    let result = match client.initialize().await {
+        Ok(()) => {
+            let tool_count = client.get_tools().await.len();
+            emit_preset_progress(app, &pkg, PresetStage::Succeeded, None);
+            PresetResult { package: pkg, ok: true, tool_count: Some(tool_count), error: None }
+        }
+        Err(e) => {
+            emit_preset_progress(app, &pkg, PresetStage::Failed, Some(e.clone()));
+            PresetResult { package: pkg, ok: false, tool_count: None, error: Some(e) }
+        }
+    };
+
+    client.shutdown().await;
+    result
+}
+
+// Install a set of MCP servers via uvx (best-effort; network required),
+// streaming per-package progress events and probing each with a real MCP
+// handshake rather than trusting `--help`'s exit code alone.
 #[tauri::command]
-pub async fn install_mcp_presets(packages: Option<Vec<String>>) -> Result<String, String> {
+pub async fn install_mcp_presets(app: AppHandle, packages: Option<Vec<String>>) -> Result<Vec<PresetResult>, String> {
     // Ensure uvx exists
     let path = augment_path(&HashMap::new());
     match Command::new("uvx").arg("--version").env("PATH", &path).output().await {
@@ -229,28 +398,16 @@ pub async fn install_mcp_presets(packages: Option<Vec<String>>) -> Result<String
     let targets: Vec<String> = packages
         .unwrap_or_else(|| DEFAULT_MCP_PACKAGES.iter().map(|s| s.to_string()).collect());
 
-    let mut log = String::new();
-    for pkg in targets {
-        let cmd = Command::new("uvx")
-            .arg(&pkg)
-            .arg("--help")
-            .env("PATH", &path)
-            .output()
-            .await
-            .map_err(|e| format!("Failed to install {}: {}", pkg, e))?;
-
-        if cmd.status.success() {
-            log.push_str(&format!("✅ {} ready\n", pkg));
-        } else {
-            log.push_str(&format!(
-                "❌ {} failed: {}\n",
-                pkg,
-                String::from_utf8_lossy(&cmd.stderr)
-            ));
-        }
-    }
+    let results = futures::stream::iter(targets.into_iter().map(|pkg| {
+        let app = app.clone();
+        let path = path.clone();
+        async move { install_and_probe_preset(&app, path, pkg).await }
+    }))
+    .buffer_unordered(MAX_CONCURRENT_PRESET_INSTALLS)
+    .collect::<Vec<_>>()
+    .await;
 
-    Ok(log)
+    Ok(results)
 }
 
 // Explicit helper to install uvx (invoked only via UI button)
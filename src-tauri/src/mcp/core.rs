@@ -33,6 +33,66 @@ pub struct JsonRpcError {
     pub data: Option<Value>,
 }
 
+/// A line read from the server's stdout, classified by shape since MCP is
+/// bidirectional: a server can send us a `JsonRpcResponse` to one of our own
+/// requests, but it can also send its own `JsonRpcRequest` (e.g.
+/// `sampling/createMessage`, `roots/list`) or `JsonRpcNotification` (e.g.
+/// `notifications/tools/list_changed`) that we're expected to act on.
+/// Mirrors the untagged `Message` split LSP implementations use for the same
+/// reason - responses carry `result`/`error` and no `method`, while incoming
+/// calls carry a `method` and are further split on whether `id` is present.
+#[derive(Debug, Clone)]
+pub enum IncomingMessage {
+    Response(JsonRpcResponse),
+    Request(JsonRpcRequest),
+    Notification(JsonRpcNotification),
+}
+
+impl IncomingMessage {
+    /// Classify a raw JSON-RPC line. Goes through `Value` first rather than
+    /// trying each struct in turn, since every field on `JsonRpcResponse` is
+    /// optional and would otherwise also parse a request/notification body.
+    pub fn parse(text: &str) -> Result<Self, serde_json::Error> {
+        let value: Value = serde_json::from_str(text)?;
+        if value.get("method").is_some() {
+            if value.get("id").is_some() {
+                Ok(Self::Request(serde_json::from_value(value)?))
+            } else {
+                Ok(Self::Notification(serde_json::from_value(value)?))
+            }
+        } else {
+            Ok(Self::Response(serde_json::from_value(value)?))
+        }
+    }
+}
+
+/// A decoded server notification, handed to subscribers of
+/// `McpClient::subscribe`. Covers all of `notifications/progress`,
+/// `notifications/message`, `notifications/tools/list_changed` and any
+/// other `notifications/*` method the server sends - callers match on
+/// `method` for the ones they care about.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpNotification {
+    pub method: String,
+    pub params: Option<Value>,
+    /// `params.progressToken` lifted out for `notifications/progress`
+    /// events, so callers can correlate a progress stream back to the
+    /// `progressToken` they attached to their own request's `params._meta`
+    /// without re-parsing `params` themselves.
+    pub progress_token: Option<Value>,
+}
+
+impl From<JsonRpcNotification> for McpNotification {
+    fn from(notif: JsonRpcNotification) -> Self {
+        let progress_token = notif
+            .params
+            .as_ref()
+            .and_then(|p| p.get("progressToken"))
+            .cloned();
+        Self { method: notif.method, params: notif.params, progress_token }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct McpTool {
     pub name: String,
@@ -0,0 +1,141 @@
+//! Structured logging + live activity stream for the MCP subsystem.
+//!
+//! Replaces the module's previous bare `println!`/`eprintln!` calls: every
+//! record still goes through the `log` crate (so it lands in whatever sink
+//! the rest of the app already uses - see e.g. `commands::vcluster_tunnel`'s
+//! `log::info!` usage), and also gets forwarded as an `mcp://log` Tauri
+//! event so the frontend can render a live activity stream instead of
+//! reading process stderr. `set_mcp_log_level` controls how much of the
+//! high-volume transport chatter (raw request/response bytes, per-line
+//! dispatch) actually reaches either sink - `Info` and louder always do.
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// `Error`/`Warn`/`Info` are always logged and emitted; `Debug`/`Trace`
+/// (the per-message transport traffic) are gated behind this, defaulting to
+/// off so a connected server doesn't spam the UI by default.
+static MIN_VERBOSE_LEVEL: AtomicU8 = AtomicU8::new(McpLogLevel::Info as u8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpLogLevel {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+impl McpLogLevel {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "warn" => Ok(Self::Warn),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            "trace" => Ok(Self::Trace),
+            other => Err(format!("Unknown log level '{}' (expected error/warn/info/debug/trace)", other)),
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            0 => Self::Error,
+            1 => Self::Warn,
+            2 => Self::Info,
+            3 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+/// Called once from `lib.rs`'s `setup()` so later log calls can forward to
+/// the frontend. Before this runs (or in contexts with no app, like tests),
+/// records still go through the `log` crate - they just don't also emit.
+pub fn init(app: AppHandle) {
+    let _ = APP_HANDLE.set(app);
+}
+
+/// Raises or lowers the verbose-transport-logging threshold at runtime, for
+/// the `set_mcp_log_level` command. `Info`/`Warn`/`Error` all behave the
+/// same (verbose traffic off); `Debug` and `Trace` progressively include it.
+pub fn set_level(level: McpLogLevel) {
+    MIN_VERBOSE_LEVEL.store(level as u8, Ordering::Relaxed);
+    log_event(McpLogLevel::Info, format!("[MCP] log level set to {:?}", level));
+}
+
+pub fn current_level() -> McpLogLevel {
+    McpLogLevel::from_u8(MIN_VERBOSE_LEVEL.load(Ordering::Relaxed))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+#[derive(Clone, serde::Serialize)]
+struct McpLogEvent {
+    level: McpLogLevel,
+    message: String,
+    timestamp_ms: u64,
+}
+
+/// Logs `message` at `level` through the `log` crate and, if at or above
+/// `current_level()`, also emits it as an `mcp://log` event. Use this
+/// instead of `println!`/`eprintln!` anywhere in `mcp::*`.
+pub fn log_event(level: McpLogLevel, message: String) {
+    match level {
+        McpLogLevel::Error => log::error!("{}", message),
+        McpLogLevel::Warn => log::warn!("{}", message),
+        McpLogLevel::Info => log::info!("{}", message),
+        McpLogLevel::Debug => log::debug!("{}", message),
+        McpLogLevel::Trace => log::trace!("{}", message),
+    }
+
+    if level <= current_level() {
+        if let Some(app) = APP_HANDLE.get() {
+            let _ = app.emit("mcp://log", &McpLogEvent { level, message, timestamp_ms: now_ms() });
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct McpToolCallEvent {
+    pub server: String,
+    pub tool: String,
+    pub arg_bytes: usize,
+    pub elapsed_ms: u128,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Records one dispatched `tools/call`: a `log::info!` line plus an
+/// `mcp://tool-call` event carrying server name, tool name, argument byte
+/// size, and elapsed time, for the frontend's per-server latency view.
+/// Always emitted regardless of `current_level()` - this is the whole point
+/// of the activity stream, not verbose transport noise.
+pub fn record_tool_call(server: &str, tool: &str, arg_bytes: usize, start: Instant, result: &Result<serde_json::Value, String>) {
+    let elapsed_ms = start.elapsed().as_millis();
+    let success = result.is_ok();
+    let error = result.as_ref().err().cloned();
+
+    log::info!(
+        "[MCP] tool_call server={} tool={} arg_bytes={} elapsed_ms={} success={}",
+        server, tool, arg_bytes, elapsed_ms, success
+    );
+
+    if let Some(app) = APP_HANDLE.get() {
+        let event = McpToolCallEvent {
+            server: server.to_string(),
+            tool: tool.to_string(),
+            arg_bytes,
+            elapsed_ms,
+            success,
+            error,
+        };
+        let _ = app.emit("mcp://tool-call", &event);
+    }
+}
@@ -1,50 +1,370 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio_util::sync::CancellationToken;
 use serde_json::{Value, json};
-use crate::mcp::client::McpClient;
+use crate::mcp::client::{McpClient, McpClientStatus};
+use crate::mcp::logging::{log_event, McpLogLevel};
+use crate::mcp::transport::HttpSseTransport;
+use crate::commands::ai_utilities::get_secret;
+
+/// How often the health-check task re-checks each server's status and (for
+/// transports without their own crash supervisor) pings it to detect a
+/// disconnect that wouldn't otherwise surface until the next tool call.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+/// Exponential reconnect backoff for remote (HTTP-transport) servers: 2s,
+/// 4s, 8s, ... capped at 60s, same shape as `code-tunnel`'s reconnect loop.
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(2);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+/// Give up and mark the server `Failed` after this many consecutive
+/// reconnect attempts, mirroring `McpClient`'s stdio `max_restarts` budget.
+const MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Keychain key a remote server's bearer/OAuth token is stored under (see
+/// `commands::ai_utilities::set_secret`/`get_secret`), so reconnecting
+/// always picks up the current token rather than a copy captured at connect
+/// time.
+pub fn mcp_token_key(name: &str) -> String {
+    format!("mcp_token::{}", name)
+}
+
+/// Key `McpManager::tool_schemas` under, so two servers exposing a
+/// same-named tool (e.g. both having a `search` tool) don't collide.
+fn tool_schema_key(server_name: &str, tool_name: &str) -> String {
+    format!("{}::{}", server_name, tool_name)
+}
+
+/// Broadcast on `McpManager::subscribe_status` whenever a server's status
+/// changes, so the frontend can show a per-server health badge without
+/// polling `server_status` on a timer.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct McpServerStatusEvent {
+    pub server: String,
+    pub status: McpClientStatus,
+}
+
+/// `mcp_connection_status`'s `state` field - a coarser view than
+/// `McpClientStatus` that only applies to remote servers (stdio servers are
+/// always `Connected` or `Failed`, since `McpClient`'s own supervisor
+/// already owns restart/backoff for them).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct McpConnectionStatus {
+    pub state: ConnectionState,
+    pub last_error: Option<String>,
+    pub retries: u32,
+}
+
+/// Enough to re-handshake a remote server from scratch: everything
+/// `add_http_server` needs except the token, which is re-read from the
+/// keychain at connect time (`has_token` just says whether to look).
+#[derive(Clone)]
+struct RemoteServerConfig {
+    url: String,
+    headers: HashMap<String, String>,
+    has_token: bool,
+}
+
+/// Resolve one remote server's full header set, re-reading its token from
+/// the keychain each time so a rotated token takes effect on the next
+/// reconnect without the caller having to reconnect manually.
+fn resolve_headers(name: &str, config: &RemoteServerConfig) -> HashMap<String, String> {
+    let mut headers = config.headers.clone();
+    if config.has_token {
+        if let Some(token) = get_secret(&mcp_token_key(name)) {
+            headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+        }
+    }
+    headers
+}
+
+/// Re-handshake a remote server from its stored `RemoteServerConfig`,
+/// swapping a fresh `McpClient` into `clients` on success. A free function
+/// (rather than an `&self` method) so it can be called from inside the
+/// health-check task's `'static` closure with only the individual `Arc`s it
+/// needs, instead of requiring `McpManager` itself to live behind an `Arc`.
+async fn do_reconnect(
+    name: &str,
+    remote_configs: &Mutex<HashMap<String, RemoteServerConfig>>,
+    clients: &Mutex<HashMap<String, Arc<McpClient>>>,
+) -> Result<(), String> {
+    let config = remote_configs
+        .lock()
+        .await
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("'{}' is not a remote (HTTP) server", name))?;
+
+    let resolved_headers = resolve_headers(name, &config);
+    let (transport, inbound) = HttpSseTransport::connect(&config.url, resolved_headers).await?;
+    let client = McpClient::with_transport(transport, inbound).await?;
+    client.initialize().await?;
+
+    clients.lock().await.insert(name.to_string(), Arc::new(client));
+    Ok(())
+}
+
+/// Exponential-backoff reconnect loop for a remote server whose ping just
+/// failed. Runs inline on the health-check task (not a separate spawn) so
+/// the normal status-polling loop naturally resumes once this returns -
+/// either because the server came back, or because it gave up after
+/// `MAX_RECONNECT_ATTEMPTS` and marked the server `Failed`.
+async fn reconnect_with_backoff(
+    name: &str,
+    first_error: String,
+    remote_configs: &Mutex<HashMap<String, RemoteServerConfig>>,
+    clients: &Mutex<HashMap<String, Arc<McpClient>>>,
+    connection_status: &Mutex<HashMap<String, McpConnectionStatus>>,
+    status_tx: &broadcast::Sender<McpServerStatusEvent>,
+) {
+    {
+        let mut statuses = connection_status.lock().await;
+        let entry = statuses.entry(name.to_string()).or_insert(McpConnectionStatus {
+            state: ConnectionState::Reconnecting,
+            last_error: None,
+            retries: 0,
+        });
+        entry.state = ConnectionState::Reconnecting;
+        entry.last_error = Some(first_error);
+    }
+    let _ = status_tx.send(McpServerStatusEvent { server: name.to_string(), status: McpClientStatus::Restarting });
+
+    let mut delay = RECONNECT_BASE_DELAY;
+    for attempt in 1..=MAX_RECONNECT_ATTEMPTS {
+        log_event(McpLogLevel::Warn, format!("[MCP] Reconnecting to '{}' (attempt {}/{}) in {:?}", name, attempt, MAX_RECONNECT_ATTEMPTS, delay));
+        tokio::time::sleep(delay).await;
+
+        match do_reconnect(name, remote_configs, clients).await {
+            Ok(()) => {
+                log_event(McpLogLevel::Info, format!("[MCP] Reconnected to '{}' after {} attempt(s)", name, attempt));
+                connection_status.lock().await.insert(
+                    name.to_string(),
+                    McpConnectionStatus { state: ConnectionState::Connected, last_error: None, retries: 0 },
+                );
+                let _ = status_tx.send(McpServerStatusEvent { server: name.to_string(), status: McpClientStatus::Running });
+                return;
+            }
+            Err(e) => {
+                log_event(McpLogLevel::Warn, format!("[MCP] Reconnect attempt {} to '{}' failed: {}", attempt, name, e));
+                let mut statuses = connection_status.lock().await;
+                if let Some(entry) = statuses.get_mut(name) {
+                    entry.retries = attempt;
+                    entry.last_error = Some(e);
+                }
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+            }
+        }
+    }
+
+    log_event(McpLogLevel::Error, format!("[MCP] Giving up reconnecting to '{}' after {} attempts", name, MAX_RECONNECT_ATTEMPTS));
+    if let Some(entry) = connection_status.lock().await.get_mut(name) {
+        entry.state = ConnectionState::Failed;
+    }
+    let _ = status_tx.send(McpServerStatusEvent { server: name.to_string(), status: McpClientStatus::Failed });
+}
 
 pub struct McpManager {
     clients: Arc<Mutex<HashMap<String, Arc<McpClient>>>>,
+    // Health-check task per server, so `remove_server` can stop polling a
+    // server that's no longer registered instead of leaking the task.
+    health_tasks: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    status_tx: broadcast::Sender<McpServerStatusEvent>,
+    // Connect parameters for remote servers only, keyed by name, so
+    // `reconnect_server` and the health-check task's auto-reconnect loop can
+    // re-handshake without the caller re-supplying `url`/`headers`.
+    remote_configs: Arc<Mutex<HashMap<String, RemoteServerConfig>>>,
+    connection_status: Arc<Mutex<HashMap<String, McpConnectionStatus>>>,
+    // Each tool's `inputSchema`, keyed by `tool_schema_key(server, tool)`, so
+    // `call_mcp_tool` can validate arguments locally before dispatch instead
+    // of paying a round trip to find out they're malformed. Rebuilt wholesale
+    // by `refresh_tool_schemas` whenever `list_all_tools` re-fetches the tool
+    // list, rather than tracked incrementally per server.
+    tool_schemas: Arc<Mutex<HashMap<String, Value>>>,
 }
 
 impl McpManager {
     pub fn new() -> Self {
+        let (status_tx, _) = broadcast::channel(64);
         Self {
             clients: Arc::new(Mutex::new(HashMap::new())),
+            health_tasks: Arc::new(Mutex::new(HashMap::new())),
+            status_tx,
+            remote_configs: Arc::new(Mutex::new(HashMap::new())),
+            connection_status: Arc::new(Mutex::new(HashMap::new())),
+            tool_schemas: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
     pub async fn add_server(&self, name: String, command: String, args: Vec<String>, env: HashMap<String, String>) -> Result<(), String> {
         let client = McpClient::new(&command, &args, &env).await?;
         client.initialize().await?;
-        
+        let client = Arc::new(client);
+
         let mut clients = self.clients.lock().await;
-        eprintln!("[MCP] Added server: {}", name);
-        clients.insert(name, Arc::new(client));
+        log_event(McpLogLevel::Info, format!("[MCP] Added server: {}", name));
+        clients.insert(name.clone(), client.clone());
+        drop(clients);
+
+        self.spawn_health_check(name, client).await;
         Ok(())
     }
 
+    /// Connect to an already-running MCP endpoint over the `streamable-http`
+    /// transport instead of spawning a local process - for hosted/team
+    /// servers a user doesn't have installed locally. `headers` carries any
+    /// non-auth headers the endpoint expects; `has_token` says whether a
+    /// bearer token was separately stashed in the keychain under
+    /// `mcp_token_key(name)` for `resolve_headers` to fold in.
+    pub async fn add_http_server(&self, name: String, url: String, headers: HashMap<String, String>, has_token: bool) -> Result<(), String> {
+        let config = RemoteServerConfig { url: url.clone(), headers, has_token };
+        let resolved_headers = resolve_headers(&name, &config);
+
+        let (transport, inbound) = HttpSseTransport::connect(&url, resolved_headers).await?;
+        let client = McpClient::with_transport(transport, inbound).await?;
+        client.initialize().await?;
+        let client = Arc::new(client);
+
+        self.remote_configs.lock().await.insert(name.clone(), config);
+        self.connection_status.lock().await.insert(
+            name.clone(),
+            McpConnectionStatus { state: ConnectionState::Connected, last_error: None, retries: 0 },
+        );
+
+        let mut clients = self.clients.lock().await;
+        log_event(McpLogLevel::Info, format!("[MCP] Added HTTP server: {} ({})", name, url));
+        clients.insert(name.clone(), client.clone());
+        drop(clients);
+
+        self.spawn_health_check(name, client).await;
+        Ok(())
+    }
+
+    /// Manual entry point for the `reconnect_mcp_server` command - same
+    /// effect as a successful attempt inside the health-check task's backoff
+    /// loop, so the UI has an escape hatch (e.g. right after the user fixes
+    /// an expired token) instead of waiting for the next automatic try.
+    pub async fn reconnect_server(&self, name: &str) -> Result<(), String> {
+        match do_reconnect(name, &self.remote_configs, &self.clients).await {
+            Ok(()) => {
+                self.connection_status.lock().await.insert(
+                    name.to_string(),
+                    McpConnectionStatus { state: ConnectionState::Connected, last_error: None, retries: 0 },
+                );
+                let _ = self.status_tx.send(McpServerStatusEvent { server: name.to_string(), status: McpClientStatus::Running });
+                Ok(())
+            }
+            Err(e) => {
+                let mut statuses = self.connection_status.lock().await;
+                let entry = statuses.entry(name.to_string()).or_insert(McpConnectionStatus {
+                    state: ConnectionState::Failed,
+                    last_error: None,
+                    retries: 0,
+                });
+                entry.state = ConnectionState::Failed;
+                entry.last_error = Some(e.clone());
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn connection_status(&self, name: &str) -> Option<McpConnectionStatus> {
+        self.connection_status.lock().await.get(name).cloned()
+    }
+
+    /// Periodically re-read `client.status()` and broadcast changes, and for
+    /// transports with no process-level crash supervisor (anything other
+    /// than stdio - see `McpClient::new` vs `with_transport`) issue a cheap
+    /// `tools/list` ping so a dropped HTTP/SSE or socket connection is
+    /// noticed here instead of surfacing as a confusing error on the next
+    /// real tool call. For a remote server (one with a `remote_configs`
+    /// entry) a failed ping triggers `reconnect_with_backoff` right away
+    /// instead of just logging it.
+    async fn spawn_health_check(&self, name: String, client: Arc<McpClient>) {
+        let status_tx = self.status_tx.clone();
+        let remote_configs = self.remote_configs.clone();
+        let clients = self.clients.clone();
+        let connection_status = self.connection_status.clone();
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let mut last_status = client.status();
+            let _ = status_tx.send(McpServerStatusEvent { server: task_name.clone(), status: last_status });
+
+            loop {
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+                if client.status() == McpClientStatus::Running {
+                    if let Err(e) = client
+                        .request_with_opts("tools/list", None, PING_TIMEOUT, CancellationToken::new())
+                        .await
+                    {
+                        log_event(McpLogLevel::Warn, format!("[MCP] Health check ping to '{}' failed: {}", task_name, e));
+                        if remote_configs.lock().await.contains_key(&task_name) {
+                            reconnect_with_backoff(&task_name, e, &remote_configs, &clients, &connection_status, &status_tx).await;
+                        }
+                    }
+                }
+
+                let status = client.status();
+                if status != last_status {
+                    log_event(McpLogLevel::Info, format!("[MCP] Server '{}' status changed: {:?} -> {:?}", task_name, last_status, status));
+                    let _ = status_tx.send(McpServerStatusEvent { server: task_name.clone(), status });
+                    last_status = status;
+                }
+
+                if status == McpClientStatus::Failed {
+                    return;
+                }
+            }
+        });
+
+        self.health_tasks.lock().await.insert(name, handle);
+    }
+
     pub async fn remove_server(&self, name: &str) {
         let mut clients = self.clients.lock().await;
         if let Some(client) = clients.remove(name) {
             client.shutdown().await;
-            eprintln!("[MCP] Removed server: {}", name);
+            log_event(McpLogLevel::Info, format!("[MCP] Removed server: {}", name));
         }
+        drop(clients);
+
+        if let Some(task) = self.health_tasks.lock().await.remove(name) {
+            task.abort();
+        }
+
+        if self.remote_configs.lock().await.remove(name).is_some() {
+            let _ = crate::commands::ai_utilities::delete_secret(&mcp_token_key(name));
+        }
+        self.connection_status.lock().await.remove(name);
     }
-    
+
     pub async fn get_client(&self, name: &str) -> Option<Arc<McpClient>> {
         let clients = self.clients.lock().await;
         clients.get(name).cloned()
     }
 
+    /// Tool list per server, served from each `McpClient`'s own cache
+    /// (populated on `initialize()` and kept fresh by
+    /// `notifications/tools/list_changed`) rather than issuing a live
+    /// `tools/list` request to every client on every call.
     pub async fn list_all_tools(&self) -> Vec<Value> {
         let clients = self.clients.lock().await;
         let mut all_tools = Vec::new();
+        let mut schemas = HashMap::new();
 
         for (server_name, client) in clients.iter() {
             let tools = client.get_tools().await;
             for tool in tools {
+                 schemas.insert(tool_schema_key(server_name, &tool.name), tool.input_schema.clone());
                  all_tools.push(json!({
                      "name": format!("{}__{}", server_name, tool.name),
                      "original_name": tool.name,
@@ -54,12 +374,70 @@ impl McpManager {
                  }));
             }
         }
+        drop(clients);
+        *self.tool_schemas.lock().await = schemas;
+
         all_tools
     }
 
+    /// Validate `args` against `tool_name`'s cached `inputSchema` before
+    /// `call_mcp_tool` builds the `tools/call` params, so a malformed call
+    /// fails immediately with a precise message instead of round-tripping to
+    /// the server. Passes through silently (no schema cached yet, or the
+    /// schema itself doesn't compile) rather than blocking a call the server
+    /// itself would be able to judge.
+    pub async fn validate_tool_args(&self, server_name: &str, tool_name: &str, args: &Value) -> Result<(), String> {
+        let Some(schema) = self.tool_schemas.lock().await.get(&tool_schema_key(server_name, tool_name)).cloned() else {
+            return Ok(());
+        };
+
+        let compiled = match jsonschema::JSONSchema::compile(&schema) {
+            Ok(compiled) => compiled,
+            Err(_) => return Ok(()),
+        };
+
+        if let Err(errors) = compiled.validate(args) {
+            let messages: Vec<String> = errors
+                .map(|e| format!("{} (at {})", e, e.instance_path))
+                .collect();
+            return Err(format!("Invalid arguments for tool '{}': {}", tool_name, messages.join("; ")));
+        }
+
+        Ok(())
+    }
+
     /// Returns list of currently connected server names
     pub async fn list_connected_servers(&self) -> Vec<String> {
         let clients = self.clients.lock().await;
         clients.keys().cloned().collect()
     }
+
+    /// Current `Connected`/`Reconnecting`/`Failed`-equivalent status
+    /// (`McpClientStatus`) of every registered server, for a one-shot
+    /// dashboard render; `subscribe_status` is the push-based complement for
+    /// live badge updates.
+    pub async fn server_status(&self) -> HashMap<String, McpClientStatus> {
+        let clients = self.clients.lock().await;
+        clients.iter().map(|(name, client)| (name.clone(), client.status())).collect()
+    }
+
+    pub fn subscribe_status(&self) -> broadcast::Receiver<McpServerStatusEvent> {
+        self.status_tx.subscribe()
+    }
+
+    /// Tool identifiers in the `mcp__<server>__<tool>` form the `claude` CLI
+    /// expects in its `--allowed-tools` argument, so discovered MCP tools can
+    /// be passed straight into `call_claude_code`'s `allowed_tools` list
+    /// alongside built-ins like `Read`/`Bash(kubectl:*)`.
+    pub async fn allowed_tool_names(&self) -> Vec<String> {
+        let clients = self.clients.lock().await;
+        let mut names = Vec::new();
+
+        for (server_name, client) in clients.iter() {
+            for tool in client.get_tools().await {
+                names.push(format!("mcp__{}__{}", server_name, tool.name));
+            }
+        }
+        names
+    }
 }
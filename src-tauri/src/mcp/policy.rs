@@ -0,0 +1,198 @@
+//! Configurable allow/deny policy for resolved MCP server commands.
+//!
+//! Replaces `connect_mcp_server`'s and `check_command_exists`'s previous
+//! hardcoded `open`/`calculator`/`.app` checks with rules loaded from a
+//! persisted config file (`~/.opspilot/mcp_policy.json`, same home-directory
+//! convention as `OpsPilotConfig`), so an admin can tighten the default
+//! (e.g. restrict to `uvx`/`npx` under known install dirs) or extend the
+//! deny rules without a code change. Missing or invalid config falls back to
+//! `CommandPolicy::default()`, which blocks the same things the old hardcoded
+//! checks did. This is a policy layer on top of `client::check_command_safety`,
+//! not a replacement for it - that function stays a hardcoded, unconfigurable
+//! backstop against every spawn regardless of policy.
+use std::collections::HashMap;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::mcp::logging::{log_event, McpLogLevel};
+
+/// A denied invocation, naming the rule that tripped and why, so callers can
+/// surface something more actionable than a generic "blocked" string.
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyDenied {
+    pub rule: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for PolicyDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Blocked by MCP command policy rule '{}': {}", self.rule, self.reason)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandPolicy {
+    /// Absolute-path prefixes (after `~` expansion) a resolved command must
+    /// fall under - e.g. `["~/.local/bin", "/opt/homebrew/bin"]` to allow
+    /// only uvx/npx-style installs from known locations. Empty (the default)
+    /// means no restriction here, matching today's behavior of only denying
+    /// specific commands rather than allowlisting directories.
+    #[serde(default)]
+    pub allowed_command_dirs: Vec<String>,
+    /// Glob patterns (`*` wildcard only) matched against the resolved
+    /// command's lowercased absolute path.
+    #[serde(default = "default_denied_command_globs")]
+    pub denied_command_globs: Vec<String>,
+    /// Glob patterns matched against each lowercased argument.
+    #[serde(default = "default_denied_arg_globs")]
+    pub denied_arg_globs: Vec<String>,
+    /// Environment variable names that may never be forwarded to a spawned
+    /// server, regardless of what the caller supplied.
+    #[serde(default)]
+    pub denied_env_vars: Vec<String>,
+}
+
+fn default_denied_command_globs() -> Vec<String> {
+    vec!["*open*".to_string(), "*calculator*".to_string(), "*.app".to_string()]
+}
+
+fn default_denied_arg_globs() -> Vec<String> {
+    vec![
+        "open".to_string(),
+        "*calculator*".to_string(),
+        "*calc*.app".to_string(),
+        "*calc*.exe".to_string(),
+    ]
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_command_dirs: Vec::new(),
+            denied_command_globs: default_denied_command_globs(),
+            denied_arg_globs: default_denied_arg_globs(),
+            denied_env_vars: Vec::new(),
+        }
+    }
+}
+
+impl CommandPolicy {
+    /// Evaluate a resolved `command` (ideally an absolute path, as
+    /// `connect_mcp_server` resolves it before calling this) plus its `args`
+    /// and `env` against every rule, stopping at the first violation.
+    pub fn evaluate(&self, command: &str, args: &[String], env: &HashMap<String, String>) -> Result<(), PolicyDenied> {
+        let lower_cmd = command.to_lowercase();
+
+        if !self.allowed_command_dirs.is_empty() {
+            let allowed = self
+                .allowed_command_dirs
+                .iter()
+                .any(|dir| lower_cmd.starts_with(&expand_home(dir).to_lowercase()));
+            if !allowed {
+                return Err(PolicyDenied {
+                    rule: "allowed_command_dirs".to_string(),
+                    reason: format!("'{}' is not under an allowed install directory", command),
+                });
+            }
+        }
+
+        for pattern in &self.denied_command_globs {
+            if glob_match(&pattern.to_lowercase(), &lower_cmd) {
+                return Err(PolicyDenied {
+                    rule: format!("denied_command_globs:{}", pattern),
+                    reason: format!("command '{}' matches denied pattern '{}'", command, pattern),
+                });
+            }
+        }
+
+        for arg in args {
+            let lower_arg = arg.to_lowercase();
+            for pattern in &self.denied_arg_globs {
+                if glob_match(&pattern.to_lowercase(), &lower_arg) {
+                    return Err(PolicyDenied {
+                        rule: format!("denied_arg_globs:{}", pattern),
+                        reason: format!("argument '{}' matches denied pattern '{}'", arg, pattern),
+                    });
+                }
+            }
+        }
+
+        for key in env.keys() {
+            if self.denied_env_vars.iter().any(|denied| denied.eq_ignore_ascii_case(key)) {
+                return Err(PolicyDenied {
+                    rule: "denied_env_vars".to_string(),
+                    reason: format!("environment variable '{}' may not be passed to an MCP server", key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest).to_string_lossy().to_string();
+        }
+    }
+    path.to_string()
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+/// (including none) and every other character must match literally. Good
+/// enough for path-prefix/suffix/contains-style deny rules without pulling
+/// in a glob crate for this one use.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let mut star: Option<usize> = None;
+    let mut match_idx = 0usize;
+
+    while ti < t.len() {
+        if pi < p.len() && p[pi] == t[ti] {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some(pi);
+            match_idx = ti;
+            pi += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            match_idx += 1;
+            ti = match_idx;
+        } else {
+            return false;
+        }
+    }
+
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+fn policy_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".opspilot").join("mcp_policy.json"))
+}
+
+/// Load the policy from `~/.opspilot/mcp_policy.json`, falling back to
+/// `CommandPolicy::default()` if the file is missing or fails to parse.
+pub fn load() -> CommandPolicy {
+    let Some(path) = policy_config_path() else {
+        return CommandPolicy::default();
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(policy) => policy,
+            Err(e) => {
+                log_event(McpLogLevel::Warn, format!("[MCP] Ignoring invalid policy config at {}: {}", path.display(), e));
+                CommandPolicy::default()
+            }
+        },
+        Err(_) => CommandPolicy::default(),
+    }
+}
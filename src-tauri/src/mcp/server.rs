@@ -0,0 +1,214 @@
+/// OpsPilot acting as an MCP *server* rather than a client: a stdio
+/// JSON-RPC loop that lets Claude Code (or any other MCP-capable client)
+/// drive cluster operations through the same `commands::k8s` functions the
+/// app's own frontend calls, instead of shelling out to `kubectl` itself.
+///
+/// Entered via `opspilot --mcp-server` (see `main.rs`) rather than the
+/// normal Tauri GUI path - there's no `AppState`/`Window` here, so every
+/// tool goes through `commands::k8s`'s standalone, kubeconfig-building
+/// functions rather than the app's cached client/session maps.
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::commands::k8s::{k8s_describe_pod, k8s_get_pods, k8s_pod_logs, k8s_scale_deployment};
+use crate::mcp::core::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, McpInitializeResult, McpListToolsResult, McpServerInfo, McpTool};
+use crate::mcp::logging::{log_event, McpLogLevel};
+
+const SERVER_NAME: &str = "opspilot";
+const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+fn tool_definitions() -> Vec<McpTool> {
+    vec![
+        McpTool {
+            name: "get_pods".to_string(),
+            description: Some("List pods, optionally scoped to a namespace".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "namespace": { "type": "string", "description": "Namespace to list pods in; omit for all namespaces" }
+                }
+            }),
+        },
+        McpTool {
+            name: "describe_resource".to_string(),
+            description: Some("Describe a pod: status, node, containers and recent events".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "namespace": { "type": "string" },
+                    "name": { "type": "string" }
+                },
+                "required": ["namespace", "name"]
+            }),
+        },
+        McpTool {
+            name: "pod_logs".to_string(),
+            description: Some("Tail a container's logs".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "namespace": { "type": "string" },
+                    "name": { "type": "string" },
+                    "container": { "type": "string" },
+                    "tail_lines": { "type": "integer", "description": "Defaults to 200" }
+                },
+                "required": ["namespace", "name"]
+            }),
+        },
+        McpTool {
+            name: "scale_deployment".to_string(),
+            description: Some("Set a Deployment's replica count".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "namespace": { "type": "string" },
+                    "name": { "type": "string" },
+                    "replicas": { "type": "integer" }
+                },
+                "required": ["namespace", "name", "replicas"]
+            }),
+        },
+    ]
+}
+
+fn field_str(input: &Value, field: &str) -> Result<String, String> {
+    input
+        .get(field)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("Missing required field: {}", field))
+}
+
+/// Dispatch one `tools/call` by name, returning the JSON to put in the
+/// response's `result.content`. Mirrors `ai_local::execute_investigation_tool`'s
+/// shape (name + input -> text result) but against live cluster operations
+/// instead of a `kubectl` subprocess.
+async fn call_tool(name: &str, input: &Value) -> Result<Value, String> {
+    match name {
+        "get_pods" => {
+            let namespace = input.get("namespace").and_then(|v| v.as_str()).map(|s| s.to_string());
+            let pods = k8s_get_pods(None, namespace).await?;
+            serde_json::to_value(pods).map_err(|e| e.to_string())
+        }
+        "describe_resource" => {
+            let namespace = field_str(input, "namespace")?;
+            let name = field_str(input, "name")?;
+            let description = k8s_describe_pod(None, namespace, name).await?;
+            serde_json::to_value(description).map_err(|e| e.to_string())
+        }
+        "pod_logs" => {
+            let namespace = field_str(input, "namespace")?;
+            let name = field_str(input, "name")?;
+            let container = input.get("container").and_then(|v| v.as_str()).map(|s| s.to_string());
+            let tail_lines = input.get("tail_lines").and_then(|v| v.as_i64());
+            let logs = k8s_pod_logs(None, namespace, name, container, tail_lines).await?;
+            Ok(Value::String(logs))
+        }
+        "scale_deployment" => {
+            let namespace = field_str(input, "namespace")?;
+            let name = field_str(input, "name")?;
+            let replicas = input
+                .get("replicas")
+                .and_then(|v| v.as_i64())
+                .ok_or("Missing required field: replicas")? as i32;
+            k8s_scale_deployment(None, namespace, name, replicas).await?;
+            Ok(json!({ "scaled": true }))
+        }
+        other => Err(format!("Unknown tool: {}", other)),
+    }
+}
+
+fn ok_response(id: Option<u64>, result: Value) -> JsonRpcResponse {
+    JsonRpcResponse { jsonrpc: "2.0".to_string(), result: Some(result), error: None, id }
+}
+
+fn err_response(id: Option<u64>, code: i64, message: String) -> JsonRpcResponse {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: None,
+        error: Some(JsonRpcError { code, message, data: None }),
+        id,
+    }
+}
+
+async fn handle_request(req: JsonRpcRequest) -> JsonRpcResponse {
+    match req.method.as_str() {
+        "initialize" => {
+            let result = McpInitializeResult {
+                protocol_version: PROTOCOL_VERSION.to_string(),
+                server_info: McpServerInfo { name: SERVER_NAME.to_string(), version: SERVER_VERSION.to_string() },
+                capabilities: json!({ "tools": {} }),
+            };
+            match serde_json::to_value(result) {
+                Ok(v) => ok_response(req.id, v),
+                Err(e) => err_response(req.id, -32603, e.to_string()),
+            }
+        }
+        "tools/list" => {
+            let result = McpListToolsResult { tools: tool_definitions() };
+            match serde_json::to_value(result) {
+                Ok(v) => ok_response(req.id, v),
+                Err(e) => err_response(req.id, -32603, e.to_string()),
+            }
+        }
+        "tools/call" => {
+            let params = req.params.unwrap_or(Value::Null);
+            let name = match params.get("name").and_then(|v| v.as_str()) {
+                Some(n) => n.to_string(),
+                None => return err_response(req.id, -32602, "Missing params.name".to_string()),
+            };
+            let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+            match call_tool(&name, &arguments).await {
+                Ok(content) => ok_response(req.id, json!({ "content": content })),
+                Err(e) => err_response(req.id, -32000, e),
+            }
+        }
+        other => err_response(req.id, -32601, format!("Method not found: {}", other)),
+    }
+}
+
+/// Run the stdio JSON-RPC loop until stdin closes. `initialized` and any
+/// other notifications (no `id`) are accepted and ignored - there's no
+/// client state to update here, unlike `McpClient`'s handshake.
+pub async fn run_stdio_server() -> Result<(), String> {
+    let stdin = tokio::io::stdin();
+    let mut stdout = tokio::io::stdout();
+    let mut lines = BufReader::new(stdin).lines();
+
+    while let Some(line) = lines.next_line().await.map_err(|e| e.to_string())? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let value: Value = match serde_json::from_str(&line) {
+            Ok(v) => v,
+            Err(e) => {
+                log_event(McpLogLevel::Warn, format!("[mcp::server] Failed to parse request: {}", e));
+                continue;
+            }
+        };
+
+        // Notifications (no `id`) never get a response.
+        if value.get("id").is_none() {
+            continue;
+        }
+
+        let req: JsonRpcRequest = match serde_json::from_value(value) {
+            Ok(r) => r,
+            Err(e) => {
+                log_event(McpLogLevel::Warn, format!("[mcp::server] Malformed request: {}", e));
+                continue;
+            }
+        };
+
+        let response = handle_request(req).await;
+        let serialized = serde_json::to_string(&response).map_err(|e| e.to_string())?;
+        stdout.write_all(serialized.as_bytes()).await.map_err(|e| e.to_string())?;
+        stdout.write_all(b"\n").await.map_err(|e| e.to_string())?;
+        stdout.flush().await.map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+/// Line-oriented transports `McpClient` can speak JSON-RPC over.
+///
+/// `Transport` only covers the outgoing half (`send_line`): inbound messages
+/// are delivered out-of-band, over the `mpsc::UnboundedReceiver<String>`
+/// each transport's constructor returns alongside itself. `McpClient` runs a
+/// single dispatch loop over that receiver for the client's whole lifetime,
+/// so request/response bookkeeping, `initialize`, and the ready-gate in
+/// `client.rs` are identical no matter which transport actually moves the
+/// bytes.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::ChildStderr;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::mcp::logging::{log_event, McpLogLevel};
+
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Write one newline-delimited JSON-RPC message.
+    async fn send_line(&self, line: String) -> Result<(), String>;
+}
+
+/// Log a child process's stderr until it closes. Shared by every transport
+/// that spawns a helper process (stdio, and eventually a socket transport
+/// that launches its own server).
+pub fn spawn_stderr_reader(stderr: ChildStderr) {
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(stderr);
+        let mut line = String::new();
+        while let Ok(n) = reader.read_line(&mut line).await {
+            if n == 0 {
+                break;
+            }
+            log_event(McpLogLevel::Debug, format!("[MCP stderr] {}", line.trim()));
+            line.clear();
+        }
+    });
+}
+
+/// Forward newline-delimited messages read from `reader` onto `tx` until
+/// EOF or a read error. `label` is just for the close/error log line.
+pub fn pump_lines<R>(reader: R, tx: mpsc::UnboundedSender<String>, label: &'static str)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        loop {
+            match reader.read_line(&mut line).await {
+                Ok(0) => {
+                    log_event(McpLogLevel::Info, format!("[MCP] {} closed", label));
+                    break;
+                }
+                Ok(_) => {
+                    let text = line.trim();
+                    if !text.is_empty() && tx.send(text.to_string()).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    log_event(McpLogLevel::Warn, format!("[MCP] {} read error: {}", label, e));
+                    break;
+                }
+            }
+            line.clear();
+        }
+    });
+}
+
+/// Talks JSON-RPC over a spawned child process's stdin/stdout pipes - the
+/// original MCP transport. `McpClient::new` builds one of these; its stdin
+/// half can be swapped out by `McpClient`'s crash supervisor via
+/// `replace_stdin` after it respawns the process, without disturbing the
+/// `Arc<dyn Transport>` callers already hold.
+pub struct StdioTransport {
+    stdin: Mutex<tokio::process::ChildStdin>,
+}
+
+impl StdioTransport {
+    pub fn new(stdin: tokio::process::ChildStdin) -> Self {
+        Self { stdin: Mutex::new(stdin) }
+    }
+
+    /// Swap in a freshly spawned process's stdin. Used only by `McpClient`'s
+    /// supervisor after it respawns a crashed server.
+    pub async fn replace_stdin(&self, stdin: tokio::process::ChildStdin) {
+        *self.stdin.lock().await = stdin;
+    }
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn send_line(&self, line: String) -> Result<(), String> {
+        let mut stdin = self.stdin.lock().await;
+        stdin.write_all(line.as_bytes()).await.map_err(|e| e.to_string())?;
+        stdin.write_all(b"\n").await.map_err(|e| e.to_string())?;
+        stdin.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Talks JSON-RPC over a single persistent framed connection - a Unix
+/// domain socket on unix, a named pipe on Windows. Follows the same
+/// "one read loop feeding a shared dispatch path" shape ethers' IPC
+/// transport uses for local Ethereum node sockets.
+#[cfg(unix)]
+pub struct SocketTransport {
+    writer: Mutex<tokio::net::unix::OwnedWriteHalf>,
+}
+
+#[cfg(unix)]
+impl SocketTransport {
+    pub async fn connect(path: &str) -> Result<(Arc<Self>, mpsc::UnboundedReceiver<String>), String> {
+        let stream = tokio::net::UnixStream::connect(path)
+            .await
+            .map_err(|e| format!("Failed to connect to MCP socket {}: {}", path, e))?;
+        let (read_half, write_half) = stream.into_split();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        pump_lines(read_half, tx, "socket");
+
+        Ok((Arc::new(Self { writer: Mutex::new(write_half) }), rx))
+    }
+}
+
+#[cfg(unix)]
+#[async_trait]
+impl Transport for SocketTransport {
+    async fn send_line(&self, line: String) -> Result<(), String> {
+        let mut writer = self.writer.lock().await;
+        writer.write_all(line.as_bytes()).await.map_err(|e| e.to_string())?;
+        writer.write_all(b"\n").await.map_err(|e| e.to_string())?;
+        writer.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(windows)]
+pub struct SocketTransport {
+    writer: Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
+}
+
+#[cfg(windows)]
+impl SocketTransport {
+    /// `path` is a named pipe path, e.g. `\\.\pipe\opspilot-mcp`.
+    pub async fn connect(path: &str) -> Result<(Arc<Self>, mpsc::UnboundedReceiver<String>), String> {
+        let client = tokio::net::windows::named_pipe::ClientOptions::new()
+            .open(path)
+            .map_err(|e| format!("Failed to connect to MCP named pipe {}: {}", path, e))?;
+        let (read_half, write_half) = tokio::io::split(client);
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        pump_lines(read_half, tx, "named pipe");
+
+        Ok((Arc::new(Self { writer: Mutex::new(Box::new(write_half)) }), rx))
+    }
+}
+
+#[cfg(windows)]
+#[async_trait]
+impl Transport for SocketTransport {
+    async fn send_line(&self, line: String) -> Result<(), String> {
+        let mut writer = self.writer.lock().await;
+        writer.write_all(line.as_bytes()).await.map_err(|e| e.to_string())?;
+        writer.write_all(b"\n").await.map_err(|e| e.to_string())?;
+        writer.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Talks JSON-RPC over the `streamable-http` transport MCP defines: each
+/// outgoing message is an HTTP POST to `endpoint`, and inbound messages
+/// (responses, server-initiated requests/notifications) arrive as `data:`
+/// lines on a long-lived `text/event-stream` GET against the same endpoint.
+pub struct HttpSseTransport {
+    client: reqwest::Client,
+    endpoint: String,
+    /// Extra headers (bearer tokens, team auth, ...) sent with both the SSE
+    /// GET and every outgoing POST, per `connect_mcp_server`'s optional
+    /// header map.
+    headers: std::collections::HashMap<String, String>,
+}
+
+impl HttpSseTransport {
+    pub async fn connect(
+        endpoint: &str,
+        headers: std::collections::HashMap<String, String>,
+    ) -> Result<(Arc<Self>, mpsc::UnboundedReceiver<String>), String> {
+        let client = reqwest::Client::new();
+
+        let mut request = client
+            .get(endpoint)
+            .header("Accept", "text/event-stream");
+        for (key, value) in &headers {
+            request = request.header(key.as_str(), value.as_str());
+        }
+
+        let sse_response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to open MCP SSE stream at {}: {}", endpoint, e))?;
+
+        if !sse_response.status().is_success() {
+            return Err(format!("MCP SSE stream at {} returned status {}", endpoint, sse_response.status()));
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(pump_sse_events(sse_response, tx));
+
+        Ok((
+            Arc::new(Self { client, endpoint: endpoint.to_string(), headers }),
+            rx,
+        ))
+    }
+}
+
+/// Forward the `data:` payload of each SSE event onto `tx` until the stream
+/// closes. A minimal parser rather than pulling in an SSE crate: buffers
+/// bytes, splits on `\n\n` event boundaries, and concatenates any `data:`
+/// lines within an event per the SSE spec.
+async fn pump_sse_events(response: reqwest::Response, tx: mpsc::UnboundedSender<String>) {
+    use futures::StreamExt;
+
+    let mut stream = response.bytes_stream();
+    let mut buf = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(c) => c,
+            Err(e) => {
+                log_event(McpLogLevel::Warn, format!("[MCP] SSE stream error: {}", e));
+                break;
+            }
+        };
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(boundary) = buf.find("\n\n") {
+            let event: String = buf.drain(..boundary + 2).collect();
+            let data: String = event
+                .lines()
+                .filter_map(|l| l.strip_prefix("data:"))
+                .map(|l| l.trim())
+                .collect::<Vec<_>>()
+                .join("\n");
+            if !data.is_empty() && tx.send(data).is_err() {
+                return;
+            }
+        }
+    }
+    log_event(McpLogLevel::Info, "[MCP] SSE stream closed".to_string());
+}
+
+#[async_trait]
+impl Transport for HttpSseTransport {
+    async fn send_line(&self, line: String) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json");
+        for (key, value) in &self.headers {
+            request = request.header(key.as_str(), value.as_str());
+        }
+
+        let response = request
+            .body(line)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to POST MCP message to {}: {}", self.endpoint, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("MCP endpoint {} rejected message with status {}", self.endpoint, response.status()));
+        }
+        Ok(())
+    }
+}
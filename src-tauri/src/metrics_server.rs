@@ -0,0 +1,128 @@
+//! Optional local Prometheus exposition endpoint for the cluster cockpit.
+//! Binds to 127.0.0.1 only (this is a desktop agent, not a fleet component)
+//! on a configurable port and serves `/metrics` by re-running the same
+//! aggregation code path as `get_cluster_cockpit`, so an existing
+//! Prometheus/Grafana stack can scrape the agent for long-term dashboards
+//! without the app needing to be focused.
+
+use axum::{extract::State, routing::get, Router};
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+use crate::models::ClusterCockpitData;
+use crate::state::AppState;
+
+const DEFAULT_PORT: u16 = 9091;
+
+#[derive(Clone)]
+struct MetricsServerState {
+    app_handle: AppHandle,
+}
+
+static SHUTDOWN_TX: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
+static RUNNING_PORT: Mutex<Option<u16>> = Mutex::new(None);
+
+/// The port the metrics server is currently listening on, if it's running.
+pub fn running_port() -> Option<u16> {
+    *RUNNING_PORT.lock().unwrap()
+}
+
+/// Start the metrics server on `127.0.0.1:<port>` (defaults to 9091 if
+/// `None`). A no-op (returns the existing port) if already running.
+pub async fn start(app_handle: AppHandle, port: Option<u16>) -> Result<u16, String> {
+    if let Some(existing) = running_port() {
+        return Ok(existing);
+    }
+
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let state = MetricsServerState { app_handle };
+
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to bind metrics server to 127.0.0.1:{}: {}", port, e))?;
+    let bound_port = listener.local_addr().map_err(|e| format!("Failed to read bound address: {}", e))?.port();
+
+    let (tx, rx) = oneshot::channel();
+    *SHUTDOWN_TX.lock().unwrap() = Some(tx);
+    *RUNNING_PORT.lock().unwrap() = Some(bound_port);
+
+    println!("[metrics-server] Serving /metrics on 127.0.0.1:{}", bound_port);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                rx.await.ok();
+            })
+            .await
+        {
+            eprintln!("[metrics-server] Server error: {}", e);
+        }
+        *RUNNING_PORT.lock().unwrap() = None;
+    });
+
+    Ok(bound_port)
+}
+
+/// Stop the metrics server if it's running.
+pub fn stop() {
+    if let Some(tx) = SHUTDOWN_TX.lock().unwrap().take() {
+        let _ = tx.send(());
+    }
+    *RUNNING_PORT.lock().unwrap() = None;
+}
+
+async fn metrics_handler(State(state): State<MetricsServerState>) -> String {
+    let app_state = state.app_handle.state::<AppState>();
+    match crate::commands::cluster::get_cluster_cockpit(app_state).await {
+        Ok(data) => render_prometheus(&data),
+        Err(e) => format!(
+            "# error computing cluster metrics: {}\nopspilot_metrics_available 0\n",
+            e.replace('\n', " ")
+        ),
+    }
+}
+
+fn render_prometheus(data: &ClusterCockpitData) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# HELP opspilot_cluster_cpu_usage_millicores Cluster-wide CPU usage in millicores");
+    let _ = writeln!(out, "# TYPE opspilot_cluster_cpu_usage_millicores gauge");
+    let _ = writeln!(out, "opspilot_cluster_cpu_usage_millicores {}", data.total_cpu_usage);
+
+    let _ = writeln!(out, "# HELP opspilot_cluster_memory_usage_bytes Cluster-wide memory usage in bytes");
+    let _ = writeln!(out, "# TYPE opspilot_cluster_memory_usage_bytes gauge");
+    let _ = writeln!(out, "opspilot_cluster_memory_usage_bytes {}", data.total_memory_usage);
+
+    let _ = writeln!(out, "# HELP opspilot_pods_by_phase Number of pods in each phase");
+    let _ = writeln!(out, "# TYPE opspilot_pods_by_phase gauge");
+    let _ = writeln!(out, "opspilot_pods_by_phase{{phase=\"running\"}} {}", data.pod_status.running);
+    let _ = writeln!(out, "opspilot_pods_by_phase{{phase=\"pending\"}} {}", data.pod_status.pending);
+    let _ = writeln!(out, "opspilot_pods_by_phase{{phase=\"succeeded\"}} {}", data.pod_status.succeeded);
+    let _ = writeln!(out, "opspilot_pods_by_phase{{phase=\"failed\"}} {}", data.pod_status.failed);
+    let _ = writeln!(out, "opspilot_pods_by_phase{{phase=\"unknown\"}} {}", data.pod_status.unknown);
+
+    let _ = writeln!(out, "# HELP opspilot_node_cpu_usage_millicores Per-node CPU usage in millicores");
+    let _ = writeln!(out, "# TYPE opspilot_node_cpu_usage_millicores gauge");
+    for node in &data.nodes {
+        let _ = writeln!(out, "opspilot_node_cpu_usage_millicores{{node=\"{}\"}} {}", escape_label(&node.name), node.cpu_usage);
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP opspilot_metrics_available Whether the Kubernetes metrics-server API was reachable (1) or the cockpit fell back to pod-request estimates (0)"
+    );
+    let _ = writeln!(out, "# TYPE opspilot_metrics_available gauge");
+    let _ = writeln!(out, "opspilot_metrics_available {}", if data.metrics_available { 1 } else { 0 });
+
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
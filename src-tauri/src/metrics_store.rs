@@ -0,0 +1,295 @@
+//! Embedded on-disk time-series store for the cluster metrics timeline,
+//! backed by SQLite via rusqlite. The in-memory `MetricsHistoryBuffer` in
+//! `AppState` stays the hot path for the cockpit's own 15s cache; this store
+//! is what survives app restarts and context switches, and is the source of
+//! truth for `get_metrics_history`/`get_metrics_history_range`.
+//!
+//! Raw 30s samples are kept for the last hour, then downsampled into 5-minute
+//! buckets (kept for a week) and finally 1-hour buckets (kept for ~3 months)
+//! so the UI can zoom out over long windows without loading thousands of raw
+//! rows. `run_compaction` performs the rollup+prune and is called on a timer
+//! from a background task started in `lib.rs`.
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::models::ClusterMetricsSnapshot;
+
+const RAW_RETENTION_SECS: i64 = 60 * 60; // 1 hour
+const FIVE_MIN_RETENTION_SECS: i64 = 7 * 24 * 60 * 60; // 1 week
+const HOURLY_RETENTION_SECS: i64 = 90 * 24 * 60 * 60; // ~3 months
+const FIVE_MIN_BUCKET_SECS: i64 = 5 * 60;
+const HOURLY_BUCKET_SECS: i64 = 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsResolution {
+    Raw,
+    FiveMinute,
+    Hourly,
+}
+
+impl MetricsResolution {
+    fn table(self) -> &'static str {
+        match self {
+            MetricsResolution::Raw => "metrics_raw",
+            MetricsResolution::FiveMinute => "metrics_5m",
+            MetricsResolution::Hourly => "metrics_1h",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "raw" => Ok(MetricsResolution::Raw),
+            "five_minute" | "5m" => Ok(MetricsResolution::FiveMinute),
+            "hourly" | "1h" => Ok(MetricsResolution::Hourly),
+            other => Err(format!("Unknown metrics resolution: {}", other)),
+        }
+    }
+}
+
+/// A row of (possibly aggregated) metrics history. At `Raw` resolution the
+/// `_min`/`_max` fields equal the `avg` fields since each row is a single
+/// sample.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsRollupRow {
+    pub timestamp: i64,
+    pub total_nodes: usize,
+    pub healthy_nodes: usize,
+    pub total_pods: usize,
+    pub running_pods: usize,
+    pub pending_pods: usize,
+    pub failed_pods: usize,
+    pub total_deployments: usize,
+    pub unhealthy_deployments: usize,
+    pub cpu_usage_percent: f64,
+    pub cpu_usage_percent_min: f64,
+    pub cpu_usage_percent_max: f64,
+    pub memory_usage_percent: f64,
+    pub memory_usage_percent_min: f64,
+    pub memory_usage_percent_max: f64,
+}
+
+impl From<MetricsRollupRow> for ClusterMetricsSnapshot {
+    fn from(row: MetricsRollupRow) -> Self {
+        ClusterMetricsSnapshot {
+            timestamp: row.timestamp,
+            total_nodes: row.total_nodes,
+            healthy_nodes: row.healthy_nodes,
+            total_pods: row.total_pods,
+            running_pods: row.running_pods,
+            pending_pods: row.pending_pods,
+            failed_pods: row.failed_pods,
+            total_deployments: row.total_deployments,
+            unhealthy_deployments: row.unhealthy_deployments,
+            cpu_usage_percent: row.cpu_usage_percent,
+            memory_usage_percent: row.memory_usage_percent,
+        }
+    }
+}
+
+pub fn now_secs() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+fn db_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".opspilot").join("metrics.db"))
+}
+
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().map_err(|e| format!("Metrics store lock poisoned: {}", e))?;
+
+    if guard.is_none() {
+        let path = db_path().ok_or("Could not determine home directory for metrics store")?;
+        if let Some(parent) = path.parent() {
+            fs_create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&path).map_err(|e| format!("Failed to open metrics store: {}", e))?;
+        init_schema(&conn).map_err(|e| format!("Failed to initialize metrics store schema: {}", e))?;
+        *guard = Some(conn);
+    }
+
+    f(guard.as_ref().unwrap()).map_err(|e| format!("Metrics store query failed: {}", e))
+}
+
+fn fs_create_dir_all(dir: &std::path::Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create metrics store directory: {}", e))
+}
+
+fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    for table in [MetricsResolution::Raw.table(), MetricsResolution::FiveMinute.table(), MetricsResolution::Hourly.table()] {
+        conn.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {table} (
+                    context TEXT NOT NULL,
+                    timestamp INTEGER NOT NULL,
+                    total_nodes INTEGER NOT NULL,
+                    healthy_nodes INTEGER NOT NULL,
+                    total_pods INTEGER NOT NULL,
+                    running_pods INTEGER NOT NULL,
+                    pending_pods INTEGER NOT NULL,
+                    failed_pods INTEGER NOT NULL,
+                    total_deployments INTEGER NOT NULL,
+                    unhealthy_deployments INTEGER NOT NULL DEFAULT 0,
+                    cpu_usage_percent REAL NOT NULL,
+                    cpu_usage_percent_min REAL NOT NULL,
+                    cpu_usage_percent_max REAL NOT NULL,
+                    memory_usage_percent REAL NOT NULL,
+                    memory_usage_percent_min REAL NOT NULL,
+                    memory_usage_percent_max REAL NOT NULL,
+                    PRIMARY KEY (context, timestamp)
+                )"
+            ),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Insert a raw snapshot into the store. Intended to be called via
+/// `insert_snapshot_async` so the cockpit's write path doesn't block on disk
+/// I/O.
+pub fn insert_snapshot(context: &str, snapshot: &ClusterMetricsSnapshot) -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO metrics_raw (
+                context, timestamp, total_nodes, healthy_nodes, total_pods, running_pods,
+                pending_pods, failed_pods, total_deployments, unhealthy_deployments,
+                cpu_usage_percent, cpu_usage_percent_min, cpu_usage_percent_max,
+                memory_usage_percent, memory_usage_percent_min, memory_usage_percent_max
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?11, ?11, ?12, ?12, ?12)",
+            params![
+                context,
+                snapshot.timestamp,
+                snapshot.total_nodes as i64,
+                snapshot.healthy_nodes as i64,
+                snapshot.total_pods as i64,
+                snapshot.running_pods as i64,
+                snapshot.pending_pods as i64,
+                snapshot.failed_pods as i64,
+                snapshot.total_deployments as i64,
+                snapshot.unhealthy_deployments as i64,
+                snapshot.cpu_usage_percent,
+                snapshot.memory_usage_percent,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Fire-and-forget version of `insert_snapshot` for use on the cockpit's hot
+/// write path.
+pub fn insert_snapshot_async(context: String, snapshot: ClusterMetricsSnapshot) {
+    tokio::spawn(async move {
+        if let Err(e) = insert_snapshot(&context, &snapshot) {
+            eprintln!("[metrics-store] Failed to persist snapshot: {}", e);
+        }
+    });
+}
+
+/// Read rows for `context` in `[from_ts, to_ts]` at the given resolution,
+/// ordered oldest-first.
+pub fn query_range(context: &str, from_ts: i64, to_ts: i64, resolution: MetricsResolution) -> Result<Vec<MetricsRollupRow>, String> {
+    with_connection(|conn| {
+        let sql = format!(
+            "SELECT timestamp, total_nodes, healthy_nodes, total_pods, running_pods, pending_pods,
+                    failed_pods, total_deployments, unhealthy_deployments, cpu_usage_percent,
+                    cpu_usage_percent_min, cpu_usage_percent_max, memory_usage_percent,
+                    memory_usage_percent_min, memory_usage_percent_max
+             FROM {}
+             WHERE context = ?1 AND timestamp BETWEEN ?2 AND ?3
+             ORDER BY timestamp ASC",
+            resolution.table()
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![context, from_ts, to_ts], |row| {
+            Ok(MetricsRollupRow {
+                timestamp: row.get(0)?,
+                total_nodes: row.get::<_, i64>(1)? as usize,
+                healthy_nodes: row.get::<_, i64>(2)? as usize,
+                total_pods: row.get::<_, i64>(3)? as usize,
+                running_pods: row.get::<_, i64>(4)? as usize,
+                pending_pods: row.get::<_, i64>(5)? as usize,
+                failed_pods: row.get::<_, i64>(6)? as usize,
+                total_deployments: row.get::<_, i64>(7)? as usize,
+                unhealthy_deployments: row.get::<_, i64>(8)? as usize,
+                cpu_usage_percent: row.get(9)?,
+                cpu_usage_percent_min: row.get(10)?,
+                cpu_usage_percent_max: row.get(11)?,
+                memory_usage_percent: row.get(12)?,
+                memory_usage_percent_min: row.get(13)?,
+                memory_usage_percent_max: row.get(14)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    })
+}
+
+/// Roll up raw rows older than an hour into 5-minute buckets, roll up
+/// 5-minute rows older than a week into 1-hour buckets, then prune anything
+/// past the hourly retention window. Safe to call repeatedly; buckets are
+/// upserted so a partially-aggregated bucket just gets recomputed.
+pub fn run_compaction() -> Result<(), String> {
+    let now = now_secs();
+    with_connection(|conn| {
+        rollup(conn, "metrics_raw", "metrics_5m", FIVE_MIN_BUCKET_SECS, now - RAW_RETENTION_SECS)?;
+        rollup(conn, "metrics_5m", "metrics_1h", HOURLY_BUCKET_SECS, now - FIVE_MIN_RETENTION_SECS)?;
+        conn.execute("DELETE FROM metrics_1h WHERE timestamp < ?1", params![now - HOURLY_RETENTION_SECS])?;
+        Ok(())
+    })
+}
+
+/// Aggregate every row in `from_table` older than `older_than_ts` into
+/// `bucket_secs`-wide buckets in `into_table`, then delete the source rows.
+fn rollup(conn: &Connection, from_table: &str, into_table: &str, bucket_secs: i64, older_than_ts: i64) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO {into_table} (
+                context, timestamp, total_nodes, healthy_nodes, total_pods, running_pods,
+                pending_pods, failed_pods, total_deployments, unhealthy_deployments,
+                cpu_usage_percent, cpu_usage_percent_min, cpu_usage_percent_max,
+                memory_usage_percent, memory_usage_percent_min, memory_usage_percent_max
+            )
+            SELECT
+                context,
+                (timestamp / {bucket_secs}) * {bucket_secs} AS bucket,
+                CAST(AVG(total_nodes) AS INTEGER), CAST(AVG(healthy_nodes) AS INTEGER),
+                SUM(total_pods),
+                SUM(running_pods), SUM(pending_pods), SUM(failed_pods),
+                CAST(AVG(total_deployments) AS INTEGER), CAST(AVG(unhealthy_deployments) AS INTEGER),
+                AVG(cpu_usage_percent), MIN(cpu_usage_percent_min), MAX(cpu_usage_percent_max),
+                AVG(memory_usage_percent), MIN(memory_usage_percent_min), MAX(memory_usage_percent_max)
+            FROM {from_table}
+            WHERE timestamp < ?1
+            GROUP BY context, bucket"
+        ),
+        params![older_than_ts],
+    )?;
+
+    conn.execute(&format!("DELETE FROM {from_table} WHERE timestamp < ?1"), params![older_than_ts])?;
+
+    Ok(())
+}
+
+/// Background compaction loop: runs `run_compaction` every 5 minutes for the
+/// lifetime of the process. Spawn with `tauri::async_runtime::spawn` at
+/// startup, same as the other background tasks in `lib.rs`.
+pub async fn run_compaction_loop() {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+    loop {
+        interval.tick().await;
+        if let Err(e) = run_compaction() {
+            eprintln!("[metrics-store] Compaction failed: {}", e);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 
 #![allow(dead_code)]
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct NavGroup {
@@ -18,6 +18,26 @@ pub struct NavResource {
     pub title: String,
 }
 
+/// Result of one context's turn in the nav-structure sweep, persisted
+/// alongside the rest of the `.kube/cache/opspilot/` cache files.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ContextSweepProgress {
+    pub context: String,
+    pub last_swept_unix: i64,
+    pub last_duration_ms: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Persisted + live-queryable state of the nav-structure sweep: the
+/// tranquility multiplier governing how long it sleeps between contexts, and
+/// the most recent outcome per context.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NavSweepStatus {
+    pub tranquility: f64,
+    pub contexts: Vec<ContextSweepProgress>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct ResourceRequest {
     pub group: String,
@@ -29,6 +49,23 @@ pub struct ResourceRequest {
     pub include_raw: Option<bool>,
 }
 
+/// A user-registered status extraction rule for one GVK, consulted by
+/// `extract_status` before its built-in heuristics. Lets custom operators
+/// whose CRDs expose status under a nonstandard key (or as a specific
+/// condition, e.g. `conditions[type=Ready].status`) still render a sensible
+/// status column instead of falling through to "-".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusRule {
+    /// Dot-separated path rooted at `status`, e.g. "phase" or
+    /// "conditions[type=Ready].status" (the bracket form picks the array
+    /// element whose `type` field equals "Ready").
+    pub path: String,
+    /// Optional raw extracted value -> display value remap, e.g.
+    /// {"True": "Ready", "False": "NotReady"}.
+    #[serde(default)]
+    pub value_map: HashMap<String, String>,
+}
+
 #[derive(Serialize, Clone)]
 pub struct ResourceSummary {
     pub id: String,
@@ -52,6 +89,40 @@ pub struct ResourceSummary {
     pub labels: Option<BTreeMap<String, String>>,
 }
 
+impl ResourceSummary {
+    /// Stable hash over the fields that represent this resource's observable
+    /// state (not `raw_json`, which is a display convenience and often left
+    /// empty by the list-building code paths). Two calls with unchanged
+    /// state produce the same hash, so callers can skip re-rendering a
+    /// resource whose hash hasn't moved between polls.
+    pub fn content_hash(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.id.hash(&mut hasher);
+        self.name.hash(&mut hasher);
+        self.namespace.hash(&mut hasher);
+        self.kind.hash(&mut hasher);
+        self.group.hash(&mut hasher);
+        self.version.hash(&mut hasher);
+        self.status.hash(&mut hasher);
+        self.ready.hash(&mut hasher);
+        self.restarts.hash(&mut hasher);
+        self.node.hash(&mut hasher);
+        self.ip.hash(&mut hasher);
+        self.labels.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// The result of diffing a fresh `InitialClusterData` fetch against the
+/// previous poll's content hashes: only what's new, gone, or mutated.
+#[derive(Serialize, Clone)]
+pub struct ClusterDelta {
+    pub added: Vec<ResourceSummary>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ResourceSummary>,
+}
+
 #[derive(Serialize, Clone)]
 pub struct ResourceWatchEvent {
     pub event_type: String, // "ADDED", "MODIFIED", "DELETED", "RESTARTED"
@@ -96,8 +167,15 @@ pub struct NodeHealth {
     pub memory_capacity: u64,    // in bytes
     pub memory_allocatable: u64,
     pub memory_usage: u64,
+    pub ephemeral_storage_capacity: u64,    // in bytes
+    pub ephemeral_storage_allocatable: u64,
     pub pods_capacity: u32,
     pub pods_running: u32,
+    pub disk_pressure: bool,
+    pub memory_pressure: bool,
+    pub pid_pressure: bool,
+    pub scheduling_disabled: bool,
+    pub draining: bool,
     pub conditions: Vec<NodeCondition>,
     pub taints: Vec<String>,
 }
@@ -152,6 +230,8 @@ pub struct ClusterCockpitData {
     pub total_memory_allocatable: u64,
     pub total_memory_usage: u64,
     pub total_pods_capacity: u32,
+    pub total_ephemeral_storage_capacity: u64,   // bytes
+    pub total_ephemeral_storage_allocatable: u64,
 
     pub pod_status: PodStatusBreakdown,
     pub nodes: Vec<NodeHealth>,
@@ -202,7 +282,7 @@ pub struct DeploymentIssue {
     pub reason: String,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ClusterIssue {
     pub severity: String, // "critical" or "warning"
     pub resource_kind: String,
@@ -211,7 +291,7 @@ pub struct ClusterIssue {
     pub message: String,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ResourceCost {
     pub name: String,
     pub namespace: String,
@@ -222,9 +302,18 @@ pub struct ResourceCost {
     pub memory_cost_monthly: f64,
     pub total_cost_monthly: f64,
     pub pod_count: u32,
+    // Actual-usage figures from the metrics API, only populated when
+    // `get_cluster_cost_report` was called with `use_actual_usage: true` and
+    // metrics-server reported usage for this workload's pods.
+    pub cpu_used_cores: Option<f64>,
+    pub memory_used_gb: Option<f64>,
+    pub total_cost_used_monthly: Option<f64>,
+    // `total_cost_used_monthly / total_cost_monthly` - how much of what's
+    // requested is actually being used. Below 1.0 means over-provisioned.
+    pub efficiency: Option<f64>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct NamespaceCost {
     pub namespace: String,
     pub total_cost_monthly: f64,
@@ -235,9 +324,11 @@ pub struct NamespaceCost {
     pub pod_count: u32,
     #[serde(rename = "topResources")]
     pub top_resources: Vec<ResourceCost>,
+    pub total_cost_used_monthly: Option<f64>,
+    pub efficiency: Option<f64>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ClusterCostReport {
     pub total_cost_monthly: f64,
     pub cpu_cost_monthly: f64,
@@ -251,6 +342,10 @@ pub struct ClusterCostReport {
     pub provider: String,
     pub currency: String,
     pub generated_at: String,
+    // Cluster-wide actual-usage cost and requested-vs-used efficiency; see
+    // `ResourceCost::total_cost_used_monthly`.
+    pub total_cost_used_monthly: Option<f64>,
+    pub efficiency: Option<f64>,
 }
 
 #[derive(Serialize, Clone)]
@@ -265,12 +360,39 @@ pub struct ClusterEventSummary {
     pub event_type: String,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct UnhealthyReport {
     pub timestamp: String,
     pub issues: Vec<ClusterIssue>,
 }
 
+/// Throttling/retry knobs for the list calls behind `InitialClusterData`, so
+/// large clusters don't hammer the apiserver on every poll and a transient
+/// `429`/connection hiccup doesn't abort the whole refresh.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchConfig {
+    pub min_interval_ms: u64,
+    pub max_retries: u32,
+    pub base_backoff_ms: u64,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self { min_interval_ms: 50, max_retries: 3, base_backoff_ms: 250 }
+    }
+}
+
+impl FetchConfig {
+    pub fn min_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.min_interval_ms)
+    }
+
+    pub fn base_backoff(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.base_backoff_ms)
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct InitialClusterData {
     pub stats: ClusterStats,
@@ -279,6 +401,134 @@ pub struct InitialClusterData {
     pub nodes: Vec<ResourceSummary>,
     pub deployments: Vec<ResourceSummary>,
     pub services: Vec<ResourceSummary>,
+    pub graph: crate::cluster_graph::ClusterGraph,
+}
+
+/// Small, serializable digest of `InitialClusterData` - counts rather than
+/// full resource lists - for CLI output, alerts, or a quick health check
+/// without shipping every `ResourceSummary` over the wire.
+#[derive(Serialize, Clone)]
+pub struct ClusterSummary {
+    pub total_nodes: usize,
+    pub nodes_under_pressure: usize,
+    pub total_pods: usize,
+    pub pods_by_phase: std::collections::HashMap<String, usize>,
+    pub unhealthy_pods: usize,
+    pub total_deployments: usize,
+    pub deployments_with_unavailable_replicas: usize,
+    pub total_services: usize,
+    pub services_without_endpoints: usize,
+}
+
+impl InitialClusterData {
+    pub fn summarize(&self) -> ClusterSummary {
+        let mut pods_by_phase: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        let mut unhealthy_pods = 0;
+        for pod in &self.pods {
+            *pods_by_phase.entry(pod.status.clone()).or_insert(0) += 1;
+            if pod.status != "Running" && pod.status != "Succeeded" {
+                unhealthy_pods += 1;
+            }
+        }
+
+        let nodes_under_pressure = self.nodes.iter().filter(|n| n.status != "Ready").count();
+
+        let deployments_with_unavailable_replicas = self.deployments.iter().filter(|d| {
+            d.ready.as_ref().map(|r| {
+                match r.split_once('/') {
+                    Some((ready, desired)) => ready != desired,
+                    None => false,
+                }
+            }).unwrap_or(false)
+        }).count();
+
+        let services_without_endpoints = self.services.iter().filter(|s| {
+            let id = crate::cluster_graph::ResourceId::new("Service", &s.namespace, &s.name);
+            self.graph.pods_behind_service(&id).is_empty()
+        }).count();
+
+        ClusterSummary {
+            total_nodes: self.nodes.len(),
+            nodes_under_pressure,
+            total_pods: self.pods.len(),
+            pods_by_phase,
+            unhealthy_pods,
+            total_deployments: self.deployments.len(),
+            deployments_with_unavailable_replicas,
+            total_services: self.services.len(),
+            services_without_endpoints,
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct ClusterMetricsSnapshot {
+    pub timestamp: i64,
+    pub total_nodes: usize,
+    pub healthy_nodes: usize,
+    pub total_pods: usize,
+    pub running_pods: usize,
+    pub pending_pods: usize,
+    pub failed_pods: usize,
+    pub total_deployments: usize,
+    pub unhealthy_deployments: usize,
+    pub cpu_usage_percent: f64,
+    pub memory_usage_percent: f64,
+}
+
+/// Short in-memory ring buffer of recent snapshots for a single context,
+/// used as the cockpit's hot path. Long-term history lives in the on-disk
+/// metrics store (`metrics_store.rs`).
+pub struct MetricsHistoryBuffer {
+    pub context: String,
+    pub capacity: usize,
+    pub snapshots: Vec<ClusterMetricsSnapshot>,
+}
+
+/// Min/max/avg/p95 over a trailing window, for a single metric series.
+#[derive(Serialize, Clone)]
+pub struct SeriesStats {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub p95: f64,
+}
+
+/// A Prometheus-style `rate()` over a trailing window, expressed per-minute.
+/// `reset_detected` is set when the underlying counter decreased across the
+/// window (e.g. a context switch cleared the history) - in that case
+/// `per_minute` falls back to the raw last value rather than a misleading
+/// negative rate.
+#[derive(Serialize, Clone)]
+pub struct RateStat {
+    pub per_minute: f64,
+    pub reset_detected: bool,
+}
+
+/// Headline stats for the cockpit's sparklines over a trailing window, so
+/// the frontend doesn't have to ship and re-derive the whole raw buffer on
+/// every poll.
+#[derive(Serialize, Clone)]
+pub struct MetricsSummary {
+    pub window_secs: i64,
+    pub sample_count: usize,
+    pub cpu_usage_percent: SeriesStats,
+    pub memory_usage_percent: SeriesStats,
+    pub failed_pods_per_minute: RateStat,
+    pub unhealthy_deployments_per_minute: RateStat,
+}
+
+impl MetricsHistoryBuffer {
+    pub fn new(context: String, capacity: usize) -> Self {
+        Self { context, capacity, snapshots: Vec::with_capacity(capacity) }
+    }
+
+    pub fn push(&mut self, snapshot: ClusterMetricsSnapshot) {
+        if self.snapshots.len() >= self.capacity {
+            self.snapshots.remove(0);
+        }
+        self.snapshots.push(snapshot);
+    }
 }
 
 #[derive(Serialize, Clone)]
@@ -0,0 +1,82 @@
+//! Cloud pricing used by `commands::cost::get_cluster_cost_report`. Costs
+//! were previously hardcoded to a single set of Azure D-series constants;
+//! this makes the per-core/per-GB rates pluggable per cloud so the same
+//! report logic works regardless of where the cluster actually runs.
+
+/// A source of per-resource hourly rates. Implementations are flat constants
+/// today (no live pricing API calls), matching the "baseline VM family,
+/// East-US-equivalent region" approximation the original Azure constants
+/// used.
+pub trait PricingProvider: Send + Sync {
+    fn cpu_price_per_core_hour(&self) -> f64;
+    fn memory_price_per_gb_hour(&self) -> f64;
+    fn currency(&self) -> &'static str;
+    fn name(&self) -> &'static str;
+}
+
+/// Azure D2s v3: $0.096/hour for 2 vCPU + 8GB RAM, split roughly 4:1 between
+/// CPU and memory per the original estimate in `commands::cost`.
+pub struct AzurePricing;
+
+impl PricingProvider for AzurePricing {
+    fn cpu_price_per_core_hour(&self) -> f64 {
+        0.048
+    }
+    fn memory_price_per_gb_hour(&self) -> f64 {
+        0.006
+    }
+    fn currency(&self) -> &'static str {
+        "USD"
+    }
+    fn name(&self) -> &'static str {
+        "Azure"
+    }
+}
+
+/// AWS m5.large: $0.096/hour for 2 vCPU + 8GB RAM, same baseline shape as
+/// the Azure D-series comparable.
+pub struct AwsPricing;
+
+impl PricingProvider for AwsPricing {
+    fn cpu_price_per_core_hour(&self) -> f64 {
+        0.047
+    }
+    fn memory_price_per_gb_hour(&self) -> f64 {
+        0.0055
+    }
+    fn currency(&self) -> &'static str {
+        "USD"
+    }
+    fn name(&self) -> &'static str {
+        "AWS"
+    }
+}
+
+/// GCP e2-standard-2: $0.067/hour for 2 vCPU + 8GB RAM.
+pub struct GcpPricing;
+
+impl PricingProvider for GcpPricing {
+    fn cpu_price_per_core_hour(&self) -> f64 {
+        0.0335
+    }
+    fn memory_price_per_gb_hour(&self) -> f64 {
+        0.0045
+    }
+    fn currency(&self) -> &'static str {
+        "USD"
+    }
+    fn name(&self) -> &'static str {
+        "GCP"
+    }
+}
+
+/// Resolve a provider by name (case-insensitive), falling back to Azure for
+/// anything unrecognized so a stale/garbage config value never breaks cost
+/// reporting outright.
+pub fn provider_for(name: &str) -> Box<dyn PricingProvider> {
+    match name.to_ascii_lowercase().as_str() {
+        "aws" => Box::new(AwsPricing),
+        "gcp" => Box::new(GcpPricing),
+        _ => Box::new(AzurePricing),
+    }
+}
@@ -0,0 +1,591 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use axum::{
+    body::Body,
+    extract::{State, Request},
+    http::{HeaderValue, StatusCode, Uri},
+    response::Response,
+    routing::any,
+    Router,
+};
+use tower_http::cors::CorsLayer;
+use tokio::sync::oneshot;
+use base64::Engine;
+use sha1::{Digest, Sha1};
+
+/// One supported dashboard: the bits of `proxy_handler`'s behavior that
+/// differ per upstream. Every profile gets the same cookie-rewriting,
+/// token-gating, and WebSocket tunneling for free; only the server-side
+/// auto-login step is profile-specific, and optional.
+#[derive(Clone, Copy)]
+pub struct ProxyProfile {
+    pub id: &'static str,
+    pub display_name: &'static str,
+    /// JSON login endpoint the proxy can call server-side with the user's
+    /// credentials to mint a session token, e.g. ArgoCD's `/api/v1/session`.
+    /// `None` for dashboards with no such API - those skip auto-login
+    /// entirely and just proxy the upstream's own login page through.
+    pub login_path: Option<&'static str>,
+    /// Cookie name the upstream expects its session token back as, and the
+    /// one we check for to know auto-login has already happened.
+    pub login_cookie_name: &'static str,
+}
+
+pub const ARGOCD_PROFILE: ProxyProfile = ProxyProfile {
+    id: "argocd",
+    display_name: "ArgoCD",
+    login_path: Some("/api/v1/session"),
+    login_cookie_name: "argocd.token",
+};
+pub const GRAFANA_PROFILE: ProxyProfile = ProxyProfile {
+    id: "grafana",
+    display_name: "Grafana",
+    login_path: None,
+    login_cookie_name: "grafana_session",
+};
+pub const PROMETHEUS_PROFILE: ProxyProfile = ProxyProfile {
+    id: "prometheus",
+    display_name: "Prometheus",
+    login_path: None,
+    login_cookie_name: "",
+};
+pub const KIALI_PROFILE: ProxyProfile = ProxyProfile {
+    id: "kiali",
+    display_name: "Kiali",
+    login_path: None,
+    login_cookie_name: "",
+};
+
+/// Every profile shipped out of the box, keyed by `ProxyProfile::id`.
+pub fn builtin_profile(id: &str) -> Option<ProxyProfile> {
+    match id {
+        "argocd" => Some(ARGOCD_PROFILE),
+        "grafana" => Some(GRAFANA_PROFILE),
+        "prometheus" => Some(PROMETHEUS_PROFILE),
+        "kiali" => Some(KIALI_PROFILE),
+        _ => None,
+    }
+}
+
+// Shared state to hold the target dashboard's port and client. This allows
+// us to update the target if the underlying port-forward restarts.
+#[derive(Clone)]
+pub struct ProxyState {
+    pub target_port: Arc<Mutex<Option<u16>>>,
+    pub protocol: String, // "http" or "https"
+    pub client: reqwest::Client,
+    pub profile: ProxyProfile,
+    /// Username/password for profiles with a `login_path`; `None` for
+    /// dashboards with no server-side login step.
+    pub creds: Option<(String, String)>,
+    /// Opaque per-instance token gating access to this proxy's localhost
+    /// port. Required as `?t=` on the first navigation, after which it's
+    /// carried as an `HttpOnly` cookie - see `proxy_handler`.
+    pub auth_token: String,
+}
+
+const AUTH_COOKIE: &str = "op_proxy_auth";
+
+/// 32 random bytes, base64-encoded. Reuses the crate's established
+/// UUID-based token minting (see `control_socket::write_token`) rather than
+/// pulling in a dedicated CSPRNG crate just for this.
+fn generate_auth_token() -> String {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    bytes[16..].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Log in to the upstream's `login_path` server-side with the stored
+/// credentials and return its session token, so the credentials never leave
+/// this process or reach the browser. Only called when
+/// `state.profile.login_path` and `state.creds` are both set.
+async fn fetch_session_token(state: &ProxyState, target_port: u16) -> Result<String, String> {
+    #[derive(serde::Serialize)]
+    struct LoginRequest<'a> {
+        username: &'a str,
+        password: &'a str,
+    }
+    #[derive(serde::Deserialize)]
+    struct LoginResponse {
+        token: String,
+    }
+
+    let login_path = state.profile.login_path.ok_or("profile has no login endpoint")?;
+    let (username, password) = state.creds.as_ref().ok_or("no credentials configured for this proxy")?;
+
+    let url = format!("{}://localhost:{}{}", state.protocol, target_port, login_path);
+    let response = state
+        .client
+        .post(&url)
+        .json(&LoginRequest { username, password })
+        .send()
+        .await
+        .map_err(|e| format!("{} login request failed: {}", state.profile.display_name, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("{} login rejected with status {}", state.profile.display_name, response.status()));
+    }
+
+    response
+        .json::<LoginResponse>()
+        .await
+        .map(|r| r.token)
+        .map_err(|e| format!("{} login response had no token: {}", state.profile.display_name, e))
+}
+
+/// Caller-supplied identifier for one running proxy instance, e.g. a
+/// `(namespace, service)` pair. Lets more than one dashboard be proxied at
+/// once instead of every `start_proxy` call fighting over a single global
+/// instance.
+pub type ProxyId = String;
+
+/// Everything needed to stop a running proxy and report its local port back
+/// to the caller. Held in `PROXY_REGISTRY`, one per `ProxyId`.
+struct ProxyHandle {
+    local_port: u16,
+    auth_token: String,
+    shutdown_tx: oneshot::Sender<()>,
+}
+
+/// Every proxy instance currently running, keyed by caller-supplied id.
+/// Replaces the old single-instance `SHUTDOWN_TX`/`RUNNING_PORT` statics so
+/// a second `start_proxy` call for a different target no longer just hands
+/// back the first instance's port.
+static PROXY_REGISTRY: LazyLock<Mutex<HashMap<ProxyId, ProxyHandle>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the local port the proxy is listening on and the opaque auth
+/// token callers must present (as `?t=<token>` on first navigation) to use
+/// it - see `AUTH_COOKIE`.
+pub async fn start_proxy(
+    id: &str,
+    target_port_upstream: u16,
+    protocol: &str,
+    profile: ProxyProfile,
+    creds: Option<(String, String)>,
+) -> Result<(u16, String), String> {
+    {
+        let registry = PROXY_REGISTRY.lock().unwrap();
+        if let Some(handle) = registry.get(id) {
+            println!("[WebUI Proxy] '{}' already running on port {}", id, handle.local_port);
+            return Ok((handle.local_port, handle.auth_token.clone()));
+        }
+    }
+
+    // Create a shared client efficiently
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .redirect(reqwest::redirect::Policy::none()) // Don't follow redirects automatically, let browser handle
+        .build()
+        .map_err(|e| format!("Failed to build proxy client: {}", e))?;
+
+    let auth_token = generate_auth_token();
+    let state = ProxyState {
+        target_port: Arc::new(Mutex::new(Some(target_port_upstream))),
+        protocol: protocol.to_string(),
+        client,
+        profile,
+        creds,
+        auth_token: auth_token.clone(),
+    };
+
+    let app = Router::new()
+        .route("/", any(proxy_handler))
+        .route("/{*path}", any(proxy_handler))
+        .layer(CorsLayer::permissive())
+        .with_state(state);
+
+    // Bind to port 0 to let OS choose a free port
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("Failed to bind proxy: {}", e))?;
+
+    let port = listener.local_addr().unwrap().port();
+
+    // Setup shutdown channel
+    let (tx, rx) = oneshot::channel();
+    {
+        let mut registry = PROXY_REGISTRY.lock().unwrap();
+        registry.insert(id.to_string(), ProxyHandle { local_port: port, auth_token: auth_token.clone(), shutdown_tx: tx });
+    }
+
+    println!("[WebUI Proxy] Started '{}' ({}) HTTP->{} proxy on 127.0.0.1:{} -> target:{}", id, profile.display_name, protocol, port, target_port_upstream);
+
+    // Spawn server in background
+    let id = id.to_string();
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                rx.await.ok();
+            })
+            .await
+        {
+            eprintln!("[WebUI Proxy] Server error: {}", e);
+        }
+
+        // Cleanup on exit
+        PROXY_REGISTRY.lock().unwrap().remove(&id);
+    });
+
+    Ok((port, auth_token))
+}
+
+pub fn stop_proxy(id: &str) {
+    if let Some(handle) = PROXY_REGISTRY.lock().unwrap().remove(id) {
+        let _ = handle.shutdown_tx.send(());
+    }
+}
+
+/// Reads a single cookie value out of the request's `Cookie` header.
+fn cookie_value(req: &Request, name: &str) -> Option<String> {
+    let raw = req.headers().get("cookie")?.to_str().ok()?;
+    raw.split(';').find_map(|pair| {
+        let (k, v) = pair.trim().split_once('=')?;
+        (k == name).then(|| v.to_string())
+    })
+}
+
+/// Drops the `t=<token>` query param before the request is forwarded
+/// upstream, so the one-time auth token never reaches the proxied dashboard.
+fn strip_auth_token_param(query: &str) -> String {
+    let pairs: Vec<&str> = query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|p| !p.is_empty() && !p.starts_with("t="))
+        .collect();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+async fn proxy_handler(
+    State(state): State<ProxyState>,
+    mut req: Request,
+) -> Result<Response, StatusCode> {
+    let path = req.uri().path().to_string();
+    let raw_query = req.uri().query().map(|q| format!("?{}", q)).unwrap_or_default();
+
+    // Get target port
+    let target_port = {
+        let guard = state.target_port.lock().unwrap();
+        if let Some(p) = *guard {
+            p
+        } else {
+            eprintln!("[WebUI Proxy] Error: Target port not set");
+            return Err(StatusCode::SERVICE_UNAVAILABLE);
+        }
+    };
+
+    // Require the per-instance token on every request: either as the
+    // `HttpOnly` cookie set below on the first successful navigation, or as
+    // a `?t=` query param on that first navigation itself.
+    let query_token = raw_query
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|p| p.strip_prefix("t=").map(str::to_string));
+    let authorized_via_cookie = cookie_value(&req, AUTH_COOKIE).as_deref() == Some(state.auth_token.as_str());
+    let authorized_via_query = query_token.as_deref() == Some(state.auth_token.as_str());
+    if !authorized_via_cookie && !authorized_via_query {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    // First navigation with the token in the URL: mint the auth cookie and,
+    // if this profile has a login endpoint, silently log in upstream so the
+    // browser never sees the credentials.
+    let needs_login = authorized_via_query
+        && !authorized_via_cookie
+        && state.profile.login_path.is_some()
+        && state.creds.is_some()
+        && cookie_value(&req, state.profile.login_cookie_name).is_none();
+    let query = strip_auth_token_param(&raw_query);
+
+    let is_websocket_upgrade = req
+        .headers()
+        .get("upgrade")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false);
+
+    if is_websocket_upgrade {
+        return proxy_websocket(state, req, target_port, &path, &query).await;
+    }
+
+    let uri_string = format!("{}://localhost:{}{}{}", state.protocol, target_port, path, query);
+
+    println!("[WebUI Proxy] Forwarding: {} -> {}", path, uri_string);
+
+    let url = uri_string.parse::<Uri>().map_err(|e| {
+        eprintln!("[WebUI Proxy] Invalid URI constructed: {} ({})", uri_string, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    *req.uri_mut() = url;
+    
+    // Remove host header so reqwest calculates it
+    let method = req.method().clone();
+    let mut headers = req.headers().clone();
+    headers.remove("host");
+    headers.remove("connection"); // Avoid 'connection: close' issues?
+    headers.remove("accept-encoding"); // Let reqwest negotiate compression, or just get plain text
+    
+    // Create request with body and headers
+    let body_bytes = axum::body::to_bytes(req.into_body(), 100 * 1024 * 1024).await // 100MB limit
+        .map_err(|_e| {
+            eprintln!("[WebUI Proxy] Failed to read request body");
+            StatusCode::BAD_REQUEST
+        })?;
+
+    let request_builder = state.client.request(method.clone(), uri_string.clone())
+        .headers(headers)
+        .body(body_bytes);
+
+    let response = request_builder.send().await
+        .map_err(|e| {
+            eprintln!("[WebUI Proxy] Upstream Request Error: {} ({} {})", e, method, uri_string);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let status = response.status();
+    let resp_headers = response.headers().clone();
+
+    // Check content type to decide whether to inject script
+    let content_type = resp_headers.get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+    
+    let is_html = content_type.contains("text/html");
+
+    println!("[WebUI Proxy] Upstream Response: {} {} [HTML={}]", method, status, is_html);
+
+    // Build response
+    let mut builder = Response::builder().status(status);
+    
+    // Copy headers but STRIP hop-by-hop and blocking headers
+    if let Some(headers_mut) = builder.headers_mut() {
+        for (name, value) in resp_headers {
+            if let Some(name) = name {
+                let name_lower = name.as_str().to_lowercase();
+                // Standard hop-by-hop headers + Proxy specific
+                if name_lower == "connection"
+                   || name_lower == "keep-alive"
+                   || name_lower == "proxy-authenticate"
+                   || name_lower == "proxy-authorization"
+                   || name_lower == "te"
+                   || name_lower == "trailer"
+                   || name_lower == "transfer-encoding"
+                   || name_lower == "upgrade"
+                   || name_lower == "content-length" 
+                   || name_lower == "content-encoding" // Let browser handle decoding if any
+                   || name_lower == "x-frame-options"
+                   || name_lower == "content-security-policy"
+                {
+                    continue;
+                }
+                
+                // IMPORTANT: Handle Cookies for HTTP Proxy
+                // ArgoCD sends "Secure; SameSite=None" which requires HTTPS.
+                // Since we proxy over HTTP (localhost), we must strip "Secure".
+                // And "SameSite=None" requires "Secure", so we must strip that too (reverting to Lax/Default).
+                if name_lower == "set-cookie" {
+                    if let Ok(v_str) = value.to_str() {
+                        let new_val = v_str
+                            .replace("; Secure", "")
+                            .replace("; SameSite=None", "");
+                        
+                        println!("[WebUI Proxy] Rewrote Cookie: {} -> {}", v_str, new_val);
+                        
+                        if let Ok(hv) = HeaderValue::from_str(&new_val) {
+                             headers_mut.insert(name, hv);
+                             continue;
+                        }
+                    }
+                }
+
+                headers_mut.insert(name, value);
+            }
+        }
+        // Force permissive headers
+        headers_mut.insert("Access-Control-Allow-Origin", HeaderValue::from_static("*"));
+
+        if authorized_via_query {
+            if let Ok(hv) = HeaderValue::from_str(&format!("{}={}; HttpOnly; Path=/; SameSite=Lax", AUTH_COOKIE, state.auth_token)) {
+                headers_mut.append("set-cookie", hv);
+            }
+        }
+        if needs_login {
+            match fetch_session_token(&state, target_port).await {
+                Ok(token) => {
+                    if let Ok(hv) = HeaderValue::from_str(&format!("{}={}; Path=/; SameSite=Lax", state.profile.login_cookie_name, token)) {
+                        headers_mut.append("set-cookie", hv);
+                    }
+                }
+                Err(e) => eprintln!("[WebUI Proxy] Server-side auto-login failed: {}", e),
+            }
+        }
+    }
+
+    if is_html {
+        // The browser is already authenticated via the upstream's own
+        // session cookie set above (when the profile supports it), so
+        // there's nothing left to inject here - just buffer and pass the
+        // page through untouched (still buffered rather than streamed, since a
+        // future request may need to rewrite it).
+        let body_bytes = response.bytes().await.map_err(|e| {
+            eprintln!("[WebUI Proxy] Failed to buffer HTML body: {}", e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+        builder.body(Body::from(body_bytes))
+             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+
+    } else {
+        // Non-HTML: Stream as before
+        use futures::TryStreamExt;
+        let stream = response.bytes_stream().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+        let body = Body::from_stream(stream);
+    
+        builder.body(body)
+            .map_err(|e| {
+                 eprintln!("[WebUI Proxy] Failed to build response: {}", e);
+                 StatusCode::INTERNAL_SERVER_ERROR
+            })
+    }
+}
+
+/// RFC 6455 `Sec-WebSocket-Accept` value for the given client `Sec-WebSocket-Key`.
+fn websocket_accept_key(client_key: &str) -> String {
+    const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+    let mut hasher = Sha1::new();
+    hasher.update(client_key.as_bytes());
+    hasher.update(WS_GUID.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+}
+
+/// ArgoCD's live application/resource views and pod terminals, and Grafana's
+/// live dashboards, run over WebSocket. The buffered HTTP path above can't
+/// carry those, so on an
+/// `Upgrade: websocket` request we take the connection's `OnUpgrade` instead,
+/// open our own WebSocket connection to the same rewritten target, and
+/// splice the two together frame-for-frame until either side closes.
+async fn proxy_websocket(
+    state: ProxyState,
+    mut req: Request,
+    target_port: u16,
+    path: &str,
+    query: &str,
+) -> Result<Response, StatusCode> {
+    let client_key = req
+        .headers()
+        .get("sec-websocket-key")
+        .and_then(|v| v.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?
+        .to_string();
+    let subprotocol = req
+        .headers()
+        .get("sec-websocket-protocol")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let ws_scheme = if state.protocol == "https" { "wss" } else { "ws" };
+    let upstream_url = format!("{}://localhost:{}{}{}", ws_scheme, target_port, path, query);
+
+    let on_upgrade = hyper::upgrade::on(&mut req);
+
+    let mut upstream_request = tokio_tungstenite::tungstenite::handshake::client::Request::builder()
+        .uri(&upstream_url)
+        .method("GET");
+    if let Some(ref proto) = subprotocol {
+        upstream_request = upstream_request.header("Sec-WebSocket-Protocol", proto);
+    }
+    let upstream_request = upstream_request
+        .body(())
+        .map_err(|e| {
+            eprintln!("[WebUI Proxy] Failed to build upstream WS request {}: {}", upstream_url, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let connector = if ws_scheme == "wss" {
+        Some(tokio_tungstenite::Connector::NativeTls(
+            native_tls::TlsConnector::builder()
+                .danger_accept_invalid_certs(true)
+                .build()
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        ))
+    } else {
+        None
+    };
+
+    println!("[WebUI Proxy] Upgrading WebSocket: {} -> {}", path, upstream_url);
+
+    tokio::spawn(async move {
+        let (upstream, _response) = match tokio_tungstenite::connect_async_tls_with_config(
+            upstream_request,
+            None,
+            false,
+            connector,
+        )
+        .await
+        {
+            Ok(pair) => pair,
+            Err(e) => {
+                eprintln!("[WebUI Proxy] Upstream WS connect failed {}: {}", upstream_url, e);
+                return;
+            }
+        };
+
+        let client_io = match on_upgrade.await {
+            Ok(io) => io,
+            Err(e) => {
+                eprintln!("[WebUI Proxy] Client WS upgrade failed: {}", e);
+                return;
+            }
+        };
+        let client_ws = tokio_tungstenite::WebSocketStream::from_raw_socket(
+            hyper_util::rt::TokioIo::new(client_io),
+            tokio_tungstenite::tungstenite::protocol::Role::Server,
+            None,
+        )
+        .await;
+
+        let (mut client_write, mut client_read) = futures::StreamExt::split(client_ws);
+        let (mut upstream_write, mut upstream_read) = futures::StreamExt::split(upstream);
+
+        let client_to_upstream = async {
+            while let Some(Ok(msg)) = futures::StreamExt::next(&mut client_read).await {
+                let closed = msg.is_close();
+                if futures::SinkExt::send(&mut upstream_write, msg).await.is_err() || closed {
+                    break;
+                }
+            }
+        };
+        let upstream_to_client = async {
+            while let Some(Ok(msg)) = futures::StreamExt::next(&mut upstream_read).await {
+                let closed = msg.is_close();
+                if futures::SinkExt::send(&mut client_write, msg).await.is_err() || closed {
+                    break;
+                }
+            }
+        };
+
+        tokio::select! {
+            _ = client_to_upstream => {}
+            _ = upstream_to_client => {}
+        }
+    });
+
+    let mut builder = Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Accept", websocket_accept_key(&client_key));
+    if let Some(proto) = subprotocol {
+        builder = builder.header("Sec-WebSocket-Protocol", proto);
+    }
+
+    builder
+        .body(Body::empty())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
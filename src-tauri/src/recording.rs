@@ -0,0 +1,76 @@
+//! Asciicast v2 writer for PTY sessions (see `commands::terminal`). A
+//! recording is a plain-text file: one JSON header line followed by one
+//! `[elapsed_seconds, "o", chunk]` event per emitted output chunk and
+//! `[elapsed_seconds, "r", "{cols}x{rows}"]` per resize - the format
+//! https://docs.asciinema.org/manual/asciicast/v2/ expects for playback.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Terminal size a recording starts with when the caller doesn't track one
+/// for the session (`start_terminal_agent`'s `cols`/`rows` aren't persisted
+/// anywhere `start_recording` can read them back from). The first resize
+/// event corrects this for playback, same as a real terminal reflowing on
+/// its first `SIGWINCH`.
+const DEFAULT_COLS: u16 = 80;
+const DEFAULT_ROWS: u16 = 24;
+
+pub struct Recording {
+    file: Mutex<File>,
+    started_at: Instant,
+    pub path: PathBuf,
+}
+
+impl Recording {
+    pub fn start(path: PathBuf, cols: Option<u16>, rows: Option<u16>) -> Result<Self, String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to create recording file '{}': {}", path.display(), e))?;
+
+        let header = serde_json::json!({
+            "version": 2,
+            "width": cols.unwrap_or(DEFAULT_COLS),
+            "height": rows.unwrap_or(DEFAULT_ROWS),
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        });
+        writeln!(file, "{}", header).map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+            started_at: Instant::now(),
+            path,
+        })
+    }
+
+    fn elapsed(&self) -> f64 {
+        self.started_at.elapsed().as_secs_f64()
+    }
+
+    /// Appends an `"o"` (output) event. Called from the PTY reader loop for
+    /// every chunk it decodes and emits to the frontend, so a recording is
+    /// always byte-for-byte what the user saw.
+    pub fn record_output(&self, chunk: &str) {
+        let line = serde_json::json!([self.elapsed(), "o", chunk]);
+        if let Ok(mut f) = self.file.lock() {
+            let _ = writeln!(f, "{}", line);
+        }
+    }
+
+    /// Appends an `"r"` (resize) event so asciicast players reflow at the
+    /// right point instead of assuming a fixed size for the whole replay.
+    pub fn record_resize(&self, cols: u16, rows: u16) {
+        let line = serde_json::json!([self.elapsed(), "r", format!("{}x{}", cols, rows)]);
+        if let Ok(mut f) = self.file.lock() {
+            let _ = writeln!(f, "{}", line);
+        }
+    }
+}
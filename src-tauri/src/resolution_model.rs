@@ -0,0 +1,380 @@
+//! A tiny logistic-regression classifier predicting whether an
+//! investigation will resolve as `ResolutionType::Solved`, trained over
+//! `LearningData.outcomes`. Tool recommendations and pattern success used
+//! to come from raw frequency counts alone; this gives
+//! `get_learned_tool_recommendations` a model-backed signal - the marginal
+//! lift a tool gives the predicted solve probability - instead.
+//!
+//! There's no linfa/GBDT dependency available in this workspace, so the
+//! model is a hand-rolled logistic regression trained by batch gradient
+//! descent, with the query embedding reduced to a few principal
+//! components via power-iteration PCA (also hand-rolled, for the same
+//! reason). It's small enough to retrain from scratch every time rather
+//! than incrementally.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::Manager;
+
+use crate::learning::{InvestigationOutcome, ResolutionType};
+
+/// How many principal components of the question embedding feed the model.
+const EMBEDDING_PCA_DIMS: usize = 5;
+/// Retrain once at least this many new outcomes have accumulated since the
+/// model currently on disk was trained.
+const RETRAIN_INTERVAL: usize = 20;
+/// Below this many labeled outcomes there isn't enough signal to fit a
+/// model worth trusting over the raw-frequency fallback.
+const MIN_TRAINING_OUTCOMES: usize = 10;
+const LEARNING_RATE: f32 = 0.1;
+const L2_LAMBDA: f32 = 0.01;
+const TRAIN_EPOCHS: usize = 300;
+
+/// A trained resolution-prediction model, persisted as JSON alongside
+/// `learning_data.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionModel {
+    /// Tool vocabulary, in the fixed order the multi-hot features use.
+    feature_tools: Vec<String>,
+    pca_mean: Vec<f32>,
+    pca_components: Vec<Vec<f32>>,
+    weights: Vec<f32>,
+    bias: f32,
+    /// Outcome count the model was last trained on, to decide when
+    /// `maybe_retrain` should refresh it.
+    trained_on: usize,
+}
+
+fn model_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_handle.path().app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(app_data_dir.join("resolution_model.json"))
+}
+
+fn load_model(app_handle: &tauri::AppHandle) -> Option<ResolutionModel> {
+    let path = model_path(app_handle).ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_model(app_handle: &tauri::AppHandle, model: &ResolutionModel) -> Result<(), String> {
+    let path = model_path(app_handle)?;
+    let content = serde_json::to_string_pretty(model)
+        .map_err(|e| format!("Failed to serialize resolution model: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write resolution model: {}", e))
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Fit the top-`k` principal components of `vectors` by power iteration
+/// with deflation: repeatedly find the dominant eigenvector of the
+/// (mean-centered) covariance matrix, then subtract its contribution
+/// before finding the next one. Good enough for a handful of components
+/// over a few hundred embeddings without pulling in a linear-algebra crate.
+fn fit_pca(vectors: &[Vec<f32>], k: usize) -> (Vec<f32>, Vec<Vec<f32>>) {
+    let dim = vectors.first().map(|v| v.len()).unwrap_or(0);
+    if dim == 0 || vectors.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut mean = vec![0.0f32; dim];
+    for v in vectors {
+        for (i, x) in v.iter().enumerate() {
+            mean[i] += x;
+        }
+    }
+    for m in &mut mean {
+        *m /= vectors.len() as f32;
+    }
+
+    let mut centered: Vec<Vec<f32>> = vectors
+        .iter()
+        .map(|v| v.iter().zip(&mean).map(|(x, m)| x - m).collect())
+        .collect();
+
+    let mut components = Vec::new();
+    for _ in 0..k.min(dim) {
+        let mut vec_est = vec![1.0f32 / (dim as f32).sqrt(); dim];
+
+        for _ in 0..50 {
+            let mut next = vec![0.0f32; dim];
+            for row in &centered {
+                let dot: f32 = row.iter().zip(&vec_est).map(|(a, b)| a * b).sum();
+                for (n, r) in next.iter_mut().zip(row) {
+                    *n += dot * r;
+                }
+            }
+            let norm = next.iter().map(|x| x * x).sum::<f32>().sqrt();
+            if norm < 1e-8 {
+                break;
+            }
+            vec_est = next.into_iter().map(|x| x / norm).collect();
+        }
+
+        // Deflate: remove this component's contribution so the next
+        // iteration finds an orthogonal direction.
+        for row in &mut centered {
+            let dot: f32 = row.iter().zip(&vec_est).map(|(a, b)| a * b).sum();
+            for (r, c) in row.iter_mut().zip(&vec_est) {
+                *r -= dot * c;
+            }
+        }
+
+        components.push(vec_est);
+    }
+
+    (mean, components)
+}
+
+fn project_embedding(embedding: &[f32], mean: &[f32], components: &[Vec<f32>]) -> Vec<f32> {
+    if embedding.is_empty() || mean.is_empty() {
+        return vec![0.0; components.len()];
+    }
+    components
+        .iter()
+        .map(|c| embedding.iter().zip(mean).zip(c).map(|((x, m), c)| (x - m) * c).sum())
+        .collect()
+}
+
+fn multi_hot(tools: &[String], vocab: &[String]) -> Vec<f32> {
+    vocab.iter().map(|t| if tools.iter().any(|u| u == t) { 1.0 } else { 0.0 }).collect()
+}
+
+/// Assemble the full feature vector: multi-hot tools, then the scalar
+/// signals, then the embedding's principal components.
+fn build_features(
+    tools_multi_hot: &[f32],
+    duration_ms: f32,
+    useful_count: f32,
+    non_useful_count: f32,
+    confirmed_count: f32,
+    refuted_count: f32,
+    embedding_pca: &[f32],
+) -> Vec<f32> {
+    let mut features = Vec::with_capacity(tools_multi_hot.len() + 5 + embedding_pca.len());
+    features.extend_from_slice(tools_multi_hot);
+    // Normalize duration to a roughly [0, ~few] range so gradient descent
+    // doesn't get dominated by a feature in the tens-of-thousands.
+    features.push(duration_ms / 60_000.0);
+    features.push(useful_count);
+    features.push(non_useful_count);
+    features.push(confirmed_count);
+    features.push(refuted_count);
+    features.extend_from_slice(embedding_pca);
+    features
+}
+
+fn outcome_features(
+    outcome: &InvestigationOutcome,
+    vocab: &[String],
+    pca_mean: &[f32],
+    pca_components: &[Vec<f32>],
+) -> Vec<f32> {
+    let tools: Vec<String> = outcome.tools_used.iter().map(|t| t.tool.clone()).collect();
+    let useful_count = outcome.tools_used.iter().filter(|t| t.useful).count() as f32;
+    let non_useful_count = outcome.tools_used.len() as f32 - useful_count;
+    let embedding_pca = project_embedding(&outcome.question_embedding, pca_mean, pca_components);
+
+    build_features(
+        &multi_hot(&tools, vocab),
+        outcome.duration_ms as f32,
+        useful_count,
+        non_useful_count,
+        outcome.hypotheses_confirmed.len() as f32,
+        outcome.hypotheses_refuted.len() as f32,
+        &embedding_pca,
+    )
+}
+
+/// Train a fresh model from scratch over `outcomes`.
+fn train(outcomes: &[InvestigationOutcome]) -> ResolutionModel {
+    let mut feature_tools: Vec<String> = outcomes
+        .iter()
+        .flat_map(|o| o.tools_used.iter().map(|t| t.tool.clone()))
+        .collect();
+    feature_tools.sort();
+    feature_tools.dedup();
+
+    let embeddings: Vec<Vec<f32>> = outcomes
+        .iter()
+        .map(|o| o.question_embedding.clone())
+        .filter(|e| !e.is_empty())
+        .collect();
+    let (pca_mean, pca_components) = fit_pca(&embeddings, EMBEDDING_PCA_DIMS);
+
+    let samples: Vec<(Vec<f32>, f32)> = outcomes
+        .iter()
+        .map(|o| {
+            let label = if o.resolution == ResolutionType::Solved { 1.0 } else { 0.0 };
+            (outcome_features(o, &feature_tools, &pca_mean, &pca_components), label)
+        })
+        .collect();
+
+    let feature_dim = samples.first().map(|(f, _)| f.len()).unwrap_or(0);
+    let mut weights = vec![0.0f32; feature_dim];
+    let mut bias = 0.0f32;
+
+    if !samples.is_empty() && feature_dim > 0 {
+        let n = samples.len() as f32;
+        for _ in 0..TRAIN_EPOCHS {
+            let mut weight_grad = vec![0.0f32; feature_dim];
+            let mut bias_grad = 0.0f32;
+
+            for (features, label) in &samples {
+                let pred = sigmoid(features.iter().zip(&weights).map(|(x, w)| x * w).sum::<f32>() + bias);
+                let error = pred - label;
+                for (g, x) in weight_grad.iter_mut().zip(features) {
+                    *g += error * x;
+                }
+                bias_grad += error;
+            }
+
+            for (w, g) in weights.iter_mut().zip(&weight_grad) {
+                *w -= LEARNING_RATE * (g / n + L2_LAMBDA * *w);
+            }
+            bias -= LEARNING_RATE * bias_grad / n;
+        }
+    }
+
+    ResolutionModel {
+        feature_tools,
+        pca_mean,
+        pca_components,
+        weights,
+        bias,
+        trained_on: outcomes.len(),
+    }
+}
+
+/// Retrain and persist the model if none exists yet, or if enough new
+/// outcomes have accumulated since the last training run to be worth the
+/// cost. Cheap to call after every recorded outcome - it no-ops most of
+/// the time.
+pub fn maybe_retrain(app_handle: &tauri::AppHandle, outcomes: &[InvestigationOutcome]) -> Result<(), String> {
+    if outcomes.len() < MIN_TRAINING_OUTCOMES {
+        return Ok(());
+    }
+
+    let needs_training = match load_model(app_handle) {
+        Some(model) => outcomes.len().saturating_sub(model.trained_on) >= RETRAIN_INTERVAL,
+        None => true,
+    };
+
+    if needs_training {
+        let model = train(outcomes);
+        save_model(app_handle, &model)?;
+    }
+
+    Ok(())
+}
+
+/// Predict the probability that an investigation using `tools` would
+/// resolve as solved, given the question's embedding. Duration and
+/// hypothesis counts aren't known ahead of time, so those features are
+/// left at zero - the model still has the tool multi-hot and the question
+/// embedding to work with.
+fn predict(model: &ResolutionModel, tools: &[String], embedding: &[f32]) -> f32 {
+    let embedding_pca = project_embedding(embedding, &model.pca_mean, &model.pca_components);
+    let features = build_features(
+        &multi_hot(tools, &model.feature_tools),
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        &embedding_pca,
+    );
+
+    if features.len() != model.weights.len() {
+        return 0.5;
+    }
+
+    sigmoid(features.iter().zip(&model.weights).map(|(x, w)| x * w).sum::<f32>() + model.bias)
+}
+
+/// Predict the solve probability for `tools_planned` against `question`.
+/// Returns a neutral 0.5 if no model has been trained yet.
+pub fn predict_resolution(app_handle: &tauri::AppHandle, tools_planned: &[String], embedding: &[f32]) -> f32 {
+    match load_model(app_handle) {
+        Some(model) => predict(&model, tools_planned, embedding),
+        None => 0.5,
+    }
+}
+
+/// The marginal lift `tool` gives the solve probability on its own,
+/// relative to no tools at all. `None` if no model has been trained yet,
+/// so callers can fall back to their own ranking.
+pub fn marginal_lift(app_handle: &tauri::AppHandle, tool: &str, embedding: &[f32]) -> Option<f32> {
+    let model = load_model(app_handle)?;
+    let baseline = predict(&model, &[], embedding);
+    let with_tool = predict(&model, &[tool.to_string()], embedding);
+    Some(with_tool - baseline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learning::ToolRecord;
+
+    fn make_outcome(id: &str, tool: &str, resolved: bool) -> InvestigationOutcome {
+        InvestigationOutcome {
+            id: id.to_string(),
+            timestamp: 0,
+            question: "why is the pod failing".to_string(),
+            question_embedding: Vec::new(),
+            tools_used: vec![ToolRecord {
+                tool: tool.to_string(),
+                args: None,
+                status: "success".to_string(),
+                useful: resolved,
+                duration_ms: 100,
+            }],
+            resolution: if resolved { ResolutionType::Solved } else { ResolutionType::Inconclusive },
+            root_cause: None,
+            confidence_score: if resolved { 0.9 } else { 0.2 },
+            duration_ms: 1000,
+            hypotheses_confirmed: Vec::new(),
+            hypotheses_refuted: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn train_and_predict_converge_to_the_expected_sign_on_a_separable_dataset() {
+        // "fix-tool" always accompanies a solved investigation, "other-tool"
+        // never does - logistic regression should learn a positive weight
+        // for the former and a negative one for the latter well within
+        // TRAIN_EPOCHS.
+        let mut outcomes = Vec::new();
+        for i in 0..15 {
+            outcomes.push(make_outcome(&format!("solved-{i}"), "fix-tool", true));
+            outcomes.push(make_outcome(&format!("unsolved-{i}"), "other-tool", false));
+        }
+
+        let model = train(&outcomes);
+
+        let solved_prob = predict(&model, &["fix-tool".to_string()], &[]);
+        let unsolved_prob = predict(&model, &["other-tool".to_string()], &[]);
+
+        assert!(solved_prob > 0.5, "expected fix-tool to predict a likely solve, got {}", solved_prob);
+        assert!(unsolved_prob < 0.5, "expected other-tool to predict a likely non-solve, got {}", unsolved_prob);
+        assert!(solved_prob > unsolved_prob);
+    }
+
+    #[test]
+    fn predict_returns_neutral_probability_on_vocab_mismatch() {
+        let outcomes = vec![make_outcome("a", "fix-tool", true)];
+        let mut model = train(&outcomes);
+        // Simulate a model trained on a different tool vocabulary than the
+        // one `predict` builds its feature vector against, so the lengths
+        // no longer line up - the guard should return a neutral 0.5 instead
+        // of panicking on a mismatched zip.
+        model.weights.push(1.0);
+
+        assert_eq!(predict(&model, &["fix-tool".to_string()], &[]), 0.5);
+    }
+}
@@ -0,0 +1,74 @@
+//! Optional Lua hook for customizing vcluster CLI argument construction.
+//! Gated behind the `scripting` Cargo feature so the default build stays
+//! CLI-only and carries no `mlua` dependency.
+//!
+//! Users with non-standard setups can drop a `vcluster.lua` in
+//! `~/.opspilot/` exposing `set_connect_args(function(ctx) ... return args end)`,
+//! where `ctx` carries `{name, namespace, status, default_args}`. The
+//! returned table becomes the final argv passed to the `vcluster` binary.
+
+use mlua::{Function, Lua, Table};
+
+/// Context passed into the `set_connect_args` Lua hook.
+pub struct ConnectContext {
+    pub name: String,
+    pub namespace: String,
+    pub status: String,
+}
+
+fn vcluster_lua_path() -> Option<std::path::PathBuf> {
+    dirs::home_dir().map(|home| home.join(".opspilot").join("vcluster.lua"))
+}
+
+/// Run `~/.opspilot/vcluster.lua`'s `set_connect_args` hook (if the script
+/// exists and defines it) over `default_args`. Falls back to `default_args`
+/// unchanged if there's no script, no hook, or the hook errors.
+pub fn apply_connect_args_hook(default_args: Vec<String>, ctx: &ConnectContext) -> Vec<String> {
+    let Some(path) = vcluster_lua_path() else {
+        return default_args;
+    };
+    if !path.exists() {
+        return default_args;
+    }
+
+    let script = match std::fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("[scripting] Failed to read {}: {}", path.display(), e);
+            return default_args;
+        }
+    };
+
+    match run_connect_args_hook(&script, &default_args, ctx) {
+        Ok(Some(args)) => args,
+        Ok(None) => default_args,
+        Err(e) => {
+            log::warn!("[scripting] vcluster.lua set_connect_args failed: {}", e);
+            default_args
+        }
+    }
+}
+
+fn run_connect_args_hook(
+    script: &str,
+    default_args: &[String],
+    ctx: &ConnectContext,
+) -> mlua::Result<Option<Vec<String>>> {
+    let lua = Lua::new();
+    lua.load(script).exec()?;
+
+    let hook: Option<Function> = lua.globals().get("set_connect_args").ok();
+    let Some(hook) = hook else {
+        return Ok(None);
+    };
+
+    let ctx_table = lua.create_table()?;
+    ctx_table.set("name", ctx.name.clone())?;
+    ctx_table.set("namespace", ctx.namespace.clone())?;
+    ctx_table.set("status", ctx.status.clone())?;
+    ctx_table.set("default_args", default_args.to_vec())?;
+
+    let result: Table = hook.call(ctx_table)?;
+    let args = result.sequence_values::<String>().collect::<mlua::Result<Vec<String>>>()?;
+    Ok(Some(args))
+}
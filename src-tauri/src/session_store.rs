@@ -0,0 +1,117 @@
+//! Durable record of long-running sessions - today just `PortForwardSession`s
+//! started via `commands::networking::start_port_forward` - so they survive
+//! an app restart. Follows the same embedded-sqlite pattern as
+//! `metrics_store`: one lazily-opened connection behind a `static Mutex`,
+//! keyed by session id rather than time.
+//!
+//! `commands::session_manager` is the Tauri-facing layer on top of this:
+//! `list_sessions` joins these descriptors against `AppState.port_forwards`'s
+//! live status, `restart_session` re-runs `start_port_forward` from a stored
+//! descriptor, and `set_session_autoreconnect` flips the flag consulted at
+//! startup restore time.
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDescriptor {
+    pub id: String,
+    pub namespace: String,
+    pub kind: String,
+    pub name: String,
+    pub local_port: u16,
+    pub pod_port: u16,
+    pub autoreconnect: bool,
+}
+
+fn db_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".opspilot").join("sessions.db"))
+}
+
+static DB: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut guard = DB.lock().map_err(|e| format!("Session store lock poisoned: {}", e))?;
+
+    if guard.is_none() {
+        let path = db_path().ok_or("Could not determine home directory for session store")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create session store directory: {}", e))?;
+        }
+        let conn = Connection::open(&path).map_err(|e| format!("Failed to open session store: {}", e))?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS port_forward_sessions (
+                id TEXT PRIMARY KEY,
+                namespace TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                name TEXT NOT NULL,
+                local_port INTEGER NOT NULL,
+                pod_port INTEGER NOT NULL,
+                autoreconnect INTEGER NOT NULL
+            )",
+            [],
+        ).map_err(|e| format!("Failed to initialize session store schema: {}", e))?;
+        *guard = Some(conn);
+    }
+
+    f(guard.as_ref().unwrap()).map_err(|e| format!("Session store query failed: {}", e))
+}
+
+pub fn save_session(descriptor: &SessionDescriptor) -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO port_forward_sessions
+                (id, namespace, kind, name, local_port, pod_port, autoreconnect)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                descriptor.id,
+                descriptor.namespace,
+                descriptor.kind,
+                descriptor.name,
+                descriptor.local_port,
+                descriptor.pod_port,
+                descriptor.autoreconnect as i64,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn remove_session(id: &str) -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM port_forward_sessions WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+}
+
+pub fn set_autoreconnect(id: &str, autoreconnect: bool) -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE port_forward_sessions SET autoreconnect = ?1 WHERE id = ?2",
+            params![autoreconnect as i64, id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn list_sessions() -> Result<Vec<SessionDescriptor>, String> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, namespace, kind, name, local_port, pod_port, autoreconnect FROM port_forward_sessions",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SessionDescriptor {
+                id: row.get(0)?,
+                namespace: row.get(1)?,
+                kind: row.get(2)?,
+                name: row.get(3)?,
+                local_port: row.get::<_, i64>(4)? as u16,
+                pod_port: row.get::<_, i64>(5)? as u16,
+                autoreconnect: row.get::<_, i64>(6)? != 0,
+            })
+        })?;
+        rows.collect()
+    })
+}
@@ -0,0 +1,172 @@
+//! Hot-reloads the agent sidecar when its knowledge base or packaged binary
+//! changes on disk, mirroring a dev-server workflow instead of requiring a
+//! full app relaunch to pick up KB edits.
+//!
+//! Off by default: continuously watching the filesystem is a dev/test
+//! convenience, not something every production install needs running. Set
+//! `OPSPILOT_SIDECAR_HOT_RELOAD=1` to enable it.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use log::{info, warn, error};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::agent_sidecar::{kb_dir_for, start_agent_sidecar, stop_agent_sidecar, DEFAULT_SIDECAR_ID};
+use crate::workers::{BackgroundWorker, WorkerState};
+
+/// Collapse a burst of filesystem events (an editor's save-then-rename, a
+/// KB re-index writing several files) into a single restart instead of one
+/// per event.
+const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Tauri event emitted around a hot-reload restart so the UI can show a
+/// "reloading knowledge base" indicator.
+const RELOAD_EVENT: &str = "sidecar://reloading";
+
+#[derive(Clone, Serialize)]
+struct ReloadPayload {
+    reloading: bool,
+}
+
+/// True when hot-reload should run at all.
+pub fn hot_reload_enabled() -> bool {
+    std::env::var("OPSPILOT_SIDECAR_HOT_RELOAD").map(|v| v == "1").unwrap_or(false)
+}
+
+/// Best-effort path to the packaged `agent-server` sidecar binary, following
+/// Tauri's `<name>-<target-triple>[.exe]` sidecar naming convention, resolved
+/// next to the running executable. Returns `None` (rather than erroring) when
+/// the triple can't be determined, since watching the binary is a nice-to-have
+/// on top of watching the KB directory.
+fn agent_binary_path() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let triple = tauri::utils::platform::target_triple().ok()?;
+    let filename = if cfg!(windows) {
+        format!("agent-server-{}.exe", triple)
+    } else {
+        format!("agent-server-{}", triple)
+    };
+    Some(exe_dir.join(filename))
+}
+
+/// Background worker that watches the resolved KB directory and packaged
+/// agent binary for changes and cycles the sidecar through
+/// `stop_agent_sidecar` + `start_agent_sidecar` when they do. Debounced so a
+/// burst of events triggers exactly one restart.
+pub struct SidecarHotReloadWorker {
+    app_handle: tauri::AppHandle,
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    last_error: Option<String>,
+}
+
+impl SidecarHotReloadWorker {
+    /// Builds the worker, or `None` when hot-reload is disabled or no
+    /// watcher could be started at all (e.g. the KB directory doesn't exist
+    /// yet and the agent binary path couldn't be resolved).
+    pub fn new(app_handle: tauri::AppHandle) -> Option<Self> {
+        if !hot_reload_enabled() {
+            return None;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        }) {
+            Ok(w) => w,
+            Err(e) => {
+                warn!("[sidecar-watch] Failed to create filesystem watcher: {}", e);
+                return None;
+            }
+        };
+
+        let kb_path = kb_dir_for(&app_handle);
+        let mut watching_anything = false;
+        if kb_path.exists() {
+            match watcher.watch(&kb_path, RecursiveMode::Recursive) {
+                Ok(()) => {
+                    watching_anything = true;
+                    info!("[sidecar-watch] Watching KB directory {:?} for hot-reload", kb_path);
+                }
+                Err(e) => warn!("[sidecar-watch] Failed to watch KB dir {:?}: {}", kb_path, e),
+            }
+        }
+
+        if let Some(bin_path) = agent_binary_path() {
+            if bin_path.exists() {
+                match watcher.watch(&bin_path, RecursiveMode::NonRecursive) {
+                    Ok(()) => {
+                        watching_anything = true;
+                        info!("[sidecar-watch] Watching agent binary {:?} for hot-reload", bin_path);
+                    }
+                    Err(e) => warn!("[sidecar-watch] Failed to watch agent binary {:?}: {}", bin_path, e),
+                }
+            }
+        }
+
+        if !watching_anything {
+            warn!("[sidecar-watch] Hot-reload enabled but nothing to watch; disabling");
+            return None;
+        }
+
+        Some(Self { app_handle, _watcher: watcher, events: rx, last_error: None })
+    }
+}
+
+impl BackgroundWorker for SidecarHotReloadWorker {
+    fn name(&self) -> &str {
+        "sidecar-hot-reload"
+    }
+
+    async fn work(&mut self) -> WorkerState {
+        // Drain whatever arrived since the last cycle; only *that* something
+        // changed matters, not the individual events.
+        let mut changed = false;
+        while let Ok(res) = self.events.try_recv() {
+            match res {
+                Ok(_event) => changed = true,
+                Err(e) => self.last_error = Some(e.to_string()),
+            }
+        }
+
+        if !changed {
+            return WorkerState::Idle(Duration::from_millis(500));
+        }
+
+        // Debounce: keep draining for the debounce window so a burst of
+        // saves collapses into a single restart.
+        let deadline = Instant::now() + DEBOUNCE;
+        while Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            while self.events.try_recv().is_ok() {}
+        }
+
+        info!("[sidecar-watch] Change detected, reloading agent sidecar");
+        let _ = self.app_handle.emit(RELOAD_EVENT, ReloadPayload { reloading: true });
+
+        if let Err(e) = stop_agent_sidecar(&self.app_handle, DEFAULT_SIDECAR_ID).await {
+            warn!("[sidecar-watch] stop_agent_sidecar failed during reload: {}", e);
+        }
+        match start_agent_sidecar(&self.app_handle, DEFAULT_SIDECAR_ID).await {
+            Ok(()) => {
+                self.last_error = None;
+                info!("[sidecar-watch] Agent sidecar reloaded");
+            }
+            Err(e) => {
+                error!("[sidecar-watch] Failed to restart agent sidecar: {}", e);
+                self.last_error = Some(e);
+            }
+        }
+
+        let _ = self.app_handle.emit(RELOAD_EVENT, ReloadPayload { reloading: false });
+        WorkerState::Idle(Duration::from_millis(500))
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.last_error.clone()
+    }
+}
@@ -4,7 +4,53 @@ use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as TokioMutex;
 use portable_pty::MasterPty;
 use kube::{Client, Discovery};
-use crate::models::{ClusterStats, InitialClusterData};
+use kube::api::DynamicObject;
+use crate::models::{ClusterStats, ClusterCockpitData, InitialClusterData, MetricsHistoryBuffer, NavSweepStatus, StatusRule};
+use crate::workers::WorkerManager;
+use std::time::Duration;
+
+/// User-configurable timeouts for connecting to a cluster, parsed from the
+/// humantime-style strings in `OpsPilotConfig` (see
+/// `commands::ai_utilities::parse_connection_timeouts`) by `load_opspilot_config`/
+/// `save_opspilot_config` and consulted by `commands::context::set_kube_config`.
+/// Defaults match the values that were hardcoded before this was configurable,
+/// so a user who never touches the setting sees no behavior change.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionTimeouts {
+    /// Overall budget for resolving the kubeconfig into a `kube::Config`.
+    pub client_timeout: Duration,
+    pub connect_timeout: Duration,
+    pub read_timeout: Duration,
+    /// Budget for the post-connect `list_api_groups` sanity check.
+    pub api_check_timeout: Duration,
+    /// Budget for `vcluster disconnect` when switching away from a vcluster context.
+    pub vcluster_disconnect_timeout: Duration,
+}
+
+impl Default for ConnectionTimeouts {
+    fn default() -> Self {
+        Self {
+            client_timeout: Duration::from_secs(25),
+            connect_timeout: Duration::from_secs(5),
+            read_timeout: Duration::from_secs(5),
+            api_check_timeout: Duration::from_secs(8),
+            vcluster_disconnect_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Reflector-style local mirror of one active `start_resource_watch` stream,
+/// keyed by object uid. Rebuilt from scratch on every `Init` event (initial
+/// connect or post-reconnect relist) so it always reflects one consistent
+/// point-in-time view of the watched GVK, rather than an event log the
+/// frontend has to replay itself.
+pub struct WatchStoreEntry {
+    pub objects: HashMap<String, DynamicObject>,
+    pub kind: String,
+    pub group: String,
+    pub version: String,
+    pub include_raw: bool,
+}
 
 #[allow(dead_code)]
 pub struct ExecSession {
@@ -14,15 +60,120 @@ pub struct ExecSession {
 pub struct ShellSession {
     pub writer: Arc<Mutex<Box<dyn std::io::Write + Send>>>,
     pub master: Arc<Mutex<Box<dyn MasterPty + Send>>>,
+    /// The spawned process, so `kill_session`/`stop_local_shell` can
+    /// actually terminate it and `wait_session` can read a real exit code,
+    /// instead of just dropping this session's map entry and leaking the
+    /// PTY slave and its child.
+    pub child: Arc<Mutex<Box<dyn portable_pty::Child + Send + Sync>>>,
+}
+
+/// Health of one `PortForwardSession`'s upstream connection, broadcast on
+/// the `pf_status` Tauri event and readable synchronously via
+/// `list_port_forwards`. See `commands::networking::start_port_forward`'s
+/// per-connection retry loop.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum PortForwardStatus {
+    /// At least one connection is currently bridged to the pod.
+    Connected,
+    /// The last bridge attempt failed; retrying with backoff.
+    Reconnecting { attempt: u32 },
+    /// Retries exhausted; the forward is dead until restarted.
+    Failed { reason: String },
+}
+
+/// Live throughput counters for a `PortForwardSession`, shared with its
+/// per-connection tasks so `list_port_forwards` can report traffic without
+/// waiting on them. `AtomicU64`/`AtomicUsize` rather than a `Mutex` since
+/// these are incremented on every `copy` poll and read independently of
+/// `status`.
+#[derive(Default)]
+pub struct PortForwardCounters {
+    pub bytes_up: std::sync::atomic::AtomicU64,
+    pub bytes_down: std::sync::atomic::AtomicU64,
+    pub active_connections: std::sync::atomic::AtomicUsize,
 }
 
 pub struct PortForwardSession {
     pub id: String,
+    /// Name of the target resource - a pod name for `kind == "Pod"`, or a
+    /// Service/Deployment/StatefulSet name otherwise; see
+    /// `commands::networking::ForwardTarget`. The specific pod actually
+    /// bridged to can change over the session's lifetime (self-healing).
     pub pod_name: String,
+    /// Kind of `pod_name`: `"Pod"`, `"Service"`, `"Deployment"` or `"StatefulSet"`.
+    pub kind: String,
     pub namespace: String,
     pub local_port: u16,
     pub pod_port: u16,
     pub handle: tokio::task::JoinHandle<()>,
+    // Latest connection health, shared with the listener task so
+    // `list_port_forwards` can read it without waiting on the task itself.
+    pub status: Arc<Mutex<PortForwardStatus>>,
+    // Cumulative bytes transferred and currently-open connections. See
+    // `PortForwardCounters`.
+    pub counters: Arc<PortForwardCounters>,
+}
+
+/// A running forward created via `commands::port_forward_manager`, proxying
+/// a dynamically allocated local port to a Ready pod backing a Service.
+/// Unlike `PortForwardSession` (one specific pod, one specific local port
+/// chosen by the caller), this is keyed by `(namespace, service, target_port)`
+/// and the local port is allocated for you - see `ForwardInfo::local_port`.
+pub struct ServiceForwardSession {
+    pub id: String,
+    pub namespace: String,
+    pub service: String,
+    pub target_port: u16,
+    pub local_port: u16,
+    pub handle: tokio::task::JoinHandle<()>,
+}
+
+/// A running `vcluster_tunnel` relay, re-exposing a connected vcluster's
+/// local proxy on every interface. See `commands::vcluster_tunnel`.
+pub struct VClusterTunnelSession {
+    pub id: String,
+    pub name: String,
+    pub namespace: String,
+    pub bind_addr: std::net::SocketAddr,
+    pub kubeconfig_path: std::path::PathBuf,
+    pub handle: tokio::task::JoinHandle<()>,
+}
+
+/// A running `commands::webui_proxy` instance, tracked here only for
+/// `list_webui_proxies` - the actual proxy server and its routing state live
+/// in `proxy::webui::PROXY_REGISTRY`, keyed by the same id.
+pub struct WebUiProxyEntry {
+    pub id: String,
+    pub profile_id: String,
+    pub local_port: u16,
+    pub auth_token: String,
+}
+
+/// How to authenticate an SSH connection registered via
+/// `commands::remote::connect_remote_host`. Only key-based auth is
+/// supported for now - an interactive password prompt needs a channel back
+/// to the frontend that doesn't exist yet, so `connect_remote_host` rejects
+/// `Password` up front rather than pretending to support it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RemoteAuth {
+    Key { path: String },
+    Password,
+}
+
+/// One bastion/jump host registered via `connect_remote_host`, reused by
+/// `start_remote_shell`/`start_remote_exec` to build the `ssh` command line
+/// without re-prompting for connection details each time.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RemoteHost {
+    pub alias: String,
+    pub host: String,
+    pub user: String,
+    pub auth: RemoteAuth,
+    /// Version of the OpsPilot helper binary last uploaded to this host, if
+    /// any - lets `connect_remote_host` skip re-uploading when it's current.
+    pub helper_version: Option<String>,
 }
 
 pub struct AppState {
@@ -32,10 +183,20 @@ pub struct AppState {
     pub sessions: Arc<Mutex<HashMap<String, Arc<ExecSession>>>>,
     pub shell_sessions: Arc<Mutex<HashMap<String, Arc<ShellSession>>>>,
     pub port_forwards: Arc<Mutex<HashMap<String, PortForwardSession>>>,
+    // Forwards started via `commands::port_forward_manager`, keyed by
+    // "namespace/service/target_port". See `ServiceForwardSession`.
+    pub service_forwards: Arc<Mutex<HashMap<String, ServiceForwardSession>>>,
+    // Proxies started via `commands::webui_proxy`, keyed by proxy id. See
+    // `WebUiProxyEntry`.
+    pub webui_proxies: Arc<Mutex<HashMap<String, WebUiProxyEntry>>>,
     pub log_streams: Arc<Mutex<HashMap<String, tokio::sync::oneshot::Sender<()>>>>,
     pub discovery_cache: Arc<Mutex<Option<(std::time::Instant, Arc<Discovery>)>>>,
     pub vcluster_cache: Arc<Mutex<Option<(std::time::Instant, String)>>>,
     pub cluster_stats_cache: Arc<Mutex<Option<(std::time::Instant, ClusterStats)>>>,
+    pub cockpit_cache: Arc<Mutex<Option<(std::time::Instant, ClusterCockpitData)>>>,
+    // Short in-memory ring buffer backing the cockpit's hot path; long-term
+    // history is persisted by `metrics_store`.
+    pub metrics_history: Arc<Mutex<Option<MetricsHistoryBuffer>>>,
     // Cache pod limits to avoid refetching pods for metrics (30s TTL)
     pub pod_limits_cache: Arc<Mutex<Option<(std::time::Instant, HashMap<String, (Option<u64>, Option<u64>)>)>>>,
     // Cache Kubernetes client to avoid re-creating connections (2 minute TTL)
@@ -45,9 +206,51 @@ pub struct AppState {
     pub initial_data_cache: Arc<Mutex<Option<(std::time::Instant, InitialClusterData)>>>,
     // Persistent session for Claude Code
     pub claude_session: Arc<Mutex<Option<ShellSession>>>,
-    // Store vcluster proxy process ID to kill it on disconnect
-    #[allow(dead_code)]
-    pub vcluster_pid: Arc<Mutex<Option<u32>>>,
+    // The currently running `vcluster connect` child, owned by the supervisor
+    // task spawned in `connect_vcluster` for as long as the process is alive.
+    pub vcluster_child: Arc<TokioMutex<Option<tokio::process::Child>>>,
+    // Signals the supervisor task to kill `vcluster_child` cleanly (used by
+    // `disconnect_vcluster` instead of leaving the child to `kill_stale_vcluster_processes`'s pkill fallback).
+    pub vcluster_cancel_connect: Arc<Mutex<Option<tokio::sync::mpsc::UnboundedSender<()>>>>,
+    // One `kube::Client` per connected vcluster, keyed by "name/namespace".
+    // Populated by `connect_vcluster` and read by the fan-out query commands
+    // so several vclusters can be queried at once without clobbering
+    // `selected_context`/`client_cache`, which only ever track one "active" context.
+    pub vcluster_clients: Arc<Mutex<HashMap<String, Client>>>,
+    // Active `vcluster_tunnel` relays, keyed by "name/namespace". See
+    // `commands::vcluster_tunnel` and `VClusterTunnelSession`.
+    pub vcluster_tunnels: Arc<Mutex<HashMap<String, VClusterTunnelSession>>>,
+    // Previous content hash per resource uid, used by `get_cluster_delta` to
+    // report only what changed since the last poll.
+    pub resource_snapshot: Arc<Mutex<HashMap<String, u64>>>,
+    // User-registered status extraction rules, keyed by "group/version/kind",
+    // consulted by `extract_status` before its built-in heuristics.
+    pub status_rules: Arc<Mutex<HashMap<String, StatusRule>>>,
+    // Reflector stores for active `start_resource_watch` streams, keyed by
+    // watch_id. See `WatchStoreEntry`.
+    pub watch_store: Arc<Mutex<HashMap<String, WatchStoreEntry>>>,
+    // Registry of supervised background tasks (discovery pre-warming, log
+    // rotation, ...). See `crate::workers`.
+    pub worker_manager: Arc<WorkerManager>,
+    // Tranquility setting and per-context progress for `NavSweepWorker`,
+    // persisted to `.kube/cache/opspilot/nav_sweep_status.json`.
+    pub nav_sweep_status: Arc<Mutex<NavSweepStatus>>,
+    // Connection/read timeouts for `commands::context::set_kube_config`,
+    // refreshed from `OpsPilotConfig` by `load_opspilot_config`/
+    // `save_opspilot_config`. See `ConnectionTimeouts`.
+    pub connection_timeouts: Arc<Mutex<ConnectionTimeouts>>,
+    // Registered bastion hosts, keyed by alias. See `RemoteHost` and
+    // `commands::remote`.
+    pub remote_hosts: Arc<Mutex<HashMap<String, RemoteHost>>>,
+    // Selected cloud for `commands::cost::get_cluster_cost_report`'s pricing
+    // constants - "azure" (default), "aws", or "gcp". See
+    // `crate::pricing::provider_for`.
+    pub pricing_provider: Arc<Mutex<String>>,
+    // Active asciicast recordings, keyed by the same session_id used in
+    // `shell_sessions`. Populated by `commands::terminal::start_recording`
+    // and consulted by the PTY reader loop and the `resize_*` commands. See
+    // `crate::recording::Recording`.
+    pub recordings: Arc<Mutex<HashMap<String, Arc<crate::recording::Recording>>>>,
 }
 
 impl AppState {
@@ -58,15 +261,31 @@ impl AppState {
             sessions: Arc::new(Mutex::new(HashMap::new())),
             shell_sessions: Arc::new(Mutex::new(HashMap::new())),
             port_forwards: Arc::new(Mutex::new(HashMap::new())),
+            service_forwards: Arc::new(Mutex::new(HashMap::new())),
+            webui_proxies: Arc::new(Mutex::new(HashMap::new())),
             log_streams: Arc::new(Mutex::new(HashMap::new())),
             discovery_cache: Arc::new(Mutex::new(None)),
             vcluster_cache: Arc::new(Mutex::new(None)),
             cluster_stats_cache: Arc::new(Mutex::new(None)),
+            cockpit_cache: Arc::new(Mutex::new(None)),
+            metrics_history: Arc::new(Mutex::new(None)),
             pod_limits_cache: Arc::new(Mutex::new(None)),
             client_cache: Arc::new(Mutex::new(None)),
             initial_data_cache: Arc::new(Mutex::new(None)),
             claude_session: Arc::new(Mutex::new(None)),
-            vcluster_pid: Arc::new(Mutex::new(None)),
+            vcluster_child: Arc::new(TokioMutex::new(None)),
+            vcluster_cancel_connect: Arc::new(Mutex::new(None)),
+            vcluster_clients: Arc::new(Mutex::new(HashMap::new())),
+            vcluster_tunnels: Arc::new(Mutex::new(HashMap::new())),
+            resource_snapshot: Arc::new(Mutex::new(HashMap::new())),
+            status_rules: Arc::new(Mutex::new(HashMap::new())),
+            watch_store: Arc::new(Mutex::new(HashMap::new())),
+            worker_manager: Arc::new(WorkerManager::new()),
+            nav_sweep_status: Arc::new(Mutex::new(crate::workers::load_sweep_status())),
+            connection_timeouts: Arc::new(Mutex::new(ConnectionTimeouts::default())),
+            remote_hosts: Arc::new(Mutex::new(HashMap::new())),
+            pricing_provider: Arc::new(Mutex::new("azure".to_string())),
+            recordings: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
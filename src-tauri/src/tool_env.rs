@@ -0,0 +1,154 @@
+//! Cross-platform environment/PATH resolution shared by every spawned tool
+//! command. Generalizes the macOS-only `PATH`-patching hack in `main()` and
+//! the Windows-only common-install-path hunt in `commands::dependencies`
+//! into one augmented search environment, so a tool installed outside the
+//! GUI-launched app's inherited `PATH` (Homebrew, asdf/mise shims, Scoop,
+//! WinGet Links, registry `App Paths` directories) is still found and run.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+fn registry_app_paths_dirs() -> Vec<PathBuf> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let mut dirs = Vec::new();
+
+    for (hive, view) in [
+        (HKEY_LOCAL_MACHINE, KEY_WOW64_64KEY),
+        (HKEY_LOCAL_MACHINE, KEY_WOW64_32KEY),
+        (HKEY_CURRENT_USER, KEY_WOW64_64KEY),
+        (HKEY_CURRENT_USER, KEY_WOW64_32KEY),
+    ] {
+        let root = RegKey::predef(hive);
+        let Ok(app_paths) = root.open_subkey_with_flags(
+            r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths",
+            KEY_READ | view,
+        ) else { continue };
+
+        for subkey_name in app_paths.enum_keys().flatten() {
+            let Ok(entry) = app_paths.open_subkey(&subkey_name) else { continue };
+            if let Ok(extra_path) = entry.get_value::<String, _>("Path") {
+                dirs.extend(std::env::split_paths(&extra_path));
+            }
+        }
+    }
+
+    dirs
+}
+
+#[cfg(not(target_os = "windows"))]
+fn registry_app_paths_dirs() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+fn candidate_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let home = dirs::home_dir();
+
+    #[cfg(target_os = "macos")]
+    {
+        dirs.push(PathBuf::from("/opt/homebrew/bin"));
+        dirs.push(PathBuf::from("/opt/homebrew/sbin"));
+        dirs.push(PathBuf::from("/usr/local/bin"));
+    }
+
+    if let Some(home) = &home {
+        dirs.push(home.join(".local").join("bin"));
+        dirs.push(home.join(".asdf").join("shims"));
+        dirs.push(home.join(".local").join("share").join("mise").join("shims"));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(home) = &home {
+            dirs.push(home.join("scoop").join("shims"));
+        }
+        if let Ok(localappdata) = std::env::var("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(localappdata).join("Microsoft").join("WinGet").join("Links"));
+        }
+        dirs.extend(registry_app_paths_dirs());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        dirs.push(PathBuf::from("/usr/local/bin"));
+        dirs.push(PathBuf::from("/usr/bin"));
+        dirs.push(PathBuf::from("/bin"));
+    }
+
+    dirs
+}
+
+/// Ordered, de-duplicated list of extra directories to search for tools,
+/// beyond whatever the process inherited in `PATH`. Only directories that
+/// actually exist are returned.
+pub fn extra_search_dirs() -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    candidate_dirs()
+        .into_iter()
+        .filter(|d| d.exists())
+        .filter(|d| seen.insert(d.clone()))
+        .collect()
+}
+
+/// The effective `PATH` value: `extra_search_dirs` prepended to the
+/// inherited `PATH`.
+pub fn augmented_path() -> String {
+    let existing = std::env::var("PATH").unwrap_or_default();
+    let extra = extra_search_dirs();
+
+    if extra.is_empty() {
+        return existing;
+    }
+
+    let mut parts: Vec<String> = extra.iter().map(|d| d.to_string_lossy().into_owned()).collect();
+    if !existing.is_empty() {
+        parts.push(existing);
+    }
+
+    std::env::join_paths(parts.iter().map(PathBuf::from))
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or(existing)
+}
+
+/// Apply the augmented `PATH` to the current process's environment. Call
+/// once at startup; replaces the previous macOS-only shim in `main()`.
+pub fn apply_to_process_env() {
+    std::env::set_var("PATH", augmented_path());
+}
+
+/// Find a bare executable name in `extra_search_dirs`, trying common Windows
+/// executable extensions too. Used as a fallback when a tool isn't resolved
+/// via `which`/`where` or (on Windows) the registry.
+pub fn find_in_search_dirs(name: &str) -> Option<PathBuf> {
+    let candidates: Vec<String> = if cfg!(target_os = "windows") {
+        vec![format!("{}.exe", name), format!("{}.cmd", name), name.to_string()]
+    } else {
+        vec![name.to_string()]
+    };
+
+    for dir in extra_search_dirs() {
+        for candidate in &candidates {
+            let path = dir.join(candidate);
+            if path.exists() {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Apply the augmented `PATH` to a `Command` about to be spawned, so tools
+/// found out-of-PATH via `extra_search_dirs` actually run even when the
+/// process-wide environment wasn't (or couldn't be) patched at startup.
+pub fn inherit_env(cmd: &mut Command) {
+    cmd.env("PATH", augmented_path());
+}
+
+/// Same as `inherit_env`, for a `tokio::process::Command`.
+pub fn inherit_env_tokio(cmd: &mut tokio::process::Command) {
+    cmd.env("PATH", augmented_path());
+}
@@ -25,26 +25,44 @@ pub fn init_logger() -> Result<PathBuf, String> {
 
     let log_file = log_dir.join("opspilot.log");
 
-    // Rotation: If file exists and is large (>5MB), rename it
+    rotate_if_needed(&log_file, &log_dir);
+
+    *get_log_path().lock().unwrap() = Some(log_file.clone());
+
+    // Write init message
+    log_to_file("system", "INFO", "Logger initialized");
+
+    Ok(log_file)
+}
+
+/// Rename the active log file aside and prune old rotations if it's grown
+/// past the 5MB threshold. Called once from `init_logger` at startup and
+/// then periodically by `LogRotationWorker` so a long-running session
+/// doesn't leave a single ever-growing file in between.
+fn rotate_if_needed(log_file: &PathBuf, log_dir: &PathBuf) {
     if log_file.exists() {
-        if let Ok(metadata) = fs::metadata(&log_file) {
+        if let Ok(metadata) = fs::metadata(log_file) {
             if metadata.len() > 5 * 1024 * 1024 {
                 let timestamp = Local::now().format("%Y%m%d_%H%M%S");
                 let rotated = log_dir.join(format!("opspilot_{}.log", timestamp));
-                let _ = fs::rename(&log_file, &rotated);
+                let _ = fs::rename(log_file, &rotated);
 
                 // Cleanup old logs (keep last 5)
-                cleanup_old_logs(&log_dir);
+                cleanup_old_logs(log_dir);
             }
         }
     }
+}
 
-    *get_log_path().lock().unwrap() = Some(log_file.clone());
-
-    // Write init message
-    log_to_file("system", "INFO", "Logger initialized");
-
-    Ok(log_file)
+/// Re-checks the active log file for rotation outside of `init_logger`,
+/// used by the periodic `LogRotationWorker`.
+pub fn check_rotation() {
+    let path = get_log_path().lock().unwrap().clone();
+    if let Some(log_file) = path {
+        if let Some(log_dir) = log_file.parent() {
+            rotate_if_needed(&log_file, &log_dir.to_path_buf());
+        }
+    }
 }
 
 fn cleanup_old_logs(log_dir: &PathBuf) {
@@ -0,0 +1,366 @@
+//! Background worker subsystem: long-running tasks supervised by a
+//! `WorkerManager` instead of one-off `tokio::spawn`s scattered through
+//! `lib.rs`. Each worker decides its own cadence by returning
+//! `WorkerState::Idle(duration)` after a cycle, so a slow worker doesn't
+//! starve a fast one, and each is individually pausable/cancellable through
+//! `control_worker` rather than only all-or-nothing at process exit.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::client::{build_client, create_client};
+use crate::commands::context::list_contexts;
+use crate::commands::discovery::{build_nav_structure, get_discovery_cache_path, get_opspilot_cache_dir, save_cached_nav_structure};
+use crate::models::{ContextSweepProgress, NavSweepStatus};
+use crate::state::AppState;
+
+/// What a `BackgroundWorker` wants to happen after one call to `work()`.
+pub enum WorkerState {
+    /// Immediately call `work()` again (e.g. more pages left to process).
+    Busy,
+    /// Sleep for the given duration before the next cycle.
+    Idle(Duration),
+    /// Retire for good; the manager won't call `work()` again.
+    Done,
+}
+
+/// One unit of supervised background work. Implementors own whatever state
+/// they need between cycles (clients, cursors, last-seen timestamps); the
+/// manager only ever calls `work()` and reads `last_error()`.
+pub trait BackgroundWorker: Send + 'static {
+    fn name(&self) -> &str;
+    fn work(&mut self) -> impl std::future::Future<Output = WorkerState> + Send;
+    /// Most recent error from a `work()` cycle, surfaced by `list_workers`
+    /// without requiring `work()` itself to return a `Result`.
+    fn last_error(&self) -> Option<String> {
+        None
+    }
+}
+
+enum WorkerControl {
+    Pause,
+    Resume,
+    Cancel,
+}
+
+/// Snapshot of one worker's status, returned by `list_workers`.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerStatus {
+    pub name: String,
+    pub state: String, // "active" | "idle" | "paused" | "dead"
+    pub last_error: Option<String>,
+    pub last_run_unix: Option<i64>,
+}
+
+struct WorkerHandle {
+    status: Arc<Mutex<WorkerStatus>>,
+    control_tx: tokio::sync::mpsc::UnboundedSender<WorkerControl>,
+}
+
+/// Registry of spawned background workers, stored in `AppState`.
+pub struct WorkerManager {
+    workers: Mutex<HashMap<String, WorkerHandle>>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self { workers: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn list_statuses(&self) -> Vec<WorkerStatus> {
+        let mut statuses: Vec<WorkerStatus> = self.workers.lock().unwrap()
+            .values()
+            .map(|h| h.status.lock().unwrap().clone())
+            .collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    pub fn control(&self, name: &str, action: &str) -> Result<(), String> {
+        let ctrl = match action {
+            "pause" => WorkerControl::Pause,
+            "resume" => WorkerControl::Resume,
+            "cancel" => WorkerControl::Cancel,
+            other => return Err(format!("Unknown worker action: {}", other)),
+        };
+        let workers = self.workers.lock().unwrap();
+        let handle = workers.get(name).ok_or_else(|| format!("No worker named '{}'", name))?;
+        handle.control_tx.send(ctrl).map_err(|_| format!("Worker '{}' is no longer running", name))
+    }
+
+    /// Spawn `worker` on its own tokio task and register it under
+    /// `worker.name()` so `list_workers`/`control_worker` can see it.
+    pub fn spawn<W: BackgroundWorker>(&self, mut worker: W) {
+        let name = worker.name().to_string();
+        let status = Arc::new(Mutex::new(WorkerStatus {
+            name: name.clone(),
+            state: "idle".to_string(),
+            last_error: None,
+            last_run_unix: None,
+        }));
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::unbounded_channel::<WorkerControl>();
+        self.workers.lock().unwrap().insert(name.clone(), WorkerHandle { status: status.clone(), control_tx });
+
+        tokio::spawn(async move {
+            let mut paused = false;
+            loop {
+                // Drain any control messages queued up since the last cycle
+                // without blocking the happy path.
+                while let Ok(ctrl) = control_rx.try_recv() {
+                    match ctrl {
+                        WorkerControl::Pause => paused = true,
+                        WorkerControl::Resume => paused = false,
+                        WorkerControl::Cancel => {
+                            status.lock().unwrap().state = "dead".to_string();
+                            return;
+                        }
+                    }
+                }
+
+                if paused {
+                    status.lock().unwrap().state = "paused".to_string();
+                    match control_rx.recv().await {
+                        Some(WorkerControl::Resume) => paused = false,
+                        Some(WorkerControl::Pause) => {}
+                        Some(WorkerControl::Cancel) | None => {
+                            status.lock().unwrap().state = "dead".to_string();
+                            return;
+                        }
+                    }
+                    continue;
+                }
+
+                status.lock().unwrap().state = "active".to_string();
+                let next = worker.work().await;
+                {
+                    let mut s = status.lock().unwrap();
+                    s.last_run_unix = Some(chrono::Utc::now().timestamp());
+                    s.last_error = worker.last_error();
+                }
+
+                match next {
+                    WorkerState::Busy => continue,
+                    WorkerState::Idle(delay) => {
+                        status.lock().unwrap().state = "idle".to_string();
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            ctrl = control_rx.recv() => {
+                                match ctrl {
+                                    Some(WorkerControl::Cancel) | None => {
+                                        status.lock().unwrap().state = "dead".to_string();
+                                        return;
+                                    }
+                                    Some(WorkerControl::Pause) => paused = true,
+                                    Some(WorkerControl::Resume) => {}
+                                }
+                            }
+                        }
+                    }
+                    WorkerState::Done => {
+                        status.lock().unwrap().state = "dead".to_string();
+                        return;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Keeps the discovery cache pre-warmed: re-runs `Discovery::run` shortly
+/// before the 60s TTL that `get_cached_discovery` checks against, so a
+/// command never has to block on a cold `discover_api_resources` call.
+pub struct DiscoveryRefreshWorker {
+    app_handle: tauri::AppHandle,
+    last_error: Option<String>,
+}
+
+impl DiscoveryRefreshWorker {
+    pub fn new(app_handle: tauri::AppHandle) -> Self {
+        Self { app_handle, last_error: None }
+    }
+}
+
+impl BackgroundWorker for DiscoveryRefreshWorker {
+    fn name(&self) -> &str {
+        "discovery-refresh"
+    }
+
+    async fn work(&mut self) -> WorkerState {
+        let state = self.app_handle.state::<AppState>();
+
+        let client = match create_client(state.clone()).await {
+            Ok(c) => c,
+            Err(e) => {
+                self.last_error = Some(e);
+                return WorkerState::Idle(Duration::from_secs(30));
+            }
+        };
+
+        match kube::Discovery::new(client).run().await {
+            Ok(discovery) => {
+                self.last_error = None;
+                let mut cache = state.discovery_cache.lock().unwrap();
+                *cache = Some((std::time::Instant::now(), Arc::new(discovery)));
+            }
+            Err(e) => {
+                self.last_error = Some(e.to_string());
+            }
+        }
+
+        // Re-run 5s before the 60s TTL `get_cached_discovery` checks, so the
+        // cache never goes cold out from under a command.
+        WorkerState::Idle(Duration::from_secs(55))
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.last_error.clone()
+    }
+}
+
+/// Periodically checks the active log file against the 5MB rotation
+/// threshold, instead of only rotating once at `init_logger` time.
+pub struct LogRotationWorker;
+
+impl BackgroundWorker for LogRotationWorker {
+    fn name(&self) -> &str {
+        "log-rotation"
+    }
+
+    async fn work(&mut self) -> WorkerState {
+        crate::utils::logging::check_rotation();
+        WorkerState::Idle(Duration::from_secs(300))
+    }
+}
+
+const DEFAULT_TRANQUILITY: f64 = 2.0;
+const SWEEP_STALE_SOON_SECS: u64 = 3000; // refresh 10min before the 1h TTL `load_cached_nav_structure` enforces
+
+fn sweep_status_path() -> Option<std::path::PathBuf> {
+    let mut p = get_opspilot_cache_dir()?;
+    p.push("nav_sweep_status.json");
+    Some(p)
+}
+
+/// Load the persisted sweep status, or a fresh default if none exists yet.
+pub fn load_sweep_status() -> NavSweepStatus {
+    if let Some(path) = sweep_status_path() {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            if let Ok(status) = serde_json::from_str::<NavSweepStatus>(&content) {
+                return status;
+            }
+        }
+    }
+    NavSweepStatus { tranquility: DEFAULT_TRANQUILITY, contexts: Vec::new() }
+}
+
+pub(crate) fn save_sweep_status(status: &NavSweepStatus) {
+    if let Some(path) = sweep_status_path() {
+        if let Ok(content) = serde_json::to_string_pretty(status) {
+            let _ = std::fs::write(path, content);
+        }
+    }
+}
+
+/// True if `context`'s on-disk discovery cache is missing or will cross the
+/// 1h staleness window within `SWEEP_STALE_SOON_SECS`.
+fn cache_stale_soon(context: &str) -> bool {
+    let Some(path) = get_discovery_cache_path(context) else { return true };
+    let Ok(metadata) = std::fs::metadata(&path) else { return true };
+    let Ok(modified) = metadata.modified() else { return true };
+    match modified.elapsed() {
+        Ok(age) => age.as_secs() > SWEEP_STALE_SOON_SECS,
+        Err(_) => true,
+    }
+}
+
+/// Keeps every context's on-disk nav structure (`discovery_<ctx>.json`)
+/// pre-warmed so switching contexts never triggers a cold `discover_api_resources`
+/// run. Rate-limited by a "tranquility" multiplier: after refreshing one
+/// context, it sleeps `tranquility * that_context's_discovery_duration`
+/// before moving to the next, so a busy machine backs off and an idle one
+/// sweeps through the whole kubeconfig quickly.
+pub struct NavSweepWorker {
+    status: Arc<Mutex<NavSweepStatus>>,
+    contexts: Vec<String>,
+    cursor: usize,
+    last_error: Option<String>,
+}
+
+impl NavSweepWorker {
+    pub fn new(status: Arc<Mutex<NavSweepStatus>>) -> Self {
+        Self { status, contexts: Vec::new(), cursor: 0, last_error: None }
+    }
+
+    fn tranquility(&self) -> f64 {
+        self.status.lock().unwrap().tranquility
+    }
+
+    fn record(&self, progress: ContextSweepProgress) {
+        let mut status = self.status.lock().unwrap();
+        if let Some(existing) = status.contexts.iter_mut().find(|c| c.context == progress.context) {
+            *existing = progress;
+        } else {
+            status.contexts.push(progress);
+        }
+        save_sweep_status(&status);
+    }
+}
+
+impl BackgroundWorker for NavSweepWorker {
+    fn name(&self) -> &str {
+        "nav-sweep"
+    }
+
+    async fn work(&mut self) -> WorkerState {
+        if self.cursor >= self.contexts.len() {
+            self.contexts = match list_contexts(None).await {
+                Ok(ctxs) => ctxs.into_iter().map(|c| c.name).collect(),
+                Err(e) => {
+                    self.last_error = Some(e);
+                    return WorkerState::Idle(Duration::from_secs(60));
+                }
+            };
+            self.cursor = 0;
+            if self.contexts.is_empty() {
+                return WorkerState::Idle(Duration::from_secs(60));
+            }
+        }
+
+        let context = self.contexts[self.cursor].clone();
+        self.cursor += 1;
+
+        if !cache_stale_soon(&context) {
+            // Already warm; move on without hitting the API server.
+            return WorkerState::Idle(Duration::from_millis(200));
+        }
+
+        let started = std::time::Instant::now();
+        let outcome: Result<(), String> = async {
+            let client = build_client(None, Some(&context)).await?;
+            let nav = build_nav_structure(client, &context).await?;
+            save_cached_nav_structure(&context, &nav);
+            Ok(())
+        }.await;
+        let duration = started.elapsed();
+
+        self.last_error = outcome.as_ref().err().cloned();
+        self.record(ContextSweepProgress {
+            context,
+            last_swept_unix: chrono::Utc::now().timestamp(),
+            last_duration_ms: duration.as_millis() as u64,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+
+        let tranquility = self.tranquility();
+        let sleep_secs = (tranquility * duration.as_secs_f64()).clamp(1.0, 300.0);
+        WorkerState::Idle(Duration::from_secs_f64(sleep_secs))
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.last_error.clone()
+    }
+}